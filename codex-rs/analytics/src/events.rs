@@ -674,6 +674,13 @@ fn analytics_hook_event_name(event_name: HookEventName) -> &'static str {
         HookEventName::SessionStart => "SessionStart",
         HookEventName::UserPromptSubmit => "UserPromptSubmit",
         HookEventName::Stop => "Stop",
+        HookEventName::PreCompact => "PreCompact",
+        HookEventName::PostCompact => "PostCompact",
+        HookEventName::SubagentStart => "SubagentStart",
+        HookEventName::SubagentStop => "SubagentStop",
+        HookEventName::PreApplyPatch => "PreApplyPatch",
+        HookEventName::PostApplyPatch => "PostApplyPatch",
+        HookEventName::TurnError => "TurnError",
     }
 }
 
@@ -0,0 +1,21 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FuzzyFileSearchContentMatchRange } from "./FuzzyFileSearchContentMatchRange";
+
+/**
+ * Superset of [`codex_file_search::ContentMatch`]
+ */
+export type FuzzyFileSearchContentMatch = { root: string, path: string, fileName: string,
+/**
+ * 1-based line number.
+ */
+lineNumber: number,
+/**
+ * 1-based byte column of the first match on the line.
+ */
+column: number, line: string,
+/**
+ * Byte ranges of every match within `line`.
+ */
+ranges: Array<FuzzyFileSearchContentMatchRange>, contextBefore: Array<string>, contextAfter: Array<string>, };
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FuzzyFileSearchContentMatch } from "./FuzzyFileSearchContentMatch";
+
+export type FuzzyFileSearchContentSessionUpdatedNotification = { sessionId: string, query: string, matches: Array<FuzzyFileSearchContentMatch>,
+/**
+ * Whether the search stopped early after finding the maximum number of matches.
+ */
+truncated: boolean,
+/**
+ * Why the query could not be searched, such as an invalid regular expression.
+ */
+error: string | null, };
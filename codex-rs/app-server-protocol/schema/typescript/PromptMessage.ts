@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from "./serde_json/JsonValue";
+
+/**
+ * A single message in the result of rendering a prompt.
+ */
+export type PromptMessage = {
+/**
+ * Either `user` or `assistant`.
+ */
+role: string, content: JsonValue, };
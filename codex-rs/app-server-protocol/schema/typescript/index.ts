@@ -15,10 +15,14 @@ export type { ConversationSummary } from "./ConversationSummary";
 export type { ExecCommandApprovalParams } from "./ExecCommandApprovalParams";
 export type { ExecCommandApprovalResponse } from "./ExecCommandApprovalResponse";
 export type { ExecPolicyAmendment } from "./ExecPolicyAmendment";
+export type { ExecPolicyAmendmentScope } from "./ExecPolicyAmendmentScope";
 export type { FileChange } from "./FileChange";
 export type { ForcedLoginMethod } from "./ForcedLoginMethod";
 export type { FunctionCallOutputBody } from "./FunctionCallOutputBody";
 export type { FunctionCallOutputContentItem } from "./FunctionCallOutputContentItem";
+export type { FuzzyFileSearchContentMatch } from "./FuzzyFileSearchContentMatch";
+export type { FuzzyFileSearchContentMatchRange } from "./FuzzyFileSearchContentMatchRange";
+export type { FuzzyFileSearchContentSessionUpdatedNotification } from "./FuzzyFileSearchContentSessionUpdatedNotification";
 export type { FuzzyFileSearchMatchType } from "./FuzzyFileSearchMatchType";
 export type { FuzzyFileSearchParams } from "./FuzzyFileSearchParams";
 export type { FuzzyFileSearchResponse } from "./FuzzyFileSearchResponse";
@@ -47,6 +51,9 @@ export type { NetworkPolicyRuleAction } from "./NetworkPolicyRuleAction";
 export type { ParsedCommand } from "./ParsedCommand";
 export type { Personality } from "./Personality";
 export type { PlanType } from "./PlanType";
+export type { Prompt } from "./Prompt";
+export type { PromptArgument } from "./PromptArgument";
+export type { PromptMessage } from "./PromptMessage";
 export type { RealtimeConversationVersion } from "./RealtimeConversationVersion";
 export type { RealtimeOutputModality } from "./RealtimeOutputModality";
 export type { RealtimeVoice } from "./RealtimeVoice";
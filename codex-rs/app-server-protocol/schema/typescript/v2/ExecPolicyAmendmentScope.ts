@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where and for how long an approved [`ExecPolicyAmendment`] applies once it is saved.
+ */
+export type ExecPolicyAmendmentScope = "permanent" | "thread" | "project" | "day";
@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type McpServerPromptGetParams = { threadId: string, server: string, name: string,
+/**
+ * Values for the prompt's declared arguments, keyed by argument name.
+ */
+arguments?: { [key in string]?: string } | null, };
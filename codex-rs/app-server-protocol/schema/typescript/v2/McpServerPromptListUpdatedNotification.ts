@@ -0,0 +1,11 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Notification emitted when an MCP server reports that its prompt list changed.
+ *
+ * Treat this as an invalidation signal and re-run `mcpServer/prompt/list`
+ * for the thread when refreshed prompts are needed.
+ */
+export type McpServerPromptListUpdatedNotification = { threadId: string, server: string, };
@@ -0,0 +1,11 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Notification emitted when a resource the thread subscribed to changed.
+ *
+ * The difference from the previous contents has already been queued as
+ * context for the thread's next turn.
+ */
+export type McpServerResourceUpdatedNotification = { threadId: string, server: string, uri: string, };
@@ -93,6 +93,7 @@ export type { DynamicToolCallStatus } from "./DynamicToolCallStatus";
 export type { DynamicToolSpec } from "./DynamicToolSpec";
 export type { ErrorNotification } from "./ErrorNotification";
 export type { ExecPolicyAmendment } from "./ExecPolicyAmendment";
+export type { ExecPolicyAmendmentScope } from "./ExecPolicyAmendmentScope";
 export type { ExperimentalFeature } from "./ExperimentalFeature";
 export type { ExperimentalFeatureEnablementSetParams } from "./ExperimentalFeatureEnablementSetParams";
 export type { ExperimentalFeatureEnablementSetResponse } from "./ExperimentalFeatureEnablementSetResponse";
@@ -212,7 +213,18 @@ export type { McpServerElicitationRequestResponse } from "./McpServerElicitation
 export type { McpServerOauthLoginCompletedNotification } from "./McpServerOauthLoginCompletedNotification";
 export type { McpServerOauthLoginParams } from "./McpServerOauthLoginParams";
 export type { McpServerOauthLoginResponse } from "./McpServerOauthLoginResponse";
+export type { McpServerPromptGetParams } from "./McpServerPromptGetParams";
+export type { McpServerPromptGetResponse } from "./McpServerPromptGetResponse";
+export type { McpServerPromptListParams } from "./McpServerPromptListParams";
+export type { McpServerPromptListResponse } from "./McpServerPromptListResponse";
+export type { McpServerPromptListUpdatedNotification } from "./McpServerPromptListUpdatedNotification";
+export type { McpServerPrompts } from "./McpServerPrompts";
 export type { McpServerRefreshResponse } from "./McpServerRefreshResponse";
+export type { McpServerResourceSubscribeParams } from "./McpServerResourceSubscribeParams";
+export type { McpServerResourceSubscribeResponse } from "./McpServerResourceSubscribeResponse";
+export type { McpServerResourceUnsubscribeParams } from "./McpServerResourceUnsubscribeParams";
+export type { McpServerResourceUnsubscribeResponse } from "./McpServerResourceUnsubscribeResponse";
+export type { McpServerResourceUpdatedNotification } from "./McpServerResourceUpdatedNotification";
 export type { McpServerStartupState } from "./McpServerStartupState";
 export type { McpServerStatus } from "./McpServerStatus";
 export type { McpServerStatusDetail } from "./McpServerStatusDetail";
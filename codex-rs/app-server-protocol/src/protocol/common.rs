@@ -537,6 +537,26 @@ client_request_definitions! {
         response: v2::McpResourceReadResponse,
     },
 
+    McpServerPromptList => "mcpServer/prompt/list" {
+        params: v2::McpServerPromptListParams,
+        response: v2::McpServerPromptListResponse,
+    },
+
+    McpServerPromptGet => "mcpServer/prompt/get" {
+        params: v2::McpServerPromptGetParams,
+        response: v2::McpServerPromptGetResponse,
+    },
+
+    McpServerResourceSubscribe => "mcpServer/resource/subscribe" {
+        params: v2::McpServerResourceSubscribeParams,
+        response: v2::McpServerResourceSubscribeResponse,
+    },
+
+    McpServerResourceUnsubscribe => "mcpServer/resource/unsubscribe" {
+        params: v2::McpServerResourceUnsubscribeParams,
+        response: v2::McpServerResourceUnsubscribeResponse,
+    },
+
     McpServerToolCall => "mcpServer/tool/call" {
         params: v2::McpServerToolCallParams,
         response: v2::McpServerToolCallResponse,
@@ -663,6 +683,14 @@ client_request_definitions! {
         params: FuzzyFileSearchSessionStopParams,
         response: FuzzyFileSearchSessionStopResponse,
     },
+    /// Starts a session that searches file contents instead of file names. Queries are sent
+    /// with `fuzzyFileSearch/sessionUpdate` and the session is ended with
+    /// `fuzzyFileSearch/sessionStop`.
+    #[experimental("fuzzyFileSearch/contentSessionStart")]
+    FuzzyFileSearchContentSessionStart => "fuzzyFileSearch/contentSessionStart" {
+        params: FuzzyFileSearchContentSessionStartParams,
+        response: FuzzyFileSearchContentSessionStartResponse,
+    },
 }
 
 /// Generates an `enum ServerRequest` where each variant is a request that the
@@ -1032,6 +1060,63 @@ pub struct FuzzyFileSearchSessionCompletedNotification {
     pub session_id: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct FuzzyFileSearchContentSessionStartParams {
+    pub session_id: String,
+    pub roots: Vec<String>,
+    /// Treat queries as regular expressions instead of literal text.
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Number of lines to include before and after each matching line, at most 10.
+    #[serde(default)]
+    pub context_lines: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS, Default)]
+pub struct FuzzyFileSearchContentSessionStartResponse {}
+
+/// Superset of [`codex_file_search::ContentMatch`]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct FuzzyFileSearchContentMatch {
+    pub root: String,
+    pub path: String,
+    pub file_name: String,
+    /// 1-based line number.
+    pub line_number: usize,
+    /// 1-based byte column of the first match on the line.
+    pub column: usize,
+    pub line: String,
+    /// Byte ranges of every match within `line`.
+    pub ranges: Vec<FuzzyFileSearchContentMatchRange>,
+    pub context_before: Vec<String>,
+    pub context_after: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+pub struct FuzzyFileSearchContentMatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct FuzzyFileSearchContentSessionUpdatedNotification {
+    pub session_id: String,
+    pub query: String,
+    pub matches: Vec<FuzzyFileSearchContentMatch>,
+    /// Whether the search stopped early after finding the maximum number of matches.
+    pub truncated: bool,
+    /// Why the query could not be searched, such as an invalid regular expression.
+    pub error: Option<String>,
+}
+
 server_notification_definitions! {
     /// NEW NOTIFICATIONS
     Error => "error" (v2::ErrorNotification),
@@ -1072,6 +1157,8 @@ server_notification_definitions! {
     McpToolCallProgress => "item/mcpToolCall/progress" (v2::McpToolCallProgressNotification),
     McpServerOauthLoginCompleted => "mcpServer/oauthLogin/completed" (v2::McpServerOauthLoginCompletedNotification),
     McpServerStatusUpdated => "mcpServer/startupStatus/updated" (v2::McpServerStatusUpdatedNotification),
+    McpServerPromptListUpdated => "mcpServer/promptList/updated" (v2::McpServerPromptListUpdatedNotification),
+    McpServerResourceUpdated => "mcpServer/resource/updated" (v2::McpServerResourceUpdatedNotification),
     AccountUpdated => "account/updated" (v2::AccountUpdatedNotification),
     AccountRateLimitsUpdated => "account/rateLimits/updated" (v2::AccountRateLimitsUpdatedNotification),
     AppListUpdated => "app/list/updated" (v2::AppListUpdatedNotification),
@@ -1090,6 +1177,7 @@ server_notification_definitions! {
     ConfigWarning => "configWarning" (v2::ConfigWarningNotification),
     FuzzyFileSearchSessionUpdated => "fuzzyFileSearch/sessionUpdated" (FuzzyFileSearchSessionUpdatedNotification),
     FuzzyFileSearchSessionCompleted => "fuzzyFileSearch/sessionCompleted" (FuzzyFileSearchSessionCompletedNotification),
+    FuzzyFileSearchContentSessionUpdated => "fuzzyFileSearch/contentSessionUpdated" (FuzzyFileSearchContentSessionUpdatedNotification),
     #[experimental("thread/realtime/started")]
     ThreadRealtimeStarted => "thread/realtime/started" (v2::ThreadRealtimeStartedNotification),
     #[experimental("thread/realtime/itemAdded")]
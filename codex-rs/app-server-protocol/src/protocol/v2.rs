@@ -10,6 +10,7 @@ use codex_protocol::account::PlanType;
 use codex_protocol::account::ProviderAccount;
 use codex_protocol::approvals::ElicitationRequest as CoreElicitationRequest;
 use codex_protocol::approvals::ExecPolicyAmendment as CoreExecPolicyAmendment;
+use codex_protocol::approvals::ExecPolicyAmendmentScope as CoreExecPolicyAmendmentScope;
 use codex_protocol::approvals::GuardianAssessmentAction as CoreGuardianAssessmentAction;
 use codex_protocol::approvals::GuardianAssessmentDecisionSource as CoreGuardianAssessmentDecisionSource;
 use codex_protocol::approvals::GuardianCommandSource as CoreGuardianCommandSource;
@@ -32,6 +33,9 @@ use codex_protocol::config_types::WebSearchToolConfig;
 use codex_protocol::items::AgentMessageContent as CoreAgentMessageContent;
 use codex_protocol::items::TurnItem as CoreTurnItem;
 use codex_protocol::mcp::CallToolResult as CoreMcpCallToolResult;
+use codex_protocol::mcp::GetPromptResult as CoreMcpGetPromptResult;
+use codex_protocol::mcp::Prompt as McpPrompt;
+use codex_protocol::mcp::PromptMessage as McpPromptMessage;
 use codex_protocol::mcp::Resource as McpResource;
 pub use codex_protocol::mcp::ResourceContent as McpResourceContent;
 use codex_protocol::mcp::ResourceTemplate as McpResourceTemplate;
@@ -440,13 +444,25 @@ v2_enum_from_core!(
 
 v2_enum_from_core!(
     pub enum HookEventName from CoreHookEventName {
-        PreToolUse, PermissionRequest, PostToolUse, SessionStart, UserPromptSubmit, Stop
+        PreToolUse,
+        PermissionRequest,
+        PostToolUse,
+        SessionStart,
+        UserPromptSubmit,
+        Stop,
+        PreCompact,
+        PostCompact,
+        SubagentStart,
+        SubagentStop,
+        PreApplyPatch,
+        PostApplyPatch,
+        TurnError
     }
 );
 
 v2_enum_from_core!(
     pub enum HookHandlerType from CoreHookHandlerType {
-        Command, Prompt, Agent
+        Command, Prompt, Agent, Server
     }
 );
 
@@ -487,7 +503,7 @@ v2_enum_from_core!(
 
 v2_enum_from_core!(
     pub enum HookOutputEntryKind from CoreHookOutputEntryKind {
-        Warning, Stop, Feedback, Context, Error
+        Warning, Stop, Feedback, Context, Error, Rewrite
     }
 );
 
@@ -981,6 +997,27 @@ pub struct ManagedHooksRequirements {
     #[serde(rename = "Stop")]
     #[ts(rename = "Stop")]
     pub stop: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "PreCompact")]
+    #[ts(rename = "PreCompact")]
+    pub pre_compact: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "PostCompact")]
+    #[ts(rename = "PostCompact")]
+    pub post_compact: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "SubagentStart")]
+    #[ts(rename = "SubagentStart")]
+    pub subagent_start: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "SubagentStop")]
+    #[ts(rename = "SubagentStop")]
+    pub subagent_stop: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "PreApplyPatch")]
+    #[ts(rename = "PreApplyPatch")]
+    pub pre_apply_patch: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "PostApplyPatch")]
+    #[ts(rename = "PostApplyPatch")]
+    pub post_apply_patch: Vec<ConfiguredHookMatcherGroup>,
+    #[serde(rename = "TurnError")]
+    #[ts(rename = "TurnError")]
+    pub turn_error: Vec<ConfiguredHookMatcherGroup>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
@@ -1013,6 +1050,9 @@ pub enum ConfiguredHookHandler {
     #[serde(rename = "agent")]
     #[ts(rename = "agent")]
     Agent {},
+    #[serde(rename = "server")]
+    #[ts(rename = "server")]
+    Server {},
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1210,6 +1250,12 @@ pub enum CommandExecutionApprovalDecision {
     /// matching commands can run without prompting.
     AcceptWithExecpolicyAmendment {
         execpolicy_amendment: ExecPolicyAmendment,
+        /// Limits where and for how long the saved rule applies.
+        #[serde(
+            default,
+            skip_serializing_if = "ExecPolicyAmendmentScope::is_permanent"
+        )]
+        scope: ExecPolicyAmendmentScope,
     },
     /// User chose a persistent network policy rule (allow/deny) for this host.
     ApplyNetworkPolicyAmendment {
@@ -1227,8 +1273,10 @@ impl From<CoreReviewDecision> for CommandExecutionApprovalDecision {
             CoreReviewDecision::Approved => Self::Accept,
             CoreReviewDecision::ApprovedExecpolicyAmendment {
                 proposed_execpolicy_amendment,
+                scope,
             } => Self::AcceptWithExecpolicyAmendment {
                 execpolicy_amendment: proposed_execpolicy_amendment.into(),
+                scope: scope.into(),
             },
             CoreReviewDecision::ApprovedForSession => Self::AcceptForSession,
             CoreReviewDecision::NetworkPolicyAmendment {
@@ -1914,6 +1962,28 @@ impl From<CoreExecPolicyAmendment> for ExecPolicyAmendment {
     }
 }
 
+v2_enum_from_core!(
+    /// Where and for how long an approved [`ExecPolicyAmendment`] applies once it is saved.
+    #[derive(Default)]
+    pub enum ExecPolicyAmendmentScope from CoreExecPolicyAmendmentScope {
+        /// The rule applies to every thread and directory until it is removed.
+        #[default]
+        Permanent,
+        /// The rule only applies to commands run by the thread that approved it.
+        Thread,
+        /// The rule only applies to commands run inside the approving turn's working directory.
+        Project,
+        /// The rule applies everywhere but expires one day after it is approved.
+        Day
+    }
+);
+
+impl ExecPolicyAmendmentScope {
+    pub fn is_permanent(&self) -> bool {
+        matches!(self, Self::Permanent)
+    }
+}
+
 v2_enum_from_core!(
     pub enum NetworkPolicyRuleAction from CoreNetworkPolicyRuleAction {
         Allow, Deny
@@ -2554,6 +2624,88 @@ pub struct McpResourceReadResponse {
     pub contents: Vec<McpResourceContent>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptListResponse {
+    /// Prompts grouped by MCP server, sorted by server name.
+    pub data: Vec<McpServerPrompts>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPrompts {
+    pub server: String,
+    pub prompts: Vec<McpPrompt>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptGetParams {
+    pub thread_id: String,
+    pub server: String,
+    pub name: String,
+    /// Values for the prompt's declared arguments, keyed by argument name.
+    #[ts(optional = nullable)]
+    pub arguments: Option<HashMap<String, String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptGetResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+    pub messages: Vec<McpPromptMessage>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerResourceSubscribeParams {
+    pub thread_id: String,
+    pub server: String,
+    pub uri: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerResourceSubscribeResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerResourceUnsubscribeParams {
+    pub thread_id: String,
+    pub server: String,
+    pub uri: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerResourceUnsubscribeResponse {}
+
+impl From<CoreMcpGetPromptResult> for McpServerPromptGetResponse {
+    fn from(result: CoreMcpGetPromptResult) -> Self {
+        Self {
+            description: result.description,
+            messages: result.messages,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -6730,6 +6882,31 @@ pub struct McpServerStatusUpdatedNotification {
     pub error: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+/// Notification emitted when an MCP server reports that its prompt list changed.
+///
+/// Treat this as an invalidation signal and re-run `mcpServer/prompt/list`
+/// for the thread when refreshed prompts are needed.
+pub struct McpServerPromptListUpdatedNotification {
+    pub thread_id: String,
+    pub server: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+/// Notification emitted when a resource the thread subscribed to changed.
+///
+/// The difference from the previous contents has already been queued as
+/// context for the thread's next turn.
+pub struct McpServerResourceUpdatedNotification {
+    pub thread_id: String,
+    pub server: String,
+    pub uri: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
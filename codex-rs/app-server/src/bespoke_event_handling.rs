@@ -54,6 +54,8 @@ use codex_app_server_protocol::ItemStartedNotification;
 use codex_app_server_protocol::McpServerElicitationAction;
 use codex_app_server_protocol::McpServerElicitationRequestParams;
 use codex_app_server_protocol::McpServerElicitationRequestResponse;
+use codex_app_server_protocol::McpServerPromptListUpdatedNotification;
+use codex_app_server_protocol::McpServerResourceUpdatedNotification;
 use codex_app_server_protocol::McpServerStartupState;
 use codex_app_server_protocol::McpServerStatusUpdatedNotification;
 use codex_app_server_protocol::McpToolCallError;
@@ -274,6 +276,31 @@ pub(crate) async fn apply_bespoke_event_handling(
                     .await;
             }
         }
+        EventMsg::McpPromptsChanged(event) => {
+            if let ApiVersion::V2 = api_version {
+                outgoing
+                    .send_server_notification(ServerNotification::McpServerPromptListUpdated(
+                        McpServerPromptListUpdatedNotification {
+                            thread_id: conversation_id.to_string(),
+                            server: event.server,
+                        },
+                    ))
+                    .await;
+            }
+        }
+        EventMsg::McpResourceUpdated(event) => {
+            if let ApiVersion::V2 = api_version {
+                outgoing
+                    .send_server_notification(ServerNotification::McpServerResourceUpdated(
+                        McpServerResourceUpdatedNotification {
+                            thread_id: conversation_id.to_string(),
+                            server: event.server,
+                            uri: event.uri,
+                        },
+                    ))
+                    .await;
+            }
+        }
         EventMsg::Warning(warning_event) => {
             if let ApiVersion::V2 = api_version {
                 let notification = WarningNotification {
@@ -2896,9 +2923,11 @@ async fn on_command_execution_request_approval_response(
                 }
                 CommandExecutionApprovalDecision::AcceptWithExecpolicyAmendment {
                     execpolicy_amendment,
+                    scope,
                 } => (
                     ReviewDecision::ApprovedExecpolicyAmendment {
                         proposed_execpolicy_amendment: execpolicy_amendment.into_core(),
+                        scope: scope.to_core(),
                     },
                     None,
                 ),
@@ -8,8 +8,10 @@ use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_PARAMS_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::error_code::invalid_params;
+use crate::fuzzy_file_search::FileSearchIndex;
 use crate::fuzzy_file_search::FuzzyFileSearchSession;
 use crate::fuzzy_file_search::run_fuzzy_file_search;
+use crate::fuzzy_file_search::start_content_search_session;
 use crate::fuzzy_file_search::start_fuzzy_file_search_session;
 use crate::models::supported_models;
 use crate::outgoing_message::ConnectionId;
@@ -59,6 +61,8 @@ use codex_app_server_protocol::ExperimentalFeatureListResponse;
 use codex_app_server_protocol::ExperimentalFeatureStage as ApiExperimentalFeatureStage;
 use codex_app_server_protocol::FeedbackUploadParams;
 use codex_app_server_protocol::FeedbackUploadResponse;
+use codex_app_server_protocol::FuzzyFileSearchContentSessionStartParams;
+use codex_app_server_protocol::FuzzyFileSearchContentSessionStartResponse;
 use codex_app_server_protocol::FuzzyFileSearchParams;
 use codex_app_server_protocol::FuzzyFileSearchResponse;
 use codex_app_server_protocol::FuzzyFileSearchSessionStartParams;
@@ -96,7 +100,16 @@ use codex_app_server_protocol::McpResourceReadResponse;
 use codex_app_server_protocol::McpServerOauthLoginCompletedNotification;
 use codex_app_server_protocol::McpServerOauthLoginParams;
 use codex_app_server_protocol::McpServerOauthLoginResponse;
+use codex_app_server_protocol::McpServerPromptGetParams;
+use codex_app_server_protocol::McpServerPromptGetResponse;
+use codex_app_server_protocol::McpServerPromptListParams;
+use codex_app_server_protocol::McpServerPromptListResponse;
+use codex_app_server_protocol::McpServerPrompts;
 use codex_app_server_protocol::McpServerRefreshResponse;
+use codex_app_server_protocol::McpServerResourceSubscribeParams;
+use codex_app_server_protocol::McpServerResourceSubscribeResponse;
+use codex_app_server_protocol::McpServerResourceUnsubscribeParams;
+use codex_app_server_protocol::McpServerResourceUnsubscribeResponse;
 use codex_app_server_protocol::McpServerStatus;
 use codex_app_server_protocol::McpServerStatusDetail;
 use codex_app_server_protocol::McpServerToolCallParams;
@@ -373,6 +386,7 @@ use codex_thread_store::ReadThreadByRolloutPathParams as StoreReadThreadByRollou
 use codex_thread_store::ReadThreadParams as StoreReadThreadParams;
 use codex_thread_store::RemoteThreadStore;
 use codex_thread_store::SortDirection as StoreSortDirection;
+use codex_thread_store::SqliteThreadStore;
 use codex_thread_store::StoredThread;
 use codex_thread_store::ThreadMetadataPatch as StoreThreadMetadataPatch;
 use codex_thread_store::ThreadSortKey as StoreThreadSortKey;
@@ -527,6 +541,7 @@ pub(crate) struct CodexMessageProcessor {
     workspace_settings_cache: Arc<workspace_settings::WorkspaceSettingsCache>,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
     fuzzy_search_sessions: Arc<Mutex<HashMap<String, FuzzyFileSearchSession>>>,
+    file_search_index: Option<Arc<FileSearchIndex>>,
     background_tasks: TaskTracker,
     feedback: CodexFeedback,
     log_db: Option<LogDbLayer>,
@@ -681,11 +696,16 @@ pub(crate) struct CodexMessageProcessorArgs {
     pub(crate) config_manager: ConfigManager,
     pub(crate) feedback: CodexFeedback,
     pub(crate) log_db: Option<LogDbLayer>,
+    /// Persistent index for fuzzy file searches, when `file_search_index` is enabled.
+    pub(crate) file_search_index: Option<Arc<FileSearchIndex>>,
 }
 
 fn configured_thread_store(config: &Config) -> Arc<dyn ThreadStore> {
     match &config.experimental_thread_store {
         ThreadStoreConfig::Local => Arc::new(configured_local_thread_store(config)),
+        ThreadStoreConfig::Sqlite => Arc::new(SqliteThreadStore::new(
+            codex_rollout::RolloutConfig::from_view(config),
+        )),
         ThreadStoreConfig::Remote { endpoint } => Arc::new(RemoteThreadStore::new(endpoint)),
         #[cfg(debug_assertions)]
         ThreadStoreConfig::InMemory { id } => InMemoryThreadStore::for_id(id),
@@ -775,6 +795,7 @@ impl CodexMessageProcessor {
             config_manager,
             feedback,
             log_db,
+            file_search_index,
         } = args;
         Self {
             auth_manager,
@@ -795,6 +816,7 @@ impl CodexMessageProcessor {
             ),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
             fuzzy_search_sessions: Arc::new(Mutex::new(HashMap::new())),
+            file_search_index,
             background_tasks: TaskTracker::new(),
             feedback,
             log_db,
@@ -1175,6 +1197,22 @@ impl CodexMessageProcessor {
                 self.read_mcp_resource(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::McpServerPromptList { request_id, params } => {
+                self.list_mcp_server_prompts(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::McpServerPromptGet { request_id, params } => {
+                self.get_mcp_server_prompt(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::McpServerResourceSubscribe { request_id, params } => {
+                self.subscribe_mcp_server_resource(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::McpServerResourceUnsubscribe { request_id, params } => {
+                self.unsubscribe_mcp_server_resource(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::McpServerToolCall { request_id, params } => {
                 self.call_mcp_server_tool(to_connection_request_id(request_id), params)
                     .await;
@@ -1225,6 +1263,13 @@ impl CodexMessageProcessor {
                 self.fuzzy_file_search_session_stop(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::FuzzyFileSearchContentSessionStart { request_id, params } => {
+                self.fuzzy_file_search_content_session_start(
+                    to_connection_request_id(request_id),
+                    params,
+                )
+                .await;
+            }
             ClientRequest::OneOffCommandExec { request_id, params } => {
                 self.exec_one_off_command(to_connection_request_id(request_id), params)
                     .await;
@@ -5629,6 +5674,122 @@ impl CodexMessageProcessor {
         outgoing.send_result(request_id, result).await;
     }
 
+    async fn list_mcp_server_prompts(
+        &self,
+        request_id: ConnectionRequestId,
+        params: McpServerPromptListParams,
+    ) {
+        let outgoing = Arc::clone(&self.outgoing);
+        let (_, thread) = match self.load_thread(&params.thread_id).await {
+            Ok(thread) => thread,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        tokio::spawn(async move {
+            let mut data = thread
+                .list_mcp_prompts()
+                .await
+                .into_iter()
+                .map(|(server, prompts)| McpServerPrompts { server, prompts })
+                .collect::<Vec<_>>();
+            data.sort_by(|a, b| a.server.cmp(&b.server));
+            outgoing
+                .send_response(request_id, McpServerPromptListResponse { data })
+                .await;
+        });
+    }
+
+    async fn get_mcp_server_prompt(
+        &self,
+        request_id: ConnectionRequestId,
+        params: McpServerPromptGetParams,
+    ) {
+        let outgoing = Arc::clone(&self.outgoing);
+        let McpServerPromptGetParams {
+            thread_id,
+            server,
+            name,
+            arguments,
+        } = params;
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(thread) => thread,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        tokio::spawn(async move {
+            let result = thread
+                .get_mcp_prompt(&server, &name, arguments.unwrap_or_default())
+                .await
+                .map(McpServerPromptGetResponse::from)
+                .map_err(|error| internal_error(format!("{error:#}")));
+            outgoing.send_result(request_id, result).await;
+        });
+    }
+
+    async fn subscribe_mcp_server_resource(
+        &self,
+        request_id: ConnectionRequestId,
+        params: McpServerResourceSubscribeParams,
+    ) {
+        let outgoing = Arc::clone(&self.outgoing);
+        let McpServerResourceSubscribeParams {
+            thread_id,
+            server,
+            uri,
+        } = params;
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(thread) => thread,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        tokio::spawn(async move {
+            let result = thread
+                .subscribe_mcp_resource(&server, &uri)
+                .await
+                .map(|()| McpServerResourceSubscribeResponse {})
+                .map_err(|error| internal_error(format!("{error:#}")));
+            outgoing.send_result(request_id, result).await;
+        });
+    }
+
+    async fn unsubscribe_mcp_server_resource(
+        &self,
+        request_id: ConnectionRequestId,
+        params: McpServerResourceUnsubscribeParams,
+    ) {
+        let outgoing = Arc::clone(&self.outgoing);
+        let McpServerResourceUnsubscribeParams {
+            thread_id,
+            server,
+            uri,
+        } = params;
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(thread) => thread,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        tokio::spawn(async move {
+            let result = thread
+                .unsubscribe_mcp_resource(&server, &uri)
+                .await
+                .map(|()| McpServerResourceUnsubscribeResponse {})
+                .map_err(|error| internal_error(format!("{error:#}")));
+            outgoing.send_result(request_id, result).await;
+        });
+    }
+
     async fn call_mcp_server_tool(
         &self,
         request_id: ConnectionRequestId,
@@ -7488,7 +7649,15 @@ impl CodexMessageProcessor {
 
         let results = match query.as_str() {
             "" => vec![],
-            _ => run_fuzzy_file_search(query, roots, cancel_flag.clone()).await,
+            _ => {
+                run_fuzzy_file_search(
+                    query,
+                    roots,
+                    cancel_flag.clone(),
+                    self.file_search_index.as_deref(),
+                )
+                .await
+            }
         };
 
         if let Some(token) = cancellation_token {
@@ -7522,11 +7691,15 @@ impl CodexMessageProcessor {
             return Err(invalid_request("sessionId must not be empty"));
         }
 
-        let session =
-            start_fuzzy_file_search_session(session_id.clone(), roots, self.outgoing.clone())
-                .map_err(|err| {
-                    internal_error(format!("failed to start fuzzy file search session: {err}"))
-                })?;
+        let session = start_fuzzy_file_search_session(
+            session_id.clone(),
+            roots,
+            self.outgoing.clone(),
+            self.file_search_index.as_deref(),
+        )
+        .map_err(|err| {
+            internal_error(format!("failed to start fuzzy file search session: {err}"))
+        })?;
         self.fuzzy_search_sessions
             .lock()
             .await
@@ -7534,6 +7707,39 @@ impl CodexMessageProcessor {
         Ok(FuzzyFileSearchSessionStartResponse {})
     }
 
+    async fn fuzzy_file_search_content_session_start(
+        &self,
+        request_id: ConnectionRequestId,
+        params: FuzzyFileSearchContentSessionStartParams,
+    ) {
+        let result = self
+            .fuzzy_file_search_content_session_start_response(params)
+            .await;
+        self.outgoing.send_result(request_id, result).await;
+    }
+
+    async fn fuzzy_file_search_content_session_start_response(
+        &self,
+        params: FuzzyFileSearchContentSessionStartParams,
+    ) -> Result<FuzzyFileSearchContentSessionStartResponse, JSONRPCErrorError> {
+        if params.session_id.is_empty() {
+            return Err(invalid_request("sessionId must not be empty"));
+        }
+
+        let session_id = params.session_id.clone();
+        let session =
+            start_content_search_session(params, self.outgoing.clone()).map_err(|err| {
+                internal_error(format!("failed to start content search session: {err}"))
+            })?;
+        // Content sessions share the fuzzy search session map, so they are updated and
+        // stopped with the same methods.
+        self.fuzzy_search_sessions
+            .lock()
+            .await
+            .insert(session_id, session);
+        Ok(FuzzyFileSearchContentSessionStartResponse {})
+    }
+
     async fn fuzzy_file_search_session_update(
         &self,
         request_id: ConnectionRequestId,
@@ -9947,6 +10153,7 @@ mod tests {
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             websocket_connect_timeout_ms: None,
+            max_output_tokens: None,
             requires_openai_auth: false,
             supports_websockets: true,
         };
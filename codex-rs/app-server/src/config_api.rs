@@ -307,6 +307,13 @@ fn map_hooks_requirements_to_api(hooks: ManagedHooksRequirementsToml) -> Managed
         session_start,
         user_prompt_submit,
         stop,
+        pre_compact,
+        post_compact,
+        subagent_start,
+        subagent_stop,
+        pre_apply_patch,
+        post_apply_patch,
+        turn_error,
     } = hooks;
 
     ManagedHooksRequirements {
@@ -318,6 +325,13 @@ fn map_hooks_requirements_to_api(hooks: ManagedHooksRequirementsToml) -> Managed
         session_start: map_hook_matcher_groups_to_api(session_start),
         user_prompt_submit: map_hook_matcher_groups_to_api(user_prompt_submit),
         stop: map_hook_matcher_groups_to_api(stop),
+        pre_compact: map_hook_matcher_groups_to_api(pre_compact),
+        post_compact: map_hook_matcher_groups_to_api(post_compact),
+        subagent_start: map_hook_matcher_groups_to_api(subagent_start),
+        subagent_stop: map_hook_matcher_groups_to_api(subagent_stop),
+        pre_apply_patch: map_hook_matcher_groups_to_api(pre_apply_patch),
+        post_apply_patch: map_hook_matcher_groups_to_api(post_apply_patch),
+        turn_error: map_hook_matcher_groups_to_api(turn_error),
     }
 }
 
@@ -354,8 +368,9 @@ fn map_hook_handler_to_api(handler: CoreHookHandlerConfig) -> ConfiguredHookHand
             r#async,
             status_message,
         },
-        CoreHookHandlerConfig::Prompt {} => ConfiguredHookHandler::Prompt {},
-        CoreHookHandlerConfig::Agent {} => ConfiguredHookHandler::Agent {},
+        CoreHookHandlerConfig::Prompt(_) => ConfiguredHookHandler::Prompt {},
+        CoreHookHandlerConfig::Agent(_) => ConfiguredHookHandler::Agent {},
+        CoreHookHandlerConfig::Server(_) => ConfiguredHookHandler::Server {},
     }
 }
 
@@ -625,6 +640,13 @@ mod tests {
                 session_start: Vec::new(),
                 user_prompt_submit: Vec::new(),
                 stop: Vec::new(),
+                pre_compact: Vec::new(),
+                post_compact: Vec::new(),
+                subagent_start: Vec::new(),
+                subagent_stop: Vec::new(),
+                pre_apply_patch: Vec::new(),
+                post_apply_patch: Vec::new(),
+                turn_error: Vec::new(),
             }),
         );
         assert_eq!(
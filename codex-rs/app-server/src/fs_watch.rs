@@ -1,4 +1,5 @@
 use crate::error_code::invalid_request;
+use crate::fuzzy_file_search::FileSearchIndex;
 use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::OutgoingMessageSender;
 use codex_app_server_protocol::FsChangedNotification;
@@ -14,6 +15,7 @@ use codex_core::file_watcher::FileWatcherSubscriber;
 use codex_core::file_watcher::Receiver;
 use codex_core::file_watcher::WatchPath;
 use codex_core::file_watcher::WatchRegistration;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::hash_map::Entry;
@@ -72,6 +74,7 @@ impl DebouncedReceiver {
 pub(crate) struct FsWatchManager {
     outgoing: Arc<OutgoingMessageSender>,
     file_watcher: Arc<FileWatcher>,
+    file_search_index: Option<Arc<FileSearchIndex>>,
     state: Arc<AsyncMutex<FsWatchState>>,
 }
 
@@ -93,7 +96,10 @@ struct WatchKey {
 }
 
 impl FsWatchManager {
-    pub(crate) fn new(outgoing: Arc<OutgoingMessageSender>) -> Self {
+    pub(crate) fn new(
+        outgoing: Arc<OutgoingMessageSender>,
+        file_search_index: Option<Arc<FileSearchIndex>>,
+    ) -> Self {
         let file_watcher = match FileWatcher::new() {
             Ok(file_watcher) => Arc::new(file_watcher),
             Err(err) => {
@@ -101,16 +107,18 @@ impl FsWatchManager {
                 Arc::new(FileWatcher::noop())
             }
         };
-        Self::new_with_file_watcher(outgoing, file_watcher)
+        Self::new_with_file_watcher(outgoing, file_watcher, file_search_index)
     }
 
     fn new_with_file_watcher(
         outgoing: Arc<OutgoingMessageSender>,
         file_watcher: Arc<FileWatcher>,
+        file_search_index: Option<Arc<FileSearchIndex>>,
     ) -> Self {
         Self {
             outgoing,
             file_watcher,
+            file_search_index,
             state: Arc::new(AsyncMutex::new(FsWatchState::default())),
         }
     }
@@ -126,6 +134,7 @@ impl FsWatchManager {
             watch_id: watch_id.clone(),
         };
         let outgoing = self.outgoing.clone();
+        let file_search_index = self.file_search_index.clone();
         let (subscriber, rx) = self.file_watcher.add_subscriber();
         let watch_root = params.path.clone();
         let registration = subscriber.register_paths(vec![WatchPath {
@@ -168,6 +177,14 @@ impl FsWatchManager {
                     .map(|path| watch_root.join(path))
                     .collect::<Vec<_>>();
                 changed_paths.sort_by(|left, right| left.as_path().cmp(right.as_path()));
+                if let Some(file_search_index) = file_search_index.as_ref() {
+                    file_search_index.apply_changes(
+                        changed_paths
+                            .iter()
+                            .map(AbsolutePathBuf::to_path_buf)
+                            .collect(),
+                    );
+                }
                 if !changed_paths.is_empty() {
                     outgoing
                         .send_server_notification_to_connection_and_wait(
@@ -236,6 +253,7 @@ mod tests {
         FsWatchManager::new_with_file_watcher(
             Arc::new(OutgoingMessageSender::new(tx)),
             Arc::new(FileWatcher::noop()),
+            /*file_search_index*/ None,
         )
     }
 
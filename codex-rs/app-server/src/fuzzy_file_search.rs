@@ -1,27 +1,107 @@
+use std::collections::HashMap;
 use std::num::NonZero;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
+use codex_app_server_protocol::FuzzyFileSearchContentMatch;
+use codex_app_server_protocol::FuzzyFileSearchContentMatchRange;
+use codex_app_server_protocol::FuzzyFileSearchContentSessionStartParams;
+use codex_app_server_protocol::FuzzyFileSearchContentSessionUpdatedNotification;
 use codex_app_server_protocol::FuzzyFileSearchMatchType;
 use codex_app_server_protocol::FuzzyFileSearchResult;
 use codex_app_server_protocol::FuzzyFileSearchSessionCompletedNotification;
 use codex_app_server_protocol::FuzzyFileSearchSessionUpdatedNotification;
 use codex_app_server_protocol::ServerNotification;
+use codex_core::file_watcher::FileWatcher;
+use codex_core::file_watcher::FileWatcherSubscriber;
+use codex_core::file_watcher::ThrottledWatchReceiver;
+use codex_core::file_watcher::WatchPath;
+use codex_core::file_watcher::WatchRegistration;
 use codex_file_search as file_search;
 use tracing::warn;
 
 use crate::outgoing_message::OutgoingMessageSender;
 
 const MATCH_LIMIT: usize = 50;
+const CONTENT_MATCH_LIMIT: usize = 200;
+const MAX_CONTEXT_LINES: usize = 10;
 const MAX_THREADS: usize = 12;
+const INDEX_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Persistent index shared by the file searches of this process. Searched
+/// roots are watched so that the index stays current between searches.
+pub(crate) struct FileSearchIndex {
+    store: Arc<file_search::FileIndexStore>,
+    subscriber: FileWatcherSubscriber,
+    registrations: Mutex<HashMap<PathBuf, WatchRegistration>>,
+}
+
+impl FileSearchIndex {
+    pub(crate) fn new(codex_home: &Path) -> Arc<Self> {
+        let file_watcher = match FileWatcher::new() {
+            Ok(file_watcher) => Arc::new(file_watcher),
+            Err(err) => {
+                warn!("file search index falling back to noop core watcher: {err}");
+                Arc::new(FileWatcher::noop())
+            }
+        };
+        let store = Arc::new(file_search::FileIndexStore::new(
+            codex_home.join(file_search::INDEX_DIR_NAME),
+        ));
+        let (subscriber, rx) = file_watcher.add_subscriber();
+
+        let task_store = store.clone();
+        tokio::spawn(async move {
+            let mut rx = ThrottledWatchReceiver::new(rx, INDEX_UPDATE_INTERVAL);
+            while let Some(event) = rx.recv().await {
+                let store = task_store.clone();
+                let _ =
+                    tokio::task::spawn_blocking(move || store.apply_changes(&event.paths)).await;
+            }
+        });
+
+        Arc::new(Self {
+            store,
+            subscriber,
+            registrations: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Updates the index after `changed_paths` were created, modified or
+    /// removed, such as the paths reported by `fs/changed`.
+    pub(crate) fn apply_changes(&self, changed_paths: Vec<PathBuf>) {
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || store.apply_changes(&changed_paths));
+    }
+
+    /// Returns the store to search `roots` with, watching any root that is not
+    /// watched yet.
+    fn store_for(&self, roots: &[PathBuf]) -> Arc<file_search::FileIndexStore> {
+        #[expect(clippy::unwrap_used)]
+        let mut registrations = self.registrations.lock().unwrap();
+        for root in roots {
+            if !registrations.contains_key(root) {
+                let registration = self.subscriber.register_paths(vec![WatchPath {
+                    path: root.clone(),
+                    recursive: true,
+                }]);
+                registrations.insert(root.clone(), registration);
+            }
+        }
+        self.store.clone()
+    }
+}
 
 pub(crate) async fn run_fuzzy_file_search(
     query: String,
     roots: Vec<String>,
     cancellation_flag: Arc<AtomicBool>,
+    index: Option<&FileSearchIndex>,
 ) -> Vec<FuzzyFileSearchResult> {
     if roots.is_empty() {
         return Vec::new();
@@ -37,6 +117,7 @@ pub(crate) async fn run_fuzzy_file_search(
     #[expect(clippy::expect_used)]
     let threads = NonZero::new(threads.max(1)).expect("threads should be non-zero");
     let search_dirs: Vec<PathBuf> = roots.iter().map(PathBuf::from).collect();
+    let index = index.map(|index| index.store_for(&search_dirs));
 
     let mut files = match tokio::task::spawn_blocking(move || {
         file_search::run(
@@ -46,6 +127,7 @@ pub(crate) async fn run_fuzzy_file_search(
                 limit,
                 threads,
                 compute_indices: true,
+                index,
                 ..Default::default()
             },
             Some(cancellation_flag),
@@ -119,6 +201,7 @@ pub(crate) fn start_fuzzy_file_search_session(
     session_id: String,
     roots: Vec<String>,
     outgoing: Arc<OutgoingMessageSender>,
+    index: Option<&FileSearchIndex>,
 ) -> anyhow::Result<FuzzyFileSearchSession> {
     #[expect(clippy::expect_used)]
     let limit = NonZero::new(MATCH_LIMIT).expect("MATCH_LIMIT should be a valid non-zero usize");
@@ -129,6 +212,7 @@ pub(crate) fn start_fuzzy_file_search_session(
     #[expect(clippy::expect_used)]
     let threads = NonZero::new(threads.max(1)).expect("threads should be non-zero");
     let search_dirs: Vec<PathBuf> = roots.iter().map(PathBuf::from).collect();
+    let index = index.map(|index| index.store_for(&search_dirs));
     let canceled = Arc::new(AtomicBool::new(false));
 
     let shared = Arc::new(SessionShared {
@@ -148,6 +232,58 @@ pub(crate) fn start_fuzzy_file_search_session(
             limit,
             threads,
             compute_indices: true,
+            index,
+            ..Default::default()
+        },
+        reporter,
+        Some(canceled),
+    )?;
+
+    Ok(FuzzyFileSearchSession { session, shared })
+}
+
+pub(crate) fn start_content_search_session(
+    params: FuzzyFileSearchContentSessionStartParams,
+    outgoing: Arc<OutgoingMessageSender>,
+) -> anyhow::Result<FuzzyFileSearchSession> {
+    let FuzzyFileSearchContentSessionStartParams {
+        session_id,
+        roots,
+        regex,
+        case_sensitive,
+        context_lines,
+    } = params;
+    #[expect(clippy::expect_used)]
+    let limit = NonZero::new(CONTENT_MATCH_LIMIT)
+        .expect("CONTENT_MATCH_LIMIT should be a valid non-zero usize");
+    let cores = std::thread::available_parallelism()
+        .map(std::num::NonZero::get)
+        .unwrap_or(1);
+    let threads = cores.min(MAX_THREADS);
+    #[expect(clippy::expect_used)]
+    let threads = NonZero::new(threads.max(1)).expect("threads should be non-zero");
+    let search_dirs: Vec<PathBuf> = roots.iter().map(PathBuf::from).collect();
+    let canceled = Arc::new(AtomicBool::new(false));
+
+    let shared = Arc::new(SessionShared {
+        session_id,
+        latest_query: Mutex::new(String::new()),
+        outgoing,
+        runtime: tokio::runtime::Handle::current(),
+        canceled: canceled.clone(),
+    });
+
+    let reporter = Arc::new(SessionReporterImpl {
+        shared: shared.clone(),
+    });
+    let session = file_search::create_content_session(
+        search_dirs,
+        file_search::ContentSearchOptions {
+            limit,
+            threads,
+            regex,
+            case_sensitive,
+            context_lines: context_lines.min(MAX_CONTEXT_LINES),
             ..Default::default()
         },
         reporter,
@@ -202,6 +338,34 @@ impl SessionReporterImpl {
         });
     }
 
+    fn send_content_snapshot(&self, snapshot: &file_search::ContentSearchSnapshot) {
+        if self.shared.canceled.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let query = {
+            #[expect(clippy::unwrap_used)]
+            self.shared.latest_query.lock().unwrap().clone()
+        };
+        if snapshot.query != query {
+            return;
+        }
+
+        let notification = ServerNotification::FuzzyFileSearchContentSessionUpdated(
+            FuzzyFileSearchContentSessionUpdatedNotification {
+                session_id: self.shared.session_id.clone(),
+                query,
+                matches: collect_content_matches(snapshot),
+                truncated: snapshot.truncated,
+                error: snapshot.error.clone(),
+            },
+        );
+        let outgoing = self.shared.outgoing.clone();
+        self.shared.runtime.spawn(async move {
+            outgoing.send_server_notification(notification).await;
+        });
+    }
+
     fn send_complete(&self) {
         if self.shared.canceled.load(Ordering::Relaxed) {
             return;
@@ -222,6 +386,10 @@ impl file_search::SessionReporter for SessionReporterImpl {
         self.send_snapshot(snapshot);
     }
 
+    fn on_content_update(&self, snapshot: &file_search::ContentSearchSnapshot) {
+        self.send_content_snapshot(snapshot);
+    }
+
     fn on_complete(&self) {
         self.send_complete();
     }
@@ -254,3 +422,33 @@ fn collect_files(snapshot: &file_search::FileSearchSnapshot) -> Vec<FuzzyFileSea
     >(|f| f.score, |f| f.path.as_str()));
     files
 }
+
+fn collect_content_matches(
+    snapshot: &file_search::ContentSearchSnapshot,
+) -> Vec<FuzzyFileSearchContentMatch> {
+    snapshot
+        .matches
+        .iter()
+        .map(|m| {
+            let file_name = m.path.file_name().unwrap_or_default();
+            FuzzyFileSearchContentMatch {
+                root: m.root.to_string_lossy().to_string(),
+                path: m.path.to_string_lossy().to_string(),
+                file_name: file_name.to_string_lossy().to_string(),
+                line_number: m.line_number,
+                column: m.column,
+                line: m.line.clone(),
+                ranges: m
+                    .ranges
+                    .iter()
+                    .map(|range| FuzzyFileSearchContentMatchRange {
+                        start: range.start,
+                        end: range.end,
+                    })
+                    .collect(),
+                context_before: m.context_before.clone(),
+                context_after: m.context_after.clone(),
+            }
+        })
+        .collect()
+}
@@ -14,6 +14,7 @@ use crate::error_code::invalid_request;
 use crate::external_agent_config_api::ExternalAgentConfigApi;
 use crate::fs_api::FsApi;
 use crate::fs_watch::FsWatchManager;
+use crate::fuzzy_file_search::FileSearchIndex;
 use crate::outgoing_message::ConnectionId;
 use crate::outgoing_message::ConnectionRequestId;
 use crate::outgoing_message::OutgoingMessageSender;
@@ -294,6 +295,10 @@ impl MessageProcessor {
             .plugins_manager()
             .set_analytics_events_client(analytics_events_client.clone());
 
+        let file_search_index = config
+            .features
+            .enabled(Feature::FileSearchIndex)
+            .then(|| FileSearchIndex::new(&config.codex_home));
         let codex_message_processor = CodexMessageProcessor::new(CodexMessageProcessorArgs {
             auth_manager: auth_manager.clone(),
             thread_manager: Arc::clone(&thread_manager),
@@ -304,6 +309,7 @@ impl MessageProcessor {
             config_manager: config_manager.clone(),
             feedback,
             log_db,
+            file_search_index: file_search_index.clone(),
         });
         if matches!(plugin_startup_tasks, crate::PluginStartupTasks::Start) {
             // Keep plugin startup warmups aligned at app-server startup.
@@ -327,7 +333,7 @@ impl MessageProcessor {
                 .local_environment()
                 .get_filesystem(),
         );
-        let fs_watch_manager = FsWatchManager::new(outgoing.clone());
+        let fs_watch_manager = FsWatchManager::new(outgoing.clone(), file_search_index);
 
         Self {
             outgoing,
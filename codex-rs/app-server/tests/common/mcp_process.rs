@@ -51,6 +51,10 @@ use codex_app_server_protocol::MarketplaceAddParams;
 use codex_app_server_protocol::MarketplaceRemoveParams;
 use codex_app_server_protocol::MarketplaceUpgradeParams;
 use codex_app_server_protocol::McpResourceReadParams;
+use codex_app_server_protocol::McpServerPromptGetParams;
+use codex_app_server_protocol::McpServerPromptListParams;
+use codex_app_server_protocol::McpServerResourceSubscribeParams;
+use codex_app_server_protocol::McpServerResourceUnsubscribeParams;
 use codex_app_server_protocol::McpServerToolCallParams;
 use codex_app_server_protocol::MockExperimentalMethodParams;
 use codex_app_server_protocol::ModelListParams;
@@ -542,6 +546,44 @@ impl McpProcess {
         self.send_request("mcpServer/resource/read", params).await
     }
 
+    /// Send an `mcpServer/prompt/list` JSON-RPC request.
+    pub async fn send_mcp_server_prompt_list_request(
+        &mut self,
+        params: McpServerPromptListParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("mcpServer/prompt/list", params).await
+    }
+
+    /// Send an `mcpServer/prompt/get` JSON-RPC request.
+    pub async fn send_mcp_server_prompt_get_request(
+        &mut self,
+        params: McpServerPromptGetParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("mcpServer/prompt/get", params).await
+    }
+
+    /// Send an `mcpServer/resource/subscribe` JSON-RPC request.
+    pub async fn send_mcp_server_resource_subscribe_request(
+        &mut self,
+        params: McpServerResourceSubscribeParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("mcpServer/resource/subscribe", params)
+            .await
+    }
+
+    /// Send an `mcpServer/resource/unsubscribe` JSON-RPC request.
+    pub async fn send_mcp_server_resource_unsubscribe_request(
+        &mut self,
+        params: McpServerResourceUnsubscribeParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("mcpServer/resource/unsubscribe", params)
+            .await
+    }
+
     /// Send an `mcpServer/tool/call` JSON-RPC request.
     pub async fn send_mcp_server_tool_call_request(
         &mut self,
@@ -1032,6 +1074,34 @@ impl McpProcess {
             .await
     }
 
+    pub async fn send_fuzzy_file_search_content_session_start_request(
+        &mut self,
+        session_id: &str,
+        roots: Vec<String>,
+        regex: bool,
+    ) -> anyhow::Result<i64> {
+        let params = serde_json::json!({
+            "sessionId": session_id,
+            "roots": roots,
+            "regex": regex,
+        });
+        self.send_request("fuzzyFileSearch/contentSessionStart", Some(params))
+            .await
+    }
+
+    pub async fn start_fuzzy_file_search_content_session(
+        &mut self,
+        session_id: &str,
+        roots: Vec<String>,
+        regex: bool,
+    ) -> anyhow::Result<JSONRPCResponse> {
+        let request_id = self
+            .send_fuzzy_file_search_content_session_start_request(session_id, roots, regex)
+            .await?;
+        self.read_stream_until_response_message(RequestId::Integer(request_id))
+            .await
+    }
+
     pub async fn send_fuzzy_file_search_session_update_request(
         &mut self,
         session_id: &str,
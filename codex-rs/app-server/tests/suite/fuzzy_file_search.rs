@@ -1,6 +1,7 @@
 use anyhow::Result;
 use anyhow::anyhow;
 use app_test_support::McpProcess;
+use codex_app_server_protocol::FuzzyFileSearchContentSessionUpdatedNotification;
 use codex_app_server_protocol::FuzzyFileSearchSessionCompletedNotification;
 use codex_app_server_protocol::FuzzyFileSearchSessionUpdatedNotification;
 use codex_app_server_protocol::JSONRPCResponse;
@@ -21,6 +22,7 @@ const SHORT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_millis
 const STOP_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_millis(250);
 const SESSION_UPDATED_METHOD: &str = "fuzzyFileSearch/sessionUpdated";
 const SESSION_COMPLETED_METHOD: &str = "fuzzyFileSearch/sessionCompleted";
+const CONTENT_SESSION_UPDATED_METHOD: &str = "fuzzyFileSearch/contentSessionUpdated";
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum FileExpectation {
@@ -98,6 +100,53 @@ async fn wait_for_session_updated(
     >(params)?)
 }
 
+async fn wait_for_content_session_updated(
+    mcp: &mut McpProcess,
+    session_id: &str,
+    query: &str,
+    match_expectation: FileExpectation,
+) -> Result<FuzzyFileSearchContentSessionUpdatedNotification> {
+    let description = format!("content session update for sessionId={session_id}, query={query}");
+    let notification = match timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_matching_notification(&description, |notification| {
+            if notification.method != CONTENT_SESSION_UPDATED_METHOD {
+                return false;
+            }
+            let Some(params) = notification.params.as_ref() else {
+                return false;
+            };
+            let Ok(payload) = serde_json::from_value::<
+                FuzzyFileSearchContentSessionUpdatedNotification,
+            >(params.clone()) else {
+                return false;
+            };
+            let matches_match = match match_expectation {
+                FileExpectation::Any => true,
+                FileExpectation::Empty => payload.matches.is_empty(),
+                FileExpectation::NonEmpty => !payload.matches.is_empty(),
+            };
+            payload.session_id == session_id && payload.query == query && matches_match
+        }),
+    )
+    .await
+    {
+        Ok(result) => result?,
+        Err(_) => {
+            anyhow::bail!(
+                "timed out waiting for {description}; buffered notifications={:?}",
+                mcp.pending_notification_methods()
+            )
+        }
+    };
+    let params = notification
+        .params
+        .ok_or_else(|| anyhow!("missing notification params"))?;
+    Ok(serde_json::from_value::<
+        FuzzyFileSearchContentSessionUpdatedNotification,
+    >(params)?)
+}
+
 async fn wait_for_session_completed(
     mcp: &mut McpProcess,
     session_id: &str,
@@ -609,3 +658,103 @@ async fn test_fuzzy_file_search_query_cleared_sends_blank_snapshot() -> Result<(
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fuzzy_file_search_content_session_streams_matches() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let root = TempDir::new()?;
+    std::fs::write(root.path().join("alpha.txt"), "first\nneedle here\n")?;
+    std::fs::write(root.path().join("beta.txt"), "nothing to see\n")?;
+    let mut mcp = initialized_mcp(&codex_home).await?;
+
+    let root_path = root.path().to_string_lossy().to_string();
+    let session_id = "content-session";
+
+    mcp.start_fuzzy_file_search_content_session(session_id, vec![root_path.clone()], false)
+        .await?;
+    mcp.update_fuzzy_file_search_session(session_id, "needle")
+        .await?;
+
+    let payload =
+        wait_for_content_session_updated(&mut mcp, session_id, "needle", FileExpectation::NonEmpty)
+            .await?;
+    assert_eq!(payload.error, None);
+    assert_eq!(payload.matches.len(), 1);
+    let content_match = &payload.matches[0];
+    assert_eq!(content_match.root, root_path);
+    assert_eq!(content_match.path, "alpha.txt");
+    assert_eq!(content_match.line_number, 2);
+    assert_eq!(content_match.column, 1);
+    assert_eq!(content_match.line, "needle here");
+    let completed = wait_for_session_completed(&mut mcp, session_id).await?;
+    assert_eq!(completed.session_id, session_id);
+
+    mcp.stop_fuzzy_file_search_session(session_id).await?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fuzzy_file_search_content_session_reports_invalid_regex() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let root = TempDir::new()?;
+    std::fs::write(root.path().join("alpha.txt"), "contents\n")?;
+    let mut mcp = initialized_mcp(&codex_home).await?;
+
+    let root_path = root.path().to_string_lossy().to_string();
+    let session_id = "content-session-regex";
+
+    mcp.start_fuzzy_file_search_content_session(session_id, vec![root_path], true)
+        .await?;
+    mcp.update_fuzzy_file_search_session(session_id, "(")
+        .await?;
+
+    let payload =
+        wait_for_content_session_updated(&mut mcp, session_id, "(", FileExpectation::Any).await?;
+    assert!(payload.matches.is_empty());
+    assert!(payload.error.is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fuzzy_file_search_session_uses_persistent_index() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "danger-full-access"
+
+[features]
+shell_snapshot = false
+file_search_index = true
+"#,
+    )?;
+    let root = TempDir::new()?;
+    std::fs::write(root.path().join("alpha.txt"), "contents")?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let root_path = root.path().to_string_lossy().to_string();
+    let session_id = "session-indexed";
+    mcp.start_fuzzy_file_search_session(session_id, vec![root_path.clone()])
+        .await?;
+    mcp.update_fuzzy_file_search_session(session_id, "alp")
+        .await?;
+
+    let payload =
+        wait_for_session_updated(&mut mcp, session_id, "alp", FileExpectation::NonEmpty).await?;
+    assert_eq!(payload.files.len(), 1);
+    assert_eq!(payload.files[0].root, root_path);
+    assert_eq!(payload.files[0].path, "alpha.txt");
+
+    let index_files = std::fs::read_dir(codex_home.path().join("file_search_index"))?
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "idx"))
+        .count();
+    assert_eq!(index_files, 1);
+
+    Ok(())
+}
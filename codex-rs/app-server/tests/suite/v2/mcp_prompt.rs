@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use app_test_support::write_mock_responses_config_toml;
+use axum::Router;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::McpServerPromptGetParams;
+use codex_app_server_protocol::McpServerPromptGetResponse;
+use codex_app_server_protocol::McpServerPromptListParams;
+use codex_app_server_protocol::McpServerPromptListResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use core_test_support::responses;
+use pretty_assertions::assert_eq;
+use rmcp::handler::server::ServerHandler;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
+use rmcp::model::ListPromptsResult;
+use rmcp::model::ServerCapabilities;
+use rmcp::model::ServerInfo;
+use rmcp::service::RequestContext;
+use rmcp::service::RoleServer;
+use rmcp::transport::StreamableHttpServerConfig;
+use rmcp::transport::StreamableHttpService;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(10);
+const TEST_SERVER_NAME: &str = "prompt_server";
+const TEST_PROMPT_NAME: &str = "write_migration";
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn mcp_server_prompts_can_be_listed_and_rendered() -> Result<()> {
+    let responses_server = responses::start_mock_server().await;
+    let (mcp_server_url, mcp_server_handle) = start_mcp_server().await?;
+    let codex_home = TempDir::new()?;
+    write_mock_responses_config_toml(
+        codex_home.path(),
+        &responses_server.uri(),
+        &BTreeMap::new(),
+        /*auto_compact_limit*/ 1024,
+        /*requires_openai_auth*/ None,
+        "mock_provider",
+        "compact",
+    )?;
+
+    let config_path = codex_home.path().join("config.toml");
+    let mut config_toml = std::fs::read_to_string(&config_path)?;
+    config_toml.push_str(&format!(
+        r#"
+[mcp_servers.{TEST_SERVER_NAME}]
+url = "{mcp_server_url}/mcp"
+"#
+    ));
+    std::fs::write(config_path, config_toml)?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let thread_start_id = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let thread_start_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(thread_start_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response(thread_start_resp)?;
+
+    let list_request_id = mcp
+        .send_mcp_server_prompt_list_request(McpServerPromptListParams {
+            thread_id: thread.id.clone(),
+        })
+        .await?;
+    let list_response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(list_request_id)),
+    )
+    .await??;
+    let response: McpServerPromptListResponse = to_response(list_response)?;
+    assert_eq!(
+        serde_json::to_value(response)?,
+        json!({
+            "data": [
+                {
+                    "server": TEST_SERVER_NAME,
+                    "prompts": [
+                        {
+                            "name": TEST_PROMPT_NAME,
+                            "description": "Draft a database migration.",
+                            "arguments": [
+                                {
+                                    "name": "table",
+                                    "required": true
+                                }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        })
+    );
+
+    let get_request_id = mcp
+        .send_mcp_server_prompt_get_request(McpServerPromptGetParams {
+            thread_id: thread.id,
+            server: TEST_SERVER_NAME.to_string(),
+            name: TEST_PROMPT_NAME.to_string(),
+            arguments: Some(HashMap::from([(
+                "table".to_string(),
+                "accounts".to_string(),
+            )])),
+        })
+        .await?;
+    let get_response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(get_request_id)),
+    )
+    .await??;
+    let response: McpServerPromptGetResponse = to_response(get_response)?;
+    assert_eq!(
+        serde_json::to_value(response)?,
+        json!({
+            "description": "Migration prompt",
+            "messages": [
+                {
+                    "role": "user",
+                    "content": {
+                        "type": "text",
+                        "text": "Write a migration for the accounts table."
+                    }
+                }
+            ]
+        })
+    );
+
+    mcp_server_handle.abort();
+    let _ = mcp_server_handle.await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mcp_server_prompt_list_returns_error_for_unknown_thread() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_mcp_server_prompt_list_request(McpServerPromptListParams {
+            thread_id: "00000000-0000-4000-8000-000000000000".to_string(),
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert!(
+        error.error.message.contains("thread not found"),
+        "expected thread-not-found error, got: {error:?}"
+    );
+
+    Ok(())
+}
+
+#[derive(Clone, Default)]
+struct PromptMcpServer;
+
+impl ServerHandler for PromptMcpServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_prompts().build(),
+            ..ServerInfo::default()
+        }
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<rmcp::model::PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, rmcp::ErrorData> {
+        serde_json::from_value(json!({
+            "prompts": [
+                {
+                    "name": TEST_PROMPT_NAME,
+                    "description": "Draft a database migration.",
+                    "arguments": [
+                        {
+                            "name": "table",
+                            "required": true
+                        }
+                    ]
+                }
+            ]
+        }))
+        .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, rmcp::ErrorData> {
+        assert_eq!(request.name, TEST_PROMPT_NAME);
+        let table = request
+            .arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get("table"))
+            .and_then(|value| value.as_str())
+            .unwrap_or_default();
+
+        serde_json::from_value(json!({
+            "description": "Migration prompt",
+            "messages": [
+                {
+                    "role": "user",
+                    "content": {
+                        "type": "text",
+                        "text": format!("Write a migration for the {table} table.")
+                    }
+                }
+            ]
+        }))
+        .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
+}
+
+async fn start_mcp_server() -> Result<(String, JoinHandle<()>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let mcp_service = StreamableHttpService::new(
+        || Ok(PromptMcpServer),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+    let router = Router::new().nest_service("/mcp", mcp_service);
+
+    let handle = tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    Ok((format!("http://{addr}"), handle))
+}
@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use anyhow::bail;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use app_test_support::write_mock_responses_config_toml;
+use axum::Router;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::McpServerResourceSubscribeParams;
+use codex_app_server_protocol::McpServerResourceSubscribeResponse;
+use codex_app_server_protocol::McpServerResourceUnsubscribeParams;
+use codex_app_server_protocol::McpServerResourceUnsubscribeResponse;
+use codex_app_server_protocol::McpServerResourceUpdatedNotification;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ServerNotification;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use core_test_support::responses;
+use pretty_assertions::assert_eq;
+use rmcp::handler::server::ServerHandler;
+use rmcp::model::ReadResourceRequestParams;
+use rmcp::model::ReadResourceResult;
+use rmcp::model::ResourceContents;
+use rmcp::model::ResourceUpdatedNotificationParam;
+use rmcp::model::ServerCapabilities;
+use rmcp::model::ServerInfo;
+use rmcp::model::SubscribeRequestParams;
+use rmcp::model::UnsubscribeRequestParams;
+use rmcp::service::Peer;
+use rmcp::service::RequestContext;
+use rmcp::service::RoleServer;
+use rmcp::transport::StreamableHttpServerConfig;
+use rmcp::transport::StreamableHttpService;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(10);
+const TEST_SERVER_NAME: &str = "status_server";
+const TEST_RESOURCE_URI: &str = "status://build";
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn mcp_server_resource_updates_are_reported_for_subscribed_threads() -> Result<()> {
+    let responses_server = responses::start_mock_server().await;
+    let mcp_server = StatusMcpServer::default();
+    let (mcp_server_url, mcp_server_handle) = start_mcp_server(mcp_server.clone()).await?;
+    let codex_home = TempDir::new()?;
+    write_mock_responses_config_toml(
+        codex_home.path(),
+        &responses_server.uri(),
+        &BTreeMap::new(),
+        /*auto_compact_limit*/ 1024,
+        /*requires_openai_auth*/ None,
+        "mock_provider",
+        "compact",
+    )?;
+
+    let config_path = codex_home.path().join("config.toml");
+    let mut config_toml = std::fs::read_to_string(&config_path)?;
+    config_toml.push_str(&format!(
+        r#"
+[mcp_servers.{TEST_SERVER_NAME}]
+url = "{mcp_server_url}/mcp"
+"#
+    ));
+    std::fs::write(config_path, config_toml)?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let thread_start_id = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let thread_start_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(thread_start_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response(thread_start_resp)?;
+
+    let subscribe_request_id = mcp
+        .send_mcp_server_resource_subscribe_request(McpServerResourceSubscribeParams {
+            thread_id: thread.id.clone(),
+            server: TEST_SERVER_NAME.to_string(),
+            uri: TEST_RESOURCE_URI.to_string(),
+        })
+        .await?;
+    let subscribe_response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(subscribe_request_id)),
+    )
+    .await??;
+    let _: McpServerResourceSubscribeResponse = to_response(subscribe_response)?;
+
+    mcp_server.publish("build: passing").await?;
+    let notification = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("mcpServer/resource/updated"),
+    )
+    .await??;
+    let parsed: ServerNotification = notification.try_into()?;
+    let ServerNotification::McpServerResourceUpdated(payload) = parsed else {
+        bail!("unexpected notification: {parsed:?}");
+    };
+    assert_eq!(
+        payload,
+        McpServerResourceUpdatedNotification {
+            thread_id: thread.id.clone(),
+            server: TEST_SERVER_NAME.to_string(),
+            uri: TEST_RESOURCE_URI.to_string(),
+        }
+    );
+
+    let unsubscribe_request_id = mcp
+        .send_mcp_server_resource_unsubscribe_request(McpServerResourceUnsubscribeParams {
+            thread_id: thread.id,
+            server: TEST_SERVER_NAME.to_string(),
+            uri: TEST_RESOURCE_URI.to_string(),
+        })
+        .await?;
+    let unsubscribe_response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(unsubscribe_request_id)),
+    )
+    .await??;
+    let _: McpServerResourceUnsubscribeResponse = to_response(unsubscribe_response)?;
+    assert!(mcp_server.subscriber().is_none());
+
+    mcp_server_handle.abort();
+    let _ = mcp_server_handle.await;
+
+    Ok(())
+}
+
+#[derive(Clone)]
+struct StatusMcpServer {
+    status: Arc<StdMutex<String>>,
+    subscriber: Arc<StdMutex<Option<Peer<RoleServer>>>>,
+}
+
+impl Default for StatusMcpServer {
+    fn default() -> Self {
+        Self {
+            status: Arc::new(StdMutex::new("build: running".to_string())),
+            subscriber: Arc::new(StdMutex::new(None)),
+        }
+    }
+}
+
+impl StatusMcpServer {
+    fn subscriber(&self) -> Option<Peer<RoleServer>> {
+        self.subscriber
+            .lock()
+            .expect("subscriber lock should not be poisoned")
+            .clone()
+    }
+
+    async fn publish(&self, status: &str) -> Result<()> {
+        *self
+            .status
+            .lock()
+            .expect("status lock should not be poisoned") = status.to_string();
+        let Some(peer) = self.subscriber() else {
+            bail!("no client subscribed to {TEST_RESOURCE_URI}");
+        };
+        peer.notify_resource_updated(ResourceUpdatedNotificationParam {
+            uri: TEST_RESOURCE_URI.to_string(),
+        })
+        .await?;
+        Ok(())
+    }
+}
+
+impl ServerHandler for StatusMcpServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .build(),
+            ..ServerInfo::default()
+        }
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, rmcp::ErrorData> {
+        let text = self
+            .status
+            .lock()
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))?
+            .clone();
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: request.uri,
+                mime_type: Some("text/plain".to_string()),
+                text,
+                meta: None,
+            }],
+        })
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), rmcp::ErrorData> {
+        assert_eq!(request.uri, TEST_RESOURCE_URI);
+        *self
+            .subscriber
+            .lock()
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))? =
+            Some(context.peer);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), rmcp::ErrorData> {
+        assert_eq!(request.uri, TEST_RESOURCE_URI);
+        *self
+            .subscriber
+            .lock()
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))? = None;
+        Ok(())
+    }
+}
+
+async fn start_mcp_server(server: StatusMcpServer) -> Result<(String, JoinHandle<()>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let mcp_service = StreamableHttpService::new(
+        move || Ok(server.clone()),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+    let router = Router::new().nest_service("/mcp", mcp_service);
+
+    let handle = tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    Ok((format!("http://{addr}"), handle))
+}
@@ -20,7 +20,9 @@ mod initialize;
 mod marketplace_add;
 mod marketplace_remove;
 mod marketplace_upgrade;
+mod mcp_prompt;
 mod mcp_resource;
+mod mcp_resource_subscription;
 mod mcp_server_elicitation;
 mod mcp_server_status;
 mod mcp_tool;
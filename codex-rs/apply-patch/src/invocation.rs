@@ -17,10 +17,11 @@ use crate::ApplyPatchFileChange;
 use crate::ApplyPatchFileUpdate;
 use crate::IoError;
 use crate::MaybeApplyPatchVerified;
+use crate::MergeBases;
 use crate::parser::Hunk;
 use crate::parser::ParseError;
 use crate::parser::parse_patch;
-use crate::unified_diff_from_chunks;
+use crate::unified_diff_from_chunks_with_merge_bases;
 use std::str::Utf8Error;
 use tree_sitter::LanguageError;
 
@@ -129,6 +130,25 @@ pub fn maybe_parse_apply_patch(argv: &[String]) -> MaybeApplyPatch {
     }
 }
 
+/// Absolute paths of the files updated by the `apply_patch` invocation in `argv`, which are the
+/// only files whose merge bases [`maybe_parse_apply_patch_verified_with_merge_bases`] reads.
+pub fn apply_patch_updated_paths(argv: &[String], cwd: &AbsolutePathBuf) -> Vec<AbsolutePathBuf> {
+    let MaybeApplyPatch::Body(ApplyPatchArgs { hunks, workdir, .. }) =
+        maybe_parse_apply_patch(argv)
+    else {
+        return Vec::new();
+    };
+    let effective_cwd = workdir
+        .as_ref()
+        .map(|dir| cwd.join(Path::new(dir)))
+        .unwrap_or_else(|| cwd.clone());
+    hunks
+        .iter()
+        .filter(|hunk| matches!(hunk, Hunk::UpdateFile { .. }))
+        .map(|hunk| hunk.resolve_path(&effective_cwd))
+        .collect()
+}
+
 /// cwd must be an absolute path so that we can resolve relative paths in the
 /// patch.
 pub async fn maybe_parse_apply_patch_verified(
@@ -136,6 +156,26 @@ pub async fn maybe_parse_apply_patch_verified(
     cwd: &AbsolutePathBuf,
     fs: &dyn ExecutorFileSystem,
     sandbox: Option<&codex_exec_server::FileSystemSandboxContext>,
+) -> MaybeApplyPatchVerified {
+    maybe_parse_apply_patch_verified_with_merge_bases(
+        argv,
+        cwd,
+        fs,
+        sandbox,
+        &MergeBases::default(),
+    )
+    .await
+}
+
+/// Like [`maybe_parse_apply_patch_verified`], but updates whose context has drifted from a file
+/// recorded in `merge_bases` are verified against a three-way merge, matching what
+/// [`crate::apply_patch_with_merge_bases`] will write.
+pub async fn maybe_parse_apply_patch_verified_with_merge_bases(
+    argv: &[String],
+    cwd: &AbsolutePathBuf,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&codex_exec_server::FileSystemSandboxContext>,
+    merge_bases: &MergeBases,
 ) -> MaybeApplyPatchVerified {
     // Detect a raw patch body passed directly as the command or as the body of a shell
     // script. In these cases, report an explicit error rather than applying the patch.
@@ -167,9 +207,78 @@ pub async fn maybe_parse_apply_patch_verified(
                     Hunk::AddFile { contents, .. } => {
                         changes.insert(
                             path.into_path_buf(),
-                            ApplyPatchFileChange::Add { content: contents },
+                            ApplyPatchFileChange::Add {
+                                content: contents,
+                                mode: None,
+                            },
                         );
                     }
+                    Hunk::AddBinaryFile { contents, .. } => {
+                        changes.insert(
+                            path.into_path_buf(),
+                            ApplyPatchFileChange::AddBinary {
+                                content: contents,
+                                mode: None,
+                            },
+                        );
+                    }
+                    Hunk::AddSymlink { target, .. } => {
+                        changes.insert(
+                            path.into_path_buf(),
+                            ApplyPatchFileChange::AddSymlink { target },
+                        );
+                    }
+                    Hunk::SetMode { mode, .. } => {
+                        // Fold the mode into the add or update of the same path; after a move
+                        // it names the destination.
+                        let key = path.to_path_buf();
+                        let existing = if changes.contains_key(&key) {
+                            changes.get_mut(&key)
+                        } else {
+                            changes.values_mut().find(|change| {
+                                matches!(
+                                    change,
+                                    ApplyPatchFileChange::Update {
+                                        move_path: Some(dest),
+                                        ..
+                                    } if *dest == key
+                                )
+                            })
+                        };
+                        match existing {
+                            Some(
+                                ApplyPatchFileChange::Add { mode: existing, .. }
+                                | ApplyPatchFileChange::AddBinary { mode: existing, .. }
+                                | ApplyPatchFileChange::Update { mode: existing, .. },
+                            ) => *existing = Some(mode),
+                            Some(_) => {}
+                            None => {
+                                let content = match fs.read_file_text(&path, sandbox).await {
+                                    Ok(content) => content,
+                                    Err(e) => {
+                                        return MaybeApplyPatchVerified::CorrectnessError(
+                                            ApplyPatchError::IoError(IoError {
+                                                context: format!(
+                                                    "Failed to read {}",
+                                                    path.display()
+                                                ),
+                                                source: e,
+                                            }),
+                                        );
+                                    }
+                                };
+                                changes.insert(
+                                    key,
+                                    ApplyPatchFileChange::Update {
+                                        unified_diff: String::new(),
+                                        move_path: None,
+                                        new_content: content,
+                                        mode: Some(mode),
+                                    },
+                                );
+                            }
+                        }
+                    }
                     Hunk::DeleteFile { .. } => {
                         let content = match fs.read_file_text(&path, sandbox).await {
                             Ok(content) => content,
@@ -193,7 +302,15 @@ pub async fn maybe_parse_apply_patch_verified(
                         let ApplyPatchFileUpdate {
                             unified_diff,
                             content: contents,
-                        } = match unified_diff_from_chunks(&path, &chunks, fs, sandbox).await {
+                        } = match unified_diff_from_chunks_with_merge_bases(
+                            &path,
+                            &chunks,
+                            fs,
+                            sandbox,
+                            merge_bases,
+                        )
+                        .await
+                        {
                             Ok(diff) => diff,
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(e);
@@ -205,6 +322,7 @@ pub async fn maybe_parse_apply_patch_verified(
                                 unified_diff,
                                 move_path: move_path.map(|p| effective_cwd.join(p).into_path_buf()),
                                 new_content: contents,
+                                mode: None,
                             },
                         );
                     }
@@ -794,6 +912,7 @@ PATCH"#,
                         .to_string(),
                         move_path: None,
                         new_content: "updated session directory content\n".to_string(),
+                        mode: None,
                     },
                 )]),
                 patch: argv[1].clone(),
@@ -854,4 +973,69 @@ PATCH"#,
             other => panic!("expected update change, got {other:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn test_apply_patch_folds_set_mode_into_changes() {
+        let session_dir = tempdir().unwrap();
+        fs::write(session_dir.path().join("tool.sh"), "echo tool\n").unwrap();
+        fs::write(session_dir.path().join("old.sh"), "old\n").unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Add File: run.sh
+*** Set Mode: 755
++echo run
+*** Update File: old.sh
+*** Move to: new.sh
+*** Set Mode: 700
+@@
+-old
++new
+*** Update File: tool.sh
+*** Set Mode: 755"#,
+        );
+        let argv = vec!["apply_patch".to_string(), patch.clone()];
+
+        let result = maybe_parse_apply_patch_verified(
+            &argv,
+            &AbsolutePathBuf::from_absolute_path(session_dir.path()).unwrap(),
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            MaybeApplyPatchVerified::Body(ApplyPatchAction {
+                changes: HashMap::from([
+                    (
+                        session_dir.path().join("run.sh"),
+                        ApplyPatchFileChange::Add {
+                            content: "echo run\n".to_string(),
+                            mode: Some(0o755),
+                        },
+                    ),
+                    (
+                        session_dir.path().join("old.sh"),
+                        ApplyPatchFileChange::Update {
+                            unified_diff: "@@ -1 +1 @@\n-old\n+new\n".to_string(),
+                            move_path: Some(session_dir.path().join("new.sh")),
+                            new_content: "new\n".to_string(),
+                            mode: Some(0o700),
+                        },
+                    ),
+                    (
+                        session_dir.path().join("tool.sh"),
+                        ApplyPatchFileChange::Update {
+                            unified_diff: String::new(),
+                            move_path: None,
+                            new_content: "echo tool\n".to_string(),
+                            mode: Some(0o755),
+                        },
+                    ),
+                ]),
+                patch,
+                cwd: AbsolutePathBuf::from_absolute_path(session_dir.path()).unwrap(),
+            })
+        );
+    }
 }
@@ -1,7 +1,9 @@
 mod invocation;
+mod merge;
 mod parser;
 mod seek_sequence;
 mod standalone_executable;
+mod transaction;
 
 use std::collections::HashMap;
 use std::io;
@@ -10,10 +12,8 @@ use std::path::PathBuf;
 
 use anyhow::Context;
 use anyhow::Result;
-use codex_exec_server::CreateDirectoryOptions;
 use codex_exec_server::ExecutorFileSystem;
 use codex_exec_server::FileSystemSandboxContext;
-use codex_exec_server::RemoveOptions;
 use codex_utils_absolute_path::AbsolutePathBuf;
 pub use parser::Hunk;
 pub use parser::ParseError;
@@ -23,11 +23,18 @@ pub use parser::parse_patch;
 pub use parser::parse_patch_streaming;
 use similar::TextDiff;
 use thiserror::Error;
+use tokio_util::sync::CancellationToken;
 
+pub use invocation::apply_patch_updated_paths;
 pub use invocation::maybe_parse_apply_patch_verified;
+pub use invocation::maybe_parse_apply_patch_verified_with_merge_bases;
+pub use merge::MergeBases;
 pub use standalone_executable::main;
+pub use standalone_executable::run_patch;
 
 use crate::invocation::ExtractHeredocError;
+use crate::transaction::RollbackFailed;
+use crate::transaction::StagedChanges;
 
 /// Detailed instructions for gpt-4.1 on how to use the `apply_patch` tool.
 pub const APPLY_PATCH_TOOL_INSTRUCTIONS: &str = include_str!("../apply_patch_tool_instructions.md");
@@ -55,6 +62,14 @@ pub enum ApplyPatchError {
         "patch detected without explicit call to apply_patch. Rerun as [\"apply_patch\", \"<patch>\"]"
     )]
     ImplicitInvocation,
+    /// The patch no longer applied cleanly, and merging it with the file's concurrent changes
+    /// conflicted. The file was written with conflict markers around each conflicting region, and
+    /// no other file of the patch was changed.
+    #[error(
+        "Merge conflict in {}: {conflicts} conflicting region(s) marked with <<<<<<< / >>>>>>>",
+        path.display()
+    )]
+    MergeConflict { path: PathBuf, conflicts: usize },
 }
 
 impl From<std::io::Error> for ApplyPatchError {
@@ -102,6 +117,15 @@ pub struct ApplyPatchArgs {
 pub enum ApplyPatchFileChange {
     Add {
         content: String,
+        /// Permission bits requested with `*** Set Mode`, if any.
+        mode: Option<u32>,
+    },
+    AddBinary {
+        content: Vec<u8>,
+        mode: Option<u32>,
+    },
+    AddSymlink {
+        target: PathBuf,
     },
     Delete {
         content: String,
@@ -111,6 +135,9 @@ pub enum ApplyPatchFileChange {
         move_path: Option<PathBuf>,
         /// new_content that will result after the unified_diff is applied.
         new_content: String,
+        /// Permission bits requested with `*** Set Mode`, if any. A mode-only
+        /// update has an empty `unified_diff`.
+        mode: Option<u32>,
     },
 }
 
@@ -169,7 +196,39 @@ impl ApplyPatchAction {
 + {content}
 *** End Patch"#,
         );
-        let changes = HashMap::from([(path.to_path_buf(), ApplyPatchFileChange::Add { content })]);
+        let changes = HashMap::from([(
+            path.to_path_buf(),
+            ApplyPatchFileChange::Add {
+                content,
+                mode: None,
+            },
+        )]);
+        #[expect(clippy::expect_used)]
+        Self {
+            changes,
+            cwd: path.parent().expect("path should have parent"),
+            patch,
+        }
+    }
+
+    /// Should be used exclusively for testing.
+    pub fn new_add_symlink_for_test(path: &AbsolutePathBuf, target: PathBuf) -> Self {
+        #[expect(clippy::expect_used)]
+        let filename = path
+            .file_name()
+            .expect("path should not be empty")
+            .to_string_lossy();
+        let patch = format!(
+            r#"*** Begin Patch
+*** Add Symlink: {filename}
+*** Link to: {}
+*** End Patch"#,
+            target.display()
+        );
+        let changes = HashMap::from([(
+            path.to_path_buf(),
+            ApplyPatchFileChange::AddSymlink { target },
+        )]);
         #[expect(clippy::expect_used)]
         Self {
             changes,
@@ -187,6 +246,33 @@ pub async fn apply_patch(
     stderr: &mut impl std::io::Write,
     fs: &dyn ExecutorFileSystem,
     sandbox: Option<&FileSystemSandboxContext>,
+) -> Result<(), ApplyPatchError> {
+    apply_patch_with_merge_bases(
+        patch,
+        cwd,
+        stdout,
+        stderr,
+        fs,
+        sandbox,
+        &MergeBases::default(),
+        &CancellationToken::new(),
+    )
+    .await
+}
+
+/// Like [`apply_patch`], but an update whose context no longer matches a file recorded in
+/// `merge_bases` is applied to the recorded contents and three-way merged into the file.
+/// Cancelling `interrupt` stops the patch at its next write and rolls back what it changed.
+#[allow(clippy::too_many_arguments)]
+pub async fn apply_patch_with_merge_bases(
+    patch: &str,
+    cwd: &AbsolutePathBuf,
+    stdout: &mut impl std::io::Write,
+    stderr: &mut impl std::io::Write,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+    merge_bases: &MergeBases,
+    interrupt: &CancellationToken,
 ) -> Result<(), ApplyPatchError> {
     let hunks = match parse_patch(patch) {
         Ok(source) => source.hunks,
@@ -210,7 +296,17 @@ pub async fn apply_patch(
         }
     };
 
-    apply_hunks(&hunks, cwd, stdout, stderr, fs, sandbox).await?;
+    apply_hunks_with_merge_bases(
+        &hunks,
+        cwd,
+        stdout,
+        stderr,
+        fs,
+        sandbox,
+        merge_bases,
+        interrupt,
+    )
+    .await?;
 
     Ok(())
 }
@@ -223,17 +319,60 @@ pub async fn apply_hunks(
     stderr: &mut impl std::io::Write,
     fs: &dyn ExecutorFileSystem,
     sandbox: Option<&FileSystemSandboxContext>,
+) -> Result<(), ApplyPatchError> {
+    apply_hunks_with_merge_bases(
+        hunks,
+        cwd,
+        stdout,
+        stderr,
+        fs,
+        sandbox,
+        &MergeBases::default(),
+        &CancellationToken::new(),
+    )
+    .await
+}
+
+/// Like [`apply_hunks`], with the three-way merge fallback described in
+/// [`apply_patch_with_merge_bases`], stopping early when `interrupt` is cancelled.
+#[allow(clippy::too_many_arguments)]
+pub async fn apply_hunks_with_merge_bases(
+    hunks: &[Hunk],
+    cwd: &AbsolutePathBuf,
+    stdout: &mut impl std::io::Write,
+    stderr: &mut impl std::io::Write,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+    merge_bases: &MergeBases,
+    interrupt: &CancellationToken,
 ) -> Result<(), ApplyPatchError> {
     // Delegate to a helper that applies each hunk to the filesystem.
-    match apply_hunks_to_files(hunks, cwd, fs, sandbox).await {
+    match apply_hunks_to_files(hunks, cwd, fs, sandbox, merge_bases, interrupt).await {
         Ok(affected) => {
             print_summary(&affected, stdout).map_err(ApplyPatchError::from)?;
             Ok(())
         }
         Err(err) => {
-            let msg = err.to_string();
+            let mut msg = err.to_string();
+            // Failed patches are rolled back, except for a merge conflict, which writes its
+            // markers into the one conflicted file, and a rollback that itself failed.
+            if !matches!(
+                err.downcast_ref::<ApplyPatchError>(),
+                Some(ApplyPatchError::MergeConflict { .. })
+            ) && !err.is::<RollbackFailed>()
+                && !hunks.is_empty()
+            {
+                msg.push_str("\nNo files were changed.");
+            }
             writeln!(stderr, "{msg}").map_err(ApplyPatchError::from)?;
-            if let Some(io) = err.downcast_ref::<std::io::Error>() {
+            if let Some(ApplyPatchError::MergeConflict { path, conflicts }) =
+                err.downcast_ref::<ApplyPatchError>()
+            {
+                Err(ApplyPatchError::MergeConflict {
+                    path: path.clone(),
+                    conflicts: *conflicts,
+                })
+            } else if let Some(io) = err.downcast_ref::<std::io::Error>() {
                 Err(ApplyPatchError::from(io))
             } else {
                 Err(ApplyPatchError::IoError(IoError {
@@ -253,147 +392,131 @@ pub struct AffectedPaths {
     pub added: Vec<PathBuf>,
     pub modified: Vec<PathBuf>,
     pub deleted: Vec<PathBuf>,
+    /// Modified paths whose update was three-way merged with concurrent changes.
+    pub merged: Vec<PathBuf>,
 }
 
 /// Apply the hunks to the filesystem, returning which files were added, modified, or deleted.
-/// Returns an error if the patch could not be applied.
+/// Every hunk is staged before anything is written, and a failure while writing rolls back the
+/// files already changed, so an error leaves the filesystem as it was. The one exception is a
+/// merge conflict, which writes the conflicted file and nothing else.
 async fn apply_hunks_to_files(
     hunks: &[Hunk],
     cwd: &AbsolutePathBuf,
     fs: &dyn ExecutorFileSystem,
     sandbox: Option<&FileSystemSandboxContext>,
+    merge_bases: &MergeBases,
+    interrupt: &CancellationToken,
 ) -> anyhow::Result<AffectedPaths> {
     if hunks.is_empty() {
         anyhow::bail!("No files were modified.");
     }
 
+    let mut staged = StagedChanges::default();
     let mut added: Vec<PathBuf> = Vec::new();
     let mut modified: Vec<PathBuf> = Vec::new();
     let mut deleted: Vec<PathBuf> = Vec::new();
+    let mut merged: Vec<PathBuf> = Vec::new();
     for hunk in hunks {
         let affected_path = hunk.path().to_path_buf();
         let path_abs = hunk.resolve_path(cwd);
         match hunk {
             Hunk::AddFile { contents, .. } => {
-                write_file_with_missing_parent_retry(
-                    fs,
-                    &path_abs,
-                    contents.clone().into_bytes(),
-                    sandbox,
-                )
-                .await?;
+                staged.write(path_abs, contents.clone().into_bytes());
+                added.push(affected_path);
+            }
+            Hunk::AddBinaryFile { contents, .. } => {
+                staged.write(path_abs, contents.clone());
                 added.push(affected_path);
             }
+            Hunk::AddSymlink { target, .. } => {
+                staged.symlink(path_abs, target.clone());
+                added.push(affected_path);
+            }
+            Hunk::SetMode { mode, .. } => {
+                staged.set_mode(path_abs, *mode);
+                // The mode usually accompanies an add or update of the same path.
+                if !added.contains(&affected_path) && !modified.contains(&affected_path) {
+                    modified.push(affected_path);
+                }
+            }
             Hunk::DeleteFile { .. } => {
-                let result: io::Result<()> = async {
-                    let metadata = fs.get_metadata(&path_abs, sandbox).await?;
-                    if metadata.is_directory {
-                        return Err(io::Error::new(
-                            io::ErrorKind::InvalidInput,
-                            "path is a directory",
-                        ));
-                    }
-                    fs.remove(
-                        &path_abs,
-                        RemoveOptions {
-                            recursive: false,
-                            force: false,
-                        },
-                        sandbox,
-                    )
+                staged
+                    .check_removable(&path_abs, fs, sandbox)
                     .await
-                }
-                .await;
-                result.with_context(|| format!("Failed to delete file {}", path_abs.display()))?;
+                    .with_context(|| format!("Failed to delete file {}", path_abs.display()))?;
+                staged.remove(path_abs);
                 deleted.push(affected_path);
             }
             Hunk::UpdateFile {
                 move_path, chunks, ..
             } => {
-                let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(&path_abs, chunks, fs, sandbox).await?;
-                if let Some(dest) = move_path {
-                    let dest_abs = AbsolutePathBuf::resolve_path_against_base(dest, cwd);
-                    write_file_with_missing_parent_retry(
-                        fs,
-                        &dest_abs,
-                        new_contents.into_bytes(),
-                        sandbox,
-                    )
-                    .await?;
-                    let result: io::Result<()> = async {
-                        let metadata = fs.get_metadata(&path_abs, sandbox).await?;
-                        if metadata.is_directory {
-                            return Err(io::Error::new(
-                                io::ErrorKind::InvalidInput,
-                                "path is a directory",
-                            ));
+                let original_contents = staged
+                    .read_file_text(&path_abs, fs, sandbox)
+                    .await
+                    .map_err(|err| {
+                        ApplyPatchError::IoError(IoError {
+                            context: format!(
+                                "Failed to read file to update {}",
+                                path_abs.display()
+                            ),
+                            source: err,
+                        })
+                    })?;
+                let new_contents = match apply_chunks_to_contents(
+                    &original_contents,
+                    path_abs.as_path(),
+                    chunks,
+                ) {
+                    Ok(new_contents) => new_contents,
+                    Err(err @ ApplyPatchError::ComputeReplacements(_)) => {
+                        let Some(base) = merge_bases.get(path_abs.as_path()) else {
+                            return Err(err.into());
+                        };
+                        let merge =
+                            merge_with_base(path_abs.as_path(), base, &original_contents, chunks)
+                                // Report the original mismatch when the patch does not even
+                                // apply to the recorded contents.
+                                .map_err(|_| err)?;
+                        if merge.conflicts > 0 {
+                            // Nothing has been committed yet, so the conflicted file is the only
+                            // change this patch makes.
+                            fs.write_file(&path_abs, merge.contents.into_bytes(), sandbox)
+                                .await
+                                .with_context(|| {
+                                    format!("Failed to write file {}", path_abs.display())
+                                })?;
+                            return Err(ApplyPatchError::MergeConflict {
+                                path: path_abs.to_path_buf(),
+                                conflicts: merge.conflicts,
+                            }
+                            .into());
                         }
-                        fs.remove(
-                            &path_abs,
-                            RemoveOptions {
-                                recursive: false,
-                                force: false,
-                            },
-                            sandbox,
-                        )
-                        .await
+                        merged.push(affected_path.clone());
+                        merge.contents
                     }
-                    .await;
-                    result.with_context(|| {
-                        format!("Failed to remove original {}", path_abs.display())
-                    })?;
-                    modified.push(affected_path);
+                    Err(err) => return Err(err.into()),
+                };
+                if let Some(dest) = move_path {
+                    let dest_abs = AbsolutePathBuf::resolve_path_against_base(dest, cwd);
+                    staged.write(dest_abs, new_contents.into_bytes());
+                    staged.remove(path_abs);
                 } else {
-                    fs.write_file(&path_abs, new_contents.into_bytes(), sandbox)
-                        .await
-                        .with_context(|| format!("Failed to write file {}", path_abs.display()))?;
-                    modified.push(affected_path);
+                    staged.write(path_abs, new_contents.into_bytes());
                 }
+                modified.push(affected_path);
             }
         }
     }
+    staged.commit(fs, sandbox, interrupt).await?;
     Ok(AffectedPaths {
         added,
         modified,
         deleted,
+        merged,
     })
 }
 
-async fn write_file_with_missing_parent_retry(
-    fs: &dyn ExecutorFileSystem,
-    path_abs: &AbsolutePathBuf,
-    contents: Vec<u8>,
-    sandbox: Option<&FileSystemSandboxContext>,
-) -> anyhow::Result<()> {
-    match fs.write_file(path_abs, contents.clone(), sandbox).await {
-        Ok(()) => Ok(()),
-        Err(err) if err.kind() == io::ErrorKind::NotFound => {
-            if let Some(parent_abs) = path_abs.parent() {
-                fs.create_directory(
-                    &parent_abs,
-                    CreateDirectoryOptions { recursive: true },
-                    sandbox,
-                )
-                .await
-                .with_context(|| {
-                    format!(
-                        "Failed to create parent directories for {}",
-                        path_abs.display()
-                    )
-                })?;
-            }
-            fs.write_file(path_abs, contents, sandbox)
-                .await
-                .with_context(|| format!("Failed to write file {}", path_abs.display()))?;
-            Ok(())
-        }
-        Err(err) => {
-            Err(err).with_context(|| format!("Failed to write file {}", path_abs.display()))
-        }
-    }
-}
-
 struct AppliedPatch {
     original_contents: String,
     new_contents: String,
@@ -414,6 +537,34 @@ async fn derive_new_contents_from_chunks(
         })
     })?;
 
+    let new_contents = apply_chunks_to_contents(&original_contents, path_abs.as_path(), chunks)?;
+    Ok(AppliedPatch {
+        original_contents,
+        new_contents,
+    })
+}
+
+/// Applies `chunks` to the last-read `base` contents of `path` and merges the result with the
+/// file's `current_contents`.
+fn merge_with_base(
+    path: &Path,
+    base: &str,
+    current_contents: &str,
+    chunks: &[UpdateFileChunk],
+) -> std::result::Result<merge::MergeResult, ApplyPatchError> {
+    let patched_base = apply_chunks_to_contents(base, path, chunks)?;
+    Ok(merge::merge_three_way(
+        base,
+        current_contents,
+        &patched_base,
+    ))
+}
+
+fn apply_chunks_to_contents(
+    original_contents: &str,
+    path: &Path,
+    chunks: &[UpdateFileChunk],
+) -> std::result::Result<String, ApplyPatchError> {
     let mut original_lines: Vec<String> = original_contents.split('\n').map(String::from).collect();
 
     // Drop the trailing empty element that results from the final newline so
@@ -422,17 +573,13 @@ async fn derive_new_contents_from_chunks(
         original_lines.pop();
     }
 
-    let replacements = compute_replacements(&original_lines, path_abs.as_path(), chunks)?;
+    let replacements = compute_replacements(&original_lines, path, chunks)?;
     let new_lines = apply_replacements(original_lines, &replacements);
     let mut new_lines = new_lines;
     if !new_lines.last().is_some_and(String::is_empty) {
         new_lines.push(String::new());
     }
-    let new_contents = new_lines.join("\n");
-    Ok(AppliedPatch {
-        original_contents,
-        new_contents,
-    })
+    Ok(new_lines.join("\n"))
 }
 
 /// Compute a list of replacements needed to transform `original_lines` into the
@@ -591,6 +738,49 @@ pub async fn unified_diff_from_chunks_with_context(
     })
 }
 
+/// Like [`unified_diff_from_chunks`], but when the chunks no longer match the file and
+/// `merge_bases` has the contents the model last read, the update is computed with the same
+/// three-way merge [`apply_patch_with_merge_bases`] performs. Conflicting regions show up with
+/// conflict markers in the new contents.
+pub async fn unified_diff_from_chunks_with_merge_bases(
+    path_abs: &AbsolutePathBuf,
+    chunks: &[UpdateFileChunk],
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+    merge_bases: &MergeBases,
+) -> std::result::Result<ApplyPatchFileUpdate, ApplyPatchError> {
+    match unified_diff_from_chunks(path_abs, chunks, fs, sandbox).await {
+        Err(err @ ApplyPatchError::ComputeReplacements(_)) => {
+            let Some(base) = merge_bases.get(path_abs.as_path()) else {
+                return Err(err);
+            };
+            let current_contents =
+                fs.read_file_text(path_abs, sandbox)
+                    .await
+                    .map_err(|source| {
+                        ApplyPatchError::IoError(IoError {
+                            context: format!(
+                                "Failed to read file to update {}",
+                                path_abs.display()
+                            ),
+                            source,
+                        })
+                    })?;
+            let merge = merge_with_base(path_abs.as_path(), base, &current_contents, chunks)
+                .map_err(|_| err)?;
+            let unified_diff = TextDiff::from_lines(&current_contents, &merge.contents)
+                .unified_diff()
+                .context_radius(1)
+                .to_string();
+            Ok(ApplyPatchFileUpdate {
+                unified_diff,
+                content: merge.contents,
+            })
+        }
+        result => result,
+    }
+}
+
 /// Print the summary of changes in git-style format.
 /// Write a summary of changes to the given writer.
 pub fn print_summary(
@@ -607,6 +797,15 @@ pub fn print_summary(
     for path in &affected.deleted {
         writeln!(out, "D {}", path.display())?;
     }
+    if !affected.merged.is_empty() {
+        writeln!(
+            out,
+            "The patch no longer matched these files exactly and was merged with their current contents:"
+        )?;
+        for path in &affected.merged {
+            writeln!(out, "  {}", path.display())?;
+        }
+    }
     Ok(())
 }
 
@@ -732,6 +931,188 @@ mod tests {
         );
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_apply_patch_binary_symlink_and_mode() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let cwd = dir.path().abs();
+        fs::write(dir.path().join("tool.sh"), "echo tool\n").unwrap();
+        let patch = wrap_patch(
+            r#"*** Add Binary File: assets/data.bin
++AAEC/w==
+*** Add Symlink: links/current
+*** Link to: ../assets/data.bin
+*** Add File: run.sh
+*** Set Mode: 755
++echo run
+*** Update File: tool.sh
+*** Set Mode: 700"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        apply_patch(
+            &patch,
+            &cwd,
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await
+        .unwrap();
+
+        let mode = |name: &str| {
+            fs::metadata(dir.path().join(name))
+                .unwrap()
+                .permissions()
+                .mode()
+                & 0o7777
+        };
+        assert_eq!(
+            fs::read(dir.path().join("assets/data.bin")).unwrap(),
+            vec![0, 1, 2, 255]
+        );
+        assert_eq!(
+            fs::read_link(dir.path().join("links/current")).unwrap(),
+            PathBuf::from("../assets/data.bin")
+        );
+        assert_eq!(
+            fs::read(dir.path().join("links/current")).unwrap(),
+            vec![0, 1, 2, 255]
+        );
+        assert_eq!(mode("run.sh"), 0o755);
+        assert_eq!(mode("tool.sh"), 0o700);
+        assert_eq!(String::from_utf8(stderr).unwrap(), "");
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            "Success. Updated the following files:\nA assets/data.bin\nA links/current\nA run.sh\nM tool.sh\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_merges_with_concurrent_changes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("merge.txt");
+        let path_abs = path.as_path().abs();
+        let mut merge_bases = MergeBases::default();
+        merge_bases.record(&path_abs, "a\nb\nc\nd\ne\n".to_string());
+        // A formatter touched the first line after the model read the file.
+        fs::write(&path, "A\nb\nc\nd\ne\n").unwrap();
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+ a
+ b
+-c
++C2
+ d
+ e"#,
+            path.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        apply_patch_with_merge_bases(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+            &merge_bases,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "A\nb\nC2\nd\ne\n");
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            format!(
+                "Success. Updated the following files:\nM {path}\nThe patch no longer matched these files exactly and was merged with their current contents:\n  {path}\n",
+                path = path.display()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_writes_conflict_markers_when_merge_conflicts() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("conflict.txt");
+        let path_abs = path.as_path().abs();
+        let mut merge_bases = MergeBases::default();
+        merge_bases.record(&path_abs, "a\nb\nc\n".to_string());
+        fs::write(&path, "a\nx\nc\n").unwrap();
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+ a
+-b
++y
+ c"#,
+            path.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let result = apply_patch_with_merge_bases(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+            &merge_bases,
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            Err(ApplyPatchError::MergeConflict {
+                path: path.clone(),
+                conflicts: 1,
+            })
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "a\n<<<<<<< current\nx\n||||||| last read\nb\n=======\ny\n>>>>>>> apply_patch\nc\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_without_merge_base_still_fails_on_drift() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("drift.txt");
+        fs::write(&path, "a\nx\nc\n").unwrap();
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+ a
+-b
++y
+ c"#,
+            path.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+
+        let result = apply_patch_with_merge_bases(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+            &MergeBases::default(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApplyPatchError::IoError(_))));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a\nx\nc\n");
+    }
+
     #[tokio::test]
     async fn test_delete_file_hunk_removes_file() {
         let dir = tempdir().unwrap();
@@ -1317,4 +1698,181 @@ g
         .await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_apply_patch_rolls_back_when_a_later_change_fails() {
+        let dir = tempdir().unwrap();
+        let modified = dir.path().join("modified.txt");
+        let deleted = dir.path().join("deleted.txt");
+        let blocker = dir.path().join("blocker");
+        fs::write(&modified, "before\n").unwrap();
+        fs::write(&deleted, "obsolete\n").unwrap();
+        fs::write(&blocker, "not a directory\n").unwrap();
+
+        // The last hunk can only fail once the others have been written, because its parent
+        // path is a file.
+        let patch = wrap_patch(
+            r#"*** Update File: modified.txt
+@@
+-before
++after
+*** Delete File: deleted.txt
+*** Add File: nested/dir/created.txt
++created
+*** Add File: blocker/child.txt
++child"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApplyPatchError::IoError(_))));
+        let stderr = String::from_utf8(stderr).unwrap();
+        assert!(stderr.starts_with(&format!(
+            "Failed to write file {}",
+            dir.path().abs().join("blocker/child.txt").display()
+        )));
+        assert!(stderr.ends_with("\nNo files were changed.\n"));
+        assert_eq!(fs::read_to_string(&modified).unwrap(), "before\n");
+        assert_eq!(fs::read_to_string(&deleted).unwrap(), "obsolete\n");
+        let mut entries = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(entries, vec!["blocker", "deleted.txt", "modified.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_apply_patch_rolls_back_when_interrupted() {
+        let dir = tempdir().unwrap();
+        let modified = dir.path().join("modified.txt");
+        fs::write(&modified, "before\n").unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Update File: modified.txt
+@@
+-before
++after
+*** Add File: created.txt
++created"#,
+        );
+        let interrupt = CancellationToken::new();
+        interrupt.cancel();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch_with_merge_bases(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+            &MergeBases::default(),
+            &interrupt,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            String::from_utf8(stderr).unwrap(),
+            "apply_patch was interrupted\nNo files were changed.\n"
+        );
+        assert_eq!(fs::read_to_string(&modified).unwrap(), "before\n");
+        assert!(!dir.path().join("created.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_apply_patch_rolls_back_symlinks_as_links() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("target.txt");
+        let link = dir.path().join("link.txt");
+        let dangling = dir.path().join("dangling");
+        fs::write(&target, "before\n").unwrap();
+        std::os::unix::fs::symlink("target.txt", &link).unwrap();
+        std::os::unix::fs::symlink("missing.txt", &dangling).unwrap();
+
+        // Creating a link over the existing dangling one fails after `link.txt` has been written
+        // through, and only after the dangling link has been backed up.
+        let patch = wrap_patch(
+            r#"*** Update File: link.txt
+@@
+-before
++after
+*** Add Symlink: dangling
+*** Link to: elsewhere.txt"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("target.txt"));
+        assert_eq!(fs::read_to_string(&target).unwrap(), "before\n");
+        assert_eq!(
+            fs::read_link(&dangling).unwrap(),
+            PathBuf::from("missing.txt")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_sees_earlier_hunks_of_the_same_patch() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("twice.txt");
+        fs::write(&path, "one\n").unwrap();
+
+        let patch = wrap_patch(
+            r#"*** Update File: twice.txt
+@@
+-one
++two
+*** Update File: twice.txt
+@@
+-two
++three
+*** Delete File: twice.txt
+*** Update File: twice.txt
+@@
+-three
++four"#,
+        );
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(
+            &patch,
+            &dir.path().abs(),
+            &mut stdout,
+            &mut stderr,
+            LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            String::from_utf8(stderr).unwrap(),
+            format!(
+                "Failed to read file to update {}: removed by an earlier hunk of this patch\nNo files were changed.\n",
+                dir.path().abs().join("twice.txt").display()
+            )
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "one\n");
+    }
 }
@@ -0,0 +1,220 @@
+//! Three-way merge fallback used when a patch's context no longer matches the file on disk.
+//!
+//! The patch is applied to the contents the model last read (the merge base), and the result is
+//! merged diff3-style with the current file contents.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_utils_absolute_path::AbsolutePathBuf;
+use similar::Algorithm;
+use similar::DiffOp;
+use similar::capture_diff_slices;
+
+const CONFLICT_START: &str = "<<<<<<< current";
+const CONFLICT_BASE: &str = "||||||| last read";
+const CONFLICT_SEPARATOR: &str = "=======";
+const CONFLICT_END: &str = ">>>>>>> apply_patch";
+
+/// File contents the model last read, keyed by absolute path. Updates to files recorded here can
+/// fall back to a three-way merge when their context lines no longer match the file on disk.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MergeBases {
+    contents_by_path: HashMap<PathBuf, String>,
+}
+
+impl MergeBases {
+    /// Records `contents` as the last-read contents of `path`, replacing any earlier snapshot.
+    pub fn record(&mut self, path: &AbsolutePathBuf, contents: String) {
+        self.contents_by_path.insert(path.to_path_buf(), contents);
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&str> {
+        self.contents_by_path.get(path).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contents_by_path.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct MergeResult {
+    /// Merged contents; conflicting regions are wrapped in diff3-style conflict markers.
+    pub(crate) contents: String,
+    /// Number of conflicting regions.
+    pub(crate) conflicts: usize,
+}
+
+/// Merges the changes between `base` and `patched` into `current`.
+pub(crate) fn merge_three_way(base: &str, current: &str, patched: &str) -> MergeResult {
+    let base_lines = split_lines(base);
+    let current_lines = split_lines(current);
+    let patched_lines = split_lines(patched);
+    let current_matches = matching_lines(&base_lines, &current_lines);
+    let patched_matches = matching_lines(&base_lines, &patched_lines);
+
+    let mut merged: Vec<&str> = Vec::new();
+    let mut conflicts = 0;
+    let (mut base_idx, mut current_idx, mut patched_idx) = (0, 0, 0);
+    loop {
+        // Copy lines that are unchanged on both sides.
+        let mut stable = 0;
+        while base_idx + stable < base_lines.len()
+            && current_matches[base_idx + stable] == Some(current_idx + stable)
+            && patched_matches[base_idx + stable] == Some(patched_idx + stable)
+        {
+            stable += 1;
+        }
+        if stable > 0 {
+            merged.extend_from_slice(&base_lines[base_idx..base_idx + stable]);
+            base_idx += stable;
+            current_idx += stable;
+            patched_idx += stable;
+            continue;
+        }
+
+        // Find where both sides line up with the base again.
+        let (base_end, current_end, patched_end) = (base_idx..base_lines.len())
+            .find_map(|idx| match (current_matches[idx], patched_matches[idx]) {
+                (Some(current_end), Some(patched_end))
+                    if current_end >= current_idx && patched_end >= patched_idx =>
+                {
+                    Some((idx, current_end, patched_end))
+                }
+                _ => None,
+            })
+            .unwrap_or((base_lines.len(), current_lines.len(), patched_lines.len()));
+
+        let base_chunk = &base_lines[base_idx..base_end];
+        let current_chunk = &current_lines[current_idx..current_end];
+        let patched_chunk = &patched_lines[patched_idx..patched_end];
+        if current_chunk == base_chunk || current_chunk == patched_chunk {
+            merged.extend_from_slice(patched_chunk);
+        } else if patched_chunk == base_chunk {
+            merged.extend_from_slice(current_chunk);
+        } else {
+            conflicts += 1;
+            merged.push(CONFLICT_START);
+            merged.extend_from_slice(current_chunk);
+            merged.push(CONFLICT_BASE);
+            merged.extend_from_slice(base_chunk);
+            merged.push(CONFLICT_SEPARATOR);
+            merged.extend_from_slice(patched_chunk);
+            merged.push(CONFLICT_END);
+        }
+
+        if base_end == base_lines.len()
+            && current_end == current_lines.len()
+            && patched_end == patched_lines.len()
+        {
+            break;
+        }
+        base_idx = base_end;
+        current_idx = current_end;
+        patched_idx = patched_end;
+    }
+
+    let mut contents = merged.join("\n");
+    if !merged.is_empty() {
+        contents.push('\n');
+    }
+    MergeResult {
+        contents,
+        conflicts,
+    }
+}
+
+/// Splits `text` into lines, ignoring the empty element after a trailing newline.
+fn split_lines(text: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    if lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// For each line of `base`, the index of the matching line in `other`, if it was kept.
+fn matching_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+    for op in capture_diff_slices(Algorithm::Myers, base, other) {
+        if let DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = op
+        {
+            for offset in 0..len {
+                matches[old_index + offset] = Some(new_index + offset);
+            }
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn merges_non_overlapping_changes() {
+        let base = "a\nb\nc\nd\ne\n";
+        let current = "a\nB\nc\nd\ne\n";
+        let patched = "a\nb\nc\nd\nE\n";
+
+        assert_eq!(
+            merge_three_way(base, current, patched),
+            MergeResult {
+                contents: "a\nB\nc\nd\nE\n".to_string(),
+                conflicts: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn merges_insertions_on_both_sides() {
+        let base = "fn main() {\n}\n";
+        let current = "use std::fs;\n\nfn main() {\n}\n";
+        let patched = "fn main() {\n    println!(\"hi\");\n}\n";
+
+        assert_eq!(
+            merge_three_way(base, current, patched),
+            MergeResult {
+                contents: "use std::fs;\n\nfn main() {\n    println!(\"hi\");\n}\n".to_string(),
+                conflicts: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn identical_changes_are_not_conflicts() {
+        let base = "a\nb\n";
+        let changed = "a\nB\n";
+
+        assert_eq!(
+            merge_three_way(base, changed, changed),
+            MergeResult {
+                contents: changed.to_string(),
+                conflicts: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn overlapping_changes_produce_conflict_markers() {
+        let base = "a\nb\nc\n";
+        let current = "a\nx\nc\n";
+        let patched = "a\ny\nc\n";
+
+        assert_eq!(
+            merge_three_way(base, current, patched),
+            MergeResult {
+                contents: "a\n<<<<<<< current\nx\n||||||| last read\nb\n=======\ny\n>>>>>>> apply_patch\nc\n"
+                    .to_string(),
+                conflicts: 1,
+            }
+        );
+    }
+}
@@ -7,22 +7,30 @@
 //! begin_patch: "*** Begin Patch" LF
 //! end_patch: "*** End Patch" LF?
 //!
-//! hunk: add_hunk | delete_hunk | update_hunk
-//! add_hunk: "*** Add File: " filename LF add_line+
+//! hunk: add_hunk | add_binary_hunk | add_symlink_hunk | delete_hunk | update_hunk
+//! add_hunk: "*** Add File: " filename LF set_mode? add_line+
+//! add_binary_hunk: "*** Add Binary File: " filename LF set_mode? add_line+
+//! add_symlink_hunk: "*** Add Symlink: " filename LF "*** Link to: " filename LF
 //! delete_hunk: "*** Delete File: " filename LF
-//! update_hunk: "*** Update File: " filename LF change_move? change?
+//! update_hunk: "*** Update File: " filename LF change_move? set_mode? change?
 //! filename: /(.+)/
 //! add_line: "+" /(.+)/ LF -> line
 //!
+//! set_mode: "*** Set Mode: " /0?[0-7]{3}/ LF
 //! change_move: "*** Move to: " filename LF
 //! change: (change_context | change_line)+ eof_line?
 //! change_context: ("@@" | "@@ " /(.+)/) LF
 //! change_line: ("+" | "-" | " ") /(.+)/ LF
 //! eof_line: "*** End of File" LF
 //!
+//! The add lines of an `add_binary_hunk` hold the file's contents as base64, split across as many
+//! lines as needed.
+//!
 //! The parser below is a little more lenient than the explicit spec and allows for
 //! leading/trailing whitespace around patch markers.
 use crate::ApplyPatchArgs;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use codex_utils_absolute_path::AbsolutePathBuf;
 #[cfg(test)]
 use codex_utils_absolute_path::test_support::PathBufExt;
@@ -34,6 +42,10 @@ use thiserror::Error;
 const BEGIN_PATCH_MARKER: &str = "*** Begin Patch";
 const END_PATCH_MARKER: &str = "*** End Patch";
 const ADD_FILE_MARKER: &str = "*** Add File: ";
+const ADD_BINARY_FILE_MARKER: &str = "*** Add Binary File: ";
+const ADD_SYMLINK_MARKER: &str = "*** Add Symlink: ";
+const LINK_TO_MARKER: &str = "*** Link to: ";
+const SET_MODE_MARKER: &str = "*** Set Mode: ";
 const DELETE_FILE_MARKER: &str = "*** Delete File: ";
 const UPDATE_FILE_MARKER: &str = "*** Update File: ";
 const MOVE_TO_MARKER: &str = "*** Move to: ";
@@ -65,9 +77,25 @@ pub enum Hunk {
         path: PathBuf,
         contents: String,
     },
+    /// A file added from base64-encoded contents.
+    AddBinaryFile {
+        path: PathBuf,
+        contents: Vec<u8>,
+    },
+    AddSymlink {
+        path: PathBuf,
+        /// Link target, stored as given; relative targets resolve against the link's directory.
+        target: PathBuf,
+    },
     DeleteFile {
         path: PathBuf,
     },
+    /// Sets the permission bits of `path`. Emitted right after the add or update hunk that
+    /// declared `*** Set Mode`, or on its own for an update that only changes the mode.
+    SetMode {
+        path: PathBuf,
+        mode: u32,
+    },
     UpdateFile {
         path: PathBuf,
         move_path: Option<PathBuf>,
@@ -82,7 +110,11 @@ impl Hunk {
     pub fn resolve_path(&self, cwd: &AbsolutePathBuf) -> AbsolutePathBuf {
         let path = match self {
             Hunk::UpdateFile { path, .. } => path,
-            Hunk::AddFile { .. } | Hunk::DeleteFile { .. } => self.path(),
+            Hunk::AddFile { .. }
+            | Hunk::AddBinaryFile { .. }
+            | Hunk::AddSymlink { .. }
+            | Hunk::DeleteFile { .. }
+            | Hunk::SetMode { .. } => self.path(),
         };
         AbsolutePathBuf::resolve_path_against_base(path, cwd)
     }
@@ -91,7 +123,10 @@ impl Hunk {
     pub fn path(&self) -> &Path {
         match self {
             Hunk::AddFile { path, .. } => path,
+            Hunk::AddBinaryFile { path, .. } => path,
+            Hunk::AddSymlink { path, .. } => path,
             Hunk::DeleteFile { path } => path,
+            Hunk::SetMode { path, .. } => path,
             Hunk::UpdateFile {
                 move_path: Some(path),
                 ..
@@ -198,8 +233,9 @@ fn parse_patch_text(patch: &str, mode: ParseMode) -> Result<ApplyPatchArgs, Pars
     let mut line_number = 2;
     let allow_incomplete = matches!(mode, ParseMode::Streaming);
     while !remaining_lines.is_empty() {
-        let (hunk, hunk_lines) = parse_one_hunk(remaining_lines, line_number, allow_incomplete)?;
-        hunks.push(hunk);
+        let (parsed_hunks, hunk_lines) =
+            parse_one_hunk(remaining_lines, line_number, allow_incomplete)?;
+        hunks.extend(parsed_hunks);
         line_number += hunk_lines;
         remaining_lines = &remaining_lines[hunk_lines..]
     }
@@ -296,19 +332,21 @@ fn check_start_and_end_lines_strict(
 }
 
 /// Attempts to parse a single hunk from the start of lines.
-/// Returns the parsed hunk and the number of lines parsed (or a ParseError).
+/// Returns the parsed hunks and the number of lines parsed (or a ParseError). A hunk that
+/// declares `*** Set Mode` is followed by a [`SetMode`] hunk for the path it writes.
 fn parse_one_hunk(
     lines: &[&str],
     line_number: usize,
     allow_incomplete: bool,
-) -> Result<(Hunk, usize), ParseError> {
+) -> Result<(Vec<Hunk>, usize), ParseError> {
     // Be tolerant of case mismatches and extra padding around marker strings.
     let first_line = lines[0].trim();
     if let Some(path) = first_line.strip_prefix(ADD_FILE_MARKER) {
         // Add File
+        let (mode, mode_lines) = parse_set_mode(&lines[1..], line_number + 1)?;
         let mut contents = String::new();
-        let mut parsed_lines = 1;
-        for add_line in &lines[1..] {
+        let mut parsed_lines = 1 + mode_lines;
+        for add_line in &lines[parsed_lines..] {
             if let Some(line_to_add) = add_line.strip_prefix('+') {
                 contents.push_str(line_to_add);
                 contents.push('\n');
@@ -318,18 +356,77 @@ fn parse_one_hunk(
             }
         }
         return Ok((
-            AddFile {
-                path: PathBuf::from(path),
-                contents,
-            },
+            with_mode(
+                AddFile {
+                    path: PathBuf::from(path),
+                    contents,
+                },
+                mode,
+            ),
+            parsed_lines,
+        ));
+    } else if let Some(path) = first_line.strip_prefix(ADD_BINARY_FILE_MARKER) {
+        // Add Binary File
+        let (mode, mode_lines) = parse_set_mode(&lines[1..], line_number + 1)?;
+        let mut encoded = String::new();
+        let mut parsed_lines = 1 + mode_lines;
+        for add_line in &lines[parsed_lines..] {
+            if let Some(line_to_add) = add_line.strip_prefix('+') {
+                encoded.push_str(line_to_add.trim());
+                parsed_lines += 1;
+            } else {
+                break;
+            }
+        }
+        if allow_incomplete {
+            // Only decode complete base64 quanta while the contents are still streaming in.
+            encoded.truncate(encoded.len() - encoded.len() % 4);
+        }
+        let contents = BASE64_STANDARD
+            .decode(encoded)
+            .map_err(|err| InvalidHunkError {
+                message: format!(
+                    "Add binary file hunk for path '{path}' is not valid base64: {err}"
+                ),
+                line_number,
+            })?;
+        return Ok((
+            with_mode(
+                AddBinaryFile {
+                    path: PathBuf::from(path),
+                    contents,
+                },
+                mode,
+            ),
             parsed_lines,
         ));
+    } else if let Some(path) = first_line.strip_prefix(ADD_SYMLINK_MARKER) {
+        // Add Symlink
+        let Some(target) = lines
+            .get(1)
+            .and_then(|line| line.strip_prefix(LINK_TO_MARKER))
+            .filter(|target| !target.is_empty())
+        else {
+            return Err(InvalidHunkError {
+                message: format!(
+                    "Add symlink hunk for path '{path}' must be followed by '*** Link to: {{target}}'"
+                ),
+                line_number,
+            });
+        };
+        return Ok((
+            vec![AddSymlink {
+                path: PathBuf::from(path),
+                target: PathBuf::from(target),
+            }],
+            2,
+        ));
     } else if let Some(path) = first_line.strip_prefix(DELETE_FILE_MARKER) {
         // Delete File
         return Ok((
-            DeleteFile {
+            vec![DeleteFile {
                 path: PathBuf::from(path),
-            },
+            }],
             1,
         ));
     } else if let Some(path) = first_line.strip_prefix(UPDATE_FILE_MARKER) {
@@ -347,6 +444,11 @@ fn parse_one_hunk(
             parsed_lines += 1;
         }
 
+        // Optional: file mode line
+        let (mode, mode_lines) = parse_set_mode(remaining_lines, line_number + parsed_lines)?;
+        remaining_lines = &remaining_lines[mode_lines..];
+        parsed_lines += mode_lines;
+
         let mut chunks = Vec::new();
         // NOTE: we need to know to stop once we reach the next special marker header.
         while !remaining_lines.is_empty() {
@@ -383,6 +485,16 @@ fn parse_one_hunk(
         }
 
         if chunks.is_empty() {
+            // An update may consist of nothing but a mode change.
+            if let (Some(mode), None) = (mode, move_path) {
+                return Ok((
+                    vec![SetMode {
+                        path: PathBuf::from(path),
+                        mode,
+                    }],
+                    parsed_lines,
+                ));
+            }
             return Err(InvalidHunkError {
                 message: format!("Update file hunk for path '{path}' is empty"),
                 line_number,
@@ -390,23 +502,67 @@ fn parse_one_hunk(
         }
 
         return Ok((
-            UpdateFile {
-                path: PathBuf::from(path),
-                move_path: move_path.map(PathBuf::from),
-                chunks,
-            },
+            with_mode(
+                UpdateFile {
+                    path: PathBuf::from(path),
+                    move_path: move_path.map(PathBuf::from),
+                    chunks,
+                },
+                mode,
+            ),
             parsed_lines,
         ));
+    } else if first_line.starts_with(SET_MODE_MARKER.trim_end()) {
+        return Err(InvalidHunkError {
+            message: format!(
+                "'{first_line}' must directly follow an '*** Add File', '*** Add Binary File' or '*** Update File' header (after any '*** Move to' line)"
+            ),
+            line_number,
+        });
     }
 
     Err(InvalidHunkError {
         message: format!(
-            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}'"
+            "'{first_line}' is not a valid hunk header. Valid hunk headers: '*** Add File: {{path}}', '*** Add Binary File: {{path}}', '*** Add Symlink: {{path}}', '*** Delete File: {{path}}', '*** Update File: {{path}}'"
         ),
         line_number,
     })
 }
 
+/// Highest mode `*** Set Mode` accepts; setuid, setgid and sticky bits are never set by a patch.
+const MAX_FILE_MODE: u32 = 0o777;
+
+/// Parses an optional `*** Set Mode: <octal>` line at the start of `lines`, returning the mode and
+/// the number of lines consumed.
+fn parse_set_mode(lines: &[&str], line_number: usize) -> Result<(Option<u32>, usize), ParseError> {
+    let Some(mode) = lines
+        .first()
+        .and_then(|line| line.trim().strip_prefix(SET_MODE_MARKER))
+    else {
+        return Ok((None, 0));
+    };
+    let mode = mode.trim();
+    let is_octal = (3..=4).contains(&mode.len()) && mode.chars().all(|c| matches!(c, '0'..='7'));
+    match u32::from_str_radix(mode, 8) {
+        Ok(mode) if is_octal && mode <= MAX_FILE_MODE => Ok((Some(mode), 1)),
+        _ => Err(InvalidHunkError {
+            message: format!(
+                "Invalid file mode '{mode}'. Expected octal permission bits no greater than 777, such as 644 or 755"
+            ),
+            line_number,
+        }),
+    }
+}
+
+/// Returns `hunk`, followed by a [`SetMode`] hunk for the path it writes when `mode` is set.
+fn with_mode(hunk: Hunk, mode: Option<u32>) -> Vec<Hunk> {
+    let set_mode = mode.map(|mode| SetMode {
+        path: hunk.path().to_path_buf(),
+        mode,
+    });
+    std::iter::once(hunk).chain(set_mode).collect()
+}
+
 fn parse_update_file_chunk(
     lines: &[&str],
     line_number: usize,
@@ -638,8 +794,9 @@ fn test_parse_patch_streaming_large_patch_by_character() {
             .hunks
             .iter()
             .map(|hunk| match hunk {
-                AddFile { .. } => "add",
+                AddFile { .. } | AddBinaryFile { .. } | AddSymlink { .. } => "add",
                 DeleteFile { .. } => "delete",
+                SetMode { .. } => "set-mode",
                 UpdateFile {
                     move_path: Some(_), ..
                 } => "move-update",
@@ -810,6 +967,139 @@ fn test_parse_patch() {
     );
 }
 
+#[test]
+fn test_parse_patch_binary_symlink_and_mode() {
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n\
+             *** Add Binary File: assets/logo.bin\n\
+             *** Set Mode: 600\n\
+             +AAEC\n\
+             +/w==\n\
+             *** Add Symlink: current\n\
+             *** Link to: releases/v2\n\
+             *** Add File: run.sh\n\
+             *** Set Mode: 755\n\
+             +echo hi\n\
+             *** Update File: old.sh\n\
+             *** Move to: new.sh\n\
+             *** Set Mode: 0755\n\
+             @@\n\
+             -old\n\
+             +new\n\
+             *** Update File: private.txt\n\
+             *** Set Mode: 600\n\
+             *** End Patch",
+            ParseMode::Strict
+        )
+        .unwrap()
+        .hunks,
+        vec![
+            AddBinaryFile {
+                path: PathBuf::from("assets/logo.bin"),
+                contents: vec![0, 1, 2, 255],
+            },
+            SetMode {
+                path: PathBuf::from("assets/logo.bin"),
+                mode: 0o600,
+            },
+            AddSymlink {
+                path: PathBuf::from("current"),
+                target: PathBuf::from("releases/v2"),
+            },
+            AddFile {
+                path: PathBuf::from("run.sh"),
+                contents: "echo hi\n".to_string(),
+            },
+            SetMode {
+                path: PathBuf::from("run.sh"),
+                mode: 0o755,
+            },
+            UpdateFile {
+                path: PathBuf::from("old.sh"),
+                move_path: Some(PathBuf::from("new.sh")),
+                chunks: vec![UpdateFileChunk {
+                    change_context: None,
+                    old_lines: vec!["old".to_string()],
+                    new_lines: vec!["new".to_string()],
+                    is_end_of_file: false,
+                }],
+            },
+            SetMode {
+                path: PathBuf::from("new.sh"),
+                mode: 0o755,
+            },
+            SetMode {
+                path: PathBuf::from("private.txt"),
+                mode: 0o600,
+            },
+        ]
+    );
+
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n*** Add File: run.sh\n*** Set Mode: 999\n+echo hi\n*** End Patch",
+            ParseMode::Strict
+        ),
+        Err(InvalidHunkError {
+            message: "Invalid file mode '999'. Expected octal permission bits no greater than 777, such as 644 or 755"
+                .to_string(),
+            line_number: 3,
+        })
+    );
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n*** Update File: run.sh\n*** Set Mode: 4755\n*** End Patch",
+            ParseMode::Strict
+        ),
+        Err(InvalidHunkError {
+            message: "Invalid file mode '4755'. Expected octal permission bits no greater than 777, such as 644 or 755"
+                .to_string(),
+            line_number: 3,
+        })
+    );
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n*** Add Symlink: current\n*** End Patch",
+            ParseMode::Strict
+        ),
+        Err(InvalidHunkError {
+            message:
+                "Add symlink hunk for path 'current' must be followed by '*** Link to: {target}'"
+                    .to_string(),
+            line_number: 2,
+        })
+    );
+    assert!(matches!(
+        parse_patch_text(
+            "*** Begin Patch\n*** Add Binary File: data.bin\n+not base64!\n*** End Patch",
+            ParseMode::Strict
+        ),
+        Err(InvalidHunkError { line_number: 2, .. })
+    ));
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n*** Set Mode: 755\n*** End Patch",
+            ParseMode::Strict
+        ),
+        Err(InvalidHunkError {
+            message: "'*** Set Mode: 755' must directly follow an '*** Add File', '*** Add Binary File' or '*** Update File' header (after any '*** Move to' line)".to_string(),
+            line_number: 2,
+        })
+    );
+
+    // Partially streamed base64 decodes only the complete groups received so far.
+    assert_eq!(
+        parse_patch_streaming("*** Begin Patch\n*** Add Binary File: data.bin\n+AAEC/w")
+            .unwrap()
+            .hunks,
+        vec![AddBinaryFile {
+            path: PathBuf::from("data.bin"),
+            contents: vec![0, 1, 2],
+        }]
+    );
+}
+
 #[test]
 fn test_parse_patch_accepts_relative_and_absolute_hunk_paths() {
     let dir = tempfile::tempdir().unwrap();
@@ -1004,7 +1294,7 @@ fn test_parse_one_hunk() {
         parse_one_hunk(&["bad"], /*line_number*/ 234, /*allow_incomplete*/ false),
         Err(InvalidHunkError {
             message: "'bad' is not a valid hunk header. \
-            Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}'".to_string(),
+            Valid hunk headers: '*** Add File: {path}', '*** Add Binary File: {path}', '*** Add Symlink: {path}', '*** Delete File: {path}', '*** Update File: {path}'".to_string(),
             line_number: 234
         })
     );
@@ -1,6 +1,10 @@
 use std::io::Read;
 use std::io::Write;
 
+use tokio_util::sync::CancellationToken;
+
+use crate::MergeBases;
+
 pub fn main() -> ! {
     let exit_code = run_main();
     std::process::exit(exit_code);
@@ -46,6 +50,13 @@ pub fn run_main() -> i32 {
         return 2;
     }
 
+    run_patch(&patch_arg)
+}
+
+/// Applies `patch` to the current directory with the local filesystem, printing the result, and
+/// returns the process exit code. An interrupt or termination signal received while the patch is
+/// being written rolls it back instead of leaving it half applied.
+pub fn run_patch(patch: &str) -> i32 {
     let mut stdout = std::io::stdout();
     let mut stderr = std::io::stderr();
     let cwd = match codex_utils_absolute_path::AbsolutePathBuf::current_dir() {
@@ -65,14 +76,29 @@ pub fn run_main() -> i32 {
             return 1;
         }
     };
-    match runtime.block_on(crate::apply_patch(
-        &patch_arg,
-        &cwd,
-        &mut stdout,
-        &mut stderr,
-        codex_exec_server::LOCAL_FS.as_ref(),
-        /*sandbox*/ None,
-    )) {
+    let result = runtime.block_on(async {
+        let interrupt = CancellationToken::new();
+        // Without the handlers a signal still stops the process, just without rolling back.
+        if let Ok(signals) = interrupt_signals() {
+            let interrupt = interrupt.clone();
+            tokio::spawn(async move {
+                signals.await;
+                interrupt.cancel();
+            });
+        }
+        crate::apply_patch_with_merge_bases(
+            patch,
+            &cwd,
+            &mut stdout,
+            &mut stderr,
+            codex_exec_server::LOCAL_FS.as_ref(),
+            /*sandbox*/ None,
+            &MergeBases::default(),
+            &interrupt,
+        )
+        .await
+    });
+    match result {
         Ok(()) => {
             // Flush to ensure output ordering when used in pipelines.
             let _ = stdout.flush();
@@ -81,3 +107,29 @@ pub fn run_main() -> i32 {
         Err(_) => 1,
     }
 }
+
+/// Registers for the signals that would otherwise kill the process, returning a future that
+/// resolves when any of them arrives.
+#[cfg(unix)]
+fn interrupt_signals() -> std::io::Result<impl Future<Output = ()>> {
+    use tokio::signal::unix::SignalKind;
+    use tokio::signal::unix::signal;
+
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut hangup = signal(SignalKind::hangup())?;
+    Ok(async move {
+        tokio::select! {
+            _ = interrupt.recv() => {}
+            _ = terminate.recv() => {}
+            _ = hangup.recv() => {}
+        }
+    })
+}
+
+#[cfg(not(unix))]
+fn interrupt_signals() -> std::io::Result<impl Future<Output = ()>> {
+    Ok(async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+}
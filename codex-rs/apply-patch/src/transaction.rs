@@ -0,0 +1,407 @@
+//! Transactional application of a patch.
+//!
+//! Every hunk is first staged in memory, so a patch whose context does not match or whose files
+//! are missing fails before anything is written. The staged changes are then committed one by
+//! one. Each file is copied aside before it is first touched, and if a later step fails, or the
+//! patch is interrupted, the originals are restored and any newly created files and
+//! directories are removed.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use anyhow::Context;
+use codex_exec_server::CopyOptions;
+use codex_exec_server::CreateDirectoryOptions;
+use codex_exec_server::ExecutorFileSystem;
+use codex_exec_server::FileSystemSandboxContext;
+use codex_exec_server::RemoveOptions;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use thiserror::Error;
+use tokio_util::sync::CancellationToken;
+
+/// A commit failed and restoring the original files failed as well.
+#[derive(Debug, Error)]
+#[error("{error}\nRolling back failed, so some files may have been changed: {rollback_error}")]
+pub(crate) struct RollbackFailed {
+    error: anyhow::Error,
+    rollback_error: io::Error,
+}
+
+#[derive(Debug)]
+enum StagedChange {
+    Write {
+        path: AbsolutePathBuf,
+        contents: Vec<u8>,
+    },
+    Symlink {
+        path: AbsolutePathBuf,
+        target: PathBuf,
+    },
+    SetMode {
+        path: AbsolutePathBuf,
+        mode: u32,
+    },
+    Remove {
+        path: AbsolutePathBuf,
+    },
+}
+
+impl StagedChange {
+    fn path(&self) -> &AbsolutePathBuf {
+        match self {
+            StagedChange::Write { path, .. }
+            | StagedChange::Symlink { path, .. }
+            | StagedChange::SetMode { path, .. }
+            | StagedChange::Remove { path } => path,
+        }
+    }
+}
+
+/// Changes staged for a single patch, in the order its hunks listed them.
+#[derive(Debug, Default)]
+pub(crate) struct StagedChanges {
+    changes: Vec<StagedChange>,
+    /// Contents each written path will have once committed; `None` for removed paths. Reads go
+    /// through this so a hunk sees the changes of the hunks before it.
+    contents: HashMap<PathBuf, Option<Vec<u8>>>,
+}
+
+impl StagedChanges {
+    pub(crate) fn write(&mut self, path: AbsolutePathBuf, contents: Vec<u8>) {
+        self.contents
+            .insert(path.to_path_buf(), Some(contents.clone()));
+        self.changes.push(StagedChange::Write { path, contents });
+    }
+
+    pub(crate) fn symlink(&mut self, path: AbsolutePathBuf, target: PathBuf) {
+        self.contents.remove(path.as_path());
+        self.changes.push(StagedChange::Symlink { path, target });
+    }
+
+    pub(crate) fn set_mode(&mut self, path: AbsolutePathBuf, mode: u32) {
+        self.changes.push(StagedChange::SetMode { path, mode });
+    }
+
+    pub(crate) fn remove(&mut self, path: AbsolutePathBuf) {
+        self.contents.insert(path.to_path_buf(), None);
+        self.changes.push(StagedChange::Remove { path });
+    }
+
+    /// Reads `path` as it will be once the changes staged so far are committed.
+    pub(crate) async fn read_file_text(
+        &self,
+        path: &AbsolutePathBuf,
+        fs: &dyn ExecutorFileSystem,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> io::Result<String> {
+        match self.contents.get(path.as_path()) {
+            Some(Some(contents)) => String::from_utf8(contents.clone())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            Some(None) => Err(removed_error()),
+            None => fs.read_file_text(path, sandbox).await,
+        }
+    }
+
+    /// Checks that `path` will be a file that can be removed once the changes staged so far are
+    /// committed.
+    pub(crate) async fn check_removable(
+        &self,
+        path: &AbsolutePathBuf,
+        fs: &dyn ExecutorFileSystem,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> io::Result<()> {
+        match self.contents.get(path.as_path()) {
+            Some(Some(_)) => Ok(()),
+            Some(None) => Err(removed_error()),
+            None => {
+                let metadata = fs.get_metadata(path, sandbox).await?;
+                if metadata.is_directory {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "path is a directory",
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Applies the staged changes. If any of them fails, or `interrupt` is cancelled, the
+    /// changes already made are rolled back before the error is returned.
+    pub(crate) async fn commit(
+        self,
+        fs: &dyn ExecutorFileSystem,
+        sandbox: Option<&FileSystemSandboxContext>,
+        interrupt: &CancellationToken,
+    ) -> anyhow::Result<()> {
+        let mut journal = Journal::default();
+        for change in &self.changes {
+            let result = if interrupt.is_cancelled() {
+                Err(anyhow::anyhow!("apply_patch was interrupted"))
+            } else {
+                journal.apply(change, fs, sandbox).await
+            };
+            if let Err(error) = result {
+                return match journal.roll_back(fs, sandbox).await {
+                    Ok(()) => Err(error),
+                    Err(rollback_error) => Err(RollbackFailed {
+                        error,
+                        rollback_error,
+                    }
+                    .into()),
+                };
+            }
+        }
+        journal.discard_backups(fs, sandbox).await;
+        Ok(())
+    }
+}
+
+/// Record of what a commit has done so far, so that it can be undone.
+#[derive(Debug, Default)]
+struct Journal {
+    /// Paths touched so far, each with a copy of its original contents if it existed.
+    touched: Vec<(AbsolutePathBuf, Option<Backup>)>,
+    /// Topmost directories created to hold new files.
+    created_directories: Vec<AbsolutePathBuf>,
+}
+
+impl Journal {
+    async fn apply(
+        &mut self,
+        change: &StagedChange,
+        fs: &dyn ExecutorFileSystem,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> anyhow::Result<()> {
+        let path = change.path();
+        if !self.touched.iter().any(|(touched, _)| touched == path) {
+            let backup = back_up(path, fs, sandbox)
+                .await
+                .with_context(|| format!("Failed to back up {}", path.display()))?;
+            self.touched.push((path.clone(), backup));
+        }
+
+        match change {
+            StagedChange::Write { path, contents } => {
+                self.create_missing_parents(path, fs, sandbox).await?;
+                fs.write_file(path, contents.clone(), sandbox)
+                    .await
+                    .with_context(|| format!("Failed to write file {}", path.display()))
+            }
+            StagedChange::Symlink { path, target } => {
+                self.create_missing_parents(path, fs, sandbox).await?;
+                fs.create_symlink(path, target, sandbox)
+                    .await
+                    .with_context(|| format!("Failed to create symlink {}", path.display()))
+            }
+            StagedChange::SetMode { path, mode } => fs
+                .set_mode(path, *mode, sandbox)
+                .await
+                .with_context(|| format!("Failed to set mode of {}", path.display())),
+            StagedChange::Remove { path } => fs
+                .remove(
+                    path,
+                    RemoveOptions {
+                        recursive: false,
+                        force: false,
+                    },
+                    sandbox,
+                )
+                .await
+                .with_context(|| format!("Failed to delete file {}", path.display())),
+        }
+    }
+
+    async fn create_missing_parents(
+        &mut self,
+        path: &AbsolutePathBuf,
+        fs: &dyn ExecutorFileSystem,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> anyhow::Result<()> {
+        let Some(parent) = path.parent() else {
+            return Ok(());
+        };
+        let mut topmost_missing = None;
+        for ancestor in parent.ancestors() {
+            match fs.get_metadata(&ancestor, sandbox).await {
+                Ok(_) => break,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    topmost_missing = Some(ancestor);
+                }
+                Err(err) => {
+                    return Err(err).with_context(|| {
+                        format!("Failed to create parent directories for {}", path.display())
+                    });
+                }
+            }
+        }
+        let Some(topmost_missing) = topmost_missing else {
+            return Ok(());
+        };
+        fs.create_directory(&parent, CreateDirectoryOptions { recursive: true }, sandbox)
+            .await
+            .with_context(|| {
+                format!("Failed to create parent directories for {}", path.display())
+            })?;
+        self.created_directories.push(topmost_missing);
+        Ok(())
+    }
+
+    /// Restores every touched path, continuing past failures and returning the first one.
+    async fn roll_back(
+        &self,
+        fs: &dyn ExecutorFileSystem,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> io::Result<()> {
+        let mut result = Ok(());
+        for (path, backup) in self.touched.iter().rev() {
+            result = result.and(restore(path, backup.as_ref(), fs, sandbox).await);
+        }
+        for directory in self.created_directories.iter().rev() {
+            let removed = fs
+                .remove(
+                    directory,
+                    RemoveOptions {
+                        recursive: true,
+                        force: true,
+                    },
+                    sandbox,
+                )
+                .await;
+            result = result.and(removed);
+        }
+        result
+    }
+
+    async fn discard_backups(
+        &self,
+        fs: &dyn ExecutorFileSystem,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) {
+        // A leftover copy does not affect the patch that was just applied.
+        for backup in self
+            .touched
+            .iter()
+            .filter_map(|(_, backup)| backup.as_ref())
+        {
+            let _ = fs.remove(backup.copy(), FORCE_REMOVE, sandbox).await;
+        }
+    }
+}
+
+const FORCE_REMOVE: RemoveOptions = RemoveOptions {
+    recursive: false,
+    force: true,
+};
+
+/// Original state of a path the commit touched.
+#[derive(Debug)]
+enum Backup {
+    /// A regular file, copied aside.
+    File { copy: AbsolutePathBuf },
+    /// A symlink, copied aside as a link. Writes through the link change its target, so the
+    /// target's contents are kept as well when it is a file.
+    Symlink {
+        copy: AbsolutePathBuf,
+        target_contents: Option<Vec<u8>>,
+    },
+}
+
+impl Backup {
+    fn copy(&self) -> &AbsolutePathBuf {
+        match self {
+            Backup::File { copy } | Backup::Symlink { copy, .. } => copy,
+        }
+    }
+}
+
+/// Copies `path` next to itself if it exists, returning the backup. Returns `None` when nothing
+/// is at `path`.
+async fn back_up(
+    path: &AbsolutePathBuf,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> io::Result<Option<Backup>> {
+    // `get_metadata` follows links, so a dangling symlink looks missing here. `copy` works on the
+    // link itself and copies it as a link, so it decides whether anything exists at `path`.
+    let metadata = match fs.get_metadata(path, sandbox).await {
+        Ok(metadata) => Some(metadata),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err),
+    };
+    let copy = backup_path(path);
+    match fs
+        .copy(path, &copy, CopyOptions { recursive: false }, sandbox)
+        .await
+    {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    }
+    let backup = match metadata {
+        Some(metadata) if !metadata.is_symlink => Backup::File { copy },
+        Some(metadata) if metadata.is_file => Backup::Symlink {
+            copy,
+            target_contents: Some(fs.read_file(path, sandbox).await?),
+        },
+        _ => Backup::Symlink {
+            copy,
+            target_contents: None,
+        },
+    };
+    Ok(Some(backup))
+}
+
+/// Puts `path` back the way it was before the commit touched it.
+async fn restore(
+    path: &AbsolutePathBuf,
+    backup: Option<&Backup>,
+    fs: &dyn ExecutorFileSystem,
+    sandbox: Option<&FileSystemSandboxContext>,
+) -> io::Result<()> {
+    fs.remove(path, FORCE_REMOVE, sandbox).await?;
+    let Some(backup) = backup else {
+        return Ok(());
+    };
+    fs.copy(
+        backup.copy(),
+        path,
+        CopyOptions { recursive: false },
+        sandbox,
+    )
+    .await?;
+    fs.remove(backup.copy(), FORCE_REMOVE, sandbox).await?;
+    if let Backup::Symlink {
+        target_contents: Some(contents),
+        ..
+    } = backup
+    {
+        // The restored link leads back to the target, so this restores the target's contents.
+        fs.write_file(path, contents.clone(), sandbox).await?;
+    }
+    Ok(())
+}
+
+fn backup_path(path: &AbsolutePathBuf) -> AbsolutePathBuf {
+    static NEXT_BACKUP_ID: AtomicU64 = AtomicU64::new(0);
+    let id = NEXT_BACKUP_ID.fetch_add(1, Ordering::Relaxed);
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let backup_name = format!(".{file_name}.apply-patch-{}-{id}.bak", std::process::id());
+    match path.parent() {
+        Some(parent) => parent.join(backup_name),
+        None => path.join(backup_name),
+    }
+}
+
+fn removed_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        "removed by an earlier hunk of this patch",
+    )
+}
@@ -109,7 +109,7 @@ fn test_apply_patch_cli_reports_missing_context() -> anyhow::Result<()> {
         .assert()
         .failure()
         .stderr(format!(
-            "Failed to find expected lines in {}:\nmissing\n",
+            "Failed to find expected lines in {}:\nmissing\nNo files were changed.\n",
             expected_target_path.display()
         ));
     assert_eq!(fs::read_to_string(&target_path)?, "line1\nline2\n");
@@ -127,7 +127,7 @@ fn test_apply_patch_cli_rejects_missing_file_delete() -> anyhow::Result<()> {
         .assert()
         .failure()
         .stderr(format!(
-            "Failed to delete file {}\n",
+            "Failed to delete file {}\nNo files were changed.\n",
             missing_path.display()
         ));
 
@@ -157,7 +157,7 @@ fn test_apply_patch_cli_requires_existing_file_for_update() -> anyhow::Result<()
         .assert()
         .failure()
         .stderr(format!(
-            "Failed to read file to update {}: No such file or directory (os error 2)\n",
+            "Failed to read file to update {}: No such file or directory (os error 2)\nNo files were changed.\n",
             missing_path.display()
         ));
 
@@ -217,7 +217,7 @@ fn test_apply_patch_cli_delete_directory_fails() -> anyhow::Result<()> {
         .assert()
         .failure()
         .stderr(format!(
-            "Failed to delete file {}\n",
+            "Failed to delete file {}\nNo files were changed.\n",
             expected_dir.display()
         ));
 
@@ -232,7 +232,7 @@ fn test_apply_patch_cli_rejects_invalid_hunk_header() -> anyhow::Result<()> {
         .arg("*** Begin Patch\n*** Frobnicate File: foo\n*** End Patch")
         .assert()
         .failure()
-        .stderr("Invalid patch hunk on line 2: '*** Frobnicate File: foo' is not a valid hunk header. Valid hunk headers: '*** Add File: {path}', '*** Delete File: {path}', '*** Update File: {path}'\n");
+        .stderr("Invalid patch hunk on line 2: '*** Frobnicate File: foo' is not a valid hunk header. Valid hunk headers: '*** Add File: {path}', '*** Add Binary File: {path}', '*** Add Symlink: {path}', '*** Delete File: {path}', '*** Update File: {path}'\n");
 
     Ok(())
 }
@@ -258,7 +258,7 @@ fn test_apply_patch_cli_updates_file_appends_trailing_newline() -> anyhow::Resul
 }
 
 #[test]
-fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow::Result<()> {
+fn test_apply_patch_cli_failure_after_partial_success_rolls_back() -> anyhow::Result<()> {
     let tmp = tempdir()?;
     let new_file = tmp.path().join("created.txt");
     let missing_file = resolved_under(tmp.path(), "missing.txt")?;
@@ -269,11 +269,11 @@ fn test_apply_patch_cli_failure_after_partial_success_leaves_changes() -> anyhow
         .failure()
         .stdout("")
         .stderr(format!(
-            "Failed to read file to update {}: No such file or directory (os error 2)\n",
+            "Failed to read file to update {}: No such file or directory (os error 2)\nNo files were changed.\n",
             missing_file.display()
         ));
 
-    assert_eq!(fs::read_to_string(&new_file)?, "hello\n");
+    assert!(!new_file.exists());
 
     Ok(())
 }
@@ -23,6 +23,8 @@ use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
 use codex_exec::ReviewArgs;
 use codex_execpolicy::ExecPolicyCheckCommand;
+use codex_execpolicy::ExecPolicyLintCommand;
+use codex_execpolicy::ExecPolicyPruneCommand;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use codex_rollout_trace::REDUCED_STATE_FILE_NAME;
 use codex_rollout_trace::replay_bundle;
@@ -64,6 +66,9 @@ use codex_login::AuthManager;
 use codex_models_manager::bundled_models_response;
 use codex_models_manager::collaboration_mode_presets::CollaborationModesConfig;
 use codex_models_manager::manager::RefreshStrategy;
+use codex_network_proxy::har_log_path;
+use codex_network_proxy::list_har_logs;
+use codex_network_proxy::read_har_log;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::user_input::UserInput;
 use codex_terminal_detection::TerminalName;
@@ -217,6 +222,9 @@ enum DebugSubcommand {
     #[clap(hide = true)]
     TraceReduce(DebugTraceReduceCommand),
 
+    /// List or show the network proxy's per-thread HAR request logs.
+    NetworkLog(DebugNetworkLogCommand),
+
     /// Internal: reset local memory state for a fresh start.
     #[clap(hide = true)]
     ClearMemories,
@@ -269,6 +277,21 @@ struct DebugTraceReduceCommand {
     output: Option<PathBuf>,
 }
 
+#[derive(Debug, Parser)]
+struct DebugNetworkLogCommand {
+    /// Thread id whose log to show. Lists threads with a network log when omitted.
+    #[arg(value_name = "THREAD_ID")]
+    thread_id: Option<String>,
+
+    /// Print the full HAR document as JSON instead of a summary.
+    #[arg(long = "har", default_value_t = false)]
+    har: bool,
+
+    /// Write the HAR document to FILE instead of printing a summary.
+    #[arg(long = "output", short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
 #[derive(Debug, Parser)]
 struct ResumeCommand {
     /// Conversation/session id (UUID) or thread name. UUIDs take precedence if it parses.
@@ -348,6 +371,14 @@ enum ExecpolicySubcommand {
     /// Check execpolicy files against a command.
     #[clap(name = "check")]
     Check(ExecPolicyCheckCommand),
+
+    /// Report unused and shadowed rules by replaying recorded sessions.
+    #[clap(name = "lint")]
+    Lint(ExecPolicyLintCommand),
+
+    /// Remove rules whose `expires_at` has passed.
+    #[clap(name = "prune")]
+    Prune(ExecPolicyPruneCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -619,6 +650,23 @@ fn run_execpolicycheck(cmd: ExecPolicyCheckCommand) -> anyhow::Result<()> {
     cmd.run()
 }
 
+fn run_execpolicylint(cmd: ExecPolicyLintCommand) -> anyhow::Result<()> {
+    // Split `bash -lc` scripts the same way exec approval does before consulting the policy.
+    cmd.run_with_command_splitter(&|command| {
+        if let Some(commands) = codex_shell_command::bash::parse_shell_lc_plain_commands(command)
+            && !commands.is_empty()
+        {
+            return commands;
+        }
+        if let Some(single_command) =
+            codex_shell_command::bash::parse_shell_lc_single_command_prefix(command)
+        {
+            return vec![single_command];
+        }
+        vec![command.to_vec()]
+    })
+}
+
 async fn run_debug_app_server_command(cmd: DebugAppServerCommand) -> anyhow::Result<()> {
     match cmd.subcommand {
         DebugAppServerSubcommand::SendMessageV2(cmd) => {
@@ -1100,6 +1148,14 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
                 )?;
                 run_debug_trace_reduce_command(cmd).await?;
             }
+            DebugSubcommand::NetworkLog(cmd) => {
+                reject_remote_mode_for_subcommand(
+                    root_remote.as_deref(),
+                    root_remote_auth_token_env.as_deref(),
+                    "debug network-log",
+                )?;
+                run_debug_network_log_command(cmd).await?;
+            }
             DebugSubcommand::ClearMemories => {
                 reject_remote_mode_for_subcommand(
                     root_remote.as_deref(),
@@ -1118,6 +1174,22 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
                 )?;
                 run_execpolicycheck(cmd)?
             }
+            ExecpolicySubcommand::Lint(cmd) => {
+                reject_remote_mode_for_subcommand(
+                    root_remote.as_deref(),
+                    root_remote_auth_token_env.as_deref(),
+                    "execpolicy lint",
+                )?;
+                run_execpolicylint(cmd)?
+            }
+            ExecpolicySubcommand::Prune(cmd) => {
+                reject_remote_mode_for_subcommand(
+                    root_remote.as_deref(),
+                    root_remote_auth_token_env.as_deref(),
+                    "execpolicy prune",
+                )?;
+                cmd.run()?
+            }
         },
         Some(Subcommand::Apply(mut apply_cli)) => {
             reject_remote_mode_for_subcommand(
@@ -1305,6 +1377,60 @@ async fn run_debug_trace_reduce_command(cmd: DebugTraceReduceCommand) -> anyhow:
     Ok(())
 }
 
+async fn run_debug_network_log_command(cmd: DebugNetworkLogCommand) -> anyhow::Result<()> {
+    let codex_home = find_codex_home()?;
+    let Some(thread_id) = cmd.thread_id else {
+        let thread_ids = list_har_logs(codex_home.as_path())?;
+        if thread_ids.is_empty() {
+            println!(
+                "No network logs found. Enable them with `enabled = true` under `[permissions.<profile>.network.har]`."
+            );
+        }
+        for thread_id in thread_ids {
+            println!("{thread_id}");
+        }
+        return Ok(());
+    };
+
+    let path = har_log_path(codex_home.as_path(), &thread_id);
+    if !tokio::fs::try_exists(&path).await? {
+        anyhow::bail!(
+            "no network log for thread {thread_id} (expected {})",
+            path.display()
+        );
+    }
+    let har = read_har_log(&path)?;
+
+    if let Some(output) = cmd.output {
+        tokio::fs::write(&output, serde_json::to_vec_pretty(&har)?).await?;
+        println!("{}", output.display());
+        return Ok(());
+    }
+    if cmd.har {
+        println!("{}", serde_json::to_string_pretty(&har)?);
+        return Ok(());
+    }
+
+    for entry in &har.log.entries {
+        let blocked = entry
+            .block_reason
+            .as_deref()
+            .map(|reason| format!("  blocked: {reason}"))
+            .unwrap_or_default();
+        println!(
+            "{}  {:<7} {:>3}  {:>9}B  {:>7.0}ms  {}{blocked}",
+            entry.started_date_time,
+            entry.request.method,
+            entry.response.status,
+            entry.response.body_size.max(0),
+            entry.time,
+            entry.request.url,
+        );
+    }
+
+    Ok(())
+}
+
 async fn run_debug_prompt_input_command(
     cmd: DebugPromptInputCommand,
     root_config_overrides: CliConfigOverrides,
@@ -1838,6 +1964,30 @@ mod tests {
         assert!(cmd.bundled);
     }
 
+    #[test]
+    fn debug_network_log_parses_thread_and_output() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "debug",
+            "network-log",
+            "thread-1",
+            "-o",
+            "/tmp/thread-1.har",
+        ])
+        .expect("parse");
+
+        let Some(Subcommand::Debug(DebugCommand {
+            subcommand: DebugSubcommand::NetworkLog(cmd),
+        })) = cli.subcommand
+        else {
+            panic!("expected debug network-log subcommand");
+        };
+
+        assert_eq!(cmd.thread_id.as_deref(), Some("thread-1"));
+        assert!(!cmd.har);
+        assert_eq!(cmd.output, Some(PathBuf::from("/tmp/thread-1.har")));
+    }
+
     #[test]
     fn responses_subcommand_is_not_registered() {
         let command = MultitoolCli::command();
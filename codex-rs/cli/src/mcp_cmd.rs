@@ -7,6 +7,7 @@ use anyhow::anyhow;
 use anyhow::bail;
 use clap::ArgGroup;
 use codex_config::types::AppToolApproval;
+use codex_config::types::McpSamplingApproval;
 use codex_config::types::McpServerConfig;
 use codex_config::types::McpServerTransportConfig;
 use codex_core::McpManager;
@@ -306,6 +307,7 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
         startup_timeout_sec: None,
         tool_timeout_sec: None,
         default_tools_approval_mode: None,
+        sampling_approval_mode: None,
         enabled_tools: None,
         disabled_tools: None,
         scopes: None,
@@ -893,6 +895,14 @@ async fn run_get(config_overrides: &CliConfigOverrides, get_args: GetArgs) -> Re
         };
         println!("  default_tools_approval_mode: {approval_mode}");
     }
+    if let Some(approval_mode) = server.sampling_approval_mode {
+        let approval_mode = match approval_mode {
+            McpSamplingApproval::Prompt => "prompt",
+            McpSamplingApproval::Approve => "approve",
+            McpSamplingApproval::Deny => "deny",
+        };
+        println!("  sampling_approval_mode: {approval_mode}");
+    }
     println!("  remove: codex mcp remove {}", get_args.name);
 
     Ok(())
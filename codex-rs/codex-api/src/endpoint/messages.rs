@@ -0,0 +1,137 @@
+use crate::auth::SharedAuthProvider;
+use crate::common::ResponseStream;
+use crate::common::ResponsesApiRequest;
+use crate::endpoint::ResponsesOptions;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::Compression;
+use crate::requests::build_messages_request;
+use crate::requests::headers::build_conversation_headers;
+use crate::requests::headers::insert_header;
+use crate::requests::headers::subagent_header;
+use crate::sse::spawn_messages_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestCompression;
+use codex_client::RequestTelemetry;
+use http::HeaderValue;
+use http::Method;
+use std::sync::Arc;
+use tracing::instrument;
+
+const ANTHROPIC_VERSION_HEADER: &str = "anthropic-version";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Streams turns from providers that speak the Anthropic-compatible Messages API.
+///
+/// Requests are built as [`ResponsesApiRequest`]s like every other turn and
+/// converted to `/messages` bodies here; the streamed content blocks come back
+/// as the same [`ResponseStream`] events the Responses client produces.
+pub struct MessagesClient<T: HttpTransport> {
+    session: EndpointSession<T>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+    max_output_tokens: Option<u64>,
+}
+
+impl<T: HttpTransport> MessagesClient<T> {
+    pub fn new(transport: T, provider: Provider, auth: SharedAuthProvider) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, auth),
+            sse_telemetry: None,
+            max_output_tokens: None,
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+            max_output_tokens: self.max_output_tokens,
+        }
+    }
+
+    /// Output token cap sent as `max_tokens`; `None` keeps the default of 32000.
+    pub fn with_max_output_tokens(self, max_output_tokens: Option<u64>) -> Self {
+        Self {
+            max_output_tokens,
+            ..self
+        }
+    }
+
+    fn path() -> &'static str {
+        "messages"
+    }
+
+    #[instrument(
+        name = "messages.stream_request",
+        level = "info",
+        skip_all,
+        fields(
+            transport = "messages_http",
+            http.method = "POST",
+            api.path = "messages"
+        )
+    )]
+    pub async fn stream_request(
+        &self,
+        request: ResponsesApiRequest,
+        options: ResponsesOptions,
+    ) -> Result<ResponseStream, ApiError> {
+        let ResponsesOptions {
+            conversation_id,
+            session_source,
+            extra_headers,
+            compression,
+            turn_state: _,
+        } = options;
+
+        let messages_request = build_messages_request(&request, self.max_output_tokens);
+
+        let mut headers = extra_headers;
+        if let Some(ref conv_id) = conversation_id {
+            insert_header(&mut headers, "x-client-request-id", conv_id);
+        }
+        headers.extend(build_conversation_headers(conversation_id));
+        if let Some(subagent) = subagent_header(&session_source) {
+            insert_header(&mut headers, "x-openai-subagent", &subagent);
+        }
+        // Providers may pin a newer API version through `http_headers`.
+        if !headers.contains_key(ANTHROPIC_VERSION_HEADER) {
+            insert_header(&mut headers, ANTHROPIC_VERSION_HEADER, ANTHROPIC_VERSION);
+        }
+
+        let request_compression = match compression {
+            Compression::None => RequestCompression::None,
+            Compression::Zstd => RequestCompression::Zstd,
+        };
+
+        let stream_response = self
+            .session
+            .stream_with(
+                Method::POST,
+                Self::path(),
+                headers,
+                Some(messages_request.body),
+                |req| {
+                    req.headers.insert(
+                        http::header::ACCEPT,
+                        HeaderValue::from_static("text/event-stream"),
+                    );
+                    req.compression = request_compression;
+                },
+            )
+            .await?;
+
+        Ok(spawn_messages_stream(
+            stream_response,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+            messages_request.tools,
+        ))
+    }
+}
@@ -1,5 +1,7 @@
+pub(crate) mod chat;
 pub(crate) mod compact;
 pub(crate) mod memories;
+pub(crate) mod messages;
 pub(crate) mod models;
 pub(crate) mod realtime_call;
 pub(crate) mod realtime_websocket;
@@ -7,8 +9,10 @@ pub(crate) mod responses;
 pub(crate) mod responses_websocket;
 mod session;
 
+pub use chat::ChatClient;
 pub use compact::CompactClient;
 pub use memories::MemoriesClient;
+pub use messages::MessagesClient;
 pub use models::ModelsClient;
 pub use realtime_call::RealtimeCallClient;
 pub use realtime_call::RealtimeCallResponse;
@@ -0,0 +1,277 @@
+//! Converts Responses API requests into Chat Completions request bodies.
+//!
+//! Codex builds every turn as a [`ResponsesApiRequest`]. Providers configured with
+//! `wire_api = "chat"` only understand `/chat/completions`, so the history, tool
+//! specs, and output controls are translated here and the tool names are recorded
+//! so streamed tool calls can be mapped back onto Responses items.
+
+use crate::common::ResponsesApiRequest;
+use crate::requests::flat_tools::FREEFORM_INPUT_ARGUMENT;
+use crate::requests::flat_tools::FlatToolNames;
+use crate::requests::flat_tools::LOCAL_SHELL_TOOL_NAME;
+use crate::requests::flat_tools::flat_function_name;
+use crate::requests::flat_tools::flatten_tools;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ImageDetail;
+use codex_protocol::models::ResponseItem;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+
+/// A Chat Completions request body plus the tool names it advertised.
+pub(crate) struct ChatRequest {
+    pub(crate) body: Value,
+    pub(crate) tools: FlatToolNames,
+}
+
+pub(crate) fn build_chat_request(request: &ResponsesApiRequest) -> ChatRequest {
+    let (tools, tool_names) = chat_tools(&request.tools);
+    let mut body = Map::new();
+    body.insert("model".to_string(), Value::String(request.model.clone()));
+    body.insert(
+        "messages".to_string(),
+        Value::Array(chat_messages(&request.instructions, &request.input)),
+    );
+    body.insert("stream".to_string(), Value::Bool(true));
+    body.insert(
+        "stream_options".to_string(),
+        json!({ "include_usage": true }),
+    );
+    if !tools.is_empty() {
+        body.insert("tools".to_string(), Value::Array(tools));
+        body.insert(
+            "tool_choice".to_string(),
+            Value::String(request.tool_choice.clone()),
+        );
+        body.insert(
+            "parallel_tool_calls".to_string(),
+            Value::Bool(request.parallel_tool_calls),
+        );
+    }
+    if let Some(effort) = request
+        .reasoning
+        .as_ref()
+        .and_then(|reasoning| reasoning.effort)
+        && let Ok(effort) = serde_json::to_value(effort)
+    {
+        body.insert("reasoning_effort".to_string(), effort);
+    }
+    if let Some(text) = request.text.as_ref() {
+        if let Some(verbosity) = text.verbosity.as_ref()
+            && let Ok(verbosity) = serde_json::to_value(verbosity)
+        {
+            body.insert("verbosity".to_string(), verbosity);
+        }
+        if let Some(format) = text.format.as_ref() {
+            body.insert(
+                "response_format".to_string(),
+                json!({
+                    "type": "json_schema",
+                    "json_schema": {
+                        "name": format.name,
+                        "schema": format.schema,
+                        "strict": format.strict,
+                    },
+                }),
+            );
+        }
+    }
+    if let Some(service_tier) = request.service_tier.as_ref() {
+        body.insert(
+            "service_tier".to_string(),
+            Value::String(service_tier.clone()),
+        );
+    }
+    if let Some(prompt_cache_key) = request.prompt_cache_key.as_ref() {
+        body.insert(
+            "prompt_cache_key".to_string(),
+            Value::String(prompt_cache_key.clone()),
+        );
+    }
+
+    ChatRequest {
+        body: Value::Object(body),
+        tools: tool_names,
+    }
+}
+
+/// Wraps the flattened tool specs in Chat Completions `function` tools.
+fn chat_tools(tools: &[Value]) -> (Vec<Value>, FlatToolNames) {
+    let (functions, names) = flatten_tools(tools);
+    let tools = functions
+        .into_iter()
+        .map(|function| {
+            let mut definition = Map::new();
+            definition.insert("name".to_string(), Value::String(function.name));
+            if !function.description.is_empty() {
+                definition.insert(
+                    "description".to_string(),
+                    Value::String(function.description),
+                );
+            }
+            definition.insert("parameters".to_string(), function.parameters);
+            if function.strict {
+                definition.insert("strict".to_string(), Value::Bool(true));
+            }
+            json!({ "type": "function", "function": definition })
+        })
+        .collect();
+    (tools, names)
+}
+
+/// Converts Responses history into Chat Completions messages.
+///
+/// Tool calls are folded into the preceding assistant message so each batch of
+/// parallel calls is followed by its `tool` results. Reasoning, hosted tool calls,
+/// and compaction items have no Chat Completions form and are skipped.
+fn chat_messages(instructions: &str, input: &[ResponseItem]) -> Vec<Value> {
+    let mut messages = Vec::new();
+    if !instructions.is_empty() {
+        messages.push(json!({ "role": "system", "content": instructions }));
+    }
+    for item in input {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = match role.as_str() {
+                    "developer" | "system" => "system",
+                    "assistant" => "assistant",
+                    _ => "user",
+                };
+                messages.push(json!({
+                    "role": role,
+                    "content": chat_message_content(content),
+                }));
+            }
+            ResponseItem::FunctionCall {
+                name,
+                namespace,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let name = flat_function_name(namespace.as_deref(), name);
+                push_tool_call(&mut messages, call_id, &name, arguments.clone());
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                let arguments = json!({ FREEFORM_INPUT_ARGUMENT: input }).to_string();
+                push_tool_call(&mut messages, call_id, name, arguments);
+            }
+            ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                action,
+                ..
+            } => {
+                let arguments = serde_json::to_string(action).unwrap_or_default();
+                push_tool_call(&mut messages, call_id, LOCAL_SHELL_TOOL_NAME, arguments);
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput {
+                call_id, output, ..
+            } => {
+                messages.push(json!({
+                    "role": "tool",
+                    "tool_call_id": call_id,
+                    "content": output.body.to_text().unwrap_or_default(),
+                }));
+            }
+            ResponseItem::Reasoning { .. }
+            | ResponseItem::LocalShellCall { call_id: None, .. }
+            | ResponseItem::ToolSearchCall { .. }
+            | ResponseItem::ToolSearchOutput { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::ImageGenerationCall { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+    messages
+}
+
+/// Text-only content is sent as a plain string, which every server accepts;
+/// anything with images uses the content-part array form.
+fn chat_message_content(content: &[ContentItem]) -> Value {
+    let text_only = content.iter().all(|item| {
+        matches!(
+            item,
+            ContentItem::InputText { .. } | ContentItem::OutputText { .. }
+        )
+    });
+    if text_only {
+        let text = content
+            .iter()
+            .filter_map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    Some(text.as_str())
+                }
+                ContentItem::InputImage { .. } => None,
+            })
+            .collect::<String>();
+        return Value::String(text);
+    }
+
+    Value::Array(
+        content
+            .iter()
+            .map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    json!({ "type": "text", "text": text })
+                }
+                ContentItem::InputImage { image_url, detail } => {
+                    let mut image = Map::new();
+                    image.insert("url".to_string(), Value::String(image_url.clone()));
+                    if let Some(detail) = detail {
+                        image.insert(
+                            "detail".to_string(),
+                            Value::String(chat_image_detail(*detail).to_string()),
+                        );
+                    }
+                    json!({ "type": "image_url", "image_url": image })
+                }
+            })
+            .collect(),
+    )
+}
+
+fn chat_image_detail(detail: ImageDetail) -> &'static str {
+    match detail {
+        ImageDetail::Auto => "auto",
+        ImageDetail::Low => "low",
+        ImageDetail::High | ImageDetail::Original => "high",
+    }
+}
+
+fn push_tool_call(messages: &mut Vec<Value>, call_id: &str, name: &str, arguments: String) {
+    let tool_call = json!({
+        "id": call_id,
+        "type": "function",
+        "function": {
+            "name": name,
+            "arguments": arguments,
+        },
+    });
+    if let Some(Value::Object(last)) = messages.last_mut()
+        && last.get("role").and_then(Value::as_str) == Some("assistant")
+    {
+        match last.get_mut("tool_calls") {
+            Some(Value::Array(tool_calls)) => tool_calls.push(tool_call),
+            _ => {
+                last.insert("tool_calls".to_string(), Value::Array(vec![tool_call]));
+            }
+        }
+        return;
+    }
+    messages.push(json!({
+        "role": "assistant",
+        "content": Value::Null,
+        "tool_calls": [tool_call],
+    }));
+}
+
+#[cfg(test)]
+#[path = "chat_tests.rs"]
+mod tests;
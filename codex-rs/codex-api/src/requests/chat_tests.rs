@@ -0,0 +1,214 @@
+use super::*;
+use crate::common::Reasoning;
+use crate::requests::flat_tools::FlatTool;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::openai_models::ReasoningEffort;
+use pretty_assertions::assert_eq;
+
+fn request(input: Vec<ResponseItem>, tools: Vec<Value>) -> ResponsesApiRequest {
+    ResponsesApiRequest {
+        model: "local-model".to_string(),
+        instructions: "You are a helpful assistant.".to_string(),
+        input,
+        tools,
+        tool_choice: "auto".to_string(),
+        parallel_tool_calls: true,
+        reasoning: None,
+        store: false,
+        stream: true,
+        include: Vec::new(),
+        service_tier: None,
+        prompt_cache_key: None,
+        text: None,
+        client_metadata: None,
+    }
+}
+
+fn function_call(call_id: &str, name: &str, arguments: &str) -> ResponseItem {
+    ResponseItem::FunctionCall {
+        id: None,
+        name: name.to_string(),
+        namespace: None,
+        arguments: arguments.to_string(),
+        call_id: call_id.to_string(),
+    }
+}
+
+fn function_call_output(call_id: &str, output: &str) -> ResponseItem {
+    ResponseItem::FunctionCallOutput {
+        call_id: call_id.to_string(),
+        output: FunctionCallOutputPayload::from_text(output.to_string()),
+    }
+}
+
+#[test]
+fn history_groups_parallel_tool_calls_under_one_assistant_message() {
+    let input = vec![
+        ResponseItem::Message {
+            id: None,
+            role: "developer".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "Prefer short answers.".to_string(),
+            }],
+            phase: None,
+        },
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "List both directories.".to_string(),
+            }],
+            phase: None,
+        },
+        ResponseItem::Reasoning {
+            id: "rs_1".to_string(),
+            summary: Vec::new(),
+            content: None,
+            encrypted_content: None,
+        },
+        ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText {
+                text: "Listing now.".to_string(),
+            }],
+            phase: None,
+        },
+        function_call("call_a", "shell", r#"{"command":["ls","a"]}"#),
+        function_call("call_b", "shell", r#"{"command":["ls","b"]}"#),
+        function_call_output("call_a", "a.txt"),
+        function_call_output("call_b", "b.txt"),
+    ];
+
+    let chat = build_chat_request(&request(input, Vec::new()));
+
+    assert_eq!(
+        chat.body,
+        json!({
+            "model": "local-model",
+            "messages": [
+                { "role": "system", "content": "You are a helpful assistant." },
+                { "role": "system", "content": "Prefer short answers." },
+                { "role": "user", "content": "List both directories." },
+                {
+                    "role": "assistant",
+                    "content": "Listing now.",
+                    "tool_calls": [
+                        {
+                            "id": "call_a",
+                            "type": "function",
+                            "function": { "name": "shell", "arguments": r#"{"command":["ls","a"]}"# },
+                        },
+                        {
+                            "id": "call_b",
+                            "type": "function",
+                            "function": { "name": "shell", "arguments": r#"{"command":["ls","b"]}"# },
+                        },
+                    ],
+                },
+                { "role": "tool", "tool_call_id": "call_a", "content": "a.txt" },
+                { "role": "tool", "tool_call_id": "call_b", "content": "b.txt" },
+            ],
+            "stream": true,
+            "stream_options": { "include_usage": true },
+        })
+    );
+}
+
+#[test]
+fn tools_are_flattened_into_chat_functions_and_resolved_back() {
+    let tools = vec![
+        json!({
+            "type": "function",
+            "name": "shell",
+            "description": "Runs a command.",
+            "strict": false,
+            "parameters": { "type": "object", "properties": {} },
+        }),
+        json!({
+            "type": "namespace",
+            "name": "mcp__docs__",
+            "description": "Docs server.",
+            "tools": [{
+                "type": "function",
+                "name": "search",
+                "description": "Searches docs.",
+                "strict": false,
+                "defer_loading": true,
+                "parameters": { "type": "object", "properties": {} },
+            }],
+        }),
+        json!({
+            "type": "custom",
+            "name": "apply_patch",
+            "description": "Applies a patch.",
+            "format": { "type": "grammar", "syntax": "lark", "definition": "start: patch" },
+        }),
+        json!({ "type": "web_search" }),
+    ];
+    let mut request = request(Vec::new(), tools);
+    request.reasoning = Some(Reasoning {
+        effort: Some(ReasoningEffort::High),
+        summary: None,
+    });
+
+    let chat = build_chat_request(&request);
+
+    assert_eq!(
+        chat.body.get("tools"),
+        Some(&json!([
+            {
+                "type": "function",
+                "function": {
+                    "name": "shell",
+                    "description": "Runs a command.",
+                    "parameters": { "type": "object", "properties": {} },
+                },
+            },
+            {
+                "type": "function",
+                "function": {
+                    "name": "mcp__docs__search",
+                    "description": "Searches docs.",
+                    "parameters": { "type": "object", "properties": {} },
+                },
+            },
+            {
+                "type": "function",
+                "function": {
+                    "name": "apply_patch",
+                    "description": "Applies a patch.\n\nThe `input` argument must follow this lark grammar:\nstart: patch",
+                    "parameters": {
+                        "type": "object",
+                        "properties": { "input": { "type": "string" } },
+                        "required": ["input"],
+                        "additionalProperties": false,
+                    },
+                },
+            },
+        ]))
+    );
+    assert_eq!(chat.body.get("tool_choice"), Some(&json!("auto")));
+    assert_eq!(chat.body.get("parallel_tool_calls"), Some(&json!(true)));
+    assert_eq!(chat.body.get("reasoning_effort"), Some(&json!("high")));
+    assert_eq!(
+        chat.tools.resolve("mcp__docs__search"),
+        FlatTool::Function {
+            namespace: Some("mcp__docs__".to_string()),
+            name: "search".to_string(),
+        }
+    );
+    assert_eq!(
+        chat.tools.resolve("apply_patch"),
+        FlatTool::Freeform {
+            name: "apply_patch".to_string(),
+        }
+    );
+    assert_eq!(
+        chat.tools.resolve("unknown"),
+        FlatTool::Function {
+            namespace: None,
+            name: "unknown".to_string(),
+        }
+    );
+}
@@ -0,0 +1,189 @@
+//! Flattens Responses tool specs for wire protocols that only know plain functions.
+//!
+//! Chat Completions and Messages-style APIs have no namespaces or freeform tools, so
+//! namespaced functions are advertised as `{namespace}{name}` and freeform tools as
+//! functions taking a single `input` string. [`FlatToolNames`] records the mapping
+//! so streamed calls can be turned back into the Responses items Codex dispatches.
+
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Name given to local shell calls replayed as plain function calls.
+pub(crate) const LOCAL_SHELL_TOOL_NAME: &str = "local_shell";
+/// Argument that carries the raw input of freeform tools exposed as functions.
+pub(crate) const FREEFORM_INPUT_ARGUMENT: &str = "input";
+
+/// A tool advertised as a plain function.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FlatFunction {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) parameters: Value,
+    pub(crate) strict: bool,
+}
+
+/// Maps flat function names back onto the Responses tool they stand for.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FlatToolNames {
+    tools: HashMap<String, FlatTool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FlatTool {
+    Function {
+        namespace: Option<String>,
+        name: String,
+    },
+    Freeform {
+        name: String,
+    },
+}
+
+impl FlatToolNames {
+    /// Resolves a streamed function name; unknown names are treated as plain functions.
+    pub(crate) fn resolve(&self, flat_name: &str) -> FlatTool {
+        self.tools
+            .get(flat_name)
+            .cloned()
+            .unwrap_or_else(|| FlatTool::Function {
+                namespace: None,
+                name: flat_name.to_string(),
+            })
+    }
+
+    /// Extracts the freeform tool input from the JSON arguments of its function call.
+    pub(crate) fn freeform_input(arguments: &str) -> String {
+        serde_json::from_str::<Value>(arguments)
+            .ok()
+            .and_then(|value| {
+                value
+                    .get(FREEFORM_INPUT_ARGUMENT)
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| arguments.to_string())
+    }
+}
+
+pub(crate) fn flat_function_name(namespace: Option<&str>, name: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{namespace}{name}"),
+        None => name.to_string(),
+    }
+}
+
+/// Converts serialized Responses tool specs into plain functions.
+///
+/// Hosted tools with no function equivalent (web search, image generation, tool
+/// search, local shell) are dropped. Deferred tools are advertised up front since
+/// there is no tool search to load them later.
+pub(crate) fn flatten_tools(tools: &[Value]) -> (Vec<FlatFunction>, FlatToolNames) {
+    let mut functions = Vec::new();
+    let mut names = FlatToolNames::default();
+    for tool in tools {
+        match tool.get("type").and_then(Value::as_str) {
+            Some("function") => {
+                if let Some(function) = flat_function(tool, /*namespace*/ None)
+                    && let Some(name) = tool.get("name").and_then(Value::as_str)
+                {
+                    names.tools.insert(
+                        function.name.clone(),
+                        FlatTool::Function {
+                            namespace: None,
+                            name: name.to_string(),
+                        },
+                    );
+                    functions.push(function);
+                }
+            }
+            Some("namespace") => {
+                let Some(namespace) = tool.get("name").and_then(Value::as_str) else {
+                    continue;
+                };
+                let members = tool
+                    .get("tools")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten();
+                for member in members {
+                    if let Some(function) = flat_function(member, Some(namespace))
+                        && let Some(name) = member.get("name").and_then(Value::as_str)
+                    {
+                        names.tools.insert(
+                            function.name.clone(),
+                            FlatTool::Function {
+                                namespace: Some(namespace.to_string()),
+                                name: name.to_string(),
+                            },
+                        );
+                        functions.push(function);
+                    }
+                }
+            }
+            Some("custom") => {
+                if let Some(function) = flat_freeform_function(tool) {
+                    names.tools.insert(
+                        function.name.clone(),
+                        FlatTool::Freeform {
+                            name: function.name.clone(),
+                        },
+                    );
+                    functions.push(function);
+                }
+            }
+            _ => {}
+        }
+    }
+    (functions, names)
+}
+
+fn flat_function(tool: &Value, namespace: Option<&str>) -> Option<FlatFunction> {
+    let name = tool.get("name").and_then(Value::as_str)?;
+    Some(FlatFunction {
+        name: flat_function_name(namespace, name),
+        description: tool
+            .get("description")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        parameters: tool
+            .get("parameters")
+            .cloned()
+            .unwrap_or_else(|| json!({ "type": "object", "properties": {} })),
+        strict: tool.get("strict").and_then(Value::as_bool) == Some(true),
+    })
+}
+
+fn flat_freeform_function(tool: &Value) -> Option<FlatFunction> {
+    let name = tool.get("name").and_then(Value::as_str)?;
+    let mut description = tool
+        .get("description")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    if let Some(format) = tool.get("format")
+        && let Some(definition) = format.get("definition").and_then(Value::as_str)
+    {
+        let syntax = format
+            .get("syntax")
+            .and_then(Value::as_str)
+            .unwrap_or("grammar");
+        description.push_str(&format!(
+            "\n\nThe `{FREEFORM_INPUT_ARGUMENT}` argument must follow this {syntax} grammar:\n{definition}"
+        ));
+    }
+    Some(FlatFunction {
+        name: name.to_string(),
+        description,
+        parameters: json!({
+            "type": "object",
+            "properties": {
+                FREEFORM_INPUT_ARGUMENT: { "type": "string" },
+            },
+            "required": [FREEFORM_INPUT_ARGUMENT],
+            "additionalProperties": false,
+        }),
+        strict: false,
+    })
+}
@@ -0,0 +1,312 @@
+//! Converts Responses API requests into Messages API (`/v1/messages`) request bodies.
+//!
+//! The Messages API keeps the system prompt outside the conversation, requires
+//! strictly alternating `user`/`assistant` turns made of content blocks, and pairs
+//! each `tool_use` block with a `tool_result` block in the following user turn.
+//! Thinking blocks are replayed with their signatures so extended thinking keeps
+//! working across tool calls.
+
+use crate::common::ResponsesApiRequest;
+use crate::requests::flat_tools::FREEFORM_INPUT_ARGUMENT;
+use crate::requests::flat_tools::FlatToolNames;
+use crate::requests::flat_tools::LOCAL_SHELL_TOOL_NAME;
+use crate::requests::flat_tools::flat_function_name;
+use crate::requests::flat_tools::flatten_tools;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+
+/// Output token cap sent when the provider does not configure `max_output_tokens`; the
+/// Messages API requires one.
+pub(crate) const DEFAULT_MESSAGES_MAX_TOKENS: u64 = 32_000;
+
+/// Output tokens kept for the visible response when the thinking budget is capped.
+const MIN_RESPONSE_TOKENS: u64 = 4_000;
+
+/// Smallest thinking budget the Messages API accepts.
+const MIN_THINKING_BUDGET_TOKENS: u64 = 1_024;
+
+/// A Messages API request body plus the tool names it advertised.
+pub(crate) struct MessagesRequest {
+    pub(crate) body: Value,
+    pub(crate) tools: FlatToolNames,
+}
+
+/// `max_output_tokens` is the provider's configured output cap. The thinking budget is lowered
+/// to stay below it, and thinking is left off when the cap leaves no room for it.
+pub(crate) fn build_messages_request(
+    request: &ResponsesApiRequest,
+    max_output_tokens: Option<u64>,
+) -> MessagesRequest {
+    let max_tokens = max_output_tokens.unwrap_or(DEFAULT_MESSAGES_MAX_TOKENS);
+    let (functions, tool_names) = flatten_tools(&request.tools);
+    let (system, messages) = messages_history(&request.instructions, &request.input);
+
+    let mut body = Map::new();
+    body.insert("model".to_string(), Value::String(request.model.clone()));
+    body.insert("max_tokens".to_string(), json!(max_tokens));
+    if !system.is_empty() {
+        body.insert("system".to_string(), Value::String(system));
+    }
+    body.insert("messages".to_string(), Value::Array(messages));
+    body.insert("stream".to_string(), Value::Bool(true));
+    if !functions.is_empty() {
+        let tools = functions
+            .into_iter()
+            .map(|function| {
+                json!({
+                    "name": function.name,
+                    "description": function.description,
+                    "input_schema": function.parameters,
+                })
+            })
+            .collect();
+        body.insert("tools".to_string(), Value::Array(tools));
+        body.insert(
+            "tool_choice".to_string(),
+            json!({
+                "type": "auto",
+                "disable_parallel_tool_use": !request.parallel_tool_calls,
+            }),
+        );
+    }
+    if let Some(budget_tokens) = request
+        .reasoning
+        .as_ref()
+        .and_then(|reasoning| reasoning.effort)
+        .and_then(thinking_budget_tokens)
+        .map(|budget| budget.min(max_tokens.saturating_sub(MIN_RESPONSE_TOKENS)))
+        .filter(|budget| *budget >= MIN_THINKING_BUDGET_TOKENS)
+    {
+        body.insert(
+            "thinking".to_string(),
+            json!({ "type": "enabled", "budget_tokens": budget_tokens }),
+        );
+    }
+
+    MessagesRequest {
+        body: Value::Object(body),
+        tools: tool_names,
+    }
+}
+
+/// Thinking budget for each reasoning effort; `none` and `minimal` disable thinking.
+fn thinking_budget_tokens(effort: ReasoningEffort) -> Option<u64> {
+    match effort {
+        ReasoningEffort::None | ReasoningEffort::Minimal => None,
+        ReasoningEffort::Low => Some(4_000),
+        ReasoningEffort::Medium => Some(10_000),
+        ReasoningEffort::High => Some(20_000),
+        ReasoningEffort::XHigh => Some(28_000),
+    }
+}
+
+/// Splits Responses history into the system prompt and alternating conversation turns.
+///
+/// Developer and system messages are appended to the system prompt. Hosted tool
+/// calls, compaction items, and reasoning without a thinking signature have no
+/// Messages form and are skipped.
+fn messages_history(instructions: &str, input: &[ResponseItem]) -> (String, Vec<Value>) {
+    let mut system = vec![instructions.to_string()];
+    let mut messages = Vec::new();
+    for item in input {
+        match item {
+            ResponseItem::Message { role, content, .. } => match role.as_str() {
+                "developer" | "system" => system.push(content_text(content)),
+                "assistant" => {
+                    let text = content_text(content);
+                    if !text.is_empty() {
+                        push_block(
+                            &mut messages,
+                            "assistant",
+                            json!({ "type": "text", "text": text }),
+                        );
+                    }
+                }
+                _ => {
+                    for block in content.iter().filter_map(user_content_block) {
+                        push_block(&mut messages, "user", block);
+                    }
+                }
+            },
+            ResponseItem::Reasoning {
+                content,
+                encrypted_content: Some(signature),
+                ..
+            } => {
+                let thinking = content
+                    .iter()
+                    .flatten()
+                    .map(|content| match content {
+                        ReasoningItemContent::ReasoningText { text }
+                        | ReasoningItemContent::Text { text } => text.as_str(),
+                    })
+                    .collect::<String>();
+                let block = if content.is_some() {
+                    json!({ "type": "thinking", "thinking": thinking, "signature": signature })
+                } else {
+                    json!({ "type": "redacted_thinking", "data": signature })
+                };
+                push_block(&mut messages, "assistant", block);
+            }
+            ResponseItem::FunctionCall {
+                name,
+                namespace,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let input = serde_json::from_str::<Value>(arguments)
+                    .ok()
+                    .filter(Value::is_object)
+                    .unwrap_or_else(|| json!({}));
+                let name = flat_function_name(namespace.as_deref(), name);
+                push_block(&mut messages, "assistant", tool_use(call_id, &name, input));
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                let input = json!({ FREEFORM_INPUT_ARGUMENT: input });
+                push_block(&mut messages, "assistant", tool_use(call_id, name, input));
+            }
+            ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                action,
+                ..
+            } => {
+                let input = serde_json::to_value(action).unwrap_or_else(|_| json!({}));
+                push_block(
+                    &mut messages,
+                    "assistant",
+                    tool_use(call_id, LOCAL_SHELL_TOOL_NAME, input),
+                );
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput {
+                call_id, output, ..
+            } => {
+                push_block(&mut messages, "user", tool_result(call_id, output));
+            }
+            ResponseItem::Reasoning {
+                encrypted_content: None,
+                ..
+            }
+            | ResponseItem::LocalShellCall { call_id: None, .. }
+            | ResponseItem::ToolSearchCall { .. }
+            | ResponseItem::ToolSearchOutput { .. }
+            | ResponseItem::WebSearchCall { .. }
+            | ResponseItem::ImageGenerationCall { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+    let system = system
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    (system, messages)
+}
+
+fn content_text(content: &[ContentItem]) -> String {
+    content
+        .iter()
+        .filter_map(|item| match item {
+            ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                Some(text.as_str())
+            }
+            ContentItem::InputImage { .. } => None,
+        })
+        .collect()
+}
+
+fn user_content_block(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            (!text.is_empty()).then(|| json!({ "type": "text", "text": text }))
+        }
+        ContentItem::InputImage { image_url, .. } => Some(image_block(image_url)),
+    }
+}
+
+/// Data URLs become base64 image sources; anything else is passed as a URL source.
+fn image_block(image_url: &str) -> Value {
+    if let Some(rest) = image_url.strip_prefix("data:")
+        && let Some((media_type, data)) = rest.split_once(";base64,")
+    {
+        return json!({
+            "type": "image",
+            "source": { "type": "base64", "media_type": media_type, "data": data },
+        });
+    }
+    json!({
+        "type": "image",
+        "source": { "type": "url", "url": image_url },
+    })
+}
+
+fn tool_use(call_id: &str, name: &str, input: Value) -> Value {
+    json!({
+        "type": "tool_use",
+        "id": call_id,
+        "name": name,
+        "input": input,
+    })
+}
+
+fn tool_result(call_id: &str, output: &FunctionCallOutputPayload) -> Value {
+    let content = match &output.body {
+        FunctionCallOutputBody::Text(text) => Value::String(text.clone()),
+        FunctionCallOutputBody::ContentItems(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| match item {
+                    FunctionCallOutputContentItem::InputText { text } => {
+                        json!({ "type": "text", "text": text })
+                    }
+                    FunctionCallOutputContentItem::InputImage { image_url, .. } => {
+                        image_block(image_url)
+                    }
+                })
+                .collect(),
+        ),
+    };
+    let mut block = Map::new();
+    block.insert("type".to_string(), Value::String("tool_result".to_string()));
+    block.insert(
+        "tool_use_id".to_string(),
+        Value::String(call_id.to_string()),
+    );
+    block.insert("content".to_string(), content);
+    if output.success == Some(false) {
+        block.insert("is_error".to_string(), Value::Bool(true));
+    }
+    Value::Object(block)
+}
+
+/// Appends a content block, merging it into the last message when the role matches
+/// so turns keep alternating.
+fn push_block(messages: &mut Vec<Value>, role: &str, block: Value) {
+    if let Some(Value::Object(last)) = messages.last_mut()
+        && last.get("role").and_then(Value::as_str) == Some(role)
+        && let Some(Value::Array(content)) = last.get_mut("content")
+    {
+        content.push(block);
+        return;
+    }
+    messages.push(json!({ "role": role, "content": [block] }));
+}
+
+#[cfg(test)]
+#[path = "messages_tests.rs"]
+mod tests;
@@ -0,0 +1,213 @@
+use super::*;
+use crate::common::Reasoning;
+use pretty_assertions::assert_eq;
+
+fn request(input: Vec<ResponseItem>, tools: Vec<Value>) -> ResponsesApiRequest {
+    ResponsesApiRequest {
+        model: "claude-sonnet".to_string(),
+        instructions: "You are a coding agent.".to_string(),
+        input,
+        tools,
+        tool_choice: "auto".to_string(),
+        parallel_tool_calls: true,
+        reasoning: None,
+        store: false,
+        stream: true,
+        include: Vec::new(),
+        service_tier: None,
+        prompt_cache_key: None,
+        text: None,
+        client_metadata: None,
+    }
+}
+
+fn message(role: &str, content: Vec<ContentItem>) -> ResponseItem {
+    ResponseItem::Message {
+        id: None,
+        role: role.to_string(),
+        content,
+        phase: None,
+    }
+}
+
+#[test]
+fn history_alternates_turns_and_replays_thinking_with_tool_results() {
+    let input = vec![
+        message(
+            "developer",
+            vec![ContentItem::InputText {
+                text: "Sandbox is read-only.".to_string(),
+            }],
+        ),
+        message(
+            "user",
+            vec![
+                ContentItem::InputText {
+                    text: "What is in this screenshot and in a.txt?".to_string(),
+                },
+                ContentItem::InputImage {
+                    image_url: "data:image/png;base64,AAAA".to_string(),
+                    detail: None,
+                },
+            ],
+        ),
+        ResponseItem::Reasoning {
+            id: "rs_1".to_string(),
+            summary: Vec::new(),
+            content: Some(vec![ReasoningItemContent::ReasoningText {
+                text: "Read the file first.".to_string(),
+            }]),
+            encrypted_content: Some("sig-1".to_string()),
+        },
+        ResponseItem::FunctionCall {
+            id: None,
+            name: "read".to_string(),
+            namespace: Some("mcp__fs__".to_string()),
+            arguments: r#"{"path":"a.txt"}"#.to_string(),
+            call_id: "toolu_a".to_string(),
+        },
+        ResponseItem::CustomToolCall {
+            id: None,
+            status: None,
+            call_id: "toolu_b".to_string(),
+            name: "apply_patch".to_string(),
+            input: "*** Begin Patch".to_string(),
+        },
+        ResponseItem::FunctionCallOutput {
+            call_id: "toolu_a".to_string(),
+            output: FunctionCallOutputPayload::from_text("hello".to_string()),
+        },
+        ResponseItem::CustomToolCallOutput {
+            call_id: "toolu_b".to_string(),
+            name: None,
+            output: FunctionCallOutputPayload {
+                body: FunctionCallOutputBody::Text("patch rejected".to_string()),
+                success: Some(false),
+            },
+        },
+        message(
+            "assistant",
+            vec![ContentItem::OutputText {
+                text: "The file says hello.".to_string(),
+            }],
+        ),
+    ];
+
+    let messages =
+        build_messages_request(&request(input, Vec::new()), /*max_output_tokens*/ None);
+
+    assert_eq!(
+        messages.body,
+        json!({
+            "model": "claude-sonnet",
+            "max_tokens": DEFAULT_MESSAGES_MAX_TOKENS,
+            "system": "You are a coding agent.\n\nSandbox is read-only.",
+            "messages": [
+                {
+                    "role": "user",
+                    "content": [
+                        { "type": "text", "text": "What is in this screenshot and in a.txt?" },
+                        {
+                            "type": "image",
+                            "source": { "type": "base64", "media_type": "image/png", "data": "AAAA" },
+                        },
+                    ],
+                },
+                {
+                    "role": "assistant",
+                    "content": [
+                        { "type": "thinking", "thinking": "Read the file first.", "signature": "sig-1" },
+                        {
+                            "type": "tool_use",
+                            "id": "toolu_a",
+                            "name": "mcp__fs__read",
+                            "input": { "path": "a.txt" },
+                        },
+                        {
+                            "type": "tool_use",
+                            "id": "toolu_b",
+                            "name": "apply_patch",
+                            "input": { "input": "*** Begin Patch" },
+                        },
+                    ],
+                },
+                {
+                    "role": "user",
+                    "content": [
+                        { "type": "tool_result", "tool_use_id": "toolu_a", "content": "hello" },
+                        {
+                            "type": "tool_result",
+                            "tool_use_id": "toolu_b",
+                            "content": "patch rejected",
+                            "is_error": true,
+                        },
+                    ],
+                },
+                {
+                    "role": "assistant",
+                    "content": [{ "type": "text", "text": "The file says hello." }],
+                },
+            ],
+            "stream": true,
+        })
+    );
+}
+
+#[test]
+fn tools_and_reasoning_effort_map_to_messages_fields() {
+    let tools = vec![
+        json!({
+            "type": "function",
+            "name": "shell",
+            "description": "Runs a command.",
+            "strict": false,
+            "parameters": { "type": "object", "properties": {} },
+        }),
+        json!({ "type": "image_generation", "output_format": "png" }),
+    ];
+    let mut request = request(Vec::new(), tools);
+    request.parallel_tool_calls = false;
+    request.reasoning = Some(Reasoning {
+        effort: Some(ReasoningEffort::Medium),
+        summary: None,
+    });
+
+    let messages = build_messages_request(&request, /*max_output_tokens*/ None);
+
+    assert_eq!(
+        messages.body.get("tools"),
+        Some(&json!([{
+            "name": "shell",
+            "description": "Runs a command.",
+            "input_schema": { "type": "object", "properties": {} },
+        }]))
+    );
+    assert_eq!(
+        messages.body.get("tool_choice"),
+        Some(&json!({ "type": "auto", "disable_parallel_tool_use": true }))
+    );
+    assert_eq!(
+        messages.body.get("thinking"),
+        Some(&json!({ "type": "enabled", "budget_tokens": 10_000 }))
+    );
+}
+
+#[test]
+fn thinking_budget_stays_below_configured_max_tokens() {
+    let mut request = request(Vec::new(), Vec::new());
+    request.reasoning = Some(Reasoning {
+        effort: Some(ReasoningEffort::High),
+        summary: None,
+    });
+
+    let messages = build_messages_request(&request, Some(12_000));
+    assert_eq!(messages.body.get("max_tokens"), Some(&json!(12_000)));
+    assert_eq!(
+        messages.body.get("thinking"),
+        Some(&json!({ "type": "enabled", "budget_tokens": 8_000 }))
+    );
+
+    let messages = build_messages_request(&request, Some(4_500));
+    assert_eq!(messages.body.get("max_tokens"), Some(&json!(4_500)));
+    assert_eq!(messages.body.get("thinking"), None);
+}
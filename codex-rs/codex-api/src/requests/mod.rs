@@ -1,5 +1,11 @@
+pub(crate) mod chat;
+pub(crate) mod flat_tools;
 pub(crate) mod headers;
+pub(crate) mod messages;
 pub(crate) mod responses;
 
+pub(crate) use chat::build_chat_request;
+pub(crate) use flat_tools::FlatToolNames;
+pub(crate) use messages::build_messages_request;
 pub use responses::Compression;
 pub(crate) use responses::attach_item_ids;
@@ -0,0 +1,438 @@
+//! Translates Chat Completions streaming chunks into Responses stream events.
+//!
+//! Chat Completions streams a single assistant turn as `choices[0].delta`
+//! fragments: text in `content`, reasoning in `reasoning_content` (or
+//! `reasoning` on some servers), and tool calls keyed by `index` so several
+//! parallel calls can interleave. The fragments are reassembled into the same
+//! `OutputItemAdded`/delta/`OutputItemDone`/`Completed` sequence the Responses
+//! stream produces.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::requests::FlatToolNames;
+use crate::requests::flat_tools::FlatTool;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+const DONE_SENTINEL: &str = "[DONE]";
+const DEFAULT_RESPONSE_ID: &str = "chatcmpl";
+
+pub(crate) fn spawn_chat_completions_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    tools: FlatToolNames,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_chat_sse(
+        stream_response.bytes,
+        tx_event,
+        idle_timeout,
+        telemetry,
+        tools,
+    ));
+
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+    #[serde(default)]
+    usage: Option<ChatUsage>,
+    #[serde(default)]
+    error: Option<ChatError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    #[serde(default)]
+    index: u32,
+    #[serde(default)]
+    delta: Option<ChatDelta>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatDelta {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    reasoning_content: Option<String>,
+    #[serde(default)]
+    reasoning: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<ChatToolCallDelta>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatToolCallDelta {
+    #[serde(default)]
+    index: u32,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<ChatFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatFunctionDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatUsage {
+    #[serde(default)]
+    prompt_tokens: i64,
+    #[serde(default)]
+    completion_tokens: i64,
+    #[serde(default)]
+    total_tokens: i64,
+    #[serde(default)]
+    prompt_tokens_details: Option<ChatPromptTokensDetails>,
+    #[serde(default)]
+    completion_tokens_details: Option<ChatCompletionTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatPromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: i64,
+}
+
+impl From<ChatUsage> for TokenUsage {
+    fn from(val: ChatUsage) -> Self {
+        TokenUsage {
+            input_tokens: val.prompt_tokens,
+            cached_input_tokens: val
+                .prompt_tokens_details
+                .map(|d| d.cached_tokens)
+                .unwrap_or(0),
+            output_tokens: val.completion_tokens,
+            reasoning_output_tokens: val
+                .completion_tokens_details
+                .map(|d| d.reasoning_tokens)
+                .unwrap_or(0),
+            total_tokens: val.total_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatError {
+    #[serde(default)]
+    code: Option<serde_json::Value>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    id: Option<String>,
+    name: String,
+    arguments: String,
+}
+
+/// Accumulates one streamed assistant turn.
+#[derive(Default)]
+struct ChatStreamState {
+    tools: FlatToolNames,
+    response_id: Option<String>,
+    created: bool,
+    reasoning: Option<String>,
+    message: Option<String>,
+    tool_calls: BTreeMap<u32, PendingToolCall>,
+    finish_reason: Option<String>,
+    usage: Option<TokenUsage>,
+}
+
+impl ChatStreamState {
+    fn new(tools: FlatToolNames) -> Self {
+        Self {
+            tools,
+            ..Self::default()
+        }
+    }
+
+    fn response_id(&self) -> &str {
+        self.response_id.as_deref().unwrap_or(DEFAULT_RESPONSE_ID)
+    }
+
+    fn handle_chunk(&mut self, chunk: ChatCompletionChunk) -> Result<Vec<ResponseEvent>, ApiError> {
+        if let Some(error) = chunk.error {
+            return Err(chat_error(error));
+        }
+
+        let mut events = Vec::new();
+        if self.response_id.is_none() {
+            self.response_id = chunk.id;
+        }
+        if !self.created {
+            self.created = true;
+            events.push(ResponseEvent::Created);
+        }
+        if let Some(usage) = chunk.usage {
+            self.usage = Some(usage.into());
+        }
+
+        // Only the first choice is requested; servers that ignore `n` still
+        // number the primary completion 0.
+        for choice in chunk.choices.into_iter().filter(|choice| choice.index == 0) {
+            let delta = choice.delta.unwrap_or_default();
+            if let Some(reasoning) = delta.reasoning_content.or(delta.reasoning)
+                && !reasoning.is_empty()
+            {
+                self.push_reasoning_delta(reasoning, &mut events);
+            }
+            if let Some(content) = delta.content
+                && !content.is_empty()
+            {
+                self.push_text_delta(content, &mut events);
+            }
+            for tool_call in delta.tool_calls.into_iter().flatten() {
+                let pending = self.tool_calls.entry(tool_call.index).or_default();
+                if let Some(id) = tool_call.id
+                    && !id.is_empty()
+                {
+                    pending.id = Some(id);
+                }
+                if let Some(function) = tool_call.function {
+                    if let Some(name) = function.name {
+                        pending.name.push_str(&name);
+                    }
+                    if let Some(arguments) = function.arguments {
+                        pending.arguments.push_str(&arguments);
+                    }
+                }
+            }
+            if let Some(finish_reason) = choice.finish_reason {
+                self.finish_reason = Some(finish_reason);
+            }
+        }
+
+        if self.finish_reason.is_some() {
+            self.flush_items(&mut events);
+        }
+        Ok(events)
+    }
+
+    fn push_reasoning_delta(&mut self, delta: String, events: &mut Vec<ResponseEvent>) {
+        if self.reasoning.is_none() {
+            self.reasoning = Some(String::new());
+            events.push(ResponseEvent::OutputItemAdded(
+                self.reasoning_item(/*content*/ Vec::new()),
+            ));
+        }
+        if let Some(reasoning) = self.reasoning.as_mut() {
+            reasoning.push_str(&delta);
+        }
+        events.push(ResponseEvent::ReasoningContentDelta {
+            delta,
+            content_index: 0,
+        });
+    }
+
+    fn push_text_delta(&mut self, delta: String, events: &mut Vec<ResponseEvent>) {
+        self.flush_reasoning(events);
+        if self.message.is_none() {
+            self.message = Some(String::new());
+            events.push(ResponseEvent::OutputItemAdded(
+                self.message_item(/*content*/ Vec::new()),
+            ));
+        }
+        if let Some(message) = self.message.as_mut() {
+            message.push_str(&delta);
+        }
+        events.push(ResponseEvent::OutputTextDelta(delta));
+    }
+
+    fn reasoning_item(&self, content: Vec<ReasoningItemContent>) -> ResponseItem {
+        ResponseItem::Reasoning {
+            id: format!("rs_{}", self.response_id()),
+            summary: Vec::new(),
+            content: Some(content),
+            encrypted_content: None,
+        }
+    }
+
+    fn message_item(&self, content: Vec<ContentItem>) -> ResponseItem {
+        ResponseItem::Message {
+            id: Some(format!("msg_{}", self.response_id())),
+            role: "assistant".to_string(),
+            content,
+            phase: None,
+        }
+    }
+
+    fn flush_reasoning(&mut self, events: &mut Vec<ResponseEvent>) {
+        if let Some(text) = self.reasoning.take() {
+            events.push(ResponseEvent::OutputItemDone(
+                self.reasoning_item(vec![ReasoningItemContent::ReasoningText { text }]),
+            ));
+        }
+    }
+
+    /// Emits every open item. Tool calls are emitted in `index` order once the
+    /// choice finishes, since their arguments stream in fragments until then.
+    fn flush_items(&mut self, events: &mut Vec<ResponseEvent>) {
+        self.flush_reasoning(events);
+        if let Some(text) = self.message.take() {
+            events.push(ResponseEvent::OutputItemDone(
+                self.message_item(vec![ContentItem::OutputText { text }]),
+            ));
+        }
+        let tool_calls = std::mem::take(&mut self.tool_calls);
+        for (index, tool_call) in tool_calls {
+            if tool_call.name.is_empty() {
+                debug!("dropping chat tool call {index} without a function name");
+                continue;
+            }
+            let call_id = tool_call
+                .id
+                .unwrap_or_else(|| format!("call_{}_{index}", self.response_id()));
+            let item = match self.tools.resolve(&tool_call.name) {
+                FlatTool::Function { namespace, name } => ResponseItem::FunctionCall {
+                    id: None,
+                    name,
+                    namespace,
+                    arguments: tool_call.arguments,
+                    call_id,
+                },
+                FlatTool::Freeform { name } => ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id,
+                    name,
+                    input: FlatToolNames::freeform_input(&tool_call.arguments),
+                },
+            };
+            events.push(ResponseEvent::OutputItemDone(item));
+        }
+    }
+
+    fn completed(&mut self) -> Result<ResponseEvent, ApiError> {
+        match self.finish_reason.as_deref() {
+            None => Err(ApiError::Stream(
+                "stream closed before finish_reason".to_string(),
+            )),
+            Some(reason @ ("length" | "content_filter")) => Err(ApiError::Stream(format!(
+                "Incomplete response returned, reason: {reason}"
+            ))),
+            Some(_) => Ok(ResponseEvent::Completed {
+                response_id: self.response_id().to_string(),
+                token_usage: self.usage.take(),
+                end_turn: None,
+            }),
+        }
+    }
+}
+
+fn chat_error(error: ChatError) -> ApiError {
+    let code = error.code.as_ref().map(|code| match code {
+        serde_json::Value::String(code) => code.clone(),
+        code => code.to_string(),
+    });
+    let message = error.message.unwrap_or_default();
+    match code.as_deref() {
+        Some("context_length_exceeded") => ApiError::ContextWindowExceeded,
+        Some("insufficient_quota") => ApiError::QuotaExceeded,
+        Some("server_is_overloaded" | "slow_down") => ApiError::ServerOverloaded,
+        _ => ApiError::Stream(message),
+    }
+}
+
+async fn process_chat_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    tools: FlatToolNames,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = ChatStreamState::new(tools);
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event.send(state.completed()).await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        if sse.data.trim() == DONE_SENTINEL {
+            let _ = tx_event.send(state.completed()).await;
+            return;
+        }
+
+        let chunk: ChatCompletionChunk = match serde_json::from_str(&sse.data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                debug!("Failed to parse SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+
+        match state.handle_chunk(chunk) {
+            Ok(events) => {
+                for event in events {
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+    }
+}
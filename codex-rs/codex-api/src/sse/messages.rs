@@ -0,0 +1,484 @@
+//! Translates Messages API (`/v1/messages`) streaming events into Responses stream events.
+//!
+//! The Messages stream is framed as `message_start`, then one
+//! `content_block_start`/`content_block_delta`/`content_block_stop` group per text,
+//! thinking, or `tool_use` block, then `message_delta` (stop reason and output
+//! usage) and `message_stop`. Each content block becomes one Responses output item.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::requests::FlatToolNames;
+use crate::requests::flat_tools::FlatTool;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub(crate) fn spawn_messages_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    tools: FlatToolNames,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_messages_sse(
+        stream_response.bytes,
+        tx_event,
+        idle_timeout,
+        telemetry,
+        tools,
+    ));
+
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum MessagesStreamEvent {
+    MessageStart {
+        message: MessageStart,
+    },
+    ContentBlockStart {
+        index: u32,
+        content_block: ContentBlockStart,
+    },
+    ContentBlockDelta {
+        index: u32,
+        delta: ContentBlockDelta,
+    },
+    ContentBlockStop {
+        index: u32,
+    },
+    MessageDelta {
+        delta: MessageDelta,
+        #[serde(default)]
+        usage: Option<MessagesUsage>,
+    },
+    MessageStop,
+    Ping,
+    Error {
+        error: MessagesError,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageStart {
+    id: String,
+    #[serde(default)]
+    usage: Option<MessagesUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlockStart {
+    Text {
+        #[serde(default)]
+        text: String,
+    },
+    Thinking {
+        #[serde(default)]
+        thinking: String,
+    },
+    RedactedThinking {
+        data: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ContentBlockDelta {
+    TextDelta {
+        text: String,
+    },
+    ThinkingDelta {
+        thinking: String,
+    },
+    SignatureDelta {
+        signature: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageDelta {
+    #[serde(default)]
+    stop_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MessagesUsage {
+    #[serde(default)]
+    input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<i64>,
+    #[serde(default)]
+    output_tokens: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesError {
+    #[serde(default, rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+enum OpenBlock {
+    Text {
+        text: String,
+    },
+    Thinking {
+        thinking: String,
+        signature: String,
+    },
+    RedactedThinking {
+        data: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input_json: String,
+    },
+}
+
+/// Accumulates one streamed message.
+#[derive(Default)]
+struct MessagesStreamState {
+    tools: FlatToolNames,
+    message_id: String,
+    blocks: HashMap<u32, OpenBlock>,
+    stop_reason: Option<String>,
+    input_tokens: i64,
+    cached_input_tokens: i64,
+    output_tokens: i64,
+}
+
+impl MessagesStreamState {
+    fn new(tools: FlatToolNames) -> Self {
+        Self {
+            tools,
+            ..Self::default()
+        }
+    }
+
+    fn record_usage(&mut self, usage: MessagesUsage) {
+        let cache_read = usage.cache_read_input_tokens.unwrap_or(0);
+        if let Some(input_tokens) = usage.input_tokens {
+            // Messages usage reports cached and cache-creation tokens separately
+            // from `input_tokens`; Codex accounting counts them all as input.
+            self.input_tokens =
+                input_tokens + cache_read + usage.cache_creation_input_tokens.unwrap_or(0);
+            self.cached_input_tokens = cache_read;
+        }
+        if let Some(output_tokens) = usage.output_tokens {
+            self.output_tokens = output_tokens;
+        }
+    }
+
+    fn item_id(&self, prefix: &str, index: u32) -> String {
+        format!("{prefix}_{}_{index}", self.message_id)
+    }
+
+    fn handle_event(
+        &mut self,
+        event: MessagesStreamEvent,
+    ) -> Result<Option<ResponseEvent>, ApiError> {
+        match event {
+            MessagesStreamEvent::MessageStart { message } => {
+                self.message_id = message.id;
+                if let Some(usage) = message.usage {
+                    self.record_usage(usage);
+                }
+                Ok(Some(ResponseEvent::Created))
+            }
+            MessagesStreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => Ok(self.start_block(index, content_block)),
+            MessagesStreamEvent::ContentBlockDelta { index, delta } => {
+                Ok(self.apply_delta(index, delta))
+            }
+            MessagesStreamEvent::ContentBlockStop { index } => Ok(self
+                .blocks
+                .remove(&index)
+                .map(|block| self.finish_block(index, block))
+                .map(ResponseEvent::OutputItemDone)),
+            MessagesStreamEvent::MessageDelta { delta, usage } => {
+                if let Some(stop_reason) = delta.stop_reason {
+                    self.stop_reason = Some(stop_reason);
+                }
+                if let Some(usage) = usage {
+                    self.record_usage(usage);
+                }
+                Ok(None)
+            }
+            MessagesStreamEvent::MessageStop => self.completed().map(Some),
+            MessagesStreamEvent::Error { error } => Err(messages_error(error)),
+            MessagesStreamEvent::Ping | MessagesStreamEvent::Unknown => Ok(None),
+        }
+    }
+
+    fn start_block(&mut self, index: u32, block: ContentBlockStart) -> Option<ResponseEvent> {
+        let (open, added) = match block {
+            ContentBlockStart::Text { text } => {
+                let added = ResponseItem::Message {
+                    id: Some(self.item_id("msg", index)),
+                    role: "assistant".to_string(),
+                    content: Vec::new(),
+                    phase: None,
+                };
+                (OpenBlock::Text { text }, Some(added))
+            }
+            ContentBlockStart::Thinking { thinking } => {
+                let added = ResponseItem::Reasoning {
+                    id: self.item_id("rs", index),
+                    summary: Vec::new(),
+                    content: Some(Vec::new()),
+                    encrypted_content: None,
+                };
+                let open = OpenBlock::Thinking {
+                    thinking,
+                    signature: String::new(),
+                };
+                (open, Some(added))
+            }
+            ContentBlockStart::RedactedThinking { data } => {
+                (OpenBlock::RedactedThinking { data }, None)
+            }
+            ContentBlockStart::ToolUse { id, name } => {
+                let open = OpenBlock::ToolUse {
+                    id,
+                    name,
+                    input_json: String::new(),
+                };
+                (open, None)
+            }
+            ContentBlockStart::Unknown => return None,
+        };
+        self.blocks.insert(index, open);
+        added.map(ResponseEvent::OutputItemAdded)
+    }
+
+    fn apply_delta(&mut self, index: u32, delta: ContentBlockDelta) -> Option<ResponseEvent> {
+        let block = self.blocks.get_mut(&index)?;
+        match (block, delta) {
+            (OpenBlock::Text { text }, ContentBlockDelta::TextDelta { text: delta }) => {
+                text.push_str(&delta);
+                Some(ResponseEvent::OutputTextDelta(delta))
+            }
+            (
+                OpenBlock::Thinking { thinking, .. },
+                ContentBlockDelta::ThinkingDelta { thinking: delta },
+            ) => {
+                thinking.push_str(&delta);
+                Some(ResponseEvent::ReasoningContentDelta {
+                    delta,
+                    content_index: 0,
+                })
+            }
+            (
+                OpenBlock::Thinking { signature, .. },
+                ContentBlockDelta::SignatureDelta { signature: delta },
+            ) => {
+                signature.push_str(&delta);
+                None
+            }
+            (
+                OpenBlock::ToolUse { input_json, .. },
+                ContentBlockDelta::InputJsonDelta { partial_json },
+            ) => {
+                input_json.push_str(&partial_json);
+                None
+            }
+            (_, delta) => {
+                trace!("ignoring mismatched messages delta for block {index}: {delta:?}");
+                None
+            }
+        }
+    }
+
+    fn finish_block(&self, index: u32, block: OpenBlock) -> ResponseItem {
+        match block {
+            OpenBlock::Text { text } => ResponseItem::Message {
+                id: Some(self.item_id("msg", index)),
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText { text }],
+                phase: None,
+            },
+            OpenBlock::Thinking {
+                thinking,
+                signature,
+            } => ResponseItem::Reasoning {
+                id: self.item_id("rs", index),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText { text: thinking }]),
+                encrypted_content: (!signature.is_empty()).then_some(signature),
+            },
+            OpenBlock::RedactedThinking { data } => ResponseItem::Reasoning {
+                id: self.item_id("rs", index),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some(data),
+            },
+            OpenBlock::ToolUse {
+                id,
+                name,
+                input_json,
+            } => {
+                let arguments = if input_json.trim().is_empty() {
+                    "{}".to_string()
+                } else {
+                    input_json
+                };
+                match self.tools.resolve(&name) {
+                    FlatTool::Function { namespace, name } => ResponseItem::FunctionCall {
+                        id: None,
+                        name,
+                        namespace,
+                        arguments,
+                        call_id: id,
+                    },
+                    FlatTool::Freeform { name } => ResponseItem::CustomToolCall {
+                        id: None,
+                        status: None,
+                        call_id: id,
+                        name,
+                        input: FlatToolNames::freeform_input(&arguments),
+                    },
+                }
+            }
+        }
+    }
+
+    fn completed(&self) -> Result<ResponseEvent, ApiError> {
+        if let Some(reason @ ("max_tokens" | "refusal")) = self.stop_reason.as_deref() {
+            return Err(ApiError::Stream(format!(
+                "Incomplete response returned, reason: {reason}"
+            )));
+        }
+        Ok(ResponseEvent::Completed {
+            response_id: self.message_id.clone(),
+            token_usage: Some(TokenUsage {
+                input_tokens: self.input_tokens,
+                cached_input_tokens: self.cached_input_tokens,
+                output_tokens: self.output_tokens,
+                reasoning_output_tokens: 0,
+                total_tokens: self.input_tokens + self.output_tokens,
+            }),
+            end_turn: None,
+        })
+    }
+}
+
+fn messages_error(error: MessagesError) -> ApiError {
+    let message = error.message.unwrap_or_default();
+    match error.kind.as_deref() {
+        Some("overloaded_error") => ApiError::ServerOverloaded,
+        Some("rate_limit_error" | "api_error") => ApiError::Retryable {
+            message,
+            delay: None,
+        },
+        Some("invalid_request_error") if message.contains("prompt is too long") => {
+            ApiError::ContextWindowExceeded
+        }
+        Some("invalid_request_error") => ApiError::InvalidRequest { message },
+        _ => ApiError::Stream(message),
+    }
+}
+
+async fn process_messages_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    tools: FlatToolNames,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = MessagesStreamState::new(tools);
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        let event: MessagesStreamEvent = match serde_json::from_str(&sse.data) {
+            Ok(event) => event,
+            Err(e) => {
+                debug!("Failed to parse SSE event: {e}, data: {}", &sse.data);
+                continue;
+            }
+        };
+
+        match state.handle_event(event) {
+            Ok(Some(event)) => {
+                let is_completed = matches!(event, ResponseEvent::Completed { .. });
+                if tx_event.send(Ok(event)).await.is_err() {
+                    return;
+                }
+                if is_completed {
+                    return;
+                }
+            }
+            Ok(None) => {}
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+    }
+}
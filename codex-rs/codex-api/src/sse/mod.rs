@@ -1,5 +1,9 @@
+pub(crate) mod chat;
+pub(crate) mod messages;
 pub(crate) mod responses;
 
+pub(crate) use chat::spawn_chat_completions_stream;
+pub(crate) use messages::spawn_messages_stream;
 pub(crate) use responses::ResponsesStreamEvent;
 pub(crate) use responses::process_responses_event;
 pub use responses::spawn_response_stream;
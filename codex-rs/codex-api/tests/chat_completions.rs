@@ -0,0 +1,326 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_api::AuthProvider;
+use codex_api::ChatClient;
+use codex_api::Provider;
+use codex_api::ResponseEvent;
+use codex_api::ResponsesApiRequest;
+use codex_api::ResponsesOptions;
+use codex_api::RetryConfig;
+use codex_client::ReqwestTransport;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use futures::StreamExt;
+use http::HeaderMap;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+#[derive(Clone, Default)]
+struct NoAuth;
+
+impl AuthProvider for NoAuth {
+    fn add_auth_headers(&self, _headers: &mut HeaderMap) {}
+}
+
+fn provider(base_url: &str) -> Provider {
+    Provider {
+        name: "local".to_string(),
+        base_url: base_url.to_string(),
+        query_params: None,
+        headers: HeaderMap::new(),
+        retry: RetryConfig {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            retry_429: false,
+            retry_5xx: false,
+            retry_transport: false,
+        },
+        stream_idle_timeout: Duration::from_secs(5),
+    }
+}
+
+fn request(tools: Vec<Value>) -> ResponsesApiRequest {
+    ResponsesApiRequest {
+        model: "qwen".to_string(),
+        instructions: "Be brief.".to_string(),
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "What is in a and b?".to_string(),
+            }],
+            phase: None,
+        }],
+        tools,
+        tool_choice: "auto".to_string(),
+        parallel_tool_calls: true,
+        reasoning: None,
+        store: false,
+        stream: true,
+        include: Vec::new(),
+        service_tier: None,
+        prompt_cache_key: None,
+        text: None,
+        client_metadata: None,
+    }
+}
+
+fn sse_body(chunks: &[Value]) -> String {
+    let mut body = String::new();
+    for chunk in chunks {
+        body.push_str(&format!("data: {chunk}\n\n"));
+    }
+    body.push_str("data: [DONE]\n\n");
+    body
+}
+
+async fn mount_chat_stream(server: &MockServer, body: String) {
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(body),
+        )
+        .mount(server)
+        .await;
+}
+
+async fn collect_events(
+    client: &ChatClient<ReqwestTransport>,
+    request: ResponsesApiRequest,
+) -> Result<Vec<ResponseEvent>> {
+    let mut stream = client
+        .stream_request(request, ResponsesOptions::default())
+        .await?;
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        events.push(event?);
+    }
+    Ok(events)
+}
+
+#[tokio::test]
+async fn chat_stream_reassembles_reasoning_text_and_parallel_tool_calls() -> Result<()> {
+    let server = MockServer::start().await;
+    let chunk = |delta: Value, finish_reason: Value| {
+        json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "choices": [{ "index": 0, "delta": delta, "finish_reason": finish_reason }],
+        })
+    };
+    let body = sse_body(&[
+        chunk(
+            json!({ "role": "assistant", "reasoning_content": "Need both " }),
+            Value::Null,
+        ),
+        chunk(json!({ "reasoning_content": "files." }), Value::Null),
+        chunk(json!({ "content": "Checking." }), Value::Null),
+        chunk(
+            json!({ "tool_calls": [
+                { "index": 0, "id": "call_a", "type": "function", "function": { "name": "mcp__fs__read", "arguments": "{\"path\":" } },
+                { "index": 1, "id": "call_b", "type": "function", "function": { "name": "apply_patch", "arguments": "" } },
+            ] }),
+            Value::Null,
+        ),
+        chunk(
+            json!({ "tool_calls": [
+                { "index": 1, "function": { "arguments": "{\"input\":\"*** Begin Patch\"}" } },
+                { "index": 0, "function": { "arguments": "\"a\"}" } },
+            ] }),
+            Value::Null,
+        ),
+        chunk(json!({}), json!("tool_calls")),
+        json!({
+            "id": "chatcmpl-1",
+            "choices": [],
+            "usage": {
+                "prompt_tokens": 12,
+                "completion_tokens": 8,
+                "total_tokens": 20,
+                "completion_tokens_details": { "reasoning_tokens": 3 },
+            },
+        }),
+    ]);
+    mount_chat_stream(&server, body).await;
+
+    let client = ChatClient::new(
+        ReqwestTransport::new(reqwest::Client::new()),
+        provider(&format!("{}/v1", server.uri())),
+        Arc::new(NoAuth),
+    );
+    let tools = vec![
+        json!({
+            "type": "namespace",
+            "name": "mcp__fs__",
+            "description": "Filesystem.",
+            "tools": [{
+                "type": "function",
+                "name": "read",
+                "description": "Reads a file.",
+                "strict": false,
+                "parameters": { "type": "object", "properties": { "path": { "type": "string" } } },
+            }],
+        }),
+        json!({
+            "type": "custom",
+            "name": "apply_patch",
+            "description": "Applies a patch.",
+            "format": { "type": "grammar", "syntax": "lark", "definition": "start: patch" },
+        }),
+    ];
+    let events = collect_events(&client, request(tools)).await?;
+
+    let received = server.received_requests().await.unwrap_or_default();
+    assert_eq!(received.len(), 1);
+    let sent: Value = serde_json::from_slice(&received[0].body)?;
+    assert_eq!(
+        sent["messages"],
+        json!([
+            { "role": "system", "content": "Be brief." },
+            { "role": "user", "content": "What is in a and b?" },
+        ])
+    );
+    assert_eq!(sent["tools"][0]["function"]["name"], "mcp__fs__read");
+    assert_eq!(sent["tools"][1]["function"]["name"], "apply_patch");
+
+    let reasoning = |content: Vec<ReasoningItemContent>| ResponseItem::Reasoning {
+        id: "rs_chatcmpl-1".to_string(),
+        summary: Vec::new(),
+        content: Some(content),
+        encrypted_content: None,
+    };
+    let message = |content: Vec<ContentItem>| ResponseItem::Message {
+        id: Some("msg_chatcmpl-1".to_string()),
+        role: "assistant".to_string(),
+        content,
+        phase: None,
+    };
+    let summary: Vec<String> = events
+        .iter()
+        .map(|event| match event {
+            ResponseEvent::Created => "created".to_string(),
+            ResponseEvent::OutputItemAdded(_) => "added".to_string(),
+            ResponseEvent::OutputItemDone(_) => "done".to_string(),
+            ResponseEvent::ReasoningContentDelta { delta, .. } => format!("reasoning:{delta}"),
+            ResponseEvent::OutputTextDelta(delta) => format!("text:{delta}"),
+            ResponseEvent::Completed { .. } => "completed".to_string(),
+            other => format!("{other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            "created",
+            "added",
+            "reasoning:Need both ",
+            "reasoning:files.",
+            "done",
+            "added",
+            "text:Checking.",
+            "done",
+            "done",
+            "done",
+            "completed",
+        ]
+    );
+
+    let done_items: Vec<&ResponseItem> = events
+        .iter()
+        .filter_map(|event| match event {
+            ResponseEvent::OutputItemDone(item) => Some(item),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        done_items,
+        vec![
+            &reasoning(vec![ReasoningItemContent::ReasoningText {
+                text: "Need both files.".to_string(),
+            }]),
+            &message(vec![ContentItem::OutputText {
+                text: "Checking.".to_string(),
+            }]),
+            &ResponseItem::FunctionCall {
+                id: None,
+                name: "read".to_string(),
+                namespace: Some("mcp__fs__".to_string()),
+                arguments: "{\"path\":\"a\"}".to_string(),
+                call_id: "call_a".to_string(),
+            },
+            &ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call_b".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch".to_string(),
+            },
+        ]
+    );
+
+    let Some(ResponseEvent::Completed {
+        response_id,
+        token_usage,
+        end_turn,
+    }) = events.last()
+    else {
+        panic!("expected completed event, got {events:?}");
+    };
+    assert_eq!(response_id, "chatcmpl-1");
+    assert_eq!(
+        token_usage,
+        &Some(TokenUsage {
+            input_tokens: 12,
+            cached_input_tokens: 0,
+            output_tokens: 8,
+            reasoning_output_tokens: 3,
+            total_tokens: 20,
+        })
+    );
+    assert_eq!(end_turn, &None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn chat_stream_reports_truncated_completion_as_error() -> Result<()> {
+    let server = MockServer::start().await;
+    let body = sse_body(&[json!({
+        "id": "chatcmpl-2",
+        "choices": [{ "index": 0, "delta": { "content": "Partial" }, "finish_reason": "length" }],
+    })]);
+    mount_chat_stream(&server, body).await;
+
+    let client = ChatClient::new(
+        ReqwestTransport::new(reqwest::Client::new()),
+        provider(&format!("{}/v1", server.uri())),
+        Arc::new(NoAuth),
+    );
+    let mut stream = client
+        .stream_request(request(Vec::new()), ResponsesOptions::default())
+        .await?;
+    let mut last = None;
+    while let Some(event) = stream.next().await {
+        last = Some(event);
+    }
+
+    let Some(Err(err)) = last else {
+        panic!("expected the stream to end with an error");
+    };
+    assert_eq!(
+        err.to_string(),
+        "stream error: Incomplete response returned, reason: length"
+    );
+    Ok(())
+}
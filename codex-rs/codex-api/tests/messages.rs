@@ -0,0 +1,329 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_api::ApiError;
+use codex_api::AuthProvider;
+use codex_api::MessagesClient;
+use codex_api::Provider;
+use codex_api::ResponseEvent;
+use codex_api::ResponsesApiRequest;
+use codex_api::ResponsesOptions;
+use codex_api::RetryConfig;
+use codex_client::ReqwestTransport;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use futures::StreamExt;
+use http::HeaderMap;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+#[derive(Clone, Default)]
+struct NoAuth;
+
+impl AuthProvider for NoAuth {
+    fn add_auth_headers(&self, _headers: &mut HeaderMap) {}
+}
+
+fn provider(base_url: &str) -> Provider {
+    Provider {
+        name: "gateway".to_string(),
+        base_url: base_url.to_string(),
+        query_params: None,
+        headers: HeaderMap::new(),
+        retry: RetryConfig {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            retry_429: false,
+            retry_5xx: false,
+            retry_transport: false,
+        },
+        stream_idle_timeout: Duration::from_secs(5),
+    }
+}
+
+fn request(tools: Vec<Value>) -> ResponsesApiRequest {
+    ResponsesApiRequest {
+        model: "claude-sonnet".to_string(),
+        instructions: "Be brief.".to_string(),
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "What is in a and b?".to_string(),
+            }],
+            phase: None,
+        }],
+        tools,
+        tool_choice: "auto".to_string(),
+        parallel_tool_calls: true,
+        reasoning: None,
+        store: false,
+        stream: true,
+        include: Vec::new(),
+        service_tier: None,
+        prompt_cache_key: None,
+        text: None,
+        client_metadata: None,
+    }
+}
+
+fn sse_body(events: &[Value]) -> String {
+    let mut body = String::new();
+    for event in events {
+        let kind = event["type"].as_str().unwrap_or_default();
+        body.push_str(&format!("event: {kind}\ndata: {event}\n\n"));
+    }
+    body
+}
+
+async fn mount_messages_stream(server: &MockServer, body: String) {
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(header("anthropic-version", "2023-06-01"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(body),
+        )
+        .mount(server)
+        .await;
+}
+
+fn client(server: &MockServer) -> MessagesClient<ReqwestTransport> {
+    MessagesClient::new(
+        ReqwestTransport::new(reqwest::Client::new()),
+        provider(&format!("{}/v1", server.uri())),
+        Arc::new(NoAuth),
+    )
+}
+
+async fn collect_events(
+    client: &MessagesClient<ReqwestTransport>,
+    request: ResponsesApiRequest,
+) -> Vec<Result<ResponseEvent, ApiError>> {
+    let mut events = Vec::new();
+    match client
+        .stream_request(request, ResponsesOptions::default())
+        .await
+    {
+        Ok(mut stream) => {
+            while let Some(event) = stream.next().await {
+                events.push(event);
+            }
+        }
+        Err(err) => events.push(Err(err)),
+    }
+    events
+}
+
+#[tokio::test]
+async fn messages_stream_maps_thinking_text_and_tool_use_blocks() -> Result<()> {
+    let server = MockServer::start().await;
+    let body = sse_body(&[
+        json!({
+            "type": "message_start",
+            "message": {
+                "id": "msg_01",
+                "type": "message",
+                "role": "assistant",
+                "content": [],
+                "usage": {
+                    "input_tokens": 10,
+                    "cache_creation_input_tokens": 2,
+                    "cache_read_input_tokens": 30,
+                    "output_tokens": 1,
+                },
+            },
+        }),
+        json!({ "type": "content_block_start", "index": 0, "content_block": { "type": "thinking", "thinking": "" } }),
+        json!({ "type": "content_block_delta", "index": 0, "delta": { "type": "thinking_delta", "thinking": "Need both " } }),
+        json!({ "type": "content_block_delta", "index": 0, "delta": { "type": "thinking_delta", "thinking": "files." } }),
+        json!({ "type": "content_block_delta", "index": 0, "delta": { "type": "signature_delta", "signature": "sig-1" } }),
+        json!({ "type": "content_block_stop", "index": 0 }),
+        json!({ "type": "ping" }),
+        json!({ "type": "content_block_start", "index": 1, "content_block": { "type": "text", "text": "" } }),
+        json!({ "type": "content_block_delta", "index": 1, "delta": { "type": "text_delta", "text": "Checking." } }),
+        json!({ "type": "content_block_stop", "index": 1 }),
+        json!({ "type": "content_block_start", "index": 2, "content_block": { "type": "tool_use", "id": "toolu_a", "name": "mcp__fs__read", "input": {} } }),
+        json!({ "type": "content_block_delta", "index": 2, "delta": { "type": "input_json_delta", "partial_json": "{\"path\":" } }),
+        json!({ "type": "content_block_delta", "index": 2, "delta": { "type": "input_json_delta", "partial_json": "\"a\"}" } }),
+        json!({ "type": "content_block_stop", "index": 2 }),
+        json!({ "type": "content_block_start", "index": 3, "content_block": { "type": "tool_use", "id": "toolu_b", "name": "apply_patch", "input": {} } }),
+        json!({ "type": "content_block_delta", "index": 3, "delta": { "type": "input_json_delta", "partial_json": "{\"input\":\"*** Begin Patch\"}" } }),
+        json!({ "type": "content_block_stop", "index": 3 }),
+        json!({ "type": "message_delta", "delta": { "stop_reason": "tool_use" }, "usage": { "output_tokens": 8 } }),
+        json!({ "type": "message_stop" }),
+    ]);
+    mount_messages_stream(&server, body).await;
+
+    let tools = vec![
+        json!({
+            "type": "namespace",
+            "name": "mcp__fs__",
+            "description": "Filesystem.",
+            "tools": [{
+                "type": "function",
+                "name": "read",
+                "description": "Reads a file.",
+                "strict": false,
+                "parameters": { "type": "object", "properties": { "path": { "type": "string" } } },
+            }],
+        }),
+        json!({
+            "type": "custom",
+            "name": "apply_patch",
+            "description": "Applies a patch.",
+            "format": { "type": "grammar", "syntax": "lark", "definition": "start: patch" },
+        }),
+    ];
+    let events = collect_events(&client(&server), request(tools))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let received = server.received_requests().await.unwrap_or_default();
+    assert_eq!(received.len(), 1);
+    let sent: Value = serde_json::from_slice(&received[0].body)?;
+    assert_eq!(sent["system"], "Be brief.");
+    assert_eq!(
+        sent["messages"],
+        json!([{
+            "role": "user",
+            "content": [{ "type": "text", "text": "What is in a and b?" }],
+        }])
+    );
+    assert_eq!(sent["tools"][0]["name"], "mcp__fs__read");
+    assert_eq!(sent["tools"][1]["name"], "apply_patch");
+
+    let summary: Vec<String> = events
+        .iter()
+        .map(|event| match event {
+            ResponseEvent::Created => "created".to_string(),
+            ResponseEvent::OutputItemAdded(_) => "added".to_string(),
+            ResponseEvent::OutputItemDone(_) => "done".to_string(),
+            ResponseEvent::ReasoningContentDelta { delta, .. } => format!("reasoning:{delta}"),
+            ResponseEvent::OutputTextDelta(delta) => format!("text:{delta}"),
+            ResponseEvent::Completed { .. } => "completed".to_string(),
+            other => format!("{other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            "created",
+            "added",
+            "reasoning:Need both ",
+            "reasoning:files.",
+            "done",
+            "added",
+            "text:Checking.",
+            "done",
+            "done",
+            "done",
+            "completed",
+        ]
+    );
+
+    let done_items: Vec<&ResponseItem> = events
+        .iter()
+        .filter_map(|event| match event {
+            ResponseEvent::OutputItemDone(item) => Some(item),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        done_items,
+        vec![
+            &ResponseItem::Reasoning {
+                id: "rs_msg_01_0".to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "Need both files.".to_string(),
+                }]),
+                encrypted_content: Some("sig-1".to_string()),
+            },
+            &ResponseItem::Message {
+                id: Some("msg_msg_01_1".to_string()),
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "Checking.".to_string(),
+                }],
+                phase: None,
+            },
+            &ResponseItem::FunctionCall {
+                id: None,
+                name: "read".to_string(),
+                namespace: Some("mcp__fs__".to_string()),
+                arguments: "{\"path\":\"a\"}".to_string(),
+                call_id: "toolu_a".to_string(),
+            },
+            &ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "toolu_b".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch".to_string(),
+            },
+        ]
+    );
+
+    let Some(ResponseEvent::Completed {
+        response_id,
+        token_usage,
+        end_turn,
+    }) = events.last()
+    else {
+        panic!("expected completed event, got {events:?}");
+    };
+    assert_eq!(response_id, "msg_01");
+    assert_eq!(
+        token_usage,
+        &Some(TokenUsage {
+            input_tokens: 42,
+            cached_input_tokens: 30,
+            output_tokens: 8,
+            reasoning_output_tokens: 0,
+            total_tokens: 50,
+        })
+    );
+    assert_eq!(end_turn, &None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn messages_stream_surfaces_overloaded_error_event() -> Result<()> {
+    let server = MockServer::start().await;
+    let body = sse_body(&[
+        json!({
+            "type": "message_start",
+            "message": { "id": "msg_02", "usage": { "input_tokens": 5, "output_tokens": 0 } },
+        }),
+        json!({
+            "type": "error",
+            "error": { "type": "overloaded_error", "message": "Overloaded" },
+        }),
+    ]);
+    mount_messages_stream(&server, body).await;
+
+    let events = collect_events(&client(&server), request(Vec::new())).await;
+
+    let Some(Err(err)) = events.last() else {
+        panic!("expected the stream to end with an error, got {events:?}");
+    };
+    assert!(
+        matches!(err, ApiError::ServerOverloaded),
+        "unexpected error: {err:?}"
+    );
+    Ok(())
+}
@@ -2,11 +2,12 @@
 //!
 //! [`McpConnectionManager`] owns the set of running async RMCP clients keyed by
 //! MCP server name. It coordinates startup status events, keeps server origin
-//! metadata, aggregates tools/resources/templates across servers, routes tool
+//! metadata, aggregates tools/resources/templates/prompts across servers, routes tool
 //! calls to the right client, and exposes the public manager API used by
 //! `codex-core`.
 
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
@@ -19,6 +20,8 @@ use crate::codex_apps::write_cached_codex_apps_tools_if_needed;
 use crate::elicitation::ElicitationRequestManager;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::ToolPluginProvenance;
+use crate::resource_subscriptions::McpResourceUpdateHandler;
+use crate::resource_subscriptions::ResourceSubscriptionManager;
 use crate::rmcp_client::AsyncManagedClient;
 use crate::rmcp_client::DEFAULT_STARTUP_TIMEOUT;
 use crate::rmcp_client::MCP_TOOLS_FETCH_UNCACHED_DURATION_METRIC;
@@ -26,8 +29,11 @@ use crate::rmcp_client::MCP_TOOLS_LIST_DURATION_METRIC;
 use crate::rmcp_client::ManagedClient;
 use crate::rmcp_client::StartupOutcomeError;
 use crate::rmcp_client::list_tools_for_client_uncached;
+use crate::roots::RootsManager;
 use crate::runtime::McpRuntimeEnvironment;
 use crate::runtime::emit_duration;
+use crate::sampling::McpSampler;
+use crate::sampling::SamplingRequestManager;
 use crate::tools::ToolInfo;
 use crate::tools::filter_tools;
 use crate::tools::qualify_tools;
@@ -43,6 +49,8 @@ use codex_config::types::OAuthCredentialsStoreMode;
 use codex_login::CodexAuth;
 use codex_protocol::ToolName;
 use codex_protocol::mcp::CallToolResult;
+use codex_protocol::mcp::GetPromptResult;
+use codex_protocol::mcp::Prompt;
 use codex_protocol::models::PermissionProfile;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::Event;
@@ -52,6 +60,7 @@ use codex_protocol::protocol::McpStartupFailure;
 use codex_protocol::protocol::McpStartupStatus;
 use codex_protocol::protocol::McpStartupUpdateEvent;
 use codex_rmcp_client::ElicitationResponse;
+use rmcp::model::GetPromptRequestParams;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::PaginatedRequestParams;
@@ -60,6 +69,8 @@ use rmcp::model::ReadResourceResult;
 use rmcp::model::RequestId;
 use rmcp::model::Resource;
 use rmcp::model::ResourceTemplate;
+use rmcp::model::SubscribeRequestParams;
+use rmcp::model::UnsubscribeRequestParams;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 use tracing::instrument;
@@ -71,6 +82,9 @@ pub struct McpConnectionManager {
     clients: HashMap<String, AsyncManagedClient>,
     server_origins: HashMap<String, String>,
     elicitation_requests: ElicitationRequestManager,
+    sampling_requests: SamplingRequestManager,
+    roots: RootsManager,
+    resource_subscriptions: ResourceSubscriptionManager,
 }
 
 impl McpConnectionManager {
@@ -85,6 +99,9 @@ impl McpConnectionManager {
                 approval_policy.value(),
                 permission_profile.get().clone(),
             ),
+            sampling_requests: SamplingRequestManager::default(),
+            roots: RootsManager::default(),
+            resource_subscriptions: ResourceSubscriptionManager::default(),
         }
     }
 
@@ -108,6 +125,49 @@ impl McpConnectionManager {
         }
     }
 
+    /// Installs the model client that answers `sampling/createMessage`
+    /// requests from servers managed by this instance.
+    pub fn set_sampler(&self, sampler: Arc<dyn McpSampler>) {
+        self.sampling_requests.set_sampler(sampler);
+    }
+
+    pub fn sampler(&self) -> Option<Arc<dyn McpSampler>> {
+        self.sampling_requests.sampler()
+    }
+
+    /// Installs the handler notified when a subscribed resource changes on
+    /// any server managed by this instance.
+    pub fn set_resource_update_handler(&self, handler: Arc<dyn McpResourceUpdateHandler>) {
+        self.resource_subscriptions.set_handler(handler);
+    }
+
+    pub fn resource_update_handler(&self) -> Option<Arc<dyn McpResourceUpdateHandler>> {
+        self.resource_subscriptions.handler()
+    }
+
+    /// Updates the roots reported to servers from the thread's cwd and
+    /// permissions, and sends `notifications/roots/list_changed` to every
+    /// server when they differ from the last reported set.
+    pub fn set_roots(&self, cwd: &Path, permission_profile: &PermissionProfile) {
+        if !self.roots.update(cwd, permission_profile) {
+            return;
+        }
+        for (server_name, async_managed_client) in &self.clients {
+            let server_name = server_name.clone();
+            let async_managed_client = async_managed_client.clone();
+            tokio::spawn(async move {
+                let Ok(managed_client) = async_managed_client.client().await else {
+                    return;
+                };
+                if let Err(error) = managed_client.client.notify_roots_list_changed().await {
+                    warn!(
+                        "failed to notify MCP server `{server_name}` of changed roots: {error:#}"
+                    );
+                }
+            });
+        }
+    }
+
     #[allow(clippy::new_ret_no_self, clippy::too_many_arguments)]
     pub async fn new(
         mcp_servers: &HashMap<String, McpServerConfig>,
@@ -127,8 +187,14 @@ impl McpConnectionManager {
         let mut clients = HashMap::new();
         let mut server_origins = HashMap::new();
         let mut join_set = JoinSet::new();
+        let roots = RootsManager::new(
+            &runtime_environment.fallback_cwd(),
+            &initial_permission_profile,
+        );
         let elicitation_requests =
             ElicitationRequestManager::new(approval_policy.value(), initial_permission_profile);
+        let sampling_requests = SamplingRequestManager::default();
+        let resource_subscriptions = ResourceSubscriptionManager::default();
         let tool_plugin_provenance = Arc::new(tool_plugin_provenance);
         let startup_submit_id = submit_id.clone();
         let codex_apps_auth_provider = auth
@@ -177,6 +243,9 @@ impl McpConnectionManager {
                 cancel_token.clone(),
                 tx_event.clone(),
                 elicitation_requests.clone(),
+                sampling_requests.clone(),
+                roots.clone(),
+                resource_subscriptions.clone(),
                 codex_apps_tools_cache_context,
                 Arc::clone(&tool_plugin_provenance),
                 runtime_environment.clone(),
@@ -221,6 +290,9 @@ impl McpConnectionManager {
             clients,
             server_origins,
             elicitation_requests: elicitation_requests.clone(),
+            sampling_requests,
+            roots,
+            resource_subscriptions,
         };
         tokio::spawn(async move {
             let outcomes = join_set.join_all().await;
@@ -495,6 +567,77 @@ impl McpConnectionManager {
         aggregated
     }
 
+    /// Returns a single map that contains all prompts. Each key is the server
+    /// name and the value is that server's prompts. Prompt listings are cached
+    /// per server until the server reports that its prompt list changed.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        let mut join_set = JoinSet::new();
+
+        for (server_name, async_managed_client) in &self.clients {
+            let server_name = server_name.clone();
+            let Ok(managed_client) = async_managed_client.client().await else {
+                continue;
+            };
+            if !managed_client.server_supports_prompts {
+                continue;
+            }
+
+            join_set.spawn(async move { (server_name, managed_client.listed_prompts().await) });
+        }
+
+        let mut aggregated: HashMap<String, Vec<Prompt>> = HashMap::new();
+
+        while let Some(join_res) = join_set.join_next().await {
+            match join_res {
+                Ok((server_name, Ok(prompts))) => {
+                    aggregated.insert(server_name, prompts);
+                }
+                Ok((server_name, Err(err))) => {
+                    warn!("Failed to list prompts for MCP server '{server_name}': {err:#}");
+                }
+                Err(err) => {
+                    warn!("Task panic when listing prompts for MCP server: {err:#}");
+                }
+            }
+        }
+
+        aggregated
+    }
+
+    /// Render the prompt `name` from the specified server.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<GetPromptResult> {
+        let managed = self.client_by_name(server).await?;
+        let timeout = managed.tool_timeout;
+        let arguments = (!arguments.is_empty()).then(|| {
+            arguments
+                .into_iter()
+                .map(|(key, value)| (key, serde_json::Value::String(value)))
+                .collect()
+        });
+
+        let result = managed
+            .client
+            .get_prompt(
+                GetPromptRequestParams {
+                    meta: None,
+                    name: name.to_string(),
+                    arguments,
+                },
+                timeout,
+            )
+            .await
+            .with_context(|| format!("prompts/get failed for `{server}/{name}`"))?;
+        let result = serde_json::to_value(result)
+            .and_then(GetPromptResult::from_mcp_value)
+            .with_context(|| format!("invalid prompts/get result from `{server}/{name}`"))?;
+        Ok(result)
+    }
+
     /// Invoke the tool indicated by the (server, tool) pair.
     pub async fn call_tool(
         &self,
@@ -592,6 +735,45 @@ impl McpConnectionManager {
             .with_context(|| format!("resources/read failed for `{server}` ({uri})"))
     }
 
+    /// Subscribe to updates for a resource on the specified server.
+    pub async fn subscribe_resource(&self, server: &str, uri: &str) -> Result<()> {
+        let managed = self.client_by_name(server).await?;
+        if !managed.server_supports_resource_subscriptions {
+            return Err(anyhow!(
+                "MCP server `{server}` does not support resource subscriptions"
+            ));
+        }
+
+        managed
+            .client
+            .subscribe_resource(
+                SubscribeRequestParams {
+                    meta: None,
+                    uri: uri.to_string(),
+                },
+                managed.tool_timeout,
+            )
+            .await
+            .with_context(|| format!("resources/subscribe failed for `{server}` ({uri})"))
+    }
+
+    /// Stop receiving updates for a resource on the specified server.
+    pub async fn unsubscribe_resource(&self, server: &str, uri: &str) -> Result<()> {
+        let managed = self.client_by_name(server).await?;
+
+        managed
+            .client
+            .unsubscribe_resource(
+                UnsubscribeRequestParams {
+                    meta: None,
+                    uri: uri.to_string(),
+                },
+                managed.tool_timeout,
+            )
+            .await
+            .with_context(|| format!("resources/unsubscribe failed for `{server}` ({uri})"))
+    }
+
     pub async fn resolve_tool_info(&self, tool_name: &ToolName) -> Option<ToolInfo> {
         let all_tools = self.list_all_tools().await;
         all_tools
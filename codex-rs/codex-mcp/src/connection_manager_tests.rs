@@ -830,6 +830,7 @@ fn mcp_init_error_display_prompts_for_github_pat() {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -882,6 +883,7 @@ fn mcp_init_error_display_reports_generic_errors() {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
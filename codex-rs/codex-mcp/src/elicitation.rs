@@ -64,6 +64,25 @@ impl ElicitationRequestManager {
             .map_err(|e| anyhow!("failed to send elicitation response: {e:?}"))
     }
 
+    pub(crate) fn approval_policy(&self) -> AskForApproval {
+        self.approval_policy
+            .lock()
+            .map(|policy| *policy)
+            .unwrap_or(AskForApproval::Never)
+    }
+
+    /// Surfaces `request` to the user and waits for the response that
+    /// [`Self::resolve`] delivers for the same server and request id.
+    pub(crate) async fn request_from_user(
+        &self,
+        server_name: String,
+        id: RequestId,
+        request: ElicitationRequest,
+        tx_event: &Sender<Event>,
+    ) -> Result<ElicitationResponse> {
+        request_from_user(&self.requests, server_name, id, request, tx_event).await
+    }
+
     pub(crate) fn make_sender(
         &self,
         server_name: String,
@@ -134,37 +153,47 @@ impl ElicitationRequestManager {
                         elicitation_id,
                     },
                 };
-                let (tx, rx) = oneshot::channel();
-                {
-                    let mut lock = elicitation_requests.lock().await;
-                    lock.insert((server_name.clone(), id.clone()), tx);
-                }
-                let _ = tx_event
-                    .send(Event {
-                        id: "mcp_elicitation_request".to_string(),
-                        msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
-                            turn_id: None,
-                            server_name,
-                            id: match id.clone() {
-                                rmcp::model::NumberOrString::String(value) => {
-                                    ProtocolRequestId::String(value.to_string())
-                                }
-                                rmcp::model::NumberOrString::Number(value) => {
-                                    ProtocolRequestId::Integer(value)
-                                }
-                            },
-                            request,
-                        }),
-                    })
-                    .await;
-                rx.await
-                    .context("elicitation request channel closed unexpectedly")
+                request_from_user(&elicitation_requests, server_name, id, request, &tx_event).await
             }
             .boxed()
         })
     }
 }
 
+async fn request_from_user(
+    requests: &Mutex<ResponderMap>,
+    server_name: String,
+    id: RequestId,
+    request: ElicitationRequest,
+    tx_event: &Sender<Event>,
+) -> Result<ElicitationResponse> {
+    let (tx, rx) = oneshot::channel();
+    {
+        let mut lock = requests.lock().await;
+        lock.insert((server_name.clone(), id.clone()), tx);
+    }
+    let _ = tx_event
+        .send(Event {
+            id: "mcp_elicitation_request".to_string(),
+            msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
+                turn_id: None,
+                server_name,
+                id: protocol_request_id(&id),
+                request,
+            }),
+        })
+        .await;
+    rx.await
+        .context("elicitation request channel closed unexpectedly")
+}
+
+pub(crate) fn protocol_request_id(id: &RequestId) -> ProtocolRequestId {
+    match id.clone() {
+        rmcp::model::NumberOrString::String(value) => ProtocolRequestId::String(value.to_string()),
+        rmcp::model::NumberOrString::Number(value) => ProtocolRequestId::Integer(value),
+    }
+}
+
 pub(crate) fn elicitation_is_rejected_by_policy(approval_policy: AskForApproval) -> bool {
     match approval_policy {
         AskForApproval::Never => true,
@@ -1,7 +1,10 @@
 pub use connection_manager::McpConnectionManager;
+pub use resource_subscriptions::McpResourceUpdateHandler;
 pub use rmcp_client::MCP_SANDBOX_STATE_META_CAPABILITY;
 pub use runtime::McpRuntimeEnvironment;
 pub use runtime::SandboxState;
+pub use sampling::McpSampler;
+pub use sampling::McpSamplingOutput;
 pub use tools::ToolInfo;
 
 pub use mcp::CODEX_APPS_MCP_SERVER_NAME;
@@ -42,6 +45,9 @@ pub(crate) mod codex_apps;
 pub(crate) mod connection_manager;
 pub(crate) mod elicitation;
 pub(crate) mod mcp;
+pub(crate) mod resource_subscriptions;
 pub(crate) mod rmcp_client;
+pub(crate) mod roots;
 pub(crate) mod runtime;
+pub(crate) mod sampling;
 pub(crate) mod tools;
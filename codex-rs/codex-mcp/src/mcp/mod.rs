@@ -405,6 +405,7 @@ fn codex_apps_mcp_server_config(config: &McpConfig) -> McpServerConfig {
         startup_timeout_sec: Some(Duration::from_secs(30)),
         tool_timeout_sec: None,
         default_tools_approval_mode: None,
+        sampling_approval_mode: None,
         enabled_tools: None,
         disabled_tools: None,
         scopes: None,
@@ -182,6 +182,7 @@ async fn effective_mcp_servers_preserve_user_servers_and_add_codex_apps() {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -206,6 +207,7 @@ async fn effective_mcp_servers_preserve_user_servers_and_add_codex_apps() {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
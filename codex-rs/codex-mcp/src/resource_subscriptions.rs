@@ -0,0 +1,59 @@
+//! MCP resource subscription updates.
+//!
+//! Servers that advertise `resources.subscribe` send
+//! `notifications/resources/updated` for every resource a client subscribed
+//! to. Each server gets a listener that forwards those notifications to the
+//! session's handler, which decides how the change reaches the conversation.
+
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+
+use codex_rmcp_client::McpServerChange;
+use tokio::sync::broadcast;
+
+/// Reacts to `notifications/resources/updated` from subscribed servers.
+pub trait McpResourceUpdateHandler: Send + Sync {
+    /// Called when `server` reports that the resource at `uri` changed.
+    fn resource_updated(&self, server: String, uri: String);
+}
+
+/// Holds the session's update handler, which is installed after the
+/// connection manager is built and shared by every server's listener.
+#[derive(Clone, Default)]
+pub(crate) struct ResourceSubscriptionManager {
+    handler: Arc<StdMutex<Option<Arc<dyn McpResourceUpdateHandler>>>>,
+}
+
+impl ResourceSubscriptionManager {
+    pub(crate) fn set_handler(&self, handler: Arc<dyn McpResourceUpdateHandler>) {
+        if let Ok(mut slot) = self.handler.lock() {
+            *slot = Some(handler);
+        }
+    }
+
+    pub(crate) fn handler(&self) -> Option<Arc<dyn McpResourceUpdateHandler>> {
+        self.handler.lock().ok().and_then(|slot| slot.clone())
+    }
+
+    /// Forwards resource-updated notifications from `server_name` to the
+    /// installed handler until the client's change channel closes.
+    pub(crate) fn spawn_listener(
+        &self,
+        server_name: String,
+        mut server_changes: broadcast::Receiver<McpServerChange>,
+    ) {
+        let subscriptions = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let uri = match server_changes.recv().await {
+                    Ok(McpServerChange::ResourceUpdated { uri }) => uri,
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                if let Some(handler) = subscriptions.handler() {
+                    handler.resource_updated(server_name.clone(), uri);
+                }
+            }
+        });
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! This module owns startup of individual RMCP clients: building the transport,
 //! initializing the server, listing raw tools, applying per-server tool filters,
-//! and exposing cached startup snapshots while a client is still connecting.
+//! tracking server prompts, and exposing cached startup snapshots while a client
+//! is still connecting.
 //! Higher-level aggregation and resource/tool APIs live in
 //! [`crate::connection_manager`].
 
@@ -28,8 +29,11 @@ use crate::codex_apps::write_cached_codex_apps_tools_if_needed;
 use crate::elicitation::ElicitationRequestManager;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::ToolPluginProvenance;
+use crate::resource_subscriptions::ResourceSubscriptionManager;
+use crate::roots::RootsManager;
 use crate::runtime::McpRuntimeEnvironment;
 use crate::runtime::emit_duration;
+use crate::sampling::SamplingRequestManager;
 use crate::tools::ToolFilter;
 use crate::tools::ToolInfo;
 use crate::tools::filter_tools;
@@ -40,14 +44,19 @@ use async_channel::Sender;
 use codex_api::SharedAuthProvider;
 use codex_async_utils::CancelErr;
 use codex_async_utils::OrCancelExt;
+use codex_config::McpSamplingApproval;
 use codex_config::McpServerConfig;
 use codex_config::McpServerTransportConfig;
 use codex_config::types::OAuthCredentialsStoreMode;
 use codex_exec_server::HttpClient;
 use codex_exec_server::ReqwestHttpClient;
+use codex_protocol::mcp::Prompt;
 use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::McpPromptsChangedEvent;
 use codex_rmcp_client::ExecutorStdioServerLauncher;
 use codex_rmcp_client::LocalStdioServerLauncher;
+use codex_rmcp_client::McpServerChange;
 use codex_rmcp_client::RmcpClient;
 use codex_rmcp_client::StdioServerLauncher;
 use futures::future::BoxFuture;
@@ -58,8 +67,13 @@ use rmcp::model::ElicitationCapability;
 use rmcp::model::FormElicitationCapability;
 use rmcp::model::Implementation;
 use rmcp::model::InitializeRequestParams;
+use rmcp::model::PaginatedRequestParams;
 use rmcp::model::ProtocolVersion;
+use rmcp::model::RootsCapabilities;
+use tokio::sync::RwLock;
+use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
+use tracing::warn;
 
 /// MCP server capability indicating that Codex should include [`SandboxState`]
 /// in tool-call request `_meta` under this key.
@@ -79,10 +93,30 @@ pub(crate) struct ManagedClient {
     pub(crate) tool_timeout: Option<Duration>,
     pub(crate) server_instructions: Option<String>,
     pub(crate) server_supports_sandbox_state_meta_capability: bool,
+    pub(crate) server_supports_prompts: bool,
+    pub(crate) server_supports_resource_subscriptions: bool,
+    /// Prompts listed from the server, or `None` until the next listing after
+    /// startup or after the server reports `notifications/prompts/list_changed`.
+    pub(crate) prompts: Arc<RwLock<Option<Vec<Prompt>>>>,
     pub(crate) codex_apps_tools_cache_context: Option<CodexAppsToolsCacheContext>,
 }
 
 impl ManagedClient {
+    /// Returns the server's prompts, listing them from the server when the
+    /// cache is empty. Servers that do not advertise prompts return nothing.
+    pub(crate) async fn listed_prompts(&self) -> Result<Vec<Prompt>> {
+        if !self.server_supports_prompts {
+            return Ok(Vec::new());
+        }
+        if let Some(prompts) = self.prompts.read().await.as_ref() {
+            return Ok(prompts.clone());
+        }
+
+        let prompts = list_prompts_for_client_uncached(&self.client, self.tool_timeout).await?;
+        *self.prompts.write().await = Some(prompts.clone());
+        Ok(prompts)
+    }
+
     fn listed_tools(&self) -> Vec<ToolInfo> {
         let total_start = Instant::now();
         if let Some(cache_context) = self.codex_apps_tools_cache_context.as_ref()
@@ -128,6 +162,9 @@ impl AsyncManagedClient {
         cancel_token: CancellationToken,
         tx_event: Sender<Event>,
         elicitation_requests: ElicitationRequestManager,
+        sampling_requests: SamplingRequestManager,
+        roots: RootsManager,
+        resource_subscriptions: ResourceSubscriptionManager,
         codex_apps_tools_cache_context: Option<CodexAppsToolsCacheContext>,
         tool_plugin_provenance: Arc<ToolPluginProvenance>,
         runtime_environment: McpRuntimeEnvironment,
@@ -169,6 +206,10 @@ impl AsyncManagedClient {
                         tool_filter: startup_tool_filter,
                         tx_event,
                         elicitation_requests,
+                        sampling_requests,
+                        sampling_approval_mode: config.sampling_approval_mode.unwrap_or_default(),
+                        roots,
+                        resource_subscriptions,
                         codex_apps_tools_cache_context,
                     },
                 )
@@ -359,6 +400,69 @@ pub(crate) async fn list_tools_for_client_uncached(
     Ok(tools)
 }
 
+async fn list_prompts_for_client_uncached(
+    client: &RmcpClient,
+    timeout: Option<Duration>,
+) -> Result<Vec<Prompt>> {
+    let mut collected = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let params = cursor.as_ref().map(|next| PaginatedRequestParams {
+            meta: None,
+            cursor: Some(next.clone()),
+        });
+        let response = client.list_prompts(params, timeout).await?;
+        for prompt in response.prompts {
+            let prompt = serde_json::to_value(prompt).and_then(Prompt::from_mcp_value);
+            match prompt {
+                Ok(prompt) => collected.push(prompt),
+                Err(err) => warn!("Failed to convert MCP prompt: {err}"),
+            }
+        }
+
+        match response.next_cursor {
+            Some(next) => {
+                if cursor.as_ref() == Some(&next) {
+                    return Err(anyhow!("prompts/list returned duplicate cursor"));
+                }
+                cursor = Some(next);
+            }
+            None => return Ok(collected),
+        }
+    }
+}
+
+/// Drops the cached prompt list whenever the server reports that it changed
+/// and tells the session so clients can re-list.
+fn spawn_prompt_list_changed_listener(
+    server_name: String,
+    mut server_changes: broadcast::Receiver<McpServerChange>,
+    prompts: Arc<RwLock<Option<Vec<Prompt>>>>,
+    tx_event: Sender<Event>,
+) {
+    tokio::spawn(async move {
+        loop {
+            match server_changes.recv().await {
+                Ok(McpServerChange::PromptListChanged)
+                | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+
+            *prompts.write().await = None;
+            let event = Event {
+                id: "mcp_prompts_changed".to_string(),
+                msg: EventMsg::McpPromptsChanged(McpPromptsChangedEvent {
+                    server: server_name.clone(),
+                }),
+            };
+            if tx_event.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+}
+
 fn resolve_bearer_token(
     server_name: &str,
     bearer_token_env_var: Option<&str>,
@@ -408,16 +512,24 @@ async fn start_server_task(
         tool_filter,
         tx_event,
         elicitation_requests,
+        sampling_requests,
+        sampling_approval_mode,
+        roots,
+        resource_subscriptions,
         codex_apps_tools_cache_context,
     } = params;
     let elicitation = elicitation_capability_for_server(&server_name);
+    // Servers configured to deny sampling are not told that Codex supports it.
+    let supports_sampling = sampling_approval_mode != McpSamplingApproval::Deny;
     let params = InitializeRequestParams {
         meta: None,
         capabilities: ClientCapabilities {
             experimental: None,
             extensions: None,
-            roots: None,
-            sampling: None,
+            roots: Some(RootsCapabilities {
+                list_changed: Some(true),
+            }),
+            sampling: supports_sampling.then(Default::default),
             elicitation,
             tasks: None,
         },
@@ -432,10 +544,29 @@ async fn start_server_task(
         protocol_version: ProtocolVersion::V_2025_06_18,
     };
 
+    // Subscribe before initializing so no notification sent right after the
+    // handshake is missed.
+    let server_changes = client.subscribe_server_changes();
+    let resource_changes = client.subscribe_server_changes();
+    let prompts_tx_event = tx_event.clone();
+    let send_sampling = supports_sampling.then(|| {
+        sampling_requests.make_sender(
+            server_name.clone(),
+            sampling_approval_mode,
+            elicitation_requests.clone(),
+            tx_event.clone(),
+        )
+    });
     let send_elicitation = elicitation_requests.make_sender(server_name.clone(), tx_event);
 
     let initialize_result = client
-        .initialize(params, startup_timeout, send_elicitation)
+        .initialize(
+            params,
+            startup_timeout,
+            send_elicitation,
+            send_sampling,
+            Some(roots.make_lister()),
+        )
         .await
         .map_err(StartupOutcomeError::from)?;
 
@@ -445,6 +576,25 @@ async fn start_server_task(
         .as_ref()
         .and_then(|exp| exp.get(MCP_SANDBOX_STATE_META_CAPABILITY))
         .is_some();
+    let server_supports_prompts = initialize_result.capabilities.prompts.is_some();
+    let prompts = Arc::new(RwLock::new(None));
+    if server_supports_prompts {
+        spawn_prompt_list_changed_listener(
+            server_name.clone(),
+            server_changes,
+            Arc::clone(&prompts),
+            prompts_tx_event,
+        );
+    }
+    let server_supports_resource_subscriptions = initialize_result
+        .capabilities
+        .resources
+        .as_ref()
+        .and_then(|resources| resources.subscribe)
+        .unwrap_or(false);
+    if server_supports_resource_subscriptions {
+        resource_subscriptions.spawn_listener(server_name.clone(), resource_changes);
+    }
     let list_start = Instant::now();
     let fetch_start = Instant::now();
     let tools = list_tools_for_client_uncached(
@@ -481,6 +631,9 @@ async fn start_server_task(
         tool_filter,
         server_instructions: initialize_result.instructions,
         server_supports_sandbox_state_meta_capability,
+        server_supports_prompts,
+        server_supports_resource_subscriptions,
+        prompts,
         codex_apps_tools_cache_context,
     };
 
@@ -493,6 +646,10 @@ struct StartServerTaskParams {
     tool_filter: ToolFilter,
     tx_event: Sender<Event>,
     elicitation_requests: ElicitationRequestManager,
+    sampling_requests: SamplingRequestManager,
+    sampling_approval_mode: McpSamplingApproval,
+    roots: RootsManager,
+    resource_subscriptions: ResourceSubscriptionManager,
     codex_apps_tools_cache_context: Option<CodexAppsToolsCacheContext>,
 }
 
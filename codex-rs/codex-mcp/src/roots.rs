@@ -0,0 +1,132 @@
+//! MCP roots reporting.
+//!
+//! Servers learn which directories are in scope through `roots/list`. Codex
+//! answers with the thread's cwd followed by the writable roots of the active
+//! permission profile, which already include any `--add-dir` directories, so
+//! servers see the same boundaries the sandbox enforces.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+
+use codex_protocol::models::PermissionProfile;
+use codex_rmcp_client::ListRoots;
+use rmcp::model::Root;
+use url::Url;
+
+#[derive(Clone, Default)]
+pub(crate) struct RootsManager {
+    roots: Arc<StdMutex<Vec<PathBuf>>>,
+}
+
+impl RootsManager {
+    pub(crate) fn new(cwd: &Path, permission_profile: &PermissionProfile) -> Self {
+        Self {
+            roots: Arc::new(StdMutex::new(workspace_roots(cwd, permission_profile))),
+        }
+    }
+
+    /// Recomputes the roots and returns whether they differ from the ones
+    /// servers were last told about.
+    pub(crate) fn update(&self, cwd: &Path, permission_profile: &PermissionProfile) -> bool {
+        let roots = workspace_roots(cwd, permission_profile);
+        let Ok(mut current) = self.roots.lock() else {
+            return false;
+        };
+        if *current == roots {
+            return false;
+        }
+        *current = roots;
+        true
+    }
+
+    pub(crate) fn make_lister(&self) -> ListRoots {
+        let roots = Arc::clone(&self.roots);
+        Box::new(move || {
+            roots
+                .lock()
+                .map(|roots| roots.iter().map(PathBuf::as_path).map(mcp_root).collect())
+                .unwrap_or_default()
+        })
+    }
+}
+
+fn workspace_roots(cwd: &Path, permission_profile: &PermissionProfile) -> Vec<PathBuf> {
+    // Writable roots come back canonicalized, so compare against the same
+    // form of the cwd to avoid listing it twice.
+    let cwd = cwd.canonicalize().unwrap_or_else(|_| cwd.to_path_buf());
+    let mut roots = vec![cwd.clone()];
+    for writable_root in permission_profile
+        .file_system_sandbox_policy()
+        .get_writable_roots_with_cwd(&cwd)
+    {
+        let root = writable_root.root.into_path_buf();
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+    roots
+}
+
+fn mcp_root(path: &Path) -> Root {
+    let uri = Url::from_file_path(path)
+        .map(String::from)
+        .unwrap_or_else(|()| format!("file://{}", path.display()));
+    Root {
+        uri,
+        name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_protocol::models::PermissionProfile;
+    use codex_protocol::permissions::NetworkSandboxPolicy;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn roots_list_cwd_then_writable_roots_without_duplicates() {
+        let cwd = tempdir().expect("create cwd");
+        let extra = tempdir().expect("create extra dir");
+        let cwd_path = cwd.path().canonicalize().expect("canonicalize cwd");
+        let extra_path = extra.path().canonicalize().expect("canonicalize extra dir");
+
+        let read_only = workspace_roots(&cwd_path, &PermissionProfile::read_only());
+        assert_eq!(read_only, vec![cwd_path.clone()]);
+
+        let profile = PermissionProfile::workspace_write_with(
+            &[extra_path.clone().try_into().expect("absolute path")],
+            NetworkSandboxPolicy::Restricted,
+            /*exclude_tmpdir_env_var*/ true,
+            /*exclude_slash_tmp*/ true,
+        );
+        let workspace_write = workspace_roots(&cwd_path, &profile);
+        assert_eq!(workspace_write, vec![cwd_path, extra_path]);
+    }
+
+    #[test]
+    fn update_reports_only_real_changes() {
+        let cwd = tempdir().expect("create cwd");
+        let other = tempdir().expect("create other cwd");
+        let roots = RootsManager::new(cwd.path(), &PermissionProfile::read_only());
+
+        assert!(!roots.update(cwd.path(), &PermissionProfile::read_only()));
+        assert!(roots.update(other.path(), &PermissionProfile::read_only()));
+
+        let list_roots = roots.make_lister();
+        let listed = list_roots();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(
+            listed[0].uri,
+            Url::from_file_path(other.path().canonicalize().expect("canonicalize other cwd"))
+                .map(String::from)
+                .expect("file url")
+        );
+    }
+}
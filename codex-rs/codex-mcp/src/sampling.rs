@@ -0,0 +1,248 @@
+//! MCP sampling request handling.
+//!
+//! RMCP clients call into this module when a server sends
+//! `sampling/createMessage`. The server's configured approval mode decides
+//! whether the request is declined, sent straight to the session's model, or
+//! first confirmed by the user through the elicitation flow. Each request is
+//! reported as an `McpSamplingBegin`/`McpSamplingEnd` event pair so the
+//! exchange lands in the rollout.
+
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::time::Instant;
+
+use crate::elicitation::ElicitationRequestManager;
+use crate::elicitation::elicitation_is_rejected_by_policy;
+use crate::elicitation::protocol_request_id;
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use async_channel::Sender;
+use codex_config::McpSamplingApproval;
+use codex_protocol::approvals::ElicitationRequest;
+use codex_protocol::mcp::CreateMessageRequest;
+use codex_protocol::mcp::CreateMessageResult;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::McpSamplingBeginEvent;
+use codex_protocol::protocol::McpSamplingEndEvent;
+use codex_protocol::protocol::TokenUsage;
+use codex_rmcp_client::SendSampling;
+use futures::future::BoxFuture;
+use futures::future::FutureExt;
+use rmcp::model::ElicitationAction;
+use rmcp::model::RequestId;
+
+/// Answers MCP sampling requests with the session's model.
+pub trait McpSampler: Send + Sync {
+    /// Runs `request` on behalf of `server` and returns the model's reply.
+    fn create_message(
+        &self,
+        server: String,
+        request: CreateMessageRequest,
+    ) -> BoxFuture<'static, Result<McpSamplingOutput>>;
+}
+
+/// A completed sampling call and the tokens it consumed.
+pub struct McpSamplingOutput {
+    pub result: CreateMessageResult,
+    pub token_usage: Option<TokenUsage>,
+}
+
+/// Holds the session's sampler, which is installed after the connection
+/// manager is built and shared by every server's sampling sender.
+#[derive(Clone, Default)]
+pub(crate) struct SamplingRequestManager {
+    sampler: Arc<StdMutex<Option<Arc<dyn McpSampler>>>>,
+}
+
+impl SamplingRequestManager {
+    pub(crate) fn set_sampler(&self, sampler: Arc<dyn McpSampler>) {
+        if let Ok(mut slot) = self.sampler.lock() {
+            *slot = Some(sampler);
+        }
+    }
+
+    pub(crate) fn sampler(&self) -> Option<Arc<dyn McpSampler>> {
+        self.sampler.lock().ok().and_then(|slot| slot.clone())
+    }
+
+    pub(crate) fn make_sender(
+        &self,
+        server_name: String,
+        approval_mode: McpSamplingApproval,
+        elicitation_requests: ElicitationRequestManager,
+        tx_event: Sender<Event>,
+    ) -> SendSampling {
+        let sampling_requests = self.clone();
+        Box::new(move |id, params| {
+            let sampling_requests = sampling_requests.clone();
+            let server_name = server_name.clone();
+            let elicitation_requests = elicitation_requests.clone();
+            let tx_event = tx_event.clone();
+            async move {
+                let request = serde_json::to_value(params)
+                    .and_then(CreateMessageRequest::from_mcp_value)
+                    .context("failed to convert MCP sampling request")?;
+                let call_id = format!("{server_name}:{}", protocol_request_id(&id));
+                let _ = tx_event
+                    .send(Event {
+                        id: "mcp_sampling".to_string(),
+                        msg: EventMsg::McpSamplingBegin(McpSamplingBeginEvent {
+                            call_id: call_id.clone(),
+                            server: server_name.clone(),
+                            request: request.clone(),
+                        }),
+                    })
+                    .await;
+
+                let start = Instant::now();
+                let outcome = sampling_requests
+                    .run(
+                        &server_name,
+                        id,
+                        approval_mode,
+                        &elicitation_requests,
+                        &tx_event,
+                        request.clone(),
+                    )
+                    .await;
+                let (result, token_usage) = match outcome {
+                    Ok(McpSamplingOutput {
+                        result,
+                        token_usage,
+                    }) => (Ok(result), token_usage),
+                    Err(err) => (Err(err.to_string()), None),
+                };
+                let _ = tx_event
+                    .send(Event {
+                        id: "mcp_sampling".to_string(),
+                        msg: EventMsg::McpSamplingEnd(McpSamplingEndEvent {
+                            call_id,
+                            server: server_name,
+                            request,
+                            duration: start.elapsed(),
+                            result: result.clone(),
+                            token_usage,
+                        }),
+                    })
+                    .await;
+
+                let result = result.map_err(|err| anyhow!(err))?;
+                serde_json::to_value(result)
+                    .and_then(serde_json::from_value::<rmcp::model::CreateMessageResult>)
+                    .context("failed to convert MCP sampling result")
+            }
+            .boxed()
+        })
+    }
+
+    async fn run(
+        &self,
+        server_name: &str,
+        id: RequestId,
+        approval_mode: McpSamplingApproval,
+        elicitation_requests: &ElicitationRequestManager,
+        tx_event: &Sender<Event>,
+        request: CreateMessageRequest,
+    ) -> Result<McpSamplingOutput> {
+        match approval_mode {
+            McpSamplingApproval::Approve => {}
+            McpSamplingApproval::Deny => {
+                return Err(anyhow!(
+                    "sampling is disabled for MCP server '{server_name}'"
+                ));
+            }
+            McpSamplingApproval::Prompt => {
+                if elicitation_is_rejected_by_policy(elicitation_requests.approval_policy()) {
+                    return Err(anyhow!(
+                        "sampling requests from '{server_name}' need approval, which the approval policy does not allow"
+                    ));
+                }
+                let response = elicitation_requests
+                    .request_from_user(
+                        server_name.to_string(),
+                        id,
+                        sampling_approval_request(server_name, &request),
+                        tx_event,
+                    )
+                    .await?;
+                if !matches!(response.action, ElicitationAction::Accept) {
+                    return Err(anyhow!("user declined the sampling request"));
+                }
+            }
+        }
+
+        let sampler = self
+            .sampler()
+            .ok_or_else(|| anyhow!("sampling is not available in this session"))?;
+        sampler
+            .create_message(server_name.to_string(), request)
+            .await
+    }
+}
+
+/// Builds a confirm-only form elicitation that shows the user what the server
+/// wants to send to the model.
+fn sampling_approval_request(
+    server_name: &str,
+    request: &CreateMessageRequest,
+) -> ElicitationRequest {
+    let mut message = format!("MCP server '{server_name}' wants to call the model.");
+    if let Some(system_prompt) = request.system_prompt.as_deref() {
+        message.push_str(&format!("\n\nSystem: {system_prompt}"));
+    }
+    for sampling_message in &request.messages {
+        message.push_str(&format!(
+            "\n\n{}: {}",
+            sampling_message.role,
+            sampling_message.text()
+        ));
+    }
+    ElicitationRequest::Form {
+        meta: None,
+        message,
+        requested_schema: serde_json::json!({
+            "type": "object",
+            "properties": {},
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_protocol::mcp::SamplingMessage;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn sampling_approval_request_lists_system_prompt_and_messages() {
+        let request = CreateMessageRequest {
+            messages: vec![SamplingMessage {
+                role: "user".to_string(),
+                content: json!({ "type": "text", "text": "Summarize the diff." }),
+            }],
+            model_preferences: None,
+            system_prompt: Some("Be brief.".to_string()),
+            include_context: None,
+            temperature: None,
+            max_tokens: 128,
+            stop_sequences: None,
+            metadata: None,
+        };
+
+        assert_eq!(
+            sampling_approval_request("docs", &request),
+            ElicitationRequest::Form {
+                meta: None,
+                message: "MCP server 'docs' wants to call the model.\n\nSystem: Be brief.\n\nuser: Summarize the diff.".to_string(),
+                requested_schema: json!({
+                    "type": "object",
+                    "properties": {},
+                }),
+            }
+        );
+    }
+}
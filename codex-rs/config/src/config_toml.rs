@@ -423,6 +423,7 @@ pub struct ConfigToml {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ThreadStoreToml {
     Local {},
+    Sqlite {},
     Remote {
         endpoint: String,
     },
@@ -26,6 +26,20 @@ pub struct HookEventsToml {
     pub user_prompt_submit: Vec<MatcherGroup>,
     #[serde(rename = "Stop", default)]
     pub stop: Vec<MatcherGroup>,
+    #[serde(rename = "PreCompact", default)]
+    pub pre_compact: Vec<MatcherGroup>,
+    #[serde(rename = "PostCompact", default)]
+    pub post_compact: Vec<MatcherGroup>,
+    #[serde(rename = "SubagentStart", default)]
+    pub subagent_start: Vec<MatcherGroup>,
+    #[serde(rename = "SubagentStop", default)]
+    pub subagent_stop: Vec<MatcherGroup>,
+    #[serde(rename = "PreApplyPatch", default)]
+    pub pre_apply_patch: Vec<MatcherGroup>,
+    #[serde(rename = "PostApplyPatch", default)]
+    pub post_apply_patch: Vec<MatcherGroup>,
+    #[serde(rename = "TurnError", default)]
+    pub turn_error: Vec<MatcherGroup>,
 }
 
 impl HookEventsToml {
@@ -37,6 +51,13 @@ impl HookEventsToml {
             session_start,
             user_prompt_submit,
             stop,
+            pre_compact,
+            post_compact,
+            subagent_start,
+            subagent_stop,
+            pre_apply_patch,
+            post_apply_patch,
+            turn_error,
         } = self;
         pre_tool_use.is_empty()
             && permission_request.is_empty()
@@ -44,6 +65,13 @@ impl HookEventsToml {
             && session_start.is_empty()
             && user_prompt_submit.is_empty()
             && stop.is_empty()
+            && pre_compact.is_empty()
+            && post_compact.is_empty()
+            && subagent_start.is_empty()
+            && subagent_stop.is_empty()
+            && pre_apply_patch.is_empty()
+            && post_apply_patch.is_empty()
+            && turn_error.is_empty()
     }
 
     pub fn handler_count(&self) -> usize {
@@ -54,6 +82,13 @@ impl HookEventsToml {
             session_start,
             user_prompt_submit,
             stop,
+            pre_compact,
+            post_compact,
+            subagent_start,
+            subagent_stop,
+            pre_apply_patch,
+            post_apply_patch,
+            turn_error,
         } = self;
         [
             pre_tool_use,
@@ -62,6 +97,13 @@ impl HookEventsToml {
             session_start,
             user_prompt_submit,
             stop,
+            pre_compact,
+            post_compact,
+            subagent_start,
+            subagent_stop,
+            pre_apply_patch,
+            post_apply_patch,
+            turn_error,
         ]
         .into_iter()
         .flatten()
@@ -69,7 +111,7 @@ impl HookEventsToml {
         .sum()
     }
 
-    pub fn into_matcher_groups(self) -> [(HookEventName, Vec<MatcherGroup>); 6] {
+    pub fn into_matcher_groups(self) -> [(HookEventName, Vec<MatcherGroup>); 13] {
         [
             (HookEventName::PreToolUse, self.pre_tool_use),
             (HookEventName::PermissionRequest, self.permission_request),
@@ -77,6 +119,13 @@ impl HookEventsToml {
             (HookEventName::SessionStart, self.session_start),
             (HookEventName::UserPromptSubmit, self.user_prompt_submit),
             (HookEventName::Stop, self.stop),
+            (HookEventName::PreCompact, self.pre_compact),
+            (HookEventName::PostCompact, self.post_compact),
+            (HookEventName::SubagentStart, self.subagent_start),
+            (HookEventName::SubagentStop, self.subagent_stop),
+            (HookEventName::PreApplyPatch, self.pre_apply_patch),
+            (HookEventName::PostApplyPatch, self.post_apply_patch),
+            (HookEventName::TurnError, self.turn_error),
         ]
     }
 }
@@ -102,10 +151,70 @@ pub enum HookHandlerConfig {
         #[serde(default, rename = "statusMessage")]
         status_message: Option<String>,
     },
+    /// Ask a model to judge the hook input.
     #[serde(rename = "prompt")]
-    Prompt {},
+    Prompt(ModelHookHandlerConfig),
+    /// Ask a model to judge the hook input together with the session transcript.
     #[serde(rename = "agent")]
-    Agent {},
+    Agent(ModelHookHandlerConfig),
+    /// Send the hook input to a long-running hook server over a Unix socket.
+    #[serde(rename = "server")]
+    Server(ServerHookHandlerConfig),
+}
+
+/// Settings shared by the model-backed `prompt` and `agent` hook handlers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ModelHookHandlerConfig {
+    /// Instructions for the model. `$ARGUMENTS` is replaced with the hook input JSON; without the
+    /// placeholder the input is appended after the prompt.
+    pub prompt: String,
+    /// Model to query. Defaults to the session model.
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default, rename = "timeout")]
+    pub timeout_sec: Option<u64>,
+    /// JSON schema for the model output. When set, the output is interpreted like the stdout of a
+    /// command hook for the same event; otherwise the model answers with an allow/block decision.
+    #[serde(default, rename = "outputSchema")]
+    pub output_schema: Option<serde_json::Value>,
+    #[serde(default, rename = "statusMessage")]
+    pub status_message: Option<String>,
+}
+
+/// Settings for a `server` hook handler, which keeps one process alive across hook events instead
+/// of spawning a command for each of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ServerHookHandlerConfig {
+    /// Unix socket the hook server listens on. Relative paths are resolved against the directory
+    /// of the file that configures the hook.
+    pub socket: PathBuf,
+    /// Command that starts the hook server when nothing is listening on `socket`. Without it the
+    /// server has to be started outside of Codex.
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default, rename = "timeout")]
+    pub timeout_sec: Option<u64>,
+    /// Behavior while the hook server cannot be reached.
+    #[serde(default)]
+    pub fallback: ServerHookFallback,
+    /// Command hook to run in place of the server while it cannot be reached. Takes precedence
+    /// over `fallback`.
+    #[serde(default, rename = "fallbackCommand")]
+    pub fallback_command: Option<String>,
+    #[serde(default, rename = "statusMessage")]
+    pub status_message: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerHookFallback {
+    /// Report the hook run as failed and carry on, like any other hook failure.
+    #[default]
+    Fail,
+    /// Carry on as if the hook server returned no output.
+    Allow,
+    /// Block the action, as if a command hook exited with code 2.
+    Block,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
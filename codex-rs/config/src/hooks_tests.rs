@@ -5,6 +5,9 @@ use super::HookHandlerConfig;
 use super::HooksFile;
 use super::ManagedHooksRequirementsToml;
 use super::MatcherGroup;
+use super::ModelHookHandlerConfig;
+use super::ServerHookFallback;
+use super::ServerHookHandlerConfig;
 
 #[test]
 fn hooks_file_deserializes_existing_json_shape() {
@@ -117,3 +120,110 @@ command = "python3 /enterprise/place/pre.py"
         }
     );
 }
+
+#[test]
+fn hooks_file_deserializes_model_handlers() {
+    let parsed: HooksFile = serde_json::from_str(
+        r#"{
+  "hooks": {
+    "Stop": [
+      {
+        "hooks": [
+          {
+            "type": "prompt",
+            "prompt": "Did the agent run the tests? $ARGUMENTS",
+            "model": "gpt-5-mini",
+            "timeout": 20
+          },
+          {
+            "type": "agent",
+            "prompt": "Check the transcript for skipped steps.",
+            "outputSchema": {"type": "object"},
+            "statusMessage": "reviewing"
+          }
+        ]
+      }
+    ]
+  }
+}"#,
+    )
+    .expect("hooks.json should deserialize");
+
+    assert_eq!(
+        parsed.hooks.stop,
+        vec![MatcherGroup {
+            matcher: None,
+            hooks: vec![
+                HookHandlerConfig::Prompt(ModelHookHandlerConfig {
+                    prompt: "Did the agent run the tests? $ARGUMENTS".to_string(),
+                    model: Some("gpt-5-mini".to_string()),
+                    timeout_sec: Some(20),
+                    output_schema: None,
+                    status_message: None,
+                }),
+                HookHandlerConfig::Agent(ModelHookHandlerConfig {
+                    prompt: "Check the transcript for skipped steps.".to_string(),
+                    model: None,
+                    timeout_sec: None,
+                    output_schema: Some(serde_json::json!({"type": "object"})),
+                    status_message: Some("reviewing".to_string()),
+                }),
+            ],
+        }]
+    );
+}
+
+#[test]
+fn hooks_file_deserializes_server_handlers() {
+    let parsed: HooksFile = serde_json::from_str(
+        r#"{
+  "hooks": {
+    "PreToolUse": [
+      {
+        "matcher": "^Bash$",
+        "hooks": [
+          {
+            "type": "server",
+            "socket": "/tmp/policy.sock",
+            "command": "python3 /tmp/policy_server.py",
+            "timeout": 5,
+            "fallback": "block",
+            "fallbackCommand": "python3 /tmp/policy.py"
+          },
+          {
+            "type": "server",
+            "socket": "policy.sock"
+          }
+        ]
+      }
+    ]
+  }
+}"#,
+    )
+    .expect("hooks.json should deserialize");
+
+    assert_eq!(
+        parsed.hooks.pre_tool_use,
+        vec![MatcherGroup {
+            matcher: Some("^Bash$".to_string()),
+            hooks: vec![
+                HookHandlerConfig::Server(ServerHookHandlerConfig {
+                    socket: "/tmp/policy.sock".into(),
+                    command: Some("python3 /tmp/policy_server.py".to_string()),
+                    timeout_sec: Some(5),
+                    fallback: ServerHookFallback::Block,
+                    fallback_command: Some("python3 /tmp/policy.py".to_string()),
+                    status_message: None,
+                }),
+                HookHandlerConfig::Server(ServerHookHandlerConfig {
+                    socket: "policy.sock".into(),
+                    command: None,
+                    timeout_sec: None,
+                    fallback: ServerHookFallback::Fail,
+                    fallback_command: None,
+                    status_message: None,
+                }),
+            ],
+        }]
+    );
+}
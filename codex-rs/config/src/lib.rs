@@ -74,6 +74,9 @@ pub use hook_config::HookHandlerConfig;
 pub use hook_config::HooksFile;
 pub use hook_config::ManagedHooksRequirementsToml;
 pub use hook_config::MatcherGroup;
+pub use hook_config::ModelHookHandlerConfig;
+pub use hook_config::ServerHookFallback;
+pub use hook_config::ServerHookHandlerConfig;
 pub use host_name::host_name;
 pub use marketplace_edit::MarketplaceConfigUpdate;
 pub use marketplace_edit::RemoveMarketplaceConfigOutcome;
@@ -83,6 +86,7 @@ pub use marketplace_edit::remove_user_marketplace_config;
 pub use mcp_edit::ConfigEditsBuilder;
 pub use mcp_edit::load_global_mcp_servers;
 pub use mcp_types::AppToolApproval;
+pub use mcp_types::McpSamplingApproval;
 pub use mcp_types::McpServerConfig;
 pub use mcp_types::McpServerDisabledReason;
 pub use mcp_types::McpServerEnvVar;
@@ -13,6 +13,7 @@ use toml_edit::value;
 
 use crate::AppToolApproval;
 use crate::CONFIG_TOML_FILE;
+use crate::McpSamplingApproval;
 use crate::McpServerConfig;
 use crate::McpServerEnvVar;
 use crate::McpServerTransportConfig;
@@ -197,6 +198,13 @@ fn serialize_mcp_server(config: &McpServerConfig) -> TomlItem {
             AppToolApproval::Approve => "approve",
         });
     }
+    if let Some(approval_mode) = config.sampling_approval_mode {
+        entry["sampling_approval_mode"] = value(match approval_mode {
+            McpSamplingApproval::Prompt => "prompt",
+            McpSamplingApproval::Approve => "approve",
+            McpSamplingApproval::Deny => "deny",
+        });
+    }
     if let Some(enabled_tools) = &config.enabled_tools
         && !enabled_tools.is_empty()
     {
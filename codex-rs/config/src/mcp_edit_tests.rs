@@ -30,6 +30,7 @@ async fn replace_mcp_servers_serializes_per_tool_approval_overrides() -> anyhow:
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: Some(AppToolApproval::Auto),
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -22,6 +22,19 @@ pub enum AppToolApproval {
     Approve,
 }
 
+/// How Codex answers `sampling/createMessage` requests from an MCP server.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum McpSamplingApproval {
+    /// Ask the user before every sampling request.
+    #[default]
+    Prompt,
+    /// Run sampling requests from this server without asking.
+    Approve,
+    /// Reject every sampling request from this server.
+    Deny,
+}
+
 /// Human-readable reason a configured MCP server was disabled after requirements
 /// were applied.
 ///
@@ -155,6 +168,10 @@ pub struct McpServerConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_tools_approval_mode: Option<AppToolApproval>,
 
+    /// Approval mode for `sampling/createMessage` requests sent by this server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling_approval_mode: Option<McpSamplingApproval>,
+
     /// Explicit allow-list of tools exposed from this server. When set, only these tools will be registered.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enabled_tools: Option<Vec<String>>,
@@ -227,6 +244,8 @@ pub struct RawMcpServerConfig {
     #[serde(default)]
     pub default_tools_approval_mode: Option<AppToolApproval>,
     #[serde(default)]
+    pub sampling_approval_mode: Option<McpSamplingApproval>,
+    #[serde(default)]
     pub enabled_tools: Option<Vec<String>>,
     #[serde(default)]
     pub disabled_tools: Option<Vec<String>>,
@@ -264,6 +283,7 @@ impl TryFrom<RawMcpServerConfig> for McpServerConfig {
             required,
             supports_parallel_tool_calls,
             default_tools_approval_mode,
+            sampling_approval_mode,
             enabled_tools,
             disabled_tools,
             scopes,
@@ -335,6 +355,7 @@ impl TryFrom<RawMcpServerConfig> for McpServerConfig {
             supports_parallel_tool_calls: supports_parallel_tool_calls.unwrap_or_default(),
             disabled_reason: None,
             default_tools_approval_mode,
+            sampling_approval_mode,
             enabled_tools,
             disabled_tools,
             scopes,
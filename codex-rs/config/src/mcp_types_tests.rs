@@ -343,6 +343,22 @@ fn deserialize_server_config_with_default_tool_approval_mode() {
     assert_eq!(round_tripped, cfg);
 }
 
+#[test]
+fn deserialize_server_config_with_sampling_approval_mode() {
+    let cfg: McpServerConfig = toml::from_str(
+        r#"
+            command = "echo"
+            sampling_approval_mode = "deny"
+        "#,
+    )
+    .expect("should deserialize sampling approval mode");
+
+    assert_eq!(cfg.sampling_approval_mode, Some(McpSamplingApproval::Deny));
+
+    let serialized = toml::to_string(&cfg).expect("should serialize MCP config");
+    assert!(serialized.contains("sampling_approval_mode = \"deny\""));
+}
+
 #[test]
 fn serialize_round_trips_server_config_with_parallel_tool_calls() {
     let cfg: McpServerConfig = toml::from_str(
@@ -390,6 +406,7 @@ fn deserialize_ignores_unknown_server_fields() {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -1,8 +1,11 @@
 use std::collections::BTreeMap;
 
 use codex_network_proxy::NetworkDomainPermission as ProxyNetworkDomainPermission;
+use codex_network_proxy::NetworkHostBudget;
 use codex_network_proxy::NetworkMode;
 use codex_network_proxy::NetworkProxyConfig;
+use codex_network_proxy::NetworkRequestRule;
+use codex_network_proxy::NetworkRequestRuleAction;
 use codex_network_proxy::NetworkUnixSocketPermission as ProxyNetworkUnixSocketPermission;
 use codex_network_proxy::normalize_host;
 use codex_protocol::permissions::FileSystemAccessMode;
@@ -142,6 +145,43 @@ impl std::fmt::Display for NetworkUnixSocketPermissionToml {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct NetworkRequestRuleToml {
+    pub action: NetworkRequestRuleActionToml,
+    /// URL scheme such as `http` or `https`; matches any scheme when unset.
+    pub scheme: Option<String>,
+    /// Host pattern using the same syntax as `domains`; `*` matches every host.
+    pub host: String,
+    /// Path prefix (`/repos`) or glob (`/repos/our-org/**`); matches any path when unset.
+    pub path: Option<String>,
+    /// HTTP methods the rule applies to; matches any method when empty.
+    #[serde(default)]
+    pub methods: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkRequestRuleActionToml {
+    Allow,
+    Deny,
+}
+
+impl From<&NetworkRequestRuleToml> for NetworkRequestRule {
+    fn from(rule: &NetworkRequestRuleToml) -> Self {
+        Self {
+            action: match rule.action {
+                NetworkRequestRuleActionToml::Allow => NetworkRequestRuleAction::Allow,
+                NetworkRequestRuleActionToml::Deny => NetworkRequestRuleAction::Deny,
+            },
+            scheme: rule.scheme.clone(),
+            host: rule.host.clone(),
+            path: rule.path.clone(),
+            methods: rule.methods.clone(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct NetworkToml {
@@ -157,7 +197,59 @@ pub struct NetworkToml {
     pub mode: Option<NetworkMode>,
     pub domains: Option<NetworkDomainPermissionsToml>,
     pub unix_sockets: Option<NetworkUnixSocketPermissionsToml>,
+    /// Request-level rules matched on scheme, host, path and method. Rules from every config
+    /// layer are combined; matching deny rules always win.
+    pub rules: Option<Vec<NetworkRequestRuleToml>>,
     pub allow_local_binding: Option<bool>,
+    /// Record proxied requests to a per-thread HAR log under `CODEX_HOME/proxy/har`.
+    pub har: Option<NetworkHarToml>,
+    /// Block plain HTTP and MITM-inspected HTTPS requests that carry secrets to hosts outside
+    /// `allowed_hosts`.
+    pub secret_guard: Option<NetworkSecretGuardToml>,
+    /// Request-rate and byte budgets for the thread and for individual host patterns.
+    pub budgets: Option<NetworkBudgetsToml>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct NetworkHarToml {
+    pub enabled: Option<bool>,
+    /// Maximum request and response body bytes captured per entry when MITM is enabled.
+    pub max_body_bytes: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct NetworkSecretGuardToml {
+    pub enabled: Option<bool>,
+    /// Hosts that may receive secrets, using the same pattern syntax as `domains`. Hosts from
+    /// every config layer are combined.
+    pub allowed_hosts: Option<Vec<String>>,
+    /// Also block long random-looking tokens that match no known secret or credential format.
+    pub detect_high_entropy: Option<bool>,
+    /// Maximum request body bytes scanned per request.
+    pub max_body_bytes: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct NetworkBudgetsToml {
+    /// Requests and tunnels allowed per minute across all hosts.
+    pub max_requests_per_minute: Option<u32>,
+    /// Request plus response bytes allowed across all hosts.
+    pub max_total_bytes: Option<u64>,
+    /// Budgets shared by the hosts matching each pattern. An entry replaces one for the same
+    /// pattern from a lower-precedence config layer.
+    pub hosts: Option<Vec<NetworkHostBudgetToml>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct NetworkHostBudgetToml {
+    /// Host pattern using the same syntax as `domains`; `*` matches every host.
+    pub host: String,
+    pub max_requests_per_minute: Option<u32>,
+    pub max_total_bytes: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
@@ -216,9 +308,65 @@ impl NetworkToml {
             config.network.unix_sockets =
                 (!proxy_unix_sockets.entries.is_empty()).then_some(proxy_unix_sockets);
         }
+        if let Some(rules) = self.rules.as_ref() {
+            for rule in rules.iter().map(NetworkRequestRule::from) {
+                if !config.network.rules.contains(&rule) {
+                    config.network.rules.push(rule);
+                }
+            }
+        }
         if let Some(allow_local_binding) = self.allow_local_binding {
             config.network.allow_local_binding = allow_local_binding;
         }
+        if let Some(har) = self.har.as_ref() {
+            if let Some(enabled) = har.enabled {
+                config.network.har.enabled = enabled;
+            }
+            if let Some(max_body_bytes) = har.max_body_bytes {
+                config.network.har.max_body_bytes = max_body_bytes;
+            }
+        }
+        if let Some(secret_guard) = self.secret_guard.as_ref() {
+            let settings = &mut config.network.secret_guard;
+            if let Some(enabled) = secret_guard.enabled {
+                settings.enabled = enabled;
+            }
+            for host in secret_guard.allowed_hosts.iter().flatten() {
+                if !settings.allowed_hosts.contains(host) {
+                    settings.allowed_hosts.push(host.clone());
+                }
+            }
+            if let Some(detect_high_entropy) = secret_guard.detect_high_entropy {
+                settings.detect_high_entropy = detect_high_entropy;
+            }
+            if let Some(max_body_bytes) = secret_guard.max_body_bytes {
+                settings.max_body_bytes = max_body_bytes;
+            }
+        }
+        if let Some(budgets) = self.budgets.as_ref() {
+            let settings = &mut config.network.budgets;
+            if let Some(max_requests_per_minute) = budgets.max_requests_per_minute {
+                settings.max_requests_per_minute = Some(max_requests_per_minute);
+            }
+            if let Some(max_total_bytes) = budgets.max_total_bytes {
+                settings.max_total_bytes = Some(max_total_bytes);
+            }
+            for budget in budgets.hosts.iter().flatten() {
+                let budget = NetworkHostBudget {
+                    host: budget.host.clone(),
+                    max_requests_per_minute: budget.max_requests_per_minute,
+                    max_total_bytes: budget.max_total_bytes,
+                };
+                match settings
+                    .hosts
+                    .iter_mut()
+                    .find(|existing| existing.host == budget.host)
+                {
+                    Some(existing) => *existing = budget,
+                    None => settings.hosts.push(budget),
+                }
+            }
+        }
     }
 
     pub fn to_network_proxy_config(&self) -> NetworkProxyConfig {
@@ -4,6 +4,7 @@ use codex_execpolicy::RuleRef;
 use codex_execpolicy::rule::PatternToken;
 use codex_execpolicy::rule::PrefixPattern;
 use codex_execpolicy::rule::PrefixRule;
+use codex_execpolicy::rule::RuleScope;
 use multimap::MultiMap;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -174,6 +175,7 @@ impl RequirementsExecPolicyToml {
                     },
                     decision,
                     justification: justification.clone(),
+                    scope: RuleScope::default(),
                 });
                 rules_by_program.insert(head.clone(), rule);
             }
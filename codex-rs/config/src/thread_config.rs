@@ -311,6 +311,7 @@ mod tests {
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             websocket_connect_timeout_ms: None,
+            max_output_tokens: None,
             requires_openai_auth: false,
             supports_websockets: true,
         }
@@ -153,6 +153,8 @@ fn model_provider_from_proto(
     let id = provider.id;
     let wire_api = match proto::WireApi::try_from(provider.wire_api) {
         Ok(proto::WireApi::Responses) => WireApi::Responses,
+        Ok(proto::WireApi::Chat) => WireApi::Chat,
+        Ok(proto::WireApi::Messages) => WireApi::Messages,
         Ok(proto::WireApi::Unspecified) => {
             return Err(parse_error("remote thread config omitted wire_api"));
         }
@@ -182,6 +184,7 @@ fn model_provider_from_proto(
         stream_max_retries: provider.stream_max_retries,
         stream_idle_timeout_ms: provider.stream_idle_timeout_ms,
         websocket_connect_timeout_ms: provider.websocket_connect_timeout_ms,
+        max_output_tokens: provider.max_output_tokens,
         requires_openai_auth: provider.requires_openai_auth,
         supports_websockets: provider.supports_websockets,
     };
@@ -209,6 +212,7 @@ fn model_provider_to_proto(
         stream_max_retries,
         stream_idle_timeout_ms,
         websocket_connect_timeout_ms,
+        max_output_tokens,
         requires_openai_auth,
         supports_websockets,
     } = provider;
@@ -229,6 +233,7 @@ fn model_provider_to_proto(
         stream_max_retries,
         stream_idle_timeout_ms,
         websocket_connect_timeout_ms,
+        max_output_tokens,
         requires_openai_auth,
         supports_websockets,
     }
@@ -283,6 +288,8 @@ fn proto_string_map(values: HashMap<String, String>) -> proto::StringMap {
 fn proto_wire_api(wire_api: WireApi) -> proto::WireApi {
     match wire_api {
         WireApi::Responses => proto::WireApi::Responses,
+        WireApi::Chat => proto::WireApi::Chat,
+        WireApi::Messages => proto::WireApi::Messages,
     }
 }
 
@@ -448,6 +455,7 @@ mod tests {
                             websocket_connect_timeout_ms: Some(10_000),
                             requires_openai_auth: false,
                             supports_websockets: true,
+                            max_output_tokens: Some(16_000),
                         }],
                         features: HashMap::from([
                             ("plugins".to_string(), false),
@@ -509,6 +517,7 @@ mod tests {
             stream_max_retries: Some(8),
             stream_idle_timeout_ms: Some(9_000),
             websocket_connect_timeout_ms: Some(10_000),
+            max_output_tokens: Some(16_000),
             requires_openai_auth: false,
             supports_websockets: true,
             aws: None,
@@ -79,10 +79,40 @@ pub(crate) fn convert_apply_patch_to_protocol(
     let changes = action.changes();
     let mut result = HashMap::with_capacity(changes.len());
     for (path, change) in changes {
+        // Modes, binary contents and symlinks have no dedicated `FileChange` shape, so they are
+        // described with git's extended header lines ahead of the diff.
         let protocol_change = match change {
-            ApplyPatchFileChange::Add { content } => FileChange::Add {
+            ApplyPatchFileChange::Add {
+                content,
+                mode: None,
+            } => FileChange::Add {
                 content: content.clone(),
             },
+            ApplyPatchFileChange::Add {
+                content,
+                mode: Some(mode),
+            } => FileChange::Update {
+                unified_diff: format!(
+                    "new file mode {}\n{}",
+                    git_file_mode(*mode),
+                    added_lines_diff(content)
+                ),
+                move_path: None,
+            },
+            ApplyPatchFileChange::AddBinary { mode, .. } => FileChange::Update {
+                unified_diff: format!(
+                    "new file mode {}\nBinary files differ\n",
+                    git_file_mode(mode.unwrap_or(DEFAULT_FILE_MODE))
+                ),
+                move_path: None,
+            },
+            ApplyPatchFileChange::AddSymlink { target } => FileChange::Update {
+                unified_diff: format!(
+                    "new file mode 120000\n{}",
+                    added_lines_diff(&format!("{}\n", target.display()))
+                ),
+                move_path: None,
+            },
             ApplyPatchFileChange::Delete { content } => FileChange::Delete {
                 content: content.clone(),
             },
@@ -90,8 +120,12 @@ pub(crate) fn convert_apply_patch_to_protocol(
                 unified_diff,
                 move_path,
                 new_content: _new_content,
+                mode,
             } => FileChange::Update {
-                unified_diff: unified_diff.clone(),
+                unified_diff: match mode {
+                    Some(mode) => format!("new mode {}\n{unified_diff}", git_file_mode(*mode)),
+                    None => unified_diff.clone(),
+                },
                 move_path: move_path.clone(),
             },
         };
@@ -100,6 +134,20 @@ pub(crate) fn convert_apply_patch_to_protocol(
     result
 }
 
+const DEFAULT_FILE_MODE: u32 = 0o644;
+
+/// Formats permission bits the way git prints the mode of a regular file, e.g. `100755`.
+fn git_file_mode(mode: u32) -> String {
+    format!("{:06o}", 0o100000 | mode)
+}
+
+fn added_lines_diff(content: &str) -> String {
+    similar::TextDiff::from_lines("", content)
+        .unified_diff()
+        .context_radius(1)
+        .to_string()
+}
+
 #[cfg(test)]
 #[path = "apply_patch_tests.rs"]
 mod tests;
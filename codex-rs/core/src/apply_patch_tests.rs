@@ -1,5 +1,8 @@
 use super::*;
+use codex_apply_patch::MaybeApplyPatchVerified;
+use codex_exec_server::LOCAL_FS;
 use core_test_support::PathBufExt;
+use core_test_support::PathExt;
 use pretty_assertions::assert_eq;
 
 use tempfile::tempdir;
@@ -20,3 +23,56 @@ fn convert_apply_patch_maps_add_variant() {
         })
     );
 }
+
+#[tokio::test]
+async fn convert_apply_patch_describes_modes_binaries_and_symlinks() {
+    let tmp = tempdir().expect("tmp");
+    let cwd = tmp.path().abs();
+    std::fs::write(tmp.path().join("tool.sh"), "echo tool\n").expect("write tool.sh");
+    let patch = r#"*** Begin Patch
+*** Add File: run.sh
+*** Set Mode: 755
++echo run
+*** Add Binary File: data.bin
++AAEC/w==
+*** Add Symlink: current
+*** Link to: data.bin
+*** Update File: tool.sh
+*** Set Mode: 700
+*** End Patch"#;
+    let argv = vec!["apply_patch".to_string(), patch.to_string()];
+    let action = match codex_apply_patch::maybe_parse_apply_patch_verified(
+        &argv,
+        &cwd,
+        LOCAL_FS.as_ref(),
+        /*sandbox*/ None,
+    )
+    .await
+    {
+        MaybeApplyPatchVerified::Body(action) => action,
+        other => panic!("expected patch body, got: {other:?}"),
+    };
+
+    let update = |unified_diff: &str| FileChange::Update {
+        unified_diff: unified_diff.to_string(),
+        move_path: None,
+    };
+    assert_eq!(
+        convert_apply_patch_to_protocol(&action),
+        HashMap::from([
+            (
+                tmp.path().join("run.sh"),
+                update("new file mode 100755\n@@ -0,0 +1 @@\n+echo run\n"),
+            ),
+            (
+                tmp.path().join("data.bin"),
+                update("new file mode 100644\nBinary files differ\n"),
+            ),
+            (
+                tmp.path().join("current"),
+                update("new file mode 120000\n@@ -0,0 +1 @@\n+data.bin\n"),
+            ),
+            (tmp.path().join("tool.sh"), update("new mode 100700\n")),
+        ])
+    );
+}
@@ -15,6 +15,8 @@ use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::error::CodexErr;
 use codex_protocol::error::Result as CodexResult;
 use codex_protocol::mcp::CallToolResult;
+use codex_protocol::mcp::GetPromptResult;
+use codex_protocol::mcp::Prompt;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::PermissionProfile;
 use codex_protocol::models::ResponseInputItem;
@@ -26,6 +28,7 @@ use codex_protocol::protocol::Op;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::Submission;
+use codex_protocol::protocol::ThreadGoal;
 use codex_protocol::protocol::ThreadMemoryMode;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
@@ -272,6 +275,11 @@ impl CodexThread {
         self.codex.session.token_usage_info().await
     }
 
+    /// Returns the goal currently set on this thread, if any.
+    pub async fn thread_goal(&self) -> anyhow::Result<Option<ThreadGoal>> {
+        self.codex.session.get_thread_goal().await
+    }
+
     /// Records a user-role session-prefix message without creating a new user turn boundary.
     pub(crate) async fn inject_user_message_without_turn(&self, message: String) {
         let message = ResponseItem::Message {
@@ -382,6 +390,33 @@ impl CodexThread {
         Ok(serde_json::to_value(result)?)
     }
 
+    pub async fn list_mcp_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        self.codex.session.list_mcp_prompts().await
+    }
+
+    pub async fn get_mcp_prompt(
+        &self,
+        server: &str,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> anyhow::Result<GetPromptResult> {
+        self.codex
+            .session
+            .get_mcp_prompt(server, name, arguments)
+            .await
+    }
+
+    pub async fn subscribe_mcp_resource(&self, server: &str, uri: &str) -> anyhow::Result<()> {
+        self.codex.session.subscribe_mcp_resource(server, uri).await
+    }
+
+    pub async fn unsubscribe_mcp_resource(&self, server: &str, uri: &str) -> anyhow::Result<()> {
+        self.codex
+            .session
+            .unsubscribe_mcp_resource(server, uri)
+            .await
+    }
+
     pub async fn call_mcp_tool(
         &self,
         server: &str,
@@ -4,6 +4,8 @@ use std::time::Instant;
 use crate::Prompt;
 use crate::client::ModelClientSession;
 use crate::client_common::ResponseEvent;
+use crate::hook_runtime::run_post_compact_hooks;
+use crate::hook_runtime::run_pre_compact_hooks;
 #[cfg(test)]
 use crate::session::PreviousTurnSettings;
 use crate::session::session::Session;
@@ -122,6 +124,7 @@ async fn run_compact_task_inner(
     reason: CompactionReason,
     phase: CompactionPhase,
 ) -> CodexResult<()> {
+    run_pre_compact_hooks(&sess, &turn_context, trigger).await;
     let attempt = CompactionAnalyticsAttempt::begin(
         sess.as_ref(),
         turn_context.as_ref(),
@@ -145,6 +148,7 @@ async fn run_compact_task_inner(
             result.as_ref().err().map(ToString::to_string),
         )
         .await;
+    run_post_compact_hooks(&sess, &turn_context, trigger, result.is_ok()).await;
     result
 }
 
@@ -202,6 +202,7 @@ fn should_use_remote_compact_task_for_azure_provider() {
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
         websocket_connect_timeout_ms: None,
+        max_output_tokens: None,
         requires_openai_auth: false,
         supports_websockets: false,
     };
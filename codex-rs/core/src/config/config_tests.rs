@@ -101,6 +101,7 @@ fn stdio_mcp(command: &str) -> McpServerConfig {
         startup_timeout_sec: None,
         tool_timeout_sec: None,
         default_tools_approval_mode: None,
+        sampling_approval_mode: None,
         enabled_tools: None,
         disabled_tools: None,
         scopes: None,
@@ -125,6 +126,7 @@ fn http_mcp(url: &str) -> McpServerConfig {
         startup_timeout_sec: None,
         tool_timeout_sec: None,
         default_tools_approval_mode: None,
+        sampling_approval_mode: None,
         enabled_tools: None,
         disabled_tools: None,
         scopes: None,
@@ -652,7 +654,11 @@ allow_upstream_proxy = false
                             )]),
                         }),
                         unix_sockets: None,
+                        rules: None,
                         allow_local_binding: None,
+                        har: None,
+                        secret_guard: None,
+                        budgets: None,
                     }),
                 },
             )]),
@@ -2688,6 +2694,7 @@ async fn replace_mcp_servers_round_trips_entries() -> anyhow::Result<()> {
             startup_timeout_sec: Some(Duration::from_secs(3)),
             tool_timeout_sec: Some(Duration::from_secs(5)),
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -2946,6 +2953,7 @@ async fn replace_mcp_servers_serializes_env_sorted() -> anyhow::Result<()> {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -3022,6 +3030,7 @@ async fn replace_mcp_servers_serializes_env_vars() -> anyhow::Result<()> {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -3083,6 +3092,7 @@ async fn replace_mcp_servers_serializes_sourced_env_vars() -> anyhow::Result<()>
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -3134,6 +3144,7 @@ async fn replace_mcp_servers_serializes_cwd() -> anyhow::Result<()> {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -3188,6 +3199,7 @@ async fn replace_mcp_servers_streamable_http_serializes_bearer_token() -> anyhow
             startup_timeout_sec: Some(Duration::from_secs(2)),
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -3258,6 +3270,7 @@ async fn replace_mcp_servers_streamable_http_serializes_custom_headers() -> anyh
             startup_timeout_sec: Some(Duration::from_secs(2)),
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -3340,6 +3353,7 @@ async fn replace_mcp_servers_streamable_http_removes_optional_sections() -> anyh
             startup_timeout_sec: Some(Duration::from_secs(2)),
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -3375,6 +3389,7 @@ async fn replace_mcp_servers_streamable_http_removes_optional_sections() -> anyh
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -3445,6 +3460,7 @@ async fn replace_mcp_servers_streamable_http_isolates_headers_between_servers()
                 startup_timeout_sec: Some(Duration::from_secs(2)),
                 tool_timeout_sec: None,
                 default_tools_approval_mode: None,
+                sampling_approval_mode: None,
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
@@ -3470,6 +3486,7 @@ async fn replace_mcp_servers_streamable_http_isolates_headers_between_servers()
                 startup_timeout_sec: None,
                 tool_timeout_sec: None,
                 default_tools_approval_mode: None,
+                sampling_approval_mode: None,
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
@@ -3558,6 +3575,7 @@ async fn replace_mcp_servers_serializes_disabled_flag() -> anyhow::Result<()> {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -3608,6 +3626,7 @@ async fn replace_mcp_servers_serializes_required_flag() -> anyhow::Result<()> {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -3658,6 +3677,7 @@ async fn replace_mcp_servers_serializes_tool_filters() -> anyhow::Result<()> {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: Some(vec!["allowed".to_string()]),
             disabled_tools: Some(vec!["blocked".to_string()]),
             scopes: None,
@@ -3712,6 +3732,7 @@ async fn replace_mcp_servers_streamable_http_serializes_oauth_resource() -> anyh
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -5471,6 +5492,7 @@ model_verbosity = "high"
         stream_max_retries: Some(10),
         stream_idle_timeout_ms: Some(300_000),
         websocket_connect_timeout_ms: Some(15_000),
+        max_output_tokens: None,
         requires_openai_auth: false,
         supports_websockets: false,
     };
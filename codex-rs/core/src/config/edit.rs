@@ -137,6 +137,7 @@ pub fn model_availability_nux_count_edits(shown_count: &HashMap<String, u32>) ->
 // TODO(jif) move to a dedicated file
 mod document_helpers {
     use codex_config::types::AppToolApproval;
+    use codex_config::types::McpSamplingApproval;
     use codex_config::types::McpServerConfig;
     use codex_config::types::McpServerEnvVar;
     use codex_config::types::McpServerToolConfig;
@@ -255,6 +256,13 @@ mod document_helpers {
                 AppToolApproval::Approve => "approve",
             });
         }
+        if let Some(approval_mode) = config.sampling_approval_mode {
+            entry["sampling_approval_mode"] = value(match approval_mode {
+                McpSamplingApproval::Prompt => "prompt",
+                McpSamplingApproval::Approve => "approve",
+                McpSamplingApproval::Deny => "deny",
+            });
+        }
         if let Some(enabled_tools) = &config.enabled_tools
             && !enabled_tools.is_empty()
         {
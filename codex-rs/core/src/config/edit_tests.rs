@@ -707,6 +707,7 @@ fn blocking_replace_mcp_servers_round_trips() {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: Some(vec!["one".to_string(), "two".to_string()]),
             disabled_tools: None,
             scopes: None,
@@ -736,6 +737,7 @@ fn blocking_replace_mcp_servers_round_trips() {
             startup_timeout_sec: Some(std::time::Duration::from_secs(5)),
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: Some(vec!["forbidden".to_string()]),
             scopes: None,
@@ -802,6 +804,7 @@ fn blocking_replace_mcp_servers_serializes_tool_approval_overrides() {
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: Some(AppToolApproval::Prompt),
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -866,6 +869,7 @@ foo = { command = "cmd" }
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -920,6 +924,7 @@ foo = { command = "cmd" } # keep me
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -973,6 +978,7 @@ foo = { command = "cmd", args = ["--flag"] } # keep me
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -1027,6 +1033,7 @@ foo = { command = "cmd" }
             startup_timeout_sec: None,
             tool_timeout_sec: None,
             default_tools_approval_mode: None,
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
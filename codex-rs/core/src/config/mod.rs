@@ -324,6 +324,8 @@ pub enum ThreadStoreConfig {
     /// Persist threads locally using rollout JSONL files and sqlite metadata.
     #[default]
     Local,
+    /// Persist thread metadata and history in the local SQLite state database.
+    Sqlite,
     /// Persist threads through the remote thread-store service.
     Remote { endpoint: String },
     /// Test-only in-memory thread store.
@@ -1412,6 +1414,7 @@ fn thread_store_config(
 ) -> ThreadStoreConfig {
     match thread_store {
         Some(ThreadStoreToml::Local {}) => ThreadStoreConfig::Local,
+        Some(ThreadStoreToml::Sqlite {}) => ThreadStoreConfig::Sqlite,
         Some(ThreadStoreToml::Remote { endpoint }) => ThreadStoreConfig::Remote { endpoint },
         #[cfg(debug_assertions)]
         Some(ThreadStoreToml::InMemory { id }) => ThreadStoreConfig::InMemory { id },
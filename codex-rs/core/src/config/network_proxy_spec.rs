@@ -86,6 +86,10 @@ impl NetworkProxySpec {
         self.config.network.enable_socks5
     }
 
+    pub(crate) fn secret_guard_enabled(&self) -> bool {
+        self.config.network.enabled && self.config.network.secret_guard.enabled
+    }
+
     pub(crate) fn from_config_and_constraints(
         config: NetworkProxyConfig,
         requirements: Option<NetworkConstraints>,
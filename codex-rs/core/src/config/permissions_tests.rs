@@ -4,13 +4,25 @@ use crate::config::ConfigOverrides;
 use codex_config::config_toml::ConfigToml;
 use codex_config::permissions_toml::FilesystemPermissionToml;
 use codex_config::permissions_toml::FilesystemPermissionsToml;
+use codex_config::permissions_toml::NetworkBudgetsToml;
 use codex_config::permissions_toml::NetworkDomainPermissionToml;
 use codex_config::permissions_toml::NetworkDomainPermissionsToml;
+use codex_config::permissions_toml::NetworkHarToml;
+use codex_config::permissions_toml::NetworkHostBudgetToml;
+use codex_config::permissions_toml::NetworkRequestRuleActionToml;
+use codex_config::permissions_toml::NetworkRequestRuleToml;
+use codex_config::permissions_toml::NetworkSecretGuardToml;
 use codex_config::permissions_toml::NetworkToml;
 use codex_config::permissions_toml::NetworkUnixSocketPermissionToml;
 use codex_config::permissions_toml::NetworkUnixSocketPermissionsToml;
 use codex_config::permissions_toml::PermissionProfileToml;
 use codex_config::permissions_toml::PermissionsToml;
+use codex_network_proxy::NetworkBudgetSettings;
+use codex_network_proxy::NetworkHarSettings;
+use codex_network_proxy::NetworkHostBudget;
+use codex_network_proxy::NetworkRequestRule;
+use codex_network_proxy::NetworkRequestRuleAction;
+use codex_network_proxy::NetworkSecretGuardSettings;
 use codex_protocol::permissions::FileSystemAccessMode;
 use codex_protocol::permissions::FileSystemPath;
 use codex_protocol::permissions::FileSystemSandboxEntry;
@@ -236,6 +248,184 @@ fn network_toml_overlays_unix_socket_permissions_by_path() {
     );
 }
 
+#[test]
+fn network_toml_combines_request_rules_across_layers() {
+    let mut config = NetworkProxyConfig::default();
+    let deny_graphql = NetworkRequestRuleToml {
+        action: NetworkRequestRuleActionToml::Deny,
+        scheme: None,
+        host: "*".to_string(),
+        path: Some("**/graphql".to_string()),
+        methods: vec!["POST".to_string()],
+    };
+    let allow_repos = NetworkRequestRuleToml {
+        action: NetworkRequestRuleActionToml::Allow,
+        scheme: Some("https".to_string()),
+        host: "api.github.com".to_string(),
+        path: Some("/repos/our-org/**".to_string()),
+        methods: vec!["GET".to_string()],
+    };
+
+    NetworkToml {
+        rules: Some(vec![deny_graphql.clone()]),
+        ..Default::default()
+    }
+    .apply_to_network_proxy_config(&mut config);
+    NetworkToml {
+        rules: Some(vec![deny_graphql, allow_repos]),
+        ..Default::default()
+    }
+    .apply_to_network_proxy_config(&mut config);
+
+    assert_eq!(
+        config.network.rules,
+        vec![
+            NetworkRequestRule {
+                action: NetworkRequestRuleAction::Deny,
+                scheme: None,
+                host: "*".to_string(),
+                path: Some("**/graphql".to_string()),
+                methods: vec!["POST".to_string()],
+            },
+            NetworkRequestRule {
+                action: NetworkRequestRuleAction::Allow,
+                scheme: Some("https".to_string()),
+                host: "api.github.com".to_string(),
+                path: Some("/repos/our-org/**".to_string()),
+                methods: vec!["GET".to_string()],
+            },
+        ]
+    );
+}
+
+#[test]
+fn network_toml_overlays_har_settings_field_by_field() {
+    let mut config = NetworkProxyConfig::default();
+
+    NetworkToml {
+        har: Some(NetworkHarToml {
+            enabled: Some(true),
+            max_body_bytes: Some(1024),
+        }),
+        ..Default::default()
+    }
+    .apply_to_network_proxy_config(&mut config);
+    NetworkToml {
+        har: Some(NetworkHarToml {
+            enabled: None,
+            max_body_bytes: Some(2048),
+        }),
+        ..Default::default()
+    }
+    .apply_to_network_proxy_config(&mut config);
+
+    assert_eq!(
+        config.network.har,
+        NetworkHarSettings {
+            enabled: true,
+            max_body_bytes: 2048,
+        }
+    );
+}
+
+#[test]
+fn network_toml_merges_secret_guard_allowed_hosts_across_layers() {
+    let mut config = NetworkProxyConfig::default();
+
+    NetworkToml {
+        secret_guard: Some(NetworkSecretGuardToml {
+            enabled: Some(true),
+            allowed_hosts: Some(vec!["api.openai.com".to_string()]),
+            detect_high_entropy: None,
+            max_body_bytes: None,
+        }),
+        ..Default::default()
+    }
+    .apply_to_network_proxy_config(&mut config);
+    NetworkToml {
+        secret_guard: Some(NetworkSecretGuardToml {
+            enabled: None,
+            allowed_hosts: Some(vec![
+                "api.openai.com".to_string(),
+                "*.github.com".to_string(),
+            ]),
+            detect_high_entropy: Some(false),
+            max_body_bytes: Some(4096),
+        }),
+        ..Default::default()
+    }
+    .apply_to_network_proxy_config(&mut config);
+
+    assert_eq!(
+        config.network.secret_guard,
+        NetworkSecretGuardSettings {
+            enabled: true,
+            allowed_hosts: vec!["api.openai.com".to_string(), "*.github.com".to_string()],
+            detect_high_entropy: false,
+            max_body_bytes: 4096,
+        }
+    );
+}
+
+#[test]
+fn network_toml_overlays_budgets_by_host_pattern() {
+    let mut config = NetworkProxyConfig::default();
+
+    NetworkToml {
+        budgets: Some(NetworkBudgetsToml {
+            max_requests_per_minute: Some(600),
+            max_total_bytes: None,
+            hosts: Some(vec![
+                NetworkHostBudgetToml {
+                    host: "internal.example.com".to_string(),
+                    max_requests_per_minute: Some(60),
+                    max_total_bytes: None,
+                },
+                NetworkHostBudgetToml {
+                    host: "*.github.com".to_string(),
+                    max_requests_per_minute: None,
+                    max_total_bytes: Some(1024),
+                },
+            ]),
+        }),
+        ..Default::default()
+    }
+    .apply_to_network_proxy_config(&mut config);
+    NetworkToml {
+        budgets: Some(NetworkBudgetsToml {
+            max_requests_per_minute: None,
+            max_total_bytes: Some(1_000_000),
+            hosts: Some(vec![NetworkHostBudgetToml {
+                host: "internal.example.com".to_string(),
+                max_requests_per_minute: Some(10),
+                max_total_bytes: None,
+            }]),
+        }),
+        ..Default::default()
+    }
+    .apply_to_network_proxy_config(&mut config);
+
+    assert_eq!(
+        config.network.budgets,
+        NetworkBudgetSettings {
+            max_requests_per_minute: Some(600),
+            max_total_bytes: Some(1_000_000),
+            hosts: vec![
+                NetworkHostBudget {
+                    host: "internal.example.com".to_string(),
+                    max_requests_per_minute: Some(10),
+                    max_total_bytes: None,
+                },
+                NetworkHostBudget {
+                    host: "*.github.com".to_string(),
+                    max_requests_per_minute: None,
+                    max_total_bytes: Some(1024),
+                },
+            ],
+        }
+    );
+}
+
 #[test]
 fn read_write_glob_warnings_skip_supported_deny_read_globs_and_trailing_subpaths() {
     let filesystem = FilesystemPermissionsToml {
@@ -17,8 +17,10 @@ use codex_execpolicy::NetworkRuleProtocol;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
-use codex_execpolicy::blocking_append_allow_prefix_rule;
+use codex_execpolicy::RuleScope;
 use codex_execpolicy::blocking_append_network_rule;
+use codex_execpolicy::blocking_append_scoped_allow_prefix_rule;
+use codex_protocol::ThreadId;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::models::PermissionProfile;
 use codex_protocol::permissions::FileSystemSandboxKind;
@@ -124,7 +126,7 @@ pub(crate) fn child_uses_parent_exec_policy(parent_config: &Config, child_config
 
 fn is_policy_match(rule_match: &RuleMatch) -> bool {
     match rule_match {
-        RuleMatch::PrefixRuleMatch { .. } => true,
+        RuleMatch::PrefixRuleMatch { .. } | RuleMatch::FlagRuleMatch { .. } => true,
         RuleMatch::HeuristicsRuleMatch { .. } => false,
     }
 }
@@ -209,6 +211,8 @@ pub(crate) struct ExecApprovalRequest<'a> {
     pub(crate) sandbox_cwd: &'a Path,
     pub(crate) sandbox_permissions: SandboxPermissions,
     pub(crate) prefix_rule: Option<Vec<String>>,
+    /// Thread running the command, so rules scoped to that thread apply.
+    pub(crate) thread_id: Option<ThreadId>,
 }
 
 impl ExecPolicyManager {
@@ -244,6 +248,7 @@ impl ExecPolicyManager {
             sandbox_cwd,
             sandbox_permissions,
             prefix_rule,
+            thread_id,
         } = req;
         let exec_policy = self.current();
         let (commands, used_complex_parsing) = commands_for_exec_policy(command);
@@ -262,9 +267,8 @@ impl ExecPolicyManager {
                 used_complex_parsing,
             )
         };
-        let match_options = MatchOptions {
-            resolve_host_executables: true,
-        };
+        let match_options =
+            match_options_for_sandbox(file_system_sandbox_policy, sandbox_cwd, thread_id);
         let evaluation = exec_policy.check_multiple_with_options(
             commands.iter(),
             &exec_policy_fallback,
@@ -330,10 +334,12 @@ impl ExecPolicyManager {
         }
     }
 
+    /// Saves `amendment` as an allow rule limited to `scope` and adds it to the in-memory policy.
     pub(crate) async fn append_amendment_and_update(
         &self,
         codex_home: &Path,
         amendment: &ExecPolicyAmendment,
+        scope: &RuleScope,
     ) -> Result<(), ExecPolicyUpdateError> {
         let _update_guard =
             self.update_lock
@@ -348,7 +354,8 @@ impl ExecPolicyManager {
         spawn_blocking({
             let policy_path = policy_path.clone();
             let prefix = amendment.command.clone();
-            move || blocking_append_allow_prefix_rule(&policy_path, &prefix)
+            let scope = scope.clone();
+            move || blocking_append_scoped_allow_prefix_rule(&policy_path, &prefix, &scope)
         })
         .await
         .map_err(|source| ExecPolicyUpdateError::JoinBlockingTask { source })?
@@ -360,6 +367,7 @@ impl ExecPolicyManager {
         let current_policy = self.current();
         let match_options = MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         };
         let existing_evaluation = current_policy.check_multiple_with_options(
             [&amendment.command],
@@ -375,7 +383,11 @@ impl ExecPolicyManager {
         }
 
         let mut updated_policy = current_policy.as_ref().clone();
-        updated_policy.add_prefix_rule(&amendment.command, Decision::Allow)?;
+        updated_policy.add_scoped_prefix_rule(
+            &amendment.command,
+            Decision::Allow,
+            scope.clone(),
+        )?;
         self.policy.store(Arc::new(updated_policy));
         Ok(())
     }
@@ -764,6 +776,31 @@ fn try_derive_execpolicy_amendment_for_allow_rules(
         })
 }
 
+/// Match options for commands that `thread_id` will run under `file_system_sandbox_policy`, so
+/// `writable_path()` rule patterns only accept paths the sandbox lets the command write and
+/// thread-scoped rules apply to the thread that approved them.
+pub(crate) fn match_options_for_sandbox(
+    file_system_sandbox_policy: &FileSystemSandboxPolicy,
+    sandbox_cwd: &Path,
+    thread_id: Option<ThreadId>,
+) -> MatchOptions {
+    let writable_roots = file_system_sandbox_policy.get_writable_roots_with_cwd(sandbox_cwd);
+    MatchOptions {
+        resolve_host_executables: true,
+        cwd: AbsolutePathBuf::from_absolute_path(sandbox_cwd).ok(),
+        read_only_paths: writable_roots
+            .iter()
+            .flat_map(|writable_root| writable_root.read_only_subpaths.iter().cloned())
+            .collect(),
+        writable_roots: writable_roots
+            .into_iter()
+            .map(|writable_root| writable_root.root)
+            .collect(),
+        thread_id: thread_id.map(|thread_id| thread_id.to_string()),
+        ignore_scope: false,
+    }
+}
+
 fn derive_requested_execpolicy_amendment_from_prefix_rule(
     prefix_rule: Option<&Vec<String>>,
     matched_rules: &[RuleMatch],
@@ -841,6 +878,12 @@ fn derive_prompt_reason(command_args: &[String], evaluation: &Evaluation) -> Opt
                 decision: Decision::Prompt,
                 justification,
                 ..
+            }
+            | RuleMatch::FlagRuleMatch {
+                matched_prefix,
+                decision: Decision::Prompt,
+                justification,
+                ..
             } => Some((matched_prefix.len(), justification.as_deref())),
             _ => None,
         })
@@ -876,16 +919,31 @@ fn derive_forbidden_reason(command_args: &[String], evaluation: &Evaluation) ->
                 decision: Decision::Forbidden,
                 justification,
                 ..
-            } => Some((matched_prefix, justification.as_deref())),
+            } => Some((matched_prefix, None, justification.as_deref())),
+            RuleMatch::FlagRuleMatch {
+                matched_prefix,
+                decision: Decision::Forbidden,
+                forbidden_flag,
+                justification,
+                ..
+            } => Some((
+                matched_prefix,
+                forbidden_flag.as_deref(),
+                justification.as_deref(),
+            )),
             _ => None,
         })
-        .max_by_key(|(matched_prefix, _)| matched_prefix.len());
+        .max_by_key(|(matched_prefix, _, _)| matched_prefix.len());
 
     match most_specific_forbidden {
-        Some((_matched_prefix, Some(justification))) => {
+        Some((_matched_prefix, _forbidden_flag, Some(justification))) => {
             format!("`{command}` rejected: {justification}")
         }
-        Some((matched_prefix, None)) => {
+        Some((matched_prefix, Some(forbidden_flag), None)) => {
+            let prefix = render_shlex_command(matched_prefix);
+            format!("`{command}` rejected: policy forbids `{forbidden_flag}` for `{prefix}`")
+        }
+        Some((matched_prefix, None, None)) => {
             let prefix = render_shlex_command(matched_prefix);
             format!("`{command}` rejected: policy forbids commands starting with `{prefix}`")
         }
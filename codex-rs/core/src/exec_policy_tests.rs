@@ -656,6 +656,32 @@ async fn evaluates_bash_lc_inner_commands() {
     .await;
 }
 
+#[tokio::test]
+async fn forbidden_reason_names_flag_from_flag_rule() {
+    assert_exec_approval_requirement_for_command(
+        ExecApprovalRequirementScenario {
+            policy_src: Some(
+                r#"flag_rule(program="curl", forbidden_flags=[flag("-o", takes_value=True)], decision="forbidden")"#
+                    .to_string(),
+            ),
+            command: vec![
+                "bash".to_string(),
+                "-lc".to_string(),
+                "curl -sSo page.html https://example.com".to_string(),
+            ],
+            approval_policy: AskForApproval::OnRequest,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            file_system_sandbox_policy: unrestricted_file_system_sandbox_policy(),
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            prefix_rule: None,
+        },
+        ExecApprovalRequirement::Forbidden {
+            reason: "`bash -lc 'curl -sSo page.html https://example.com'` rejected: policy forbids `-o` for `curl`".to_string(),
+        },
+    )
+    .await;
+}
+
 #[test]
 fn commands_for_exec_policy_falls_back_for_empty_shell_script() {
     let command = vec!["bash".to_string(), "-lc".to_string(), "".to_string()];
@@ -1140,6 +1166,7 @@ async fn mixed_rule_and_sandbox_prompt_prioritizes_rule_for_rejection_decision()
             sandbox_cwd: Path::new("/tmp"),
             sandbox_permissions: SandboxPermissions::RequireEscalated,
             prefix_rule: None,
+            thread_id: None,
         })
         .await;
 
@@ -1180,6 +1207,7 @@ async fn mixed_rule_and_sandbox_prompt_rejects_when_granular_rules_are_disabled(
             sandbox_cwd: Path::new("/tmp"),
             sandbox_permissions: SandboxPermissions::RequireEscalated,
             prefix_rule: None,
+            thread_id: None,
         })
         .await;
 
@@ -1207,6 +1235,7 @@ async fn exec_approval_requirement_falls_back_to_heuristics() {
             sandbox_cwd: Path::new("/tmp"),
             sandbox_permissions: SandboxPermissions::UseDefault,
             prefix_rule: None,
+            thread_id: None,
         })
         .await;
 
@@ -1235,6 +1264,7 @@ async fn empty_bash_lc_script_falls_back_to_original_command() {
             sandbox_cwd: Path::new("/tmp"),
             sandbox_permissions: SandboxPermissions::UseDefault,
             prefix_rule: None,
+            thread_id: None,
         })
         .await;
 
@@ -1267,6 +1297,7 @@ async fn whitespace_bash_lc_script_falls_back_to_original_command() {
             sandbox_cwd: Path::new("/tmp"),
             sandbox_permissions: SandboxPermissions::UseDefault,
             prefix_rule: None,
+            thread_id: None,
         })
         .await;
 
@@ -1299,6 +1330,7 @@ async fn request_rule_uses_prefix_rule() {
             sandbox_cwd: Path::new("/tmp"),
             sandbox_permissions: SandboxPermissions::RequireEscalated,
             prefix_rule: Some(vec!["cargo".to_string(), "install".to_string()]),
+            thread_id: None,
         })
         .await;
 
@@ -1332,6 +1364,7 @@ async fn request_rule_falls_back_when_prefix_rule_does_not_approve_all_commands(
             sandbox_cwd: Path::new("/tmp"),
             sandbox_permissions: SandboxPermissions::RequireEscalated,
             prefix_rule: Some(vec!["cargo".to_string(), "install".to_string()]),
+            thread_id: None,
         })
         .await;
 
@@ -1372,6 +1405,7 @@ async fn heuristics_apply_when_other_commands_match_policy() {
                 sandbox_cwd: Path::new("/tmp"),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                thread_id: None,
             })
             .await,
         ExecApprovalRequirement::NeedsApproval {
@@ -1390,7 +1424,11 @@ async fn append_execpolicy_amendment_updates_policy_and_file() {
     let manager = ExecPolicyManager::default();
 
     manager
-        .append_amendment_and_update(codex_home.path(), &ExecPolicyAmendment::from(prefix))
+        .append_amendment_and_update(
+            codex_home.path(),
+            &ExecPolicyAmendment::from(prefix),
+            &RuleScope::default(),
+        )
         .await
         .expect("update policy");
     let updated_policy = manager.current();
@@ -1422,7 +1460,11 @@ async fn append_execpolicy_amendment_rejects_empty_prefix() {
     let manager = ExecPolicyManager::default();
 
     let result = manager
-        .append_amendment_and_update(codex_home.path(), &ExecPolicyAmendment::from(vec![]))
+        .append_amendment_and_update(
+            codex_home.path(),
+            &ExecPolicyAmendment::from(vec![]),
+            &RuleScope::default(),
+        )
         .await;
 
     assert!(matches!(
@@ -1434,6 +1476,50 @@ async fn append_execpolicy_amendment_rejects_empty_prefix() {
     ));
 }
 
+#[tokio::test]
+async fn append_thread_scoped_execpolicy_amendment_only_applies_to_that_thread() {
+    let codex_home = tempdir().expect("create temp dir");
+    let prefix = vec!["echo".to_string(), "hello".to_string()];
+    let manager = ExecPolicyManager::default();
+    let scope = RuleScope {
+        thread_id: Some("thread-1".to_string()),
+        ..Default::default()
+    };
+
+    manager
+        .append_amendment_and_update(
+            codex_home.path(),
+            &ExecPolicyAmendment::from(prefix.clone()),
+            &scope,
+        )
+        .await
+        .expect("update policy");
+    let updated_policy = manager.current();
+
+    let decision_for_thread = |thread_id: &str| {
+        updated_policy
+            .check_multiple_with_options(
+                [&prefix],
+                &|_| Decision::Prompt,
+                &MatchOptions {
+                    thread_id: Some(thread_id.to_string()),
+                    ..Default::default()
+                },
+            )
+            .decision
+    };
+    assert_eq!(decision_for_thread("thread-1"), Decision::Allow);
+    assert_eq!(decision_for_thread("thread-2"), Decision::Prompt);
+
+    let contents = fs::read_to_string(default_policy_path(codex_home.path()))
+        .expect("policy file should have been created");
+    assert_eq!(
+        contents,
+        r#"prefix_rule(pattern=["echo", "hello"], decision="allow", thread_id="thread-1")
+"#
+    );
+}
+
 #[tokio::test]
 async fn proposed_execpolicy_amendment_is_present_for_single_command_without_policy_match() {
     let command = vec!["cargo".to_string(), "build".to_string()];
@@ -1861,6 +1947,7 @@ async fn verify_approval_requirement_for_unsafe_powershell_command() {
                 sandbox_cwd: Path::new("/tmp"),
                 sandbox_permissions: permissions,
                 prefix_rule: None,
+                thread_id: None,
             })
             .await,
         "{pwsh_approval_reason}"
@@ -1888,6 +1975,7 @@ async fn verify_approval_requirement_for_unsafe_powershell_command() {
                 sandbox_cwd: Path::new("/tmp"),
                 sandbox_permissions: permissions,
                 prefix_rule: None,
+                thread_id: None,
             })
             .await,
         r#"On all platforms, a forbidden command should require approval
@@ -1911,6 +1999,7 @@ async fn verify_approval_requirement_for_unsafe_powershell_command() {
                 sandbox_cwd: Path::new("/tmp"),
                 sandbox_permissions: permissions,
                 prefix_rule: None,
+                thread_id: None,
             })
             .await,
         r#"On all platforms, a forbidden command should require approval
@@ -2011,6 +2100,7 @@ async fn assert_exec_approval_requirement_for_command(
             sandbox_cwd: Path::new("/tmp"),
             sandbox_permissions,
             prefix_rule,
+            thread_id: None,
         })
         .await;
 
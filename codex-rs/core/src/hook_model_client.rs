@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use codex_api::ResponseEvent;
+use codex_hooks::HookModelClient;
+use codex_hooks::HookModelRequest;
+use codex_models_manager::ModelsManagerConfig;
+use codex_models_manager::manager::SharedModelsManager;
+use codex_otel::SessionTelemetry;
+use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::config_types::ServiceTier;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_rollout_trace::InferenceTraceContext;
+use futures::StreamExt;
+
+use crate::Prompt;
+use crate::client::ModelClient;
+
+/// Answers `prompt` and `agent` hook requests with the session's model provider.
+pub(crate) struct SessionHookModelClient {
+    pub(crate) model_client: ModelClient,
+    pub(crate) models_manager: SharedModelsManager,
+    pub(crate) models_manager_config: ModelsManagerConfig,
+    pub(crate) session_telemetry: SessionTelemetry,
+    pub(crate) default_model: String,
+    pub(crate) service_tier: Option<ServiceTier>,
+}
+
+#[async_trait]
+impl HookModelClient for SessionHookModelClient {
+    async fn complete(&self, request: HookModelRequest) -> anyhow::Result<String> {
+        let model = request.model.as_deref().unwrap_or(&self.default_model);
+        let model_info = self
+            .models_manager
+            .get_model_info(model, &self.models_manager_config)
+            .await;
+        let prompt = Prompt {
+            input: vec![ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: request.input,
+                }],
+                phase: None,
+            }],
+            tools: Vec::new(),
+            parallel_tool_calls: false,
+            base_instructions: BaseInstructions {
+                text: request.instructions,
+            },
+            personality: None,
+            output_schema: Some(request.output_schema),
+            output_schema_strict: true,
+        };
+
+        let mut client_session = self.model_client.new_session();
+        let mut stream = client_session
+            .stream(
+                &prompt,
+                &model_info,
+                &self.session_telemetry,
+                /*effort*/ None,
+                ReasoningSummaryConfig::None,
+                self.service_tier,
+                /*turn_metadata_header*/ None,
+                &InferenceTraceContext::disabled(),
+            )
+            .await?;
+
+        let mut result = String::new();
+        while let Some(event) = stream.next().await.transpose()? {
+            match event {
+                ResponseEvent::OutputTextDelta(delta) => result.push_str(&delta),
+                ResponseEvent::OutputItemDone(item) => {
+                    if result.is_empty()
+                        && let ResponseItem::Message { content, .. } = item
+                        && let Some(text) = crate::compact::content_items_to_text(&content)
+                    {
+                        result.push_str(&text);
+                    }
+                }
+                ResponseEvent::Completed { .. } => break,
+                _ => {}
+            }
+        }
+        Ok(result)
+    }
+}
@@ -2,20 +2,31 @@ use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
+use codex_analytics::CompactionTrigger;
 use codex_analytics::HookRunFact;
 use codex_analytics::build_track_events_context;
+use codex_hooks::CompactTrigger;
 use codex_hooks::PermissionRequestDecision;
 use codex_hooks::PermissionRequestOutcome;
 use codex_hooks::PermissionRequestRequest;
+use codex_hooks::PostApplyPatchRequest;
+use codex_hooks::PostCompactRequest;
 use codex_hooks::PostToolUseOutcome;
 use codex_hooks::PostToolUseRequest;
+use codex_hooks::PreApplyPatchOutcome;
+use codex_hooks::PreApplyPatchRequest;
+use codex_hooks::PreCompactRequest;
 use codex_hooks::PreToolUseOutcome;
 use codex_hooks::PreToolUseRequest;
 use codex_hooks::SessionStartOutcome;
+use codex_hooks::SubagentStartRequest;
+use codex_hooks::SubagentStopRequest;
+use codex_hooks::TurnErrorRequest;
 use codex_hooks::UserPromptSubmitOutcome;
 use codex_hooks::UserPromptSubmitRequest;
 use codex_otel::HOOK_RUN_DURATION_METRIC;
 use codex_otel::HOOK_RUN_METRIC;
+use codex_protocol::ThreadId;
 use codex_protocol::items::TurnItem;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
@@ -27,9 +38,12 @@ use codex_protocol::protocol::HookRunStatus;
 use codex_protocol::protocol::HookRunSummary;
 use codex_protocol::protocol::HookSource;
 use codex_protocol::protocol::HookStartedEvent;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::SubAgentSource;
 use codex_protocol::user_input::UserInput;
 use serde_json::Value;
 
+use crate::agent::role::DEFAULT_ROLE_NAME;
 use crate::context::ContextualUserFragment;
 use crate::context::HookAdditionalContext;
 use crate::event_mapping::parse_turn_item;
@@ -134,13 +148,17 @@ pub(crate) async fn run_pending_session_start_hooks(
 /// `tool_name` is the canonical name serialized to hook stdin. Matcher aliases
 /// are internal compatibility names used only for selecting configured hook
 /// handlers.
+///
+/// Returns `Err` with the model-facing message when a hook blocks the call.
+/// Otherwise returns the hook-rewritten `tool_input`, if any; callers must map
+/// it back to the tool's payload and validate it before running the tool.
 pub(crate) async fn run_pre_tool_use_hooks(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
     tool_use_id: String,
     tool_name: &HookToolName,
     tool_input: &Value,
-) -> Option<String> {
+) -> Result<Option<Value>, String> {
     let request = PreToolUseRequest {
         session_id: sess.conversation_id,
         turn_id: turn_context.sub_id.clone(),
@@ -160,10 +178,11 @@ pub(crate) async fn run_pre_tool_use_hooks(
         hook_events,
         should_block,
         block_reason,
+        updated_input,
     } = sess.hooks().run_pre_tool_use(request).await;
     emit_hook_completed_events(sess, turn_context, hook_events).await;
 
-    if should_block {
+    let block_message = if should_block {
         block_reason.map(|reason| {
             if (tool_name.name() == "Bash" || tool_name.name() == "apply_patch")
                 && let Some(command) = tool_input.get("command").and_then(Value::as_str)
@@ -178,6 +197,10 @@ pub(crate) async fn run_pre_tool_use_hooks(
         })
     } else {
         None
+    };
+    match block_message {
+        Some(message) => Err(message),
+        None => Ok(updated_input),
     }
 }
 
@@ -274,6 +297,202 @@ pub(crate) async fn run_user_prompt_submit_hooks(
     .await
 }
 
+/// Runs matching `PreCompact` hooks before the conversation history is compacted.
+pub(crate) async fn run_pre_compact_hooks(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    trigger: CompactionTrigger,
+) {
+    let request = PreCompactRequest {
+        session_id: sess.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        cwd: turn_context.cwd.clone(),
+        transcript_path: sess.hook_transcript_path().await,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(turn_context),
+        trigger: hook_compact_trigger(trigger),
+    };
+    let preview_runs = sess.hooks().preview_pre_compact(&request);
+    emit_hook_started_events(sess, turn_context, preview_runs).await;
+
+    let outcome = sess.hooks().run_pre_compact(request).await;
+    emit_hook_completed_events(sess, turn_context, outcome.hook_events).await;
+}
+
+/// Runs matching `PostCompact` hooks once compaction has finished or failed.
+pub(crate) async fn run_post_compact_hooks(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    trigger: CompactionTrigger,
+    success: bool,
+) {
+    let request = PostCompactRequest {
+        session_id: sess.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        cwd: turn_context.cwd.clone(),
+        transcript_path: sess.hook_transcript_path().await,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(turn_context),
+        trigger: hook_compact_trigger(trigger),
+        success,
+    };
+    let preview_runs = sess.hooks().preview_post_compact(&request);
+    emit_hook_started_events(sess, turn_context, preview_runs).await;
+
+    let outcome = sess.hooks().run_post_compact(request).await;
+    emit_hook_completed_events(sess, turn_context, outcome.hook_events).await;
+}
+
+/// Runs matching `SubagentStart` hooks in the parent thread after a subagent
+/// was spawned. `agent_type` is the subagent role and doubles as the matcher
+/// input.
+pub(crate) async fn run_subagent_start_hooks(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    agent_id: ThreadId,
+    agent_type: String,
+    agent_nickname: Option<String>,
+    prompt: String,
+) {
+    let request = SubagentStartRequest {
+        session_id: sess.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        cwd: turn_context.cwd.clone(),
+        transcript_path: sess.hook_transcript_path().await,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(turn_context),
+        agent_id,
+        agent_type,
+        agent_nickname,
+        prompt,
+    };
+    let preview_runs = sess.hooks().preview_subagent_start(&request);
+    emit_hook_started_events(sess, turn_context, preview_runs).await;
+
+    let outcome = sess.hooks().run_subagent_start(request).await;
+    emit_hook_completed_events(sess, turn_context, outcome.hook_events).await;
+}
+
+/// Runs matching `SubagentStop` hooks when a spawned subagent finishes its
+/// task. Does nothing for threads that were not spawned by another thread.
+pub(crate) async fn run_subagent_stop_hooks(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    last_assistant_message: Option<String>,
+) {
+    let SessionSource::SubAgent(SubAgentSource::ThreadSpawn {
+        parent_thread_id,
+        agent_nickname,
+        agent_role,
+        ..
+    }) = &turn_context.session_source
+    else {
+        return;
+    };
+    let request = SubagentStopRequest {
+        session_id: sess.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        cwd: turn_context.cwd.clone(),
+        transcript_path: sess.hook_transcript_path().await,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(turn_context),
+        parent_session_id: *parent_thread_id,
+        agent_type: agent_role
+            .clone()
+            .unwrap_or_else(|| DEFAULT_ROLE_NAME.to_string()),
+        agent_nickname: agent_nickname.clone(),
+        last_assistant_message,
+    };
+    let preview_runs = sess.hooks().preview_subagent_stop(&request);
+    emit_hook_started_events(sess, turn_context, preview_runs).await;
+
+    let outcome = sess.hooks().run_subagent_stop(request).await;
+    emit_hook_completed_events(sess, turn_context, outcome.hook_events).await;
+}
+
+/// Runs matching `PreApplyPatch` hooks right before a verified patch is
+/// written. `files` lists every path the patch touches and is used as the
+/// matcher input.
+pub(crate) async fn run_pre_apply_patch_hooks(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    tool_use_id: String,
+    patch: String,
+    files: Vec<String>,
+) -> PreApplyPatchOutcome {
+    let request = PreApplyPatchRequest {
+        session_id: sess.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        cwd: turn_context.cwd.clone(),
+        transcript_path: sess.hook_transcript_path().await,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(turn_context),
+        tool_use_id,
+        patch,
+        files,
+    };
+    let preview_runs = sess.hooks().preview_pre_apply_patch(&request);
+    emit_hook_started_events(sess, turn_context, preview_runs).await;
+
+    let outcome = sess.hooks().run_pre_apply_patch(request).await;
+    emit_hook_completed_events(sess, turn_context, outcome.hook_events.clone()).await;
+    outcome
+}
+
+/// Runs matching `PostApplyPatch` hooks after a patch was written, whether or
+/// not every hunk applied.
+pub(crate) async fn run_post_apply_patch_hooks(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    tool_use_id: String,
+    patch: String,
+    files: Vec<String>,
+    success: bool,
+    output: String,
+) {
+    let request = PostApplyPatchRequest {
+        session_id: sess.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        cwd: turn_context.cwd.clone(),
+        transcript_path: sess.hook_transcript_path().await,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(turn_context),
+        tool_use_id,
+        patch,
+        files,
+        success,
+        output,
+    };
+    let preview_runs = sess.hooks().preview_post_apply_patch(&request);
+    emit_hook_started_events(sess, turn_context, preview_runs).await;
+
+    let outcome = sess.hooks().run_post_apply_patch(request).await;
+    emit_hook_completed_events(sess, turn_context, outcome.hook_events).await;
+}
+
+/// Runs matching `TurnError` hooks after a turn ended with an error that was
+/// reported to the client.
+pub(crate) async fn run_turn_error_hooks(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    error: String,
+) {
+    let request = TurnErrorRequest {
+        session_id: sess.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        cwd: turn_context.cwd.clone(),
+        transcript_path: sess.hook_transcript_path().await,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(turn_context),
+        error,
+    };
+    let preview_runs = sess.hooks().preview_turn_error(&request);
+    emit_hook_started_events(sess, turn_context, preview_runs).await;
+
+    let outcome = sess.hooks().run_turn_error(request).await;
+    emit_hook_completed_events(sess, turn_context, outcome.hook_events).await;
+}
+
 pub(crate) async fn inspect_pending_input(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
@@ -466,6 +685,13 @@ fn hook_run_metric_tags(run: &HookRunSummary) -> [(&'static str, &'static str);
         HookEventName::SessionStart => "SessionStart",
         HookEventName::UserPromptSubmit => "UserPromptSubmit",
         HookEventName::Stop => "Stop",
+        HookEventName::PreCompact => "PreCompact",
+        HookEventName::PostCompact => "PostCompact",
+        HookEventName::SubagentStart => "SubagentStart",
+        HookEventName::SubagentStop => "SubagentStop",
+        HookEventName::PreApplyPatch => "PreApplyPatch",
+        HookEventName::PostApplyPatch => "PostApplyPatch",
+        HookEventName::TurnError => "TurnError",
     };
     let hook_source = match run.source {
         HookSource::System => "system",
@@ -492,6 +718,13 @@ fn hook_run_metric_tags(run: &HookRunSummary) -> [(&'static str, &'static str);
     ]
 }
 
+fn hook_compact_trigger(trigger: CompactionTrigger) -> CompactTrigger {
+    match trigger {
+        CompactionTrigger::Manual => CompactTrigger::Manual,
+        CompactionTrigger::Auto => CompactTrigger::Auto,
+    }
+}
+
 fn hook_permission_mode(turn_context: &TurnContext) -> String {
     match turn_context.approval_policy.value() {
         AskForApproval::Never => "bypassPermissions",
@@ -38,6 +38,7 @@ mod flags;
 mod git_info_tests;
 mod goals;
 mod guardian;
+mod hook_model_client;
 mod hook_runtime;
 mod installation_id;
 pub(crate) mod landlock;
@@ -48,6 +49,7 @@ mod mcp_tool_approval_templates;
 mod mcp_tool_exposure;
 mod network_policy_decision;
 pub(crate) mod network_proxy_loader;
+mod network_secret_guard;
 pub use mcp::McpManager;
 pub use network_proxy_loader::MtimeConfigReloader;
 pub use network_proxy_loader::build_network_proxy_state;
@@ -55,6 +57,8 @@ pub use network_proxy_loader::build_network_proxy_state_and_reloader;
 mod original_image_detail;
 pub use codex_mcp::SandboxState;
 mod mcp_openai_file;
+mod mcp_resource_subscriptions;
+mod mcp_sampler;
 mod mcp_tool_call;
 mod memories;
 pub use codex_memories_write::clear_memory_roots_contents;
@@ -0,0 +1,327 @@
+//! MCP resource subscriptions for a thread.
+//!
+//! The model (through the `subscribe_mcp_resource` tool) or a client can
+//! subscribe the thread to a server resource. When the server reports a change,
+//! the resource is read again and the difference from the copy the thread last
+//! saw is handed to the model as developer context: injected into the running
+//! turn, or queued for the next one. Long diffs are truncated. An idle thread with an active goal is woken
+//! so the agent can react without waiting for the user.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Weak;
+
+use codex_mcp::McpConnectionManager;
+use codex_mcp::McpResourceUpdateHandler;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseInputItem;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::McpResourceUpdatedEvent;
+use codex_protocol::protocol::ThreadGoalStatus;
+use codex_utils_output_truncation::TruncationPolicy;
+use codex_utils_output_truncation::truncate_text;
+use rmcp::model::ReadResourceRequestParams;
+use rmcp::model::ReadResourceResult;
+use rmcp::model::ResourceContents;
+use tokio::sync::Mutex;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::session::session::Session;
+
+/// Largest copy of a resource kept as the baseline for diffs; bigger resources keep their start
+/// and end.
+const MAX_BASELINE_BYTES: usize = 256 * 1024;
+/// Token budget for the diff handed to the model.
+const MAX_DIFF_TOKENS: usize = 2_000;
+
+/// Resources the thread is subscribed to, keyed by `(server, uri)`, along with
+/// the text last shown to the model so updates can be reported as a diff.
+#[derive(Default)]
+pub(crate) struct McpResourceSubscriptions {
+    resources: Mutex<BTreeMap<(String, String), String>>,
+}
+
+impl McpResourceSubscriptions {
+    /// Stores `text` as the baseline for a resource and returns the baseline it replaced.
+    async fn insert(&self, server: &str, uri: &str, text: String) -> Option<String> {
+        self.resources
+            .lock()
+            .await
+            .insert((server.to_string(), uri.to_string()), text)
+    }
+
+    async fn remove(&self, server: &str, uri: &str) -> bool {
+        self.resources
+            .lock()
+            .await
+            .remove(&(server.to_string(), uri.to_string()))
+            .is_some()
+    }
+
+    /// Stores `text` for a subscribed resource and returns the previous copy,
+    /// or `None` when the resource is not subscribed.
+    async fn replace(&self, server: &str, uri: &str, text: String) -> Option<String> {
+        self.resources
+            .lock()
+            .await
+            .get_mut(&(server.to_string(), uri.to_string()))
+            .map(|previous| std::mem::replace(previous, text))
+    }
+
+    pub(crate) async fn keys(&self) -> Vec<(String, String)> {
+        self.resources.lock().await.keys().cloned().collect()
+    }
+
+    async fn take_all(&self) -> Vec<(String, String)> {
+        std::mem::take(&mut *self.resources.lock().await)
+            .into_keys()
+            .collect()
+    }
+}
+
+/// Forwards `notifications/resources/updated` from the connection manager to
+/// the session that owns it.
+pub(crate) struct SessionMcpResourceUpdateHandler {
+    pub(crate) session: Weak<Session>,
+}
+
+impl McpResourceUpdateHandler for SessionMcpResourceUpdateHandler {
+    fn resource_updated(&self, server: String, uri: String) {
+        let Some(session) = self.session.upgrade() else {
+            return;
+        };
+        tokio::spawn(async move {
+            session.handle_mcp_resource_update(server, uri).await;
+        });
+    }
+}
+
+impl Session {
+    /// Subscribes the thread to `uri` on `server` and remembers its current
+    /// contents as the baseline for later updates. The baseline is stored
+    /// before the server-side subscribe so updates sent right after it are not
+    /// dropped, and is rolled back if subscribing fails.
+    #[expect(
+        clippy::await_holding_invalid_type,
+        reason = "MCP resource calls are serialized through the session-owned manager guard"
+    )]
+    pub async fn subscribe_mcp_resource(&self, server: &str, uri: &str) -> anyhow::Result<()> {
+        let text = match self.read_resource(server, read_params(uri)).await {
+            Ok(result) => baseline_text(&result),
+            Err(err) => {
+                warn!("failed to read subscribed MCP resource `{uri}` from `{server}`: {err:#}");
+                String::new()
+            }
+        };
+        let subscriptions = &self.services.mcp_resource_subscriptions;
+        let previous = subscriptions.insert(server, uri, text).await;
+        let result = self
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .subscribe_resource(server, uri)
+            .await;
+        if result.is_err() {
+            match previous {
+                Some(previous) => {
+                    subscriptions.insert(server, uri, previous).await;
+                }
+                None => {
+                    subscriptions.remove(server, uri).await;
+                }
+            }
+        }
+        result
+    }
+
+    #[expect(
+        clippy::await_holding_invalid_type,
+        reason = "MCP resource calls are serialized through the session-owned manager guard"
+    )]
+    pub async fn unsubscribe_mcp_resource(&self, server: &str, uri: &str) -> anyhow::Result<()> {
+        if !self
+            .services
+            .mcp_resource_subscriptions
+            .remove(server, uri)
+            .await
+        {
+            anyhow::bail!("not subscribed to `{uri}` on MCP server `{server}`");
+        }
+        self.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .unsubscribe_resource(server, uri)
+            .await
+    }
+
+    /// Drops every subscription, e.g. when the thread shuts down.
+    #[expect(
+        clippy::await_holding_invalid_type,
+        reason = "MCP resource calls are serialized through the session-owned manager guard"
+    )]
+    pub(crate) async fn unsubscribe_all_mcp_resources(&self) {
+        let subscriptions = self.services.mcp_resource_subscriptions.take_all().await;
+        if subscriptions.is_empty() {
+            return;
+        }
+        let manager = self.services.mcp_connection_manager.read().await;
+        for (server, uri) in subscriptions {
+            if let Err(err) = manager.unsubscribe_resource(&server, &uri).await {
+                warn!("failed to unsubscribe from MCP resource `{uri}` on `{server}`: {err:#}");
+            }
+        }
+    }
+
+    async fn handle_mcp_resource_update(self: &Arc<Self>, server: String, uri: String) {
+        let text = match self.read_resource(&server, read_params(&uri)).await {
+            Ok(result) => baseline_text(&result),
+            Err(err) => {
+                warn!("failed to read updated MCP resource `{uri}` from `{server}`: {err:#}");
+                return;
+            }
+        };
+        let Some(previous) = self
+            .services
+            .mcp_resource_subscriptions
+            .replace(&server, &uri, text.clone())
+            .await
+        else {
+            return;
+        };
+        if previous == text {
+            return;
+        }
+
+        let items = vec![ResponseInputItem::Message {
+            role: "developer".to_string(),
+            content: vec![ContentItem::InputText {
+                text: resource_update_message(&server, &uri, &previous, &text),
+            }],
+        }];
+        if let Err(items) = self.inject_response_items(items).await {
+            self.queue_response_items_for_next_turn(items).await;
+            if matches!(
+                self.get_thread_goal().await,
+                Ok(Some(goal)) if goal.status == ThreadGoalStatus::Active
+            ) {
+                self.maybe_start_turn_for_pending_work().await;
+            }
+        }
+
+        self.send_event_raw(Event {
+            id: "mcp_resource_updated".to_string(),
+            msg: EventMsg::McpResourceUpdated(McpResourceUpdatedEvent { server, uri }),
+        })
+        .await;
+    }
+}
+
+/// Re-subscribes on a freshly started connection manager, since the old
+/// connections took their subscriptions with them.
+#[expect(
+    clippy::await_holding_invalid_type,
+    reason = "subscriptions must be renewed on the manager that is current at this point"
+)]
+pub(crate) async fn renew_mcp_resource_subscriptions(
+    manager: Arc<RwLock<McpConnectionManager>>,
+    subscriptions: Vec<(String, String)>,
+) {
+    let manager = manager.read().await;
+    for (server, uri) in subscriptions {
+        if let Err(err) = manager.subscribe_resource(&server, &uri).await {
+            warn!("failed to renew MCP resource subscription `{uri}` on `{server}`: {err:#}");
+        }
+    }
+}
+
+fn read_params(uri: &str) -> ReadResourceRequestParams {
+    ReadResourceRequestParams {
+        meta: None,
+        uri: uri.to_string(),
+    }
+}
+
+fn resource_text(result: &ReadResourceResult) -> String {
+    result
+        .contents
+        .iter()
+        .map(|contents| match contents {
+            ResourceContents::TextResourceContents { text, .. } => text.clone(),
+            ResourceContents::BlobResourceContents { mime_type, .. } => format!(
+                "[binary contents ({})]",
+                mime_type.as_deref().unwrap_or("unknown type")
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The resource text as stored for diffing, capped at [`MAX_BASELINE_BYTES`].
+fn baseline_text(result: &ReadResourceResult) -> String {
+    let text = resource_text(result);
+    if text.len() <= MAX_BASELINE_BYTES {
+        return text;
+    }
+    truncate_text(&text, TruncationPolicy::Bytes(MAX_BASELINE_BYTES))
+}
+
+fn resource_update_message(server: &str, uri: &str, previous: &str, current: &str) -> String {
+    let diff = similar::TextDiff::from_lines(previous, current)
+        .unified_diff()
+        .context_radius(3)
+        .header("previous", "current")
+        .to_string();
+    let policy = TruncationPolicy::Tokens(MAX_DIFF_TOKENS);
+    if diff.len() <= policy.byte_budget() {
+        return format!("MCP resource `{uri}` on server `{server}` changed:\n\n```diff\n{diff}```");
+    }
+    let diff = truncate_text(&diff, policy);
+    format!(
+        "MCP resource `{uri}` on server `{server}` changed. The diff is too long and was truncated; read the resource for its full contents.\n\n```diff\n{diff}\n```"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn resource_update_message_shows_line_diff() {
+        let message = resource_update_message(
+            "docs",
+            "memo://note",
+            "first\nsecond\n",
+            "first\nsecond\nthird\n",
+        );
+
+        assert_eq!(
+            message,
+            "MCP resource `memo://note` on server `docs` changed:\n\n```diff\n--- previous\n+++ current\n@@ -1,2 +1,3 @@\n first\n second\n+third\n```"
+        );
+    }
+
+    #[test]
+    fn resource_update_message_truncates_long_diffs() {
+        let current = "changed line\n".repeat(10_000);
+
+        let message = resource_update_message("docs", "memo://note", "", &current);
+
+        assert!(
+            message.starts_with(
+                "MCP resource `memo://note` on server `docs` changed. The diff is too long and was truncated; read the resource for its full contents."
+            ),
+            "unexpected message: {message}"
+        );
+        assert!(
+            message.len() < TruncationPolicy::Tokens(MAX_DIFF_TOKENS).byte_budget() + 512,
+            "message was not truncated: {} bytes",
+            message.len()
+        );
+    }
+}
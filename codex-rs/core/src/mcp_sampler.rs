@@ -0,0 +1,207 @@
+use std::sync::Weak;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_api::ResponseEvent;
+use codex_mcp::McpSampler;
+use codex_mcp::McpSamplingOutput;
+use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::mcp::CreateMessageRequest;
+use codex_protocol::mcp::CreateMessageResult;
+use codex_protocol::mcp::SamplingMessage;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_rollout_trace::InferenceTraceContext;
+use futures::StreamExt;
+use futures::future::BoxFuture;
+use futures::future::FutureExt;
+use serde_json::json;
+
+use crate::Prompt;
+use crate::session::session::Session;
+
+/// Answers MCP `sampling/createMessage` requests with the session's current
+/// model and counts the tokens against the thread.
+///
+/// MCP treats model preferences, temperature, and stop sequences as hints, so
+/// only the messages and system prompt are forwarded. The server's `max_tokens`
+/// is not enforced either; the model's own output limit applies.
+pub(crate) struct SessionMcpSampler {
+    pub(crate) session: Weak<Session>,
+}
+
+impl McpSampler for SessionMcpSampler {
+    fn create_message(
+        &self,
+        _server: String,
+        request: CreateMessageRequest,
+    ) -> BoxFuture<'static, Result<McpSamplingOutput>> {
+        let session = self.session.clone();
+        async move {
+            let session = session
+                .upgrade()
+                .ok_or_else(|| anyhow!("session is no longer running"))?;
+            create_message(&session, request).await
+        }
+        .boxed()
+    }
+}
+
+async fn create_message(
+    session: &Session,
+    request: CreateMessageRequest,
+) -> Result<McpSamplingOutput> {
+    let turn_context = session.new_default_turn().await;
+    let prompt = Prompt {
+        input: request.messages.iter().map(sampling_message_item).collect(),
+        base_instructions: BaseInstructions {
+            text: request.system_prompt.unwrap_or_default(),
+        },
+        ..Default::default()
+    };
+
+    let mut client_session = session.services.model_client.new_session();
+    let mut stream = client_session
+        .stream(
+            &prompt,
+            &turn_context.model_info,
+            &turn_context.session_telemetry,
+            /*effort*/ None,
+            ReasoningSummaryConfig::None,
+            turn_context.config.service_tier,
+            /*turn_metadata_header*/ None,
+            &InferenceTraceContext::disabled(),
+        )
+        .await?;
+
+    let mut text = String::new();
+    let mut token_usage = None;
+    while let Some(event) = stream.next().await.transpose()? {
+        match event {
+            ResponseEvent::OutputTextDelta(delta) => text.push_str(&delta),
+            ResponseEvent::OutputItemDone(item) => {
+                if text.is_empty()
+                    && let ResponseItem::Message { content, .. } = item
+                    && let Some(item_text) = crate::compact::content_items_to_text(&content)
+                {
+                    text.push_str(&item_text);
+                }
+            }
+            ResponseEvent::Completed {
+                token_usage: usage, ..
+            } => {
+                token_usage = usage;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(usage) = token_usage.as_ref() {
+        session
+            .add_side_call_token_usage(&turn_context, usage)
+            .await;
+    }
+    Ok(McpSamplingOutput {
+        result: CreateMessageResult {
+            role: "assistant".to_string(),
+            content: json!({ "type": "text", "text": text }),
+            model: turn_context.model_info.slug.clone(),
+            stop_reason: Some("endTurn".to_string()),
+        },
+        token_usage,
+    })
+}
+
+/// Converts a sampling message into a model input item. Audio and other
+/// non-text content is dropped; images are only accepted from the user.
+fn sampling_message_item(message: &SamplingMessage) -> ResponseItem {
+    let is_assistant = message.role == "assistant";
+    let blocks: Vec<&serde_json::Value> = match &message.content {
+        serde_json::Value::Array(blocks) => blocks.iter().collect(),
+        block => vec![block],
+    };
+    let content = blocks
+        .into_iter()
+        .filter_map(
+            |block| match block.get("type").and_then(serde_json::Value::as_str) {
+                Some("text") => {
+                    let text = block.get("text")?.as_str()?.to_string();
+                    Some(if is_assistant {
+                        ContentItem::OutputText { text }
+                    } else {
+                        ContentItem::InputText { text }
+                    })
+                }
+                Some("image") if !is_assistant => {
+                    let data = block.get("data")?.as_str()?;
+                    let mime_type = block.get("mimeType")?.as_str()?;
+                    Some(ContentItem::InputImage {
+                        image_url: format!("data:{mime_type};base64,{data}"),
+                        detail: None,
+                    })
+                }
+                _ => None,
+            },
+        )
+        .collect();
+    ResponseItem::Message {
+        id: None,
+        role: if is_assistant { "assistant" } else { "user" }.to_string(),
+        content,
+        phase: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn sampling_message_item_maps_text_and_user_images() {
+        let user = SamplingMessage {
+            role: "user".to_string(),
+            content: json!([
+                { "type": "text", "text": "What is in this image?" },
+                { "type": "image", "data": "AAAA", "mimeType": "image/png" },
+                { "type": "audio", "data": "AAAA", "mimeType": "audio/wav" },
+            ]),
+        };
+        let assistant = SamplingMessage {
+            role: "assistant".to_string(),
+            content: json!({ "type": "text", "text": "A cat." }),
+        };
+
+        assert_eq!(
+            sampling_message_item(&user),
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![
+                    ContentItem::InputText {
+                        text: "What is in this image?".to_string(),
+                    },
+                    ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                        detail: None,
+                    },
+                ],
+                phase: None,
+            }
+        );
+        assert_eq!(
+            sampling_message_item(&assistant),
+            ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "A cat.".to_string(),
+                }],
+                phase: None,
+            }
+        );
+    }
+}
@@ -63,6 +63,11 @@ pub(crate) fn denied_network_policy_message(blocked: &BlockedRequest) -> Option<
         "not_allowed_local" => "local/private network addresses are blocked by the sandbox policy",
         "method_not_allowed" => "request method is blocked by the current network mode",
         "proxy_disabled" => "network proxy is disabled",
+        "secret_exfiltration" => {
+            "request appears to contain a secret and the host is not allowed to receive secrets"
+        }
+        "rate_limited" => "the request rate budget for this host or thread is exhausted",
+        "byte_quota_exceeded" => "the network byte quota for this host or thread is exhausted",
         _ => "request is blocked by network policy",
     };
 
@@ -190,3 +190,25 @@ fn denied_network_policy_message_for_denylist_block_is_explicit() {
             )
         );
 }
+
+#[test]
+fn denied_network_policy_message_for_secret_exfiltration_block_is_explicit() {
+    let blocked = BlockedRequest {
+        host: "paste.example.com".to_string(),
+        reason: "secret_exfiltration".to_string(),
+        client: None,
+        method: Some("POST".to_string()),
+        mode: None,
+        protocol: "https".to_string(),
+        decision: Some("deny".to_string()),
+        source: Some("baseline_policy".to_string()),
+        port: Some(443),
+        timestamp: 0,
+    };
+    assert_eq!(
+        denied_network_policy_message(&blocked),
+        Some(
+            "Network access to \"paste.example.com\" was blocked: request appears to contain a secret and the host is not allowed to receive secrets.".to_string()
+        )
+    );
+}
@@ -0,0 +1,60 @@
+//! Collects the secret values the managed network proxy's secret guard watches for.
+
+use codex_login::CodexAuth;
+use codex_login::read_codex_api_key_from_env;
+use codex_login::read_openai_api_key_from_env;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+use std::path::PathBuf;
+use tracing::warn;
+
+/// The user's API keys and auth tokens plus every value stored with `codex-secrets`.
+pub(crate) async fn guarded_network_secrets(
+    auth: Option<&CodexAuth>,
+    codex_home: PathBuf,
+) -> Vec<String> {
+    let mut secrets = Vec::new();
+    if let Some(auth) = auth {
+        if let Some(api_key) = auth.api_key() {
+            secrets.push(api_key.to_string());
+        }
+        if let Ok(tokens) = auth.get_token_data() {
+            secrets.push(tokens.access_token);
+            secrets.push(tokens.refresh_token);
+        }
+    }
+    secrets.extend(read_openai_api_key_from_env());
+    secrets.extend(read_codex_api_key_from_env());
+
+    // Reading stored secrets can touch the OS keyring, so keep it off the async runtime.
+    let stored = tokio::task::spawn_blocking(move || stored_secret_values(codex_home)).await;
+    match stored {
+        Ok(stored) => secrets.extend(stored),
+        Err(err) => warn!("failed to read stored secrets for the network secret guard: {err}"),
+    }
+    secrets
+}
+
+fn stored_secret_values(codex_home: PathBuf) -> Vec<String> {
+    let manager = SecretsManager::new(codex_home, SecretsBackendKind::Local);
+    let entries = match manager.list(/*scope_filter*/ None) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!("failed to list stored secrets for the network secret guard: {err:#}");
+            return Vec::new();
+        }
+    };
+    entries
+        .iter()
+        .filter_map(|entry| match manager.get(&entry.scope, &entry.name) {
+            Ok(value) => value,
+            Err(err) => {
+                warn!(
+                    "failed to read stored secret {} for the network secret guard: {err:#}",
+                    entry.name
+                );
+                None
+            }
+        })
+        .collect()
+}
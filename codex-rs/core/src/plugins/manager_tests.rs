@@ -211,6 +211,7 @@ async fn load_plugins_loads_default_skills_and_mcp_servers() {
                     startup_timeout_sec: None,
                     tool_timeout_sec: None,
                     default_tools_approval_mode: None,
+                    sampling_approval_mode: None,
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
@@ -549,6 +550,7 @@ async fn load_plugins_uses_manifest_configured_component_paths() {
                 startup_timeout_sec: None,
                 tool_timeout_sec: None,
                 default_tools_approval_mode: None,
+                sampling_approval_mode: None,
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
@@ -660,6 +662,7 @@ async fn load_plugins_ignores_manifest_component_paths_without_dot_slash() {
                 startup_timeout_sec: None,
                 tool_timeout_sec: None,
                 default_tools_approval_mode: None,
+                sampling_approval_mode: None,
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
@@ -819,6 +822,7 @@ fn capability_index_filters_inactive_and_zero_capability_plugins() {
         startup_timeout_sec: None,
         tool_timeout_sec: None,
         default_tools_approval_mode: None,
+        sampling_approval_mode: None,
         enabled_tools: None,
         disabled_tools: None,
         scopes: None,
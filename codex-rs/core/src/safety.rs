@@ -64,6 +64,19 @@ pub fn assess_patch_safety(
             AskForApproval::Granular(granular_config) if !granular_config.sandbox_approval
         );
 
+    // A symlink can be created inside a writable root yet point anywhere, and later writes through
+    // it would land outside the sandbox, so a link to a non-writable target needs the user.
+    if !are_symlink_targets_writable(action, file_system_sandbox_policy, cwd) {
+        return if rejects_sandbox_approval {
+            SafetyCheck::Reject {
+                reason: patch_rejection_reason(permission_profile, file_system_sandbox_policy, cwd)
+                    .to_string(),
+            }
+        } else {
+            SafetyCheck::AskUser
+        };
+    }
+
     // Even though the patch appears to be constrained to writable paths, it is
     // possible that paths in the patch are hard links to files outside the
     // writable roots, so we should still run `apply_patch` in a sandbox in that case.
@@ -135,43 +148,67 @@ fn patch_rejection_reason(
     }
 }
 
-fn is_write_patch_constrained_to_writable_paths(
-    action: &ApplyPatchAction,
-    file_system_sandbox_policy: &FileSystemSandboxPolicy,
-    cwd: &AbsolutePathBuf,
-) -> bool {
-    // Normalize a path by removing `.` and resolving `..` without touching the
-    // filesystem (works even if the file does not exist).
-    fn normalize(path: &Path) -> Option<PathBuf> {
-        let mut out = PathBuf::new();
-        for comp in path.components() {
-            match comp {
-                Component::ParentDir => {
-                    out.pop();
-                }
-                Component::CurDir => { /* skip */ }
-                other => out.push(other.as_os_str()),
+// Normalize a path by removing `.` and resolving `..` without touching the
+// filesystem (works even if the file does not exist).
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for comp in path.components() {
+        match comp {
+            Component::ParentDir => {
+                out.pop();
             }
+            Component::CurDir => { /* skip */ }
+            other => out.push(other.as_os_str()),
         }
-        Some(out)
     }
+    out
+}
+
+// Determine whether `path` is inside **any** writable root. Both `path`
+// and roots are converted to absolute, normalized forms before the
+// prefix check.
+fn is_path_writable(
+    path: &Path,
+    file_system_sandbox_policy: &FileSystemSandboxPolicy,
+    cwd: &AbsolutePathBuf,
+) -> bool {
+    let abs = normalize(&resolve_path(cwd, &path.to_path_buf()));
+    file_system_sandbox_policy.can_write_path_with_cwd(&abs, cwd)
+}
 
-    // Determine whether `path` is inside **any** writable root. Both `path`
-    // and roots are converted to absolute, normalized forms before the
-    // prefix check.
-    let is_path_writable = |p: &PathBuf| {
-        let abs = resolve_path(cwd, p);
-        let abs = match normalize(&abs) {
-            Some(v) => v,
-            None => return false,
+/// Returns whether every symlink the patch adds points into a writable root. Relative targets are
+/// resolved against the directory containing the link, as the filesystem would.
+fn are_symlink_targets_writable(
+    action: &ApplyPatchAction,
+    file_system_sandbox_policy: &FileSystemSandboxPolicy,
+    cwd: &AbsolutePathBuf,
+) -> bool {
+    action.changes().iter().all(|(path, change)| {
+        let ApplyPatchFileChange::AddSymlink { target } = change else {
+            return true;
         };
+        let link = resolve_path(cwd, path);
+        let target = match link.parent() {
+            Some(link_dir) => link_dir.join(target),
+            None => target.clone(),
+        };
+        is_path_writable(&target, file_system_sandbox_policy, cwd)
+    })
+}
 
-        file_system_sandbox_policy.can_write_path_with_cwd(&abs, cwd)
-    };
+fn is_write_patch_constrained_to_writable_paths(
+    action: &ApplyPatchAction,
+    file_system_sandbox_policy: &FileSystemSandboxPolicy,
+    cwd: &AbsolutePathBuf,
+) -> bool {
+    let is_path_writable = |p: &PathBuf| is_path_writable(p, file_system_sandbox_policy, cwd);
 
     for (path, change) in action.changes() {
         match change {
-            ApplyPatchFileChange::Add { .. } | ApplyPatchFileChange::Delete { .. } => {
+            ApplyPatchFileChange::Add { .. }
+            | ApplyPatchFileChange::AddBinary { .. }
+            | ApplyPatchFileChange::AddSymlink { .. }
+            | ApplyPatchFileChange::Delete { .. } => {
                 if !is_path_writable(path) {
                     return false;
                 }
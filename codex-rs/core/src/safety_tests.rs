@@ -320,3 +320,33 @@ fn missing_project_dot_codex_config_requires_approval() {
         SafetyCheck::AskUser,
     );
 }
+
+#[test]
+fn symlink_to_non_writable_target_requires_approval() {
+    let tmp = TempDir::new().unwrap();
+    let cwd = tmp.path().abs();
+    let policy_workspace_only = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![],
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+    };
+    let assess = |target: &str| {
+        assess_patch_safety(
+            &ApplyPatchAction::new_add_symlink_for_test(
+                &cwd.join("sub").join("link"),
+                PathBuf::from(target),
+            ),
+            AskForApproval::OnFailure,
+            &permission_profile_for_policy(&policy_workspace_only),
+            &FileSystemSandboxPolicy::from(&policy_workspace_only),
+            &cwd,
+            WindowsSandboxLevel::Disabled,
+        )
+    };
+
+    // The relative target resolves against the link's directory, so it escapes the workspace.
+    assert_eq!(assess("../../outside.txt"), SafetyCheck::AskUser);
+    assert_eq!(assess("/etc/passwd"), SafetyCheck::AskUser);
+    assert_ne!(assess("../inner.txt"), SafetyCheck::AskUser);
+}
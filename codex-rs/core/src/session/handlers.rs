@@ -407,10 +407,11 @@ pub async fn exec_approval(
     let event_turn_id = turn_id.unwrap_or_else(|| approval_id.clone());
     if let ReviewDecision::ApprovedExecpolicyAmendment {
         proposed_execpolicy_amendment,
+        scope,
     } = &decision
     {
         match sess
-            .persist_execpolicy_amendment(proposed_execpolicy_amendment)
+            .persist_execpolicy_amendment(&event_turn_id, proposed_execpolicy_amendment, *scope)
             .await
         {
             Ok(()) => {
@@ -939,6 +940,7 @@ pub async fn set_thread_memory_mode(sess: &Arc<Session>, sub_id: String, mode: T
 pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
     sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
     let _ = sess.conversation.shutdown().await;
+    sess.unsubscribe_all_mcp_resources().await;
     sess.services
         .unified_exec_manager
         .terminate_all_processes()
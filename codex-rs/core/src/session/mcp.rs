@@ -1,4 +1,5 @@
 use super::*;
+use crate::mcp_resource_subscriptions::renew_mcp_resource_subscriptions;
 
 impl Session {
     #[expect(
@@ -170,6 +171,37 @@ impl Session {
             .await
     }
 
+    #[expect(
+        clippy::await_holding_invalid_type,
+        reason = "MCP prompt calls are serialized through the session-owned manager guard"
+    )]
+    pub async fn list_mcp_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        self.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .list_all_prompts()
+            .await
+    }
+
+    #[expect(
+        clippy::await_holding_invalid_type,
+        reason = "MCP prompt calls are serialized through the session-owned manager guard"
+    )]
+    pub async fn get_mcp_prompt(
+        &self,
+        server: &str,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> anyhow::Result<GetPromptResult> {
+        self.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .get_prompt(server, name, arguments)
+            .await
+    }
+
     #[expect(
         clippy::await_holding_invalid_type,
         reason = "MCP tool calls are serialized through the session-owned manager guard"
@@ -255,8 +287,26 @@ impl Session {
             *guard = cancel_token;
         }
 
-        let mut manager = self.services.mcp_connection_manager.write().await;
-        *manager = refreshed_manager;
+        {
+            let mut manager = self.services.mcp_connection_manager.write().await;
+            if let Some(sampler) = manager.sampler() {
+                refreshed_manager.set_sampler(sampler);
+            }
+            if let Some(handler) = manager.resource_update_handler() {
+                refreshed_manager.set_resource_update_handler(handler);
+            }
+            *manager = refreshed_manager;
+        }
+
+        // Subscriptions belong to the old connections; renew them on the new
+        // ones once the servers finish starting.
+        let subscriptions = self.services.mcp_resource_subscriptions.keys().await;
+        if !subscriptions.is_empty() {
+            tokio::spawn(renew_mcp_resource_subscriptions(
+                Arc::clone(&self.services.mcp_connection_manager),
+                subscriptions,
+            ));
+        }
     }
 
     pub(crate) async fn refresh_mcp_servers_if_requested(&self, turn_context: &TurnContext) {
@@ -32,6 +32,7 @@ use crate::default_skill_metadata_budget;
 use crate::environment_selection::selected_primary_environment;
 use crate::environment_selection::validate_environment_selections;
 use crate::exec_policy::ExecPolicyManager;
+use crate::hook_model_client::SessionHookModelClient;
 use crate::installation_id::resolve_installation_id;
 use crate::parse_turn_item;
 use crate::path_utils::normalize_for_native_workdir;
@@ -49,13 +50,16 @@ use codex_analytics::AnalyticsEventsClient;
 use codex_analytics::SubAgentThreadStartedInput;
 use codex_app_server_protocol::McpServerElicitationRequest;
 use codex_app_server_protocol::McpServerElicitationRequestParams;
+use codex_apply_patch::MergeBases;
 use codex_config::types::OAuthCredentialsStoreMode;
 use codex_exec_server::Environment;
 use codex_exec_server::EnvironmentManager;
 use codex_exec_server::FileSystemSandboxContext;
+use codex_execpolicy::RuleScope;
 use codex_features::FEATURES;
 use codex_features::Feature;
 use codex_features::unstable_features_warning_event;
+use codex_hooks::HookModelClient;
 use codex_hooks::Hooks;
 use codex_hooks::HooksConfig;
 use codex_login::AuthManager;
@@ -79,6 +83,7 @@ use codex_protocol::ToolName;
 use codex_protocol::account::PlanType as AccountPlanType;
 use codex_protocol::approvals::ElicitationRequestEvent;
 use codex_protocol::approvals::ExecPolicyAmendment;
+use codex_protocol::approvals::ExecPolicyAmendmentScope;
 use codex_protocol::approvals::NetworkPolicyAmendment;
 use codex_protocol::approvals::NetworkPolicyRuleAction;
 use codex_protocol::config_types::ApprovalsReviewer;
@@ -90,6 +95,8 @@ use codex_protocol::dynamic_tools::DynamicToolSpec;
 use codex_protocol::items::TurnItem;
 use codex_protocol::items::UserMessageItem;
 use codex_protocol::mcp::CallToolResult;
+use codex_protocol::mcp::GetPromptResult;
+use codex_protocol::mcp::Prompt;
 use codex_protocol::models::AdditionalPermissionProfile;
 use codex_protocol::models::BaseInstructions;
 use codex_protocol::models::PermissionProfile;
@@ -413,6 +420,8 @@ pub(crate) const INITIAL_SUBMIT_ID: &str = "";
 pub(crate) const SUBMISSION_CHANNEL_CAPACITY: usize = 512;
 const CYBER_VERIFY_URL: &str = "https://chatgpt.com/cyber";
 const CYBER_SAFETY_URL: &str = "https://developers.openai.com/codex/concepts/cyber-safety";
+/// Files larger than this are not kept as `apply_patch` merge bases.
+const MAX_MERGE_BASE_BYTES: usize = 1024 * 1024;
 
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
@@ -1283,7 +1292,14 @@ impl Session {
         &self,
         updates: SessionSettingsUpdate,
     ) -> ConstraintResult<()> {
-        let (previous_cwd, permission_profile_changed, next_cwd, codex_home, session_source) = {
+        let (
+            previous_cwd,
+            permission_profile_changed,
+            permission_profile,
+            next_cwd,
+            codex_home,
+            session_source,
+        ) = {
             let mut state = self.state.lock().await;
             let updated = match state.session_configuration.apply(&updates) {
                 Ok(updated) => updated,
@@ -1305,6 +1321,7 @@ impl Session {
             (
                 previous_cwd,
                 permission_profile_changed,
+                updated_permission_profile,
                 next_cwd,
                 codex_home,
                 session_source,
@@ -1321,6 +1338,13 @@ impl Session {
             self.refresh_managed_network_proxy_for_current_permission_profile()
                 .await;
         }
+        if permission_profile_changed || previous_cwd.as_path() != next_cwd.as_path() {
+            self.services
+                .mcp_connection_manager
+                .read()
+                .await
+                .set_roots(next_cwd.as_path(), &permission_profile);
+        }
 
         Ok(())
     }
@@ -1619,22 +1643,45 @@ impl Session {
     }
 
     /// Adds an execpolicy amendment to both the in-memory and on-disk policies so future
-    /// commands can use the newly approved prefix.
+    /// commands can use the newly approved prefix. `scope` limits the saved rule to this thread,
+    /// to the working directory of turn `sub_id`, or to the next day.
     pub(crate) async fn persist_execpolicy_amendment(
         &self,
+        sub_id: &str,
         amendment: &ExecPolicyAmendment,
+        scope: ExecPolicyAmendmentScope,
     ) -> Result<(), ExecPolicyUpdateError> {
-        let codex_home = self
-            .state
-            .lock()
-            .await
-            .session_configuration
-            .codex_home()
-            .clone();
+        let (codex_home, session_cwd) = {
+            let state = self.state.lock().await;
+            (
+                state.session_configuration.codex_home().clone(),
+                state.session_configuration.cwd.clone(),
+            )
+        };
+        let rule_scope = match scope {
+            ExecPolicyAmendmentScope::Permanent => RuleScope::default(),
+            ExecPolicyAmendmentScope::Thread => RuleScope {
+                thread_id: Some(self.conversation_id.to_string()),
+                ..Default::default()
+            },
+            ExecPolicyAmendmentScope::Project => RuleScope {
+                project_root: Some(
+                    self.turn_context_for_sub_id(sub_id)
+                        .await
+                        .map(|turn_context| turn_context.cwd.clone())
+                        .unwrap_or(session_cwd),
+                ),
+                ..Default::default()
+            },
+            ExecPolicyAmendmentScope::Day => RuleScope {
+                expires_at: Some(Utc::now() + chrono::Duration::days(1)),
+                ..Default::default()
+            },
+        };
 
         self.services
             .exec_policy
-            .append_amendment_and_update(&codex_home, amendment)
+            .append_amendment_and_update(&codex_home, amendment, &rule_scope)
             .await?;
 
         Ok(())
@@ -2755,6 +2802,20 @@ impl Session {
         self.send_token_count_event(turn_context).await;
     }
 
+    /// Counts tokens spent by a model call outside the turn stream, such as
+    /// MCP sampling, toward the thread total and reports the new totals.
+    pub(crate) async fn add_side_call_token_usage(
+        &self,
+        turn_context: &TurnContext,
+        token_usage: &TokenUsage,
+    ) {
+        {
+            let mut state = self.state.lock().await;
+            state.add_side_call_token_usage(token_usage);
+        }
+        self.send_token_count_event(turn_context).await;
+    }
+
     pub(crate) async fn recompute_token_usage(&self, turn_context: &TurnContext) {
         let history = self.clone_history().await;
         let base_instructions = self.get_base_instructions().await;
@@ -2828,6 +2889,31 @@ impl Session {
         state.set_server_reasoning_included(included);
     }
 
+    /// Remembers `contents` as the version of `path` the model last saw, so a later
+    /// `apply_patch` whose context has drifted can three-way merge against it. Files too large
+    /// to keep drop any earlier snapshot so merges never run against a stale base.
+    pub(crate) async fn record_merge_base(&self, path: &AbsolutePathBuf, contents: String) {
+        if !self.enabled(Feature::ApplyPatchThreeWayMerge) {
+            return;
+        }
+        let mut state = self.state.lock().await;
+        if contents.len() > MAX_MERGE_BASE_BYTES {
+            state.remove_merge_base(path);
+        } else {
+            state.record_merge_base(path, contents);
+        }
+    }
+
+    /// Merge bases for the files an `apply_patch` call updates; empty unless
+    /// [`Feature::ApplyPatchThreeWayMerge`] is enabled.
+    pub(crate) async fn merge_bases_for(&self, paths: &[AbsolutePathBuf]) -> MergeBases {
+        if !self.enabled(Feature::ApplyPatchThreeWayMerge) {
+            return MergeBases::default();
+        }
+        let state = self.state.lock().await;
+        state.merge_bases_for(paths)
+    }
+
     async fn send_token_count_event(&self, turn_context: &TurnContext) {
         let (info, rate_limits) = {
             let state = self.state.lock().await;
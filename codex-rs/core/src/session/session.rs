@@ -1,5 +1,9 @@
 use super::*;
 use crate::goals::GoalRuntimeState;
+use crate::mcp_resource_subscriptions::McpResourceSubscriptions;
+use crate::mcp_resource_subscriptions::SessionMcpResourceUpdateHandler;
+use crate::mcp_sampler::SessionMcpSampler;
+use crate::network_secret_guard::guarded_network_secrets;
 use codex_protocol::permissions::FileSystemPath;
 use codex_protocol::permissions::FileSystemSpecialPath;
 use tokio::sync::Semaphore;
@@ -745,6 +749,14 @@ impl Session {
                             managed_network_requirements_enabled,
                     ))
                     .await?;
+                    if spec.secret_guard_enabled() {
+                        let secrets = guarded_network_secrets(
+                            auth,
+                            config.codex_home.to_path_buf(),
+                        )
+                        .await;
+                        network_proxy.proxy().set_guarded_secrets(secrets).await;
+                    }
                     (Some(network_proxy), Some(session_network_proxy))
                 } else {
                     (None, None)
@@ -754,12 +766,33 @@ impl Session {
                 default_shell.derive_exec_args("", /*use_login_shell*/ false);
             let hook_shell_program = hook_shell_argv.remove(0);
             let _ = hook_shell_argv.pop();
+            let installation_id = resolve_installation_id(&config.codex_home).await?;
+            let model_client = ModelClient::new(
+                Some(Arc::clone(&auth_manager)),
+                conversation_id,
+                installation_id,
+                session_configuration.provider.clone(),
+                session_configuration.session_source.clone(),
+                config.model_verbosity,
+                config.features.enabled(Feature::EnableRequestCompression),
+                config.features.enabled(Feature::RuntimeMetrics),
+                Self::build_model_client_beta_features_header(config.as_ref()),
+            );
+            let hook_model_client: Arc<dyn HookModelClient> = Arc::new(SessionHookModelClient {
+                model_client: model_client.clone(),
+                models_manager: Arc::clone(&models_manager),
+                models_manager_config: config.to_models_manager_config(),
+                session_telemetry: session_telemetry.clone(),
+                default_model: session_configuration.collaboration_mode.model().to_string(),
+                service_tier: session_configuration.service_tier,
+            });
             let hooks = Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
                 feature_enabled: config.features.enabled(Feature::CodexHooks),
                 config_layer_stack: Some(config.config_layer_stack.clone()),
                 shell_program: Some(hook_shell_program),
                 shell_args: hook_shell_argv,
+                model_client: Some(hook_model_client),
             });
             for warning in hooks.startup_warnings() {
                 post_session_configured_events.push(Event {
@@ -770,7 +803,6 @@ impl Session {
                 });
             }
 
-            let installation_id = resolve_installation_id(&config.codex_home).await?;
             let analytics_events_client = analytics_events_client.unwrap_or_else(|| {
                 AnalyticsEventsClient::new(
                     Arc::clone(&auth_manager),
@@ -791,6 +823,7 @@ impl Session {
                     &config.permissions.permission_profile,
                 ))),
                 mcp_startup_cancellation_token: Mutex::new(CancellationToken::new()),
+                mcp_resource_subscriptions: McpResourceSubscriptions::default(),
                 unified_exec_manager: UnifiedExecProcessManager::new(
                     config.background_terminal_max_timeout,
                 ),
@@ -820,17 +853,7 @@ impl Session {
                 state_db: state_db_ctx.clone(),
                 live_thread: live_thread_init.as_ref().cloned(),
                 thread_store: Arc::clone(&thread_store),
-                model_client: ModelClient::new(
-                    Some(Arc::clone(&auth_manager)),
-                    conversation_id,
-                    installation_id,
-                    session_configuration.provider.clone(),
-                    session_configuration.session_source.clone(),
-                    config.model_verbosity,
-                    config.features.enabled(Feature::EnableRequestCompression),
-                    config.features.enabled(Feature::RuntimeMetrics),
-                    Self::build_model_client_beta_features_header(config.as_ref()),
-                ),
+                model_client,
                 code_mode_service: crate::tools::code_mode::CodeModeService::new(),
                 environment_manager,
             };
@@ -942,6 +965,14 @@ impl Session {
                 session_init.required_mcp_server_count = required_mcp_server_count,
             ))
             .await;
+            mcp_connection_manager.set_sampler(Arc::new(SessionMcpSampler {
+                session: Arc::downgrade(&sess),
+            }));
+            mcp_connection_manager.set_resource_update_handler(Arc::new(
+                SessionMcpResourceUpdateHandler {
+                    session: Arc::downgrade(&sess),
+                },
+            ));
             {
                 let mut manager_guard = sess.services.mcp_connection_manager.write().await;
                 *manager_guard = mcp_connection_manager;
@@ -1,5 +1,6 @@
 //! Session-wide mutable state.
 
+use codex_apply_patch::MergeBases;
 use codex_protocol::models::AdditionalPermissionProfile;
 use codex_protocol::models::ResponseItem;
 use codex_sandboxing::policy_transforms::merge_permission_profiles;
@@ -14,7 +15,12 @@ use codex_protocol::protocol::RateLimitSnapshot;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
 use codex_protocol::protocol::TurnContextItem;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_output_truncation::TruncationPolicy;
+use indexmap::IndexMap;
+
+/// Most files kept as `apply_patch` merge bases at once.
+const MAX_MERGE_BASES: usize = 64;
 
 /// Persistent, session-scoped state previously stored directly on `Session`.
 pub(crate) struct SessionState {
@@ -34,6 +40,9 @@ pub(crate) struct SessionState {
     pub(crate) pending_session_start_source: Option<codex_hooks::SessionStartSource>,
     granted_permissions: Option<AdditionalPermissionProfile>,
     next_turn_is_first: bool,
+    /// Contents of files as the model last read or wrote them, used as the base of the
+    /// three-way merge fallback in `apply_patch`. Ordered from least to most recently recorded.
+    merge_bases: IndexMap<AbsolutePathBuf, String>,
 }
 
 impl SessionState {
@@ -53,6 +62,7 @@ impl SessionState {
             pending_session_start_source: None,
             granted_permissions: None,
             next_turn_is_first: true,
+            merge_bases: IndexMap::new(),
         }
     }
 
@@ -124,6 +134,19 @@ impl SessionState {
         self.history.token_info()
     }
 
+    /// Adds usage from a model call made outside the conversation, such as MCP
+    /// sampling, to the thread total. Last-call usage is left alone because it
+    /// tracks how full the conversation's context window is.
+    pub(crate) fn add_side_call_token_usage(&mut self, usage: &TokenUsage) {
+        let mut info = self.token_info().unwrap_or(TokenUsageInfo {
+            total_token_usage: TokenUsage::default(),
+            last_token_usage: TokenUsage::default(),
+            model_context_window: None,
+        });
+        info.total_token_usage.add_assign(usage);
+        self.set_token_info(Some(info));
+    }
+
     pub(crate) fn set_rate_limits(&mut self, snapshot: RateLimitSnapshot) {
         self.latest_rate_limits = Some(merge_rate_limit_fields(
             self.latest_rate_limits.as_ref(),
@@ -226,6 +249,31 @@ impl SessionState {
     pub(crate) fn granted_permissions(&self) -> Option<AdditionalPermissionProfile> {
         self.granted_permissions.clone()
     }
+
+    /// Records `contents` as the most recent merge base of `path`, evicting the least recently
+    /// recorded file once more than `MAX_MERGE_BASES` are kept.
+    pub(crate) fn record_merge_base(&mut self, path: &AbsolutePathBuf, contents: String) {
+        self.merge_bases.shift_remove(path);
+        self.merge_bases.insert(path.clone(), contents);
+        if self.merge_bases.len() > MAX_MERGE_BASES {
+            self.merge_bases.shift_remove_index(0);
+        }
+    }
+
+    pub(crate) fn remove_merge_base(&mut self, path: &AbsolutePathBuf) {
+        self.merge_bases.shift_remove(path);
+    }
+
+    /// Merge bases recorded for `paths`.
+    pub(crate) fn merge_bases_for(&self, paths: &[AbsolutePathBuf]) -> MergeBases {
+        let mut merge_bases = MergeBases::default();
+        for path in paths {
+            if let Some(contents) = self.merge_bases.get(path) {
+                merge_bases.record(path, contents.clone());
+            }
+        }
+        merge_bases
+    }
 }
 
 // Sometimes new snapshots don't include credits or plan information.
@@ -160,3 +160,64 @@ async fn set_rate_limits_carries_credits_and_plan_type_from_codex_to_codex_other
         })
     );
 }
+
+#[tokio::test]
+async fn side_call_token_usage_adds_to_total_without_replacing_last_usage() {
+    let session_configuration = make_session_configuration_for_tests().await;
+    let mut state = SessionState::new(session_configuration);
+    let turn_usage = TokenUsage {
+        input_tokens: 100,
+        output_tokens: 20,
+        total_tokens: 120,
+        ..TokenUsage::default()
+    };
+    state.update_token_info_from_usage(&turn_usage, Some(1_000));
+
+    state.add_side_call_token_usage(&TokenUsage {
+        input_tokens: 10,
+        output_tokens: 5,
+        total_tokens: 15,
+        ..TokenUsage::default()
+    });
+
+    assert_eq!(
+        state.token_info(),
+        Some(TokenUsageInfo {
+            total_token_usage: TokenUsage {
+                input_tokens: 110,
+                output_tokens: 25,
+                total_tokens: 135,
+                ..TokenUsage::default()
+            },
+            last_token_usage: turn_usage,
+            model_context_window: Some(1_000),
+        })
+    );
+}
+
+#[tokio::test]
+async fn merge_bases_evict_the_least_recently_recorded_file() {
+    let session_configuration = make_session_configuration_for_tests().await;
+    let mut state = SessionState::new(session_configuration);
+    let root = AbsolutePathBuf::current_dir().expect("current dir");
+    let paths: Vec<AbsolutePathBuf> = (0..=MAX_MERGE_BASES)
+        .map(|idx| root.join(format!("file-{idx}.txt")))
+        .collect();
+
+    for path in &paths {
+        state.record_merge_base(path, "contents".to_string());
+    }
+    // Re-recording the second file makes the third the least recently recorded one.
+    state.record_merge_base(&paths[1], "updated".to_string());
+    state.record_merge_base(&root.join("extra.txt"), "contents".to_string());
+    state.remove_merge_base(&paths[3]);
+
+    let merge_bases = state.merge_bases_for(&paths[..4]);
+    assert_eq!(
+        vec![None, Some("updated"), None, None],
+        paths[..4]
+            .iter()
+            .map(|path| merge_bases.get(path.as_path()))
+            .collect::<Vec<_>>()
+    );
+}
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use tokio_util::sync::CancellationToken;
 
+use crate::hook_runtime::run_subagent_stop_hooks;
 use crate::session::turn::run_turn;
 use crate::session::turn_context::TurnContext;
 use crate::session_startup_prewarm::SessionStartupPrewarmResolution;
@@ -75,6 +76,7 @@ impl SessionTask for RegularTask {
             .instrument(run_turn_span.clone())
             .await;
             if !sess.has_pending_input().await {
+                run_subagent_stop_hooks(&sess, &ctx, last_agent_message.clone()).await;
                 return last_agent_message;
             }
             next_input = Vec::new();
@@ -57,6 +57,7 @@ use codex_state::DirectionalThreadSpawnEdgeStatus;
 use codex_thread_store::InMemoryThreadStore;
 use codex_thread_store::LocalThreadStore;
 use codex_thread_store::RemoteThreadStore;
+use codex_thread_store::SqliteThreadStore;
 use codex_thread_store::ThreadStore;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use futures::StreamExt;
@@ -258,6 +259,9 @@ fn configured_thread_store(config: &Config) -> Arc<dyn ThreadStore> {
         ThreadStoreConfig::Local => {
             Arc::new(LocalThreadStore::new(RolloutConfig::from_view(config)))
         }
+        ThreadStoreConfig::Sqlite => {
+            Arc::new(SqliteThreadStore::new(RolloutConfig::from_view(config)))
+        }
         ThreadStoreConfig::Remote { endpoint } => Arc::new(RemoteThreadStore::new(endpoint)),
         #[cfg(debug_assertions)]
         ThreadStoreConfig::InMemory { id } => InMemoryThreadStore::for_id(id),
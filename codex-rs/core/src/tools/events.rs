@@ -3,6 +3,7 @@ use crate::session::session::Session;
 use crate::session::turn_context::TurnContext;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::ToolError;
+use codex_exec_server::ExecutorFileSystem;
 use codex_protocol::error::CodexErr;
 use codex_protocol::error::SandboxErr;
 use codex_protocol::exec_output::ExecToolCallOutput;
@@ -160,6 +161,7 @@ impl ToolEmitter {
                 },
                 stage,
             ) => {
+                let succeeded = is_successful_exec(&stage);
                 emit_exec_stage(
                     ctx,
                     ExecCommandInput::new(
@@ -169,6 +171,9 @@ impl ToolEmitter {
                     stage,
                 )
                 .await;
+                if succeeded {
+                    record_read_merge_bases(ctx, cwd, parsed_cmd).await;
+                }
             }
 
             (
@@ -265,6 +270,7 @@ impl ToolEmitter {
                 },
                 stage,
             ) => {
+                let succeeded = is_successful_exec(&stage);
                 emit_exec_stage(
                     ctx,
                     ExecCommandInput::new(
@@ -278,6 +284,9 @@ impl ToolEmitter {
                     stage,
                 )
                 .await;
+                if succeeded {
+                    record_read_merge_bases(ctx, cwd, parsed_cmd).await;
+                }
             }
         }
     }
@@ -359,6 +368,32 @@ impl ToolEmitter {
     }
 }
 
+fn is_successful_exec(stage: &ToolEventStage) -> bool {
+    matches!(stage, ToolEventStage::Success(output) if output.exit_code == 0)
+}
+
+/// Records the files a successful command read as `apply_patch` merge bases, so a later patch
+/// written against what the model saw can still merge after the file changes underneath it.
+async fn record_read_merge_bases(
+    ctx: ToolEventCtx<'_>,
+    cwd: &AbsolutePathBuf,
+    parsed_cmd: &[ParsedCommand],
+) {
+    let Some(environment) = ctx.turn.environment.as_ref() else {
+        return;
+    };
+    let fs = environment.get_filesystem();
+    for parsed in parsed_cmd {
+        let ParsedCommand::Read { path, .. } = parsed else {
+            continue;
+        };
+        let path = cwd.join(path);
+        if let Ok(contents) = fs.read_file_text(&path, /*sandbox*/ None).await {
+            ctx.session.record_merge_base(&path, contents).await;
+        }
+    }
+}
+
 struct ExecCommandInput<'a> {
     command: &'a [String],
     cwd: &'a AbsolutePathBuf,
@@ -10,6 +10,8 @@ use crate::apply_patch;
 use crate::apply_patch::InternalApplyPatchInvocation;
 use crate::apply_patch::convert_apply_patch_to_protocol;
 use crate::function_tool::FunctionCallError;
+use crate::hook_runtime::run_post_apply_patch_hooks;
+use crate::hook_runtime::run_pre_apply_patch_hooks;
 use crate::session::session::Session;
 use crate::session::turn_context::TurnContext;
 use crate::tools::context::ApplyPatchToolOutput;
@@ -32,6 +34,7 @@ use crate::tools::registry::ToolKind;
 use crate::tools::runtimes::apply_patch::ApplyPatchRequest;
 use crate::tools::runtimes::apply_patch::ApplyPatchRuntime;
 use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::ToolError;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchArgs;
 use codex_apply_patch::ApplyPatchFileChange;
@@ -39,6 +42,9 @@ use codex_apply_patch::Hunk;
 use codex_apply_patch::parse_patch_streaming;
 use codex_exec_server::ExecutorFileSystem;
 use codex_features::Feature;
+use codex_protocol::error::CodexErr;
+use codex_protocol::error::SandboxErr;
+use codex_protocol::exec_output::ExecToolCallOutput;
 use codex_protocol::models::AdditionalPermissionProfile;
 use codex_protocol::models::FileSystemPermissions;
 use codex_protocol::protocol::EventMsg;
@@ -124,34 +130,67 @@ impl ApplyPatchArgumentDiffConsumer {
 }
 
 fn convert_apply_patch_hunks_to_protocol(hunks: &[Hunk]) -> HashMap<PathBuf, FileChange> {
-    hunks
-        .iter()
-        .map(|hunk| {
-            let path = hunk_source_path(hunk).to_path_buf();
-            let change = match hunk {
-                Hunk::AddFile { contents, .. } => FileChange::Add {
-                    content: contents.clone(),
-                },
-                Hunk::DeleteFile { .. } => FileChange::Delete {
-                    content: String::new(),
-                },
-                Hunk::UpdateFile {
-                    chunks, move_path, ..
-                } => FileChange::Update {
-                    unified_diff: format_update_chunks_for_progress(chunks),
-                    move_path: move_path.clone(),
-                },
-            };
-            (path, change)
-        })
-        .collect()
+    let mut changes = HashMap::new();
+    for hunk in hunks {
+        let path = hunk_source_path(hunk).to_path_buf();
+        let change = match hunk {
+            Hunk::AddFile { contents, .. } => FileChange::Add {
+                content: contents.clone(),
+            },
+            Hunk::AddBinaryFile { .. } => FileChange::Update {
+                unified_diff: "new file mode 100644\nBinary files differ\n".to_string(),
+                move_path: None,
+            },
+            Hunk::AddSymlink { target, .. } => FileChange::Update {
+                unified_diff: format!(
+                    "new file mode 120000\n@@ -0,0 +1 @@\n+{}\n",
+                    target.display()
+                ),
+                move_path: None,
+            },
+            Hunk::DeleteFile { .. } => FileChange::Delete {
+                content: String::new(),
+            },
+            Hunk::SetMode { mode, .. } => {
+                // Progress only surfaces a mode on its own when no other hunk writes the path.
+                let written = changes.contains_key(&path)
+                    || changes.values().any(|change| {
+                        matches!(
+                            change,
+                            FileChange::Update {
+                                move_path: Some(dest),
+                                ..
+                            } if *dest == path
+                        )
+                    });
+                if written {
+                    continue;
+                }
+                FileChange::Update {
+                    unified_diff: format!("new mode {:06o}\n", 0o100000 | mode),
+                    move_path: None,
+                }
+            }
+            Hunk::UpdateFile {
+                chunks, move_path, ..
+            } => FileChange::Update {
+                unified_diff: format_update_chunks_for_progress(chunks),
+                move_path: move_path.clone(),
+            },
+        };
+        changes.insert(path, change);
+    }
+    changes
 }
 
 fn hunk_source_path(hunk: &Hunk) -> &Path {
     match hunk {
-        Hunk::AddFile { path, .. } | Hunk::DeleteFile { path } | Hunk::UpdateFile { path, .. } => {
-            path
-        }
+        Hunk::AddFile { path, .. }
+        | Hunk::AddBinaryFile { path, .. }
+        | Hunk::AddSymlink { path, .. }
+        | Hunk::DeleteFile { path }
+        | Hunk::SetMode { path, .. }
+        | Hunk::UpdateFile { path, .. } => path,
     }
 }
 
@@ -258,6 +297,26 @@ fn apply_patch_payload_command(payload: &ToolPayload) -> Option<String> {
     }
 }
 
+/// Reads the patch text from an apply_patch hook `updatedInput`, which uses
+/// the same `{ "command": ... }` shape as the hook-facing tool input.
+fn apply_patch_hook_command(updated_input: &serde_json::Value) -> Result<String, String> {
+    let Some(updated_input) = updated_input.as_object() else {
+        return Err("invalid updatedInput for apply_patch: expected an object".to_string());
+    };
+    if let Some(key) = updated_input.keys().find(|key| key.as_str() != "command") {
+        return Err(format!(
+            "invalid updatedInput for apply_patch: unknown field `{key}`"
+        ));
+    }
+    updated_input
+        .get("command")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| {
+            "invalid updatedInput for apply_patch: `command` must be a string".to_string()
+        })
+}
+
 async fn effective_patch_permissions(
     session: &Session,
     turn: &TurnContext,
@@ -321,6 +380,27 @@ impl ToolHandler for ApplyPatchHandler {
         })
     }
 
+    fn rewrite_tool_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: &serde_json::Value,
+    ) -> Result<ToolPayload, String> {
+        let patch = apply_patch_hook_command(updated_input)?;
+        match &invocation.payload {
+            ToolPayload::Function { arguments } => {
+                let mut arguments: serde_json::Map<String, serde_json::Value> =
+                    serde_json::from_str(arguments)
+                        .map_err(|err| format!("failed to parse function arguments: {err}"))?;
+                arguments.insert("input".to_string(), serde_json::Value::String(patch));
+                serde_json::to_string(&arguments)
+                    .map(|arguments| ToolPayload::Function { arguments })
+                    .map_err(|err| err.to_string())
+            }
+            ToolPayload::Custom { .. } => Ok(ToolPayload::Custom { input: patch }),
+            _ => Err("unsupported payload for apply_patch handler".to_string()),
+        }
+    }
+
     fn post_tool_use_payload(
         &self,
         invocation: &ToolInvocation,
@@ -375,11 +455,17 @@ impl ToolHandler for ApplyPatchHandler {
         let sandbox = environment
             .is_remote()
             .then(|| turn.file_system_sandbox_context(/*additional_permissions*/ None));
-        match codex_apply_patch::maybe_parse_apply_patch_verified(
+        let merge_bases = session
+            .merge_bases_for(&codex_apply_patch::apply_patch_updated_paths(
+                &command, &cwd,
+            ))
+            .await;
+        match codex_apply_patch::maybe_parse_apply_patch_verified_with_merge_bases(
             &command,
             &cwd,
             fs.as_ref(),
             sandbox.as_ref(),
+            &merge_bases,
         )
         .await
         {
@@ -416,24 +502,13 @@ impl ToolHandler for ApplyPatchHandler {
                                 .permissions_preapproved,
                         };
 
-                        let mut orchestrator = ToolOrchestrator::new();
-                        let mut runtime = ApplyPatchRuntime::new();
                         let tool_ctx = ToolCtx {
                             session: session.clone(),
                             turn: turn.clone(),
                             call_id: call_id.clone(),
                             tool_name: tool_name.display(),
                         };
-                        let out = orchestrator
-                            .run(
-                                &mut runtime,
-                                &req,
-                                &tool_ctx,
-                                turn.as_ref(),
-                                turn.approval_policy.value(),
-                            )
-                            .await
-                            .map(|result| result.output);
+                        let out = run_apply_patch_with_hooks(&req, &tool_ctx).await;
                         let event_ctx = ToolEventCtx::new(
                             session.as_ref(),
                             turn.as_ref(),
@@ -465,6 +540,71 @@ impl ToolHandler for ApplyPatchHandler {
     }
 }
 
+/// Runs the patch through the orchestrator between the `PreApplyPatch` and `PostApplyPatch`
+/// hooks, so they fire once per tool call rather than once per sandbox attempt.
+async fn run_apply_patch_with_hooks(
+    req: &ApplyPatchRequest,
+    tool_ctx: &ToolCtx,
+) -> Result<ExecToolCallOutput, ToolError> {
+    let session = &tool_ctx.session;
+    let turn = &tool_ctx.turn;
+    let hook_files = req
+        .file_paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>();
+    let pre_hook_outcome = run_pre_apply_patch_hooks(
+        session,
+        turn,
+        tool_ctx.call_id.clone(),
+        req.action.patch.clone(),
+        hook_files.clone(),
+    )
+    .await;
+    if pre_hook_outcome.should_block {
+        return Err(ToolError::Rejected(
+            pre_hook_outcome
+                .block_reason
+                .unwrap_or_else(|| "apply_patch was blocked by a PreApplyPatch hook".to_string()),
+        ));
+    }
+
+    let mut orchestrator = ToolOrchestrator::new();
+    let mut runtime = ApplyPatchRuntime::new();
+    let out = orchestrator
+        .run(
+            &mut runtime,
+            req,
+            tool_ctx,
+            turn.as_ref(),
+            turn.approval_policy.value(),
+        )
+        .await
+        .map(|result| result.output);
+
+    // Only a patch that was actually written reaches the hooks; a declined approval does not.
+    let written = match &out {
+        Ok(output) => Some((output.exit_code == 0, output.aggregated_output.text.clone())),
+        Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output, .. }))) => {
+            Some((false, output.aggregated_output.text.clone()))
+        }
+        Err(_) => None,
+    };
+    if let Some((success, output)) = written {
+        run_post_apply_patch_hooks(
+            session,
+            turn,
+            tool_ctx.call_id.clone(),
+            req.action.patch.clone(),
+            hook_files,
+            success,
+            output,
+        )
+        .await;
+    }
+    out
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn intercept_apply_patch(
     command: &[String],
@@ -481,8 +621,17 @@ pub(crate) async fn intercept_apply_patch(
         .as_ref()
         .filter(|env| env.is_remote())
         .map(|_| turn.file_system_sandbox_context(/*additional_permissions*/ None));
-    match codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd, fs, sandbox.as_ref())
-        .await
+    let merge_bases = session
+        .merge_bases_for(&codex_apply_patch::apply_patch_updated_paths(command, cwd))
+        .await;
+    match codex_apply_patch::maybe_parse_apply_patch_verified_with_merge_bases(
+        command,
+        cwd,
+        fs,
+        sandbox.as_ref(),
+        &merge_bases,
+    )
+    .await
     {
         codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
             session
@@ -524,24 +673,13 @@ pub(crate) async fn intercept_apply_patch(
                             .permissions_preapproved,
                     };
 
-                    let mut orchestrator = ToolOrchestrator::new();
-                    let mut runtime = ApplyPatchRuntime::new();
                     let tool_ctx = ToolCtx {
                         session: session.clone(),
                         turn: turn.clone(),
                         call_id: call_id.to_string(),
                         tool_name: tool_name.to_string(),
                     };
-                    let out = orchestrator
-                        .run(
-                            &mut runtime,
-                            &req,
-                            &tool_ctx,
-                            turn.as_ref(),
-                            turn.approval_policy.value(),
-                        )
-                        .await
-                        .map(|result| result.output);
+                    let out = run_apply_patch_with_hooks(&req, &tool_ctx).await;
                     let event_ctx = ToolEventCtx::new(
                         session.as_ref(),
                         turn.as_ref(),
@@ -278,3 +278,44 @@ fn write_permissions_for_paths_keep_dirs_outside_workspace_root() {
         Some(vec![expected_outside])
     );
 }
+
+#[tokio::test]
+async fn rewrite_tool_input_replaces_json_and_freeform_patches() {
+    let rewritten_patch = "*** Begin Patch\n*** Add File: dry-run.txt\n+hello\n*** End Patch";
+    let handler = ApplyPatchHandler;
+
+    let invocation = invocation_for_payload(ToolPayload::Function {
+        arguments: json!({ "input": sample_patch() }).to_string(),
+    })
+    .await;
+    let Ok(ToolPayload::Function { arguments }) =
+        handler.rewrite_tool_input(&invocation, &json!({ "command": rewritten_patch }))
+    else {
+        panic!("expected rewritten function payload");
+    };
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&arguments).expect("rewritten arguments"),
+        json!({ "input": rewritten_patch })
+    );
+
+    let invocation = invocation_for_payload(ToolPayload::Custom {
+        input: sample_patch().to_string(),
+    })
+    .await;
+    let Ok(ToolPayload::Custom { input }) =
+        handler.rewrite_tool_input(&invocation, &json!({ "command": rewritten_patch }))
+    else {
+        panic!("expected rewritten freeform payload");
+    };
+    assert_eq!(input, rewritten_patch);
+
+    assert_eq!(
+        handler
+            .rewrite_tool_input(
+                &invocation,
+                &json!({ "command": rewritten_patch, "cwd": "/" })
+            )
+            .err(),
+        Some("invalid updatedInput for apply_patch: unknown field `cwd`".to_string())
+    );
+}
@@ -34,6 +34,22 @@ impl ToolHandler for McpHandler {
         })
     }
 
+    fn rewrite_tool_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: &Value,
+    ) -> Result<ToolPayload, String> {
+        let ToolPayload::Mcp { server, tool, .. } = &invocation.payload else {
+            return Err("mcp handler received unsupported payload".to_string());
+        };
+
+        Ok(ToolPayload::Mcp {
+            server: server.clone(),
+            tool: tool.clone(),
+            raw_arguments: updated_input.to_string(),
+        })
+    }
+
     fn post_tool_use_payload(
         &self,
         invocation: &ToolInvocation,
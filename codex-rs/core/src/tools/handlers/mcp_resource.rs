@@ -56,6 +56,12 @@ struct ReadResourceArgs {
     uri: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct ResourceSubscriptionArgs {
+    server: String,
+    uri: String,
+}
+
 #[derive(Debug, Serialize)]
 struct ResourceWithServer {
     server: String,
@@ -177,6 +183,13 @@ struct ReadResourcePayload {
     result: ReadResourceResult,
 }
 
+#[derive(Debug, Serialize)]
+struct ResourceSubscriptionPayload {
+    server: String,
+    uri: String,
+    subscribed: bool,
+}
+
 impl ToolHandler for McpResourceHandler {
     type Output = FunctionToolOutput;
 
@@ -233,6 +246,26 @@ impl ToolHandler for McpResourceHandler {
                 )
                 .await
             }
+            "subscribe_mcp_resource" => {
+                handle_resource_subscription(
+                    Arc::clone(&session),
+                    Arc::clone(&turn),
+                    call_id,
+                    arguments_value,
+                    /*subscribe*/ true,
+                )
+                .await
+            }
+            "unsubscribe_mcp_resource" => {
+                handle_resource_subscription(
+                    Arc::clone(&session),
+                    Arc::clone(&turn),
+                    call_id,
+                    arguments_value,
+                    /*subscribe*/ false,
+                )
+                .await
+            }
             other => Err(FunctionCallError::RespondToModel(format!(
                 "unsupported MCP resource tool: {other}"
             ))),
@@ -549,6 +582,78 @@ async fn handle_read_resource(
     }
 }
 
+async fn handle_resource_subscription(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    call_id: String,
+    arguments: Option<Value>,
+    subscribe: bool,
+) -> Result<FunctionToolOutput, FunctionCallError> {
+    let args: ResourceSubscriptionArgs = parse_args(arguments.clone())?;
+    let ResourceSubscriptionArgs { server, uri } = args;
+    let server = normalize_required_string("server", server)?;
+    let uri = normalize_required_string("uri", uri)?;
+    let tool = if subscribe {
+        "subscribe_mcp_resource"
+    } else {
+        "unsubscribe_mcp_resource"
+    };
+
+    let invocation = McpInvocation {
+        server: server.clone(),
+        tool: tool.to_string(),
+        arguments: arguments.clone(),
+    };
+
+    emit_tool_call_begin(&session, turn.as_ref(), &call_id, invocation.clone()).await;
+    let start = Instant::now();
+
+    let result = if subscribe {
+        session.subscribe_mcp_resource(&server, &uri).await
+    } else {
+        session.unsubscribe_mcp_resource(&server, &uri).await
+    };
+    let output = result
+        .map_err(|err| FunctionCallError::RespondToModel(format!("{tool} failed: {err:#}")))
+        .and_then(|()| {
+            serialize_function_output(ResourceSubscriptionPayload {
+                server,
+                uri,
+                subscribed: subscribe,
+            })
+        });
+
+    let duration = start.elapsed();
+    match output {
+        Ok(output) => {
+            let content =
+                function_call_output_content_items_to_text(&output.body).unwrap_or_default();
+            emit_tool_call_end(
+                &session,
+                turn.as_ref(),
+                &call_id,
+                invocation,
+                duration,
+                Ok(call_tool_result_from_content(&content, output.success)),
+            )
+            .await;
+            Ok(output)
+        }
+        Err(err) => {
+            emit_tool_call_end(
+                &session,
+                turn.as_ref(),
+                &call_id,
+                invocation,
+                duration,
+                Err(err.to_string()),
+            )
+            .await;
+            Err(err)
+        }
+    }
+}
+
 fn call_tool_result_from_content(content: &str, success: Option<bool>) -> CallToolResult {
     CallToolResult {
         content: vec![serde_json::json!({"type": "text", "text": content})],
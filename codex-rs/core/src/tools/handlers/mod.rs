@@ -86,6 +86,40 @@ fn resolve_workdir_base_path(
         .map_or_else(|| default_cwd.clone(), |workdir| default_cwd.join(workdir)))
 }
 
+/// Hook-facing input of shell-like tools after a `PreToolUse` hook rewrote it.
+///
+/// `cwd` is accepted in addition to the `command` exposed to hooks so a hook
+/// can pin the working directory; it maps onto the tool's `workdir` argument.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShellHookToolInput {
+    command: String,
+    #[serde(default)]
+    cwd: Option<String>,
+}
+
+fn parse_shell_hook_tool_input(updated_input: &Value) -> Result<ShellHookToolInput, String> {
+    ShellHookToolInput::deserialize(updated_input)
+        .map_err(|err| format!("invalid updatedInput for shell tool: {err}"))
+}
+
+/// Replaces `command_key` (and `workdir`, when `cwd` is set) in JSON function
+/// arguments, keeping every other argument the model supplied.
+fn rewrite_shell_function_arguments(
+    arguments: &str,
+    command_key: &str,
+    command: Value,
+    cwd: Option<String>,
+) -> Result<String, String> {
+    let mut arguments: serde_json::Map<String, Value> = serde_json::from_str(arguments)
+        .map_err(|err| format!("failed to parse function arguments: {err}"))?;
+    arguments.insert(command_key.to_string(), command);
+    if let Some(cwd) = cwd {
+        arguments.insert("workdir".to_string(), Value::String(cwd));
+    }
+    serde_json::to_string(&arguments).map_err(|err| err.to_string())
+}
+
 /// Validates feature/policy constraints for `with_additional_permissions` and
 /// normalizes any path-based permissions. Errors if the request is invalid.
 pub(crate) fn normalize_and_validate_additional_permissions(
@@ -6,6 +6,7 @@ use crate::agent::exceeds_thread_spawn_depth_limit;
 use crate::agent::next_thread_spawn_depth;
 use crate::agent::role::DEFAULT_ROLE_NAME;
 use crate::agent::role::apply_role_to_config;
+use crate::hook_runtime::run_subagent_start_hooks;
 use crate::session::turn_context::TurnEnvironment;
 
 pub(crate) struct Handler;
@@ -158,7 +159,7 @@ impl ToolHandler for Handler {
                     new_thread_id,
                     new_agent_nickname,
                     new_agent_role,
-                    prompt,
+                    prompt: prompt.clone(),
                     model: effective_model,
                     reasoning_effort: effective_reasoning_effort,
                     status,
@@ -173,6 +174,15 @@ impl ToolHandler for Handler {
             /*inc*/ 1,
             &[("role", role_tag)],
         );
+        run_subagent_start_hooks(
+            &session,
+            &turn,
+            new_thread_id,
+            role_tag.to_string(),
+            nickname.clone(),
+            prompt,
+        )
+        .await;
 
         Ok(SpawnAgentResult {
             agent_id: new_thread_id.to_string(),
@@ -5,6 +5,7 @@ use crate::agent::control::render_input_preview;
 use crate::agent::next_thread_spawn_depth;
 use crate::agent::role::DEFAULT_ROLE_NAME;
 use crate::agent::role::apply_role_to_config;
+use crate::hook_runtime::run_subagent_start_hooks;
 use crate::session::turn_context::TurnEnvironment;
 use codex_protocol::AgentPath;
 use codex_protocol::protocol::InterAgentCommunication;
@@ -184,7 +185,7 @@ impl ToolHandler for Handler {
                     new_thread_id,
                     new_agent_nickname,
                     new_agent_role,
-                    prompt,
+                    prompt: prompt.clone(),
                     model: effective_model,
                     reasoning_effort: effective_reasoning_effort,
                     status,
@@ -192,13 +193,22 @@ impl ToolHandler for Handler {
                 .into(),
             )
             .await;
-        let _ = result?;
+        let new_thread_id = result?.thread_id;
         let role_tag = role_name.unwrap_or(DEFAULT_ROLE_NAME);
         turn.session_telemetry.counter(
             "codex.multi_agent.spawn",
             /*inc*/ 1,
             &[("role", role_tag)],
         );
+        run_subagent_start_hooks(
+            &session,
+            &turn,
+            new_thread_id,
+            role_tag.to_string(),
+            nickname.clone(),
+            prompt,
+        )
+        .await;
         let task_name = new_agent_path.ok_or_else(|| {
             FunctionCallError::RespondToModel(
                 "spawned agent is missing a canonical task name".to_string(),
@@ -24,7 +24,9 @@ use crate::tools::handlers::implicit_granted_permissions;
 use crate::tools::handlers::normalize_and_validate_additional_permissions;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::handlers::parse_arguments_with_base_path;
+use crate::tools::handlers::parse_shell_hook_tool_input;
 use crate::tools::handlers::resolve_workdir_base_path;
+use crate::tools::handlers::rewrite_shell_function_arguments;
 use crate::tools::hook_names::HookToolName;
 use crate::tools::orchestrator::ToolOrchestrator;
 use crate::tools::registry::PostToolUsePayload;
@@ -212,6 +214,35 @@ impl ToolHandler for ShellHandler {
         })
     }
 
+    fn rewrite_tool_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: &JsonValue,
+    ) -> Result<ToolPayload, String> {
+        let rewrite = parse_shell_hook_tool_input(updated_input)?;
+        let command = shlex::split(&rewrite.command)
+            .filter(|command| !command.is_empty())
+            .ok_or_else(|| format!("could not split rewritten command: {}", rewrite.command))?;
+        match &invocation.payload {
+            ToolPayload::Function { arguments } => rewrite_shell_function_arguments(
+                arguments,
+                "command",
+                serde_json::json!(command),
+                rewrite.cwd,
+            )
+            .map(|arguments| ToolPayload::Function { arguments }),
+            ToolPayload::LocalShell { params } => {
+                let mut params = params.clone();
+                params.command = command;
+                if let Some(cwd) = rewrite.cwd {
+                    params.workdir = Some(cwd);
+                }
+                Ok(ToolPayload::LocalShell { params })
+            }
+            _ => Err("unsupported payload for shell handler".to_string()),
+        }
+    }
+
     fn post_tool_use_payload(
         &self,
         invocation: &ToolInvocation,
@@ -326,6 +357,24 @@ impl ToolHandler for ShellCommandHandler {
         })
     }
 
+    fn rewrite_tool_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: &JsonValue,
+    ) -> Result<ToolPayload, String> {
+        let ToolPayload::Function { arguments } = &invocation.payload else {
+            return Err("unsupported payload for shell_command handler".to_string());
+        };
+        let rewrite = parse_shell_hook_tool_input(updated_input)?;
+        rewrite_shell_function_arguments(
+            arguments,
+            "command",
+            JsonValue::String(rewrite.command),
+            rewrite.cwd,
+        )
+        .map(|arguments| ToolPayload::Function { arguments })
+    }
+
     fn post_tool_use_payload(
         &self,
         invocation: &ToolInvocation,
@@ -529,6 +578,7 @@ impl ShellHandler {
                     effective_additional_permissions.sandbox_permissions
                 },
                 prefix_rule,
+                thread_id: Some(session.conversation_id),
             })
             .await;
 
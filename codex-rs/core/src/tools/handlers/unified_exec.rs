@@ -13,7 +13,9 @@ use crate::tools::handlers::implicit_granted_permissions;
 use crate::tools::handlers::normalize_and_validate_additional_permissions;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::handlers::parse_arguments_with_base_path;
+use crate::tools::handlers::parse_shell_hook_tool_input;
 use crate::tools::handlers::resolve_workdir_base_path;
+use crate::tools::handlers::rewrite_shell_function_arguments;
 use crate::tools::hook_names::HookToolName;
 use crate::tools::registry::PostToolUsePayload;
 use crate::tools::registry::PreToolUsePayload;
@@ -152,6 +154,24 @@ impl ToolHandler for UnifiedExecHandler {
             })
     }
 
+    fn rewrite_tool_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: &serde_json::Value,
+    ) -> Result<ToolPayload, String> {
+        let ToolPayload::Function { arguments } = &invocation.payload else {
+            return Err("unified_exec handler received unsupported payload".to_string());
+        };
+        let rewrite = parse_shell_hook_tool_input(updated_input)?;
+        rewrite_shell_function_arguments(
+            arguments,
+            "cmd",
+            serde_json::Value::String(rewrite.command),
+            rewrite.cwd,
+        )
+        .map(|arguments| ToolPayload::Function { arguments })
+    }
+
     fn post_tool_use_payload(
         &self,
         invocation: &ToolInvocation,
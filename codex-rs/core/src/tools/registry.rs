@@ -28,8 +28,12 @@ use codex_hooks::HookToolKind;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::protocol::EventMsg;
 use codex_tools::ConfiguredToolSpec;
+use codex_tools::JsonSchema;
+use codex_tools::ResponsesApiNamespaceTool;
 use codex_tools::ToolName;
 use codex_tools::ToolSpec;
+use codex_tools::parse_tool_input_schema;
+use codex_tools::validate_json_value;
 use codex_utils_readiness::Readiness;
 use futures::future::BoxFuture;
 use serde_json::Value;
@@ -70,6 +74,23 @@ pub trait ToolHandler: Send + Sync {
         None
     }
 
+    /// Maps a `PreToolUse` hook's `updatedInput` back onto the invocation
+    /// payload.
+    ///
+    /// `updated_input` uses the same shape as [`PreToolUsePayload::tool_input`].
+    /// Handlers that expose a hook payload but cannot rewrite it keep this
+    /// default, which rejects the call rather than silently ignoring the hook.
+    fn rewrite_tool_input(
+        &self,
+        invocation: &ToolInvocation,
+        _updated_input: &Value,
+    ) -> Result<ToolPayload, String> {
+        Err(format!(
+            "tool {} does not support rewriting its input",
+            invocation.tool_name.display()
+        ))
+    }
+
     fn post_tool_use_payload(
         &self,
         _invocation: &ToolInvocation,
@@ -166,6 +187,12 @@ trait AnyToolHandler: Send + Sync {
 
     fn pre_tool_use_payload(&self, invocation: &ToolInvocation) -> Option<PreToolUsePayload>;
 
+    fn rewrite_tool_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: &Value,
+    ) -> Result<ToolPayload, String>;
+
     fn create_diff_consumer(&self) -> Option<Box<dyn ToolArgumentDiffConsumer>>;
     fn handle_any<'a>(
         &'a self,
@@ -189,6 +216,14 @@ where
         ToolHandler::pre_tool_use_payload(self, invocation)
     }
 
+    fn rewrite_tool_input(
+        &self,
+        invocation: &ToolInvocation,
+        updated_input: &Value,
+    ) -> Result<ToolPayload, String> {
+        ToolHandler::rewrite_tool_input(self, invocation, updated_input)
+    }
+
     fn create_diff_consumer(&self) -> Option<Box<dyn ToolArgumentDiffConsumer>> {
         ToolHandler::create_diff_consumer(self)
     }
@@ -214,11 +249,19 @@ where
 
 pub struct ToolRegistry {
     handlers: HashMap<ToolName, Arc<dyn AnyToolHandler>>,
+    /// Parameter schemas of JSON function tools, used to validate hook rewrites.
+    parameter_schemas: HashMap<ToolName, JsonSchema>,
+    /// Output schemas of JSON function tools, used to validate hook output rewrites.
+    output_schemas: HashMap<ToolName, JsonSchema>,
 }
 
 impl ToolRegistry {
     fn new(handlers: HashMap<ToolName, Arc<dyn AnyToolHandler>>) -> Self {
-        Self { handlers }
+        Self {
+            handlers,
+            parameter_schemas: HashMap::new(),
+            output_schemas: HashMap::new(),
+        }
     }
 
     #[cfg(test)]
@@ -250,6 +293,66 @@ impl ToolRegistry {
         self.handler(name)?.create_diff_consumer()
     }
 
+    /// Applies a hook-rewritten tool input and checks the resulting JSON
+    /// arguments against the tool's parameter schema, when one is known.
+    fn rewrite_payload(
+        &self,
+        handler: &dyn AnyToolHandler,
+        invocation: &ToolInvocation,
+        updated_input: &Value,
+    ) -> Result<ToolPayload, String> {
+        let payload = handler.rewrite_tool_input(invocation, updated_input)?;
+        let arguments = match &payload {
+            ToolPayload::Function { arguments } => Some(arguments.as_str()),
+            ToolPayload::Mcp { raw_arguments, .. } => Some(raw_arguments.as_str()),
+            ToolPayload::ToolSearch { .. }
+            | ToolPayload::Custom { .. }
+            | ToolPayload::LocalShell { .. } => None,
+        };
+        if let Some(arguments) = arguments
+            && let Some(schema) = self.parameter_schemas.get(&invocation.tool_name)
+        {
+            let arguments: Value = serde_json::from_str(arguments)
+                .map_err(|err| format!("rewritten arguments are not valid JSON: {err}"))?;
+            validate_json_value(schema, &arguments).map_err(|err| {
+                format!(
+                    "rewritten arguments do not match the {} schema: {err}",
+                    invocation.tool_name.display()
+                )
+            })?;
+        }
+        Ok(payload)
+    }
+
+    /// Checks a `PostToolUse` output rewrite against the tool's output schema,
+    /// when one is known. String rewrites holding JSON are checked as that JSON.
+    fn validate_updated_output(
+        &self,
+        tool_name: &ToolName,
+        updated_output: &Value,
+    ) -> Result<(), String> {
+        let Some(schema) = self.output_schemas.get(tool_name) else {
+            return Ok(());
+        };
+        let parsed;
+        let output = match updated_output {
+            Value::String(text) => match serde_json::from_str::<Value>(text) {
+                Ok(value) => {
+                    parsed = value;
+                    &parsed
+                }
+                Err(_) => updated_output,
+            },
+            other => other,
+        };
+        validate_json_value(schema, output).map_err(|err| {
+            format!(
+                "updated output does not match the {} output schema: {err}",
+                tool_name.display()
+            )
+        })
+    }
+
     // TODO(jif) for dynamic tools.
     // pub fn register(&mut self, name: impl Into<String>, handler: Arc<dyn ToolHandler>) {
     //     let name = name.into();
@@ -264,7 +367,7 @@ impl ToolRegistry {
     )]
     pub(crate) async fn dispatch_any(
         &self,
-        invocation: ToolInvocation,
+        mut invocation: ToolInvocation,
     ) -> Result<AnyToolResult, FunctionCallError> {
         let tool_name = invocation.tool_name.clone();
         let display_name = tool_name.display();
@@ -354,8 +457,8 @@ impl ToolRegistry {
             return Err(err);
         }
 
-        if let Some(pre_tool_use_payload) = handler.pre_tool_use_payload(&invocation)
-            && let Some(message) = run_pre_tool_use_hooks(
+        if let Some(pre_tool_use_payload) = handler.pre_tool_use_payload(&invocation) {
+            let rewritten_payload = match run_pre_tool_use_hooks(
                 &invocation.session,
                 &invocation.turn,
                 invocation.call_id.clone(),
@@ -363,10 +466,26 @@ impl ToolRegistry {
                 &pre_tool_use_payload.tool_input,
             )
             .await
-        {
-            let err = FunctionCallError::RespondToModel(message);
-            dispatch_trace.record_failed(&err);
-            return Err(err);
+            {
+                Ok(Some(updated_input)) => self
+                    .rewrite_payload(handler.as_ref(), &invocation, &updated_input)
+                    .map(Some)
+                    .map_err(|message| {
+                        FunctionCallError::RespondToModel(format!(
+                            "Tool call rejected: PreToolUse hook rewrote the input invalidly: {message}"
+                        ))
+                    }),
+                Ok(None) => Ok(None),
+                Err(message) => Err(FunctionCallError::RespondToModel(message)),
+            };
+            match rewritten_payload {
+                Ok(Some(payload)) => invocation.payload = payload,
+                Ok(None) => {}
+                Err(err) => {
+                    dispatch_trace.record_failed(&err);
+                    return Err(err);
+                }
+            }
         }
 
         let is_mutating = handler.is_mutating(&invocation).await;
@@ -468,7 +587,16 @@ impl ToolRegistry {
                         .unwrap_or_else(|| "PostToolUse hook stopped execution".to_string()),
                 )
             } else {
-                outcome.feedback_message.clone()
+                outcome.feedback_message.clone().or_else(|| {
+                    let updated_output = outcome.updated_output.as_ref()?;
+                    match self.validate_updated_output(&invocation.tool_name, updated_output) {
+                        Ok(()) => Some(updated_output_text(updated_output)),
+                        Err(err) => {
+                            warn!("rejecting PostToolUse updatedOutput: {err}");
+                            None
+                        }
+                    }
+                })
             };
             if let Some(replacement_text) = replacement_text {
                 let mut guard = response_cell.lock().await;
@@ -571,11 +699,85 @@ impl ToolRegistryBuilder {
     // }
 
     pub fn build(self) -> (Vec<ConfiguredToolSpec>, ToolRegistry) {
-        let registry = ToolRegistry::new(self.handlers);
+        let registry = ToolRegistry {
+            handlers: self.handlers,
+            parameter_schemas: tool_parameter_schemas(&self.specs),
+            output_schemas: tool_output_schemas(&self.specs),
+        };
         (self.specs, registry)
     }
 }
 
+fn tool_parameter_schemas(specs: &[ConfiguredToolSpec]) -> HashMap<ToolName, JsonSchema> {
+    let mut schemas = HashMap::new();
+    for config in specs {
+        match &config.spec {
+            ToolSpec::Function(tool) => {
+                schemas.insert(ToolName::plain(tool.name.clone()), tool.parameters.clone());
+            }
+            ToolSpec::Namespace(namespace) => {
+                for tool in &namespace.tools {
+                    let ResponsesApiNamespaceTool::Function(tool) = tool;
+                    schemas.insert(
+                        ToolName::namespaced(namespace.name.clone(), tool.name.clone()),
+                        tool.parameters.clone(),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    schemas
+}
+
+fn tool_output_schemas(specs: &[ConfiguredToolSpec]) -> HashMap<ToolName, JsonSchema> {
+    let mut schemas = HashMap::new();
+    let mut insert = |name: ToolName, output_schema: Option<&Value>| {
+        let Some(output_schema) = output_schema else {
+            return;
+        };
+        match parse_tool_input_schema(output_schema) {
+            Ok(schema) => {
+                schemas.insert(name, schema);
+            }
+            Err(err) => warn!(
+                "ignoring invalid output schema for {}: {err}",
+                name.display()
+            ),
+        }
+    };
+    for config in specs {
+        match &config.spec {
+            ToolSpec::Function(tool) => {
+                insert(
+                    ToolName::plain(tool.name.clone()),
+                    tool.output_schema.as_ref(),
+                );
+            }
+            ToolSpec::Namespace(namespace) => {
+                for tool in &namespace.tools {
+                    let ResponsesApiNamespaceTool::Function(tool) = tool;
+                    insert(
+                        ToolName::namespaced(namespace.name.clone(), tool.name.clone()),
+                        tool.output_schema.as_ref(),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    schemas
+}
+
+/// Text shown to the model in place of the tool output after a `PostToolUse`
+/// hook rewrote it. Strings are used verbatim; other JSON is serialized.
+fn updated_output_text(updated_output: &Value) -> String {
+    match updated_output {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
 fn unsupported_tool_call_message(payload: &ToolPayload, tool_name: &ToolName) -> String {
     let tool_name = tool_name.display();
     match payload {
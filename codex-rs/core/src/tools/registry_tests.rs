@@ -53,3 +53,138 @@ fn handler_looks_up_namespaced_aliases_explicitly() {
             .is_some_and(|handler| Arc::ptr_eq(handler, &namespaced_handler))
     );
 }
+
+struct RewritingHandler;
+
+impl ToolHandler for RewritingHandler {
+    type Output = crate::tools::context::FunctionToolOutput;
+
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    fn rewrite_tool_input(
+        &self,
+        _invocation: &ToolInvocation,
+        updated_input: &Value,
+    ) -> Result<ToolPayload, String> {
+        Ok(ToolPayload::Function {
+            arguments: updated_input.to_string(),
+        })
+    }
+
+    async fn handle(&self, _invocation: ToolInvocation) -> Result<Self::Output, FunctionCallError> {
+        Ok(crate::tools::context::FunctionToolOutput::from_text(
+            "ok".to_string(),
+            Some(true),
+        ))
+    }
+}
+
+#[tokio::test]
+async fn rewrite_payload_validates_against_tool_parameter_schema() {
+    let tool_name = codex_tools::ToolName::plain("shell_command");
+    let schema = codex_tools::parse_tool_input_schema(&serde_json::json!({
+        "type": "object",
+        "properties": {
+            "command": {"type": "string"},
+            "workdir": {"type": "string"}
+        },
+        "required": ["command"],
+        "additionalProperties": false
+    }))
+    .expect("parse schema");
+    let handler = Arc::new(RewritingHandler) as Arc<dyn AnyToolHandler>;
+    let registry = ToolRegistry {
+        handlers: HashMap::from([(tool_name.clone(), Arc::clone(&handler))]),
+        parameter_schemas: HashMap::from([(tool_name.clone(), schema)]),
+        output_schemas: HashMap::new(),
+    };
+    let (session, turn) = crate::session::tests::make_session_and_context().await;
+    let invocation = ToolInvocation {
+        session: session.into(),
+        turn: turn.into(),
+        cancellation_token: tokio_util::sync::CancellationToken::new(),
+        tracker: Arc::new(tokio::sync::Mutex::new(
+            crate::turn_diff_tracker::TurnDiffTracker::new(),
+        )),
+        call_id: "call-1".to_string(),
+        tool_name,
+        source: crate::tools::context::ToolCallSource::Direct,
+        payload: ToolPayload::Function {
+            arguments: serde_json::json!({"command": "cargo publish"}).to_string(),
+        },
+    };
+
+    let rewritten = registry
+        .rewrite_payload(
+            handler.as_ref(),
+            &invocation,
+            &serde_json::json!({"command": "cargo publish --dry-run", "workdir": "/repo"}),
+        )
+        .expect("valid rewrite");
+    let ToolPayload::Function { arguments } = rewritten else {
+        panic!("expected function payload");
+    };
+    assert_eq!(
+        serde_json::from_str::<Value>(&arguments).expect("rewritten arguments"),
+        serde_json::json!({"command": "cargo publish --dry-run", "workdir": "/repo"})
+    );
+
+    let Err(message) = registry.rewrite_payload(
+        handler.as_ref(),
+        &invocation,
+        &serde_json::json!({"command": ["cargo", "publish"]}),
+    ) else {
+        panic!("expected schema validation to reject the rewrite");
+    };
+    assert_eq!(
+        message,
+        "rewritten arguments do not match the shell_command schema: $.command must be of type string"
+    );
+}
+
+#[test]
+fn updated_output_validates_against_tool_output_schema() {
+    let tool_name = codex_tools::ToolName::namespaced("mcp__docs__", "search");
+    let schema = codex_tools::parse_tool_input_schema(&serde_json::json!({
+        "type": "object",
+        "properties": {
+            "content": {"type": "array", "items": {"type": "object"}}
+        },
+        "required": ["content"]
+    }))
+    .expect("parse schema");
+    let registry = ToolRegistry {
+        handlers: HashMap::new(),
+        parameter_schemas: HashMap::new(),
+        output_schemas: HashMap::from([(tool_name.clone(), schema)]),
+    };
+
+    assert_eq!(
+        registry.validate_updated_output(
+            &tool_name,
+            &serde_json::json!({"content": [{"type": "text", "text": "redacted"}]}),
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        registry
+            .validate_updated_output(&tool_name, &Value::String(r#"{"content": []}"#.to_string()),),
+        Ok(())
+    );
+    assert_eq!(
+        registry.validate_updated_output(&tool_name, &Value::String("redacted".to_string())),
+        Err(
+            "updated output does not match the mcp__docs__search output schema: $ must be of type object"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        registry.validate_updated_output(
+            &codex_tools::ToolName::plain("shell_command"),
+            &Value::String("redacted".to_string()),
+        ),
+        Ok(())
+    );
+}
@@ -18,6 +18,7 @@ use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::with_cached_approval;
 use codex_apply_patch::ApplyPatchAction;
+use codex_exec_server::ExecutorFileSystem;
 use codex_exec_server::FileSystemSandboxContext;
 use codex_protocol::error::CodexErr;
 use codex_protocol::error::SandboxErr;
@@ -37,7 +38,9 @@ use codex_sandboxing::policy_transforms::effective_permission_profile;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use futures::future::BoxFuture;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Debug)]
 pub struct ApplyPatchRequest {
@@ -217,17 +220,43 @@ impl ToolRuntime<ApplyPatchRequest, ExecToolCallOutput> for ApplyPatchRuntime {
         let started_at = Instant::now();
         let fs = environment.get_filesystem();
         let sandbox = Self::file_system_sandbox_context_for_attempt(req, attempt);
-        let mut stdout = Vec::new();
-        let mut stderr = Vec::new();
-        let result = codex_apply_patch::apply_patch(
-            &req.action.patch,
-            &req.action.cwd,
-            &mut stdout,
-            &mut stderr,
-            fs.as_ref(),
-            sandbox.as_ref(),
-        )
-        .await;
+        let patch = req.action.patch.clone();
+        let cwd = req.action.cwd.clone();
+        let merge_bases = ctx.session.merge_bases_for(&req.file_paths).await;
+        // Run the patch as its own task so that interrupting the turn cannot drop it halfway
+        // through writing files, which would skip the rollback of a failed patch. Dropping this
+        // future when the turn is interrupted cancels the task, which then rolls the patch back.
+        let interrupt = CancellationToken::new();
+        let _interrupt_on_drop = interrupt.clone().drop_guard();
+        let (result, stdout, stderr) = tokio::spawn({
+            let fs = Arc::clone(&fs);
+            async move {
+                let mut stdout = Vec::new();
+                let mut stderr = Vec::new();
+                let result = codex_apply_patch::apply_patch_with_merge_bases(
+                    &patch,
+                    &cwd,
+                    &mut stdout,
+                    &mut stderr,
+                    fs.as_ref(),
+                    sandbox.as_ref(),
+                    &merge_bases,
+                    &interrupt,
+                )
+                .await;
+                (result, stdout, stderr)
+            }
+        })
+        .await
+        .map_err(|err| ToolError::Rejected(format!("apply_patch task failed: {err}")))?;
+        if result.is_ok() {
+            // The model knows what it just wrote, so later patches merge against these contents.
+            for path in &req.file_paths {
+                if let Ok(contents) = fs.read_file_text(path, /*sandbox*/ None).await {
+                    ctx.session.record_merge_base(path, contents).await;
+                }
+            }
+        }
         let stdout = String::from_utf8_lossy(&stdout).into_owned();
         let stderr = String::from_utf8_lossy(&stderr).into_owned();
         Self::emit_output_delta(ctx, ExecOutputStream::Stdout, stdout.as_bytes()).await;
@@ -22,11 +22,11 @@ use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::managed_network_for_sandbox_permissions;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Evaluation;
-use codex_execpolicy::MatchOptions;
 use codex_execpolicy::Policy;
 use codex_execpolicy::RuleMatch;
 use codex_features::Feature;
 use codex_hooks::PermissionRequestDecision;
+use codex_protocol::ThreadId;
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::error::CodexErr;
 use codex_protocol::error::SandboxErr;
@@ -600,6 +600,7 @@ impl EscalationPolicy for CoreShellActionProvider {
                     sandbox_permissions: self.approval_sandbox_permissions,
                     enable_shell_wrapper_parsing:
                         ENABLE_INTERCEPTED_EXEC_POLICY_SHELL_WRAPPER_PARSING,
+                    thread_id: Some(self.session.conversation_id),
                 },
             )
         };
@@ -650,6 +651,7 @@ fn evaluate_intercepted_exec_policy(
         sandbox_cwd,
         sandbox_permissions,
         enable_shell_wrapper_parsing,
+        thread_id,
     } = context;
     let CandidateCommands {
         commands,
@@ -683,9 +685,11 @@ fn evaluate_intercepted_exec_policy(
     policy.check_multiple_with_options(
         commands.iter(),
         &fallback,
-        &MatchOptions {
-            resolve_host_executables: true,
-        },
+        &crate::exec_policy::match_options_for_sandbox(
+            file_system_sandbox_policy,
+            sandbox_cwd,
+            thread_id,
+        ),
     )
 }
 
@@ -697,6 +701,7 @@ struct InterceptedExecPolicyContext<'a> {
     sandbox_cwd: &'a Path,
     sandbox_permissions: SandboxPermissions,
     enable_shell_wrapper_parsing: bool,
+    thread_id: Option<ThreadId>,
 }
 
 struct CandidateCommands {
@@ -16,6 +16,7 @@ use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
 use codex_hooks::Hooks;
 use codex_hooks::HooksConfig;
+use codex_protocol::ThreadId;
 use codex_protocol::models::AdditionalPermissionProfile;
 use codex_protocol::models::FileSystemPermissions;
 use codex_protocol::models::PermissionProfile;
@@ -36,6 +37,7 @@ use codex_shell_escalation::ExecResult;
 use codex_shell_escalation::ResolvedPermissionProfile;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use pretty_assertions::assert_eq;
+use pretty_assertions::assert_ne;
 use serde_json::Value;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -478,6 +480,7 @@ fn evaluate_intercepted_exec_policy_uses_wrapper_command_when_shell_wrapper_pars
             sandbox_cwd: sandbox_cwd.as_path(),
             sandbox_permissions: SandboxPermissions::UseDefault,
             enable_shell_wrapper_parsing: enable_intercepted_exec_policy_shell_wrapper_parsing,
+            thread_id: None,
         },
     );
 
@@ -533,6 +536,7 @@ fn evaluate_intercepted_exec_policy_matches_inner_shell_commands_when_enabled()
             sandbox_cwd: sandbox_cwd.as_path(),
             sandbox_permissions: SandboxPermissions::UseDefault,
             enable_shell_wrapper_parsing: enable_intercepted_exec_policy_shell_wrapper_parsing,
+            thread_id: None,
         },
     );
 
@@ -579,6 +583,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
             sandbox_cwd: sandbox_cwd.as_path(),
             sandbox_permissions: SandboxPermissions::UseDefault,
             enable_shell_wrapper_parsing: false,
+            thread_id: None,
         },
     );
 
@@ -624,6 +629,7 @@ fn intercepted_exec_policy_treats_preapproved_additional_permissions_as_default(
                 /*additional_permissions_preapproved*/ true,
             ),
             enable_shell_wrapper_parsing: false,
+            thread_id: None,
         },
     );
     let fresh_request = evaluate_intercepted_exec_policy(
@@ -637,6 +643,7 @@ fn intercepted_exec_policy_treats_preapproved_additional_permissions_as_default(
             sandbox_cwd: sandbox_cwd.as_path(),
             sandbox_permissions: SandboxPermissions::WithAdditionalPermissions,
             enable_shell_wrapper_parsing: false,
+            thread_id: None,
         },
     );
 
@@ -644,6 +651,43 @@ fn intercepted_exec_policy_treats_preapproved_additional_permissions_as_default(
     assert_eq!(fresh_request.decision, Decision::Prompt);
 }
 
+#[test]
+fn intercepted_exec_policy_applies_rules_scoped_to_the_thread() {
+    let thread_id = ThreadId::new();
+    let mut parser = PolicyParser::new();
+    let policy_src = format!(
+        r#"prefix_rule(pattern = ["rm"], decision = "forbidden", thread_id = "{thread_id}")"#
+    );
+    parser.parse("test.rules", &policy_src).unwrap();
+    let policy = parser.build();
+    let program = AbsolutePathBuf::try_from(host_absolute_path(&["bin", "rm"])).unwrap();
+    let argv = ["rm".to_string(), "-rf".to_string(), "build".to_string()];
+    let file_system_sandbox_policy = read_only_file_system_sandbox_policy();
+    let sandbox_cwd = test_sandbox_cwd();
+    let decision_for = |thread_id| {
+        evaluate_intercepted_exec_policy(
+            &policy,
+            &program,
+            &argv,
+            InterceptedExecPolicyContext {
+                approval_policy: AskForApproval::OnRequest,
+                permission_profile: permission_profile_from_sandbox_policy(
+                    &SandboxPolicy::new_read_only_policy(),
+                ),
+                file_system_sandbox_policy: &file_system_sandbox_policy,
+                sandbox_cwd: sandbox_cwd.as_path(),
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                enable_shell_wrapper_parsing: false,
+                thread_id,
+            },
+        )
+        .decision
+    };
+
+    assert_eq!(decision_for(Some(thread_id)), Decision::Forbidden);
+    assert_ne!(decision_for(Some(ThreadId::new())), Decision::Forbidden);
+}
+
 #[test]
 fn intercepted_exec_policy_rejects_disallowed_host_executable_mapping() {
     let allowed_git = host_absolute_path(&["usr", "bin", "git"]);
@@ -674,6 +718,7 @@ host_executable(name = "git", paths = ["{allowed_git_literal}"])
             sandbox_cwd: sandbox_cwd.as_path(),
             sandbox_permissions: SandboxPermissions::UseDefault,
             enable_shell_wrapper_parsing: false,
+            thread_id: None,
         },
     );
 
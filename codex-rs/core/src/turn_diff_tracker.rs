@@ -62,7 +62,8 @@ impl TurnDiffTracker {
                     .insert(internal.clone(), path.clone());
 
                 // If the file exists on disk now, snapshot as baseline; else leave missing to represent /dev/null.
-                let baseline_file_info = if path.exists() {
+                // Dangling symlinks count as existing.
+                let baseline_file_info = if path.symlink_metadata().is_ok() {
                     let mode = file_mode_for_path(path);
                     let mode_val = mode.unwrap_or(FileMode::Regular);
                     let content = blob_bytes(path, mode_val).unwrap_or_default();
@@ -294,8 +295,14 @@ impl TurnDiffTracker {
             None
         };
 
-        // Fast path: identical bytes or both missing.
+        // Fast path: identical bytes or both missing. A file whose contents are unchanged may
+        // still have changed mode.
         if left_bytes == right_bytes.as_deref() {
+            if left_bytes.is_some() && baseline_mode != current_mode {
+                aggregated.push_str(&format!("diff --git a/{left_display} b/{right_display}\n"));
+                aggregated.push_str(&format!("old mode {baseline_mode}\n"));
+                aggregated.push_str(&format!("new mode {current_mode}\n"));
+            }
             return aggregated;
         }
 
@@ -428,7 +435,7 @@ fn file_mode_for_path(_path: &Path) -> Option<FileMode> {
 }
 
 fn blob_bytes(path: &Path, mode: FileMode) -> Option<Vec<u8>> {
-    if path.exists() {
+    if path.symlink_metadata().is_ok() {
         let contents = if mode == FileMode::Symlink {
             symlink_blob_bytes(path)
                 .ok_or_else(|| anyhow!("failed to read symlink target for {}", path.display()))
@@ -139,6 +139,35 @@ index {left_oid}..{ZERO_OID}
     assert_eq!(diff, expected);
 }
 
+#[cfg(unix)]
+#[test]
+fn mode_only_change_reports_old_and_new_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("run.sh");
+    fs::write(&file, "echo hi\n").unwrap();
+    fs::set_permissions(&file, fs::Permissions::from_mode(0o644)).unwrap();
+
+    let mut acc = TurnDiffTracker::new();
+    let changes = HashMap::from([(
+        file.clone(),
+        FileChange::Update {
+            unified_diff: "new mode 100755\n".to_string(),
+            move_path: None,
+        },
+    )]);
+    acc.on_patch_begin(&changes);
+
+    fs::set_permissions(&file, fs::Permissions::from_mode(0o755)).unwrap();
+    let diff = acc.get_unified_diff().unwrap().unwrap();
+    let diff = normalize_diff_for_test(&diff, dir.path());
+    assert_eq!(
+        diff,
+        "diff --git a/<TMP>/run.sh b/<TMP>/run.sh\nold mode 100644\nnew mode 100755\n"
+    );
+}
+
 #[test]
 fn accumulates_move_and_update() {
     let dir = tempdir().unwrap();
@@ -806,6 +806,7 @@ impl UnifiedExecProcessManager {
                     request.sandbox_permissions
                 },
                 prefix_rule: request.prefix_rule.clone(),
+                thread_id: Some(context.session.conversation_id),
             })
             .await;
         let req = UnifiedExecToolRequest {
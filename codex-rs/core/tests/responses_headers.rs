@@ -66,6 +66,7 @@ async fn responses_stream_includes_subagent_header_on_review() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         websocket_connect_timeout_ms: None,
+        max_output_tokens: None,
         requires_openai_auth: false,
         supports_websockets: false,
     };
@@ -191,6 +192,7 @@ async fn responses_stream_includes_subagent_header_on_other() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         websocket_connect_timeout_ms: None,
+        max_output_tokens: None,
         requires_openai_auth: false,
         supports_websockets: false,
     };
@@ -301,6 +303,7 @@ async fn responses_respects_model_info_overrides_from_config() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         websocket_connect_timeout_ms: None,
+        max_output_tokens: None,
         requires_openai_auth: false,
         supports_websockets: false,
     };
@@ -490,6 +490,66 @@ async fn apply_patch_cli_reports_missing_context(
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn apply_patch_merges_with_file_changed_since_last_read() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = apply_patch_harness_with(|builder| {
+        builder.with_config(|config| {
+            config
+                .features
+                .enable(Feature::ApplyPatchThreeWayMerge)
+                .expect("enable apply_patch three-way merge");
+        })
+    })
+    .await?;
+    harness.write_file("merge.txt", "a\nb\nc\nd\ne\n").await?;
+
+    // The model reads the file, something else edits its last line, and the model then sends a
+    // patch whose trailing context still matches what it read.
+    let patch =
+        "*** Begin Patch\n*** Update File: merge.txt\n@@\n a\n-b\n+B\n c\n d\n e\n*** End Patch";
+    let call_id = "apply-merge";
+    mount_sse_sequence(
+        harness.server(),
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_shell_command_call("read-merge", "cat merge.txt"),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_shell_command_call("edit-merge", "printf 'a\\nb\\nc\\nd\\nE\\n' > merge.txt"),
+                ev_completed("resp-2"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-3"),
+                ev_apply_patch_function_call(call_id, patch),
+                ev_completed("resp-3"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "ok"),
+                ev_completed("resp-4"),
+            ]),
+        ],
+    )
+    .await;
+
+    harness.submit("merge a patch into a drifted file").await?;
+
+    let out = harness.function_call_stdout(call_id).await;
+    assert!(
+        out.contains("merged with their current contents"),
+        "expected merge summary, got: {out}"
+    );
+    assert_eq!(
+        harness.read_file_text("merge.txt").await?,
+        "a\nB\nc\nd\nE\n"
+    );
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 #[test_case(ApplyPatchModelOutput::Freeform)]
 #[test_case(ApplyPatchModelOutput::Function)]
@@ -21,6 +21,7 @@ use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ExecApprovalRequestEvent;
 use codex_protocol::protocol::ExecPolicyAmendment;
+use codex_protocol::protocol::ExecPolicyAmendmentScope;
 use codex_protocol::protocol::Op;
 use codex_protocol::protocol::ReviewDecision;
 use codex_protocol::protocol::SandboxPolicy;
@@ -2028,6 +2029,7 @@ async fn approving_execpolicy_amendment_persists_policy_and_skips_future_prompts
             turn_id: None,
             decision: ReviewDecision::ApprovedExecpolicyAmendment {
                 proposed_execpolicy_amendment: expected_execpolicy_amendment.clone(),
+                scope: ExecPolicyAmendmentScope::Permanent,
             },
         })
         .await?;
@@ -2269,6 +2271,7 @@ async fn spawned_subagent_execpolicy_amendment_propagates_to_parent_session() ->
             turn_id: None,
             decision: ReviewDecision::ApprovedExecpolicyAmendment {
                 proposed_execpolicy_amendment: expected_execpolicy_amendment,
+                scope: ExecPolicyAmendmentScope::Permanent,
             },
         })
         .await?;
@@ -2503,6 +2506,7 @@ async fn approving_fallback_rule_for_compound_command_works() -> Result<()> {
             turn_id: None,
             decision: ReviewDecision::ApprovedExecpolicyAmendment {
                 proposed_execpolicy_amendment: amendment.clone(),
+                scope: ExecPolicyAmendmentScope::Permanent,
             },
         })
         .await?;
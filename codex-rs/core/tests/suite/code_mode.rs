@@ -240,6 +240,7 @@ async fn run_code_mode_turn_with_rmcp_config(
                 startup_timeout_sec: Some(Duration::from_secs(10)),
                 tool_timeout_sec: None,
                 default_tools_approval_mode: None,
+                sampling_approval_mode: None,
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
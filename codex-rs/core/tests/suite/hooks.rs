@@ -100,6 +100,22 @@ else:
     Ok(())
 }
 
+fn write_prompt_stop_hook(home: &Path, prompt: &str) -> Result<()> {
+    let hooks = serde_json::json!({
+        "hooks": {
+            "Stop": [{
+                "hooks": [{
+                    "type": "prompt",
+                    "prompt": prompt,
+                    "statusMessage": "reviewing the turn",
+                }]
+            }]
+        }
+    });
+    fs::write(home.join("hooks.json"), hooks.to_string()).context("write hooks.json")?;
+    Ok(())
+}
+
 fn write_parallel_stop_hooks(home: &Path, prompts: &[&str]) -> Result<()> {
     let hook_entries = prompts
         .iter()
@@ -571,6 +587,40 @@ with Path(r"{log_path}").open("a", encoding="utf-8") as handle:
     Ok(())
 }
 
+fn write_compact_and_turn_error_hooks(home: &Path) -> Result<()> {
+    let script_path = home.join("lifecycle_hook.py");
+    let log_path = home.join("lifecycle_hook_log.jsonl");
+    let script = format!(
+        r#"import json
+from pathlib import Path
+import sys
+
+payload = json.load(sys.stdin)
+
+with Path(r"{log_path}").open("a", encoding="utf-8") as handle:
+    handle.write(json.dumps(payload) + "\n")
+"#,
+        log_path = log_path.display(),
+    );
+    let group = serde_json::json!({
+        "hooks": [{
+            "type": "command",
+            "command": format!("python3 {}", script_path.display()),
+        }]
+    });
+    let hooks = serde_json::json!({
+        "hooks": {
+            "PreCompact": [group.clone()],
+            "PostCompact": [group.clone()],
+            "TurnError": [group],
+        }
+    });
+
+    fs::write(&script_path, script).context("write lifecycle hook script")?;
+    fs::write(home.join("hooks.json"), hooks.to_string()).context("write hooks.json")?;
+    Ok(())
+}
+
 fn rollout_hook_prompt_texts(text: &str) -> Result<Vec<String>> {
     let mut texts = Vec::new();
     for line in text.lines() {
@@ -846,6 +896,85 @@ async fn stop_hook_can_block_multiple_times_in_same_turn() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn prompt_stop_hook_asks_model_and_continues_on_block() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    const JUDGE_PROMPT: &str = "Did the agent actually run the tests? $ARGUMENTS";
+    const BLOCK_REASON: &str = "Run the tests before finishing.";
+
+    let server = start_mock_server().await;
+    let block_decision =
+        serde_json::json!({"decision": "block", "reason": BLOCK_REASON}).to_string();
+    let allow_decision =
+        serde_json::json!({"decision": "allow", "reason": "tests ran"}).to_string();
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_assistant_message("msg-1", "done without tests"),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-judge-1"),
+                ev_assistant_message("msg-judge-1", &block_decision),
+                ev_completed("resp-judge-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", "tests pass"),
+                ev_completed("resp-2"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-judge-2"),
+                ev_assistant_message("msg-judge-2", &allow_decision),
+                ev_completed("resp-judge-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let mut builder = test_codex()
+        .with_pre_build_hook(|home| {
+            if let Err(error) = write_prompt_stop_hook(home, JUDGE_PROMPT) {
+                panic!("failed to write prompt stop hook test fixture: {error}");
+            }
+        })
+        .with_config(|config| {
+            config
+                .features
+                .enable(Feature::CodexHooks)
+                .expect("test config should allow feature update");
+        });
+    let test = builder.build(&server).await?;
+
+    test.submit_turn("finish the feature").await?;
+
+    let requests = responses.requests();
+    assert_eq!(requests.len(), 4);
+    for judge_request in [&requests[1], &requests[3]] {
+        let judge_input = judge_request.message_input_texts("user");
+        assert_eq!(judge_input.len(), 1);
+        assert!(
+            judge_input[0].starts_with("Did the agent actually run the tests? {"),
+            "judge request should substitute the hook input: {judge_input:?}",
+        );
+        assert!(judge_input[0].contains("\"hook_event_name\":\"Stop\""));
+        assert_eq!(
+            judge_request.body_json()["text"]["format"]["schema"]["required"],
+            serde_json::json!(["decision", "reason"]),
+        );
+    }
+    assert_eq!(
+        request_hook_prompt_texts(&requests[2]),
+        vec![BLOCK_REASON.to_string()],
+        "the block reason should be sent back to the agent as a continuation prompt",
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn session_start_hook_sees_materialized_transcript_path() -> Result<()> {
     skip_if_no_network!(Ok(()));
@@ -891,6 +1020,112 @@ async fn session_start_hook_sees_materialized_transcript_path() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn compact_and_turn_error_hooks_receive_their_inputs() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let _responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_assistant_message("msg-1", "first reply"),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", "summary"),
+                ev_completed("resp-2"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-3"),
+                serde_json::json!({
+                    "type": "response.incomplete",
+                    "response": {
+                        "id": "resp-3",
+                        "object": "response",
+                        "status": "incomplete",
+                        "error": null,
+                        "incomplete_details": {
+                            "reason": "content_filter"
+                        }
+                    }
+                }),
+            ]),
+        ],
+    )
+    .await;
+
+    let mut builder = test_codex()
+        .with_pre_build_hook(|home| {
+            if let Err(error) = write_compact_and_turn_error_hooks(home) {
+                panic!("failed to write compact and turn error hooks: {error}");
+            }
+        })
+        .with_config(|config| {
+            // Use local compaction so the summary comes from the mocked stream.
+            config.model_provider.name = "OpenAI (test)".into();
+            config.model_provider.stream_max_retries = Some(0);
+            config
+                .features
+                .enable(Feature::CodexHooks)
+                .expect("test config should allow feature update");
+        });
+    let test = builder.build(&server).await?;
+
+    test.submit_turn("hello").await?;
+
+    test.codex.submit(Op::Compact).await?;
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await;
+
+    test.codex
+        .submit(Op::UserInput {
+            environments: None,
+            items: vec![UserInput::Text {
+                text: "trigger incomplete".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            responsesapi_client_metadata: None,
+        })
+        .await?;
+    wait_for_event(&test.codex, |event| matches!(event, EventMsg::Error(_))).await;
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TurnComplete(_))
+    })
+    .await;
+
+    let hook_inputs = read_hook_inputs_from_log(
+        test.codex_home_path()
+            .join("lifecycle_hook_log.jsonl")
+            .as_path(),
+    )?;
+    assert_eq!(
+        hook_inputs
+            .iter()
+            .map(|input| input["hook_event_name"].clone())
+            .collect::<Vec<_>>(),
+        vec![
+            Value::from("PreCompact"),
+            Value::from("PostCompact"),
+            Value::from("TurnError"),
+        ]
+    );
+    assert_eq!(hook_inputs[0]["trigger"], "manual");
+    assert_eq!(hook_inputs[1]["trigger"], "manual");
+    assert_eq!(hook_inputs[1]["success"], true);
+    assert_eq!(
+        hook_inputs[2]["error"],
+        "stream disconnected before completion: Incomplete response returned, reason: content_filter"
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn resumed_thread_keeps_stop_continuation_prompt_in_history() -> Result<()> {
     skip_if_no_network!(Ok(()));
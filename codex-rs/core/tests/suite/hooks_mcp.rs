@@ -146,6 +146,7 @@ fn insert_rmcp_test_server(config: &mut Config, command: String, approval_mode:
             startup_timeout_sec: Some(Duration::from_secs(10)),
             tool_timeout_sec: None,
             default_tools_approval_mode: Some(approval_mode),
+            sampling_approval_mode: None,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
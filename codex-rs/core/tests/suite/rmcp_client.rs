@@ -17,6 +17,7 @@ use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
+use codex_config::types::McpSamplingApproval;
 use codex_config::types::McpServerConfig;
 use codex_config::types::McpServerEnvVar;
 use codex_config::types::McpServerTransportConfig;
@@ -240,6 +241,7 @@ struct TestMcpServerOptions {
     experimental_environment: Option<String>,
     supports_parallel_tool_calls: bool,
     tool_timeout_sec: Option<Duration>,
+    sampling_approval_mode: Option<McpSamplingApproval>,
 }
 
 fn stdio_transport(
@@ -284,6 +286,7 @@ fn insert_mcp_server(
             startup_timeout_sec: Some(Duration::from_secs(10)),
             tool_timeout_sec: options.tool_timeout_sec,
             default_tools_approval_mode: None,
+            sampling_approval_mode: options.sampling_approval_mode,
             enabled_tools: None,
             disabled_tools: None,
             scopes: None,
@@ -552,6 +555,308 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn stdio_server_prompts_can_be_listed_and_rendered() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let server_name = "rmcp_prompts";
+    let rmcp_test_server_bin = remote_aware_stdio_server_bin()?;
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            insert_mcp_server(
+                config,
+                server_name,
+                stdio_transport(rmcp_test_server_bin, /*env*/ None, Vec::new()),
+                TestMcpServerOptions {
+                    experimental_environment: remote_aware_experimental_environment(),
+                    ..Default::default()
+                },
+            );
+        })
+        .build_remote_aware(&server)
+        .await?;
+
+    let prompts = fixture.codex.list_mcp_prompts().await;
+    let server_prompts = prompts
+        .get(server_name)
+        .expect("prompts listed for rmcp server");
+    let prompt_names: Vec<&str> = server_prompts
+        .iter()
+        .map(|prompt| prompt.name.as_str())
+        .collect();
+    assert_eq!(prompt_names, vec!["triage_incident"]);
+    let required_arguments: Vec<&str> = server_prompts[0]
+        .arguments
+        .iter()
+        .flatten()
+        .filter(|argument| argument.is_required())
+        .map(|argument| argument.name.as_str())
+        .collect();
+    assert_eq!(required_arguments, vec!["service"]);
+
+    let rendered = fixture
+        .codex
+        .get_mcp_prompt(
+            server_name,
+            "triage_incident",
+            HashMap::from([
+                ("service".to_string(), "billing".to_string()),
+                ("severity".to_string(), "sev2".to_string()),
+            ]),
+        )
+        .await?;
+    assert_eq!(
+        rendered.text(),
+        "Triage the sev2 incident affecting billing."
+    );
+
+    let missing_argument = fixture
+        .codex
+        .get_mcp_prompt(server_name, "triage_incident", HashMap::new())
+        .await;
+    assert!(missing_argument.is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn stdio_server_lists_thread_cwd_as_root() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+
+    let call_id = "call-roots";
+    let server_name = "rmcp";
+    let namespace = format!("mcp__{server_name}__");
+
+    mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_function_call_with_namespace(call_id, &namespace, "roots", "{}"),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let final_mock = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-1", "rmcp roots tool completed."),
+            responses::ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            insert_mcp_server(
+                config,
+                server_name,
+                stdio_transport(rmcp_test_server_bin, /*env*/ None, Vec::new()),
+                TestMcpServerOptions::default(),
+            );
+        })
+        .build(&server)
+        .await?;
+
+    fixture
+        .submit_turn_with_policy(
+            "call the rmcp roots tool",
+            SandboxPolicy::new_read_only_policy(),
+        )
+        .await?;
+
+    let output_item = final_mock.single_request().function_call_output(call_id);
+    let output_text = output_item
+        .get("output")
+        .and_then(Value::as_str)
+        .expect("function_call_output output should be a string");
+    let output_json: Value = serde_json::from_str(split_wall_time_wrapped_output(output_text))
+        .expect("wrapped MCP output should preserve structured JSON");
+    let cwd = fixture.cwd.path().canonicalize()?;
+    let expected_uri = url::Url::from_file_path(&cwd)
+        .map_err(|()| anyhow::anyhow!("cwd should convert to a file URL"))?;
+    assert_eq!(
+        output_json["roots"][0]["uri"],
+        json!(String::from(expected_uri))
+    );
+
+    server.verify().await;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn stdio_server_resource_update_is_queued_for_next_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let server_name = "rmcp";
+    let memo_uri = "memo://codex/example-note";
+
+    let final_mock = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-1", "Noted the memo change."),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            insert_mcp_server(
+                config,
+                server_name,
+                stdio_transport(rmcp_test_server_bin, /*env*/ None, Vec::new()),
+                TestMcpServerOptions::default(),
+            );
+        })
+        .build(&server)
+        .await?;
+
+    fixture
+        .codex
+        .subscribe_mcp_resource(server_name, memo_uri)
+        .await?;
+    fixture
+        .codex
+        .call_mcp_tool(
+            server_name,
+            "update_memo",
+            Some(json!({})),
+            /*meta*/ None,
+        )
+        .await?;
+    wait_for_event(
+        &fixture.codex,
+        |ev| matches!(ev, EventMsg::McpResourceUpdated(event) if event.uri == memo_uri),
+    )
+    .await;
+
+    fixture
+        .submit_turn_with_policy(
+            "what changed in the memo?",
+            SandboxPolicy::new_read_only_policy(),
+        )
+        .await?;
+
+    let developer_texts = final_mock.single_request().message_input_texts("developer");
+    let update = developer_texts
+        .iter()
+        .find(|text| text.starts_with("MCP resource `memo://codex/example-note`"))
+        .expect("resource update should be queued as developer context");
+    assert!(
+        update.contains("+Revision 1."),
+        "update should include the resource diff: {update}"
+    );
+
+    fixture
+        .codex
+        .unsubscribe_mcp_resource(server_name, memo_uri)
+        .await?;
+    server.verify().await;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn stdio_server_sampling_request_uses_session_model() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+
+    let call_id = "call-sample";
+    let server_name = "rmcp";
+    let namespace = format!("mcp__{server_name}__");
+
+    mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_function_call_with_namespace(
+                call_id,
+                &namespace,
+                "sample",
+                "{\"prompt\":\"Name a color.\"}",
+            ),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let sampling_mock = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-sample", "Blue."),
+            responses::ev_completed("resp-sample"),
+        ]),
+    )
+    .await;
+    let final_mock = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-1", "rmcp sample tool completed."),
+            responses::ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            insert_mcp_server(
+                config,
+                server_name,
+                stdio_transport(rmcp_test_server_bin, /*env*/ None, Vec::new()),
+                TestMcpServerOptions {
+                    sampling_approval_mode: Some(McpSamplingApproval::Approve),
+                    ..Default::default()
+                },
+            );
+        })
+        .build(&server)
+        .await?;
+
+    fixture
+        .submit_turn_with_policy(
+            "call the rmcp sample tool",
+            SandboxPolicy::new_read_only_policy(),
+        )
+        .await?;
+
+    let sampling_request = sampling_mock.single_request();
+    let sampling_body = sampling_request.body_json();
+    assert_eq!(
+        sampling_body["instructions"],
+        json!("Answer in one short sentence.")
+    );
+    assert_eq!(
+        sampling_request.message_input_texts("user"),
+        vec!["Name a color.".to_string()]
+    );
+
+    let output_item = final_mock.single_request().function_call_output(call_id);
+    let output_text = output_item
+        .get("output")
+        .and_then(Value::as_str)
+        .expect("function_call_output output should be a string");
+    let output_json: Value = serde_json::from_str(split_wall_time_wrapped_output(output_text))
+        .expect("wrapped MCP output should preserve structured JSON");
+    assert_eq!(output_json["text"], "Blue.");
+    assert_eq!(
+        output_json["model"],
+        json!(fixture.session_configured.model)
+    );
+
+    server.verify().await;
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 #[serial(mcp_cwd)]
 async fn stdio_server_uses_configured_cwd_before_runtime_fallback() -> anyhow::Result<()> {
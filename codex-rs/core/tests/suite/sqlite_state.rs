@@ -381,6 +381,7 @@ async fn mcp_call_marks_thread_memory_mode_polluted_when_configured() -> Result<
                 startup_timeout_sec: Some(Duration::from_secs(10)),
                 tool_timeout_sec: None,
                 default_tools_approval_mode: None,
+                sampling_approval_mode: None,
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
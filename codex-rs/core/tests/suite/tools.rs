@@ -255,6 +255,7 @@ async fn historical_unavailable_mcp_call_is_exposed_as_placeholder_tool() -> Res
                     startup_timeout_sec: Some(Duration::from_secs(10)),
                     tool_timeout_sec: None,
                     default_tools_approval_mode: None,
+                    sampling_approval_mode: None,
                     enabled_tools: None,
                     disabled_tools: None,
                     scopes: None,
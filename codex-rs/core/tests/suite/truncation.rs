@@ -383,6 +383,7 @@ async fn mcp_tool_call_output_exceeds_limit_truncated_for_model() -> Result<()>
                 startup_timeout_sec: Some(std::time::Duration::from_secs(10)),
                 tool_timeout_sec: None,
                 default_tools_approval_mode: None,
+                sampling_approval_mode: None,
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
@@ -482,6 +483,7 @@ async fn mcp_image_output_preserves_image_and_no_text_summary() -> Result<()> {
                 startup_timeout_sec: Some(Duration::from_secs(10)),
                 tool_timeout_sec: None,
                 default_tools_approval_mode: None,
+                sampling_approval_mode: None,
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
@@ -762,6 +764,7 @@ async fn mcp_tool_call_output_not_truncated_with_custom_limit() -> Result<()> {
                 startup_timeout_sec: Some(std::time::Duration::from_secs(10)),
                 tool_timeout_sec: None,
                 default_tools_approval_mode: None,
+                sampling_approval_mode: None,
                 enabled_tools: None,
                 disabled_tools: None,
                 scopes: None,
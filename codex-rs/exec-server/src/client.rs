@@ -42,15 +42,19 @@ use crate::protocol::ExecParams;
 use crate::protocol::ExecResponse;
 use crate::protocol::FS_COPY_METHOD;
 use crate::protocol::FS_CREATE_DIRECTORY_METHOD;
+use crate::protocol::FS_CREATE_SYMLINK_METHOD;
 use crate::protocol::FS_GET_METADATA_METHOD;
 use crate::protocol::FS_READ_DIRECTORY_METHOD;
 use crate::protocol::FS_READ_FILE_METHOD;
 use crate::protocol::FS_REMOVE_METHOD;
+use crate::protocol::FS_SET_MODE_METHOD;
 use crate::protocol::FS_WRITE_FILE_METHOD;
 use crate::protocol::FsCopyParams;
 use crate::protocol::FsCopyResponse;
 use crate::protocol::FsCreateDirectoryParams;
 use crate::protocol::FsCreateDirectoryResponse;
+use crate::protocol::FsCreateSymlinkParams;
+use crate::protocol::FsCreateSymlinkResponse;
 use crate::protocol::FsGetMetadataParams;
 use crate::protocol::FsGetMetadataResponse;
 use crate::protocol::FsReadDirectoryParams;
@@ -59,6 +63,8 @@ use crate::protocol::FsReadFileParams;
 use crate::protocol::FsReadFileResponse;
 use crate::protocol::FsRemoveParams;
 use crate::protocol::FsRemoveResponse;
+use crate::protocol::FsSetModeParams;
+use crate::protocol::FsSetModeResponse;
 use crate::protocol::FsWriteFileParams;
 use crate::protocol::FsWriteFileResponse;
 use crate::protocol::HTTP_REQUEST_BODY_DELTA_METHOD;
@@ -404,6 +410,20 @@ impl ExecServerClient {
         self.call(FS_COPY_METHOD, &params).await
     }
 
+    pub async fn fs_create_symlink(
+        &self,
+        params: FsCreateSymlinkParams,
+    ) -> Result<FsCreateSymlinkResponse, ExecServerError> {
+        self.call(FS_CREATE_SYMLINK_METHOD, &params).await
+    }
+
+    pub async fn fs_set_mode(
+        &self,
+        params: FsSetModeParams,
+    ) -> Result<FsSetModeResponse, ExecServerError> {
+        self.call(FS_SET_MODE_METHOD, &params).await
+    }
+
     pub(crate) async fn register_session(
         &self,
         process_id: &ProcessId,
@@ -12,15 +12,19 @@ use crate::RemoveOptions;
 use crate::local_file_system::DirectFileSystem;
 use crate::protocol::FS_COPY_METHOD;
 use crate::protocol::FS_CREATE_DIRECTORY_METHOD;
+use crate::protocol::FS_CREATE_SYMLINK_METHOD;
 use crate::protocol::FS_GET_METADATA_METHOD;
 use crate::protocol::FS_READ_DIRECTORY_METHOD;
 use crate::protocol::FS_READ_FILE_METHOD;
 use crate::protocol::FS_REMOVE_METHOD;
+use crate::protocol::FS_SET_MODE_METHOD;
 use crate::protocol::FS_WRITE_FILE_METHOD;
 use crate::protocol::FsCopyParams;
 use crate::protocol::FsCopyResponse;
 use crate::protocol::FsCreateDirectoryParams;
 use crate::protocol::FsCreateDirectoryResponse;
+use crate::protocol::FsCreateSymlinkParams;
+use crate::protocol::FsCreateSymlinkResponse;
 use crate::protocol::FsGetMetadataParams;
 use crate::protocol::FsGetMetadataResponse;
 use crate::protocol::FsReadDirectoryEntry;
@@ -30,6 +34,8 @@ use crate::protocol::FsReadFileParams;
 use crate::protocol::FsReadFileResponse;
 use crate::protocol::FsRemoveParams;
 use crate::protocol::FsRemoveResponse;
+use crate::protocol::FsSetModeParams;
+use crate::protocol::FsSetModeResponse;
 use crate::protocol::FsWriteFileParams;
 use crate::protocol::FsWriteFileResponse;
 use crate::rpc::internal_error;
@@ -55,6 +61,10 @@ pub(crate) enum FsHelperRequest {
     Remove(FsRemoveParams),
     #[serde(rename = "fs/copy")]
     Copy(FsCopyParams),
+    #[serde(rename = "fs/createSymlink")]
+    CreateSymlink(FsCreateSymlinkParams),
+    #[serde(rename = "fs/setMode")]
+    SetMode(FsSetModeParams),
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -81,6 +91,10 @@ pub(crate) enum FsHelperPayload {
     Remove(FsRemoveResponse),
     #[serde(rename = "fs/copy")]
     Copy(FsCopyResponse),
+    #[serde(rename = "fs/createSymlink")]
+    CreateSymlink(FsCreateSymlinkResponse),
+    #[serde(rename = "fs/setMode")]
+    SetMode(FsSetModeResponse),
 }
 
 impl FsHelperPayload {
@@ -93,6 +107,8 @@ impl FsHelperPayload {
             Self::ReadDirectory(_) => FS_READ_DIRECTORY_METHOD,
             Self::Remove(_) => FS_REMOVE_METHOD,
             Self::Copy(_) => FS_COPY_METHOD,
+            Self::CreateSymlink(_) => FS_CREATE_SYMLINK_METHOD,
+            Self::SetMode(_) => FS_SET_MODE_METHOD,
         }
     }
 
@@ -157,6 +173,25 @@ impl FsHelperPayload {
             other => Err(unexpected_response(FS_COPY_METHOD, other.operation())),
         }
     }
+
+    pub(crate) fn expect_create_symlink(
+        self,
+    ) -> Result<FsCreateSymlinkResponse, JSONRPCErrorError> {
+        match self {
+            Self::CreateSymlink(response) => Ok(response),
+            other => Err(unexpected_response(
+                FS_CREATE_SYMLINK_METHOD,
+                other.operation(),
+            )),
+        }
+    }
+
+    pub(crate) fn expect_set_mode(self) -> Result<FsSetModeResponse, JSONRPCErrorError> {
+        match self {
+            Self::SetMode(response) => Ok(response),
+            other => Err(unexpected_response(FS_SET_MODE_METHOD, other.operation())),
+        }
+    }
 }
 
 fn unexpected_response(expected: &str, actual: &str) -> JSONRPCErrorError {
@@ -263,6 +298,20 @@ pub(crate) async fn run_direct_request(
                 .map_err(map_fs_error)?;
             Ok(FsHelperPayload::Copy(FsCopyResponse {}))
         }
+        FsHelperRequest::CreateSymlink(params) => {
+            file_system
+                .create_symlink(&params.path, &params.target, /*sandbox*/ None)
+                .await
+                .map_err(map_fs_error)?;
+            Ok(FsHelperPayload::CreateSymlink(FsCreateSymlinkResponse {}))
+        }
+        FsHelperRequest::SetMode(params) => {
+            file_system
+                .set_mode(&params.path, params.mode, /*sandbox*/ None)
+                .await
+                .map_err(map_fs_error)?;
+            Ok(FsHelperPayload::SetMode(FsSetModeResponse {}))
+        }
     }
 }
 
@@ -148,6 +148,26 @@ impl ExecutorFileSystem for LocalFileSystem {
             .copy(source_path, destination_path, options, sandbox)
             .await
     }
+
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        let (file_system, sandbox) = self.file_system_for(sandbox)?;
+        file_system.create_symlink(path, target, sandbox).await
+    }
+
+    async fn set_mode(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        let (file_system, sandbox) = self.file_system_for(sandbox)?;
+        file_system.set_mode(path, mode, sandbox).await
+    }
 }
 
 #[async_trait]
@@ -234,6 +254,30 @@ impl ExecutorFileSystem for UnsandboxedFileSystem {
             )
             .await
     }
+
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        reject_platform_sandbox_context(sandbox)?;
+        self.file_system
+            .create_symlink(path, target, /*sandbox*/ None)
+            .await
+    }
+
+    async fn set_mode(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        reject_platform_sandbox_context(sandbox)?;
+        self.file_system
+            .set_mode(path, mode, /*sandbox*/ None)
+            .await
+    }
 }
 
 #[async_trait]
@@ -395,6 +439,30 @@ impl ExecutorFileSystem for DirectFileSystem {
         .await
         .map_err(|err| io::Error::other(format!("filesystem task failed: {err}")))?
     }
+
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        reject_sandbox_context(sandbox)?;
+        let path = path.to_path_buf();
+        let target = target.to_path_buf();
+        tokio::task::spawn_blocking(move || create_symlink(target.as_path(), path.as_path()))
+            .await
+            .map_err(|err| io::Error::other(format!("filesystem task failed: {err}")))?
+    }
+
+    async fn set_mode(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        reject_sandbox_context(sandbox)?;
+        set_file_mode(path.as_path(), mode).await
+    }
 }
 
 fn reject_sandbox_context(sandbox: Option<&FileSystemSandboxContext>) -> io::Result<()> {
@@ -497,6 +565,55 @@ fn copy_symlink(source: &Path, target: &Path) -> io::Result<()> {
     }
 }
 
+/// Creates a symlink at `link` pointing at `link_target`.
+fn create_symlink(link_target: &Path, link: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(link_target, link)
+    }
+    #[cfg(windows)]
+    {
+        // Windows distinguishes file and directory links; relative targets resolve against the
+        // link's parent directory.
+        let resolved_target = link.parent().map_or_else(
+            || link_target.to_path_buf(),
+            |parent| parent.join(link_target),
+        );
+        if resolved_target.is_dir() {
+            std::os::windows::fs::symlink_dir(link_target, link)
+        } else {
+            std::os::windows::fs::symlink_file(link_target, link)
+        }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = link_target;
+        let _ = link;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "creating symlinks is unsupported on this platform",
+        ))
+    }
+}
+
+#[cfg(unix)]
+async fn set_file_mode(path: &Path, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).await
+}
+
+#[cfg(not(unix))]
+async fn set_file_mode(path: &Path, mode: u32) -> io::Result<()> {
+    // Permission bits have no equivalent here, so a requested mode is skipped rather than failing
+    // the whole patch.
+    tracing::warn!(
+        "ignoring request to set mode {mode:o} on {}: file modes are unsupported on this platform",
+        path.display()
+    );
+    Ok(())
+}
+
 #[cfg(windows)]
 fn symlink_points_to_directory(source: &Path) -> io::Result<bool> {
     use std::os::windows::fs::FileTypeExt;
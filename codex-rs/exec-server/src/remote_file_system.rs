@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use base64::Engine as _;
 use base64::engine::general_purpose::STANDARD;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use std::path::Path;
 use tokio::io;
 use tracing::trace;
 
@@ -17,10 +18,12 @@ use crate::RemoveOptions;
 use crate::client::LazyRemoteExecServerClient;
 use crate::protocol::FsCopyParams;
 use crate::protocol::FsCreateDirectoryParams;
+use crate::protocol::FsCreateSymlinkParams;
 use crate::protocol::FsGetMetadataParams;
 use crate::protocol::FsReadDirectoryParams;
 use crate::protocol::FsReadFileParams;
 use crate::protocol::FsRemoveParams;
+use crate::protocol::FsSetModeParams;
 use crate::protocol::FsWriteFileParams;
 
 const INVALID_REQUEST_ERROR_CODE: i64 = -32600;
@@ -188,6 +191,44 @@ impl ExecutorFileSystem for RemoteFileSystem {
             .map_err(map_remote_error)?;
         Ok(())
     }
+
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        trace!("remote fs create_symlink");
+        let client = self.client.get().await.map_err(map_remote_error)?;
+        client
+            .fs_create_symlink(FsCreateSymlinkParams {
+                path: path.clone(),
+                target: target.to_path_buf(),
+                sandbox: remote_sandbox_context(sandbox),
+            })
+            .await
+            .map_err(map_remote_error)?;
+        Ok(())
+    }
+
+    async fn set_mode(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        trace!("remote fs set_mode");
+        let client = self.client.get().await.map_err(map_remote_error)?;
+        client
+            .fs_set_mode(FsSetModeParams {
+                path: path.clone(),
+                mode,
+                sandbox: remote_sandbox_context(sandbox),
+            })
+            .await
+            .map_err(map_remote_error)?;
+        Ok(())
+    }
 }
 
 fn remote_sandbox_context(
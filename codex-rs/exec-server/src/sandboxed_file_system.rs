@@ -3,6 +3,7 @@ use base64::Engine as _;
 use base64::engine::general_purpose::STANDARD;
 use codex_app_server_protocol::JSONRPCErrorError;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use std::path::Path;
 use tokio::io;
 
 use crate::CopyOptions;
@@ -19,10 +20,12 @@ use crate::fs_helper::FsHelperRequest;
 use crate::fs_sandbox::FileSystemSandboxRunner;
 use crate::protocol::FsCopyParams;
 use crate::protocol::FsCreateDirectoryParams;
+use crate::protocol::FsCreateSymlinkParams;
 use crate::protocol::FsGetMetadataParams;
 use crate::protocol::FsReadDirectoryParams;
 use crate::protocol::FsReadFileParams;
 use crate::protocol::FsRemoveParams;
+use crate::protocol::FsSetModeParams;
 use crate::protocol::FsWriteFileParams;
 
 #[derive(Clone)]
@@ -216,6 +219,48 @@ impl ExecutorFileSystem for SandboxedFileSystem {
         .map_err(map_sandbox_error)?;
         Ok(())
     }
+
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        let sandbox = require_platform_sandbox(sandbox)?;
+        self.run_sandboxed(
+            sandbox,
+            FsHelperRequest::CreateSymlink(FsCreateSymlinkParams {
+                path: path.clone(),
+                target: target.to_path_buf(),
+                sandbox: None,
+            }),
+        )
+        .await?
+        .expect_create_symlink()
+        .map_err(map_sandbox_error)?;
+        Ok(())
+    }
+
+    async fn set_mode(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()> {
+        let sandbox = require_platform_sandbox(sandbox)?;
+        self.run_sandboxed(
+            sandbox,
+            FsHelperRequest::SetMode(FsSetModeParams {
+                path: path.clone(),
+                mode,
+                sandbox: None,
+            }),
+        )
+        .await?
+        .expect_set_mode()
+        .map_err(map_sandbox_error)?;
+        Ok(())
+    }
 }
 
 fn require_platform_sandbox(
@@ -15,6 +15,8 @@ use crate::protocol::FsCopyParams;
 use crate::protocol::FsCopyResponse;
 use crate::protocol::FsCreateDirectoryParams;
 use crate::protocol::FsCreateDirectoryResponse;
+use crate::protocol::FsCreateSymlinkParams;
+use crate::protocol::FsCreateSymlinkResponse;
 use crate::protocol::FsGetMetadataParams;
 use crate::protocol::FsGetMetadataResponse;
 use crate::protocol::FsReadDirectoryEntry;
@@ -24,6 +26,8 @@ use crate::protocol::FsReadFileParams;
 use crate::protocol::FsReadFileResponse;
 use crate::protocol::FsRemoveParams;
 use crate::protocol::FsRemoveResponse;
+use crate::protocol::FsSetModeParams;
+use crate::protocol::FsSetModeResponse;
 use crate::protocol::FsWriteFileParams;
 use crate::protocol::FsWriteFileResponse;
 use crate::rpc::internal_error;
@@ -159,6 +163,28 @@ impl FileSystemHandler {
             .map_err(map_fs_error)?;
         Ok(FsCopyResponse {})
     }
+
+    pub(crate) async fn create_symlink(
+        &self,
+        params: FsCreateSymlinkParams,
+    ) -> Result<FsCreateSymlinkResponse, JSONRPCErrorError> {
+        self.file_system
+            .create_symlink(&params.path, &params.target, params.sandbox.as_ref())
+            .await
+            .map_err(map_fs_error)?;
+        Ok(FsCreateSymlinkResponse {})
+    }
+
+    pub(crate) async fn set_mode(
+        &self,
+        params: FsSetModeParams,
+    ) -> Result<FsSetModeResponse, JSONRPCErrorError> {
+        self.file_system
+            .set_mode(&params.path, params.mode, params.sandbox.as_ref())
+            .await
+            .map_err(map_fs_error)?;
+        Ok(FsSetModeResponse {})
+    }
 }
 
 fn map_fs_error(err: io::Error) -> JSONRPCErrorError {
@@ -2,7 +2,6 @@
 
 mod common;
 
-#[cfg(target_os = "linux")]
 use std::os::unix::fs::PermissionsExt;
 use std::os::unix::fs::symlink;
 use std::path::Path;
@@ -443,6 +442,45 @@ async fn file_system_methods_cover_surface_area(use_remote: bool) -> Result<()>
     Ok(())
 }
 
+#[test_case(false ; "local")]
+#[test_case(true ; "remote")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn file_system_create_symlink_and_set_mode(use_remote: bool) -> Result<()> {
+    let context = create_file_system_context(use_remote).await?;
+    let file_system = context.file_system;
+
+    let tmp = TempDir::new()?;
+    let script_path = tmp.path().join("run.sh");
+    let link_path = tmp.path().join("run");
+    std::fs::write(&script_path, "#!/bin/sh\n")?;
+
+    file_system
+        .set_mode(
+            &absolute_path(script_path.clone()),
+            0o755,
+            /*sandbox*/ None,
+        )
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert_eq!(
+        std::fs::metadata(&script_path)?.permissions().mode() & 0o7777,
+        0o755
+    );
+
+    file_system
+        .create_symlink(
+            &absolute_path(link_path.clone()),
+            Path::new("run.sh"),
+            /*sandbox*/ None,
+        )
+        .await
+        .with_context(|| format!("mode={use_remote}"))?;
+    assert_eq!(std::fs::read_link(&link_path)?, PathBuf::from("run.sh"));
+    assert_eq!(std::fs::read_to_string(&link_path)?, "#!/bin/sh\n");
+
+    Ok(())
+}
+
 #[test_case(false ; "local")]
 #[test_case(true ; "remote")]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -7,8 +7,14 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use crate::decision::Decision;
+use crate::parser::PolicyParser;
 use crate::rule::NetworkRuleProtocol;
+use crate::rule::PrefixRule;
+use crate::rule::RuleScope;
 use crate::rule::normalize_network_rule_host;
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -58,6 +64,11 @@ pub enum AmendError {
         path: PathBuf,
         source: std::io::Error,
     },
+    #[error("failed to parse policy file {path}: {source}")]
+    ParsePolicyFile {
+        path: PathBuf,
+        source: crate::error::Error,
+    },
 }
 
 /// Note this thread uses advisory file locking and performs blocking I/O, so it should be used with
@@ -65,6 +76,16 @@ pub enum AmendError {
 pub fn blocking_append_allow_prefix_rule(
     policy_path: &Path,
     prefix: &[String],
+) -> Result<(), AmendError> {
+    blocking_append_scoped_allow_prefix_rule(policy_path, prefix, &RuleScope::default())
+}
+
+/// Like [`blocking_append_allow_prefix_rule`], but the rule only applies within `scope`: until
+/// `expires_at`, and only for `thread_id` / inside `project_root` when those are set.
+pub fn blocking_append_scoped_allow_prefix_rule(
+    policy_path: &Path,
+    prefix: &[String],
+    scope: &RuleScope,
 ) -> Result<(), AmendError> {
     if prefix.is_empty() {
         return Err(AmendError::EmptyPrefix);
@@ -76,10 +97,140 @@ pub fn blocking_append_allow_prefix_rule(
         .collect::<Result<Vec<_>, _>>()
         .map_err(|source| AmendError::SerializePrefix { source })?;
     let pattern = format!("[{}]", tokens.join(", "));
-    let rule = format!(r#"prefix_rule(pattern={pattern}, decision="allow")"#);
+    let mut args = vec![
+        format!("pattern={pattern}"),
+        r#"decision="allow""#.to_string(),
+    ];
+    args.extend(scope_args(scope).map_err(|source| AmendError::SerializePrefix { source })?);
+    let rule = format!("prefix_rule({})", args.join(", "));
     append_rule_line(policy_path, &rule)
 }
 
+fn scope_args(scope: &RuleScope) -> Result<Vec<String>, serde_json::Error> {
+    let mut args = Vec::new();
+    if let Some(expires_at) = scope.expires_at {
+        let expires_at = expires_at.to_rfc3339_opts(SecondsFormat::Secs, /*use_z*/ true);
+        args.push(format!(
+            "expires_at={}",
+            serde_json::to_string(&expires_at)?
+        ));
+    }
+    if let Some(thread_id) = &scope.thread_id {
+        args.push(format!("thread_id={}", serde_json::to_string(thread_id)?));
+    }
+    if let Some(project_root) = &scope.project_root {
+        args.push(format!(
+            "project_root={}",
+            serde_json::to_string(&project_root.as_path().to_string_lossy())?
+        ));
+    }
+    Ok(args)
+}
+
+/// Removes `prefix_rule` definitions whose `expires_at` is at or before `now`, returning how many
+/// were removed. Only top-level definitions that occupy whole lines are removed, which covers
+/// every rule written by [`blocking_append_scoped_allow_prefix_rule`].
+///
+/// Note this function uses advisory file locking and performs blocking I/O, so it should be used
+/// with [`tokio::task::spawn_blocking`] when called from an async context.
+pub fn blocking_prune_expired_rules(
+    policy_path: &Path,
+    now: DateTime<Utc>,
+) -> Result<usize, AmendError> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(policy_path)
+        .map_err(|source| AmendError::OpenPolicyFile {
+            path: policy_path.to_path_buf(),
+            source,
+        })?;
+    file.lock().map_err(|source| AmendError::LockPolicyFile {
+        path: policy_path.to_path_buf(),
+        source,
+    })?;
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .map_err(|source| AmendError::ReadPolicyFile {
+            path: policy_path.to_path_buf(),
+            source,
+        })?;
+
+    let expired_lines = expired_rule_lines(&policy_path.to_string_lossy(), &contents, now)
+        .map_err(|source| AmendError::ParsePolicyFile {
+            path: policy_path.to_path_buf(),
+            source,
+        })?;
+    if expired_lines.is_empty() {
+        return Ok(0);
+    }
+
+    let pruned = contents
+        .split_inclusive('\n')
+        .enumerate()
+        .filter(|(index, _line)| {
+            !expired_lines
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(&(index + 1)))
+        })
+        .map(|(_index, line)| line)
+        .collect::<String>();
+
+    file.seek(SeekFrom::Start(0))
+        .map_err(|source| AmendError::SeekPolicyFile {
+            path: policy_path.to_path_buf(),
+            source,
+        })?;
+    file.set_len(0)
+        .and_then(|()| file.write_all(pruned.as_bytes()))
+        .map_err(|source| AmendError::WritePolicyFile {
+            path: policy_path.to_path_buf(),
+            source,
+        })?;
+
+    Ok(expired_lines.len())
+}
+
+/// Returns the 1-based, inclusive line ranges of expired rule definitions in `contents`.
+fn expired_rule_lines(
+    identifier: &str,
+    contents: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<(usize, usize)>, crate::error::Error> {
+    let mut parser = PolicyParser::new();
+    parser.parse(identifier, contents)?;
+
+    let lines = contents.lines().collect::<Vec<_>>();
+    let mut expired = Vec::new();
+    for source in parser.rule_sources() {
+        let Some(prefix_rule) = source.rule.as_any().downcast_ref::<PrefixRule>() else {
+            continue;
+        };
+        let Some(location) = &source.location else {
+            continue;
+        };
+        if !prefix_rule.scope.is_expired_at(now) {
+            continue;
+        }
+
+        let range = location.range;
+        let (start, end) = (range.start.line, range.end.line);
+        // Skip definitions nested in loops or helpers, or sharing a line with other code.
+        let trailing = lines
+            .get(end - 1)
+            .map(|line| line.chars().skip(range.end.column - 1).collect::<String>())
+            .unwrap_or_default();
+        let trailing = trailing.trim();
+        let whole_lines =
+            range.start.column == 1 && (trailing.is_empty() || trailing.starts_with('#'));
+        if whole_lines && !expired.contains(&(start, end)) {
+            expired.push((start, end));
+        }
+    }
+    Ok(expired)
+}
+
 /// Note this function uses advisory file locking and performs blocking I/O, so it should be used
 /// with [`tokio::task::spawn_blocking`] when called from an async context.
 pub fn blocking_append_network_rule(
@@ -270,6 +421,69 @@ prefix_rule(pattern=["echo", "Hello, world!"], decision="allow")
         );
     }
 
+    #[test]
+    fn appends_scoped_rule() {
+        let tmp = tempdir().expect("create temp dir");
+        let policy_path = tmp.path().join("rules").join("default.rules");
+        let expires_at = DateTime::parse_from_rfc3339("2030-01-02T03:04:05Z")
+            .expect("parse timestamp")
+            .with_timezone(&Utc);
+
+        blocking_append_scoped_allow_prefix_rule(
+            &policy_path,
+            &[String::from("cargo"), String::from("test")],
+            &RuleScope {
+                expires_at: Some(expires_at),
+                thread_id: Some("thread-1".to_string()),
+                project_root: None,
+            },
+        )
+        .expect("append rule");
+
+        let contents = std::fs::read_to_string(&policy_path).expect("read policy");
+        assert_eq!(
+            contents,
+            r#"prefix_rule(pattern=["cargo", "test"], decision="allow", expires_at="2030-01-02T03:04:05Z", thread_id="thread-1")
+"#
+        );
+    }
+
+    #[test]
+    fn prunes_only_expired_rules() {
+        let tmp = tempdir().expect("create temp dir");
+        let policy_path = tmp.path().join("default.rules");
+        std::fs::write(
+            &policy_path,
+            r#"# keep me
+prefix_rule(pattern=["ls"], decision="allow")
+prefix_rule(pattern=["cargo"], decision="allow", expires_at="2020-01-01T00:00:00Z")
+prefix_rule(
+    pattern = [["npm", "pnpm"], "test"],
+    expires_at = "2020-06-01T00:00:00+02:00",
+)
+prefix_rule(pattern=["make"], decision="allow", expires_at="2030-01-01T00:00:00Z")
+"#,
+        )
+        .expect("write policy");
+        let now = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .expect("parse timestamp")
+            .with_timezone(&Utc);
+
+        let removed = blocking_prune_expired_rules(&policy_path, now).expect("prune rules");
+
+        let contents = std::fs::read_to_string(&policy_path).expect("read policy");
+        assert_eq!(
+            (removed, contents.as_str()),
+            (
+                2,
+                r#"# keep me
+prefix_rule(pattern=["ls"], decision="allow")
+prefix_rule(pattern=["make"], decision="allow", expires_at="2030-01-01T00:00:00Z")
+"#
+            )
+        );
+    }
+
     #[test]
     fn appends_network_rule() {
         let tmp = tempdir().expect("create temp dir");
@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use anyhow::Context;
 use anyhow::Result;
 use clap::Parser;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use serde::Serialize;
 
 use crate::Decision;
@@ -28,6 +29,18 @@ pub struct ExecPolicyCheckCommand {
     #[arg(long)]
     pub resolve_host_executables: bool,
 
+    /// Working directory used to resolve relative paths for `writable_path()` patterns.
+    #[arg(long, value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
+    /// Writable root accepted by `writable_path()` patterns (repeatable).
+    #[arg(long = "writable-root", value_name = "DIR")]
+    pub writable_roots: Vec<PathBuf>,
+
+    /// Thread evaluating the command, for rules scoped with `thread_id`.
+    #[arg(long = "thread-id", value_name = "ID")]
+    pub thread_id: Option<String>,
+
     /// Command tokens to check against the policy.
     #[arg(
         value_name = "COMMAND",
@@ -42,11 +55,28 @@ impl ExecPolicyCheckCommand {
     /// Load the policies for this command, evaluate the command, and render JSON output.
     pub fn run(&self) -> Result<()> {
         let policy = load_policies(&self.rules)?;
+        let cwd = self
+            .cwd
+            .as_deref()
+            .map(AbsolutePathBuf::relative_to_current_dir)
+            .transpose()
+            .context("failed to resolve --cwd")?;
+        let writable_roots = self
+            .writable_roots
+            .iter()
+            .map(AbsolutePathBuf::relative_to_current_dir)
+            .collect::<std::io::Result<Vec<_>>>()
+            .context("failed to resolve --writable-root")?;
         let matched_rules = policy.matches_for_command_with_options(
             &self.command,
             /*heuristics_fallback*/ None,
             &MatchOptions {
                 resolve_host_executables: self.resolve_host_executables,
+                cwd,
+                writable_roots,
+                read_only_paths: Vec::new(),
+                thread_id: self.thread_id.clone(),
+                ignore_scope: false,
             },
         );
 
@@ -71,6 +101,11 @@ pub fn format_matches_json(matched_rules: &[RuleMatch], pretty: bool) -> Result<
 }
 
 pub fn load_policies(policy_paths: &[PathBuf]) -> Result<Policy> {
+    Ok(parse_policy_files(policy_paths)?.build())
+}
+
+/// Parses `policy_paths` in order into a single parser, keeping rule sources available.
+pub(crate) fn parse_policy_files(policy_paths: &[PathBuf]) -> Result<PolicyParser> {
     let mut parser = PolicyParser::new();
 
     for policy_path in policy_paths {
@@ -82,7 +117,7 @@ pub fn load_policies(policy_paths: &[PathBuf]) -> Result<Policy> {
             .with_context(|| format!("failed to parse policy at {}", policy_path.display()))?;
     }
 
-    Ok(parser.build())
+    Ok(parser)
 }
 
 #[derive(Serialize)]
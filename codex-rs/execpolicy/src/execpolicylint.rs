@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use crate::Decision;
+use crate::MatchOptions;
+use crate::Policy;
+use crate::RuleSource;
+use crate::execpolicycheck::parse_policy_files;
+use crate::flag_rule::FlagRule;
+use crate::policy::render_pattern_token;
+use crate::rule::PatternToken;
+use crate::rule::PrefixRule;
+use crate::rule::RuleRef;
+
+/// Arguments for reporting how execpolicy rules behave against recorded sessions.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicyLintCommand {
+    /// Paths to execpolicy rule files to lint (repeatable).
+    #[arg(short = 'r', long = "rules", value_name = "PATH", required = true)]
+    pub rules: Vec<PathBuf>,
+
+    /// Rollout files or directories to replay (repeatable). Defaults to
+    /// `$CODEX_HOME/sessions`.
+    #[arg(long = "rollouts", value_name = "PATH")]
+    pub rollouts: Vec<PathBuf>,
+
+    /// Print the report as JSON instead of human-readable text.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Pretty-print the JSON output.
+    #[arg(long, requires = "json")]
+    pub pretty: bool,
+
+    /// Resolve absolute program paths against basename rules, gated by any
+    /// `host_executable()` definitions in the loaded policy files.
+    #[arg(long)]
+    pub resolve_host_executables: bool,
+}
+
+impl ExecPolicyLintCommand {
+    /// Lint the policies, replaying recorded commands exactly as they were recorded.
+    pub fn run(&self) -> Result<()> {
+        self.run_with_command_splitter(&|command| vec![command.to_vec()])
+    }
+
+    /// Lint the policies, using `split_command` to turn each recorded command into the commands
+    /// execpolicy would evaluate (for example, the individual commands inside `bash -lc`).
+    pub fn run_with_command_splitter(
+        &self,
+        split_command: &dyn Fn(&[String]) -> Vec<Vec<String>>,
+    ) -> Result<()> {
+        let parser = parse_policy_files(&self.rules)?;
+        let rule_sources = parser.rule_sources();
+        let policy = parser.build();
+
+        let rollout_roots = if self.rollouts.is_empty() {
+            let codex_home = codex_utils_home_dir::find_codex_home()
+                .context("failed to locate CODEX_HOME; pass --rollouts explicitly")?;
+            vec![codex_home.join("sessions").into_path_buf()]
+        } else {
+            self.rollouts.clone()
+        };
+        let rollout_files = collect_rollout_files(&rollout_roots)?;
+
+        let mut commands = Vec::new();
+        for rollout_file in &rollout_files {
+            let contents = fs::read_to_string(rollout_file)
+                .with_context(|| format!("failed to read rollout {}", rollout_file.display()))?;
+            commands.extend(
+                commands_from_rollout(&contents)
+                    .iter()
+                    .flat_map(|command| split_command(command)),
+            );
+        }
+
+        let mut report = lint_policy(
+            &policy,
+            &rule_sources,
+            &commands,
+            &MatchOptions {
+                resolve_host_executables: self.resolve_host_executables,
+                ..Default::default()
+            },
+        );
+        report.rollouts_scanned = rollout_files.len();
+
+        if self.json {
+            let json = if self.pretty {
+                serde_json::to_string_pretty(&report)?
+            } else {
+                serde_json::to_string(&report)?
+            };
+            println!("{json}");
+        } else {
+            print!("{}", format_lint_report(&report));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub rollouts_scanned: usize,
+    pub commands_replayed: usize,
+    /// Rules that did not match any replayed command.
+    pub unused_rules: Vec<RuleSummary>,
+    /// Rules that can never change a decision because an earlier, broader rule is at least as
+    /// strict.
+    pub shadowed_rules: Vec<ShadowedRule>,
+    /// Replayed commands no rule matched, most frequent first.
+    pub heuristics_fallback_commands: Vec<FallbackCommand>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleSummary {
+    pub rule: String,
+    pub decision: Decision,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShadowedRule {
+    pub rule: RuleSummary,
+    pub shadowed_by: RuleSummary,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FallbackCommand {
+    pub command: Vec<String>,
+    pub count: usize,
+}
+
+/// Replays `commands` against `policy` and reports unused rules, shadowed rules and commands that
+/// no rule matched. `rule_sources` must list the policy's rules in definition order.
+pub fn lint_policy(
+    policy: &Policy,
+    rule_sources: &[RuleSource],
+    commands: &[Vec<String>],
+    options: &MatchOptions,
+) -> LintReport {
+    let mut matched_rules: Vec<RuleRef> = Vec::new();
+    let mut fallback_counts: HashMap<&[String], usize> = HashMap::new();
+    for command in commands {
+        let matches = policy.matching_rules_with_options(command, options);
+        if matches.is_empty() {
+            *fallback_counts.entry(command.as_slice()).or_default() += 1;
+        }
+        for (rule, _rule_match) in matches {
+            if !matched_rules.iter().any(|seen| Arc::ptr_eq(seen, &rule)) {
+                matched_rules.push(rule);
+            }
+        }
+    }
+
+    let unused_rules = rule_sources
+        .iter()
+        .filter(|source| {
+            !matched_rules
+                .iter()
+                .any(|matched| Arc::ptr_eq(matched, &source.rule))
+        })
+        .map(summarize_rule)
+        .collect();
+
+    let shadowed_rules = rule_sources
+        .iter()
+        .enumerate()
+        .filter_map(|(index, later)| {
+            rule_sources[..index]
+                .iter()
+                .find(|earlier| shadows(&earlier.rule, &later.rule))
+                .map(|earlier| ShadowedRule {
+                    rule: summarize_rule(later),
+                    shadowed_by: summarize_rule(earlier),
+                })
+        })
+        .collect();
+
+    let mut heuristics_fallback_commands: Vec<FallbackCommand> = fallback_counts
+        .into_iter()
+        .map(|(command, count)| FallbackCommand {
+            command: command.to_vec(),
+            count,
+        })
+        .collect();
+    heuristics_fallback_commands.sort_by(|left, right| {
+        right
+            .count
+            .cmp(&left.count)
+            .then_with(|| left.command.cmp(&right.command))
+    });
+
+    LintReport {
+        rollouts_scanned: 0,
+        commands_replayed: commands.len(),
+        unused_rules,
+        shadowed_rules,
+        heuristics_fallback_commands,
+    }
+}
+
+/// Returns true when `earlier` matches every command `later` matches and is at least as strict,
+/// so `later` can never change the strictest decision.
+fn shadows(earlier: &RuleRef, later: &RuleRef) -> bool {
+    let Some(earlier) = earlier.as_any().downcast_ref::<PrefixRule>() else {
+        return false;
+    };
+    // A scoped rule only covers commands inside its own scope.
+    if !earlier.scope.is_unrestricted()
+        && later
+            .as_any()
+            .downcast_ref::<PrefixRule>()
+            .is_none_or(|later| later.scope != earlier.scope)
+    {
+        return false;
+    }
+
+    if let Some(later) = later.as_any().downcast_ref::<PrefixRule>() {
+        earlier.pattern.first == later.pattern.first
+            && earlier.decision >= later.decision
+            && earlier.pattern.rest.len() <= later.pattern.rest.len()
+            && earlier
+                .pattern
+                .rest
+                .iter()
+                .zip(later.pattern.rest.iter())
+                .all(|(broader, narrower)| token_covers(broader, narrower))
+    } else if let Some(later) = later.as_any().downcast_ref::<FlagRule>() {
+        // Flags may precede the subcommand, so only a bare program rule covers a flag rule.
+        earlier.pattern.first == later.program
+            && earlier.decision >= later.decision
+            && earlier.pattern.rest.is_empty()
+    } else {
+        false
+    }
+}
+
+fn token_covers(broader: &PatternToken, narrower: &PatternToken) -> bool {
+    match broader {
+        // `all_args()` also matches when no arguments remain, which `any_token()` does not.
+        PatternToken::Any => !matches!(narrower, PatternToken::AllArgs(_)),
+        PatternToken::Single(_) | PatternToken::Alts(_) if narrower.is_literal() => narrower
+            .alternatives()
+            .iter()
+            .all(|token| broader.alternatives().contains(token)),
+        _ => broader == narrower,
+    }
+}
+
+fn summarize_rule(source: &RuleSource) -> RuleSummary {
+    let rule = source.rule.as_any();
+    let (description, decision) = if let Some(prefix_rule) = rule.downcast_ref::<PrefixRule>() {
+        let pattern = std::iter::once(prefix_rule.pattern.first.to_string())
+            .chain(prefix_rule.pattern.rest.iter().map(render_pattern_token))
+            .collect::<Vec<_>>()
+            .join(" ");
+        (format!("prefix_rule `{pattern}`"), prefix_rule.decision)
+    } else if let Some(flag_rule) = rule.downcast_ref::<FlagRule>() {
+        let command = std::iter::once(flag_rule.program.to_string())
+            .chain(flag_rule.subcommand.iter().map(render_pattern_token))
+            .collect::<Vec<_>>()
+            .join(" ");
+        (format!("flag_rule `{command}`"), flag_rule.decision)
+    } else {
+        (format!("{:?}", source.rule), Decision::Allow)
+    };
+
+    RuleSummary {
+        rule: description,
+        decision,
+        location: source
+            .location
+            .as_ref()
+            .map(|location| format!("{}:{}", location.path, location.range.start.line)),
+    }
+}
+
+pub fn format_lint_report(report: &LintReport) -> String {
+    let mut output = format!(
+        "Replayed {} commands from {} rollouts.\n",
+        report.commands_replayed, report.rollouts_scanned
+    );
+
+    output.push_str(&format!(
+        "\nRules that never matched ({}):\n",
+        report.unused_rules.len()
+    ));
+    for rule in &report.unused_rules {
+        output.push_str(&format!("  {}\n", render_rule_summary(rule)));
+    }
+
+    output.push_str(&format!(
+        "\nRules shadowed by an earlier, broader rule ({}):\n",
+        report.shadowed_rules.len()
+    ));
+    for shadowed in &report.shadowed_rules {
+        output.push_str(&format!(
+            "  {}\n    shadowed by {}\n",
+            render_rule_summary(&shadowed.rule),
+            render_rule_summary(&shadowed.shadowed_by)
+        ));
+    }
+
+    output.push_str(&format!(
+        "\nCommands that fell through to heuristics ({}):\n",
+        report.heuristics_fallback_commands.len()
+    ));
+    for fallback in &report.heuristics_fallback_commands {
+        let command = shlex::try_join(fallback.command.iter().map(String::as_str))
+            .unwrap_or_else(|_| fallback.command.join(" "));
+        output.push_str(&format!("  {:>5}x  {command}\n", fallback.count));
+    }
+    output
+}
+
+fn render_rule_summary(summary: &RuleSummary) -> String {
+    let decision = match summary.decision {
+        Decision::Allow => "allow",
+        Decision::Prompt => "prompt",
+        Decision::Forbidden => "forbidden",
+    };
+    match &summary.location {
+        Some(location) => format!("{location}: {} ({decision})", summary.rule),
+        None => format!("{} ({decision})", summary.rule),
+    }
+}
+
+fn collect_rollout_files(roots: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for root in roots {
+        if root.is_file() {
+            files.push(root.clone());
+        } else {
+            collect_jsonl_files(root, &mut files)?;
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+fn collect_jsonl_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_jsonl_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "jsonl")
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Extracts the shell commands the model ran from rollout JSONL `contents`.
+///
+/// Reads `shell`, `shell_command` and `exec_command` function calls plus `local_shell_call`
+/// items. String commands are wrapped as `bash -lc <script>`, matching how they are run. Lines
+/// that are not valid JSON are skipped so partially written rollouts can still be linted.
+pub fn commands_from_rollout(contents: &str) -> Vec<Vec<String>> {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<JsonValue>(line).ok())
+        .filter(|line| line.get("type").and_then(JsonValue::as_str) == Some("response_item"))
+        .filter_map(|line| command_from_response_item(line.get("payload")?))
+        .filter(|command| !command.is_empty())
+        .collect()
+}
+
+fn command_from_response_item(item: &JsonValue) -> Option<Vec<String>> {
+    match item.get("type").and_then(JsonValue::as_str)? {
+        "local_shell_call" => string_array(item.get("action")?.get("command")?),
+        "function_call" => {
+            if item
+                .get("namespace")
+                .is_some_and(|namespace| !namespace.is_null())
+            {
+                return None;
+            }
+            let arguments: JsonValue =
+                serde_json::from_str(item.get("arguments")?.as_str()?).ok()?;
+            match item.get("name").and_then(JsonValue::as_str)? {
+                "shell" | "container.exec" => string_array(arguments.get("command")?),
+                "shell_command" => shell_script(arguments.get("command")?),
+                "exec_command" => shell_script(arguments.get("cmd")?),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn string_array(value: &JsonValue) -> Option<Vec<String>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|token| token.as_str().map(str::to_string))
+        .collect()
+}
+
+fn shell_script(value: &JsonValue) -> Option<Vec<String>> {
+    let script = value.as_str()?;
+    Some(vec![
+        "bash".to_string(),
+        "-lc".to_string(),
+        script.to_string(),
+    ])
+}
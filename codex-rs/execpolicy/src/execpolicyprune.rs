@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::Utc;
+use clap::Parser;
+
+use crate::amend::blocking_prune_expired_rules;
+
+/// Arguments for removing expired rules from execpolicy files.
+#[derive(Debug, Parser, Clone)]
+pub struct ExecPolicyPruneCommand {
+    /// Paths to execpolicy rule files to prune (repeatable). Defaults to every `*.rules` file in
+    /// `$CODEX_HOME/rules`.
+    #[arg(short = 'r', long = "rules", value_name = "PATH")]
+    pub rules: Vec<PathBuf>,
+}
+
+impl ExecPolicyPruneCommand {
+    /// Remove rules whose `expires_at` has passed and report how many were removed per file.
+    pub fn run(&self) -> Result<()> {
+        let policy_paths = if self.rules.is_empty() {
+            default_policy_paths()?
+        } else {
+            self.rules.clone()
+        };
+
+        let now = Utc::now();
+        for policy_path in policy_paths {
+            let removed = blocking_prune_expired_rules(&policy_path, now)?;
+            println!(
+                "{}: removed {removed} expired rule{}",
+                policy_path.display(),
+                if removed == 1 { "" } else { "s" }
+            );
+        }
+        Ok(())
+    }
+}
+
+fn default_policy_paths() -> Result<Vec<PathBuf>> {
+    let codex_home = codex_utils_home_dir::find_codex_home()
+        .context("failed to locate CODEX_HOME; pass --rules explicitly")?;
+    let rules_dir = codex_home.join("rules").into_path_buf();
+    let entries = match fs::read_dir(&rules_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to read {}", rules_dir.display()));
+        }
+    };
+
+    let mut policy_paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file()
+            && path
+                .extension()
+                .is_some_and(|extension| extension == "rules")
+        {
+            policy_paths.push(path);
+        }
+    }
+    policy_paths.sort();
+    Ok(policy_paths)
+}
@@ -0,0 +1,174 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::decision::Decision;
+use crate::policy::MatchOptions;
+use crate::rule::PatternToken;
+use crate::rule::Rule;
+use crate::rule::RuleMatch;
+
+/// A command-line flag known to a [`FlagRule`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlagSpec {
+    /// The flag as typed, e.g. `-o` or `--upload-file`.
+    pub name: String,
+    /// Whether the flag consumes a value (`--flag value`, `--flag=value`, `-fvalue`).
+    pub takes_value: bool,
+}
+
+/// Rule that understands a program's flags, so options may appear in any order and in any of
+/// the usual spellings (`--flag=value`, `--flag value`, combined short flags like `-rf`).
+///
+/// The rule applies when the leading positional arguments match `subcommand`. It then:
+/// - matches with `Decision::Forbidden` when `decision` is forbidden and one of
+///   `forbidden_flags` is used, reporting that flag;
+/// - otherwise matches with `decision` when no `forbidden_flags` are used and every flag is
+///   listed in `allowed_flags` (any flag is accepted when `allowed_flags` is `None`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlagRule {
+    pub program: Arc<str>,
+    pub subcommand: Arc<[PatternToken]>,
+    pub allowed_flags: Option<Arc<[FlagSpec]>>,
+    pub forbidden_flags: Arc<[FlagSpec]>,
+    pub decision: Decision,
+    pub justification: Option<String>,
+}
+
+/// Flags and positional arguments of a command after the program name.
+#[derive(Debug, Default, Eq, PartialEq)]
+struct ParsedArgs<'a> {
+    flags: Vec<String>,
+    positionals: Vec<&'a str>,
+}
+
+impl FlagRule {
+    fn known_flag(&self, name: &str) -> Option<&FlagSpec> {
+        self.allowed_flags
+            .iter()
+            .flat_map(|flags| flags.iter())
+            .chain(self.forbidden_flags.iter())
+            .find(|flag| flag.name == name)
+    }
+
+    fn takes_value(&self, name: &str) -> bool {
+        self.known_flag(name).is_some_and(|flag| flag.takes_value)
+    }
+
+    fn parse_args<'a>(&self, args: &'a [String]) -> ParsedArgs<'a> {
+        let mut parsed = ParsedArgs::default();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let arg = arg.as_str();
+            if arg == "--" {
+                parsed.positionals.extend(iter.by_ref().map(String::as_str));
+                break;
+            }
+            if arg == "-" || !arg.starts_with('-') {
+                parsed.positionals.push(arg);
+                continue;
+            }
+            // Flags declared verbatim win, so single-dash long options such as `find -name`
+            // are not split into short flags.
+            if self.known_flag(arg).is_some() {
+                if self.takes_value(arg) {
+                    iter.next();
+                }
+                parsed.flags.push(arg.to_string());
+                continue;
+            }
+            if let Some((name, _value)) = arg.split_once('=') {
+                parsed.flags.push(name.to_string());
+                continue;
+            }
+            if arg.starts_with("--") {
+                parsed.flags.push(arg.to_string());
+                continue;
+            }
+
+            // Split `-abc` into `-a`, `-b`, `-c`. A flag that takes a value consumes the rest
+            // of the cluster (`-C3`), or the next argument when nothing follows it.
+            let cluster = &arg[1..];
+            for (offset, ch) in cluster.char_indices() {
+                let name = format!("-{ch}");
+                let takes_value = self.takes_value(&name);
+                parsed.flags.push(name);
+                if takes_value {
+                    if offset + ch.len_utf8() == cluster.len() {
+                        iter.next();
+                    }
+                    break;
+                }
+            }
+        }
+        parsed
+    }
+
+    fn forbidden_flag_in(&self, flags: &[String]) -> Option<&FlagSpec> {
+        self.forbidden_flags
+            .iter()
+            .find(|forbidden| flags.contains(&forbidden.name))
+    }
+
+    fn allows_all(&self, flags: &[String]) -> bool {
+        match &self.allowed_flags {
+            Some(allowed) => flags
+                .iter()
+                .all(|flag| allowed.iter().any(|allowed| &allowed.name == flag)),
+            None => true,
+        }
+    }
+}
+
+impl Rule for FlagRule {
+    fn program(&self) -> &str {
+        self.program.as_ref()
+    }
+
+    fn matches(&self, cmd: &[String], options: &MatchOptions) -> Option<RuleMatch> {
+        let (program, args) = cmd.split_first()?;
+        if program != self.program.as_ref() {
+            return None;
+        }
+
+        let parsed = self.parse_args(args);
+        if parsed.positionals.len() < self.subcommand.len() {
+            return None;
+        }
+        let subcommand_matches = self
+            .subcommand
+            .iter()
+            .zip(&parsed.positionals)
+            .all(|(pattern, arg)| pattern.matches(arg, options));
+        if !subcommand_matches {
+            return None;
+        }
+
+        let matched_prefix = std::iter::once(program.clone())
+            .chain(
+                parsed.positionals[..self.subcommand.len()]
+                    .iter()
+                    .map(ToString::to_string),
+            )
+            .collect();
+        let forbidden_flag = self.forbidden_flag_in(&parsed.flags);
+        let decision = match (self.decision, forbidden_flag) {
+            (Decision::Forbidden, Some(_)) => Decision::Forbidden,
+            (Decision::Forbidden, None) if !self.forbidden_flags.is_empty() => return None,
+            (_, Some(_)) => return None,
+            (decision, None) if self.allows_all(&parsed.flags) => decision,
+            (_, None) => return None,
+        };
+
+        Some(RuleMatch::FlagRuleMatch {
+            matched_prefix,
+            decision,
+            forbidden_flag: forbidden_flag.map(|flag| flag.name.clone()),
+            resolved_program: None,
+            justification: self.justification.clone(),
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
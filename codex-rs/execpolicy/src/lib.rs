@@ -2,14 +2,20 @@ pub(crate) mod amend;
 pub(crate) mod decision;
 pub(crate) mod error;
 pub(crate) mod execpolicycheck;
+pub(crate) mod execpolicylint;
+pub(crate) mod execpolicyprune;
 mod executable_name;
+pub(crate) mod flag_rule;
 pub(crate) mod parser;
+pub(crate) mod pattern;
 pub(crate) mod policy;
 pub mod rule;
 
 pub use amend::AmendError;
 pub use amend::blocking_append_allow_prefix_rule;
 pub use amend::blocking_append_network_rule;
+pub use amend::blocking_append_scoped_allow_prefix_rule;
+pub use amend::blocking_prune_expired_rules;
 pub use decision::Decision;
 pub use error::Error;
 pub use error::ErrorLocation;
@@ -17,7 +23,21 @@ pub use error::Result;
 pub use error::TextPosition;
 pub use error::TextRange;
 pub use execpolicycheck::ExecPolicyCheckCommand;
+pub use execpolicylint::ExecPolicyLintCommand;
+pub use execpolicylint::FallbackCommand;
+pub use execpolicylint::LintReport;
+pub use execpolicylint::RuleSummary;
+pub use execpolicylint::ShadowedRule;
+pub use execpolicylint::commands_from_rollout;
+pub use execpolicylint::format_lint_report;
+pub use execpolicylint::lint_policy;
+pub use execpolicyprune::ExecPolicyPruneCommand;
+pub use flag_rule::FlagRule;
+pub use flag_rule::FlagSpec;
 pub use parser::PolicyParser;
+pub use parser::RuleSource;
+pub use pattern::GlobToken;
+pub use pattern::RegexToken;
 pub use policy::Evaluation;
 pub use policy::MatchOptions;
 pub use policy::Policy;
@@ -28,3 +48,4 @@ pub use rule::PrefixRule;
 pub use rule::Rule;
 pub use rule::RuleMatch;
 pub use rule::RuleRef;
+pub use rule::RuleScope;
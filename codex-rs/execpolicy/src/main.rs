@@ -1,6 +1,8 @@
 use anyhow::Result;
 use clap::Parser;
 use codex_execpolicy::ExecPolicyCheckCommand;
+use codex_execpolicy::ExecPolicyLintCommand;
+use codex_execpolicy::ExecPolicyPruneCommand;
 
 /// CLI for evaluating exec policies
 #[derive(Parser)]
@@ -8,11 +10,19 @@ use codex_execpolicy::ExecPolicyCheckCommand;
 enum Cli {
     /// Evaluate a command against a policy.
     Check(ExecPolicyCheckCommand),
+
+    /// Report unused and shadowed rules by replaying recorded sessions.
+    Lint(ExecPolicyLintCommand),
+
+    /// Remove rules whose `expires_at` has passed.
+    Prune(ExecPolicyPruneCommand),
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
     match cli {
         Cli::Check(cmd) => cmd.run(),
+        Cli::Lint(cmd) => cmd.run(),
+        Cli::Prune(cmd) => cmd.run(),
     }
 }
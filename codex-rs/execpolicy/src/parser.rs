@@ -1,3 +1,6 @@
+use allocative::Allocative;
+use chrono::DateTime;
+use chrono::Utc;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use multimap::MultiMap;
 use starlark::any::ProvidesStaticType;
@@ -8,13 +11,20 @@ use starlark::eval::Evaluator;
 use starlark::starlark_module;
 use starlark::syntax::AstModule;
 use starlark::syntax::Dialect;
+use starlark::values::AllocValue;
+use starlark::values::Heap;
+use starlark::values::NoSerialize;
+use starlark::values::StarlarkValue;
 use starlark::values::Value;
+use starlark::values::ValueLike;
 use starlark::values::list::ListRef;
 use starlark::values::list::UnpackList;
 use starlark::values::none::NoneType;
+use starlark::values::starlark_value;
 use std::cell::RefCell;
 use std::cell::RefMut;
 use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -26,12 +36,17 @@ use crate::error::TextPosition;
 use crate::error::TextRange;
 use crate::executable_name::executable_lookup_key;
 use crate::executable_name::executable_path_lookup_key;
+use crate::flag_rule::FlagRule;
+use crate::flag_rule::FlagSpec;
+use crate::pattern::GlobToken;
+use crate::pattern::RegexToken;
 use crate::rule::NetworkRule;
 use crate::rule::NetworkRuleProtocol;
 use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
 use crate::rule::RuleRef;
+use crate::rule::RuleScope;
 use crate::rule::validate_match_examples;
 use crate::rule::validate_not_match_examples;
 
@@ -77,17 +92,30 @@ impl PolicyParser {
         Ok(())
     }
 
+    /// Rules parsed so far, in definition order, with the location of the call that defined them.
+    pub fn rule_sources(&self) -> Vec<RuleSource> {
+        self.builder.borrow().rule_sources.clone()
+    }
+
     pub fn build(self) -> crate::policy::Policy {
         self.builder.into_inner().build()
     }
 }
 
+/// A parsed rule and the policy file location that defined it.
+#[derive(Clone, Debug)]
+pub struct RuleSource {
+    pub rule: RuleRef,
+    pub location: Option<ErrorLocation>,
+}
+
 #[derive(Debug, ProvidesStaticType)]
 struct PolicyBuilder {
     rules_by_program: MultiMap<String, RuleRef>,
     network_rules: Vec<NetworkRule>,
     host_executables_by_name: HashMap<String, Arc<[AbsolutePathBuf]>>,
     pending_example_validations: Vec<PendingExampleValidation>,
+    rule_sources: Vec<RuleSource>,
 }
 
 impl PolicyBuilder {
@@ -97,10 +125,15 @@ impl PolicyBuilder {
             network_rules: Vec::new(),
             host_executables_by_name: HashMap::new(),
             pending_example_validations: Vec::new(),
+            rule_sources: Vec::new(),
         }
     }
 
-    fn add_rule(&mut self, rule: RuleRef) {
+    fn add_rule(&mut self, rule: RuleRef, location: Option<ErrorLocation>) {
+        self.rule_sources.push(RuleSource {
+            rule: rule.clone(),
+            location,
+        });
         self.rules_by_program
             .insert(rule.program().to_string(), rule);
     }
@@ -167,6 +200,55 @@ struct PendingExampleValidation {
     location: Option<ErrorLocation>,
 }
 
+/// Starlark value returned by the `glob()`, `regex()`, `writable_path()`, `any_token()` and
+/// `all_args()` builtins. Patterns are compiled when the value is used inside a `prefix_rule`
+/// pattern.
+#[derive(Clone, Debug, PartialEq, Eq, ProvidesStaticType, NoSerialize, Allocative)]
+enum PatternMatcherValue {
+    Glob(String),
+    Regex(String),
+    WritablePath,
+    AnyToken,
+    AllArgs(Box<PatternMatcherValue>),
+}
+
+impl PatternMatcherValue {
+    fn to_pattern_token(&self) -> Result<PatternToken> {
+        match self {
+            Self::Glob(source) => GlobToken::new(source).map(PatternToken::Glob),
+            Self::Regex(source) => RegexToken::new(source).map(PatternToken::Regex),
+            Self::WritablePath => Ok(PatternToken::WritablePath),
+            Self::AnyToken => Ok(PatternToken::Any),
+            Self::AllArgs(inner) => inner
+                .to_pattern_token()
+                .map(|inner| PatternToken::AllArgs(Box::new(inner))),
+        }
+    }
+}
+
+impl fmt::Display for PatternMatcherValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Glob(source) => write!(f, "glob({source:?})"),
+            Self::Regex(source) => write!(f, "regex({source:?})"),
+            Self::WritablePath => write!(f, "writable_path()"),
+            Self::AnyToken => write!(f, "any_token()"),
+            Self::AllArgs(inner) => write!(f, "all_args({inner})"),
+        }
+    }
+}
+
+#[starlark_value(type = "PatternMatcher")]
+impl<'v> StarlarkValue<'v> for PatternMatcherValue {
+    type Canonical = PatternMatcherValue;
+}
+
+impl<'v> AllocValue<'v> for PatternMatcherValue {
+    fn alloc_value(self, heap: &'v Heap) -> Value<'v> {
+        heap.alloc_simple(self)
+    }
+}
+
 fn parse_pattern<'v>(pattern: UnpackList<Value<'v>>) -> Result<Vec<PatternToken>> {
     let tokens: Vec<PatternToken> = pattern
         .items
@@ -174,15 +256,26 @@ fn parse_pattern<'v>(pattern: UnpackList<Value<'v>>) -> Result<Vec<PatternToken>
         .map(parse_pattern_token)
         .collect::<Result<_>>()?;
     if tokens.is_empty() {
-        Err(Error::InvalidPattern("pattern cannot be empty".to_string()))
-    } else {
-        Ok(tokens)
+        return Err(Error::InvalidPattern("pattern cannot be empty".to_string()));
+    }
+    if tokens
+        .iter()
+        .rev()
+        .skip(1)
+        .any(|token| matches!(token, PatternToken::AllArgs(_)))
+    {
+        return Err(Error::InvalidPattern(
+            "all_args() must be the last pattern element".to_string(),
+        ));
     }
+    Ok(tokens)
 }
 
 fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
     if let Some(s) = value.unpack_str() {
         Ok(PatternToken::Single(s.to_string()))
+    } else if let Some(matcher) = value.downcast_ref::<PatternMatcherValue>() {
+        matcher.to_pattern_token()
     } else if let Some(list) = ListRef::from_value(value) {
         let tokens: Vec<String> = list
             .content()
@@ -209,12 +302,86 @@ fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
         }
     } else {
         Err(Error::InvalidPattern(format!(
-            "pattern element must be a string or list of strings (got {})",
+            "pattern element must be a string, list of strings, or pattern matcher (got {})",
             value.get_type()
         )))
     }
 }
 
+/// Starlark value returned by the `flag()` builtin for flags that take a value.
+#[derive(Clone, Debug, PartialEq, Eq, ProvidesStaticType, NoSerialize, Allocative)]
+struct FlagSpecValue {
+    name: String,
+    takes_value: bool,
+}
+
+impl fmt::Display for FlagSpecValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "flag({:?}, takes_value = {})",
+            self.name,
+            if self.takes_value { "True" } else { "False" }
+        )
+    }
+}
+
+#[starlark_value(type = "Flag")]
+impl<'v> StarlarkValue<'v> for FlagSpecValue {
+    type Canonical = FlagSpecValue;
+}
+
+impl<'v> AllocValue<'v> for FlagSpecValue {
+    fn alloc_value(self, heap: &'v Heap) -> Value<'v> {
+        heap.alloc_simple(self)
+    }
+}
+
+fn validate_flag_name(name: &str) -> Result<()> {
+    if name.len() < 2 || !name.starts_with('-') || name == "--" || name.contains('=') {
+        return Err(Error::InvalidRule(format!(
+            "flag must start with `-` and cannot contain `=` (got {name:?})"
+        )));
+    }
+    Ok(())
+}
+
+fn parse_flag_specs<'v>(flags: UnpackList<Value<'v>>) -> Result<Vec<FlagSpec>> {
+    flags
+        .items
+        .into_iter()
+        .map(|value| {
+            if let Some(name) = value.unpack_str() {
+                validate_flag_name(name)?;
+                Ok(FlagSpec {
+                    name: name.to_string(),
+                    takes_value: false,
+                })
+            } else if let Some(flag) = value.downcast_ref::<FlagSpecValue>() {
+                Ok(FlagSpec {
+                    name: flag.name.clone(),
+                    takes_value: flag.takes_value,
+                })
+            } else {
+                Err(Error::InvalidRule(format!(
+                    "flags must be strings or flag() values (got {})",
+                    value.get_type()
+                )))
+            }
+        })
+        .collect()
+}
+
+fn parse_justification(justification: Option<&str>) -> Result<Option<String>> {
+    match justification {
+        Some(raw) if raw.trim().is_empty() => Err(Error::InvalidRule(
+            "justification cannot be empty".to_string(),
+        )),
+        Some(raw) => Ok(Some(raw.to_string())),
+        None => Ok(None),
+    }
+}
+
 fn parse_examples<'v>(examples: UnpackList<Value<'v>>) -> Result<Vec<Vec<String>>> {
     examples.items.into_iter().map(parse_example).collect()
 }
@@ -256,6 +423,43 @@ fn parse_network_rule_decision(raw: &str) -> Result<Decision> {
     }
 }
 
+fn parse_rule_scope(
+    expires_at: Option<&str>,
+    thread_id: Option<&str>,
+    project_root: Option<&str>,
+) -> Result<RuleScope> {
+    let expires_at = expires_at
+        .map(|raw| {
+            DateTime::parse_from_rfc3339(raw)
+                .map(|expires_at| expires_at.with_timezone(&Utc))
+                .map_err(|err| {
+                    Error::InvalidRule(format!(
+                        "expires_at must be an RFC 3339 timestamp (got {raw:?}): {err}"
+                    ))
+                })
+        })
+        .transpose()?;
+    let thread_id = match thread_id {
+        Some(raw) if raw.trim().is_empty() => {
+            return Err(Error::InvalidRule("thread_id cannot be empty".to_string()));
+        }
+        other => other.map(str::to_string),
+    };
+    let project_root = project_root
+        .map(|raw| {
+            AbsolutePathBuf::from_absolute_path_checked(raw).map_err(|_| {
+                Error::InvalidRule(format!("project_root must be an absolute path (got {raw})"))
+            })
+        })
+        .transpose()?;
+
+    Ok(RuleScope {
+        expires_at,
+        thread_id,
+        project_root,
+    })
+}
+
 fn error_location_from_file_span(span: FileSpan) -> ErrorLocation {
     let resolved = span.resolve_span();
     ErrorLocation {
@@ -343,6 +547,7 @@ fn policy_builder<'v, 'a>(eval: &Evaluator<'v, 'a, '_>) -> RefMut<'a, PolicyBuil
         .borrow_mut()
 }
 
+#[allow(clippy::too_many_arguments)]
 #[starlark_module]
 fn policy_builtins(builder: &mut GlobalsBuilder) {
     fn prefix_rule<'v>(
@@ -351,6 +556,9 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         r#match: Option<UnpackList<Value<'v>>>,
         not_match: Option<UnpackList<Value<'v>>>,
         justification: Option<&'v str>,
+        expires_at: Option<&'v str>,
+        thread_id: Option<&'v str>,
+        project_root: Option<&'v str>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<NoneType> {
         let decision = match decision {
@@ -358,13 +566,8 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
             None => Decision::Allow,
         };
 
-        let justification = match justification {
-            Some(raw) if raw.trim().is_empty() => {
-                return Err(Error::InvalidRule("justification cannot be empty".to_string()).into());
-            }
-            Some(raw) => Some(raw.to_string()),
-            None => None,
-        };
+        let justification = parse_justification(justification)?;
+        let scope = parse_rule_scope(expires_at, thread_id, project_root)?;
 
         let pattern_tokens = parse_pattern(pattern)?;
 
@@ -383,6 +586,12 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         let (first_token, remaining_tokens) = pattern_tokens
             .split_first()
             .ok_or_else(|| Error::InvalidPattern("pattern cannot be empty".to_string()))?;
+        if !first_token.is_literal() {
+            return Err(Error::InvalidPattern(
+                "first pattern element must be a string or list of strings".to_string(),
+            )
+            .into());
+        }
 
         let rest: Arc<[PatternToken]> = remaining_tokens.to_vec().into();
 
@@ -397,15 +606,152 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
                     },
                     decision,
                     justification: justification.clone(),
+                    scope: scope.clone(),
+                }) as RuleRef
+            })
+            .collect();
+
+        builder.add_pending_example_validation(
+            rules.clone(),
+            matches,
+            not_matches,
+            location.clone(),
+        );
+        rules
+            .into_iter()
+            .for_each(|rule| builder.add_rule(rule, location.clone()));
+        Ok(NoneType)
+    }
+
+    fn glob(pattern: &str) -> anyhow::Result<PatternMatcherValue> {
+        GlobToken::new(pattern)?;
+        Ok(PatternMatcherValue::Glob(pattern.to_string()))
+    }
+
+    fn regex(pattern: &str) -> anyhow::Result<PatternMatcherValue> {
+        RegexToken::new(pattern)?;
+        Ok(PatternMatcherValue::Regex(pattern.to_string()))
+    }
+
+    fn writable_path() -> anyhow::Result<PatternMatcherValue> {
+        Ok(PatternMatcherValue::WritablePath)
+    }
+
+    fn any_token() -> anyhow::Result<PatternMatcherValue> {
+        Ok(PatternMatcherValue::AnyToken)
+    }
+
+    fn all_args<'v>(matcher: Value<'v>) -> anyhow::Result<PatternMatcherValue> {
+        match matcher.downcast_ref::<PatternMatcherValue>() {
+            Some(PatternMatcherValue::AllArgs(_)) | None => Err(Error::InvalidPattern(format!(
+                "all_args() takes a glob(), regex(), writable_path() or any_token() matcher (got {})",
+                matcher.get_type()
+            ))
+            .into()),
+            Some(matcher) => Ok(PatternMatcherValue::AllArgs(Box::new(matcher.clone()))),
+        }
+    }
+
+    fn flag_rule<'v>(
+        program: Value<'v>,
+        subcommand: Option<UnpackList<Value<'v>>>,
+        allowed_flags: Option<UnpackList<Value<'v>>>,
+        forbidden_flags: Option<UnpackList<Value<'v>>>,
+        decision: Option<&'v str>,
+        r#match: Option<UnpackList<Value<'v>>>,
+        not_match: Option<UnpackList<Value<'v>>>,
+        justification: Option<&'v str>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<NoneType> {
+        let decision = match decision {
+            Some(raw) => Decision::parse(raw)?,
+            None => Decision::Allow,
+        };
+        let justification = parse_justification(justification)?;
+
+        let program = parse_pattern_token(program)?;
+        if !program.is_literal() {
+            return Err(Error::InvalidRule(
+                "flag_rule program must be a string or list of strings".to_string(),
+            )
+            .into());
+        }
+        let subcommand: Arc<[PatternToken]> = subcommand
+            .map(|subcommand| {
+                subcommand
+                    .items
+                    .into_iter()
+                    .map(parse_pattern_token)
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?
+            .unwrap_or_default()
+            .into();
+        if subcommand
+            .iter()
+            .any(|token| matches!(token, PatternToken::AllArgs(_)))
+        {
+            return Err(Error::InvalidRule(
+                "all_args() is only supported in prefix_rule patterns".to_string(),
+            )
+            .into());
+        }
+        let allowed_flags: Option<Arc<[FlagSpec]>> = allowed_flags
+            .map(parse_flag_specs)
+            .transpose()?
+            .map(Into::into);
+        let forbidden_flags: Arc<[FlagSpec]> = forbidden_flags
+            .map(parse_flag_specs)
+            .transpose()?
+            .unwrap_or_default()
+            .into();
+
+        let matches: Vec<Vec<String>> =
+            r#match.map(parse_examples).transpose()?.unwrap_or_default();
+        let not_matches: Vec<Vec<String>> = not_match
+            .map(parse_examples)
+            .transpose()?
+            .unwrap_or_default();
+        let location = eval
+            .call_stack_top_location()
+            .map(error_location_from_file_span);
+
+        let mut builder = policy_builder(eval);
+        let rules: Vec<RuleRef> = program
+            .alternatives()
+            .iter()
+            .map(|program| {
+                Arc::new(FlagRule {
+                    program: Arc::from(program.as_str()),
+                    subcommand: subcommand.clone(),
+                    allowed_flags: allowed_flags.clone(),
+                    forbidden_flags: forbidden_flags.clone(),
+                    decision,
+                    justification: justification.clone(),
                 }) as RuleRef
             })
             .collect();
 
-        builder.add_pending_example_validation(rules.clone(), matches, not_matches, location);
-        rules.into_iter().for_each(|rule| builder.add_rule(rule));
+        builder.add_pending_example_validation(
+            rules.clone(),
+            matches,
+            not_matches,
+            location.clone(),
+        );
+        rules
+            .into_iter()
+            .for_each(|rule| builder.add_rule(rule, location.clone()));
         Ok(NoneType)
     }
 
+    fn flag(name: &str, takes_value: Option<bool>) -> anyhow::Result<FlagSpecValue> {
+        validate_flag_name(name)?;
+        Ok(FlagSpecValue {
+            name: name.to_string(),
+            takes_value: takes_value.unwrap_or(false),
+        })
+    }
+
     fn network_rule<'v>(
         host: &'v str,
         protocol: &'v str,
@@ -415,13 +761,7 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
     ) -> anyhow::Result<NoneType> {
         let protocol = NetworkRuleProtocol::parse(protocol)?;
         let decision = parse_network_rule_decision(decision)?;
-        let justification = match justification {
-            Some(raw) if raw.trim().is_empty() => {
-                return Err(Error::InvalidRule("justification cannot be empty".to_string()).into());
-            }
-            Some(raw) => Some(raw.to_string()),
-            None => None,
-        };
+        let justification = parse_justification(justification)?;
 
         let mut builder = policy_builder(eval);
         builder.add_network_rule(NetworkRule {
@@ -0,0 +1,169 @@
+use codex_utils_absolute_path::AbsolutePathBuf;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use regex::Regex;
+use std::fmt;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::policy::MatchOptions;
+
+/// Shell-style glob matched against a whole command token.
+///
+/// `*` and `?` never match `/`, so `glob("*.txt")` matches `notes.txt` but not
+/// `docs/notes.txt`; use `**` to cross directory boundaries.
+#[derive(Clone)]
+pub struct GlobToken {
+    source: String,
+    matcher: GlobMatcher,
+}
+
+impl GlobToken {
+    pub fn new(source: &str) -> Result<Self> {
+        if source.is_empty() {
+            return Err(Error::InvalidPattern("glob cannot be empty".to_string()));
+        }
+        let glob = GlobBuilder::new(source)
+            .literal_separator(true)
+            .backslash_escape(true)
+            .build()
+            .map_err(|err| Error::InvalidPattern(format!("invalid glob `{source}`: {err}")))?;
+        Ok(Self {
+            source: source.to_string(),
+            matcher: glob.compile_matcher(),
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub(crate) fn is_match(&self, token: &str) -> bool {
+        self.matcher.is_match(token)
+    }
+}
+
+impl fmt::Debug for GlobToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("GlobToken").field(&self.source).finish()
+    }
+}
+
+impl PartialEq for GlobToken {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for GlobToken {}
+
+/// Regular expression that must match an entire command token.
+#[derive(Clone)]
+pub struct RegexToken {
+    source: String,
+    regex: Regex,
+}
+
+impl RegexToken {
+    pub fn new(source: &str) -> Result<Self> {
+        if source.is_empty() {
+            return Err(Error::InvalidPattern("regex cannot be empty".to_string()));
+        }
+        // Anchor the expression so `regex("v[0-9]+")` cannot match `xv1y`.
+        let regex = Regex::new(&format!("^(?:{source})$"))
+            .map_err(|err| Error::InvalidPattern(format!("invalid regex `{source}`: {err}")))?;
+        Ok(Self {
+            source: source.to_string(),
+            regex,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub(crate) fn is_match(&self, token: &str) -> bool {
+        self.regex.is_match(token)
+    }
+}
+
+impl fmt::Debug for RegexToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RegexToken").field(&self.source).finish()
+    }
+}
+
+impl PartialEq for RegexToken {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for RegexToken {}
+
+/// Returns true when `token` names a path inside one of `options.writable_roots`
+/// and outside every `options.read_only_paths` carveout.
+///
+/// Relative tokens are resolved against `options.cwd`; without a cwd only
+/// absolute tokens can match. Tokens that look like flags never match. The
+/// symlink-resolved location of the path, or of its nearest existing ancestor
+/// for paths that do not exist yet, must also stay inside a writable root so a
+/// link cannot smuggle writes elsewhere.
+pub(crate) fn is_writable_path(token: &str, options: &MatchOptions) -> bool {
+    if token.is_empty() || token.starts_with('-') || options.writable_roots.is_empty() {
+        return false;
+    }
+
+    let path = match &options.cwd {
+        Some(cwd) => cwd.join(token),
+        None => match AbsolutePathBuf::from_absolute_path_checked(token) {
+            Ok(path) => path,
+            Err(_) => return false,
+        },
+    };
+
+    if !is_under_writable_root(&path, options) {
+        return false;
+    }
+
+    match canonicalize_nearest_existing(&path) {
+        Some(canonical) if canonical == path => true,
+        Some(canonical) => {
+            let canonical_options = MatchOptions {
+                writable_roots: canonicalize_all(&options.writable_roots),
+                read_only_paths: canonicalize_all(&options.read_only_paths),
+                ..options.clone()
+            };
+            is_under_writable_root(&canonical, &canonical_options)
+        }
+        None => false,
+    }
+}
+
+/// Canonicalizes the nearest existing ancestor of `path` and re-appends the
+/// components that do not exist yet.
+fn canonicalize_nearest_existing(path: &AbsolutePathBuf) -> Option<AbsolutePathBuf> {
+    path.ancestors().find_map(|ancestor| {
+        let canonical = ancestor.canonicalize().ok()?;
+        let missing = path.as_path().strip_prefix(ancestor.as_path()).ok()?;
+        Some(canonical.join(missing))
+    })
+}
+
+fn is_under_writable_root(path: &AbsolutePathBuf, options: &MatchOptions) -> bool {
+    options
+        .writable_roots
+        .iter()
+        .any(|root| path.as_path().starts_with(root.as_path()))
+        && !options
+            .read_only_paths
+            .iter()
+            .any(|read_only| path.as_path().starts_with(read_only.as_path()))
+}
+
+fn canonicalize_all(paths: &[AbsolutePathBuf]) -> Vec<AbsolutePathBuf> {
+    paths
+        .iter()
+        .map(|path| path.canonicalize().unwrap_or_else(|_| path.clone()))
+        .collect()
+}
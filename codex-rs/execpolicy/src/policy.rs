@@ -9,7 +9,9 @@ use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
 use crate::rule::RuleMatch;
 use crate::rule::RuleRef;
+use crate::rule::RuleScope;
 use crate::rule::normalize_network_rule_host;
+use chrono::Utc;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use multimap::MultiMap;
 use serde::Deserialize;
@@ -22,6 +24,17 @@ type HeuristicsFallback<'a> = Option<&'a dyn Fn(&[String]) -> Decision>;
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct MatchOptions {
     pub resolve_host_executables: bool,
+    /// Directory used to resolve relative path tokens for `writable_path()` patterns.
+    pub cwd: Option<AbsolutePathBuf>,
+    /// Roots that `writable_path()` patterns accept. When empty, those patterns never match.
+    pub writable_roots: Vec<AbsolutePathBuf>,
+    /// Carveouts inside `writable_roots` that `writable_path()` patterns reject.
+    pub read_only_paths: Vec<AbsolutePathBuf>,
+    /// Thread evaluating the command. Rules scoped to another thread are skipped.
+    pub thread_id: Option<String>,
+    /// Match rules regardless of their thread, project root or expiry, as when validating
+    /// `match` / `not_match` examples.
+    pub ignore_scope: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -66,13 +79,16 @@ impl Policy {
 
     pub fn get_allowed_prefixes(&self) -> Vec<Vec<String>> {
         let mut prefixes = Vec::new();
+        let now = Utc::now();
 
         for (_program, rules) in self.rules_by_program.iter_all() {
             for rule in rules {
                 let Some(prefix_rule) = rule.as_any().downcast_ref::<PrefixRule>() else {
                     continue;
                 };
-                if prefix_rule.decision != Decision::Allow {
+                if prefix_rule.decision != Decision::Allow
+                    || !prefix_rule.scope.applies_everywhere_at(now)
+                {
                     continue;
                 }
 
@@ -89,6 +105,16 @@ impl Policy {
     }
 
     pub fn add_prefix_rule(&mut self, prefix: &[String], decision: Decision) -> Result<()> {
+        self.add_scoped_prefix_rule(prefix, decision, RuleScope::default())
+    }
+
+    /// Like [`Policy::add_prefix_rule`], but the rule is only in effect within `scope`.
+    pub fn add_scoped_prefix_rule(
+        &mut self,
+        prefix: &[String],
+        decision: Decision,
+        scope: RuleScope,
+    ) -> Result<()> {
         let (first_token, rest) = prefix
             .split_first()
             .ok_or_else(|| Error::InvalidPattern("prefix cannot be empty".to_string()))?;
@@ -104,6 +130,7 @@ impl Policy {
             },
             decision,
             justification: None,
+            scope,
         });
 
         self.rules_by_program.insert(first_token.clone(), rule);
@@ -271,16 +298,11 @@ impl Policy {
         heuristics_fallback: HeuristicsFallback<'_>,
         options: &MatchOptions,
     ) -> Vec<RuleMatch> {
-        let matched_rules = self
-            .match_exact_rules(cmd)
-            .filter(|matched_rules| !matched_rules.is_empty())
-            .or_else(|| {
-                options
-                    .resolve_host_executables
-                    .then(|| self.match_host_executable_rules(cmd))
-                    .filter(|matched_rules| !matched_rules.is_empty())
-            })
-            .unwrap_or_default();
+        let matched_rules: Vec<RuleMatch> = self
+            .matching_rules_with_options(cmd, options)
+            .into_iter()
+            .map(|(_rule, rule_match)| rule_match)
+            .collect();
 
         if matched_rules.is_empty()
             && let Some(heuristics_fallback) = heuristics_fallback
@@ -294,17 +316,53 @@ impl Policy {
         }
     }
 
-    fn match_exact_rules(&self, cmd: &[String]) -> Option<Vec<RuleMatch>> {
+    /// Returns each rule that matches `cmd` alongside its match. Exact first-token rules are
+    /// tried first; basename rules are only consulted when none match and
+    /// `options.resolve_host_executables` is set.
+    pub fn matching_rules_with_options(
+        &self,
+        cmd: &[String],
+        options: &MatchOptions,
+    ) -> Vec<(RuleRef, RuleMatch)> {
+        self.match_exact_rules(cmd, options)
+            .filter(|matched_rules| !matched_rules.is_empty())
+            .or_else(|| {
+                options
+                    .resolve_host_executables
+                    .then(|| self.match_host_executable_rules(cmd, options))
+                    .filter(|matched_rules| !matched_rules.is_empty())
+            })
+            .unwrap_or_default()
+    }
+
+    fn match_exact_rules(
+        &self,
+        cmd: &[String],
+        options: &MatchOptions,
+    ) -> Option<Vec<(RuleRef, RuleMatch)>> {
         let first = cmd.first()?;
         Some(
             self.rules_by_program
                 .get_vec(first)
-                .map(|rules| rules.iter().filter_map(|rule| rule.matches(cmd)).collect())
+                .map(|rules| {
+                    rules
+                        .iter()
+                        .filter(|rule| options.ignore_scope || rule.is_active(options))
+                        .filter_map(|rule| {
+                            rule.matches(cmd, options)
+                                .map(|rule_match| (rule.clone(), rule_match))
+                        })
+                        .collect()
+                })
                 .unwrap_or_default(),
         )
     }
 
-    fn match_host_executable_rules(&self, cmd: &[String]) -> Vec<RuleMatch> {
+    fn match_host_executable_rules(
+        &self,
+        cmd: &[String],
+        options: &MatchOptions,
+    ) -> Vec<(RuleRef, RuleMatch)> {
         let Some(first) = cmd.first() else {
             return Vec::new();
         };
@@ -328,8 +386,11 @@ impl Policy {
             .collect::<Vec<_>>();
         rules
             .iter()
-            .filter_map(|rule| rule.matches(&basename_command))
-            .map(|rule_match| rule_match.with_resolved_program(&program))
+            .filter(|rule| options.ignore_scope || rule.is_active(options))
+            .filter_map(|rule| {
+                rule.matches(&basename_command, options)
+                    .map(|rule_match| (rule.clone(), rule_match.with_resolved_program(&program)))
+            })
             .collect()
     }
 }
@@ -339,10 +400,15 @@ fn upsert_domain(entries: &mut Vec<String>, host: &str) {
     entries.push(host.to_string());
 }
 
-fn render_pattern_token(token: &PatternToken) -> String {
+pub(crate) fn render_pattern_token(token: &PatternToken) -> String {
     match token {
         PatternToken::Single(value) => value.clone(),
         PatternToken::Alts(alternatives) => format!("[{}]", alternatives.join("|")),
+        PatternToken::Glob(glob) => format!("glob({:?})", glob.as_str()),
+        PatternToken::Regex(regex) => format!("regex({:?})", regex.as_str()),
+        PatternToken::WritablePath => "writable_path()".to_string(),
+        PatternToken::Any => "any_token()".to_string(),
+        PatternToken::AllArgs(inner) => format!("all_args({})", render_pattern_token(inner)),
     }
 }
 
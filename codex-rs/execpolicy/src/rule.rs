@@ -1,8 +1,13 @@
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::pattern::GlobToken;
+use crate::pattern::RegexToken;
+use crate::pattern::is_writable_path;
 use crate::policy::MatchOptions;
 use crate::policy::Policy;
+use chrono::DateTime;
+use chrono::Utc;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use serde::Deserialize;
 use serde::Serialize;
@@ -11,27 +16,50 @@ use std::any::Any;
 use std::fmt::Debug;
 use std::sync::Arc;
 
-/// Matches a single command token, either a fixed string or one of several allowed alternatives.
+/// Matches a single command token: a fixed string, one of several allowed alternatives, or one of
+/// the non-literal matchers created by the `glob()`, `regex()`, `writable_path()`, `any_token()`
+/// and `all_args()` policy builtins.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PatternToken {
     Single(String),
     Alts(Vec<String>),
+    Glob(GlobToken),
+    Regex(RegexToken),
+    /// A path under one of the writable roots supplied through [`MatchOptions`].
+    WritablePath,
+    Any,
+    /// Applies the inner matcher to every remaining argument. Only valid as the last pattern
+    /// element.
+    AllArgs(Box<PatternToken>),
 }
 
 impl PatternToken {
-    fn matches(&self, token: &str) -> bool {
+    pub(crate) fn matches(&self, token: &str, options: &MatchOptions) -> bool {
         match self {
             Self::Single(expected) => expected == token,
             Self::Alts(alternatives) => alternatives.iter().any(|alt| alt == token),
+            Self::Glob(glob) => glob.is_match(token),
+            Self::Regex(regex) => regex.is_match(token),
+            Self::WritablePath => is_writable_path(token, options),
+            Self::Any => true,
+            Self::AllArgs(inner) => inner.matches(token, options),
         }
     }
 
+    /// Literal strings matched by this token; empty for non-literal matchers.
     pub fn alternatives(&self) -> &[String] {
         match self {
             Self::Single(expected) => std::slice::from_ref(expected),
             Self::Alts(alternatives) => alternatives,
+            Self::Glob(_) | Self::Regex(_) | Self::WritablePath | Self::Any | Self::AllArgs(_) => {
+                &[]
+            }
         }
     }
+
+    pub fn is_literal(&self) -> bool {
+        matches!(self, Self::Single(_) | Self::Alts(_))
+    }
 }
 
 /// Prefix matcher for commands with support for alternative match tokens.
@@ -43,19 +71,31 @@ pub struct PrefixPattern {
 }
 
 impl PrefixPattern {
-    pub fn matches_prefix(&self, cmd: &[String]) -> Option<Vec<String>> {
-        let pattern_length = self.rest.len() + 1;
+    /// Returns the matched prefix of `cmd`, which is the whole command when the pattern ends
+    /// with `all_args()`.
+    pub fn matches_prefix(&self, cmd: &[String], options: &MatchOptions) -> Option<Vec<String>> {
+        let (fixed, all_args) = match self.rest.split_last() {
+            Some((PatternToken::AllArgs(inner), fixed)) => (fixed, Some(inner.as_ref())),
+            _ => (self.rest.as_ref(), None),
+        };
+        let pattern_length = fixed.len() + 1;
         if cmd.len() < pattern_length || cmd[0] != self.first.as_ref() {
             return None;
         }
 
-        for (pattern_token, cmd_token) in self.rest.iter().zip(&cmd[1..pattern_length]) {
-            if !pattern_token.matches(cmd_token) {
+        for (pattern_token, cmd_token) in fixed.iter().zip(&cmd[1..pattern_length]) {
+            if !pattern_token.matches(cmd_token, options) {
                 return None;
             }
         }
 
-        Some(cmd[..pattern_length].to_vec())
+        match all_args {
+            Some(inner) => cmd[pattern_length..]
+                .iter()
+                .all(|cmd_token| inner.matches(cmd_token, options))
+                .then(|| cmd.to_vec()),
+            None => Some(cmd[..pattern_length].to_vec()),
+        }
     }
 }
 
@@ -75,6 +115,19 @@ pub enum RuleMatch {
         #[serde(skip_serializing_if = "Option::is_none")]
         justification: Option<String>,
     },
+    FlagRuleMatch {
+        /// Program and subcommand tokens that matched; flags are not included.
+        #[serde(rename = "matchedPrefix")]
+        matched_prefix: Vec<String>,
+        decision: Decision,
+        /// The flag that made a `forbidden` rule match.
+        #[serde(rename = "forbiddenFlag", skip_serializing_if = "Option::is_none")]
+        forbidden_flag: Option<String>,
+        #[serde(rename = "resolvedProgram", skip_serializing_if = "Option::is_none")]
+        resolved_program: Option<AbsolutePathBuf>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        justification: Option<String>,
+    },
     HeuristicsRuleMatch {
         command: Vec<String>,
         decision: Decision,
@@ -85,6 +138,7 @@ impl RuleMatch {
     pub fn decision(&self) -> Decision {
         match self {
             Self::PrefixRuleMatch { decision, .. } => *decision,
+            Self::FlagRuleMatch { decision, .. } => *decision,
             Self::HeuristicsRuleMatch { decision, .. } => *decision,
         }
     }
@@ -102,6 +156,19 @@ impl RuleMatch {
                 resolved_program: Some(resolved_program.clone()),
                 justification,
             },
+            Self::FlagRuleMatch {
+                matched_prefix,
+                decision,
+                forbidden_flag,
+                justification,
+                ..
+            } => Self::FlagRuleMatch {
+                matched_prefix,
+                decision,
+                forbidden_flag,
+                resolved_program: Some(resolved_program.clone()),
+                justification,
+            },
             other => other,
         }
     }
@@ -112,6 +179,52 @@ pub struct PrefixRule {
     pub pattern: PrefixPattern,
     pub decision: Decision,
     pub justification: Option<String>,
+    pub scope: RuleScope,
+}
+
+/// Limits when a rule is in effect. The default scope applies everywhere and never expires.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RuleScope {
+    /// The rule is ignored at and after this instant.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// The rule only applies to commands run by this thread.
+    pub thread_id: Option<String>,
+    /// The rule only applies to commands whose working directory is inside this directory.
+    pub project_root: Option<AbsolutePathBuf>,
+}
+
+impl RuleScope {
+    pub fn is_unrestricted(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Returns true when the rule applies to every thread and directory and has not expired.
+    pub fn applies_everywhere_at(&self, now: DateTime<Utc>) -> bool {
+        self.thread_id.is_none() && self.project_root.is_none() && !self.is_expired_at(now)
+    }
+
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Returns true when the rule applies to a command evaluated with `options` right now.
+    pub fn is_active(&self, options: &MatchOptions) -> bool {
+        if self.is_expired_at(Utc::now()) {
+            return false;
+        }
+        if let Some(thread_id) = &self.thread_id
+            && options.thread_id.as_ref() != Some(thread_id)
+        {
+            return false;
+        }
+        match &self.project_root {
+            Some(project_root) => options
+                .cwd
+                .as_ref()
+                .is_some_and(|cwd| cwd.as_path().starts_with(project_root.as_path())),
+            None => true,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -214,7 +327,13 @@ pub(crate) fn normalize_network_rule_host(raw: &str) -> Result<String> {
 pub trait Rule: Any + Debug + Send + Sync {
     fn program(&self) -> &str;
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch>;
+    fn matches(&self, cmd: &[String], options: &MatchOptions) -> Option<RuleMatch>;
+
+    /// Whether the rule is in effect for `options`. Policy evaluation skips inactive rules;
+    /// `match` / `not_match` examples are validated regardless.
+    fn is_active(&self, _options: &MatchOptions) -> bool {
+        true
+    }
 
     fn as_any(&self) -> &dyn Any;
 }
@@ -226,9 +345,9 @@ impl Rule for PrefixRule {
         self.pattern.first.as_ref()
     }
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch> {
+    fn matches(&self, cmd: &[String], options: &MatchOptions) -> Option<RuleMatch> {
         self.pattern
-            .matches_prefix(cmd)
+            .matches_prefix(cmd, options)
             .map(|matched_prefix| RuleMatch::PrefixRuleMatch {
                 matched_prefix,
                 decision: self.decision,
@@ -237,11 +356,35 @@ impl Rule for PrefixRule {
             })
     }
 
+    fn is_active(&self, options: &MatchOptions) -> bool {
+        self.scope.is_active(options)
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
 }
 
+/// Examples are validated without a real sandbox, so `writable_path()` treats relative example
+/// paths as inside a synthetic workspace root and absolute example paths as outside of it. Rule
+/// scopes are ignored so scoped and expired rules keep loading.
+fn example_match_options() -> MatchOptions {
+    let root = if cfg!(windows) {
+        r"C:\codex-example-workspace"
+    } else {
+        "/codex-example-workspace"
+    };
+    let root = AbsolutePathBuf::from_absolute_path_checked(root).ok();
+    MatchOptions {
+        resolve_host_executables: true,
+        writable_roots: root.iter().cloned().collect(),
+        read_only_paths: Vec::new(),
+        cwd: root,
+        thread_id: None,
+        ignore_scope: true,
+    }
+}
+
 /// Count how many rules match each provided example and error if any example is unmatched.
 pub(crate) fn validate_match_examples(
     policy: &Policy,
@@ -249,9 +392,7 @@ pub(crate) fn validate_match_examples(
     matches: &[Vec<String>],
 ) -> Result<()> {
     let mut unmatched_examples = Vec::new();
-    let options = MatchOptions {
-        resolve_host_executables: true,
-    };
+    let options = example_match_options();
 
     for example in matches {
         if !policy
@@ -284,9 +425,7 @@ pub(crate) fn validate_not_match_examples(
     _rules: &[RuleRef],
     not_matches: &[Vec<String>],
 ) -> Result<()> {
-    let options = MatchOptions {
-        resolve_host_executables: true,
-    };
+    let options = example_match_options();
 
     for example in not_matches {
         if let Some(rule) = policy
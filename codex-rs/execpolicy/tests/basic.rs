@@ -17,6 +17,7 @@ use codex_execpolicy::PrefixPattern;
 use codex_execpolicy::PrefixRule;
 use codex_execpolicy::RuleMatch;
 use codex_execpolicy::RuleRef;
+use codex_execpolicy::RuleScope;
 use codex_execpolicy::blocking_append_allow_prefix_rule;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use pretty_assertions::assert_eq;
@@ -260,6 +261,7 @@ fn add_prefix_rule_extends_policy() -> Result<()> {
             },
             decision: Decision::Prompt,
             justification: None,
+            scope: RuleScope::default(),
         })],
         rules
     );
@@ -321,6 +323,7 @@ prefix_rule(
                 },
                 decision: Decision::Prompt,
                 justification: None,
+                scope: RuleScope::default(),
             }),
             RuleSnapshot::Prefix(PrefixRule {
                 pattern: PrefixPattern {
@@ -329,6 +332,7 @@ prefix_rule(
                 },
                 decision: Decision::Forbidden,
                 justification: None,
+                scope: RuleScope::default(),
             }),
         ],
         git_rules
@@ -398,6 +402,7 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            scope: RuleScope::default(),
         })],
         bash_rules
     );
@@ -409,6 +414,7 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            scope: RuleScope::default(),
         })],
         sh_rules
     );
@@ -470,6 +476,7 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            scope: RuleScope::default(),
         })],
         rules
     );
@@ -507,6 +514,422 @@ prefix_rule(
     Ok(())
 }
 
+#[test]
+fn glob_and_regex_patterns_match_whole_tokens() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["cat", glob("*.log")],
+    match = ["cat build.log"],
+    not_match = ["cat logs/build.log", "cat build.log.bak"],
+)
+prefix_rule(
+    pattern = ["git", "checkout", regex("release/v[0-9]+")],
+    decision = "prompt",
+    match = ["git checkout release/v12"],
+    not_match = ["git checkout release/v12-hotfix", "git checkout my-release/v1"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let cat_eval = policy.check(&tokens(&["cat", "server.log"]), &prompt_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["cat", "server.log"]),
+                decision: Decision::Allow,
+                resolved_program: None,
+                justification: None,
+            }],
+        },
+        cat_eval
+    );
+
+    let checkout_eval = policy.check(
+        &tokens(&["git", "checkout", "release/v3", "--force"]),
+        &allow_all,
+    );
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Prompt,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["git", "checkout", "release/v3"]),
+                decision: Decision::Prompt,
+                resolved_program: None,
+                justification: None,
+            }],
+        },
+        checkout_eval
+    );
+    Ok(())
+}
+
+#[test]
+fn any_token_matches_a_single_token() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", "push", any_token(), "main"],
+    decision = "forbidden",
+    match = ["git push origin main", "git push upstream main --force"],
+    not_match = ["git push origin", "git push origin dev"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(&tokens(&["git", "push", "fork", "main"]), &allow_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["git", "push", "fork", "main"]),
+                decision: Decision::Forbidden,
+                resolved_program: None,
+                justification: None,
+            }],
+        },
+        evaluation
+    );
+    Ok(())
+}
+
+#[test]
+fn writable_path_matches_paths_under_writable_roots() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["rm", writable_path()],
+    match = ["rm build/output.txt"],
+    not_match = ["rm /etc/passwd", "rm ../outside.txt", "rm -rf"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let workspace = tempdir().context("create temp dir")?;
+    let workspace_root = AbsolutePathBuf::try_from(workspace.path().to_path_buf())?;
+    let options = MatchOptions {
+        cwd: Some(workspace_root.clone()),
+        writable_roots: vec![workspace_root.clone()],
+        read_only_paths: vec![workspace_root.join(".git")],
+        ..Default::default()
+    };
+
+    let inside = policy.check_with_options(&tokens(&["rm", "src/lib.rs"]), &prompt_all, &options);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["rm", "src/lib.rs"]),
+                decision: Decision::Allow,
+                resolved_program: None,
+                justification: None,
+            }],
+        },
+        inside
+    );
+
+    let absolute_inside = workspace_root
+        .join("notes.txt")
+        .to_string_lossy()
+        .to_string();
+    assert!(
+        policy
+            .check_with_options(&["rm".to_string(), absolute_inside], &prompt_all, &options)
+            .is_match()
+    );
+
+    let outside = workspace_root
+        .parent()
+        .context("workspace has a parent")?
+        .join("outside.txt")
+        .to_string_lossy()
+        .to_string();
+    for cmd in [
+        vec!["rm".to_string(), outside],
+        tokens(&["rm", "../escape.txt"]),
+        tokens(&["rm", ".git/config"]),
+    ] {
+        assert_eq!(
+            Evaluation {
+                decision: Decision::Prompt,
+                matched_rules: vec![RuleMatch::HeuristicsRuleMatch {
+                    command: cmd.clone(),
+                    decision: Decision::Prompt,
+                }],
+            },
+            policy.check_with_options(&cmd, &prompt_all, &options)
+        );
+    }
+
+    assert!(
+        !policy
+            .check(&tokens(&["rm", "src/lib.rs"]), &prompt_all)
+            .is_match(),
+        "writable_path() should not match without writable roots"
+    );
+    Ok(())
+}
+
+#[test]
+fn all_args_checks_every_remaining_argument() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["rm", all_args(writable_path())],
+    match = ["rm build/output.txt", "rm build/a.txt build/b.txt"],
+    not_match = ["rm build/output.txt /etc/passwd", "rm -rf build"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let workspace = tempdir().context("create temp dir")?;
+    let workspace_root = AbsolutePathBuf::try_from(workspace.path().to_path_buf())?;
+    let options = MatchOptions {
+        cwd: Some(workspace_root.clone()),
+        writable_roots: vec![workspace_root],
+        ..Default::default()
+    };
+    let cmd = tokens(&["rm", "src/lib.rs", "src/main.rs"]);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: cmd.clone(),
+                decision: Decision::Allow,
+                resolved_program: None,
+                justification: None,
+            }],
+        },
+        policy.check_with_options(&cmd, &prompt_all, &options)
+    );
+    assert!(
+        !policy
+            .check_with_options(
+                &tokens(&["rm", "src/lib.rs", "../escape.txt"]),
+                &prompt_all,
+                &options
+            )
+            .is_match()
+    );
+
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse(
+            "test.rules",
+            r#"prefix_rule(pattern = ["rm", all_args(writable_path()), "-f"])"#,
+        )
+        .expect_err("all_args() before another element should fail");
+    assert!(
+        err.to_string()
+            .contains("all_args() must be the last pattern element"),
+        "unexpected error: {err}"
+    );
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn writable_path_resolves_symlinks_in_missing_paths() -> Result<()> {
+    let policy_src = r#"prefix_rule(pattern = ["touch", writable_path()])"#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let outside = tempdir().context("create outside dir")?;
+    let workspace = tempdir().context("create temp dir")?;
+    std::os::unix::fs::symlink(outside.path(), workspace.path().join("link"))?;
+    let workspace_root = AbsolutePathBuf::try_from(workspace.path().to_path_buf())?;
+    let options = MatchOptions {
+        cwd: Some(workspace_root.clone()),
+        writable_roots: vec![workspace_root],
+        ..Default::default()
+    };
+
+    for (cmd, expected) in [
+        (tokens(&["touch", "link/newfile"]), false),
+        (tokens(&["touch", "link/newdir/newfile"]), false),
+        (tokens(&["touch", "newdir/newfile"]), true),
+    ] {
+        assert_eq!(
+            expected,
+            policy
+                .check_with_options(&cmd, &prompt_all, &options)
+                .is_match(),
+            "{cmd:?}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn pattern_matchers_cannot_be_the_first_token() {
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse(
+            "test.rules",
+            r#"prefix_rule(pattern = [glob("git*"), "status"])"#,
+        )
+        .expect_err("non-literal first token should fail");
+    assert!(
+        err.to_string()
+            .contains("first pattern element must be a string or list of strings"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn invalid_regex_is_rejected_at_load_time() {
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse(
+            "test.rules",
+            r#"prefix_rule(pattern = ["git", regex("(unclosed")])"#,
+        )
+        .expect_err("invalid regex should fail");
+    assert!(
+        err.to_string().contains("invalid regex `(unclosed`"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn flag_rule_ignores_flag_order_and_spelling() -> Result<()> {
+    let policy_src = r#"
+flag_rule(
+    program = "git",
+    subcommand = ["status"],
+    allowed_flags = [flag("-C", takes_value = True), "--short", "-s", "-b"],
+    match = ["git status", "git -C repo status --short", "git status -sb"],
+    not_match = ["git status --porcelain", "git -C status log"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(
+        &tokens(&["git", "-C", "status", "status", "-bs"]),
+        &prompt_all,
+    );
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Allow,
+            matched_rules: vec![RuleMatch::FlagRuleMatch {
+                matched_prefix: tokens(&["git", "status"]),
+                decision: Decision::Allow,
+                forbidden_flag: None,
+                resolved_program: None,
+                justification: None,
+            }],
+        },
+        evaluation
+    );
+    Ok(())
+}
+
+#[test]
+fn flag_rule_reports_forbidden_flag() -> Result<()> {
+    let policy_src = r#"
+flag_rule(
+    program = "curl",
+    forbidden_flags = [
+        flag("-o", takes_value = True),
+        flag("--upload-file", takes_value = True),
+    ],
+    decision = "forbidden",
+    justification = "do not write or upload files with curl",
+    match = ["curl -o out.html https://example.com", "curl -sSo out.html https://example.com"],
+    not_match = ["curl https://example.com", "curl -- -o"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(
+        &tokens(&["curl", "--upload-file=secrets.txt", "https://example.com"]),
+        &allow_all,
+    );
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![RuleMatch::FlagRuleMatch {
+                matched_prefix: tokens(&["curl"]),
+                decision: Decision::Forbidden,
+                forbidden_flag: Some("--upload-file".to_string()),
+                resolved_program: None,
+                justification: Some("do not write or upload files with curl".to_string()),
+            }],
+        },
+        evaluation
+    );
+    assert_eq!(
+        serde_json::json!({
+            "flagRuleMatch": {
+                "matchedPrefix": ["curl"],
+                "decision": "forbidden",
+                "forbiddenFlag": "--upload-file",
+                "justification": "do not write or upload files with curl",
+            }
+        }),
+        serde_json::to_value(&evaluation.matched_rules[0])?
+    );
+    Ok(())
+}
+
+#[test]
+fn flag_rule_forbidden_flags_exclude_allow_rules() -> Result<()> {
+    let policy_src = r#"
+flag_rule(
+    program = "grep",
+    forbidden_flags = ["-r", "-R", "--recursive"],
+    match = ["grep -n foo src/lib.rs"],
+    not_match = ["grep -rn foo src", "grep --recursive foo src"],
+)
+flag_rule(
+    program = "grep",
+    subcommand = [any_token(), "/"],
+    forbidden_flags = ["-r", "-R", "--recursive"],
+    decision = "forbidden",
+    match = ["grep -r foo /"],
+    not_match = ["grep -r foo src", "grep foo /"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Prompt,
+            matched_rules: vec![RuleMatch::HeuristicsRuleMatch {
+                command: tokens(&["grep", "-ri", "foo", "src"]),
+                decision: Decision::Prompt,
+            }],
+        },
+        policy.check(&tokens(&["grep", "-ri", "foo", "src"]), &prompt_all)
+    );
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![RuleMatch::FlagRuleMatch {
+                matched_prefix: tokens(&["grep", "foo", "/"]),
+                decision: Decision::Forbidden,
+                forbidden_flag: Some("-R".to_string()),
+                resolved_program: None,
+                justification: None,
+            }],
+        },
+        policy.check(&tokens(&["grep", "-iR", "foo", "/"]), &prompt_all)
+    );
+    Ok(())
+}
+
 #[test]
 fn match_and_not_match_examples_are_enforced() -> Result<()> {
     let policy_src = r#"
@@ -786,6 +1209,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -843,6 +1267,7 @@ host_executable(name = "git", paths = [])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -878,6 +1303,7 @@ host_executable(name = "git", paths = ["{allowed_git_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -908,6 +1334,7 @@ prefix_rule(pattern = ["git"], decision = "prompt")
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -945,6 +1372,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            ..Default::default()
         },
     );
     assert_eq!(
@@ -961,3 +1389,151 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
     );
     Ok(())
 }
+
+#[test]
+fn expired_rules_are_ignored() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(pattern = ["cargo"], expires_at = "2000-01-01T00:00:00Z")
+prefix_rule(pattern = ["make"], expires_at = "2999-01-01T00:00:00Z")
+"#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    assert_eq!(
+        (
+            Evaluation {
+                decision: Decision::Prompt,
+                matched_rules: vec![RuleMatch::HeuristicsRuleMatch {
+                    command: tokens(&["cargo", "build"]),
+                    decision: Decision::Prompt,
+                }],
+            },
+            Decision::Allow,
+        ),
+        (
+            policy.check(&tokens(&["cargo", "build"]), &prompt_all),
+            policy.check(&tokens(&["make"]), &prompt_all).decision,
+        )
+    );
+    assert_eq!(vec![tokens(&["make"])], policy.get_allowed_prefixes());
+    Ok(())
+}
+
+#[test]
+fn scoped_rules_only_apply_within_their_scope() -> Result<()> {
+    let project_root = host_absolute_path(&["work", "project"]);
+    let policy_src = format!(
+        r#"
+prefix_rule(pattern = ["git", "push"], thread_id = "thread-1")
+prefix_rule(pattern = ["npm", "test"], project_root = {project_root:?})
+"#
+    );
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", &policy_src)?;
+    let policy = parser.build();
+    let decision_for = |cmd: &[&str], thread_id: Option<&str>, cwd: &[&str]| {
+        let options = MatchOptions {
+            thread_id: thread_id.map(str::to_string),
+            cwd: Some(absolute_path(&host_absolute_path(cwd))),
+            ..Default::default()
+        };
+        policy
+            .check_with_options(&tokens(cmd), &prompt_all, &options)
+            .decision
+    };
+
+    assert_eq!(
+        vec![
+            Decision::Allow,
+            Decision::Prompt,
+            Decision::Prompt,
+            Decision::Allow,
+            Decision::Prompt,
+        ],
+        vec![
+            decision_for(&["git", "push"], Some("thread-1"), &["tmp"]),
+            decision_for(&["git", "push"], Some("thread-2"), &["tmp"]),
+            decision_for(&["git", "push"], None, &["tmp"]),
+            decision_for(&["npm", "test"], None, &["work", "project", "pkg"]),
+            decision_for(&["npm", "test"], None, &["work", "other"]),
+        ]
+    );
+    assert_eq!(Vec::<Vec<String>>::new(), policy.get_allowed_prefixes());
+    Ok(())
+}
+
+#[test]
+fn scoped_and_expired_rules_validate_their_examples() -> Result<()> {
+    let project_root = host_absolute_path(&["work", "project"]);
+    let policy_src = format!(
+        r#"
+prefix_rule(
+    pattern = ["git", "push"],
+    thread_id = "thread-1",
+    match = [["git", "push", "origin"]],
+    not_match = [["git", "pull"]],
+)
+prefix_rule(
+    pattern = ["npm", "test"],
+    project_root = {project_root:?},
+    match = [["npm", "test"]],
+)
+prefix_rule(
+    pattern = ["cargo"],
+    expires_at = "2000-01-01T00:00:00Z",
+    match = [["cargo", "build"]],
+)
+"#
+    );
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", &policy_src)?;
+    let policy = parser.build();
+    assert_eq!(
+        Decision::Prompt,
+        policy
+            .check(&tokens(&["cargo", "build"]), &prompt_all)
+            .decision
+    );
+
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse(
+            "test.rules",
+            r#"prefix_rule(pattern = ["cargo"], expires_at = "2000-01-01T00:00:00Z", not_match = [["cargo", "build"]])"#,
+        )
+        .expect_err("not_match examples of expired rules should still be checked");
+    assert!(
+        err.to_string().contains("cargo build"),
+        "unexpected error: {err}"
+    );
+    Ok(())
+}
+
+#[test]
+fn invalid_rule_scope_is_rejected() {
+    let cases = [
+        (
+            r#"prefix_rule(pattern = ["ls"], expires_at = "tomorrow")"#,
+            "expires_at must be an RFC 3339 timestamp",
+        ),
+        (
+            r#"prefix_rule(pattern = ["ls"], project_root = "relative/dir")"#,
+            "project_root must be an absolute path",
+        ),
+        (
+            r#"prefix_rule(pattern = ["ls"], thread_id = " ")"#,
+            "thread_id cannot be empty",
+        ),
+    ];
+    for (policy_src, expected) in cases {
+        let mut parser = PolicyParser::new();
+        let err = parser
+            .parse("test.rules", policy_src)
+            .expect_err("invalid scope should fail");
+        assert!(
+            err.to_string().contains(expected),
+            "unexpected error for {policy_src}: {err}"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::Result;
+use codex_execpolicy::Decision;
+use codex_execpolicy::FallbackCommand;
+use codex_execpolicy::LintReport;
+use codex_execpolicy::MatchOptions;
+use codex_execpolicy::PolicyParser;
+use codex_execpolicy::RuleSummary;
+use codex_execpolicy::ShadowedRule;
+use codex_execpolicy::commands_from_rollout;
+use codex_execpolicy::lint_policy;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn tokens(cmd: &[&str]) -> Vec<String> {
+    cmd.iter().map(std::string::ToString::to_string).collect()
+}
+
+fn summary(rule: &str, decision: Decision, line: usize) -> RuleSummary {
+    RuleSummary {
+        rule: rule.to_string(),
+        decision,
+        location: Some(format!("test.rules:{line}")),
+    }
+}
+
+#[test]
+fn lint_reports_unused_shadowed_and_fallback_rules() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(pattern = ["git"], decision = "prompt")
+prefix_rule(pattern = ["git", "status"], decision = "allow")
+prefix_rule(pattern = ["rm", "-rf"], decision = "forbidden")
+prefix_rule(pattern = ["cargo", ["test", "check"]], decision = "allow")
+prefix_rule(pattern = ["cargo", "test"], decision = "allow")
+"#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let rule_sources = parser.rule_sources();
+    let policy = parser.build();
+
+    let commands = vec![
+        tokens(&["git", "status"]),
+        tokens(&["cargo", "check"]),
+        tokens(&["ls", "-la"]),
+        tokens(&["pwd"]),
+        tokens(&["ls", "-la"]),
+    ];
+
+    let report = lint_policy(&policy, &rule_sources, &commands, &MatchOptions::default());
+
+    assert_eq!(
+        LintReport {
+            rollouts_scanned: 0,
+            commands_replayed: 5,
+            unused_rules: vec![
+                summary("prefix_rule `rm -rf`", Decision::Forbidden, 4),
+                summary("prefix_rule `cargo test`", Decision::Allow, 6),
+            ],
+            shadowed_rules: vec![
+                ShadowedRule {
+                    rule: summary("prefix_rule `git status`", Decision::Allow, 3),
+                    shadowed_by: summary("prefix_rule `git`", Decision::Prompt, 2),
+                },
+                ShadowedRule {
+                    rule: summary("prefix_rule `cargo test`", Decision::Allow, 6),
+                    shadowed_by: summary("prefix_rule `cargo [test|check]`", Decision::Allow, 5),
+                },
+            ],
+            heuristics_fallback_commands: vec![
+                FallbackCommand {
+                    command: tokens(&["ls", "-la"]),
+                    count: 2,
+                },
+                FallbackCommand {
+                    command: tokens(&["pwd"]),
+                    count: 1,
+                },
+            ],
+        },
+        report
+    );
+    Ok(())
+}
+
+#[test]
+fn commands_from_rollout_extracts_shell_calls() {
+    let lines = [
+        json!({"type": "session_meta", "payload": {"id": "session"}}),
+        json!({
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "shell",
+                "arguments": json!({"command": ["git", "status"]}).to_string(),
+                "call_id": "call-1",
+            },
+        }),
+        json!({
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "shell_command",
+                "arguments": json!({"command": "cargo test -p foo"}).to_string(),
+                "call_id": "call-2",
+            },
+        }),
+        json!({
+            "type": "response_item",
+            "payload": {
+                "type": "local_shell_call",
+                "status": "completed",
+                "action": {"type": "exec", "command": ["ls", "-la"]},
+            },
+        }),
+        json!({
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "read_file",
+                "arguments": json!({"path": "README.md"}).to_string(),
+                "call_id": "call-3",
+            },
+        }),
+    ];
+    let contents = lines
+        .iter()
+        .map(ToString::to_string)
+        .chain(std::iter::once("{truncated".to_string()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_eq!(
+        vec![
+            tokens(&["git", "status"]),
+            tokens(&["bash", "-lc", "cargo test -p foo"]),
+            tokens(&["ls", "-la"]),
+        ],
+        commands_from_rollout(&contents)
+    );
+}
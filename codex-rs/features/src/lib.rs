@@ -98,6 +98,9 @@ pub enum Feature {
     ApplyPatchFreeform,
     /// Stream structured progress while apply_patch input is being generated.
     ApplyPatchStreamingEvents,
+    /// Fall back to a three-way merge against the last-read file contents when apply_patch
+    /// context has drifted.
+    ApplyPatchThreeWayMerge,
     /// Allow exec tools to request additional permissions while staying sandboxed.
     ExecPermissionApprovals,
     /// Expose the built-in request_permissions tool.
@@ -220,6 +223,8 @@ pub enum Feature {
     ResponsesWebsocketsV2,
     /// Enable workspace dependency support.
     WorkspaceDependencies,
+    /// Keep a persistent index of searched roots under `CODEX_HOME` for `@` file search.
+    FileSearchIndex,
 }
 
 impl Feature {
@@ -756,6 +761,12 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::UnderDevelopment,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::ApplyPatchThreeWayMerge,
+        key: "apply_patch_three_way_merge",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::ExecPermissionApprovals,
         key: "exec_permission_approvals",
@@ -1048,6 +1059,12 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Stable,
         default_enabled: true,
     },
+    FeatureSpec {
+        id: Feature::FileSearchIndex,
+        key: "file_search_index",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
 ];
 
 pub fn unstable_features_warning_event(
@@ -0,0 +1,693 @@
+//! Content search: finds the lines matching a literal or regular expression query in the files
+//! under the search directories, walking them the same way as the fuzzy filename search.
+
+use std::num::NonZero;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use crossbeam_channel::never;
+use crossbeam_channel::select;
+use crossbeam_channel::tick;
+use crossbeam_channel::unbounded;
+use regex::Regex;
+use regex::RegexBuilder;
+use serde::Serialize;
+
+use crate::FileSearchSession;
+use crate::SessionReporter;
+use crate::WorkSignal;
+use crate::build_override_matcher;
+use crate::build_walker;
+use crate::get_file_path;
+
+/// Files larger than this are skipped rather than searched.
+const MAX_FILE_BYTES: u64 = 8 * 1024 * 1024;
+/// A NUL byte in this many leading bytes marks a file as binary, so it is skipped.
+const BINARY_DETECTION_BYTES: usize = 8 * 1024;
+
+/// A line that matched a content search.
+///
+/// * `path` – Path to the file containing the line, relative to `root`.
+/// * `line_number` – 1-based number of the line within the file.
+/// * `column` – 1-based byte column of the first match on the line.
+/// * `line` – The text of the line, without its line terminator.
+/// * `ranges` – Byte ranges of every match within `line`, in ascending order.
+/// * `context_before` / `context_after` – Up to `context_lines` lines around
+///   the match, nearest last and first respectively.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ContentMatch {
+    pub root: PathBuf,
+    pub path: PathBuf,
+    pub line_number: usize,
+    pub column: usize,
+    pub line: String,
+    pub ranges: Vec<ContentMatchRange>,
+    pub context_before: Vec<String>,
+    pub context_after: Vec<String>,
+}
+
+/// Byte range of a match within [`ContentMatch::line`], end exclusive.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct ContentMatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl ContentMatch {
+    pub fn full_path(&self) -> PathBuf {
+        self.root.join(&self.path)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Default)]
+pub struct ContentSearchSnapshot {
+    pub query: String,
+    /// Matching lines found so far, ordered by root, path and line number.
+    pub matches: Vec<ContentMatch>,
+    /// Whether the search stopped early because it found `limit` matching lines.
+    pub truncated: bool,
+    pub scanned_file_count: usize,
+    pub walk_complete: bool,
+    /// Why the query could not be searched, such as an invalid regular expression.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentSearchOptions {
+    /// Maximum number of matching lines to report.
+    pub limit: NonZero<usize>,
+    pub exclude: Vec<String>,
+    pub threads: NonZero<usize>,
+    /// Treat the query as a regular expression instead of literal text.
+    pub regex: bool,
+    pub case_sensitive: bool,
+    /// Number of lines to include before and after each matching line.
+    pub context_lines: usize,
+    /// See [`crate::FileSearchOptions::respect_gitignore`].
+    pub respect_gitignore: bool,
+}
+
+impl Default for ContentSearchOptions {
+    fn default() -> Self {
+        Self {
+            #[expect(clippy::unwrap_used)]
+            limit: NonZero::new(100).unwrap(),
+            exclude: Vec::new(),
+            #[expect(clippy::unwrap_used)]
+            threads: NonZero::new(2).unwrap(),
+            regex: false,
+            case_sensitive: false,
+            context_lines: 0,
+            respect_gitignore: true,
+        }
+    }
+}
+
+/// Starts a session that searches file contents for each query passed to
+/// [`FileSearchSession::update_query`]. Results are streamed through
+/// [`SessionReporter::on_content_update`], and a new query cancels the search
+/// for the previous one.
+pub fn create_content_session(
+    search_directories: Vec<PathBuf>,
+    options: ContentSearchOptions,
+    reporter: Arc<dyn SessionReporter>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+) -> anyhow::Result<FileSearchSession> {
+    let ContentSearchOptions {
+        limit,
+        exclude,
+        threads,
+        regex,
+        case_sensitive,
+        context_lines,
+        respect_gitignore,
+    } = options;
+
+    let Some(primary_search_directory) = search_directories.first() else {
+        anyhow::bail!("at least one search directory is required");
+    };
+    let override_matcher = build_override_matcher(primary_search_directory, &exclude)?;
+    let (work_tx, work_rx) = unbounded();
+
+    let inner = Arc::new(ContentSessionInner {
+        search_directories,
+        override_matcher,
+        limit: limit.get(),
+        threads: threads.get(),
+        regex,
+        case_sensitive,
+        context_lines,
+        respect_gitignore,
+        cancelled: cancel_flag.unwrap_or_else(|| Arc::new(AtomicBool::new(false))),
+        shutdown: Arc::new(AtomicBool::new(false)),
+        reporter,
+        work_tx,
+    });
+
+    let session = FileSearchSession {
+        work_tx: inner.work_tx.clone(),
+        shutdown: inner.shutdown.clone(),
+    };
+    thread::spawn(move || content_worker(inner, work_rx));
+
+    Ok(session)
+}
+
+struct ContentSessionInner {
+    search_directories: Vec<PathBuf>,
+    override_matcher: Option<ignore::overrides::Override>,
+    limit: usize,
+    threads: usize,
+    regex: bool,
+    case_sensitive: bool,
+    context_lines: usize,
+    respect_gitignore: bool,
+    cancelled: Arc<AtomicBool>,
+    shutdown: Arc<AtomicBool>,
+    reporter: Arc<dyn SessionReporter>,
+    work_tx: Sender<WorkSignal>,
+}
+
+impl ContentSessionInner {
+    fn should_stop(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed) || self.shutdown.load(Ordering::Relaxed)
+    }
+
+    fn build_regex(&self, query: &str) -> Result<Regex, regex::Error> {
+        let pattern = if self.regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+    }
+}
+
+/// The search for a single query.
+struct ContentSearch {
+    query: String,
+    /// Set when a newer query replaces this one or the limit has been reached.
+    stopped: AtomicBool,
+    /// Set once the walk has finished, just before `WalkComplete` is sent.
+    done: AtomicBool,
+    /// Set whenever a match is added, and cleared when it is reported.
+    changed: AtomicBool,
+    scanned_file_count: AtomicUsize,
+    results: Mutex<ContentResults>,
+}
+
+#[derive(Default)]
+struct ContentResults {
+    matches: Vec<ContentMatch>,
+    truncated: bool,
+}
+
+impl ContentSearch {
+    fn new(query: String) -> Self {
+        Self {
+            query,
+            stopped: AtomicBool::new(false),
+            done: AtomicBool::new(false),
+            changed: AtomicBool::new(false),
+            scanned_file_count: AtomicUsize::new(0),
+            results: Mutex::new(ContentResults::default()),
+        }
+    }
+
+    /// Adds `matches`, returning `false` once the limit has been reached.
+    fn add_matches(&self, matches: Vec<ContentMatch>, limit: usize) -> bool {
+        #[expect(clippy::unwrap_used)]
+        let mut results = self.results.lock().unwrap();
+        for content_match in matches {
+            if results.matches.len() >= limit {
+                results.truncated = true;
+                break;
+            }
+            results.matches.push(content_match);
+        }
+        self.changed.store(true, Ordering::Relaxed);
+        !results.truncated
+    }
+
+    fn snapshot(&self, walk_complete: bool) -> ContentSearchSnapshot {
+        let (mut matches, truncated) = {
+            #[expect(clippy::unwrap_used)]
+            let results = self.results.lock().unwrap();
+            (results.matches.clone(), results.truncated)
+        };
+        matches.sort_by(|a, b| {
+            (&a.root, &a.path, a.line_number).cmp(&(&b.root, &b.path, b.line_number))
+        });
+        ContentSearchSnapshot {
+            query: self.query.clone(),
+            matches,
+            truncated,
+            scanned_file_count: self.scanned_file_count.load(Ordering::Relaxed),
+            walk_complete,
+            error: None,
+        }
+    }
+}
+
+fn content_worker(inner: Arc<ContentSessionInner>, work_rx: Receiver<WorkSignal>) {
+    const TICK_INTERVAL_MS: u64 = 50;
+    let mut current: Option<Arc<ContentSearch>> = None;
+    let mut next_tick = never();
+
+    loop {
+        select! {
+            recv(work_rx) -> signal => {
+                let Ok(signal) = signal else {
+                    break;
+                };
+                match signal {
+                    WorkSignal::QueryUpdated(query) => {
+                        if let Some(search) = current.take() {
+                            search.stopped.store(true, Ordering::Relaxed);
+                        }
+                        current = start_content_search(&inner, query);
+                        next_tick = if current.is_some() {
+                            tick(Duration::from_millis(TICK_INTERVAL_MS))
+                        } else {
+                            never()
+                        };
+                    }
+                    WorkSignal::WalkComplete => {
+                        // Walks of superseded queries also report completion; only the current
+                        // one ends the query.
+                        if let Some(search) = current.take_if(|search| search.done.load(Ordering::SeqCst)) {
+                            inner.reporter.on_content_update(&search.snapshot(/*walk_complete*/ true));
+                            inner.reporter.on_complete();
+                            next_tick = never();
+                        }
+                    }
+                    WorkSignal::NucleoNotify => {}
+                    WorkSignal::Shutdown => {
+                        break;
+                    }
+                }
+            }
+            recv(next_tick) -> _ => {
+                if let Some(search) = current.as_ref()
+                    && search.changed.swap(false, Ordering::Relaxed)
+                {
+                    inner.reporter.on_content_update(&search.snapshot(/*walk_complete*/ false));
+                }
+            }
+            default(Duration::from_millis(100)) => {
+                // Occasionally check the cancel flag.
+            }
+        }
+
+        if inner.should_stop() {
+            break;
+        }
+    }
+
+    if let Some(search) = current {
+        search.stopped.store(true, Ordering::Relaxed);
+    }
+    // If we cancelled or otherwise exited the loop, make sure the reporter is notified.
+    inner.reporter.on_complete();
+}
+
+/// Starts walking for `query`, or reports it as complete right away when there is
+/// nothing to search for.
+fn start_content_search(
+    inner: &Arc<ContentSessionInner>,
+    query: String,
+) -> Option<Arc<ContentSearch>> {
+    let regex = if query.is_empty() {
+        None
+    } else {
+        match inner.build_regex(&query) {
+            Ok(regex) => Some(regex),
+            Err(err) => {
+                inner.reporter.on_content_update(&ContentSearchSnapshot {
+                    query,
+                    walk_complete: true,
+                    error: Some(err.to_string()),
+                    ..Default::default()
+                });
+                inner.reporter.on_complete();
+                return None;
+            }
+        }
+    };
+    let Some(regex) = regex else {
+        inner.reporter.on_content_update(&ContentSearchSnapshot {
+            query,
+            walk_complete: true,
+            ..Default::default()
+        });
+        inner.reporter.on_complete();
+        return None;
+    };
+
+    let search = Arc::new(ContentSearch::new(query));
+    let walker_inner = inner.clone();
+    let walker_search = search.clone();
+    thread::spawn(move || content_walker_worker(walker_inner, walker_search, regex));
+    Some(search)
+}
+
+fn content_walker_worker(
+    inner: Arc<ContentSessionInner>,
+    search: Arc<ContentSearch>,
+    regex: Regex,
+) {
+    if let Some(walk_builder) = build_walker(
+        &inner.search_directories,
+        inner.threads,
+        inner.respect_gitignore,
+        inner.override_matcher.clone(),
+    ) {
+        walk_builder.build_parallel().run(|| {
+            let inner = inner.clone();
+            let search = search.clone();
+            let regex = regex.clone();
+
+            Box::new(move |entry| {
+                if search.stopped.load(Ordering::Relaxed) || inner.should_stop() {
+                    return ignore::WalkState::Quit;
+                }
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(_) => return ignore::WalkState::Continue,
+                };
+                if !entry
+                    .file_type()
+                    .is_some_and(|file_type| file_type.is_file())
+                {
+                    return ignore::WalkState::Continue;
+                }
+                let path = entry.path();
+                let Some((root_idx, relative_path)) =
+                    get_file_path(path, &inner.search_directories)
+                else {
+                    return ignore::WalkState::Continue;
+                };
+                let matches = search_file(path, &regex, inner.context_lines)
+                    .into_iter()
+                    .map(|line_match| ContentMatch {
+                        root: inner.search_directories[root_idx].clone(),
+                        path: PathBuf::from(relative_path),
+                        ..line_match
+                    })
+                    .collect::<Vec<_>>();
+                search.scanned_file_count.fetch_add(1, Ordering::Relaxed);
+                if !matches.is_empty() && !search.add_matches(matches, inner.limit) {
+                    search.stopped.store(true, Ordering::Relaxed);
+                    return ignore::WalkState::Quit;
+                }
+                ignore::WalkState::Continue
+            })
+        });
+    }
+    search.done.store(true, Ordering::SeqCst);
+    let _ = inner.work_tx.send(WorkSignal::WalkComplete);
+}
+
+/// Returns the lines of the file at `path` that match `regex`, with `root` and
+/// `path` left empty for the caller to fill in. Binary, oversized and unreadable
+/// files have no matches.
+fn search_file(path: &Path, regex: &Regex, context_lines: usize) -> Vec<ContentMatch> {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return Vec::new();
+    };
+    if metadata.len() > MAX_FILE_BYTES {
+        return Vec::new();
+    }
+    let Ok(bytes) = std::fs::read(path) else {
+        return Vec::new();
+    };
+    let head = &bytes[..bytes.len().min(BINARY_DETECTION_BYTES)];
+    if head.contains(&0) {
+        return Vec::new();
+    }
+    let contents = String::from_utf8_lossy(&bytes);
+    search_lines(&contents, regex, context_lines)
+}
+
+fn search_lines(contents: &str, regex: &Regex, context_lines: usize) -> Vec<ContentMatch> {
+    let lines = contents.lines().collect::<Vec<_>>();
+    let mut matches = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let ranges = regex
+            .find_iter(line)
+            .filter(|found| !found.is_empty())
+            .map(|found| ContentMatchRange {
+                start: found.start(),
+                end: found.end(),
+            })
+            .collect::<Vec<_>>();
+        let Some(first) = ranges.first() else {
+            continue;
+        };
+        let context_start = index.saturating_sub(context_lines);
+        let context_end = (index + 1 + context_lines).min(lines.len());
+        matches.push(ContentMatch {
+            root: PathBuf::new(),
+            path: PathBuf::new(),
+            line_number: index + 1,
+            column: first.start + 1,
+            line: (*line).to_string(),
+            context_before: lines[context_start..index]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            context_after: lines[index + 1..context_end]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            ranges,
+        });
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use std::sync::Condvar;
+    use std::time::Instant;
+
+    #[test]
+    fn search_lines_reports_columns_ranges_and_context() {
+        let regex = RegexBuilder::new("needle").build().unwrap();
+        let contents = "first\nneedle and needle\nthird\r\nfourth\n";
+
+        let matches = search_lines(contents, &regex, /*context_lines*/ 1);
+
+        assert_eq!(
+            matches,
+            vec![ContentMatch {
+                root: PathBuf::new(),
+                path: PathBuf::new(),
+                line_number: 2,
+                column: 1,
+                line: "needle and needle".to_string(),
+                ranges: vec![
+                    ContentMatchRange { start: 0, end: 6 },
+                    ContentMatchRange { start: 11, end: 17 },
+                ],
+                context_before: vec!["first".to_string()],
+                context_after: vec!["third".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn search_lines_ignores_empty_regex_matches() {
+        let regex = RegexBuilder::new("x*").build().unwrap();
+
+        let matches = search_lines("abc\nxx\n", &regex, /*context_lines*/ 0);
+
+        assert_eq!(
+            matches
+                .iter()
+                .map(|content_match| (content_match.line_number, content_match.column))
+                .collect::<Vec<_>>(),
+            vec![(2, 1)]
+        );
+    }
+
+    #[derive(Default)]
+    struct RecordingReporter {
+        updates: Mutex<Vec<ContentSearchSnapshot>>,
+        completed: Mutex<usize>,
+        completed_cv: Condvar,
+    }
+
+    impl RecordingReporter {
+        fn wait_for_completions(&self, count: usize) -> bool {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            let mut completed = self.completed.lock().unwrap();
+            while *completed < count {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return false;
+                }
+                completed = self
+                    .completed_cv
+                    .wait_timeout(completed, remaining)
+                    .unwrap()
+                    .0;
+            }
+            true
+        }
+
+        fn last_update(&self) -> ContentSearchSnapshot {
+            self.updates
+                .lock()
+                .unwrap()
+                .last()
+                .cloned()
+                .unwrap_or_default()
+        }
+    }
+
+    impl SessionReporter for RecordingReporter {
+        fn on_update(&self, _snapshot: &crate::FileSearchSnapshot) {}
+
+        fn on_content_update(&self, snapshot: &ContentSearchSnapshot) {
+            self.updates.lock().unwrap().push(snapshot.clone());
+        }
+
+        fn on_complete(&self) {
+            *self.completed.lock().unwrap() += 1;
+            self.completed_cv.notify_all();
+        }
+    }
+
+    #[test]
+    fn content_session_finds_literal_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(
+            dir.path().join("src/lib.rs"),
+            "fn main() {\n    todo!()\n}\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.txt"), "TODO: a.b\nnothing\n").unwrap();
+        fs::write(dir.path().join("blob.bin"), b"todo\0todo").unwrap();
+        let reporter = Arc::new(RecordingReporter::default());
+        let session = create_content_session(
+            vec![dir.path().to_path_buf()],
+            ContentSearchOptions::default(),
+            reporter.clone(),
+            /*cancel_flag*/ None,
+        )
+        .expect("session");
+
+        session.update_query("todo");
+        assert!(reporter.wait_for_completions(1));
+
+        let snapshot = reporter.last_update();
+        assert!(snapshot.walk_complete);
+        assert!(!snapshot.truncated);
+        assert_eq!(
+            snapshot
+                .matches
+                .iter()
+                .map(|content_match| (
+                    content_match.path.clone(),
+                    content_match.line_number,
+                    content_match.column
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (PathBuf::from("notes.txt"), 1, 1),
+                (PathBuf::from("src/lib.rs"), 2, 5),
+            ]
+        );
+
+        // Literal queries do not treat `.` as a wildcard.
+        session.update_query("a.b");
+        assert!(reporter.wait_for_completions(2));
+        assert_eq!(reporter.last_update().matches.len(), 1);
+        session.update_query("a\\.b");
+        assert!(reporter.wait_for_completions(3));
+        assert_eq!(reporter.last_update().matches, Vec::new());
+    }
+
+    #[test]
+    fn content_session_reports_invalid_regex() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.txt"), "value = 1\n").unwrap();
+        let reporter = Arc::new(RecordingReporter::default());
+        let session = create_content_session(
+            vec![dir.path().to_path_buf()],
+            ContentSearchOptions {
+                regex: true,
+                case_sensitive: true,
+                ..Default::default()
+            },
+            reporter.clone(),
+            /*cancel_flag*/ None,
+        )
+        .expect("session");
+
+        session.update_query("value (");
+        assert!(reporter.wait_for_completions(1));
+        let snapshot = reporter.last_update();
+        assert!(snapshot.error.is_some());
+        assert_eq!(snapshot.matches, Vec::new());
+
+        session.update_query("val[a-z]+ = \\d");
+        assert!(reporter.wait_for_completions(2));
+        let snapshot = reporter.last_update();
+        assert_eq!(snapshot.error, None);
+        assert_eq!(snapshot.matches.len(), 1);
+        assert_eq!(
+            snapshot.matches[0].ranges,
+            vec![ContentMatchRange { start: 0, end: 9 }]
+        );
+    }
+
+    #[test]
+    fn content_session_stops_at_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..20 {
+            fs::write(
+                dir.path().join(format!("file-{i:02}.txt")),
+                "match\nmatch\n",
+            )
+            .unwrap();
+        }
+        let reporter = Arc::new(RecordingReporter::default());
+        let session = create_content_session(
+            vec![dir.path().to_path_buf()],
+            ContentSearchOptions {
+                limit: NonZero::new(5).unwrap(),
+                ..Default::default()
+            },
+            reporter.clone(),
+            /*cancel_flag*/ None,
+        )
+        .expect("session");
+
+        session.update_query("match");
+        assert!(reporter.wait_for_completions(1));
+
+        let snapshot = reporter.last_update();
+        assert!(snapshot.truncated);
+        assert_eq!(snapshot.matches.len(), 5);
+    }
+}
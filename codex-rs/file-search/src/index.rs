@@ -0,0 +1,669 @@
+//! Persistent index of the entries under a search root.
+//!
+//! Walking a large tree on every session is slow, so a [`FileIndexStore`] records the path, kind,
+//! modification time and size of every entry the walker would visit, one file per root, and
+//! reuses it across sessions and processes. Before an index is used it is checked against the
+//! filesystem: every indexed directory whose modification time changed is listed again, so
+//! entries created or removed since the index was saved are never missed. Callers that watch the
+//! filesystem report changed paths with [`FileIndexStore::apply_changes`] to keep the in-memory
+//! indexes current between sessions.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::num::NonZero;
+use std::path::MAIN_SEPARATOR;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::build_walker;
+
+/// Name of the directory under `CODEX_HOME` that holds the indexes.
+pub const INDEX_DIR_NAME: &str = "file_search_index";
+
+const INDEX_HEADER: &str = "codex-file-index 1";
+/// Directories modified this close to the last check are listed again, because a change made
+/// within the same timestamp tick does not move their modification time.
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+/// Minimum time between writes of an index that is updated from change notifications.
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+const MAX_WALK_THREADS: usize = 8;
+/// Files whose creation, removal or modification changes which entries are walked.
+const IGNORE_FILE_NAMES: &[&str] = &[".gitignore", ".ignore"];
+
+/// An entry of an index, with its path relative to the indexed root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedEntry {
+    pub path: String,
+    pub is_dir: bool,
+}
+
+/// Indexes of the roots searched by this process, persisted under a directory such as
+/// `$CODEX_HOME/`[`INDEX_DIR_NAME`].
+#[derive(Debug)]
+pub struct FileIndexStore {
+    dir: PathBuf,
+    indexes: Mutex<HashMap<IndexKey, Arc<Mutex<FileIndex>>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct IndexKey {
+    root: PathBuf,
+    respect_gitignore: bool,
+}
+
+impl FileIndexStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            indexes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns every entry under `root`, loading the index from disk or building it on first
+    /// use, and bringing it up to date with the filesystem.
+    pub fn entries(&self, root: &Path, respect_gitignore: bool) -> Vec<IndexedEntry> {
+        let key = IndexKey {
+            root: root.to_path_buf(),
+            respect_gitignore,
+        };
+        let index = {
+            #[expect(clippy::unwrap_used)]
+            let mut indexes = self.indexes.lock().unwrap();
+            indexes
+                .entry(key.clone())
+                .or_insert_with(|| {
+                    let index = FileIndex::load(&index_file_path(&self.dir, &key))
+                        .ok()
+                        .filter(|index| index.key == key)
+                        .unwrap_or_else(|| FileIndex::new(key));
+                    Arc::new(Mutex::new(index))
+                })
+                .clone()
+        };
+
+        #[expect(clippy::unwrap_used)]
+        let mut index = index.lock().unwrap();
+        index.refresh();
+        // A failed write only costs a longer check the next time the index is loaded.
+        let _ = index.save(&self.dir);
+        index.entries()
+    }
+
+    /// Updates the loaded indexes after `changed_paths` were created, modified or removed.
+    pub fn apply_changes(&self, changed_paths: &[PathBuf]) {
+        for index in self.loaded_indexes() {
+            #[expect(clippy::unwrap_used)]
+            let mut index = index.lock().unwrap();
+            index.apply_changes(changed_paths);
+            if index
+                .last_saved
+                .is_none_or(|last_saved| last_saved.elapsed() >= SAVE_INTERVAL)
+            {
+                let _ = index.save(&self.dir);
+            }
+        }
+    }
+
+    /// Writes every index with unsaved changes to disk.
+    pub fn flush(&self) -> io::Result<()> {
+        for index in self.loaded_indexes() {
+            #[expect(clippy::unwrap_used)]
+            index.lock().unwrap().save(&self.dir)?;
+        }
+        Ok(())
+    }
+
+    fn loaded_indexes(&self) -> Vec<Arc<Mutex<FileIndex>>> {
+        #[expect(clippy::unwrap_used)]
+        self.indexes.lock().unwrap().values().cloned().collect()
+    }
+}
+
+impl Drop for FileIndexStore {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EntryMetadata {
+    is_dir: bool,
+    /// Modification time in nanoseconds since the Unix epoch.
+    modified: u64,
+    size: u64,
+}
+
+impl EntryMetadata {
+    fn from_fs(metadata: &fs::Metadata) -> Self {
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|modified| u64::try_from(modified.as_nanos()).unwrap_or(u64::MAX))
+            .unwrap_or_default();
+        Self {
+            is_dir: metadata.is_dir(),
+            modified,
+            size: metadata.len(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FileIndex {
+    key: IndexKey,
+    /// Entries keyed by their path relative to the root. The root itself is the empty path.
+    entries: BTreeMap<String, EntryMetadata>,
+    /// When the entries were last checked against the filesystem, in nanoseconds since the Unix
+    /// epoch.
+    checked_at: u64,
+    dirty: bool,
+    last_saved: Option<Instant>,
+}
+
+impl FileIndex {
+    fn new(key: IndexKey) -> Self {
+        Self {
+            key,
+            entries: BTreeMap::new(),
+            checked_at: 0,
+            dirty: false,
+            last_saved: None,
+        }
+    }
+
+    fn entries(&self) -> Vec<IndexedEntry> {
+        self.entries
+            .iter()
+            .filter(|(path, _)| !path.is_empty())
+            .map(|(path, metadata)| IndexedEntry {
+                path: path.clone(),
+                is_dir: metadata.is_dir,
+            })
+            .collect()
+    }
+
+    /// Lists again every directory that changed since the index was last checked, or walks the
+    /// whole root if the index is empty or the ignore rules may have changed.
+    fn refresh(&mut self) {
+        let checked_at = now();
+        if self.entries.is_empty() || self.ignore_files_changed() {
+            self.rebuild();
+        } else {
+            let stale_directories = self
+                .entries
+                .iter()
+                .filter(|(path, metadata)| metadata.is_dir && self.is_stale(path, metadata))
+                .map(|(path, _)| path.clone())
+                .collect::<Vec<_>>();
+            if stale_directories.is_empty() {
+                return;
+            }
+            if self.rescan_directories(stale_directories) {
+                self.rebuild();
+            }
+        }
+        self.checked_at = checked_at;
+        self.dirty = true;
+    }
+
+    fn apply_changes(&mut self, changed_paths: &[PathBuf]) {
+        let mut directories = BTreeSet::new();
+        for changed_path in changed_paths {
+            let Some(relative_path) = changed_path
+                .strip_prefix(&self.key.root)
+                .ok()
+                .and_then(Path::to_str)
+            else {
+                continue;
+            };
+            if self
+                .entries
+                .get(relative_path)
+                .is_some_and(|metadata| metadata.is_dir)
+            {
+                directories.insert(relative_path.to_string());
+            }
+            // The parent is listed again to pick up creations and removals. Paths inside
+            // directories that are not indexed, such as ignored ones, fall back to their nearest
+            // indexed ancestor.
+            let mut ancestor = Path::new(relative_path).parent();
+            while let Some(path) = ancestor {
+                let Some(path) = path.to_str() else {
+                    break;
+                };
+                if self
+                    .entries
+                    .get(path)
+                    .is_some_and(|metadata| metadata.is_dir)
+                {
+                    directories.insert(path.to_string());
+                    break;
+                }
+                ancestor = Path::new(path).parent();
+            }
+        }
+        if directories.is_empty() {
+            return;
+        }
+        if self.rescan_directories(directories.into_iter().collect()) {
+            self.rebuild();
+        }
+        self.dirty = true;
+    }
+
+    fn is_stale(&self, path: &str, metadata: &EntryMetadata) -> bool {
+        if metadata.modified.saturating_add(racy_window_nanos()) >= self.checked_at {
+            return true;
+        }
+        match fs::metadata(self.key.root.join(path)) {
+            Ok(current) => EntryMetadata::from_fs(&current) != *metadata,
+            Err(_) => true,
+        }
+    }
+
+    fn ignore_files_changed(&self) -> bool {
+        self.entries
+            .iter()
+            .filter(|(path, metadata)| !metadata.is_dir && is_ignore_file(path))
+            .any(|(path, metadata)| {
+                fs::metadata(self.key.root.join(path))
+                    .map(|current| EntryMetadata::from_fs(&current) != *metadata)
+                    .unwrap_or(true)
+            })
+    }
+
+    fn rebuild(&mut self) {
+        self.entries = self.walk(&self.key.root, /*max_depth*/ None);
+    }
+
+    /// Lists each of `directories` again, shallowest first. Returns whether an ignore file was
+    /// created, removed or modified, in which case the whole index must be rebuilt.
+    fn rescan_directories(&mut self, mut directories: Vec<String>) -> bool {
+        directories.sort_by_key(|path| Path::new(path).components().count());
+        for directory in directories {
+            // An earlier rescan may have removed or already walked this directory.
+            if !self.entries.contains_key(&directory) {
+                continue;
+            }
+            if self.rescan_directory(&directory) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn rescan_directory(&mut self, directory: &str) -> bool {
+        let directory_path = self.key.root.join(directory);
+        if !directory_path.is_dir() {
+            self.remove_subtree(directory);
+            return false;
+        }
+
+        let listed = self.walk(&directory_path, Some(1));
+        let prefix = child_prefix(directory);
+        let indexed_children = self
+            .entries
+            .range(prefix.clone()..)
+            .take_while(|(path, _)| path.starts_with(&prefix))
+            .filter(|(path, _)| !path[prefix.len()..].contains(MAIN_SEPARATOR))
+            .map(|(path, metadata)| (path.clone(), *metadata))
+            .collect::<Vec<_>>();
+
+        let mut ignore_rules_changed = false;
+        for (path, metadata) in &indexed_children {
+            let still_listed = listed
+                .get(path)
+                .is_some_and(|listed| listed.is_dir == metadata.is_dir);
+            if !still_listed {
+                ignore_rules_changed |= is_ignore_file(path);
+                self.remove_subtree(path);
+            }
+        }
+        for (path, metadata) in listed {
+            let previous = self.entries.get(&path).copied();
+            if metadata.is_dir && previous.is_none() && path != directory {
+                let subtree = self.walk(&self.key.root.join(&path), /*max_depth*/ None);
+                self.entries.extend(subtree);
+                continue;
+            }
+            if !metadata.is_dir && previous != Some(metadata) {
+                ignore_rules_changed |= is_ignore_file(&path);
+            }
+            if metadata.is_dir && path != directory {
+                // Keep the recorded time of existing subdirectories so that their own
+                // staleness check still lists them again.
+                if let Some(previous) = previous {
+                    self.entries.insert(path, previous);
+                    continue;
+                }
+            }
+            self.entries.insert(path, metadata);
+        }
+        ignore_rules_changed
+    }
+
+    fn remove_subtree(&mut self, path: &str) {
+        if path.is_empty() {
+            self.entries.clear();
+            return;
+        }
+        self.entries.remove(path);
+        let prefix = child_prefix(path);
+        let descendants = self
+            .entries
+            .range(prefix.clone()..)
+            .take_while(|(descendant, _)| descendant.starts_with(&prefix))
+            .map(|(descendant, _)| descendant.clone())
+            .collect::<Vec<_>>();
+        for descendant in descendants {
+            self.entries.remove(&descendant);
+        }
+    }
+
+    /// Walks `directory` with the same rules as a session, returning entries keyed by their
+    /// path relative to the root.
+    fn walk(&self, directory: &Path, max_depth: Option<usize>) -> BTreeMap<String, EntryMetadata> {
+        let threads = std::thread::available_parallelism()
+            .map(NonZero::get)
+            .unwrap_or(1)
+            .min(MAX_WALK_THREADS);
+        let Some(mut walk_builder) = build_walker(
+            &[directory.to_path_buf()],
+            threads,
+            self.key.respect_gitignore,
+            /*override_matcher*/ None,
+        ) else {
+            return BTreeMap::new();
+        };
+        walk_builder.max_depth(max_depth);
+
+        let entries = Mutex::new(BTreeMap::new());
+        walk_builder.build_parallel().run(|| {
+            let root = &self.key.root;
+            let entries = &entries;
+            Box::new(move |entry| {
+                let Ok(entry) = entry else {
+                    return ignore::WalkState::Continue;
+                };
+                let Some(relative_path) =
+                    entry.path().strip_prefix(root).ok().and_then(Path::to_str)
+                else {
+                    return ignore::WalkState::Continue;
+                };
+                if let Ok(metadata) = fs::metadata(entry.path()) {
+                    #[expect(clippy::unwrap_used)]
+                    entries
+                        .lock()
+                        .unwrap()
+                        .insert(relative_path.to_string(), EntryMetadata::from_fs(&metadata));
+                }
+                ignore::WalkState::Continue
+            })
+        });
+        entries
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn load(path: &Path) -> io::Result<Self> {
+        let mut lines = BufReader::new(fs::File::open(path)?).lines();
+        let mut next_line = || -> io::Result<String> {
+            lines
+                .next()
+                .unwrap_or_else(|| Err(invalid_data("truncated index")))
+        };
+        if next_line()? != INDEX_HEADER {
+            return Err(invalid_data("unsupported index version"));
+        }
+        let root = PathBuf::from(unescape(&next_line()?));
+        let respect_gitignore = next_line()? == "1";
+        let checked_at = next_line()?
+            .parse()
+            .map_err(|_| invalid_data("invalid check time"))?;
+
+        let mut entries = BTreeMap::new();
+        for line in lines {
+            let line = line?;
+            let mut fields = line.splitn(4, '\t');
+            let (Some(kind), Some(modified), Some(size), Some(path)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(invalid_data("invalid index entry"));
+            };
+            let metadata = EntryMetadata {
+                is_dir: kind == "d",
+                modified: modified
+                    .parse()
+                    .map_err(|_| invalid_data("invalid modification time"))?,
+                size: size.parse().map_err(|_| invalid_data("invalid size"))?,
+            };
+            entries.insert(unescape(path), metadata);
+        }
+
+        Ok(Self {
+            key: IndexKey {
+                root,
+                respect_gitignore,
+            },
+            entries,
+            checked_at,
+            dirty: false,
+            last_saved: None,
+        })
+    }
+
+    fn save(&mut self, dir: &Path) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        fs::create_dir_all(dir)?;
+        let path = index_file_path(dir, &self.key);
+        let temp_path = path.with_extension(format!("tmp-{}", std::process::id()));
+        let mut writer = BufWriter::new(fs::File::create(&temp_path)?);
+        writeln!(writer, "{INDEX_HEADER}")?;
+        writeln!(writer, "{}", escape(&self.key.root.to_string_lossy()))?;
+        writeln!(writer, "{}", u8::from(self.key.respect_gitignore))?;
+        writeln!(writer, "{}", self.checked_at)?;
+        for (entry_path, metadata) in &self.entries {
+            let kind = if metadata.is_dir { "d" } else { "f" };
+            writeln!(
+                writer,
+                "{kind}\t{}\t{}\t{}",
+                metadata.modified,
+                metadata.size,
+                escape(entry_path)
+            )?;
+        }
+        writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)?;
+        // Readers in other processes only ever see a complete index.
+        fs::rename(&temp_path, &path)?;
+        self.dirty = false;
+        self.last_saved = Some(Instant::now());
+        Ok(())
+    }
+}
+
+fn index_file_path(dir: &Path, key: &IndexKey) -> PathBuf {
+    // FNV-1a, which unlike `DefaultHasher` is stable across builds.
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let root = key.root.to_string_lossy();
+    for byte in root
+        .as_bytes()
+        .iter()
+        .chain([u8::from(key.respect_gitignore)].iter())
+    {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    dir.join(format!("{hash:016x}.idx"))
+}
+
+fn child_prefix(path: &str) -> String {
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!("{path}{MAIN_SEPARATOR}")
+    }
+}
+
+fn is_ignore_file(path: &str) -> bool {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| IGNORE_FILE_NAMES.contains(&name))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| u64::try_from(now.as_nanos()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+fn racy_window_nanos() -> u64 {
+    u64::try_from(RACY_WINDOW.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Escapes the characters that would break the line-based index format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn paths(entries: Vec<IndexedEntry>) -> Vec<String> {
+        entries.into_iter().map(|entry| entry.path).collect()
+    }
+
+    #[test]
+    fn index_is_reused_across_stores() {
+        let root = TempDir::new().unwrap();
+        let index_dir = TempDir::new().unwrap();
+        fs::create_dir(root.path().join("src")).unwrap();
+        fs::write(root.path().join("src/main.rs"), "").unwrap();
+
+        let store = FileIndexStore::new(index_dir.path().to_path_buf());
+        let first = store.entries(root.path(), /*respect_gitignore*/ true);
+        drop(store);
+
+        let store = FileIndexStore::new(index_dir.path().to_path_buf());
+        let second = store.entries(root.path(), /*respect_gitignore*/ true);
+        assert_eq!(first, second);
+        assert_eq!(
+            paths(second),
+            vec!["src".to_string(), format!("src{MAIN_SEPARATOR}main.rs")]
+        );
+    }
+
+    #[test]
+    fn stale_index_picks_up_new_and_removed_files() {
+        let root = TempDir::new().unwrap();
+        let index_dir = TempDir::new().unwrap();
+        fs::create_dir(root.path().join("src")).unwrap();
+        fs::write(root.path().join("src/old.rs"), "").unwrap();
+        let store = FileIndexStore::new(index_dir.path().to_path_buf());
+        store.entries(root.path(), /*respect_gitignore*/ true);
+        drop(store);
+
+        fs::remove_file(root.path().join("src/old.rs")).unwrap();
+        fs::create_dir(root.path().join("src/nested")).unwrap();
+        fs::write(root.path().join("src/nested/new.rs"), "").unwrap();
+
+        let store = FileIndexStore::new(index_dir.path().to_path_buf());
+        assert_eq!(
+            paths(store.entries(root.path(), /*respect_gitignore*/ true)),
+            vec![
+                "src".to_string(),
+                format!("src{MAIN_SEPARATOR}nested"),
+                format!("src{MAIN_SEPARATOR}nested{MAIN_SEPARATOR}new.rs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_changes_updates_loaded_index() {
+        let root = TempDir::new().unwrap();
+        let index_dir = TempDir::new().unwrap();
+        fs::write(root.path().join("a.txt"), "").unwrap();
+        let store = FileIndexStore::new(index_dir.path().to_path_buf());
+        store.entries(root.path(), /*respect_gitignore*/ true);
+
+        fs::remove_file(root.path().join("a.txt")).unwrap();
+        fs::write(root.path().join("b.txt"), "").unwrap();
+        store.apply_changes(&[root.path().join("a.txt"), root.path().join("b.txt")]);
+
+        let indexes = store.indexes.lock().unwrap();
+        let index = indexes.values().next().unwrap().lock().unwrap();
+        assert_eq!(paths(index.entries()), vec!["b.txt".to_string()]);
+    }
+
+    #[test]
+    fn new_gitignore_rebuilds_index() {
+        let root = TempDir::new().unwrap();
+        let index_dir = TempDir::new().unwrap();
+        fs::create_dir(root.path().join(".git")).unwrap();
+        fs::write(root.path().join("keep.txt"), "").unwrap();
+        fs::write(root.path().join("skip.txt"), "").unwrap();
+        let store = FileIndexStore::new(index_dir.path().to_path_buf());
+        assert!(
+            paths(store.entries(root.path(), /*respect_gitignore*/ true))
+                .contains(&"skip.txt".to_string())
+        );
+
+        fs::write(root.path().join(".gitignore"), "skip.txt\n").unwrap();
+        let entries = paths(store.entries(root.path(), /*respect_gitignore*/ true));
+        assert!(entries.contains(&"keep.txt".to_string()));
+        assert!(!entries.contains(&"skip.txt".to_string()));
+    }
+
+    #[test]
+    fn escape_round_trips_line_breaks() {
+        let value = "dir\\name\nwith\rbreaks";
+        assert_eq!(unescape(&escape(value)), value);
+    }
+}
@@ -35,8 +35,18 @@ use nucleo::pattern::AtomKind;
 use nucleo::pattern::Pattern;
 
 mod cli;
+mod content;
+mod index;
 
 pub use cli::Cli;
+pub use content::ContentMatch;
+pub use content::ContentMatchRange;
+pub use content::ContentSearchOptions;
+pub use content::ContentSearchSnapshot;
+pub use content::create_content_session;
+pub use index::FileIndexStore;
+pub use index::INDEX_DIR_NAME;
+pub use index::IndexedEntry;
 
 /// A single match result returned from the search.
 ///
@@ -110,6 +120,9 @@ pub struct FileSearchOptions {
     /// turns off `.gitignore`, git-global/exclude rules, `.ignore`, and
     /// parent-directory ignore scanning.
     pub respect_gitignore: bool,
+    /// Persistent index to read the entries of each search directory from
+    /// instead of walking it.
+    pub index: Option<Arc<FileIndexStore>>,
 }
 
 impl Default for FileSearchOptions {
@@ -122,6 +135,7 @@ impl Default for FileSearchOptions {
             threads: NonZero::new(2).unwrap(),
             compute_indices: false,
             respect_gitignore: true,
+            index: None,
         }
     }
 }
@@ -130,19 +144,22 @@ pub trait SessionReporter: Send + Sync + 'static {
     /// Called when the debounced top-N changes.
     fn on_update(&self, snapshot: &FileSearchSnapshot);
 
+    /// Called when the matches of a content search session change.
+    fn on_content_update(&self, _snapshot: &ContentSearchSnapshot) {}
+
     /// Called when the session becomes idle or is cancelled. Guaranteed to be called at least once per update_query.
     fn on_complete(&self);
 }
 
 pub struct FileSearchSession {
-    inner: Arc<SessionInner>,
+    work_tx: Sender<WorkSignal>,
+    shutdown: Arc<AtomicBool>,
 }
 
 impl FileSearchSession {
     /// Update the query. This should be cheap relative to re-walking.
     pub fn update_query(&self, pattern_text: &str) {
         let _ = self
-            .inner
             .work_tx
             .send(WorkSignal::QueryUpdated(pattern_text.to_string()));
     }
@@ -150,8 +167,8 @@ impl FileSearchSession {
 
 impl Drop for FileSearchSession {
     fn drop(&mut self) {
-        self.inner.shutdown.store(true, Ordering::Relaxed);
-        let _ = self.inner.work_tx.send(WorkSignal::Shutdown);
+        self.shutdown.store(true, Ordering::Relaxed);
+        let _ = self.work_tx.send(WorkSignal::Shutdown);
     }
 }
 
@@ -167,6 +184,7 @@ pub fn create_session(
         threads,
         compute_indices,
         respect_gitignore,
+        index,
     } = options;
 
     let Some(primary_search_directory) = search_directories.first() else {
@@ -195,19 +213,25 @@ pub fn create_session(
         threads: threads.get(),
         compute_indices,
         respect_gitignore,
+        index,
         cancelled,
         shutdown: Arc::new(AtomicBool::new(false)),
         reporter,
         work_tx,
     });
 
+    let session = FileSearchSession {
+        work_tx: inner.work_tx.clone(),
+        shutdown: inner.shutdown.clone(),
+    };
+
     let matcher_inner = inner.clone();
     thread::spawn(move || matcher_worker(matcher_inner, work_rx, nucleo));
 
     let walker_inner = inner.clone();
     thread::spawn(move || walker_worker(walker_inner, override_matcher, injector));
 
-    Ok(FileSearchSession { inner })
+    Ok(session)
 }
 
 pub trait Reporter {
@@ -270,6 +294,7 @@ pub async fn run_main<T: Reporter>(
             threads,
             compute_indices,
             respect_gitignore: true,
+            index: None,
         },
         /*cancel_flag*/ None,
     )?;
@@ -348,6 +373,7 @@ struct SessionInner {
     threads: usize,
     compute_indices: bool,
     respect_gitignore: bool,
+    index: Option<Arc<FileIndexStore>>,
     cancelled: Arc<AtomicBool>,
     shutdown: Arc<AtomicBool>,
     reporter: Arc<dyn SessionReporter>,
@@ -396,8 +422,8 @@ fn get_file_path<'a>(path: &'a Path, search_directories: &[PathBuf]) -> Option<(
     rel_path.to_str().map(|p| (root_idx, p))
 }
 
-/// Walks the search directories and feeds discovered paths into `nucleo`
-/// via the injector.
+/// Builds the walker over `search_directories` shared by the filename and
+/// content searches, or `None` when there is nothing to walk.
 ///
 /// The walker uses `require_git(true)` to match git's own ignore semantics:
 /// git never reads `.gitignore` files from directories above the repository
@@ -408,22 +434,19 @@ fn get_file_path<'a>(path: &'a Path, search_directories: &[PathBuf]) -> Option<(
 ///
 /// When `respect_gitignore` is `false`, all git-related ignore processing is
 /// disabled regardless of this flag.
-fn walker_worker(
-    inner: Arc<SessionInner>,
+fn build_walker(
+    search_directories: &[PathBuf],
+    threads: usize,
+    respect_gitignore: bool,
     override_matcher: Option<ignore::overrides::Override>,
-    injector: Injector<Arc<str>>,
-) {
-    let Some(first_root) = inner.search_directories.first() else {
-        let _ = inner.work_tx.send(WorkSignal::WalkComplete);
-        return;
-    };
-
+) -> Option<WalkBuilder> {
+    let first_root = search_directories.first()?;
     let mut walk_builder = WalkBuilder::new(first_root);
-    for root in inner.search_directories.iter().skip(1) {
+    for root in search_directories.iter().skip(1) {
         walk_builder.add(root);
     }
     walk_builder
-        .threads(inner.threads)
+        .threads(threads)
         // Allow hidden entries.
         .hidden(false)
         // Follow symlinks to search their contents.
@@ -431,7 +454,7 @@ fn walker_worker(
         // Keep ignore behavior aligned with git repositories: only apply
         // gitignore rules when a git context exists.
         .require_git(true);
-    if !inner.respect_gitignore {
+    if !respect_gitignore {
         walk_builder
             .git_ignore(false)
             .git_global(false)
@@ -442,6 +465,31 @@ fn walker_worker(
     if let Some(override_matcher) = override_matcher {
         walk_builder.overrides(override_matcher);
     }
+    Some(walk_builder)
+}
+
+/// Walks the search directories and feeds discovered paths into `nucleo`
+/// via the injector.
+fn walker_worker(
+    inner: Arc<SessionInner>,
+    override_matcher: Option<ignore::overrides::Override>,
+    injector: Injector<Arc<str>>,
+) {
+    if let Some(index) = inner.index.clone() {
+        inject_indexed_entries(&inner, &index, override_matcher.as_ref(), &injector);
+        let _ = inner.work_tx.send(WorkSignal::WalkComplete);
+        return;
+    }
+
+    let Some(walk_builder) = build_walker(
+        &inner.search_directories,
+        inner.threads,
+        inner.respect_gitignore,
+        override_matcher,
+    ) else {
+        let _ = inner.work_tx.send(WorkSignal::WalkComplete);
+        return;
+    };
 
     let walker = walk_builder.build_parallel();
 
@@ -480,6 +528,56 @@ fn walker_worker(
     let _ = inner.work_tx.send(WorkSignal::WalkComplete);
 }
 
+/// Feeds the entries recorded in `index` into `nucleo`, skipping the ones the
+/// walker would have excluded.
+fn inject_indexed_entries(
+    inner: &SessionInner,
+    index: &FileIndexStore,
+    override_matcher: Option<&ignore::overrides::Override>,
+    injector: &Injector<Arc<str>>,
+) {
+    for root in &inner.search_directories {
+        for entry in index.entries(root, inner.respect_gitignore) {
+            if inner.cancelled.load(Ordering::Relaxed) || inner.shutdown.load(Ordering::Relaxed) {
+                return;
+            }
+            let full_path = root.join(&entry.path);
+            if override_matcher
+                .is_some_and(|matcher| is_excluded(matcher, root, &full_path, entry.is_dir))
+            {
+                continue;
+            }
+            let Some(full_path_str) = full_path.to_str() else {
+                continue;
+            };
+            if let Some((_, relative_path)) =
+                get_file_path(&full_path, &inner.search_directories)
+            {
+                injector.push(Arc::from(full_path_str), |_, cols| {
+                    cols[0] = Utf32String::from(relative_path);
+                });
+            }
+        }
+    }
+}
+
+/// Whether `path` or one of its ancestors below `root` is excluded, which is
+/// how the walker prunes excluded directories.
+fn is_excluded(
+    matcher: &ignore::overrides::Override,
+    root: &Path,
+    path: &Path,
+    is_dir: bool,
+) -> bool {
+    if matcher.matched(path, is_dir).is_ignore() {
+        return true;
+    }
+    path.ancestors()
+        .skip(1)
+        .take_while(|ancestor| *ancestor != root && ancestor.starts_with(root))
+        .any(|ancestor| matcher.matched(ancestor, /*is_dir*/ true).is_ignore())
+}
+
 fn matcher_worker(
     inner: Arc<SessionInner>,
     work_rx: Receiver<WorkSignal>,
@@ -966,6 +1064,7 @@ mod tests {
             threads: NonZero::new(2).unwrap(),
             compute_indices: false,
             respect_gitignore: true,
+            index: None,
         };
         let results = run(
             "file-000",
@@ -985,6 +1084,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn run_reads_entries_from_index() {
+        let dir = create_temp_tree(/*file_count*/ 40);
+        fs::create_dir(dir.path().join("excluded")).unwrap();
+        fs::write(dir.path().join("excluded/file-0000.txt"), "excluded").unwrap();
+        let index_dir = tempfile::tempdir().unwrap();
+        let options = FileSearchOptions {
+            exclude: vec!["excluded".to_string()],
+            index: Some(Arc::new(FileIndexStore::new(index_dir.path().to_path_buf()))),
+            ..Default::default()
+        };
+        let results = run(
+            "file-0000",
+            vec![dir.path().to_path_buf()],
+            options,
+            /*cancel_flag*/ None,
+        )
+        .expect("run ok");
+
+        let paths = results
+            .matches
+            .iter()
+            .map(|m| m.path.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["file-0000.txt".to_string()]);
+    }
+
     #[test]
     fn run_returns_directory_matches_for_query() {
         let dir = tempfile::tempdir().unwrap();
@@ -1001,6 +1127,7 @@ mod tests {
                 threads: NonZero::new(2).unwrap(),
                 compute_indices: false,
                 respect_gitignore: true,
+                index: None,
             },
             /*cancel_flag*/ None,
         )
@@ -1069,6 +1196,7 @@ mod tests {
                 threads: NonZero::new(2).unwrap(),
                 compute_indices: false,
                 respect_gitignore: true,
+                index: None,
             },
             /*cancel_flag*/ None,
         )
@@ -1089,6 +1217,7 @@ mod tests {
                 threads: NonZero::new(2).unwrap(),
                 compute_indices: false,
                 respect_gitignore: true,
+                index: None,
             },
             /*cancel_flag*/ None,
         )
@@ -1133,6 +1262,7 @@ mod tests {
                 threads: NonZero::new(2).unwrap(),
                 compute_indices: false,
                 respect_gitignore: true,
+                index: None,
             },
             /*cancel_flag*/ None,
         )
@@ -1153,6 +1283,7 @@ mod tests {
                 threads: NonZero::new(2).unwrap(),
                 compute_indices: false,
                 respect_gitignore: true,
+                index: None,
             },
             /*cancel_flag*/ None,
         )
@@ -1173,6 +1304,7 @@ mod tests {
                 threads: NonZero::new(2).unwrap(),
                 compute_indices: false,
                 respect_gitignore: true,
+                index: None,
             },
             /*cancel_flag*/ None,
         )
@@ -189,4 +189,22 @@ pub trait ExecutorFileSystem: Send + Sync {
         copy_options: CopyOptions,
         sandbox: Option<&FileSystemSandboxContext>,
     ) -> FileSystemResult<()>;
+
+    /// Creates a symlink at `path` pointing at `target`. The target is stored as given, so a
+    /// relative target is resolved against the directory containing the link.
+    async fn create_symlink(
+        &self,
+        path: &AbsolutePathBuf,
+        target: &Path,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()>;
+
+    /// Sets the permission bits of `path`, for example `0o755`. Platforms without Unix permissions
+    /// ignore the request and log a warning.
+    async fn set_mode(
+        &self,
+        path: &AbsolutePathBuf,
+        mode: u32,
+        sandbox: Option<&FileSystemSandboxContext>,
+    ) -> FileSystemResult<()>;
 }
@@ -24,13 +24,14 @@ pub(crate) struct CommandRunResult {
 pub(crate) async fn run_command(
     shell: &CommandShell,
     handler: &ConfiguredHandler,
+    command: &str,
     input_json: &str,
     cwd: &Path,
 ) -> CommandRunResult {
     let started_at = chrono::Utc::now().timestamp();
     let started = Instant::now();
 
-    let mut command = build_command(shell, handler);
+    let mut command = build_command(shell, command);
     command
         .current_dir(cwd)
         .stdin(Stdio::piped())
@@ -100,18 +101,18 @@ pub(crate) async fn run_command(
     }
 }
 
-fn build_command(shell: &CommandShell, handler: &ConfiguredHandler) -> Command {
+pub(super) fn build_command(shell: &CommandShell, hook_command: &str) -> Command {
     let mut command = if shell.program.is_empty() {
         default_shell_command()
     } else {
         Command::new(&shell.program)
     };
     if shell.program.is_empty() {
-        command.arg(&handler.command);
+        command.arg(hook_command);
         command
     } else {
         command.args(&shell.args);
-        command.arg(&handler.command);
+        command.arg(hook_command);
         command
     }
 }
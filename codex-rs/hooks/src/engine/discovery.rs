@@ -11,15 +11,24 @@ use codex_config::HookHandlerConfig;
 use codex_config::HooksFile;
 use codex_config::ManagedHooksRequirementsToml;
 use codex_config::MatcherGroup;
+use codex_config::ModelHookHandlerConfig;
 use codex_config::RequirementSource;
+use codex_config::ServerHookHandlerConfig;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use serde::Deserialize;
 
 use super::ConfiguredHandler;
+use super::HandlerKind;
+use super::ModelHandlerConfig;
+use super::ServerHandlerConfig;
 use crate::events::common::matcher_pattern_for_event;
 use crate::events::common::validate_matcher_pattern;
+use codex_protocol::protocol::HookEventName;
 use codex_protocol::protocol::HookSource;
 
+const DEFAULT_COMMAND_TIMEOUT_SEC: u64 = 600;
+const DEFAULT_MODEL_TIMEOUT_SEC: u64 = 30;
+
 pub(crate) struct DiscoveryResult {
     pub handlers: Vec<ConfiguredHandler>,
     pub warnings: Vec<String>,
@@ -290,7 +299,7 @@ fn append_matcher_groups(
     warnings: &mut Vec<String>,
     display_order: &mut i64,
     source: HookHandlerSource<'_>,
-    event_name: codex_protocol::protocol::HookEventName,
+    event_name: HookEventName,
     groups: Vec<MatcherGroup>,
 ) {
     for group in groups {
@@ -311,7 +320,7 @@ fn append_group_handlers(
     warnings: &mut Vec<String>,
     display_order: &mut i64,
     source: HookHandlerSource<'_>,
-    event_name: codex_protocol::protocol::HookEventName,
+    event_name: HookEventName,
     matcher: Option<&str>,
     group_handlers: Vec<HookHandlerConfig>,
 ) {
@@ -347,12 +356,12 @@ fn append_group_handlers(
                     ));
                     continue;
                 }
-                let timeout_sec = timeout_sec.unwrap_or(600).max(1);
+                let timeout_sec = timeout_sec.unwrap_or(DEFAULT_COMMAND_TIMEOUT_SEC).max(1);
                 handlers.push(ConfiguredHandler {
                     event_name,
                     is_managed: source.is_managed,
                     matcher: matcher.map(ToOwned::to_owned),
-                    command,
+                    kind: HandlerKind::Command { command },
                     timeout_sec,
                     status_message,
                     source_path: source.path.clone(),
@@ -361,18 +370,138 @@ fn append_group_handlers(
                 });
                 *display_order += 1;
             }
-            HookHandlerConfig::Prompt {} => warnings.push(format!(
-                "skipping prompt hook in {}: prompt hooks are not supported yet",
-                source.path.display()
-            )),
-            HookHandlerConfig::Agent {} => warnings.push(format!(
-                "skipping agent hook in {}: agent hooks are not supported yet",
-                source.path.display()
-            )),
+            HookHandlerConfig::Prompt(config) => {
+                if let Some(handler) = model_handler(
+                    warnings,
+                    display_order,
+                    source,
+                    event_name,
+                    matcher,
+                    "prompt",
+                    config,
+                    HandlerKind::Prompt,
+                ) {
+                    handlers.push(handler);
+                }
+            }
+            HookHandlerConfig::Agent(config) => {
+                if let Some(handler) = model_handler(
+                    warnings,
+                    display_order,
+                    source,
+                    event_name,
+                    matcher,
+                    "agent",
+                    config,
+                    HandlerKind::Agent,
+                ) {
+                    handlers.push(handler);
+                }
+            }
+            HookHandlerConfig::Server(config) => {
+                if let Some(handler) =
+                    server_handler(warnings, display_order, source, event_name, matcher, config)
+                {
+                    handlers.push(handler);
+                }
+            }
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn model_handler(
+    warnings: &mut Vec<String>,
+    display_order: &mut i64,
+    source: HookHandlerSource<'_>,
+    event_name: HookEventName,
+    matcher: Option<&str>,
+    handler_label: &str,
+    config: ModelHookHandlerConfig,
+    kind: fn(ModelHandlerConfig) -> HandlerKind,
+) -> Option<ConfiguredHandler> {
+    // Session start hooks only add context, which an allow/block judgement cannot provide.
+    if event_name == HookEventName::SessionStart {
+        warnings.push(format!(
+            "skipping {handler_label} hook in {}: {handler_label} hooks are not supported for SessionStart",
+            source.path.display()
+        ));
+        return None;
+    }
+    if config.prompt.trim().is_empty() {
+        warnings.push(format!(
+            "skipping {handler_label} hook with an empty prompt in {}",
+            source.path.display()
+        ));
+        return None;
+    }
+    let handler = ConfiguredHandler {
+        event_name,
+        is_managed: source.is_managed,
+        matcher: matcher.map(ToOwned::to_owned),
+        kind: kind(ModelHandlerConfig {
+            prompt: config.prompt,
+            model: config.model,
+            output_schema: config.output_schema,
+        }),
+        timeout_sec: config
+            .timeout_sec
+            .unwrap_or(DEFAULT_MODEL_TIMEOUT_SEC)
+            .max(1),
+        status_message: config.status_message,
+        source_path: source.path.clone(),
+        source: source.source,
+        display_order: *display_order,
+    };
+    *display_order += 1;
+    Some(handler)
+}
+
+fn server_handler(
+    warnings: &mut Vec<String>,
+    display_order: &mut i64,
+    source: HookHandlerSource<'_>,
+    event_name: HookEventName,
+    matcher: Option<&str>,
+    config: ServerHookHandlerConfig,
+) -> Option<ConfiguredHandler> {
+    if config.socket.as_os_str().is_empty() {
+        warnings.push(format!(
+            "skipping server hook without a socket in {}",
+            source.path.display()
+        ));
+        return None;
+    }
+    let socket = source
+        .path
+        .parent()
+        .unwrap_or_else(|| source.path.clone())
+        .join(&config.socket);
+    let handler = ConfiguredHandler {
+        event_name,
+        is_managed: source.is_managed,
+        matcher: matcher.map(ToOwned::to_owned),
+        kind: HandlerKind::Server(ServerHandlerConfig {
+            socket,
+            command: config.command.filter(|command| !command.trim().is_empty()),
+            fallback: config.fallback,
+            fallback_command: config
+                .fallback_command
+                .filter(|command| !command.trim().is_empty()),
+        }),
+        timeout_sec: config
+            .timeout_sec
+            .unwrap_or(DEFAULT_COMMAND_TIMEOUT_SEC)
+            .max(1),
+        status_message: config.status_message,
+        source_path: source.path.clone(),
+        source: source.source,
+        display_order: *display_order,
+    };
+    *display_order += 1;
+    Some(handler)
+}
+
 fn hook_source_for_config_layer_source(source: &ConfigLayerSource) -> HookSource {
     match source {
         ConfigLayerSource::System { .. } => HookSource::System,
@@ -414,9 +543,15 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::ConfiguredHandler;
+    use super::HandlerKind;
+    use super::ModelHandlerConfig;
+    use super::ServerHandlerConfig;
     use super::append_matcher_groups;
     use codex_config::HookHandlerConfig;
     use codex_config::MatcherGroup;
+    use codex_config::ModelHookHandlerConfig;
+    use codex_config::ServerHookFallback;
+    use codex_config::ServerHookHandlerConfig;
 
     fn source_path() -> AbsolutePathBuf {
         test_path_buf("/tmp/hooks.json").abs()
@@ -469,7 +604,9 @@ mod tests {
                 event_name: HookEventName::UserPromptSubmit,
                 is_managed: false,
                 matcher: None,
-                command: "echo hello".to_string(),
+                kind: HandlerKind::Command {
+                    command: "echo hello".to_string(),
+                },
                 timeout_sec: 600,
                 status_message: None,
                 source_path: source_path.clone(),
@@ -502,7 +639,9 @@ mod tests {
                 event_name: HookEventName::PreToolUse,
                 is_managed: false,
                 matcher: Some("^Bash$".to_string()),
-                command: "echo hello".to_string(),
+                kind: HandlerKind::Command {
+                    command: "echo hello".to_string(),
+                },
                 timeout_sec: 600,
                 status_message: None,
                 source_path: source_path.clone(),
@@ -555,6 +694,142 @@ mod tests {
         assert_eq!(handlers[0].matcher.as_deref(), Some("Edit|Write"));
     }
 
+    fn model_hook_config(prompt: &str) -> ModelHookHandlerConfig {
+        ModelHookHandlerConfig {
+            prompt: prompt.to_string(),
+            model: None,
+            timeout_sec: None,
+            output_schema: None,
+            status_message: Some("reviewing".to_string()),
+        }
+    }
+
+    #[test]
+    fn prompt_and_agent_hooks_are_discovered_for_stop() {
+        let mut handlers = Vec::new();
+        let mut warnings = Vec::new();
+        let mut display_order = 0;
+        let source_path = source_path();
+
+        append_matcher_groups(
+            &mut handlers,
+            &mut warnings,
+            &mut display_order,
+            hook_handler_source(&source_path),
+            HookEventName::Stop,
+            vec![MatcherGroup {
+                matcher: None,
+                hooks: vec![
+                    HookHandlerConfig::Prompt(model_hook_config("Did the agent run the tests?")),
+                    HookHandlerConfig::Agent(model_hook_config("   ")),
+                ],
+            }],
+        );
+
+        assert_eq!(
+            warnings,
+            vec![format!(
+                "skipping agent hook with an empty prompt in {}",
+                source_path.display()
+            )]
+        );
+        assert_eq!(
+            handlers,
+            vec![ConfiguredHandler {
+                event_name: HookEventName::Stop,
+                is_managed: false,
+                matcher: None,
+                kind: HandlerKind::Prompt(ModelHandlerConfig {
+                    prompt: "Did the agent run the tests?".to_string(),
+                    model: None,
+                    output_schema: None,
+                }),
+                timeout_sec: 30,
+                status_message: Some("reviewing".to_string()),
+                source_path: source_path.clone(),
+                source: hook_source(),
+                display_order: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn prompt_hooks_are_skipped_for_session_start() {
+        let mut handlers = Vec::new();
+        let mut warnings = Vec::new();
+        let mut display_order = 0;
+        let source_path = source_path();
+
+        append_matcher_groups(
+            &mut handlers,
+            &mut warnings,
+            &mut display_order,
+            hook_handler_source(&source_path),
+            HookEventName::SessionStart,
+            vec![MatcherGroup {
+                matcher: None,
+                hooks: vec![HookHandlerConfig::Prompt(model_hook_config("Judge"))],
+            }],
+        );
+
+        assert_eq!(handlers, Vec::<ConfiguredHandler>::new());
+        assert_eq!(
+            warnings,
+            vec![format!(
+                "skipping prompt hook in {}: prompt hooks are not supported for SessionStart",
+                source_path.display()
+            )]
+        );
+    }
+
+    #[test]
+    fn server_hook_socket_resolves_relative_to_hook_source() {
+        let mut handlers = Vec::new();
+        let mut warnings = Vec::new();
+        let mut display_order = 0;
+        let source_path = source_path();
+
+        append_matcher_groups(
+            &mut handlers,
+            &mut warnings,
+            &mut display_order,
+            hook_handler_source(&source_path),
+            HookEventName::PreToolUse,
+            vec![MatcherGroup {
+                matcher: Some("^Bash$".to_string()),
+                hooks: vec![HookHandlerConfig::Server(ServerHookHandlerConfig {
+                    socket: "policy.sock".into(),
+                    command: Some("python3 policy_server.py".to_string()),
+                    timeout_sec: None,
+                    fallback: ServerHookFallback::Block,
+                    fallback_command: Some("  ".to_string()),
+                    status_message: None,
+                })],
+            }],
+        );
+
+        assert_eq!(warnings, Vec::<String>::new());
+        assert_eq!(
+            handlers,
+            vec![ConfiguredHandler {
+                event_name: HookEventName::PreToolUse,
+                is_managed: false,
+                matcher: Some("^Bash$".to_string()),
+                kind: HandlerKind::Server(ServerHandlerConfig {
+                    socket: test_path_buf("/tmp/policy.sock").abs(),
+                    command: Some("python3 policy_server.py".to_string()),
+                    fallback: ServerHookFallback::Block,
+                    fallback_command: None,
+                }),
+                timeout_sec: 600,
+                status_message: None,
+                source_path: source_path.clone(),
+                source: hook_source(),
+                display_order: 0,
+            }]
+        );
+    }
+
     #[test]
     fn hook_source_for_config_layer_source_discards_source_details() {
         let config_file = test_path_buf("/tmp/.codex/config.toml").abs();
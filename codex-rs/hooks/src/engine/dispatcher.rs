@@ -5,15 +5,13 @@ use futures::future::join_all;
 use codex_protocol::protocol::HookCompletedEvent;
 use codex_protocol::protocol::HookEventName;
 use codex_protocol::protocol::HookExecutionMode;
-use codex_protocol::protocol::HookHandlerType;
 use codex_protocol::protocol::HookRunStatus;
 use codex_protocol::protocol::HookRunSummary;
 use codex_protocol::protocol::HookScope;
 
-use super::CommandShell;
 use super::ConfiguredHandler;
+use super::HandlerRunner;
 use super::command_runner::CommandRunResult;
-use super::command_runner::run_command;
 use crate::events::common::matches_matcher;
 
 #[derive(Debug)]
@@ -46,7 +44,13 @@ pub(crate) fn select_handlers_for_matcher_inputs(
             HookEventName::PreToolUse
             | HookEventName::PermissionRequest
             | HookEventName::PostToolUse
-            | HookEventName::SessionStart => {
+            | HookEventName::SessionStart
+            | HookEventName::PreCompact
+            | HookEventName::PostCompact
+            | HookEventName::SubagentStart
+            | HookEventName::SubagentStop
+            | HookEventName::PreApplyPatch
+            | HookEventName::PostApplyPatch => {
                 if matcher_inputs.is_empty() {
                     matches_matcher(handler.matcher.as_deref(), /*input*/ None)
                 } else {
@@ -55,7 +59,9 @@ pub(crate) fn select_handlers_for_matcher_inputs(
                         .any(|input| matches_matcher(handler.matcher.as_deref(), Some(input)))
                 }
             }
-            HookEventName::UserPromptSubmit | HookEventName::Stop => true,
+            HookEventName::UserPromptSubmit | HookEventName::Stop | HookEventName::TurnError => {
+                true
+            }
         })
         .cloned()
         .collect()
@@ -65,7 +71,7 @@ pub(crate) fn running_summary(handler: &ConfiguredHandler) -> HookRunSummary {
     HookRunSummary {
         id: handler.run_id(),
         event_name: handler.event_name,
-        handler_type: HookHandlerType::Command,
+        handler_type: handler.handler_type(),
         execution_mode: HookExecutionMode::Sync,
         scope: scope_for_event(handler.event_name),
         source_path: handler.source_path.clone(),
@@ -81,7 +87,7 @@ pub(crate) fn running_summary(handler: &ConfiguredHandler) -> HookRunSummary {
 }
 
 pub(crate) async fn execute_handlers<T>(
-    shell: &CommandShell,
+    runner: &HandlerRunner,
     handlers: Vec<ConfiguredHandler>,
     input_json: String,
     cwd: &Path,
@@ -91,7 +97,7 @@ pub(crate) async fn execute_handlers<T>(
     let results = join_all(
         handlers
             .iter()
-            .map(|handler| run_command(shell, handler, &input_json, cwd)),
+            .map(|handler| runner.run(handler, &input_json, cwd)),
     )
     .await;
 
@@ -111,7 +117,7 @@ pub(crate) fn completed_summary(
     HookRunSummary {
         id: handler.run_id(),
         event_name: handler.event_name,
-        handler_type: HookHandlerType::Command,
+        handler_type: handler.handler_type(),
         execution_mode: HookExecutionMode::Sync,
         scope: scope_for_event(handler.event_name),
         source_path: handler.source_path.clone(),
@@ -133,7 +139,14 @@ fn scope_for_event(event_name: HookEventName) -> HookScope {
         | HookEventName::PermissionRequest
         | HookEventName::PostToolUse
         | HookEventName::UserPromptSubmit
-        | HookEventName::Stop => HookScope::Turn,
+        | HookEventName::Stop
+        | HookEventName::PreCompact
+        | HookEventName::PostCompact
+        | HookEventName::SubagentStart
+        | HookEventName::SubagentStop
+        | HookEventName::PreApplyPatch
+        | HookEventName::PostApplyPatch
+        | HookEventName::TurnError => HookScope::Turn,
     }
 }
 
@@ -147,6 +160,7 @@ mod tests {
     use super::ConfiguredHandler;
     use super::select_handlers;
     use super::select_handlers_for_matcher_inputs;
+    use crate::engine::HandlerKind;
 
     fn make_handler(
         event_name: HookEventName,
@@ -158,7 +172,9 @@ mod tests {
             event_name,
             is_managed: false,
             matcher: matcher.map(str::to_owned),
-            command: command.to_string(),
+            kind: HandlerKind::Command {
+                command: command.to_string(),
+            },
             timeout_sec: 5,
             status_message: None,
             source_path: test_path_buf("/tmp/hooks.json").abs(),
@@ -400,9 +416,16 @@ mod tests {
 
         let selected = select_handlers(&handlers, HookEventName::Stop, /*matcher_input*/ None);
 
-        assert_eq!(selected.len(), 3);
-        assert_eq!(selected[0].command, "first");
-        assert_eq!(selected[1].command, "second");
-        assert_eq!(selected[2].command, "third");
+        assert_eq!(
+            selected
+                .iter()
+                .map(|handler| handler.kind.clone())
+                .collect::<Vec<_>>(),
+            ["first", "second", "third"]
+                .map(|command| HandlerKind::Command {
+                    command: command.to_string(),
+                })
+                .to_vec(),
+        );
     }
 }
@@ -1,26 +1,50 @@
 pub(crate) mod command_runner;
 pub(crate) mod discovery;
 pub(crate) mod dispatcher;
+pub(crate) mod model_runner;
 pub(crate) mod output_parser;
 pub(crate) mod schema_loader;
+pub(crate) mod server_runner;
+
+use std::path::Path;
+use std::sync::Arc;
 
 use codex_config::ConfigLayerStack;
+use codex_config::ServerHookFallback;
+use codex_protocol::protocol::HookHandlerType;
 use codex_protocol::protocol::HookRunSummary;
 use codex_protocol::protocol::HookSource;
 use codex_utils_absolute_path::AbsolutePathBuf;
 
+use crate::engine::command_runner::CommandRunResult;
+use crate::engine::server_runner::HookServerPool;
 use crate::events::permission_request::PermissionRequestOutcome;
 use crate::events::permission_request::PermissionRequestRequest;
+use crate::events::post_apply_patch::PostApplyPatchOutcome;
+use crate::events::post_apply_patch::PostApplyPatchRequest;
+use crate::events::post_compact::PostCompactOutcome;
+use crate::events::post_compact::PostCompactRequest;
 use crate::events::post_tool_use::PostToolUseOutcome;
 use crate::events::post_tool_use::PostToolUseRequest;
+use crate::events::pre_apply_patch::PreApplyPatchOutcome;
+use crate::events::pre_apply_patch::PreApplyPatchRequest;
+use crate::events::pre_compact::PreCompactOutcome;
+use crate::events::pre_compact::PreCompactRequest;
 use crate::events::pre_tool_use::PreToolUseOutcome;
 use crate::events::pre_tool_use::PreToolUseRequest;
 use crate::events::session_start::SessionStartOutcome;
 use crate::events::session_start::SessionStartRequest;
 use crate::events::stop::StopOutcome;
 use crate::events::stop::StopRequest;
+use crate::events::subagent_start::SubagentStartOutcome;
+use crate::events::subagent_start::SubagentStartRequest;
+use crate::events::subagent_stop::SubagentStopOutcome;
+use crate::events::subagent_stop::SubagentStopRequest;
+use crate::events::turn_error::TurnErrorOutcome;
+use crate::events::turn_error::TurnErrorRequest;
 use crate::events::user_prompt_submit::UserPromptSubmitOutcome;
 use crate::events::user_prompt_submit::UserPromptSubmitRequest;
+use crate::model_client::HookModelClient;
 
 #[derive(Debug, Clone)]
 pub(crate) struct CommandShell {
@@ -28,12 +52,89 @@ pub(crate) struct CommandShell {
     pub args: Vec<String>,
 }
 
+/// Runs configured handlers as shell commands, model requests, or requests to hook servers.
+#[derive(Clone)]
+pub(crate) struct HandlerRunner {
+    pub shell: CommandShell,
+    pub model_client: Option<Arc<dyn HookModelClient>>,
+    pub servers: Arc<HookServerPool>,
+}
+
+impl HandlerRunner {
+    pub(crate) async fn run(
+        &self,
+        handler: &ConfiguredHandler,
+        input_json: &str,
+        cwd: &Path,
+    ) -> CommandRunResult {
+        match &handler.kind {
+            HandlerKind::Command { command } => {
+                command_runner::run_command(&self.shell, handler, command, input_json, cwd).await
+            }
+            HandlerKind::Prompt(config) => {
+                model_runner::run_model_handler(
+                    self.model_client.as_deref(),
+                    handler,
+                    config,
+                    /*include_transcript*/ false,
+                    input_json,
+                )
+                .await
+            }
+            HandlerKind::Agent(config) => {
+                model_runner::run_model_handler(
+                    self.model_client.as_deref(),
+                    handler,
+                    config,
+                    /*include_transcript*/ true,
+                    input_json,
+                )
+                .await
+            }
+            HandlerKind::Server(config) => {
+                server_runner::run_server_handler(
+                    &self.servers,
+                    &self.shell,
+                    handler,
+                    config,
+                    input_json,
+                    cwd,
+                )
+                .await
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum HandlerKind {
+    Command { command: String },
+    Prompt(ModelHandlerConfig),
+    Agent(ModelHandlerConfig),
+    Server(ServerHandlerConfig),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ModelHandlerConfig {
+    pub prompt: String,
+    pub model: Option<String>,
+    pub output_schema: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ServerHandlerConfig {
+    pub socket: AbsolutePathBuf,
+    pub command: Option<String>,
+    pub fallback: ServerHookFallback,
+    pub fallback_command: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct ConfiguredHandler {
     pub event_name: codex_protocol::protocol::HookEventName,
     pub is_managed: bool,
     pub matcher: Option<String>,
-    pub command: String,
+    pub kind: HandlerKind,
     pub timeout_sec: u64,
     pub status_message: Option<String>,
     pub source_path: AbsolutePathBuf,
@@ -51,6 +152,15 @@ impl ConfiguredHandler {
         )
     }
 
+    pub fn handler_type(&self) -> HookHandlerType {
+        match self.kind {
+            HandlerKind::Command { .. } => HookHandlerType::Command,
+            HandlerKind::Prompt(_) => HookHandlerType::Prompt,
+            HandlerKind::Agent(_) => HookHandlerType::Agent,
+            HandlerKind::Server(_) => HookHandlerType::Server,
+        }
+    }
+
     fn event_name_label(&self) -> &'static str {
         match self.event_name {
             codex_protocol::protocol::HookEventName::PreToolUse => "pre-tool-use",
@@ -59,6 +169,13 @@ impl ConfiguredHandler {
             codex_protocol::protocol::HookEventName::SessionStart => "session-start",
             codex_protocol::protocol::HookEventName::UserPromptSubmit => "user-prompt-submit",
             codex_protocol::protocol::HookEventName::Stop => "stop",
+            codex_protocol::protocol::HookEventName::PreCompact => "pre-compact",
+            codex_protocol::protocol::HookEventName::PostCompact => "post-compact",
+            codex_protocol::protocol::HookEventName::SubagentStart => "subagent-start",
+            codex_protocol::protocol::HookEventName::SubagentStop => "subagent-stop",
+            codex_protocol::protocol::HookEventName::PreApplyPatch => "pre-apply-patch",
+            codex_protocol::protocol::HookEventName::PostApplyPatch => "post-apply-patch",
+            codex_protocol::protocol::HookEventName::TurnError => "turn-error",
         }
     }
 }
@@ -67,7 +184,7 @@ impl ConfiguredHandler {
 pub(crate) struct ClaudeHooksEngine {
     handlers: Vec<ConfiguredHandler>,
     warnings: Vec<String>,
-    shell: CommandShell,
+    runner: HandlerRunner,
 }
 
 impl ClaudeHooksEngine {
@@ -75,12 +192,18 @@ impl ClaudeHooksEngine {
         enabled: bool,
         config_layer_stack: Option<&ConfigLayerStack>,
         shell: CommandShell,
+        model_client: Option<Arc<dyn HookModelClient>>,
     ) -> Self {
+        let runner = HandlerRunner {
+            shell,
+            model_client,
+            servers: Arc::new(HookServerPool::default()),
+        };
         if !enabled {
             return Self {
                 handlers: Vec::new(),
                 warnings: Vec::new(),
-                shell,
+                runner,
             };
         }
 
@@ -89,7 +212,7 @@ impl ClaudeHooksEngine {
         Self {
             handlers: discovered.handlers,
             warnings: discovered.warnings,
-            shell,
+            runner,
         }
     }
 
@@ -127,25 +250,25 @@ impl ClaudeHooksEngine {
         request: SessionStartRequest,
         turn_id: Option<String>,
     ) -> SessionStartOutcome {
-        crate::events::session_start::run(&self.handlers, &self.shell, request, turn_id).await
+        crate::events::session_start::run(&self.handlers, &self.runner, request, turn_id).await
     }
 
     pub(crate) async fn run_pre_tool_use(&self, request: PreToolUseRequest) -> PreToolUseOutcome {
-        crate::events::pre_tool_use::run(&self.handlers, &self.shell, request).await
+        crate::events::pre_tool_use::run(&self.handlers, &self.runner, request).await
     }
 
     pub(crate) async fn run_permission_request(
         &self,
         request: PermissionRequestRequest,
     ) -> PermissionRequestOutcome {
-        crate::events::permission_request::run(&self.handlers, &self.shell, request).await
+        crate::events::permission_request::run(&self.handlers, &self.runner, request).await
     }
 
     pub(crate) async fn run_post_tool_use(
         &self,
         request: PostToolUseRequest,
     ) -> PostToolUseOutcome {
-        crate::events::post_tool_use::run(&self.handlers, &self.shell, request).await
+        crate::events::post_tool_use::run(&self.handlers, &self.runner, request).await
     }
 
     pub(crate) fn preview_user_prompt_submit(
@@ -159,7 +282,7 @@ impl ClaudeHooksEngine {
         &self,
         request: UserPromptSubmitRequest,
     ) -> UserPromptSubmitOutcome {
-        crate::events::user_prompt_submit::run(&self.handlers, &self.shell, request).await
+        crate::events::user_prompt_submit::run(&self.handlers, &self.runner, request).await
     }
 
     pub(crate) fn preview_stop(&self, request: &StopRequest) -> Vec<HookRunSummary> {
@@ -167,7 +290,87 @@ impl ClaudeHooksEngine {
     }
 
     pub(crate) async fn run_stop(&self, request: StopRequest) -> StopOutcome {
-        crate::events::stop::run(&self.handlers, &self.shell, request).await
+        crate::events::stop::run(&self.handlers, &self.runner, request).await
+    }
+
+    pub(crate) fn preview_pre_compact(&self, request: &PreCompactRequest) -> Vec<HookRunSummary> {
+        crate::events::pre_compact::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_pre_compact(&self, request: PreCompactRequest) -> PreCompactOutcome {
+        crate::events::pre_compact::run(&self.handlers, &self.runner, request).await
+    }
+
+    pub(crate) fn preview_post_compact(&self, request: &PostCompactRequest) -> Vec<HookRunSummary> {
+        crate::events::post_compact::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_post_compact(&self, request: PostCompactRequest) -> PostCompactOutcome {
+        crate::events::post_compact::run(&self.handlers, &self.runner, request).await
+    }
+
+    pub(crate) fn preview_subagent_start(
+        &self,
+        request: &SubagentStartRequest,
+    ) -> Vec<HookRunSummary> {
+        crate::events::subagent_start::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_subagent_start(
+        &self,
+        request: SubagentStartRequest,
+    ) -> SubagentStartOutcome {
+        crate::events::subagent_start::run(&self.handlers, &self.runner, request).await
+    }
+
+    pub(crate) fn preview_subagent_stop(
+        &self,
+        request: &SubagentStopRequest,
+    ) -> Vec<HookRunSummary> {
+        crate::events::subagent_stop::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_subagent_stop(
+        &self,
+        request: SubagentStopRequest,
+    ) -> SubagentStopOutcome {
+        crate::events::subagent_stop::run(&self.handlers, &self.runner, request).await
+    }
+
+    pub(crate) fn preview_pre_apply_patch(
+        &self,
+        request: &PreApplyPatchRequest,
+    ) -> Vec<HookRunSummary> {
+        crate::events::pre_apply_patch::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_pre_apply_patch(
+        &self,
+        request: PreApplyPatchRequest,
+    ) -> PreApplyPatchOutcome {
+        crate::events::pre_apply_patch::run(&self.handlers, &self.runner, request).await
+    }
+
+    pub(crate) fn preview_post_apply_patch(
+        &self,
+        request: &PostApplyPatchRequest,
+    ) -> Vec<HookRunSummary> {
+        crate::events::post_apply_patch::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_post_apply_patch(
+        &self,
+        request: PostApplyPatchRequest,
+    ) -> PostApplyPatchOutcome {
+        crate::events::post_apply_patch::run(&self.handlers, &self.runner, request).await
+    }
+
+    pub(crate) fn preview_turn_error(&self, request: &TurnErrorRequest) -> Vec<HookRunSummary> {
+        crate::events::turn_error::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_turn_error(&self, request: TurnErrorRequest) -> TurnErrorOutcome {
+        crate::events::turn_error::run(&self.handlers, &self.runner, request).await
     }
 }
 
@@ -0,0 +1,410 @@
+use std::io::SeekFrom;
+use std::path::Path;
+use std::time::Duration;
+use std::time::Instant;
+
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+use tokio::time::timeout;
+
+use super::ConfiguredHandler;
+use super::ModelHandlerConfig;
+use super::command_runner::CommandRunResult;
+use crate::model_client::HookModelClient;
+use crate::model_client::HookModelRequest;
+
+const ARGUMENTS_PLACEHOLDER: &str = "$ARGUMENTS";
+const MAX_TRANSCRIPT_BYTES: u64 = 64 * 1024;
+const HOOK_MODEL_INSTRUCTIONS: &str = "You are evaluating a lifecycle hook for Codex, a coding \
+agent. Follow the instructions in the user message and answer only with JSON that matches the \
+requested schema.";
+
+#[derive(Deserialize)]
+struct ModelDecision {
+    decision: Decision,
+    #[serde(default)]
+    reason: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Decision {
+    Allow,
+    Block,
+}
+
+/// Output of a model handler, shaped like the result of a command hook so the event parsers can
+/// interpret it unchanged.
+struct ModelHandlerOutput {
+    exit_code: i32,
+    stdout: String,
+    stderr: String,
+}
+
+pub(crate) async fn run_model_handler(
+    model_client: Option<&dyn HookModelClient>,
+    handler: &ConfiguredHandler,
+    config: &ModelHandlerConfig,
+    include_transcript: bool,
+    input_json: &str,
+) -> CommandRunResult {
+    let started_at = chrono::Utc::now().timestamp();
+    let started = Instant::now();
+
+    let result = match model_client {
+        Some(model_client) => timeout(
+            Duration::from_secs(handler.timeout_sec),
+            query_model(model_client, config, include_transcript, input_json),
+        )
+        .await
+        .unwrap_or_else(|_| Err(format!("hook timed out after {}s", handler.timeout_sec))),
+        None => Err("model hooks are not available in this session".to_string()),
+    };
+
+    let (exit_code, stdout, stderr, error) = match result {
+        Ok(output) => (Some(output.exit_code), output.stdout, output.stderr, None),
+        Err(error) => (None, String::new(), String::new(), Some(error)),
+    };
+    CommandRunResult {
+        started_at,
+        completed_at: chrono::Utc::now().timestamp(),
+        duration_ms: started.elapsed().as_millis().try_into().unwrap_or(i64::MAX),
+        exit_code,
+        stdout,
+        stderr,
+        error,
+    }
+}
+
+async fn query_model(
+    model_client: &dyn HookModelClient,
+    config: &ModelHandlerConfig,
+    include_transcript: bool,
+    input_json: &str,
+) -> Result<ModelHandlerOutput, String> {
+    let mut input = render_prompt(&config.prompt, input_json);
+    if include_transcript && let Some(transcript_path) = transcript_path(input_json) {
+        let transcript = read_transcript_tail(Path::new(&transcript_path))
+            .await
+            .map_err(|err| format!("failed to read transcript {transcript_path}: {err}"))?;
+        input.push_str("\n\nSession transcript (JSONL, most recent entries):\n");
+        input.push_str(&transcript);
+    }
+
+    let output = model_client
+        .complete(HookModelRequest {
+            model: config.model.clone(),
+            instructions: HOOK_MODEL_INSTRUCTIONS.to_string(),
+            input,
+            output_schema: config
+                .output_schema
+                .clone()
+                .unwrap_or_else(default_output_schema),
+        })
+        .await
+        .map_err(|err| format!("hook model request failed: {err}"))?;
+
+    // With a custom schema the model answers in the event's command output format.
+    if config.output_schema.is_some() {
+        return Ok(ModelHandlerOutput {
+            exit_code: 0,
+            stdout: output,
+            stderr: String::new(),
+        });
+    }
+
+    let decision: ModelDecision = serde_json::from_str(output.trim())
+        .map_err(|err| format!("hook model returned invalid output: {err}"))?;
+    Ok(match decision.decision {
+        Decision::Allow => ModelHandlerOutput {
+            exit_code: 0,
+            stdout: String::new(),
+            stderr: String::new(),
+        },
+        // Exit code 2 with a reason on stderr is how command hooks block, deny or give feedback.
+        Decision::Block => ModelHandlerOutput {
+            exit_code: 2,
+            stdout: String::new(),
+            stderr: if decision.reason.trim().is_empty() {
+                "hook model blocked without giving a reason".to_string()
+            } else {
+                decision.reason
+            },
+        },
+    })
+}
+
+fn render_prompt(prompt: &str, input_json: &str) -> String {
+    if prompt.contains(ARGUMENTS_PLACEHOLDER) {
+        prompt.replace(ARGUMENTS_PLACEHOLDER, input_json)
+    } else {
+        format!("{prompt}\n\nHook input:\n{input_json}")
+    }
+}
+
+fn transcript_path(input_json: &str) -> Option<String> {
+    let input: Value = serde_json::from_str(input_json).ok()?;
+    input
+        .get("transcript_path")
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned)
+}
+
+/// Reads the last complete lines of the transcript, up to `MAX_TRANSCRIPT_BYTES`.
+async fn read_transcript_tail(path: &Path) -> std::io::Result<String> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let len = file.metadata().await?.len();
+    let start = len.saturating_sub(MAX_TRANSCRIPT_BYTES);
+    file.seek(SeekFrom::Start(start)).await?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).await?;
+    if start > 0 {
+        // Drop the partial line at the cut.
+        let first_line_end = bytes
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .map_or(bytes.len(), |index| index + 1);
+        bytes.drain(..first_line_end);
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn default_output_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "decision": {
+                "type": "string",
+                "enum": ["allow", "block"],
+            },
+            "reason": {
+                "type": "string",
+            },
+        },
+        "required": ["decision", "reason"],
+        "additionalProperties": false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookSource;
+    use codex_utils_absolute_path::test_support::PathBufExt;
+    use codex_utils_absolute_path::test_support::test_path_buf;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::ConfiguredHandler;
+    use super::ModelHandlerConfig;
+    use super::default_output_schema;
+    use super::run_model_handler;
+    use crate::engine::HandlerKind;
+    use crate::model_client::HookModelClient;
+    use crate::model_client::HookModelRequest;
+
+    struct FakeModelClient {
+        output: String,
+        requests: Mutex<Vec<HookModelRequest>>,
+    }
+
+    impl FakeModelClient {
+        fn new(output: &str) -> Self {
+            Self {
+                output: output.to_string(),
+                requests: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn requests(&self) -> Vec<HookModelRequest> {
+            self.requests
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .clone()
+        }
+    }
+
+    #[async_trait]
+    impl HookModelClient for FakeModelClient {
+        async fn complete(&self, request: HookModelRequest) -> anyhow::Result<String> {
+            self.requests
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .push(request);
+            Ok(self.output.clone())
+        }
+    }
+
+    fn model_config(prompt: &str, output_schema: Option<serde_json::Value>) -> ModelHandlerConfig {
+        ModelHandlerConfig {
+            prompt: prompt.to_string(),
+            model: Some("gpt-5-mini".to_string()),
+            output_schema,
+        }
+    }
+
+    fn handler(config: &ModelHandlerConfig) -> ConfiguredHandler {
+        ConfiguredHandler {
+            event_name: HookEventName::Stop,
+            is_managed: false,
+            matcher: None,
+            kind: HandlerKind::Prompt(config.clone()),
+            timeout_sec: 30,
+            status_message: None,
+            source_path: test_path_buf("/tmp/hooks.json").abs(),
+            source: HookSource::User,
+            display_order: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn block_decision_is_reported_like_exit_code_two() {
+        let client = FakeModelClient::new(r#"{"decision":"block","reason":"run the tests"}"#);
+        let config = model_config(
+            "Did the agent run the tests? $ARGUMENTS",
+            /*output_schema*/ None,
+        );
+
+        let result = run_model_handler(
+            Some(&client),
+            &handler(&config),
+            &config,
+            /*include_transcript*/ false,
+            r#"{"hook_event_name":"Stop"}"#,
+        )
+        .await;
+
+        assert_eq!(result.error, None);
+        assert_eq!(result.exit_code, Some(2));
+        assert_eq!(result.stderr, "run the tests");
+        assert_eq!(
+            client.requests(),
+            vec![HookModelRequest {
+                model: Some("gpt-5-mini".to_string()),
+                instructions: super::HOOK_MODEL_INSTRUCTIONS.to_string(),
+                input: r#"Did the agent run the tests? {"hook_event_name":"Stop"}"#.to_string(),
+                output_schema: default_output_schema(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn allow_decision_succeeds_without_output() {
+        let client = FakeModelClient::new(r#"{"decision":"allow","reason":"tests ran"}"#);
+        let config = model_config("Did the agent run the tests?", /*output_schema*/ None);
+
+        let result = run_model_handler(
+            Some(&client),
+            &handler(&config),
+            &config,
+            /*include_transcript*/ false,
+            "{}",
+        )
+        .await;
+
+        assert_eq!(result.error, None);
+        assert_eq!(result.exit_code, Some(0));
+        assert_eq!(result.stdout, "");
+        assert_eq!(result.stderr, "");
+        assert_eq!(
+            client.requests()[0].input,
+            "Did the agent run the tests?\n\nHook input:\n{}"
+        );
+    }
+
+    #[tokio::test]
+    async fn custom_schema_output_is_passed_through_as_stdout() {
+        let output = r#"{"decision":"block","reason":"keep going"}"#;
+        let client = FakeModelClient::new(output);
+        let schema = json!({"type": "object"});
+        let config = model_config("Judge $ARGUMENTS", Some(schema.clone()));
+
+        let result = run_model_handler(
+            Some(&client),
+            &handler(&config),
+            &config,
+            /*include_transcript*/ false,
+            "{}",
+        )
+        .await;
+
+        assert_eq!(result.exit_code, Some(0));
+        assert_eq!(result.stdout, output);
+        assert_eq!(client.requests()[0].output_schema, schema);
+    }
+
+    #[tokio::test]
+    async fn invalid_decision_fails_the_hook() {
+        let client = FakeModelClient::new("yes");
+        let config = model_config("Judge $ARGUMENTS", /*output_schema*/ None);
+
+        let result = run_model_handler(
+            Some(&client),
+            &handler(&config),
+            &config,
+            /*include_transcript*/ false,
+            "{}",
+        )
+        .await;
+
+        assert_eq!(result.exit_code, None);
+        assert!(
+            result
+                .error
+                .as_deref()
+                .is_some_and(|error| error.starts_with("hook model returned invalid output"))
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_model_client_fails_the_hook() {
+        let config = model_config("Judge $ARGUMENTS", /*output_schema*/ None);
+
+        let result = run_model_handler(
+            /*model_client*/ None,
+            &handler(&config),
+            &config,
+            /*include_transcript*/ false,
+            "{}",
+        )
+        .await;
+
+        assert_eq!(
+            result.error.as_deref(),
+            Some("model hooks are not available in this session")
+        );
+    }
+
+    #[tokio::test]
+    async fn agent_handlers_include_the_transcript() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let transcript_path = temp.path().join("rollout.jsonl");
+        std::fs::write(&transcript_path, "{\"line\":1}\n{\"line\":2}\n").expect("write transcript");
+        let input_json = json!({ "transcript_path": transcript_path }).to_string();
+        let client = FakeModelClient::new(r#"{"decision":"allow","reason":""}"#);
+        let config = model_config("Judge $ARGUMENTS", /*output_schema*/ None);
+
+        let result = run_model_handler(
+            Some(&client),
+            &handler(&config),
+            &config,
+            /*include_transcript*/ true,
+            &input_json,
+        )
+        .await;
+
+        assert_eq!(result.exit_code, Some(0));
+        assert_eq!(
+            client.requests()[0].input,
+            format!(
+                "Judge {input_json}\n\nSession transcript (JSONL, most recent entries):\n{{\"line\":1}}\n{{\"line\":2}}\n"
+            )
+        );
+    }
+}
@@ -16,6 +16,7 @@ pub(crate) struct SessionStartOutput {
 pub(crate) struct PreToolUseOutput {
     pub universal: UniversalOutput,
     pub block_reason: Option<String>,
+    pub updated_input: Option<Value>,
     pub invalid_reason: Option<String>,
 }
 
@@ -39,6 +40,7 @@ pub(crate) struct PostToolUseOutput {
     pub reason: Option<String>,
     pub invalid_block_reason: Option<String>,
     pub additional_context: Option<String>,
+    pub updated_output: Option<Value>,
     pub invalid_reason: Option<String>,
 }
 
@@ -59,17 +61,42 @@ pub(crate) struct StopOutput {
     pub invalid_block_reason: Option<String>,
 }
 
+/// Output of hooks that observe an event without steering it.
+#[derive(Debug, Clone)]
+pub(crate) struct ObserverOutput {
+    pub universal: UniversalOutput,
+    pub invalid_reason: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PreApplyPatchOutput {
+    pub universal: UniversalOutput,
+    pub should_block: bool,
+    pub reason: Option<String>,
+    pub invalid_block_reason: Option<String>,
+    pub invalid_reason: Option<String>,
+}
+
+use serde_json::Value;
+
 use crate::schema::BlockDecisionWire;
 use crate::schema::HookUniversalOutputWire;
 use crate::schema::PermissionRequestBehaviorWire;
 use crate::schema::PermissionRequestCommandOutputWire;
 use crate::schema::PermissionRequestDecisionWire;
+use crate::schema::PostApplyPatchCommandOutputWire;
+use crate::schema::PostCompactCommandOutputWire;
 use crate::schema::PostToolUseCommandOutputWire;
+use crate::schema::PreApplyPatchCommandOutputWire;
+use crate::schema::PreCompactCommandOutputWire;
 use crate::schema::PreToolUseCommandOutputWire;
 use crate::schema::PreToolUseDecisionWire;
 use crate::schema::PreToolUsePermissionDecisionWire;
 use crate::schema::SessionStartCommandOutputWire;
 use crate::schema::StopCommandOutputWire;
+use crate::schema::SubagentStartCommandOutputWire;
+use crate::schema::SubagentStopCommandOutputWire;
+use crate::schema::TurnErrorCommandOutputWire;
 use crate::schema::UserPromptSubmitCommandOutputWire;
 
 pub(crate) fn parse_session_start(stdout: &str) -> Option<SessionStartOutput> {
@@ -123,10 +150,16 @@ pub(crate) fn parse_pre_tool_use(stdout: &str) -> Option<PreToolUseOutput> {
     } else {
         None
     };
+    let updated_input = if invalid_reason.is_none() && block_reason.is_none() {
+        hook_specific_output.and_then(|output| output.updated_input.clone())
+    } else {
+        None
+    };
 
     Some(PreToolUseOutput {
         universal,
         block_reason,
+        updated_input,
         invalid_reason,
     })
 }
@@ -174,9 +207,15 @@ pub(crate) fn parse_post_tool_use(stdout: &str) -> Option<PostToolUseOutput> {
     } else {
         None
     };
-    let additional_context = wire
-        .hook_specific_output
-        .and_then(|output| output.additional_context);
+    let (additional_context, updated_output) = match wire.hook_specific_output {
+        Some(output) => (output.additional_context, output.updated_output),
+        None => (None, None),
+    };
+    let updated_output = if invalid_reason.is_none() {
+        updated_output
+    } else {
+        None
+    };
 
     Some(PostToolUseOutput {
         universal,
@@ -184,6 +223,7 @@ pub(crate) fn parse_post_tool_use(stdout: &str) -> Option<PostToolUseOutput> {
         reason: wire.reason,
         invalid_block_reason,
         additional_context,
+        updated_output,
         invalid_reason,
     })
 }
@@ -232,6 +272,68 @@ pub(crate) fn parse_stop(stdout: &str) -> Option<StopOutput> {
     })
 }
 
+pub(crate) fn parse_pre_compact(stdout: &str) -> Option<ObserverOutput> {
+    let wire: PreCompactCommandOutputWire = parse_json(stdout)?;
+    Some(observer_output(wire.universal, "PreCompact"))
+}
+
+pub(crate) fn parse_post_compact(stdout: &str) -> Option<ObserverOutput> {
+    let wire: PostCompactCommandOutputWire = parse_json(stdout)?;
+    Some(observer_output(wire.universal, "PostCompact"))
+}
+
+pub(crate) fn parse_subagent_start(stdout: &str) -> Option<ObserverOutput> {
+    let wire: SubagentStartCommandOutputWire = parse_json(stdout)?;
+    Some(observer_output(wire.universal, "SubagentStart"))
+}
+
+pub(crate) fn parse_subagent_stop(stdout: &str) -> Option<ObserverOutput> {
+    let wire: SubagentStopCommandOutputWire = parse_json(stdout)?;
+    Some(observer_output(wire.universal, "SubagentStop"))
+}
+
+pub(crate) fn parse_post_apply_patch(stdout: &str) -> Option<ObserverOutput> {
+    let wire: PostApplyPatchCommandOutputWire = parse_json(stdout)?;
+    Some(observer_output(wire.universal, "PostApplyPatch"))
+}
+
+pub(crate) fn parse_turn_error(stdout: &str) -> Option<ObserverOutput> {
+    let wire: TurnErrorCommandOutputWire = parse_json(stdout)?;
+    Some(observer_output(wire.universal, "TurnError"))
+}
+
+pub(crate) fn parse_pre_apply_patch(stdout: &str) -> Option<PreApplyPatchOutput> {
+    let wire: PreApplyPatchCommandOutputWire = parse_json(stdout)?;
+    let universal = UniversalOutput::from(wire.universal);
+    let invalid_reason = unsupported_observer_universal(&universal, "PreApplyPatch");
+    let should_block = matches!(wire.decision, Some(BlockDecisionWire::Block));
+    let invalid_block_reason = if should_block
+        && match wire.reason.as_deref() {
+            Some(reason) => reason.trim().is_empty(),
+            None => true,
+        } {
+        Some(invalid_block_message("PreApplyPatch"))
+    } else {
+        None
+    };
+    Some(PreApplyPatchOutput {
+        universal,
+        should_block: should_block && invalid_reason.is_none() && invalid_block_reason.is_none(),
+        reason: wire.reason,
+        invalid_block_reason,
+        invalid_reason,
+    })
+}
+
+fn observer_output(universal: HookUniversalOutputWire, event_name: &str) -> ObserverOutput {
+    let universal = UniversalOutput::from(universal);
+    let invalid_reason = unsupported_observer_universal(&universal, event_name);
+    ObserverOutput {
+        universal,
+        invalid_reason,
+    }
+}
+
 impl From<HookUniversalOutputWire> for UniversalOutput {
     fn from(value: HookUniversalOutputWire) -> Self {
         Self {
@@ -262,6 +364,22 @@ fn invalid_block_message(event_name: &str) -> String {
     format!("{event_name} hook returned decision:block without a non-empty reason")
 }
 
+fn unsupported_observer_universal(universal: &UniversalOutput, event_name: &str) -> Option<String> {
+    if !universal.continue_processing {
+        Some(format!(
+            "{event_name} hook returned unsupported continue:false"
+        ))
+    } else if universal.stop_reason.is_some() {
+        Some(format!("{event_name} hook returned unsupported stopReason"))
+    } else if universal.suppress_output {
+        Some(format!(
+            "{event_name} hook returned unsupported suppressOutput"
+        ))
+    } else {
+        None
+    }
+}
+
 fn unsupported_pre_tool_use_universal(universal: &UniversalOutput) -> Option<String> {
     if !universal.continue_processing {
         Some("PreToolUse hook returned unsupported continue:false".to_string())
@@ -337,8 +455,12 @@ fn unsupported_post_tool_use_hook_specific_output(
 fn unsupported_pre_tool_use_hook_specific_output(
     output: &crate::schema::PreToolUseHookSpecificOutputWire,
 ) -> Option<String> {
-    if output.updated_input.is_some() {
-        Some("PreToolUse hook returned unsupported updatedInput".to_string())
+    if output
+        .updated_input
+        .as_ref()
+        .is_some_and(|updated_input| !updated_input.is_object())
+    {
+        Some("PreToolUse hook returned updatedInput that is not a JSON object".to_string())
     } else if output
         .additional_context
         .as_deref()
@@ -422,6 +544,8 @@ mod tests {
     use serde_json::json;
 
     use super::parse_permission_request;
+    use super::parse_post_tool_use;
+    use super::parse_pre_tool_use;
 
     #[test]
     fn permission_request_rejects_reserved_updated_input_field() {
@@ -491,4 +615,80 @@ mod tests {
             Some("PermissionRequest hook returned unsupported interrupt:true".to_string())
         );
     }
+
+    #[test]
+    fn pre_tool_use_returns_updated_input_object() {
+        let parsed = parse_pre_tool_use(
+            &json!({
+                "hookSpecificOutput": {
+                    "hookEventName": "PreToolUse",
+                    "updatedInput": {"command": "cargo publish --dry-run"}
+                }
+            })
+            .to_string(),
+        )
+        .expect("pre tool use hook output should parse");
+
+        assert_eq!(parsed.invalid_reason, None);
+        assert_eq!(
+            parsed.updated_input,
+            Some(json!({"command": "cargo publish --dry-run"}))
+        );
+    }
+
+    #[test]
+    fn pre_tool_use_rejects_non_object_updated_input() {
+        let parsed = parse_pre_tool_use(
+            &json!({
+                "hookSpecificOutput": {
+                    "hookEventName": "PreToolUse",
+                    "updatedInput": "rm -rf /"
+                }
+            })
+            .to_string(),
+        )
+        .expect("pre tool use hook output should parse");
+
+        assert_eq!(
+            parsed.invalid_reason,
+            Some("PreToolUse hook returned updatedInput that is not a JSON object".to_string())
+        );
+        assert_eq!(parsed.updated_input, None);
+    }
+
+    #[test]
+    fn pre_tool_use_deny_wins_over_updated_input() {
+        let parsed = parse_pre_tool_use(
+            &json!({
+                "hookSpecificOutput": {
+                    "hookEventName": "PreToolUse",
+                    "permissionDecision": "deny",
+                    "permissionDecisionReason": "no publishing",
+                    "updatedInput": {"command": "cargo publish --dry-run"}
+                }
+            })
+            .to_string(),
+        )
+        .expect("pre tool use hook output should parse");
+
+        assert_eq!(parsed.block_reason, Some("no publishing".to_string()));
+        assert_eq!(parsed.updated_input, None);
+    }
+
+    #[test]
+    fn post_tool_use_returns_updated_output() {
+        let parsed = parse_post_tool_use(
+            &json!({
+                "hookSpecificOutput": {
+                    "hookEventName": "PostToolUse",
+                    "updatedOutput": "token=[REDACTED]"
+                }
+            })
+            .to_string(),
+        )
+        .expect("post tool use hook output should parse");
+
+        assert_eq!(parsed.invalid_reason, None);
+        assert_eq!(parsed.updated_output, Some(json!("token=[REDACTED]")));
+    }
 }
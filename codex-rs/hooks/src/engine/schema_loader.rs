@@ -16,6 +16,20 @@ pub(crate) struct GeneratedHookSchemas {
     pub user_prompt_submit_command_output: Value,
     pub stop_command_input: Value,
     pub stop_command_output: Value,
+    pub pre_compact_command_input: Value,
+    pub pre_compact_command_output: Value,
+    pub post_compact_command_input: Value,
+    pub post_compact_command_output: Value,
+    pub subagent_start_command_input: Value,
+    pub subagent_start_command_output: Value,
+    pub subagent_stop_command_input: Value,
+    pub subagent_stop_command_output: Value,
+    pub pre_apply_patch_command_input: Value,
+    pub pre_apply_patch_command_output: Value,
+    pub post_apply_patch_command_input: Value,
+    pub post_apply_patch_command_output: Value,
+    pub turn_error_command_input: Value,
+    pub turn_error_command_output: Value,
 }
 
 pub(crate) fn generated_hook_schemas() -> &'static GeneratedHookSchemas {
@@ -69,6 +83,62 @@ pub(crate) fn generated_hook_schemas() -> &'static GeneratedHookSchemas {
             "stop.command.output",
             include_str!("../../schema/generated/stop.command.output.schema.json"),
         ),
+        pre_compact_command_input: parse_json_schema(
+            "pre-compact.command.input",
+            include_str!("../../schema/generated/pre-compact.command.input.schema.json"),
+        ),
+        pre_compact_command_output: parse_json_schema(
+            "pre-compact.command.output",
+            include_str!("../../schema/generated/pre-compact.command.output.schema.json"),
+        ),
+        post_compact_command_input: parse_json_schema(
+            "post-compact.command.input",
+            include_str!("../../schema/generated/post-compact.command.input.schema.json"),
+        ),
+        post_compact_command_output: parse_json_schema(
+            "post-compact.command.output",
+            include_str!("../../schema/generated/post-compact.command.output.schema.json"),
+        ),
+        subagent_start_command_input: parse_json_schema(
+            "subagent-start.command.input",
+            include_str!("../../schema/generated/subagent-start.command.input.schema.json"),
+        ),
+        subagent_start_command_output: parse_json_schema(
+            "subagent-start.command.output",
+            include_str!("../../schema/generated/subagent-start.command.output.schema.json"),
+        ),
+        subagent_stop_command_input: parse_json_schema(
+            "subagent-stop.command.input",
+            include_str!("../../schema/generated/subagent-stop.command.input.schema.json"),
+        ),
+        subagent_stop_command_output: parse_json_schema(
+            "subagent-stop.command.output",
+            include_str!("../../schema/generated/subagent-stop.command.output.schema.json"),
+        ),
+        pre_apply_patch_command_input: parse_json_schema(
+            "pre-apply-patch.command.input",
+            include_str!("../../schema/generated/pre-apply-patch.command.input.schema.json"),
+        ),
+        pre_apply_patch_command_output: parse_json_schema(
+            "pre-apply-patch.command.output",
+            include_str!("../../schema/generated/pre-apply-patch.command.output.schema.json"),
+        ),
+        post_apply_patch_command_input: parse_json_schema(
+            "post-apply-patch.command.input",
+            include_str!("../../schema/generated/post-apply-patch.command.input.schema.json"),
+        ),
+        post_apply_patch_command_output: parse_json_schema(
+            "post-apply-patch.command.output",
+            include_str!("../../schema/generated/post-apply-patch.command.output.schema.json"),
+        ),
+        turn_error_command_input: parse_json_schema(
+            "turn-error.command.input",
+            include_str!("../../schema/generated/turn-error.command.input.schema.json"),
+        ),
+        turn_error_command_output: parse_json_schema(
+            "turn-error.command.output",
+            include_str!("../../schema/generated/turn-error.command.output.schema.json"),
+        ),
     })
 }
 
@@ -98,5 +168,19 @@ mod tests {
         assert_eq!(schemas.user_prompt_submit_command_output["type"], "object");
         assert_eq!(schemas.stop_command_input["type"], "object");
         assert_eq!(schemas.stop_command_output["type"], "object");
+        assert_eq!(schemas.pre_compact_command_input["type"], "object");
+        assert_eq!(schemas.pre_compact_command_output["type"], "object");
+        assert_eq!(schemas.post_compact_command_input["type"], "object");
+        assert_eq!(schemas.post_compact_command_output["type"], "object");
+        assert_eq!(schemas.subagent_start_command_input["type"], "object");
+        assert_eq!(schemas.subagent_start_command_output["type"], "object");
+        assert_eq!(schemas.subagent_stop_command_input["type"], "object");
+        assert_eq!(schemas.subagent_stop_command_output["type"], "object");
+        assert_eq!(schemas.pre_apply_patch_command_input["type"], "object");
+        assert_eq!(schemas.pre_apply_patch_command_output["type"], "object");
+        assert_eq!(schemas.post_apply_patch_command_input["type"], "object");
+        assert_eq!(schemas.post_apply_patch_command_output["type"], "object");
+        assert_eq!(schemas.turn_error_command_input["type"], "object");
+        assert_eq!(schemas.turn_error_command_output["type"], "object");
     }
 }
@@ -0,0 +1,711 @@
+//! Runs `server` hook handlers against long-running hook servers.
+//!
+//! Codex keeps one connection per socket and writes one JSON object per line, such as
+//! `{"id":1,"input":{...}}`, where `input` is the payload a command hook receives on stdin. The
+//! server answers each request with one line carrying the same id, such as
+//! `{"id":1,"exitCode":0,"stdout":"...","stderr":"..."}`. Answers may arrive in any order, so
+//! several hooks can be in flight on one connection. A JSON `output` value can be sent in place of
+//! `stdout`. Answers are interpreted exactly like the result of a command hook.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::sync::MutexGuard as StdMutexGuard;
+use std::sync::PoisonError;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_config::ServerHookFallback;
+use codex_uds::UnixStream;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::io::ReadHalf;
+use tokio::io::WriteHalf;
+use tokio::process::Child;
+use tokio::sync::Mutex;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tokio::time::timeout;
+
+use super::CommandShell;
+use super::ConfiguredHandler;
+use super::ServerHandlerConfig;
+use super::command_runner;
+use super::command_runner::CommandRunResult;
+
+const INITIAL_RECONNECT_BACKOFF: Duration = Duration::from_millis(250);
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(30);
+/// How long a server started by Codex gets to start listening on its socket.
+const SERVER_STARTUP_TIMEOUT: Duration = Duration::from_secs(5);
+const SERVER_STARTUP_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+type PendingResponses = HashMap<u64, oneshot::Sender<ServerResponse>>;
+
+#[derive(Serialize)]
+struct ServerRequest {
+    id: u64,
+    input: Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerResponse {
+    id: u64,
+    #[serde(default)]
+    exit_code: i32,
+    #[serde(default)]
+    stdout: String,
+    #[serde(default)]
+    stderr: String,
+    #[serde(default)]
+    output: Option<Value>,
+}
+
+enum RequestError {
+    /// The server could not be reached or went away before answering.
+    Unavailable(String),
+    TimedOut,
+    InvalidInput(String),
+}
+
+/// Connections to the hook servers used by `server` handlers, shared by every hook run of a
+/// session. Server processes started by Codex are killed when the pool is dropped.
+#[derive(Default)]
+pub(crate) struct HookServerPool {
+    servers: StdMutex<HashMap<AbsolutePathBuf, Arc<HookServer>>>,
+}
+
+impl HookServerPool {
+    fn server(&self, socket: &AbsolutePathBuf) -> Arc<HookServer> {
+        let mut servers = lock(&self.servers);
+        Arc::clone(
+            servers
+                .entry(socket.clone())
+                .or_insert_with(|| Arc::new(HookServer::new(socket.clone()))),
+        )
+    }
+}
+
+pub(crate) async fn run_server_handler(
+    pool: &HookServerPool,
+    shell: &CommandShell,
+    handler: &ConfiguredHandler,
+    config: &ServerHandlerConfig,
+    input_json: &str,
+    cwd: &Path,
+) -> CommandRunResult {
+    let started_at = chrono::Utc::now().timestamp();
+    let started = Instant::now();
+
+    let server = pool.server(&config.socket);
+    let result = server
+        .request(
+            shell,
+            config.command.as_deref(),
+            input_json,
+            cwd,
+            Duration::from_secs(handler.timeout_sec),
+        )
+        .await;
+    let completed = |exit_code, stdout, stderr, error| CommandRunResult {
+        started_at,
+        completed_at: chrono::Utc::now().timestamp(),
+        duration_ms: started.elapsed().as_millis().try_into().unwrap_or(i64::MAX),
+        exit_code,
+        stdout,
+        stderr,
+        error,
+    };
+    match result {
+        Ok(response) => {
+            let stdout = match response.output {
+                Some(output) => output.to_string(),
+                None => response.stdout,
+            };
+            completed(Some(response.exit_code), stdout, response.stderr, None)
+        }
+        Err(RequestError::TimedOut) => completed(
+            None,
+            String::new(),
+            String::new(),
+            Some(format!("hook timed out after {}s", handler.timeout_sec)),
+        ),
+        Err(RequestError::InvalidInput(error)) => {
+            completed(None, String::new(), String::new(), Some(error))
+        }
+        Err(RequestError::Unavailable(reason)) => {
+            if let Some(command) = config.fallback_command.as_deref() {
+                return command_runner::run_command(shell, handler, command, input_json, cwd).await;
+            }
+            match config.fallback {
+                ServerHookFallback::Fail => {
+                    completed(None, String::new(), String::new(), Some(reason))
+                }
+                ServerHookFallback::Allow => completed(Some(0), String::new(), String::new(), None),
+                ServerHookFallback::Block => completed(Some(2), String::new(), reason, None),
+            }
+        }
+    }
+}
+
+struct HookServer {
+    socket: AbsolutePathBuf,
+    next_request_id: AtomicU64,
+    state: Mutex<ServerState>,
+}
+
+#[derive(Default)]
+struct ServerState {
+    connection: Option<Arc<Connection>>,
+    failed_attempts: u32,
+    next_attempt_at: Option<Instant>,
+    process: Option<Child>,
+}
+
+impl HookServer {
+    fn new(socket: AbsolutePathBuf) -> Self {
+        Self {
+            socket,
+            next_request_id: AtomicU64::new(1),
+            state: Mutex::new(ServerState::default()),
+        }
+    }
+
+    async fn request(
+        &self,
+        shell: &CommandShell,
+        launch_command: Option<&str>,
+        input_json: &str,
+        cwd: &Path,
+        handler_timeout: Duration,
+    ) -> Result<ServerResponse, RequestError> {
+        // The handler timeout covers connecting and writing the request as well as waiting for
+        // the answer, so a wedged server cannot stall the hook run.
+        let deadline = Instant::now() + handler_timeout;
+        let input = serde_json::from_str(input_json).map_err(|err| {
+            RequestError::InvalidInput(format!("failed to parse hook input: {err}"))
+        })?;
+        let id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let line = serde_json::to_string(&ServerRequest { id, input }).map_err(|err| {
+            RequestError::InvalidInput(format!("failed to serialize hook server request: {err}"))
+        })?;
+
+        let connection = match timeout(
+            remaining(deadline),
+            self.connection(shell, launch_command, cwd),
+        )
+        .await
+        {
+            Ok(connection) => connection.map_err(RequestError::Unavailable)?,
+            Err(_) => {
+                return Err(RequestError::Unavailable(format!(
+                    "timed out connecting to hook server at {}",
+                    self.socket.display()
+                )));
+            }
+        };
+        let response = connection.send(id, line, deadline).await.map_err(|err| {
+            RequestError::Unavailable(format!(
+                "failed to write to hook server at {}: {err}",
+                self.socket.display()
+            ))
+        })?;
+        match timeout(remaining(deadline), response).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(RequestError::Unavailable(format!(
+                "hook server at {} closed the connection before answering",
+                self.socket.display()
+            ))),
+            Err(_) => {
+                connection.cancel(id);
+                Err(RequestError::TimedOut)
+            }
+        }
+    }
+
+    /// Returns the open connection, reconnecting (and starting the server when a launch command is
+    /// configured) unless the last attempt failed too recently.
+    async fn connection(
+        &self,
+        shell: &CommandShell,
+        launch_command: Option<&str>,
+        cwd: &Path,
+    ) -> Result<Arc<Connection>, String> {
+        let mut state = self.state.lock().await;
+        if let Some(connection) = state.connection.as_ref()
+            && !connection.is_closed()
+        {
+            return Ok(Arc::clone(connection));
+        }
+        state.connection = None;
+
+        if let Some(next_attempt_at) = state.next_attempt_at {
+            let now = Instant::now();
+            if now < next_attempt_at {
+                return Err(format!(
+                    "hook server at {} is unavailable; retrying in {}ms",
+                    self.socket.display(),
+                    (next_attempt_at - now).as_millis()
+                ));
+            }
+        }
+
+        match self.connect(&mut state, shell, launch_command, cwd).await {
+            Ok(stream) => {
+                let connection = Arc::new(Connection::new(stream));
+                state.connection = Some(Arc::clone(&connection));
+                state.failed_attempts = 0;
+                state.next_attempt_at = None;
+                Ok(connection)
+            }
+            Err(err) => {
+                state.failed_attempts = state.failed_attempts.saturating_add(1);
+                state.next_attempt_at =
+                    Some(Instant::now() + reconnect_backoff(state.failed_attempts));
+                Err(format!(
+                    "failed to connect to hook server at {}: {err}",
+                    self.socket.display()
+                ))
+            }
+        }
+    }
+
+    async fn connect(
+        &self,
+        state: &mut ServerState,
+        shell: &CommandShell,
+        launch_command: Option<&str>,
+        cwd: &Path,
+    ) -> io::Result<UnixStream> {
+        let err = match UnixStream::connect(self.socket.as_path()).await {
+            Ok(stream) => return Ok(stream),
+            Err(err) => err,
+        };
+        let Some(launch_command) = launch_command else {
+            return Err(err);
+        };
+
+        let running = state
+            .process
+            .as_mut()
+            .is_some_and(|process| matches!(process.try_wait(), Ok(None)));
+        if !running {
+            // A server that exited without cleaning up leaves a socket nobody listens on, which
+            // would make the new server fail to bind.
+            if codex_uds::is_stale_socket_path(self.socket.as_path())
+                .await
+                .unwrap_or(false)
+            {
+                let _ = tokio::fs::remove_file(self.socket.as_path()).await;
+            }
+            let mut command = command_runner::build_command(shell, launch_command);
+            command
+                .current_dir(cwd)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .kill_on_drop(true);
+            state.process = Some(command.spawn()?);
+        }
+
+        let deadline = Instant::now() + SERVER_STARTUP_TIMEOUT;
+        loop {
+            sleep(SERVER_STARTUP_POLL_INTERVAL).await;
+            match UnixStream::connect(self.socket.as_path()).await {
+                Ok(stream) => return Ok(stream),
+                Err(err) if Instant::now() >= deadline => return Err(err),
+                Err(_) => {}
+            }
+        }
+    }
+}
+
+fn remaining(deadline: Instant) -> Duration {
+    deadline.saturating_duration_since(Instant::now())
+}
+
+fn reconnect_backoff(failed_attempts: u32) -> Duration {
+    let exponent = failed_attempts.saturating_sub(1).min(16);
+    INITIAL_RECONNECT_BACKOFF
+        .saturating_mul(1 << exponent)
+        .min(MAX_RECONNECT_BACKOFF)
+}
+
+struct Connection {
+    writer: Mutex<WriteHalf<UnixStream>>,
+    pending: Arc<StdMutex<PendingResponses>>,
+    closed: Arc<AtomicBool>,
+    reader: JoinHandle<()>,
+}
+
+impl Connection {
+    fn new(stream: UnixStream) -> Self {
+        let (reader, writer) = tokio::io::split(stream);
+        let pending = Arc::new(StdMutex::new(PendingResponses::new()));
+        let closed = Arc::new(AtomicBool::new(false));
+        let reader = tokio::spawn(read_responses(
+            reader,
+            Arc::clone(&pending),
+            Arc::clone(&closed),
+        ));
+        Self {
+            writer: Mutex::new(writer),
+            pending,
+            closed,
+            reader,
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    /// Writes one request line, giving up once `deadline` passes. A write that times out leaves
+    /// a partial line on the socket, so the connection is marked closed and replaced on next use.
+    async fn send(
+        &self,
+        id: u64,
+        line: String,
+        deadline: Instant,
+    ) -> io::Result<oneshot::Receiver<ServerResponse>> {
+        let (sender, receiver) = oneshot::channel();
+        lock(&self.pending).insert(id, sender);
+        // The reader marks the connection closed before it drops the pending senders, so a request
+        // registered after that point has to notice the closed flag here.
+        if self.is_closed() {
+            self.cancel(id);
+            return Err(io::Error::from(io::ErrorKind::BrokenPipe));
+        }
+
+        let write = async {
+            let mut writer = self.writer.lock().await;
+            writer.write_all(line.as_bytes()).await?;
+            writer.write_all(b"\n").await?;
+            writer.flush().await
+        };
+        let written = match timeout(remaining(deadline), write).await {
+            Ok(written) => written,
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "timed out writing the request",
+            )),
+        };
+        if let Err(err) = written {
+            self.closed.store(true, Ordering::Release);
+            self.cancel(id);
+            return Err(err);
+        }
+        Ok(receiver)
+    }
+
+    fn cancel(&self, id: u64) {
+        lock(&self.pending).remove(&id);
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+async fn read_responses(
+    reader: ReadHalf<UnixStream>,
+    pending: Arc<StdMutex<PendingResponses>>,
+    closed: Arc<AtomicBool>,
+) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        // Lines that are not a response to a pending request are ignored; the request they were
+        // meant for fails once its timeout expires.
+        let Ok(response) = serde_json::from_str::<ServerResponse>(&line) else {
+            continue;
+        };
+        if let Some(sender) = lock(&pending).remove(&response.id) {
+            let _ = sender.send(response);
+        }
+    }
+    closed.store(true, Ordering::Release);
+    // Dropping the senders tells every in-flight request that the server went away.
+    lock(&pending).clear();
+}
+
+fn lock<T>(mutex: &StdMutex<T>) -> StdMutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::time::Duration;
+
+    use codex_config::ServerHookFallback;
+    use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookSource;
+    use codex_uds::UnixListener;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+    use serde_json::json;
+    use tokio::io::AsyncBufReadExt;
+    use tokio::io::AsyncWriteExt;
+    use tokio::io::BufReader;
+
+    use super::HookServerPool;
+    use super::INITIAL_RECONNECT_BACKOFF;
+    use super::reconnect_backoff;
+    use super::run_server_handler;
+    use crate::engine::CommandShell;
+    use crate::engine::ConfiguredHandler;
+    use crate::engine::HandlerKind;
+    use crate::engine::ServerHandlerConfig;
+
+    fn server_config(
+        socket: &AbsolutePathBuf,
+        fallback: ServerHookFallback,
+    ) -> ServerHandlerConfig {
+        ServerHandlerConfig {
+            socket: socket.clone(),
+            command: None,
+            fallback,
+            fallback_command: None,
+        }
+    }
+
+    fn handler(config: &ServerHandlerConfig) -> ConfiguredHandler {
+        handler_with_timeout(config, /*timeout_sec*/ 5)
+    }
+
+    fn handler_with_timeout(config: &ServerHandlerConfig, timeout_sec: u64) -> ConfiguredHandler {
+        ConfiguredHandler {
+            event_name: HookEventName::PreToolUse,
+            is_managed: false,
+            matcher: None,
+            kind: HandlerKind::Server(config.clone()),
+            timeout_sec,
+            status_message: None,
+            source_path: config.socket.clone(),
+            source: HookSource::User,
+            display_order: 0,
+        }
+    }
+
+    fn shell() -> CommandShell {
+        CommandShell {
+            program: String::new(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Accepts one connection and collects `count` requests before answering them in reverse
+    /// order, echoing each request's `input.tool` back as stdout.
+    async fn answer_in_reverse(mut listener: UnixListener, count: usize) {
+        let stream = listener.accept().await.expect("accept hook connection");
+        let (reader, mut writer) = tokio::io::split(stream);
+        let mut lines = BufReader::new(reader).lines();
+        let mut requests = Vec::new();
+        while requests.len() < count {
+            let line = lines
+                .next_line()
+                .await
+                .expect("read request")
+                .expect("request line");
+            requests.push(serde_json::from_str::<Value>(&line).expect("request JSON"));
+        }
+        for request in requests.into_iter().rev() {
+            let response = json!({
+                "id": request["id"],
+                "stdout": request["input"]["tool"],
+            });
+            writer
+                .write_all(format!("{response}\n").as_bytes())
+                .await
+                .expect("write response");
+        }
+        writer.flush().await.expect("flush responses");
+        // Keep the connection open until the test is done with it.
+        let _ = lines.next_line().await;
+    }
+
+    fn socket_path(dir: &Path) -> AbsolutePathBuf {
+        AbsolutePathBuf::from_absolute_path(dir.join("hooks.sock")).expect("absolute socket path")
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_are_matched_by_id() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let socket = socket_path(dir.path());
+        let listener = UnixListener::bind(socket.as_path()).await.expect("bind");
+        let server = tokio::spawn(answer_in_reverse(listener, 2));
+
+        let pool = HookServerPool::default();
+        let config = server_config(&socket, ServerHookFallback::Fail);
+        let handler = handler(&config);
+        let (first, second) = tokio::join!(
+            run_server_handler(
+                &pool,
+                &shell(),
+                &handler,
+                &config,
+                r#"{"tool":"first"}"#,
+                dir.path(),
+            ),
+            run_server_handler(
+                &pool,
+                &shell(),
+                &handler,
+                &config,
+                r#"{"tool":"second"}"#,
+                dir.path(),
+            ),
+        );
+
+        assert_eq!(
+            (first.exit_code, first.stdout.as_str(), first.error),
+            (Some(0), "first", None)
+        );
+        assert_eq!(
+            (second.exit_code, second.stdout.as_str(), second.error),
+            (Some(0), "second", None)
+        );
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn unreachable_server_uses_configured_fallback() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let socket = socket_path(dir.path());
+        let pool = HookServerPool::default();
+
+        let config = server_config(&socket, ServerHookFallback::Block);
+        let blocked = run_server_handler(
+            &pool,
+            &shell(),
+            &handler(&config),
+            &config,
+            "{}",
+            dir.path(),
+        )
+        .await;
+        assert_eq!(blocked.exit_code, Some(2));
+        assert!(
+            blocked.stderr.contains("failed to connect to hook server"),
+            "unexpected stderr: {}",
+            blocked.stderr
+        );
+
+        // The second run falls inside the reconnect backoff and does not try to connect again.
+        let config = server_config(&socket, ServerHookFallback::Fail);
+        let failed = run_server_handler(
+            &pool,
+            &shell(),
+            &handler(&config),
+            &config,
+            "{}",
+            dir.path(),
+        )
+        .await;
+        assert_eq!(failed.exit_code, None);
+        let error = failed.error.expect("fallback error");
+        assert!(error.contains("retrying in"), "unexpected error: {error}");
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_the_server_comes_back() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let socket = socket_path(dir.path());
+        let pool = HookServerPool::default();
+        let config = server_config(&socket, ServerHookFallback::Allow);
+        let handler = handler(&config);
+
+        let unavailable = run_server_handler(
+            &pool,
+            &shell(),
+            &handler,
+            &config,
+            r#"{"tool":"early"}"#,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(
+            (unavailable.exit_code, unavailable.stdout.as_str()),
+            (Some(0), "")
+        );
+
+        let listener = UnixListener::bind(socket.as_path()).await.expect("bind");
+        let server = tokio::spawn(answer_in_reverse(listener, 1));
+        tokio::time::sleep(INITIAL_RECONNECT_BACKOFF + Duration::from_millis(50)).await;
+
+        let answered = run_server_handler(
+            &pool,
+            &shell(),
+            &handler,
+            &config,
+            r#"{"tool":"late"}"#,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(
+            (answered.exit_code, answered.stdout.as_str(), answered.error),
+            (Some(0), "late", None)
+        );
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn stalled_write_uses_configured_fallback() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let socket = socket_path(dir.path());
+        let mut listener = UnixListener::bind(socket.as_path()).await.expect("bind");
+        // Accept the connection but never read from it, so a large request fills the socket
+        // buffer and the write stalls.
+        let server = tokio::spawn(async move {
+            let _stream = listener.accept().await.expect("accept hook connection");
+            std::future::pending::<()>().await;
+        });
+
+        let pool = HookServerPool::default();
+        let config = server_config(&socket, ServerHookFallback::Block);
+        let input = json!({ "tool": "x".repeat(16 * 1024 * 1024) }).to_string();
+        let blocked = tokio::time::timeout(
+            Duration::from_secs(10),
+            run_server_handler(
+                &pool,
+                &shell(),
+                &handler_with_timeout(&config, /*timeout_sec*/ 1),
+                &config,
+                &input,
+                dir.path(),
+            ),
+        )
+        .await
+        .expect("hook run should finish within its timeout");
+
+        assert_eq!(blocked.exit_code, Some(2));
+        assert!(
+            blocked.stderr.contains("failed to write to hook server"),
+            "unexpected stderr: {}",
+            blocked.stderr
+        );
+        server.abort();
+    }
+
+    #[test]
+    fn reconnect_backoff_doubles_up_to_the_cap() {
+        assert_eq!(reconnect_backoff(1), Duration::from_millis(250));
+        assert_eq!(reconnect_backoff(3), Duration::from_secs(1));
+        assert_eq!(reconnect_backoff(40), Duration::from_secs(30));
+    }
+}
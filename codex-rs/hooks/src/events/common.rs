@@ -6,7 +6,9 @@ use codex_protocol::protocol::HookRunStatus;
 use codex_protocol::protocol::HookRunSummary;
 
 use crate::engine::ConfiguredHandler;
+use crate::engine::command_runner::CommandRunResult;
 use crate::engine::dispatcher;
+use crate::engine::output_parser::ObserverOutput;
 
 pub(crate) fn join_text_chunks(chunks: Vec<String>) -> Option<String> {
     if chunks.is_empty() {
@@ -70,6 +72,77 @@ pub(crate) fn serialization_failure_hook_events(
         .collect()
 }
 
+/// Interprets the result of a hook that observes an event without steering it.
+///
+/// Only `systemMessage` is surfaced. Plain-text stdout is ignored so that
+/// formatters and audit loggers can print progress without failing the hook.
+pub(crate) fn parse_observer_completed(
+    handler: &ConfiguredHandler,
+    run_result: CommandRunResult,
+    turn_id: Option<String>,
+    parse_output: fn(&str) -> Option<ObserverOutput>,
+    invalid_output_message: &str,
+) -> HookCompletedEvent {
+    let mut entries = Vec::new();
+    let mut status = HookRunStatus::Completed;
+
+    match run_result.error.as_deref() {
+        Some(error) => {
+            status = HookRunStatus::Failed;
+            entries.push(HookOutputEntry {
+                kind: HookOutputEntryKind::Error,
+                text: error.to_string(),
+            });
+        }
+        None => match run_result.exit_code {
+            Some(0) => {
+                let trimmed_stdout = run_result.stdout.trim();
+                if trimmed_stdout.is_empty() {
+                } else if let Some(parsed) = parse_output(&run_result.stdout) {
+                    if let Some(system_message) = parsed.universal.system_message {
+                        entries.push(HookOutputEntry {
+                            kind: HookOutputEntryKind::Warning,
+                            text: system_message,
+                        });
+                    }
+                    if let Some(invalid_reason) = parsed.invalid_reason {
+                        status = HookRunStatus::Failed;
+                        entries.push(HookOutputEntry {
+                            kind: HookOutputEntryKind::Error,
+                            text: invalid_reason,
+                        });
+                    }
+                } else if trimmed_stdout.starts_with('{') || trimmed_stdout.starts_with('[') {
+                    status = HookRunStatus::Failed;
+                    entries.push(HookOutputEntry {
+                        kind: HookOutputEntryKind::Error,
+                        text: invalid_output_message.to_string(),
+                    });
+                }
+            }
+            Some(exit_code) => {
+                status = HookRunStatus::Failed;
+                entries.push(HookOutputEntry {
+                    kind: HookOutputEntryKind::Error,
+                    text: format!("hook exited with code {exit_code}"),
+                });
+            }
+            None => {
+                status = HookRunStatus::Failed;
+                entries.push(HookOutputEntry {
+                    kind: HookOutputEntryKind::Error,
+                    text: "hook exited without a status code".to_string(),
+                });
+            }
+        },
+    }
+
+    HookCompletedEvent {
+        turn_id,
+        run: dispatcher::completed_summary(handler, &run_result, status, entries),
+    }
+}
+
 pub(crate) fn serialization_failure_hook_events_for_tool_use(
     handlers: Vec<ConfiguredHandler>,
     turn_id: Option<String>,
@@ -103,8 +176,14 @@ pub(crate) fn matcher_pattern_for_event(
         HookEventName::PreToolUse
         | HookEventName::PermissionRequest
         | HookEventName::PostToolUse
-        | HookEventName::SessionStart => matcher,
-        HookEventName::UserPromptSubmit | HookEventName::Stop => None,
+        | HookEventName::SessionStart
+        | HookEventName::PreCompact
+        | HookEventName::PostCompact
+        | HookEventName::SubagentStart
+        | HookEventName::SubagentStop
+        | HookEventName::PreApplyPatch
+        | HookEventName::PostApplyPatch => matcher,
+        HookEventName::UserPromptSubmit | HookEventName::Stop | HookEventName::TurnError => None,
     }
 }
 
@@ -251,6 +330,10 @@ mod tests {
             matcher_pattern_for_event(HookEventName::Stop, Some("^done$")),
             None
         );
+        assert_eq!(
+            matcher_pattern_for_event(HookEventName::TurnError, Some("^timeout")),
+            None
+        );
     }
 
     #[test]
@@ -267,5 +350,17 @@ mod tests {
             matcher_pattern_for_event(HookEventName::SessionStart, Some("startup|resume")),
             Some("startup|resume")
         );
+        assert_eq!(
+            matcher_pattern_for_event(HookEventName::PreCompact, Some("auto")),
+            Some("auto")
+        );
+        assert_eq!(
+            matcher_pattern_for_event(HookEventName::SubagentStart, Some("explorer|worker")),
+            Some("explorer|worker")
+        );
+        assert_eq!(
+            matcher_pattern_for_event(HookEventName::PostApplyPatch, Some(r"\.rs$")),
+            Some(r"\.rs$")
+        );
     }
 }
@@ -1,7 +1,14 @@
 pub(crate) mod common;
 pub mod permission_request;
+pub mod post_apply_patch;
+pub mod post_compact;
 pub mod post_tool_use;
+pub mod pre_apply_patch;
+pub mod pre_compact;
 pub mod pre_tool_use;
 pub mod session_start;
 pub mod stop;
+pub mod subagent_start;
+pub mod subagent_stop;
+pub mod turn_error;
 pub mod user_prompt_submit;
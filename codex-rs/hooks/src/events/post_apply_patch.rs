@@ -0,0 +1,261 @@
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::HookCompletedEvent;
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookRunSummary;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use super::common;
+use crate::engine::ConfiguredHandler;
+use crate::engine::HandlerRunner;
+use crate::engine::command_runner::CommandRunResult;
+use crate::engine::dispatcher;
+use crate::engine::output_parser;
+use crate::schema::NullableString;
+use crate::schema::PostApplyPatchCommandInput;
+
+#[derive(Debug, Clone)]
+pub struct PostApplyPatchRequest {
+    pub session_id: ThreadId,
+    pub turn_id: String,
+    pub cwd: AbsolutePathBuf,
+    pub transcript_path: Option<PathBuf>,
+    pub model: String,
+    pub permission_mode: String,
+    pub tool_use_id: String,
+    pub patch: String,
+    pub files: Vec<String>,
+    pub success: bool,
+    pub output: String,
+}
+
+#[derive(Debug)]
+pub struct PostApplyPatchOutcome {
+    pub hook_events: Vec<HookCompletedEvent>,
+}
+
+pub(crate) fn preview(
+    handlers: &[ConfiguredHandler],
+    request: &PostApplyPatchRequest,
+) -> Vec<HookRunSummary> {
+    let matcher_inputs = request.files.iter().map(String::as_str).collect::<Vec<_>>();
+    dispatcher::select_handlers_for_matcher_inputs(
+        handlers,
+        HookEventName::PostApplyPatch,
+        &matcher_inputs,
+    )
+    .into_iter()
+    .map(|handler| {
+        common::hook_run_for_tool_use(dispatcher::running_summary(&handler), &request.tool_use_id)
+    })
+    .collect()
+}
+
+pub(crate) async fn run(
+    handlers: &[ConfiguredHandler],
+    runner: &HandlerRunner,
+    request: PostApplyPatchRequest,
+) -> PostApplyPatchOutcome {
+    let matcher_inputs = request.files.iter().map(String::as_str).collect::<Vec<_>>();
+    let matched = dispatcher::select_handlers_for_matcher_inputs(
+        handlers,
+        HookEventName::PostApplyPatch,
+        &matcher_inputs,
+    );
+    if matched.is_empty() {
+        return PostApplyPatchOutcome {
+            hook_events: Vec::new(),
+        };
+    }
+
+    let input_json = match serde_json::to_string(&PostApplyPatchCommandInput {
+        session_id: request.session_id.to_string(),
+        turn_id: request.turn_id.clone(),
+        transcript_path: NullableString::from_path(request.transcript_path.clone()),
+        cwd: request.cwd.display().to_string(),
+        hook_event_name: "PostApplyPatch".to_string(),
+        model: request.model.clone(),
+        permission_mode: request.permission_mode.clone(),
+        tool_use_id: request.tool_use_id.clone(),
+        patch: request.patch.clone(),
+        files: request.files.clone(),
+        success: request.success,
+        output: request.output.clone(),
+    }) {
+        Ok(input_json) => input_json,
+        Err(error) => {
+            return PostApplyPatchOutcome {
+                hook_events: common::serialization_failure_hook_events_for_tool_use(
+                    matched,
+                    Some(request.turn_id.clone()),
+                    format!("failed to serialize post apply patch hook input: {error}"),
+                    &request.tool_use_id,
+                ),
+            };
+        }
+    };
+
+    let results = dispatcher::execute_handlers(
+        runner,
+        matched,
+        input_json,
+        request.cwd.as_path(),
+        Some(request.turn_id.clone()),
+        parse_completed,
+    )
+    .await;
+
+    PostApplyPatchOutcome {
+        hook_events: results
+            .into_iter()
+            .map(|result| {
+                common::hook_completed_for_tool_use(result.completed, &request.tool_use_id)
+            })
+            .collect(),
+    }
+}
+
+fn parse_completed(
+    handler: &ConfiguredHandler,
+    run_result: CommandRunResult,
+    turn_id: Option<String>,
+) -> dispatcher::ParsedHandler<()> {
+    dispatcher::ParsedHandler {
+        completed: common::parse_observer_completed(
+            handler,
+            run_result,
+            turn_id,
+            output_parser::parse_post_apply_patch,
+            "hook returned invalid post apply patch hook JSON output",
+        ),
+        data: (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookOutputEntry;
+    use codex_protocol::protocol::HookOutputEntryKind;
+    use codex_protocol::protocol::HookRunStatus;
+    use codex_utils_absolute_path::test_support::PathBufExt;
+    use codex_utils_absolute_path::test_support::test_path_buf;
+    use pretty_assertions::assert_eq;
+
+    use super::PostApplyPatchRequest;
+    use super::parse_completed;
+    use super::preview;
+    use crate::engine::ConfiguredHandler;
+    use crate::engine::HandlerKind;
+    use crate::engine::command_runner::CommandRunResult;
+
+    #[test]
+    fn matcher_selects_handlers_by_patched_file() {
+        let handlers = vec![
+            handler(Some(r"\.rs$"), /*display_order*/ 0),
+            handler(Some(r"\.py$"), /*display_order*/ 1),
+        ];
+
+        let runs = preview(&handlers, &request(vec!["/repo/src/lib.rs".to_string()]));
+
+        assert_eq!(
+            runs.iter().map(|run| run.display_order).collect::<Vec<_>>(),
+            vec![0]
+        );
+        assert!(runs[0].id.ends_with(":call-1"));
+    }
+
+    #[test]
+    fn system_message_is_reported_as_warning() {
+        let parsed = parse_completed(
+            &handler(/*matcher*/ None, /*display_order*/ 0),
+            run_result(Some(0), r#"{"systemMessage":"formatted 1 file"}"#),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
+        assert_eq!(
+            parsed.completed.run.entries,
+            vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Warning,
+                text: "formatted 1 file".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn plain_stdout_is_ignored() {
+        let parsed = parse_completed(
+            &handler(/*matcher*/ None, /*display_order*/ 0),
+            run_result(Some(0), "reformatted src/lib.rs\n"),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
+        assert_eq!(parsed.completed.run.entries, Vec::new());
+    }
+
+    #[test]
+    fn continue_false_is_unsupported() {
+        let parsed = parse_completed(
+            &handler(/*matcher*/ None, /*display_order*/ 0),
+            run_result(Some(0), r#"{"continue":false}"#),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
+        assert_eq!(
+            parsed.completed.run.entries,
+            vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Error,
+                text: "PostApplyPatch hook returned unsupported continue:false".to_string(),
+            }]
+        );
+    }
+
+    fn request(files: Vec<String>) -> PostApplyPatchRequest {
+        PostApplyPatchRequest {
+            session_id: ThreadId::new(),
+            turn_id: "turn-1".to_string(),
+            cwd: test_path_buf("/repo").abs(),
+            transcript_path: None,
+            model: "gpt-test".to_string(),
+            permission_mode: "default".to_string(),
+            tool_use_id: "call-1".to_string(),
+            patch: "*** Begin Patch\n*** End Patch".to_string(),
+            files,
+            success: true,
+            output: String::new(),
+        }
+    }
+
+    fn handler(matcher: Option<&str>, display_order: i64) -> ConfiguredHandler {
+        ConfiguredHandler {
+            event_name: HookEventName::PostApplyPatch,
+            is_managed: false,
+            matcher: matcher.map(str::to_owned),
+            kind: HandlerKind::Command {
+                command: "echo hook".to_string(),
+            },
+            timeout_sec: 600,
+            status_message: None,
+            source_path: test_path_buf("/tmp/hooks.json").abs(),
+            source: codex_protocol::protocol::HookSource::User,
+            display_order,
+        }
+    }
+
+    fn run_result(exit_code: Option<i32>, stdout: &str) -> CommandRunResult {
+        CommandRunResult {
+            started_at: 1,
+            completed_at: 2,
+            duration_ms: 1,
+            exit_code,
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            error: None,
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::HookCompletedEvent;
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookRunSummary;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use super::common;
+use super::pre_compact::CompactTrigger;
+use crate::engine::ConfiguredHandler;
+use crate::engine::HandlerRunner;
+use crate::engine::command_runner::CommandRunResult;
+use crate::engine::dispatcher;
+use crate::engine::output_parser;
+use crate::schema::NullableString;
+use crate::schema::PostCompactCommandInput;
+
+#[derive(Debug, Clone)]
+pub struct PostCompactRequest {
+    pub session_id: ThreadId,
+    pub turn_id: String,
+    pub cwd: AbsolutePathBuf,
+    pub transcript_path: Option<PathBuf>,
+    pub model: String,
+    pub permission_mode: String,
+    pub trigger: CompactTrigger,
+    pub success: bool,
+}
+
+#[derive(Debug)]
+pub struct PostCompactOutcome {
+    pub hook_events: Vec<HookCompletedEvent>,
+}
+
+pub(crate) fn preview(
+    handlers: &[ConfiguredHandler],
+    request: &PostCompactRequest,
+) -> Vec<HookRunSummary> {
+    dispatcher::select_handlers(
+        handlers,
+        HookEventName::PostCompact,
+        Some(request.trigger.as_str()),
+    )
+    .into_iter()
+    .map(|handler| dispatcher::running_summary(&handler))
+    .collect()
+}
+
+pub(crate) async fn run(
+    handlers: &[ConfiguredHandler],
+    runner: &HandlerRunner,
+    request: PostCompactRequest,
+) -> PostCompactOutcome {
+    let matched = dispatcher::select_handlers(
+        handlers,
+        HookEventName::PostCompact,
+        Some(request.trigger.as_str()),
+    );
+    if matched.is_empty() {
+        return PostCompactOutcome {
+            hook_events: Vec::new(),
+        };
+    }
+
+    let input_json = match serde_json::to_string(&PostCompactCommandInput {
+        session_id: request.session_id.to_string(),
+        turn_id: request.turn_id.clone(),
+        transcript_path: NullableString::from_path(request.transcript_path.clone()),
+        cwd: request.cwd.display().to_string(),
+        hook_event_name: "PostCompact".to_string(),
+        model: request.model.clone(),
+        permission_mode: request.permission_mode.clone(),
+        trigger: request.trigger.as_str().to_string(),
+        success: request.success,
+    }) {
+        Ok(input_json) => input_json,
+        Err(error) => {
+            return PostCompactOutcome {
+                hook_events: common::serialization_failure_hook_events(
+                    matched,
+                    Some(request.turn_id.clone()),
+                    format!("failed to serialize post compact hook input: {error}"),
+                ),
+            };
+        }
+    };
+
+    let results = dispatcher::execute_handlers(
+        runner,
+        matched,
+        input_json,
+        request.cwd.as_path(),
+        Some(request.turn_id.clone()),
+        parse_completed,
+    )
+    .await;
+
+    PostCompactOutcome {
+        hook_events: results.into_iter().map(|result| result.completed).collect(),
+    }
+}
+
+fn parse_completed(
+    handler: &ConfiguredHandler,
+    run_result: CommandRunResult,
+    turn_id: Option<String>,
+) -> dispatcher::ParsedHandler<()> {
+    dispatcher::ParsedHandler {
+        completed: common::parse_observer_completed(
+            handler,
+            run_result,
+            turn_id,
+            output_parser::parse_post_compact,
+            "hook returned invalid post compact hook JSON output",
+        ),
+        data: (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookOutputEntry;
+    use codex_protocol::protocol::HookOutputEntryKind;
+    use codex_protocol::protocol::HookRunStatus;
+    use codex_utils_absolute_path::test_support::PathBufExt;
+    use codex_utils_absolute_path::test_support::test_path_buf;
+    use pretty_assertions::assert_eq;
+
+    use super::PostCompactRequest;
+    use super::parse_completed;
+    use super::preview;
+    use crate::engine::ConfiguredHandler;
+    use crate::engine::HandlerKind;
+    use crate::engine::command_runner::CommandRunResult;
+    use crate::events::pre_compact::CompactTrigger;
+
+    #[test]
+    fn matcher_selects_handlers_by_trigger() {
+        let handlers = vec![
+            handler(Some("manual"), /*display_order*/ 0),
+            handler(Some("auto"), /*display_order*/ 1),
+        ];
+
+        let runs = preview(&handlers, &request(CompactTrigger::Manual));
+
+        assert_eq!(
+            runs.iter().map(|run| run.display_order).collect::<Vec<_>>(),
+            vec![0]
+        );
+    }
+
+    #[test]
+    fn continue_false_is_unsupported() {
+        let parsed = parse_completed(
+            &handler(/*matcher*/ None, /*display_order*/ 0),
+            run_result(Some(0), r#"{"continue":false}"#),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
+        assert_eq!(
+            parsed.completed.run.entries,
+            vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Error,
+                text: "PostCompact hook returned unsupported continue:false".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn nonzero_exit_fails() {
+        let parsed = parse_completed(
+            &handler(/*matcher*/ None, /*display_order*/ 0),
+            run_result(Some(1), ""),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
+        assert_eq!(
+            parsed.completed.run.entries,
+            vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Error,
+                text: "hook exited with code 1".to_string(),
+            }]
+        );
+    }
+
+    fn request(trigger: CompactTrigger) -> PostCompactRequest {
+        PostCompactRequest {
+            session_id: ThreadId::new(),
+            turn_id: "turn-1".to_string(),
+            cwd: test_path_buf("/repo").abs(),
+            transcript_path: None,
+            model: "gpt-test".to_string(),
+            permission_mode: "default".to_string(),
+            trigger,
+            success: true,
+        }
+    }
+
+    fn handler(matcher: Option<&str>, display_order: i64) -> ConfiguredHandler {
+        ConfiguredHandler {
+            event_name: HookEventName::PostCompact,
+            is_managed: false,
+            matcher: matcher.map(str::to_owned),
+            kind: HandlerKind::Command {
+                command: "echo hook".to_string(),
+            },
+            timeout_sec: 600,
+            status_message: None,
+            source_path: test_path_buf("/tmp/hooks.json").abs(),
+            source: codex_protocol::protocol::HookSource::User,
+            display_order,
+        }
+    }
+
+    fn run_result(exit_code: Option<i32>, stdout: &str) -> CommandRunResult {
+        CommandRunResult {
+            started_at: 1,
+            completed_at: 2,
+            duration_ms: 1,
+            exit_code,
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            error: None,
+        }
+    }
+}
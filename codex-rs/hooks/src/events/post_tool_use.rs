@@ -11,8 +11,8 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use serde_json::Value;
 
 use super::common;
-use crate::engine::CommandShell;
 use crate::engine::ConfiguredHandler;
+use crate::engine::HandlerRunner;
 use crate::engine::command_runner::CommandRunResult;
 use crate::engine::dispatcher;
 use crate::engine::output_parser;
@@ -40,14 +40,18 @@ pub struct PostToolUseOutcome {
     pub stop_reason: Option<String>,
     pub additional_contexts: Vec<String>,
     pub feedback_message: Option<String>,
+    /// Replacement tool output requested by a hook. When several hooks
+    /// rewrite the output, the last one in display order wins.
+    pub updated_output: Option<Value>,
 }
 
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq)]
 struct PostToolUseHandlerData {
     should_stop: bool,
     stop_reason: Option<String>,
     additional_contexts_for_model: Vec<String>,
     feedback_messages_for_model: Vec<String>,
+    updated_output: Option<Value>,
 }
 
 pub(crate) fn preview(
@@ -69,7 +73,7 @@ pub(crate) fn preview(
 
 pub(crate) async fn run(
     handlers: &[ConfiguredHandler],
-    shell: &CommandShell,
+    runner: &HandlerRunner,
     request: PostToolUseRequest,
 ) -> PostToolUseOutcome {
     let matcher_inputs = common::matcher_inputs(&request.tool_name, &request.matcher_aliases);
@@ -85,6 +89,7 @@ pub(crate) async fn run(
             stop_reason: None,
             additional_contexts: Vec::new(),
             feedback_message: None,
+            updated_output: None,
         };
     }
 
@@ -102,7 +107,7 @@ pub(crate) async fn run(
     };
 
     let results = dispatcher::execute_handlers(
-        shell,
+        runner,
         matched,
         input_json,
         request.cwd.as_path(),
@@ -126,6 +131,10 @@ pub(crate) async fn run(
             .flat_map(|result| result.data.feedback_messages_for_model.clone())
             .collect(),
     );
+    let updated_output = results
+        .iter()
+        .rev()
+        .find_map(|result| result.data.updated_output.clone());
 
     PostToolUseOutcome {
         hook_events: results
@@ -138,6 +147,7 @@ pub(crate) async fn run(
         stop_reason,
         additional_contexts,
         feedback_message,
+        updated_output,
     }
 }
 
@@ -174,6 +184,7 @@ fn parse_completed(
     let mut stop_reason = None;
     let mut additional_contexts_for_model = Vec::new();
     let mut feedback_messages_for_model = Vec::new();
+    let mut updated_output = None;
 
     match run_result.error.as_deref() {
         Some(error) => {
@@ -244,6 +255,12 @@ fn parse_completed(
                             });
                             feedback_messages_for_model.push(reason);
                         }
+                    } else if let Some(output) = parsed.updated_output {
+                        entries.push(HookOutputEntry {
+                            kind: HookOutputEntryKind::Rewrite,
+                            text: format!("updated tool output: {output}"),
+                        });
+                        updated_output = Some(output);
                     }
                 } else if trimmed_stdout.starts_with('{') || trimmed_stdout.starts_with('[') {
                     status = HookRunStatus::Failed;
@@ -297,6 +314,7 @@ fn parse_completed(
             stop_reason,
             additional_contexts_for_model,
             feedback_messages_for_model,
+            updated_output,
         },
     }
 }
@@ -308,6 +326,7 @@ fn serialization_failure_outcome(hook_events: Vec<HookCompletedEvent>) -> PostTo
         stop_reason: None,
         additional_contexts: Vec::new(),
         feedback_message: None,
+        updated_output: None,
     }
 }
 
@@ -328,6 +347,7 @@ mod tests {
     use super::parse_completed;
     use super::preview;
     use crate::engine::ConfiguredHandler;
+    use crate::engine::HandlerKind;
     use crate::engine::command_runner::CommandRunResult;
     use crate::events::common;
 
@@ -362,6 +382,7 @@ mod tests {
                 stop_reason: None,
                 additional_contexts_for_model: Vec::new(),
                 feedback_messages_for_model: vec!["bash output looked sketchy".to_string()],
+                updated_output: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Blocked);
@@ -386,6 +407,7 @@ mod tests {
                 stop_reason: None,
                 additional_contexts_for_model: vec!["Remember the bash cleanup note.".to_string()],
                 feedback_messages_for_model: Vec::new(),
+                updated_output: None,
             }
         );
         assert_eq!(
@@ -397,6 +419,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn updated_output_rewrites_tool_output() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(
+                Some(0),
+                r#"{"hookSpecificOutput":{"hookEventName":"PostToolUse","updatedOutput":"API_KEY=[REDACTED]"}}"#,
+                "",
+            ),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(
+            parsed.data,
+            PostToolUseHandlerData {
+                should_stop: false,
+                stop_reason: None,
+                additional_contexts_for_model: Vec::new(),
+                feedback_messages_for_model: Vec::new(),
+                updated_output: Some(json!("API_KEY=[REDACTED]")),
+            }
+        );
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
+        assert_eq!(
+            parsed.completed.run.entries,
+            vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Rewrite,
+                text: r#"updated tool output: "API_KEY=[REDACTED]""#.to_string(),
+            }]
+        );
+    }
+
     #[test]
     fn unsupported_updated_mcp_tool_output_fails_open() {
         let parsed = parse_completed(
@@ -416,6 +470,7 @@ mod tests {
                 stop_reason: None,
                 additional_contexts_for_model: Vec::new(),
                 feedback_messages_for_model: Vec::new(),
+                updated_output: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
@@ -443,6 +498,7 @@ mod tests {
                 stop_reason: None,
                 additional_contexts_for_model: Vec::new(),
                 feedback_messages_for_model: vec!["post hook says pause".to_string()],
+                updated_output: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
@@ -467,6 +523,7 @@ mod tests {
                 stop_reason: Some("halt after bash output".to_string()),
                 additional_contexts_for_model: Vec::new(),
                 feedback_messages_for_model: vec!["post-tool hook says stop".to_string()],
+                updated_output: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Stopped);
@@ -494,6 +551,7 @@ mod tests {
                 stop_reason: None,
                 additional_contexts_for_model: Vec::new(),
                 feedback_messages_for_model: Vec::new(),
+                updated_output: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
@@ -545,7 +603,9 @@ mod tests {
             event_name: HookEventName::PostToolUse,
             is_managed: false,
             matcher: Some("^Bash$".to_string()),
-            command: "python3 post_tool_use_hook.py".to_string(),
+            kind: HandlerKind::Command {
+                command: "python3 post_tool_use_hook.py".to_string(),
+            },
             timeout_sec: 5,
             status_message: Some("running post tool use hook".to_string()),
             source_path: test_path_buf("/tmp/hooks.json").abs(),
@@ -11,8 +11,8 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use serde_json::Value;
 
 use super::common;
-use crate::engine::CommandShell;
 use crate::engine::ConfiguredHandler;
+use crate::engine::HandlerRunner;
 use crate::engine::command_runner::CommandRunResult;
 use crate::engine::dispatcher;
 use crate::engine::output_parser;
@@ -37,12 +37,17 @@ pub struct PreToolUseOutcome {
     pub hook_events: Vec<HookCompletedEvent>,
     pub should_block: bool,
     pub block_reason: Option<String>,
+    /// Replacement tool input requested by a hook, in the same shape as the
+    /// request's `tool_input`. When several hooks rewrite the input, the last
+    /// one in display order wins. Callers must validate it before use.
+    pub updated_input: Option<Value>,
 }
 
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, PartialEq)]
 struct PreToolUseHandlerData {
     should_block: bool,
     block_reason: Option<String>,
+    updated_input: Option<Value>,
 }
 
 pub(crate) fn preview(
@@ -64,7 +69,7 @@ pub(crate) fn preview(
 
 pub(crate) async fn run(
     handlers: &[ConfiguredHandler],
-    shell: &CommandShell,
+    runner: &HandlerRunner,
     request: PreToolUseRequest,
 ) -> PreToolUseOutcome {
     let matcher_inputs = common::matcher_inputs(&request.tool_name, &request.matcher_aliases);
@@ -78,6 +83,7 @@ pub(crate) async fn run(
             hook_events: Vec::new(),
             should_block: false,
             block_reason: None,
+            updated_input: None,
         };
     }
 
@@ -95,7 +101,7 @@ pub(crate) async fn run(
     };
 
     let results = dispatcher::execute_handlers(
-        shell,
+        runner,
         matched,
         input_json,
         request.cwd.as_path(),
@@ -108,6 +114,14 @@ pub(crate) async fn run(
     let block_reason = results
         .iter()
         .find_map(|result| result.data.block_reason.clone());
+    let updated_input = if should_block {
+        None
+    } else {
+        results
+            .iter()
+            .rev()
+            .find_map(|result| result.data.updated_input.clone())
+    };
 
     PreToolUseOutcome {
         hook_events: results
@@ -118,6 +132,7 @@ pub(crate) async fn run(
             .collect(),
         should_block,
         block_reason,
+        updated_input,
     }
 }
 
@@ -151,6 +166,7 @@ fn parse_completed(
     let mut status = HookRunStatus::Completed;
     let mut should_block = false;
     let mut block_reason = None;
+    let mut updated_input = None;
 
     match run_result.error.as_deref() {
         Some(error) => {
@@ -185,6 +201,12 @@ fn parse_completed(
                             kind: HookOutputEntryKind::Feedback,
                             text: reason,
                         });
+                    } else if let Some(input) = parsed.updated_input {
+                        entries.push(HookOutputEntry {
+                            kind: HookOutputEntryKind::Rewrite,
+                            text: format!("updated tool input: {input}"),
+                        });
+                        updated_input = Some(input);
                     }
                 } else if trimmed_stdout.starts_with('{') || trimmed_stdout.starts_with('[') {
                     status = HookRunStatus::Failed;
@@ -238,6 +260,7 @@ fn parse_completed(
         data: PreToolUseHandlerData {
             should_block,
             block_reason,
+            updated_input,
         },
     }
 }
@@ -247,6 +270,7 @@ fn serialization_failure_outcome(hook_events: Vec<HookCompletedEvent>) -> PreToo
         hook_events,
         should_block: false,
         block_reason: None,
+        updated_input: None,
     }
 }
 
@@ -266,6 +290,7 @@ mod tests {
     use super::parse_completed;
     use super::preview;
     use crate::engine::ConfiguredHandler;
+    use crate::engine::HandlerKind;
     use crate::engine::command_runner::CommandRunResult;
     use crate::events::common;
 
@@ -298,6 +323,7 @@ mod tests {
             PreToolUseHandlerData {
                 should_block: true,
                 block_reason: Some("do not run that".to_string()),
+                updated_input: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Blocked);
@@ -310,6 +336,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn updated_input_rewrites_tool_input() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(
+                Some(0),
+                r#"{"hookSpecificOutput":{"hookEventName":"PreToolUse","updatedInput":{"command":"cargo publish --dry-run"}}}"#,
+                "",
+            ),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(
+            parsed.data,
+            PreToolUseHandlerData {
+                should_block: false,
+                block_reason: None,
+                updated_input: Some(serde_json::json!({"command": "cargo publish --dry-run"})),
+            }
+        );
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
+        assert_eq!(
+            parsed.completed.run.entries,
+            vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Rewrite,
+                text: r#"updated tool input: {"command":"cargo publish --dry-run"}"#.to_string(),
+            }]
+        );
+    }
+
     #[test]
     fn deprecated_block_decision_blocks_processing() {
         let parsed = parse_completed(
@@ -327,6 +383,7 @@ mod tests {
             PreToolUseHandlerData {
                 should_block: true,
                 block_reason: Some("do not run that".to_string()),
+                updated_input: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Blocked);
@@ -356,6 +413,7 @@ mod tests {
             PreToolUseHandlerData {
                 should_block: false,
                 block_reason: None,
+                updated_input: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
@@ -381,6 +439,7 @@ mod tests {
             PreToolUseHandlerData {
                 should_block: false,
                 block_reason: None,
+                updated_input: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
@@ -410,6 +469,7 @@ mod tests {
             PreToolUseHandlerData {
                 should_block: false,
                 block_reason: None,
+                updated_input: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
@@ -435,6 +495,7 @@ mod tests {
             PreToolUseHandlerData {
                 should_block: false,
                 block_reason: None,
+                updated_input: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
@@ -454,6 +515,7 @@ mod tests {
             PreToolUseHandlerData {
                 should_block: false,
                 block_reason: None,
+                updated_input: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
@@ -479,6 +541,7 @@ mod tests {
             PreToolUseHandlerData {
                 should_block: true,
                 block_reason: Some("blocked by policy".to_string()),
+                updated_input: None,
             }
         );
         assert_eq!(parsed.completed.run.status, HookRunStatus::Blocked);
@@ -536,7 +599,9 @@ mod tests {
             event_name: HookEventName::PreToolUse,
             is_managed: false,
             matcher: Some("^Bash$".to_string()),
-            command: "echo hook".to_string(),
+            kind: HandlerKind::Command {
+                command: "echo hook".to_string(),
+            },
             timeout_sec: 5,
             status_message: None,
             source_path: test_path_buf("/tmp/hooks.json").abs(),
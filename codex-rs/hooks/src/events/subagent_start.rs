@@ -0,0 +1,235 @@
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::HookCompletedEvent;
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookRunSummary;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use super::common;
+use crate::engine::ConfiguredHandler;
+use crate::engine::HandlerRunner;
+use crate::engine::command_runner::CommandRunResult;
+use crate::engine::dispatcher;
+use crate::engine::output_parser;
+use crate::schema::NullableString;
+use crate::schema::SubagentStartCommandInput;
+
+#[derive(Debug, Clone)]
+pub struct SubagentStartRequest {
+    pub session_id: ThreadId,
+    pub turn_id: String,
+    pub cwd: AbsolutePathBuf,
+    pub transcript_path: Option<PathBuf>,
+    pub model: String,
+    pub permission_mode: String,
+    pub agent_id: ThreadId,
+    pub agent_type: String,
+    pub agent_nickname: Option<String>,
+    pub prompt: String,
+}
+
+#[derive(Debug)]
+pub struct SubagentStartOutcome {
+    pub hook_events: Vec<HookCompletedEvent>,
+}
+
+pub(crate) fn preview(
+    handlers: &[ConfiguredHandler],
+    request: &SubagentStartRequest,
+) -> Vec<HookRunSummary> {
+    dispatcher::select_handlers(
+        handlers,
+        HookEventName::SubagentStart,
+        Some(request.agent_type.as_str()),
+    )
+    .into_iter()
+    .map(|handler| dispatcher::running_summary(&handler))
+    .collect()
+}
+
+pub(crate) async fn run(
+    handlers: &[ConfiguredHandler],
+    runner: &HandlerRunner,
+    request: SubagentStartRequest,
+) -> SubagentStartOutcome {
+    let matched = dispatcher::select_handlers(
+        handlers,
+        HookEventName::SubagentStart,
+        Some(request.agent_type.as_str()),
+    );
+    if matched.is_empty() {
+        return SubagentStartOutcome {
+            hook_events: Vec::new(),
+        };
+    }
+
+    let input_json = match serde_json::to_string(&SubagentStartCommandInput {
+        session_id: request.session_id.to_string(),
+        turn_id: request.turn_id.clone(),
+        transcript_path: NullableString::from_path(request.transcript_path.clone()),
+        cwd: request.cwd.display().to_string(),
+        hook_event_name: "SubagentStart".to_string(),
+        model: request.model.clone(),
+        permission_mode: request.permission_mode.clone(),
+        agent_id: request.agent_id.to_string(),
+        agent_type: request.agent_type.clone(),
+        agent_nickname: NullableString::from_string(request.agent_nickname.clone()),
+        prompt: request.prompt.clone(),
+    }) {
+        Ok(input_json) => input_json,
+        Err(error) => {
+            return SubagentStartOutcome {
+                hook_events: common::serialization_failure_hook_events(
+                    matched,
+                    Some(request.turn_id.clone()),
+                    format!("failed to serialize subagent start hook input: {error}"),
+                ),
+            };
+        }
+    };
+
+    let results = dispatcher::execute_handlers(
+        runner,
+        matched,
+        input_json,
+        request.cwd.as_path(),
+        Some(request.turn_id.clone()),
+        parse_completed,
+    )
+    .await;
+
+    SubagentStartOutcome {
+        hook_events: results.into_iter().map(|result| result.completed).collect(),
+    }
+}
+
+fn parse_completed(
+    handler: &ConfiguredHandler,
+    run_result: CommandRunResult,
+    turn_id: Option<String>,
+) -> dispatcher::ParsedHandler<()> {
+    dispatcher::ParsedHandler {
+        completed: common::parse_observer_completed(
+            handler,
+            run_result,
+            turn_id,
+            output_parser::parse_subagent_start,
+            "hook returned invalid subagent start hook JSON output",
+        ),
+        data: (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookOutputEntry;
+    use codex_protocol::protocol::HookOutputEntryKind;
+    use codex_protocol::protocol::HookRunStatus;
+    use codex_utils_absolute_path::test_support::PathBufExt;
+    use codex_utils_absolute_path::test_support::test_path_buf;
+    use pretty_assertions::assert_eq;
+
+    use super::SubagentStartRequest;
+    use super::parse_completed;
+    use super::preview;
+    use crate::engine::ConfiguredHandler;
+    use crate::engine::HandlerKind;
+    use crate::engine::command_runner::CommandRunResult;
+
+    #[test]
+    fn matcher_selects_handlers_by_agent_type() {
+        let handlers = vec![
+            handler(Some("^explorer$"), /*display_order*/ 0),
+            handler(Some("^worker$"), /*display_order*/ 1),
+        ];
+
+        let runs = preview(&handlers, &request("worker"));
+
+        assert_eq!(
+            runs.iter().map(|run| run.display_order).collect::<Vec<_>>(),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn system_message_is_reported_as_warning() {
+        let parsed = parse_completed(
+            &handler(/*matcher*/ None, /*display_order*/ 0),
+            run_result(Some(0), r#"{"systemMessage":"worker registered"}"#),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
+        assert_eq!(
+            parsed.completed.run.entries,
+            vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Warning,
+                text: "worker registered".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn stop_reason_is_unsupported() {
+        let parsed = parse_completed(
+            &handler(/*matcher*/ None, /*display_order*/ 0),
+            run_result(Some(0), r#"{"stopReason":"no more agents"}"#),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Failed);
+        assert_eq!(
+            parsed.completed.run.entries,
+            vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Error,
+                text: "SubagentStart hook returned unsupported stopReason".to_string(),
+            }]
+        );
+    }
+
+    fn request(agent_type: &str) -> SubagentStartRequest {
+        SubagentStartRequest {
+            session_id: ThreadId::new(),
+            turn_id: "turn-1".to_string(),
+            cwd: test_path_buf("/repo").abs(),
+            transcript_path: None,
+            model: "gpt-test".to_string(),
+            permission_mode: "default".to_string(),
+            agent_id: ThreadId::new(),
+            agent_type: agent_type.to_string(),
+            agent_nickname: None,
+            prompt: "look around".to_string(),
+        }
+    }
+
+    fn handler(matcher: Option<&str>, display_order: i64) -> ConfiguredHandler {
+        ConfiguredHandler {
+            event_name: HookEventName::SubagentStart,
+            is_managed: false,
+            matcher: matcher.map(str::to_owned),
+            kind: HandlerKind::Command {
+                command: "echo hook".to_string(),
+            },
+            timeout_sec: 600,
+            status_message: None,
+            source_path: test_path_buf("/tmp/hooks.json").abs(),
+            source: codex_protocol::protocol::HookSource::User,
+            display_order,
+        }
+    }
+
+    fn run_result(exit_code: Option<i32>, stdout: &str) -> CommandRunResult {
+        CommandRunResult {
+            started_at: 1,
+            completed_at: 2,
+            duration_ms: 1,
+            exit_code,
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+            error: None,
+        }
+    }
+}
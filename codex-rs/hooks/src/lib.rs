@@ -1,6 +1,7 @@
 mod engine;
 pub(crate) mod events;
 mod legacy_notify;
+mod model_client;
 mod registry;
 mod schema;
 mod types;
@@ -8,8 +9,17 @@ mod types;
 pub use events::permission_request::PermissionRequestDecision;
 pub use events::permission_request::PermissionRequestOutcome;
 pub use events::permission_request::PermissionRequestRequest;
+pub use events::post_apply_patch::PostApplyPatchOutcome;
+pub use events::post_apply_patch::PostApplyPatchRequest;
+pub use events::post_compact::PostCompactOutcome;
+pub use events::post_compact::PostCompactRequest;
 pub use events::post_tool_use::PostToolUseOutcome;
 pub use events::post_tool_use::PostToolUseRequest;
+pub use events::pre_apply_patch::PreApplyPatchOutcome;
+pub use events::pre_apply_patch::PreApplyPatchRequest;
+pub use events::pre_compact::CompactTrigger;
+pub use events::pre_compact::PreCompactOutcome;
+pub use events::pre_compact::PreCompactRequest;
 pub use events::pre_tool_use::PreToolUseOutcome;
 pub use events::pre_tool_use::PreToolUseRequest;
 pub use events::session_start::SessionStartOutcome;
@@ -17,10 +27,18 @@ pub use events::session_start::SessionStartRequest;
 pub use events::session_start::SessionStartSource;
 pub use events::stop::StopOutcome;
 pub use events::stop::StopRequest;
+pub use events::subagent_start::SubagentStartOutcome;
+pub use events::subagent_start::SubagentStartRequest;
+pub use events::subagent_stop::SubagentStopOutcome;
+pub use events::subagent_stop::SubagentStopRequest;
+pub use events::turn_error::TurnErrorOutcome;
+pub use events::turn_error::TurnErrorRequest;
 pub use events::user_prompt_submit::UserPromptSubmitOutcome;
 pub use events::user_prompt_submit::UserPromptSubmitRequest;
 pub use legacy_notify::legacy_notify_json;
 pub use legacy_notify::notify_hook;
+pub use model_client::HookModelClient;
+pub use model_client::HookModelRequest;
 pub use registry::Hooks;
 pub use registry::HooksConfig;
 pub use registry::command_from_argv;
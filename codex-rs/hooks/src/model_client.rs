@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// A single model request issued by a `prompt` or `agent` hook handler.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookModelRequest {
+    /// Model to query. `None` selects the session model.
+    pub model: Option<String>,
+    pub instructions: String,
+    pub input: String,
+    /// JSON schema the final model message must conform to.
+    pub output_schema: Value,
+}
+
+/// Sends hook requests to a model on behalf of the session that owns the hooks.
+#[async_trait]
+pub trait HookModelClient: Send + Sync {
+    /// Returns the text of the final assistant message.
+    async fn complete(&self, request: HookModelRequest) -> anyhow::Result<String>;
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use codex_config::ConfigLayerStack;
 use tokio::process::Command;
 
@@ -5,16 +7,31 @@ use crate::engine::ClaudeHooksEngine;
 use crate::engine::CommandShell;
 use crate::events::permission_request::PermissionRequestOutcome;
 use crate::events::permission_request::PermissionRequestRequest;
+use crate::events::post_apply_patch::PostApplyPatchOutcome;
+use crate::events::post_apply_patch::PostApplyPatchRequest;
+use crate::events::post_compact::PostCompactOutcome;
+use crate::events::post_compact::PostCompactRequest;
 use crate::events::post_tool_use::PostToolUseOutcome;
 use crate::events::post_tool_use::PostToolUseRequest;
+use crate::events::pre_apply_patch::PreApplyPatchOutcome;
+use crate::events::pre_apply_patch::PreApplyPatchRequest;
+use crate::events::pre_compact::PreCompactOutcome;
+use crate::events::pre_compact::PreCompactRequest;
 use crate::events::pre_tool_use::PreToolUseOutcome;
 use crate::events::pre_tool_use::PreToolUseRequest;
 use crate::events::session_start::SessionStartOutcome;
 use crate::events::session_start::SessionStartRequest;
 use crate::events::stop::StopOutcome;
 use crate::events::stop::StopRequest;
+use crate::events::subagent_start::SubagentStartOutcome;
+use crate::events::subagent_start::SubagentStartRequest;
+use crate::events::subagent_stop::SubagentStopOutcome;
+use crate::events::subagent_stop::SubagentStopRequest;
+use crate::events::turn_error::TurnErrorOutcome;
+use crate::events::turn_error::TurnErrorRequest;
 use crate::events::user_prompt_submit::UserPromptSubmitOutcome;
 use crate::events::user_prompt_submit::UserPromptSubmitRequest;
+use crate::model_client::HookModelClient;
 use crate::types::Hook;
 use crate::types::HookEvent;
 use crate::types::HookPayload;
@@ -27,6 +44,8 @@ pub struct HooksConfig {
     pub config_layer_stack: Option<ConfigLayerStack>,
     pub shell_program: Option<String>,
     pub shell_args: Vec<String>,
+    /// Client used by `prompt` and `agent` hook handlers. Those handlers fail when it is unset.
+    pub model_client: Option<Arc<dyn HookModelClient>>,
 }
 
 #[derive(Clone)]
@@ -57,6 +76,7 @@ impl Hooks {
                 program: config.shell_program.unwrap_or_default(),
                 args: config.shell_args,
             },
+            config.model_client,
         );
         Self {
             after_agent,
@@ -166,6 +186,86 @@ impl Hooks {
     pub async fn run_stop(&self, request: StopRequest) -> StopOutcome {
         self.engine.run_stop(request).await
     }
+
+    pub fn preview_pre_compact(
+        &self,
+        request: &PreCompactRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_pre_compact(request)
+    }
+
+    pub async fn run_pre_compact(&self, request: PreCompactRequest) -> PreCompactOutcome {
+        self.engine.run_pre_compact(request).await
+    }
+
+    pub fn preview_post_compact(
+        &self,
+        request: &PostCompactRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_post_compact(request)
+    }
+
+    pub async fn run_post_compact(&self, request: PostCompactRequest) -> PostCompactOutcome {
+        self.engine.run_post_compact(request).await
+    }
+
+    pub fn preview_subagent_start(
+        &self,
+        request: &SubagentStartRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_subagent_start(request)
+    }
+
+    pub async fn run_subagent_start(&self, request: SubagentStartRequest) -> SubagentStartOutcome {
+        self.engine.run_subagent_start(request).await
+    }
+
+    pub fn preview_subagent_stop(
+        &self,
+        request: &SubagentStopRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_subagent_stop(request)
+    }
+
+    pub async fn run_subagent_stop(&self, request: SubagentStopRequest) -> SubagentStopOutcome {
+        self.engine.run_subagent_stop(request).await
+    }
+
+    pub fn preview_pre_apply_patch(
+        &self,
+        request: &PreApplyPatchRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_pre_apply_patch(request)
+    }
+
+    pub async fn run_pre_apply_patch(&self, request: PreApplyPatchRequest) -> PreApplyPatchOutcome {
+        self.engine.run_pre_apply_patch(request).await
+    }
+
+    pub fn preview_post_apply_patch(
+        &self,
+        request: &PostApplyPatchRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_post_apply_patch(request)
+    }
+
+    pub async fn run_post_apply_patch(
+        &self,
+        request: PostApplyPatchRequest,
+    ) -> PostApplyPatchOutcome {
+        self.engine.run_post_apply_patch(request).await
+    }
+
+    pub fn preview_turn_error(
+        &self,
+        request: &TurnErrorRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_turn_error(request)
+    }
+
+    pub async fn run_turn_error(&self, request: TurnErrorRequest) -> TurnErrorOutcome {
+        self.engine.run_turn_error(request).await
+    }
 }
 
 pub fn command_from_argv(argv: &[String]) -> Option<Command> {
@@ -22,6 +22,7 @@ use codex_protocol::protocol::ExecApprovalRequestEvent;
 use codex_protocol::protocol::Op;
 use codex_protocol::protocol::Submission;
 use codex_protocol::protocol::TurnCompleteEvent;
+use codex_protocol::protocol::TurnDiffEvent;
 use codex_protocol::user_input::UserInput;
 use rmcp::model::CallToolResult;
 use rmcp::model::Content;
@@ -56,6 +57,7 @@ pub(crate) fn create_call_tool_result_with_thread_id(
 ///
 /// On completion (success or error) the function sends the appropriate
 /// `tools/call` response so the LLM can continue the conversation.
+#[allow(clippy::too_many_arguments)]
 pub async fn run_codex_tool_session(
     id: RequestId,
     initial_prompt: String,
@@ -63,6 +65,8 @@ pub async fn run_codex_tool_session(
     outgoing: Arc<OutgoingMessageSender>,
     thread_manager: Arc<ThreadManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    progress_token: Option<serde_json::Value>,
+    turn_diffs: Arc<Mutex<HashMap<ThreadId, String>>>,
 ) {
     let NewThread {
         thread_id,
@@ -139,10 +143,13 @@ pub async fn run_codex_tool_session(
         outgoing,
         id,
         running_requests_id_to_codex_uuid,
+        progress_token,
+        turn_diffs,
     )
     .await;
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run_codex_tool_session_reply(
     thread_id: ThreadId,
     thread: Arc<CodexThread>,
@@ -150,6 +157,8 @@ pub async fn run_codex_tool_session_reply(
     request_id: RequestId,
     prompt: String,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    progress_token: Option<serde_json::Value>,
+    turn_diffs: Arc<Mutex<HashMap<ThreadId, String>>>,
 ) {
     running_requests_id_to_codex_uuid
         .lock()
@@ -189,6 +198,8 @@ pub async fn run_codex_tool_session_reply(
         outgoing,
         request_id,
         running_requests_id_to_codex_uuid,
+        progress_token,
+        turn_diffs,
     )
     .await;
 }
@@ -199,8 +210,11 @@ async fn run_codex_tool_session_inner(
     outgoing: Arc<OutgoingMessageSender>,
     request_id: RequestId,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    progress_token: Option<serde_json::Value>,
+    turn_diffs: Arc<Mutex<HashMap<ThreadId, String>>>,
 ) {
     let request_id_str = request_id.to_string();
+    let mut progress = 0;
 
     // Stream events until the task needs to pause for user interaction or
     // completes.
@@ -217,6 +231,15 @@ async fn run_codex_tool_session_inner(
                     )
                     .await;
 
+                if let Some(progress_token) = progress_token.as_ref()
+                    && let Some(message) = progress_message(&event.msg)
+                {
+                    progress += 1;
+                    outgoing
+                        .send_progress_notification(progress_token, progress, message)
+                        .await;
+                }
+
                 match event.msg {
                     EventMsg::ExecApprovalRequest(ev) => {
                         let approval_id = ev.effective_approval_id();
@@ -315,6 +338,22 @@ async fn run_codex_tool_session_inner(
                             .remove(&request_id);
                         break;
                     }
+                    EventMsg::TurnAborted(_) => {
+                        let result = create_call_tool_result_with_thread_id(
+                            thread_id,
+                            "Turn interrupted.".to_string(),
+                            Some(true),
+                        );
+                        outgoing.send_response(request_id.clone(), result).await;
+                        running_requests_id_to_codex_uuid
+                            .lock()
+                            .await
+                            .remove(&request_id);
+                        break;
+                    }
+                    EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => {
+                        turn_diffs.lock().await.insert(thread_id, unified_diff);
+                    }
                     EventMsg::SessionConfigured(_) => {
                         tracing::error!("unexpected SessionConfigured event");
                     }
@@ -344,6 +383,8 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::AgentReasoningSectionBreak(_)
                     | EventMsg::McpToolCallBegin(_)
                     | EventMsg::McpToolCallEnd(_)
+                    | EventMsg::McpSamplingBegin(_)
+                    | EventMsg::McpSamplingEnd(_)
                     | EventMsg::McpListToolsResponse(_)
                     | EventMsg::ListSkillsResponse(_)
                     | EventMsg::RealtimeConversationListVoicesResponse(_)
@@ -356,12 +397,10 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::PatchApplyBegin(_)
                     | EventMsg::PatchApplyUpdated(_)
                     | EventMsg::PatchApplyEnd(_)
-                    | EventMsg::TurnDiff(_)
                     | EventMsg::WebSearchBegin(_)
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
                     | EventMsg::PlanUpdate(_)
-                    | EventMsg::TurnAborted(_)
                     | EventMsg::UserMessage(_)
                     | EventMsg::ShutdownComplete
                     | EventMsg::ViewImageToolCall(_)
@@ -377,6 +416,8 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::ReasoningContentDelta(_)
                     | EventMsg::ReasoningRawContentDelta(_)
                     | EventMsg::SkillsUpdateAvailable
+                    | EventMsg::McpPromptsChanged(_)
+                    | EventMsg::McpResourceUpdated(_)
                     | EventMsg::UndoStarted(_)
                     | EventMsg::UndoCompleted(_)
                     | EventMsg::ExitedReviewMode(_)
@@ -424,6 +465,23 @@ async fn run_codex_tool_session_inner(
     }
 }
 
+/// Short description of `msg` for `notifications/progress`, for the events
+/// that mark visible progress through a turn.
+fn progress_message(msg: &EventMsg) -> Option<String> {
+    match msg {
+        EventMsg::TurnStarted(_) => Some("Turn started".to_string()),
+        EventMsg::AgentMessage(event) => Some(event.message.clone()),
+        EventMsg::ExecCommandBegin(event) => Some(format!("Running `{}`", event.command.join(" "))),
+        EventMsg::PatchApplyBegin(_) => Some("Applying patch".to_string()),
+        EventMsg::McpToolCallBegin(event) => Some(format!(
+            "Calling {}/{}",
+            event.invocation.server, event.invocation.tool
+        )),
+        EventMsg::WebSearchBegin(_) => Some("Searching the web".to_string()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -26,6 +26,7 @@ use rmcp::model::JsonRpcNotification;
 use rmcp::model::JsonRpcRequest;
 use rmcp::model::JsonRpcResponse;
 use rmcp::model::RequestId;
+use rmcp::model::ResourcesCapability;
 use rmcp::model::ServerCapabilities;
 use rmcp::model::ToolsCapability;
 use serde_json::json;
@@ -37,6 +38,8 @@ use crate::codex_tool_config::CodexToolCallReplyParam;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_param;
 use crate::codex_tool_config::create_tool_for_codex_tool_call_reply_param;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::thread_tool_config::create_thread_tools;
+use crate::thread_tools::ThreadTools;
 
 pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
@@ -44,6 +47,8 @@ pub(crate) struct MessageProcessor {
     arg0_paths: Arg0DispatchPaths,
     thread_manager: Arc<ThreadManager>,
     running_requests_id_to_codex_uuid: Arc<Mutex<HashMap<RequestId, ThreadId>>>,
+    turn_diffs: Arc<Mutex<HashMap<ThreadId, String>>>,
+    thread_tools: Arc<ThreadTools>,
 }
 
 impl MessageProcessor {
@@ -73,12 +78,21 @@ impl MessageProcessor {
             environment_manager,
             /*analytics_events_client*/ None,
         ));
+        let turn_diffs = Arc::new(Mutex::new(HashMap::new()));
+        let thread_tools = Arc::new(ThreadTools::new(
+            config,
+            arg0_paths.clone(),
+            thread_manager.clone(),
+            turn_diffs.clone(),
+        ));
         Self {
             outgoing,
             initialized: false,
             arg0_paths,
             thread_manager,
             running_requests_id_to_codex_uuid: Arc::new(Mutex::new(HashMap::new())),
+            turn_diffs,
+            thread_tools,
         }
     }
 
@@ -94,13 +108,14 @@ impl MessageProcessor {
                 self.handle_ping(request_id).await;
             }
             ClientRequest::ListResourcesRequest(params) => {
-                self.handle_list_resources(params.params);
+                self.handle_list_resources(request_id, params.params);
             }
             ClientRequest::ListResourceTemplatesRequest(params) => {
-                self.handle_list_resource_templates(params.params);
+                self.handle_list_resource_templates(request_id, params.params)
+                    .await;
             }
             ClientRequest::ReadResourceRequest(params) => {
-                self.handle_read_resource(params.params);
+                self.handle_read_resource(request_id, params.params);
             }
             ClientRequest::SubscribeRequest(params) => {
                 self.handle_subscribe(params.params);
@@ -250,6 +265,7 @@ impl MessageProcessor {
                 tools: Some(ToolsCapability {
                     list_changed: Some(true),
                 }),
+                resources: Some(ResourcesCapability::default()),
                 ..Default::default()
             },
             instructions: None,
@@ -284,16 +300,78 @@ impl MessageProcessor {
         self.outgoing.send_response(id, json!({})).await;
     }
 
-    fn handle_list_resources(&self, params: Option<rmcp::model::PaginatedRequestParams>) {
+    fn handle_list_resources(
+        &self,
+        id: RequestId,
+        params: Option<rmcp::model::PaginatedRequestParams>,
+    ) {
         tracing::info!("resources/list -> params: {:?}", params);
+        let cursor = params.and_then(|params| params.cursor);
+        let outgoing = self.outgoing.clone();
+        let thread_tools = self.thread_tools.clone();
+
+        // Listing scans the rollout directory, so keep it off the message loop.
+        task::spawn(async move {
+            match thread_tools.list_resources(cursor.as_deref()).await {
+                Ok(result) => outgoing.send_response(id, result).await,
+                Err(err) => {
+                    outgoing
+                        .send_error(
+                            id,
+                            ErrorData::invalid_params(
+                                format!("failed to list threads: {err:#}"),
+                                None,
+                            ),
+                        )
+                        .await
+                }
+            }
+        });
     }
 
-    fn handle_list_resource_templates(&self, params: Option<rmcp::model::PaginatedRequestParams>) {
+    async fn handle_list_resource_templates(
+        &self,
+        id: RequestId,
+        params: Option<rmcp::model::PaginatedRequestParams>,
+    ) {
         tracing::info!("resources/templates/list -> params: {:?}", params);
+        self.outgoing
+            .send_response(id, ThreadTools::list_resource_templates())
+            .await;
     }
 
-    fn handle_read_resource(&self, params: rmcp::model::ReadResourceRequestParams) {
+    fn handle_read_resource(&self, id: RequestId, params: rmcp::model::ReadResourceRequestParams) {
         tracing::info!("resources/read -> params: {:?}", params);
+        let outgoing = self.outgoing.clone();
+        let thread_tools = self.thread_tools.clone();
+        task::spawn(async move {
+            let uri = params.uri;
+            match thread_tools.read_resource(&uri).await {
+                Ok(Some(result)) => outgoing.send_response(id, result).await,
+                Ok(None) => {
+                    outgoing
+                        .send_error(
+                            id,
+                            ErrorData::resource_not_found(
+                                format!("resource not found: {uri}"),
+                                Some(json!({ "uri": uri })),
+                            ),
+                        )
+                        .await
+                }
+                Err(err) => {
+                    outgoing
+                        .send_error(
+                            id,
+                            ErrorData::resource_not_found(
+                                format!("failed to read {uri}: {err:#}"),
+                                Some(json!({ "uri": uri })),
+                            ),
+                        )
+                        .await
+                }
+            }
+        });
     }
 
     fn handle_subscribe(&self, params: rmcp::model::SubscribeRequestParams) {
@@ -318,12 +396,14 @@ impl MessageProcessor {
         params: Option<rmcp::model::PaginatedRequestParams>,
     ) {
         tracing::trace!("tools/list -> {params:?}");
+        let mut tools = vec![
+            create_tool_for_codex_tool_call_param(),
+            create_tool_for_codex_tool_call_reply_param(),
+        ];
+        tools.extend(create_thread_tools());
         let result = rmcp::model::ListToolsResult {
             meta: None,
-            tools: vec![
-                create_tool_for_codex_tool_call_param(),
-                create_tool_for_codex_tool_call_reply_param(),
-            ],
+            tools,
             next_cursor: None,
         };
 
@@ -333,15 +413,32 @@ impl MessageProcessor {
     async fn handle_call_tool(&self, id: RequestId, params: CallToolRequestParams) {
         tracing::info!("tools/call -> params: {:?}", params);
         let CallToolRequestParams {
-            name, arguments, ..
+            meta,
+            name,
+            arguments,
+            ..
         } = params;
+        // Clients opt in to `notifications/progress` by sending a progress token.
+        let progress_token = meta.and_then(|meta| meta.0.get("progressToken").cloned());
 
         match name.as_ref() {
-            "codex" => self.handle_tool_call_codex(id, arguments).await,
+            "codex" => {
+                self.handle_tool_call_codex(id, arguments, progress_token)
+                    .await
+            }
             "codex-reply" => {
-                self.handle_tool_call_codex_session_reply(id, arguments)
+                self.handle_tool_call_codex_session_reply(id, arguments, progress_token)
                     .await
             }
+            name if ThreadTools::handles_tool(name) => {
+                let name = name.to_string();
+                let outgoing = self.outgoing.clone();
+                let thread_tools = self.thread_tools.clone();
+                task::spawn(async move {
+                    let result = thread_tools.call_tool(&name, arguments).await;
+                    outgoing.send_response(id, result).await;
+                });
+            }
             _ => {
                 let result = CallToolResult {
                     content: vec![rmcp::model::Content::text(format!("Unknown tool '{name}'"))],
@@ -358,6 +455,7 @@ impl MessageProcessor {
         &self,
         id: RequestId,
         arguments: Option<rmcp::model::JsonObject>,
+        progress_token: Option<serde_json::Value>,
     ) {
         let arguments = arguments.map(serde_json::Value::Object);
         let (initial_prompt, config): (String, Config) = match arguments {
@@ -408,6 +506,7 @@ impl MessageProcessor {
         let outgoing = self.outgoing.clone();
         let thread_manager = self.thread_manager.clone();
         let running_requests_id_to_codex_uuid = self.running_requests_id_to_codex_uuid.clone();
+        let turn_diffs = self.turn_diffs.clone();
 
         // Spawn an async task to handle the Codex session so that we do not
         // block the synchronous message-processing loop.
@@ -420,6 +519,8 @@ impl MessageProcessor {
                 outgoing,
                 thread_manager,
                 running_requests_id_to_codex_uuid,
+                progress_token,
+                turn_diffs,
             )
             .await;
         });
@@ -429,6 +530,7 @@ impl MessageProcessor {
         &self,
         request_id: RequestId,
         arguments: Option<rmcp::model::JsonObject>,
+        progress_token: Option<serde_json::Value>,
     ) {
         let arguments = arguments.map(serde_json::Value::Object);
         tracing::info!("tools/call -> params: {:?}", arguments);
@@ -488,6 +590,7 @@ impl MessageProcessor {
         // Clone outgoing to move into async task.
         let outgoing = self.outgoing.clone();
         let running_requests_id_to_codex_uuid = self.running_requests_id_to_codex_uuid.clone();
+        let turn_diffs = self.turn_diffs.clone();
 
         let codex = match self.thread_manager.get_thread(thread_id).await {
             Ok(c) => c,
@@ -517,6 +620,8 @@ impl MessageProcessor {
                     request_id,
                     prompt,
                     running_requests_id_to_codex_uuid,
+                    progress_token,
+                    turn_diffs,
                 )
                 .await;
             }
@@ -124,6 +124,25 @@ impl OutgoingMessageSender {
         .await;
     }
 
+    /// Sends `notifications/progress` for a request that supplied
+    /// `_meta.progressToken`.
+    pub(crate) async fn send_progress_notification(
+        &self,
+        progress_token: &Value,
+        progress: u64,
+        message: String,
+    ) {
+        self.send_notification(OutgoingNotification {
+            method: "notifications/progress".to_string(),
+            params: Some(serde_json::json!({
+                "progressToken": progress_token,
+                "progress": progress,
+                "message": message,
+            })),
+        })
+        .await;
+    }
+
     pub(crate) async fn send_notification(&self, notification: OutgoingNotification) {
         let outgoing_message = OutgoingMessage::Notification(notification);
         let _ = self.sender.send(outgoing_message);
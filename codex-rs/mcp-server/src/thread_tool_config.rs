@@ -0,0 +1,139 @@
+//! Parameters and tool definitions for the thread and turn tools that mirror
+//! the app-server `thread/*` and `turn/*` methods.
+
+use codex_protocol::ThreadId;
+use rmcp::model::Tool;
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::codex_tool_config::create_tool_input_schema;
+
+pub(crate) const THREAD_LIST_TOOL_NAME: &str = "codex-thread-list";
+pub(crate) const THREAD_READ_TOOL_NAME: &str = "codex-thread-read";
+pub(crate) const THREAD_FORK_TOOL_NAME: &str = "codex-thread-fork";
+pub(crate) const TURN_INTERRUPT_TOOL_NAME: &str = "codex-turn-interrupt";
+pub(crate) const THREAD_DIFF_TOOL_NAME: &str = "codex-thread-diff";
+pub(crate) const THREAD_GOAL_TOOL_NAME: &str = "codex-thread-goal";
+
+/// Arguments for `codex-thread-list`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadListToolParam {
+    /// Maximum number of threads to return. Defaults to 25.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// Opaque cursor returned as `nextCursor` by a previous call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Arguments for the tools that operate on a single thread.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadToolParam {
+    /// The thread id, as returned by `codex` or `codex-thread-list`.
+    pub thread_id: String,
+}
+
+impl ThreadToolParam {
+    pub(crate) fn thread_id(&self) -> anyhow::Result<ThreadId> {
+        Ok(ThreadId::from_string(&self.thread_id)?)
+    }
+}
+
+/// Builds the `Tool` definitions for every thread and turn tool.
+pub(crate) fn create_thread_tools() -> Vec<Tool> {
+    vec![
+        create_tool::<ThreadListToolParam>(
+            THREAD_LIST_TOOL_NAME,
+            "List Codex Threads",
+            "List recorded Codex threads, newest first. Each thread can also be read as a `codex://thread/{id}` resource.",
+        ),
+        create_tool::<ThreadToolParam>(
+            THREAD_READ_TOOL_NAME,
+            "Read Codex Thread",
+            "Read the user and agent messages recorded for a Codex thread.",
+        ),
+        create_tool::<ThreadToolParam>(
+            THREAD_FORK_TOOL_NAME,
+            "Fork Codex Thread",
+            "Start a new thread from the history of an existing one. Continue it with `codex-reply`.",
+        ),
+        create_tool::<ThreadToolParam>(
+            TURN_INTERRUPT_TOOL_NAME,
+            "Interrupt Codex Turn",
+            "Interrupt the turn currently running on a thread.",
+        ),
+        create_tool::<ThreadToolParam>(
+            THREAD_DIFF_TOOL_NAME,
+            "Codex Thread Diff",
+            "Return the unified diff of the changes made by the thread's latest turn. The thread must be loaded by this server.",
+        ),
+        create_tool::<ThreadToolParam>(
+            THREAD_GOAL_TOOL_NAME,
+            "Codex Thread Goal",
+            "Return the goal set on a thread, if any.",
+        ),
+    ]
+}
+
+fn create_tool<T: JsonSchema>(name: &'static str, title: &str, description: &'static str) -> Tool {
+    let schema = SchemaSettings::draft2019_09()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator()
+        .into_root_schema_for::<T>();
+
+    let input_schema = create_tool_input_schema(schema, "thread tool schema should serialize");
+
+    Tool {
+        name: name.into(),
+        title: Some(title.to_string()),
+        input_schema,
+        output_schema: None,
+        description: Some(description.into()),
+        annotations: None,
+        execution: None,
+        icons: None,
+        meta: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn verify_thread_read_tool_json_schema() {
+        let tools = create_thread_tools();
+        let tool = tools
+            .iter()
+            .find(|tool| tool.name == THREAD_READ_TOOL_NAME)
+            .expect("thread read tool should be defined");
+        let tool_json = serde_json::to_value(tool).expect("tool serializes");
+        let expected_tool_json = serde_json::json!({
+          "description": "Read the user and agent messages recorded for a Codex thread.",
+          "inputSchema": {
+            "properties": {
+              "threadId": {
+                "description": "The thread id, as returned by `codex` or `codex-thread-list`.",
+                "type": "string"
+              }
+            },
+            "required": [
+              "threadId"
+            ],
+            "type": "object"
+          },
+          "name": "codex-thread-read",
+          "title": "Read Codex Thread"
+        });
+        assert_eq!(expected_tool_json, tool_json);
+    }
+}
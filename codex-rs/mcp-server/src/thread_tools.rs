@@ -0,0 +1,525 @@
+//! Thread and turn tools that mirror the app-server `thread/*` and `turn/*`
+//! methods, plus the `codex://thread/{id}` resources built from the same
+//! recorded thread history.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use codex_arg0::Arg0DispatchPaths;
+use codex_core::Cursor;
+use codex_core::ForkSnapshot;
+use codex_core::NewThread;
+use codex_core::RolloutRecorder;
+use codex_core::SortDirection;
+use codex_core::ThreadManager;
+use codex_core::ThreadSortKey;
+use codex_core::ThreadsPage;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::find_thread_names_by_ids;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::parse_cursor;
+use codex_core::read_session_meta_line;
+use codex_protocol::ThreadId;
+use codex_protocol::models::PermissionProfile;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::RolloutItem;
+use rmcp::model::CallToolResult;
+use rmcp::model::Content;
+use rmcp::model::JsonObject;
+use rmcp::model::ListResourceTemplatesResult;
+use rmcp::model::ListResourcesResult;
+use rmcp::model::RawResource;
+use rmcp::model::RawResourceTemplate;
+use rmcp::model::ReadResourceResult;
+use rmcp::model::Resource;
+use rmcp::model::ResourceContents;
+use rmcp::model::ResourceTemplate;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use tokio::sync::Mutex;
+
+use crate::thread_tool_config::THREAD_DIFF_TOOL_NAME;
+use crate::thread_tool_config::THREAD_FORK_TOOL_NAME;
+use crate::thread_tool_config::THREAD_GOAL_TOOL_NAME;
+use crate::thread_tool_config::THREAD_LIST_TOOL_NAME;
+use crate::thread_tool_config::THREAD_READ_TOOL_NAME;
+use crate::thread_tool_config::TURN_INTERRUPT_TOOL_NAME;
+use crate::thread_tool_config::ThreadListToolParam;
+use crate::thread_tool_config::ThreadToolParam;
+
+pub(crate) const THREAD_RESOURCE_URI_PREFIX: &str = "codex://thread/";
+const THREAD_RESOURCE_MIME_TYPE: &str = "text/markdown";
+const DEFAULT_THREAD_LIST_LIMIT: u32 = 25;
+const MAX_THREAD_LIST_LIMIT: u32 = 100;
+
+/// Serves the thread tools and resources for the threads known to this server
+/// and the ones recorded under `CODEX_HOME`.
+pub(crate) struct ThreadTools {
+    config: Arc<Config>,
+    arg0_paths: Arg0DispatchPaths,
+    thread_manager: Arc<ThreadManager>,
+    /// Latest `TurnDiff` seen for each thread driven through `codex` or
+    /// `codex-reply`.
+    turn_diffs: Arc<Mutex<HashMap<ThreadId, String>>>,
+}
+
+/// Session settings a fork inherits from its source thread.
+#[derive(Debug, Default)]
+struct ForkSettings {
+    model: Option<String>,
+    model_provider: Option<String>,
+    approval_policy: Option<AskForApproval>,
+    permission_profile: Option<PermissionProfile>,
+    cwd: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ThreadMessage {
+    role: &'static str,
+    text: String,
+}
+
+impl ThreadTools {
+    pub(crate) fn new(
+        config: Arc<Config>,
+        arg0_paths: Arg0DispatchPaths,
+        thread_manager: Arc<ThreadManager>,
+        turn_diffs: Arc<Mutex<HashMap<ThreadId, String>>>,
+    ) -> Self {
+        Self {
+            config,
+            arg0_paths,
+            thread_manager,
+            turn_diffs,
+        }
+    }
+
+    pub(crate) fn handles_tool(name: &str) -> bool {
+        matches!(
+            name,
+            THREAD_LIST_TOOL_NAME
+                | THREAD_READ_TOOL_NAME
+                | THREAD_FORK_TOOL_NAME
+                | TURN_INTERRUPT_TOOL_NAME
+                | THREAD_DIFF_TOOL_NAME
+                | THREAD_GOAL_TOOL_NAME
+        )
+    }
+
+    /// Runs one of the thread tools and shapes the outcome as a `tools/call`
+    /// result, mirroring the JSON payload in `structured_content`.
+    pub(crate) async fn call_tool(
+        &self,
+        name: &str,
+        arguments: Option<JsonObject>,
+    ) -> CallToolResult {
+        match self.dispatch(name, arguments).await {
+            Ok(value) => CallToolResult {
+                content: vec![Content::text(value.to_string())],
+                structured_content: Some(value),
+                is_error: None,
+                meta: None,
+            },
+            Err(err) => CallToolResult {
+                content: vec![Content::text(format!("{name} failed: {err:#}"))],
+                structured_content: None,
+                is_error: Some(true),
+                meta: None,
+            },
+        }
+    }
+
+    async fn dispatch(
+        &self,
+        name: &str,
+        arguments: Option<JsonObject>,
+    ) -> anyhow::Result<serde_json::Value> {
+        if name == THREAD_LIST_TOOL_NAME {
+            return self.list_threads(parse_arguments(arguments)?).await;
+        }
+        let thread_id = parse_arguments::<ThreadToolParam>(arguments)?.thread_id()?;
+        match name {
+            THREAD_READ_TOOL_NAME => {
+                let messages = self.thread_messages(thread_id).await?;
+                Ok(json!({
+                    "threadId": thread_id,
+                    "uri": thread_resource_uri(thread_id),
+                    "messages": messages,
+                }))
+            }
+            THREAD_FORK_TOOL_NAME => self.fork_thread(thread_id).await,
+            TURN_INTERRUPT_TOOL_NAME => {
+                let thread = self
+                    .thread_manager
+                    .get_thread(thread_id)
+                    .await
+                    .with_context(|| format!("thread is not loaded: {thread_id}"))?;
+                thread.submit(Op::Interrupt).await?;
+                Ok(json!({ "threadId": thread_id }))
+            }
+            THREAD_DIFF_TOOL_NAME => {
+                // Diffs are only tracked for threads loaded in this process; a `null` diff means
+                // the loaded thread has not changed any files yet.
+                self.thread_manager
+                    .get_thread(thread_id)
+                    .await
+                    .with_context(|| format!("thread is not loaded: {thread_id}"))?;
+                let diff = self.turn_diffs.lock().await.get(&thread_id).cloned();
+                Ok(json!({ "threadId": thread_id, "diff": diff }))
+            }
+            THREAD_GOAL_TOOL_NAME => {
+                let thread = self
+                    .thread_manager
+                    .get_thread(thread_id)
+                    .await
+                    .with_context(|| format!("thread is not loaded: {thread_id}"))?;
+                let goal = thread.thread_goal().await?;
+                Ok(json!({ "threadId": thread_id, "goal": goal }))
+            }
+            _ => anyhow::bail!("unknown tool '{name}'"),
+        }
+    }
+
+    async fn list_threads(&self, params: ThreadListToolParam) -> anyhow::Result<serde_json::Value> {
+        let limit = params.limit.unwrap_or(DEFAULT_THREAD_LIST_LIMIT);
+        let page = self
+            .list_thread_page(limit, params.cursor.as_deref())
+            .await?;
+        let loaded: HashSet<ThreadId> = self
+            .thread_manager
+            .list_thread_ids()
+            .await
+            .into_iter()
+            .collect();
+        let names = self.thread_names(&page).await;
+        let threads = page
+            .items
+            .iter()
+            .filter_map(|item| {
+                let thread_id = item.thread_id?;
+                Some(json!({
+                    "threadId": thread_id,
+                    "uri": thread_resource_uri(thread_id),
+                    "name": names.get(&thread_id),
+                    "preview": item.first_user_message,
+                    "cwd": item.cwd,
+                    "createdAt": item.created_at,
+                    "updatedAt": item.updated_at,
+                    "loaded": loaded.contains(&thread_id),
+                }))
+            })
+            .collect::<Vec<_>>();
+        Ok(json!({
+            "threads": threads,
+            "nextCursor": page.next_cursor.as_ref().and_then(cursor_token),
+        }))
+    }
+
+    async fn fork_thread(&self, source_thread_id: ThreadId) -> anyhow::Result<serde_json::Value> {
+        let path = self.rollout_path(source_thread_id).await?;
+        let settings = self.fork_settings(source_thread_id, &path).await?;
+        let overrides = ConfigOverrides {
+            model: settings.model,
+            model_provider: settings.model_provider,
+            cwd: Some(settings.cwd),
+            approval_policy: settings.approval_policy,
+            permission_profile: settings.permission_profile,
+            codex_self_exe: self.arg0_paths.codex_self_exe.clone(),
+            codex_linux_sandbox_exe: self.arg0_paths.codex_linux_sandbox_exe.clone(),
+            main_execve_wrapper_exe: self.arg0_paths.main_execve_wrapper_exe.clone(),
+            ..Default::default()
+        };
+        let config =
+            Config::load_with_cli_overrides_and_harness_overrides(Vec::new(), overrides).await?;
+        let NewThread { thread_id, .. } = self
+            .thread_manager
+            .fork_thread(
+                ForkSnapshot::Interrupted,
+                config,
+                path,
+                /*persist_extended_history*/ false,
+                /*parent_trace*/ None,
+            )
+            .await?;
+        Ok(json!({
+            "threadId": thread_id,
+            "forkedFromId": source_thread_id,
+            "uri": thread_resource_uri(thread_id),
+        }))
+    }
+
+    /// Settings of a loaded thread come from its live config; otherwise they are read from the
+    /// last turn context recorded in its rollout.
+    async fn fork_settings(
+        &self,
+        thread_id: ThreadId,
+        path: &Path,
+    ) -> anyhow::Result<ForkSettings> {
+        if let Ok(thread) = self.thread_manager.get_thread(thread_id).await {
+            let snapshot = thread.config_snapshot().await;
+            return Ok(ForkSettings {
+                model: Some(snapshot.model),
+                model_provider: Some(snapshot.model_provider_id),
+                approval_policy: Some(snapshot.approval_policy),
+                permission_profile: Some(snapshot.permission_profile),
+                cwd: snapshot.cwd.into_path_buf(),
+            });
+        }
+        let meta = read_session_meta_line(path).await?.meta;
+        let history = RolloutRecorder::get_rollout_history(path).await?;
+        Ok(fork_settings_from_rollout(
+            meta.cwd,
+            meta.model_provider,
+            &history.get_rollout_items(),
+        ))
+    }
+
+    pub(crate) async fn list_resources(
+        &self,
+        cursor: Option<&str>,
+    ) -> anyhow::Result<ListResourcesResult> {
+        let page = self
+            .list_thread_page(DEFAULT_THREAD_LIST_LIMIT, cursor)
+            .await?;
+        let names = self.thread_names(&page).await;
+        let resources = page
+            .items
+            .iter()
+            .filter_map(|item| {
+                let thread_id = item.thread_id?;
+                let raw = RawResource {
+                    uri: thread_resource_uri(thread_id),
+                    name: thread_id.to_string(),
+                    title: names.get(&thread_id).cloned(),
+                    description: item.first_user_message.clone(),
+                    mime_type: Some(THREAD_RESOURCE_MIME_TYPE.to_string()),
+                    size: None,
+                    icons: None,
+                    meta: None,
+                };
+                Some(Resource::new(raw, None))
+            })
+            .collect();
+        Ok(ListResourcesResult {
+            resources,
+            next_cursor: page.next_cursor.as_ref().and_then(cursor_token),
+            meta: None,
+        })
+    }
+
+    pub(crate) fn list_resource_templates() -> ListResourceTemplatesResult {
+        let raw = RawResourceTemplate {
+            uri_template: format!("{THREAD_RESOURCE_URI_PREFIX}{{id}}"),
+            name: "codex-thread".to_string(),
+            title: Some("Codex Thread".to_string()),
+            description: Some("User and agent messages recorded for a Codex thread.".to_string()),
+            mime_type: Some(THREAD_RESOURCE_MIME_TYPE.to_string()),
+            icons: None,
+        };
+        ListResourceTemplatesResult {
+            resource_templates: vec![ResourceTemplate::new(raw, None)],
+            next_cursor: None,
+            meta: None,
+        }
+    }
+
+    /// Reads a `codex://thread/{id}` resource. Returns `Ok(None)` for URIs
+    /// that do not name a thread.
+    pub(crate) async fn read_resource(
+        &self,
+        uri: &str,
+    ) -> anyhow::Result<Option<ReadResourceResult>> {
+        let Some(thread_id) = uri
+            .strip_prefix(THREAD_RESOURCE_URI_PREFIX)
+            .and_then(|id| ThreadId::from_string(id).ok())
+        else {
+            return Ok(None);
+        };
+        let messages = self.thread_messages(thread_id).await?;
+        Ok(Some(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(THREAD_RESOURCE_MIME_TYPE.to_string()),
+                text: render_thread_transcript(thread_id, &messages),
+                meta: None,
+            }],
+        }))
+    }
+
+    async fn list_thread_page(
+        &self,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> anyhow::Result<ThreadsPage> {
+        let cursor = match cursor {
+            Some(token) => {
+                Some(parse_cursor(token).with_context(|| format!("invalid cursor: {token}"))?)
+            }
+            None => None,
+        };
+        let page = RolloutRecorder::list_threads(
+            self.config.as_ref(),
+            limit.clamp(1, MAX_THREAD_LIST_LIMIT) as usize,
+            cursor.as_ref(),
+            ThreadSortKey::UpdatedAt,
+            SortDirection::Desc,
+            /*allowed_sources*/ &[],
+            /*model_providers*/ None,
+            /*cwd_filters*/ None,
+            self.config.model_provider_id.as_str(),
+            /*search_term*/ None,
+        )
+        .await?;
+        Ok(page)
+    }
+
+    async fn thread_names(&self, page: &ThreadsPage) -> HashMap<ThreadId, String> {
+        let thread_ids = page
+            .items
+            .iter()
+            .filter_map(|item| item.thread_id)
+            .collect::<HashSet<_>>();
+        find_thread_names_by_ids(&self.config.codex_home, &thread_ids)
+            .await
+            .unwrap_or_default()
+    }
+
+    async fn thread_messages(&self, thread_id: ThreadId) -> anyhow::Result<Vec<ThreadMessage>> {
+        let path = self.rollout_path(thread_id).await?;
+        let history = RolloutRecorder::get_rollout_history(&path).await?;
+        Ok(thread_messages_from_rollout(&history.get_rollout_items()))
+    }
+
+    /// Locates the rollout for `thread_id`, flushing it first when the thread
+    /// is loaded so the file reflects the turns recorded so far.
+    async fn rollout_path(&self, thread_id: ThreadId) -> anyhow::Result<PathBuf> {
+        if let Ok(thread) = self.thread_manager.get_thread(thread_id).await {
+            let Some(path) = thread.rollout_path() else {
+                anyhow::bail!("thread {thread_id} is ephemeral and has no recorded history");
+            };
+            thread.flush_rollout().await?;
+            return Ok(path);
+        }
+        find_thread_path_by_id_str(&self.config.codex_home, &thread_id.to_string())
+            .await?
+            .with_context(|| format!("thread not found: {thread_id}"))
+    }
+}
+
+fn parse_arguments<T: DeserializeOwned>(arguments: Option<JsonObject>) -> anyhow::Result<T> {
+    serde_json::from_value(serde_json::Value::Object(arguments.unwrap_or_default()))
+        .context("invalid arguments")
+}
+
+fn thread_resource_uri(thread_id: ThreadId) -> String {
+    format!("{THREAD_RESOURCE_URI_PREFIX}{thread_id}")
+}
+
+fn cursor_token(cursor: &Cursor) -> Option<String> {
+    match serde_json::to_value(cursor) {
+        Ok(serde_json::Value::String(token)) => Some(token),
+        _ => None,
+    }
+}
+
+fn fork_settings_from_rollout(
+    cwd: PathBuf,
+    model_provider: Option<String>,
+    items: &[RolloutItem],
+) -> ForkSettings {
+    let Some(turn_context) = items.iter().rev().find_map(|item| match item {
+        RolloutItem::TurnContext(turn_context) => Some(turn_context),
+        _ => None,
+    }) else {
+        return ForkSettings {
+            model_provider,
+            cwd,
+            ..Default::default()
+        };
+    };
+    let permission_profile = turn_context.permission_profile.clone().unwrap_or_else(|| {
+        PermissionProfile::from_legacy_sandbox_policy_for_cwd(
+            &turn_context.sandbox_policy,
+            &turn_context.cwd,
+        )
+    });
+    ForkSettings {
+        model: Some(turn_context.model.clone()),
+        model_provider,
+        approval_policy: Some(turn_context.approval_policy),
+        permission_profile: Some(permission_profile),
+        cwd: turn_context.cwd.clone(),
+    }
+}
+
+fn thread_messages_from_rollout(items: &[RolloutItem]) -> Vec<ThreadMessage> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            RolloutItem::EventMsg(EventMsg::UserMessage(event)) => Some(ThreadMessage {
+                role: "user",
+                text: event.message.clone(),
+            }),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => Some(ThreadMessage {
+                role: "assistant",
+                text: event.message.clone(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn render_thread_transcript(thread_id: ThreadId, messages: &[ThreadMessage]) -> String {
+    let mut transcript = format!("# Codex thread {thread_id}\n");
+    for message in messages {
+        let heading = match message.role {
+            "user" => "User",
+            _ => "Codex",
+        };
+        transcript.push_str(&format!("\n## {heading}\n\n{}\n", message.text.trim_end()));
+    }
+    transcript
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn transcript_lists_user_and_agent_messages_in_order() {
+        let thread_id = ThreadId::new();
+        let items = vec![
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: "fix the build".to_string(),
+                images: None,
+                local_images: Vec::new(),
+                text_elements: Vec::new(),
+            })),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Fixed.\n".to_string(),
+                phase: None,
+                memory_citation: None,
+            })),
+        ];
+
+        let messages = thread_messages_from_rollout(&items);
+
+        assert_eq!(
+            render_thread_transcript(thread_id, &messages),
+            format!(
+                "# Codex thread {thread_id}\n\n## User\n\nfix the build\n\n## Codex\n\nFixed.\n"
+            )
+        );
+    }
+}
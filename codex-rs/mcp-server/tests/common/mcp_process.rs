@@ -176,6 +176,7 @@ impl McpProcess {
                     "tools": {
                         "listChanged": true
                     },
+                    "resources": {},
                 },
                 "serverInfo": {
                     "name": "codex-mcp-server",
@@ -219,6 +220,29 @@ impl McpProcess {
         .await
     }
 
+    /// Calls any tool by name. `meta` is sent as the request's `_meta`, e.g.
+    /// to supply a `progressToken`.
+    pub async fn send_tool_call(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Value,
+        meta: Option<serde_json::Value>,
+    ) -> anyhow::Result<i64> {
+        let mut params = json!({
+            "name": name,
+            "arguments": arguments,
+        });
+        if let Some(meta) = meta {
+            params["_meta"] = meta;
+        }
+        self.send_request("tools/call", Some(params)).await
+    }
+
+    pub async fn send_read_resource_request(&mut self, uri: &str) -> anyhow::Result<i64> {
+        self.send_request("resources/read", Some(json!({ "uri": uri })))
+            .await
+    }
+
     async fn send_request(
         &mut self,
         method: &str,
@@ -324,6 +348,33 @@ impl McpProcess {
         }
     }
 
+    pub async fn read_stream_until_notification_message(
+        &mut self,
+        method: &str,
+    ) -> anyhow::Result<JsonRpcNotification<CustomNotification>> {
+        eprintln!("in read_stream_until_notification_message({method})");
+
+        loop {
+            let message = self.read_jsonrpc_message().await?;
+            match message {
+                JsonRpcMessage::Notification(notification) => {
+                    if notification.notification.method == method {
+                        return Ok(notification);
+                    }
+                }
+                JsonRpcMessage::Request(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Request: {message:?}");
+                }
+                JsonRpcMessage::Error(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Error: {message:?}");
+                }
+                JsonRpcMessage::Response(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Response: {message:?}");
+                }
+            }
+        }
+    }
+
     /// Reads notifications until a legacy TurnComplete event is observed:
     /// Method "codex/event" with params.msg.type == "task_complete".
     pub async fn read_stream_until_legacy_task_complete_notification(
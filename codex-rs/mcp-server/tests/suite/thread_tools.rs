@@ -0,0 +1,256 @@
+use std::path::Path;
+
+use core_test_support::skip_if_no_network;
+use mcp_test_support::McpProcess;
+use mcp_test_support::create_final_assistant_message_sse_response;
+use mcp_test_support::create_mock_responses_server;
+use pretty_assertions::assert_eq;
+use pretty_assertions::assert_ne;
+use rmcp::model::RequestId;
+use serde_json::json;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_thread_tools_read_thread_started_by_codex_tool() {
+    skip_if_no_network!();
+
+    if let Err(err) = thread_tools_read_thread_started_by_codex_tool().await {
+        panic!("failure: {err}");
+    }
+}
+
+async fn thread_tools_read_thread_started_by_codex_tool() -> anyhow::Result<()> {
+    let server =
+        create_mock_responses_server(vec![create_final_assistant_message_sse_response("Enjoy!")?])
+            .await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+    let mut mcp_process = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp_process.initialize()).await??;
+
+    let codex_request_id = mcp_process
+        .send_tool_call(
+            "codex",
+            json!({ "prompt": "How are you?" }),
+            Some(json!({ "progressToken": "codex-progress" })),
+        )
+        .await?;
+    let progress = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_notification_message("notifications/progress"),
+    )
+    .await??;
+    let progress_params = progress
+        .notification
+        .params
+        .ok_or_else(|| anyhow::anyhow!("progress notification should include params"))?;
+    assert_eq!(progress_params["progressToken"], json!("codex-progress"));
+    assert_eq!(progress_params["progress"], json!(1));
+
+    let codex_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Number(codex_request_id)),
+    )
+    .await??;
+    let thread_id = codex_response.result["structuredContent"]["threadId"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("codex tool response should include a threadId"))?
+        .to_string();
+
+    let read_request_id = mcp_process
+        .send_tool_call(
+            "codex-thread-read",
+            json!({ "threadId": thread_id }),
+            /*meta*/ None,
+        )
+        .await?;
+    let read_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Number(read_request_id)),
+    )
+    .await??;
+    assert_eq!(
+        read_response.result["structuredContent"],
+        json!({
+            "threadId": thread_id,
+            "uri": format!("codex://thread/{thread_id}"),
+            "messages": [
+                { "role": "user", "text": "How are you?" },
+                { "role": "assistant", "text": "Enjoy!" },
+            ],
+        })
+    );
+
+    let resource_request_id = mcp_process
+        .send_read_resource_request(&format!("codex://thread/{thread_id}"))
+        .await?;
+    let resource_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Number(resource_request_id)),
+    )
+    .await??;
+    assert_eq!(
+        resource_response.result,
+        json!({
+            "contents": [{
+                "uri": format!("codex://thread/{thread_id}"),
+                "mimeType": "text/markdown",
+                "text": format!(
+                    "# Codex thread {thread_id}\n\n## User\n\nHow are you?\n\n## Codex\n\nEnjoy!\n"
+                ),
+            }],
+        })
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_thread_tools_fork_thread_started_by_codex_tool() {
+    skip_if_no_network!();
+
+    if let Err(err) = thread_tools_fork_thread_started_by_codex_tool().await {
+        panic!("failure: {err}");
+    }
+}
+
+async fn thread_tools_fork_thread_started_by_codex_tool() -> anyhow::Result<()> {
+    let server =
+        create_mock_responses_server(vec![create_final_assistant_message_sse_response("Enjoy!")?])
+            .await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+    let mut mcp_process = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp_process.initialize()).await??;
+    let thread_id = start_codex_thread(&mut mcp_process).await?;
+
+    let fork_request_id = mcp_process
+        .send_tool_call(
+            "codex-thread-fork",
+            json!({ "threadId": thread_id }),
+            /*meta*/ None,
+        )
+        .await?;
+    let fork_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Number(fork_request_id)),
+    )
+    .await??;
+    let fork = &fork_response.result["structuredContent"];
+    let fork_thread_id = fork["threadId"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("fork response should include a threadId"))?;
+    assert_ne!(fork_thread_id, thread_id);
+    assert_eq!(
+        fork,
+        &json!({
+            "threadId": fork_thread_id,
+            "forkedFromId": thread_id,
+            "uri": format!("codex://thread/{fork_thread_id}"),
+        })
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_thread_tools_interrupt_and_diff_require_a_loaded_thread() {
+    skip_if_no_network!();
+
+    if let Err(err) = thread_tools_interrupt_and_diff_require_a_loaded_thread().await {
+        panic!("failure: {err}");
+    }
+}
+
+async fn thread_tools_interrupt_and_diff_require_a_loaded_thread() -> anyhow::Result<()> {
+    let server =
+        create_mock_responses_server(vec![create_final_assistant_message_sse_response("Enjoy!")?])
+            .await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+    let mut mcp_process = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp_process.initialize()).await??;
+    let thread_id = start_codex_thread(&mut mcp_process).await?;
+
+    let interrupt_request_id = mcp_process
+        .send_tool_call(
+            "codex-turn-interrupt",
+            json!({ "threadId": thread_id }),
+            /*meta*/ None,
+        )
+        .await?;
+    let interrupt_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Number(interrupt_request_id)),
+    )
+    .await??;
+    assert_eq!(
+        interrupt_response.result["structuredContent"],
+        json!({ "threadId": thread_id })
+    );
+
+    let unknown_thread_id = "00000000-0000-0000-0000-000000000123";
+    for tool in ["codex-turn-interrupt", "codex-thread-diff"] {
+        let request_id = mcp_process
+            .send_tool_call(
+                tool,
+                json!({ "threadId": unknown_thread_id }),
+                /*meta*/ None,
+            )
+            .await?;
+        let response = timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp_process.read_stream_until_response_message(RequestId::Number(request_id)),
+        )
+        .await??;
+        assert_eq!(response.result["isError"], json!(true), "{tool}");
+    }
+
+    Ok(())
+}
+
+/// Run the `codex` tool to completion and return the id of the thread it started.
+async fn start_codex_thread(mcp_process: &mut McpProcess) -> anyhow::Result<String> {
+    let codex_request_id = mcp_process
+        .send_tool_call(
+            "codex",
+            json!({ "prompt": "How are you?" }),
+            /*meta*/ None,
+        )
+        .await?;
+    let codex_response = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp_process.read_stream_until_response_message(RequestId::Number(codex_request_id)),
+    )
+    .await??;
+    codex_response.result["structuredContent"]["threadId"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("codex tool response should include a threadId"))
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "responses"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
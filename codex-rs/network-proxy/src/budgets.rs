@@ -0,0 +1,562 @@
+//! Request-rate and byte budgets from `network.budgets`.
+//!
+//! Budgets are compiled into [`NetworkBudgets`] with the rest of the config, while usage is kept
+//! in a [`BudgetUsageTracker`] owned by `NetworkProxyState` so it survives config reloads.
+
+use crate::config::NetworkBudgetSettings;
+use crate::policy::compile_allowlist_globset;
+use crate::reasons::REASON_BYTE_QUOTA_EXCEEDED;
+use crate::reasons::REASON_RATE_LIMITED;
+use anyhow::Context;
+use anyhow::Result;
+use globset::GlobSet;
+use rama_core::bytes::Bytes;
+use rama_core::error::BoxError;
+use rama_core::extensions::Extensions;
+use rama_core::extensions::ExtensionsMut;
+use rama_core::extensions::ExtensionsRef;
+use rama_core::futures::stream::Stream;
+use rama_http::Body;
+use rama_http::BodyDataStream;
+use rama_http::Request;
+use rama_http::Response;
+use rama_net::stream::Socket;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+const THREAD_SCOPE: &str = "thread";
+
+/// Current usage of one budget, for status displays.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct NetworkBudgetUsage {
+    /// `thread` for the thread-wide budget, otherwise the host pattern of a `budgets.hosts` entry.
+    pub scope: String,
+    pub requests_last_minute: u32,
+    pub max_requests_per_minute: Option<u32>,
+    pub total_bytes: u64,
+    pub max_total_bytes: Option<u64>,
+}
+
+/// `network.budgets` compiled for matching against request hosts.
+#[derive(Clone, Debug, Default)]
+pub struct NetworkBudgets {
+    thread: BudgetLimits,
+    hosts: Vec<HostBudget>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct BudgetLimits {
+    max_requests_per_minute: Option<u32>,
+    max_total_bytes: Option<u64>,
+}
+
+#[derive(Clone, Debug)]
+struct HostBudget {
+    pattern: String,
+    host: GlobSet,
+    limits: BudgetLimits,
+}
+
+impl NetworkBudgets {
+    pub(crate) fn compile(settings: &NetworkBudgetSettings) -> Result<Self> {
+        let hosts = settings
+            .hosts
+            .iter()
+            .enumerate()
+            .map(|(index, budget)| {
+                let host = compile_allowlist_globset(std::slice::from_ref(&budget.host))
+                    .with_context(|| format!("invalid network.budgets.hosts[{index}]"))?;
+                Ok(HostBudget {
+                    pattern: budget.host.clone(),
+                    host,
+                    limits: BudgetLimits {
+                        max_requests_per_minute: budget.max_requests_per_minute,
+                        max_total_bytes: budget.max_total_bytes,
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            thread: BudgetLimits {
+                max_requests_per_minute: settings.max_requests_per_minute,
+                max_total_bytes: settings.max_total_bytes,
+            },
+            hosts,
+        })
+    }
+
+    /// The thread-wide budget followed by every host budget whose pattern matches `host`.
+    fn scopes_for_host<'a>(
+        &'a self,
+        host: &'a str,
+    ) -> impl Iterator<Item = (&'a str, BudgetLimits)> + 'a {
+        std::iter::once((THREAD_SCOPE, self.thread)).chain(
+            self.hosts
+                .iter()
+                .filter(move |budget| budget.host.is_match(host))
+                .map(|budget| (budget.pattern.as_str(), budget.limits)),
+        )
+    }
+
+    fn scopes(&self) -> impl Iterator<Item = (&str, BudgetLimits)> {
+        std::iter::once((THREAD_SCOPE, self.thread)).chain(
+            self.hosts
+                .iter()
+                .map(|budget| (budget.pattern.as_str(), budget.limits)),
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum BudgetDecision {
+    Allow(BudgetMeter),
+    Blocked {
+        reason: &'static str,
+        /// The exhausted budget, `thread` or a host pattern.
+        scope: String,
+    },
+}
+
+/// Adds transferred bytes to every budget a request was admitted under, and stops the transfer
+/// once one of those byte quotas is used up.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct BudgetMeter {
+    counters: Arc<[MeterCounter]>,
+}
+
+#[derive(Debug)]
+struct MeterCounter {
+    bytes: Arc<AtomicU64>,
+    max_total_bytes: Option<u64>,
+}
+
+impl PartialEq for BudgetMeter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.counters, &other.counters)
+    }
+}
+
+impl Eq for BudgetMeter {}
+
+impl BudgetMeter {
+    pub(crate) fn add(&self, bytes: usize) {
+        let bytes = u64::try_from(bytes).unwrap_or(u64::MAX);
+        for counter in self.counters.iter() {
+            counter.bytes.fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether any byte quota this meter counts against has been used up, by this transfer or by
+    /// others sharing the budget.
+    fn quota_exhausted(&self) -> bool {
+        self.counters.iter().any(|counter| {
+            counter
+                .max_total_bytes
+                .is_some_and(|max| counter.bytes.load(Ordering::Relaxed) >= max)
+        })
+    }
+
+    pub(crate) fn meter_request(&self, req: Request) -> Request {
+        let (parts, body) = req.into_parts();
+        Request::from_parts(parts, self.meter_body(body))
+    }
+
+    pub(crate) fn meter_response(&self, resp: Response) -> Response {
+        let (parts, body) = resp.into_parts();
+        Response::from_parts(parts, self.meter_body(body))
+    }
+
+    /// Count the bytes of `body` as it streams through.
+    fn meter_body(&self, body: Body) -> Body {
+        if self.counters.is_empty() {
+            return body;
+        }
+        Body::from_stream(MeteredBodyStream {
+            inner: Box::pin(body.into_data_stream()),
+            meter: self.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct BudgetCounter {
+    requests: VecDeque<Instant>,
+    bytes: Arc<AtomicU64>,
+}
+
+impl BudgetCounter {
+    fn requests_in_window(&mut self, now: Instant) -> usize {
+        while let Some(oldest) = self.requests.front()
+            && now.duration_since(*oldest) >= RATE_WINDOW
+        {
+            self.requests.pop_front();
+        }
+        self.requests.len()
+    }
+}
+
+/// Usage counters keyed by budget scope.
+#[derive(Debug, Default)]
+pub(crate) struct BudgetUsageTracker {
+    counters: Mutex<HashMap<String, BudgetCounter>>,
+}
+
+impl BudgetUsageTracker {
+    /// Check every budget that applies to `host` and, when all of them have room, count the
+    /// request against them. Datagrams (`count_request = false`) only consume byte budgets.
+    pub(crate) fn admit(
+        &self,
+        budgets: &NetworkBudgets,
+        host: &str,
+        count_request: bool,
+    ) -> BudgetDecision {
+        self.admit_at(budgets, host, count_request, Instant::now())
+    }
+
+    fn admit_at(
+        &self,
+        budgets: &NetworkBudgets,
+        host: &str,
+        count_request: bool,
+        now: Instant,
+    ) -> BudgetDecision {
+        let mut counters = self
+            .counters
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let scopes: Vec<(&str, BudgetLimits)> = budgets.scopes_for_host(host).collect();
+        for (scope, limits) in &scopes {
+            let counter = counters.entry((*scope).to_string()).or_default();
+            if let Some(max) = limits.max_total_bytes
+                && counter.bytes.load(Ordering::Relaxed) >= max
+            {
+                return BudgetDecision::Blocked {
+                    reason: REASON_BYTE_QUOTA_EXCEEDED,
+                    scope: (*scope).to_string(),
+                };
+            }
+            if count_request
+                && let Some(max) = limits.max_requests_per_minute
+                && counter.requests_in_window(now) >= usize::try_from(max).unwrap_or(usize::MAX)
+            {
+                return BudgetDecision::Blocked {
+                    reason: REASON_RATE_LIMITED,
+                    scope: (*scope).to_string(),
+                };
+            }
+        }
+
+        let mut meter_counters = Vec::with_capacity(scopes.len());
+        for (scope, limits) in &scopes {
+            let counter = counters.entry((*scope).to_string()).or_default();
+            if count_request {
+                counter.requests_in_window(now);
+                counter.requests.push_back(now);
+            }
+            meter_counters.push(MeterCounter {
+                bytes: counter.bytes.clone(),
+                max_total_bytes: limits.max_total_bytes,
+            });
+        }
+        BudgetDecision::Allow(BudgetMeter {
+            counters: Arc::from(meter_counters),
+        })
+    }
+
+    pub(crate) fn usage(&self, budgets: &NetworkBudgets) -> Vec<NetworkBudgetUsage> {
+        self.usage_at(budgets, Instant::now())
+    }
+
+    fn usage_at(&self, budgets: &NetworkBudgets, now: Instant) -> Vec<NetworkBudgetUsage> {
+        let mut counters = self
+            .counters
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        budgets
+            .scopes()
+            .map(|(scope, limits)| {
+                let (requests_last_minute, total_bytes) = match counters.get_mut(scope) {
+                    Some(counter) => (
+                        counter.requests_in_window(now),
+                        counter.bytes.load(Ordering::Relaxed),
+                    ),
+                    None => (0, 0),
+                };
+                NetworkBudgetUsage {
+                    scope: scope.to_string(),
+                    requests_last_minute: u32::try_from(requests_last_minute).unwrap_or(u32::MAX),
+                    max_requests_per_minute: limits.max_requests_per_minute,
+                    total_bytes,
+                    max_total_bytes: limits.max_total_bytes,
+                }
+            })
+            .collect()
+    }
+}
+
+struct MeteredBodyStream {
+    inner: Pin<Box<BodyDataStream>>,
+    meter: BudgetMeter,
+}
+
+impl Stream for MeteredBodyStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.meter.quota_exhausted() {
+            return Poll::Ready(Some(Err(Box::new(quota_exceeded_error()))));
+        }
+        let poll = this.inner.as_mut().poll_next(cx);
+        if let Poll::Ready(Some(Ok(bytes))) = &poll {
+            this.meter.add(bytes.len());
+        }
+        poll
+    }
+}
+
+fn quota_exceeded_error() -> io::Error {
+    io::Error::other(REASON_BYTE_QUOTA_EXCEEDED)
+}
+
+/// Wraps the upstream side of a tunnel and counts the bytes read from and written to it. Reads
+/// and writes fail once the tunnel's byte quota is used up.
+pub(crate) struct MeteredStream<S> {
+    inner: S,
+    meter: BudgetMeter,
+}
+
+impl<S> MeteredStream<S> {
+    pub(crate) fn new(inner: S, meter: BudgetMeter) -> Self {
+        Self { inner, meter }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for MeteredStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.meter.quota_exhausted() {
+            return Poll::Ready(Err(quota_exceeded_error()));
+        }
+        let before = buf.filled().len();
+        let poll = Pin::new(&mut this.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = &poll {
+            this.meter.add(buf.filled().len() - before);
+        }
+        poll
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for MeteredStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.meter.quota_exhausted() {
+            return Poll::Ready(Err(quota_exceeded_error()));
+        }
+        let poll = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = &poll {
+            this.meter.add(*written);
+        }
+        poll
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+impl<S: ExtensionsRef> ExtensionsRef for MeteredStream<S> {
+    fn extensions(&self) -> &Extensions {
+        self.inner.extensions()
+    }
+}
+
+impl<S: ExtensionsMut> ExtensionsMut for MeteredStream<S> {
+    fn extensions_mut(&mut self) -> &mut Extensions {
+        self.inner.extensions_mut()
+    }
+}
+
+impl<S: Socket> Socket for MeteredStream<S> {
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.inner.peer_addr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NetworkHostBudget;
+    use pretty_assertions::assert_eq;
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncWriteExt;
+
+    fn budgets() -> NetworkBudgets {
+        NetworkBudgets::compile(&NetworkBudgetSettings {
+            max_requests_per_minute: None,
+            max_total_bytes: Some(1_000),
+            hosts: vec![NetworkHostBudget {
+                host: "*.internal.example".to_string(),
+                max_requests_per_minute: Some(2),
+                max_total_bytes: None,
+            }],
+        })
+        .unwrap()
+    }
+
+    fn blocked(reason: &'static str, scope: &str) -> BudgetDecision {
+        BudgetDecision::Blocked {
+            reason,
+            scope: scope.to_string(),
+        }
+    }
+
+    #[test]
+    fn host_rate_limit_applies_to_matching_hosts_within_window() {
+        let budgets = budgets();
+        let tracker = BudgetUsageTracker::default();
+        let start = Instant::now();
+
+        for _ in 0..2 {
+            assert!(matches!(
+                tracker.admit_at(
+                    &budgets,
+                    "api.internal.example",
+                    /*count_request*/ true,
+                    start
+                ),
+                BudgetDecision::Allow(_)
+            ));
+        }
+        assert_eq!(
+            tracker.admit_at(
+                &budgets,
+                "db.internal.example",
+                /*count_request*/ true,
+                start
+            ),
+            blocked(REASON_RATE_LIMITED, "*.internal.example")
+        );
+        assert!(matches!(
+            tracker.admit_at(&budgets, "example.com", /*count_request*/ true, start),
+            BudgetDecision::Allow(_)
+        ));
+        assert!(matches!(
+            tracker.admit_at(
+                &budgets,
+                "api.internal.example",
+                /*count_request*/ true,
+                start + RATE_WINDOW
+            ),
+            BudgetDecision::Allow(_)
+        ));
+    }
+
+    #[test]
+    fn byte_quota_blocks_once_exhausted() {
+        let budgets = budgets();
+        let tracker = BudgetUsageTracker::default();
+        let start = Instant::now();
+
+        let BudgetDecision::Allow(meter) =
+            tracker.admit_at(&budgets, "example.com", /*count_request*/ true, start)
+        else {
+            panic!("first request should be admitted");
+        };
+        meter.add(1_200);
+
+        assert_eq!(
+            tracker.admit_at(&budgets, "example.com", /*count_request*/ false, start),
+            blocked(REASON_BYTE_QUOTA_EXCEEDED, "thread")
+        );
+        assert_eq!(
+            tracker.usage_at(&budgets, start),
+            vec![
+                NetworkBudgetUsage {
+                    scope: "thread".to_string(),
+                    requests_last_minute: 1,
+                    max_requests_per_minute: None,
+                    total_bytes: 1_200,
+                    max_total_bytes: Some(1_000),
+                },
+                NetworkBudgetUsage {
+                    scope: "*.internal.example".to_string(),
+                    requests_last_minute: 0,
+                    max_requests_per_minute: Some(2),
+                    total_bytes: 0,
+                    max_total_bytes: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn metered_streams_stop_once_the_byte_quota_is_exhausted() {
+        let budgets = budgets();
+        let tracker = BudgetUsageTracker::default();
+        let BudgetDecision::Allow(meter) =
+            tracker.admit(&budgets, "example.com", /*count_request*/ true)
+        else {
+            panic!("request should be admitted");
+        };
+        let (client, mut upstream) = tokio::io::duplex(/*max_buf_size*/ 4_096);
+        let mut stream = MeteredStream::new(client, meter);
+
+        stream
+            .write_all(&[0; 1_200])
+            .await
+            .expect("quota has room when the write starts");
+        upstream
+            .write_all(b"reply")
+            .await
+            .expect("upstream should write");
+
+        let err = stream
+            .write_all(b"more")
+            .await
+            .expect_err("writes should stop once the quota is used up");
+        assert_eq!(err.to_string(), REASON_BYTE_QUOTA_EXCEEDED);
+        let err = stream
+            .read(&mut [0; 16])
+            .await
+            .expect_err("reads should stop once the quota is used up");
+        assert_eq!(err.to_string(), REASON_BYTE_QUOTA_EXCEEDED);
+        assert_eq!(
+            tracker.usage(&budgets)[0].total_bytes,
+            1_200,
+            "bytes refused after the quota ran out are not counted"
+        );
+    }
+}
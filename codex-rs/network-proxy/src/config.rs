@@ -114,6 +114,106 @@ pub struct NetworkUnixSocketPermissions {
     pub entries: BTreeMap<String, NetworkUnixSocketPermission>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkRequestRuleAction {
+    Allow,
+    Deny,
+}
+
+/// A request-level rule evaluated after the host passes `domains`.
+///
+/// Matching deny rules always win. Once a host matches any allow rule, requests to it must match
+/// one of those allow rules; a matching allow rule also lifts the limited-mode method restriction.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkRequestRule {
+    pub action: NetworkRequestRuleAction,
+    /// URL scheme such as `http` or `https`; matches any scheme when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheme: Option<String>,
+    /// Host pattern using the same syntax as `domains`; `*` matches every host.
+    pub host: String,
+    /// Path prefix (`/repos`) or glob (`/repos/our-org/**`, `**/graphql`); matches any path when
+    /// unset. Prefixes match whole path segments, `*` stays within one segment and `**` spans
+    /// segments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// HTTP methods the rule applies to; matches any method when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<String>,
+}
+
+const DEFAULT_HAR_MAX_BODY_BYTES: usize = 64 * 1024;
+const DEFAULT_SECRET_GUARD_MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Optional HAR recording of proxied requests, written per thread under `CODEX_HOME`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct NetworkHarSettings {
+    pub enabled: bool,
+    /// Request and response bodies are captured up to this many bytes when `mitm` is enabled.
+    pub max_body_bytes: usize,
+}
+
+impl Default for NetworkHarSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_body_bytes: DEFAULT_HAR_MAX_BODY_BYTES,
+        }
+    }
+}
+
+/// Blocks readable outbound requests (plain HTTP and MITM-inspected HTTPS) that carry secrets to
+/// hosts outside `allowed_hosts`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct NetworkSecretGuardSettings {
+    pub enabled: bool,
+    /// Hosts that may receive secrets, using the same pattern syntax as `domains`.
+    pub allowed_hosts: Vec<String>,
+    /// Also block long random-looking tokens that match no known secret or credential format.
+    pub detect_high_entropy: bool,
+    /// Request bodies are scanned up to this many bytes; larger bodies are blocked.
+    pub max_body_bytes: usize,
+}
+
+impl Default for NetworkSecretGuardSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed_hosts: Vec::new(),
+            detect_high_entropy: true,
+            max_body_bytes: DEFAULT_SECRET_GUARD_MAX_BODY_BYTES,
+        }
+    }
+}
+
+/// Request-rate and byte budgets for the thread as a whole and for individual host patterns.
+/// Once a budget is exhausted, new requests that fall under it are blocked, and bodies or tunnels
+/// still streaming under an exhausted byte quota are cut off.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct NetworkBudgetSettings {
+    /// Requests (including HTTPS and SOCKS5 tunnels) allowed in any 60-second window across all
+    /// hosts.
+    pub max_requests_per_minute: Option<u32>,
+    /// Request plus response bytes allowed across all hosts for the lifetime of the proxy.
+    pub max_total_bytes: Option<u64>,
+    /// Budgets shared by all hosts matching a pattern. A request must fit every matching budget.
+    pub hosts: Vec<NetworkHostBudget>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkHostBudget {
+    /// Host pattern using the same syntax as `domains`; `*` matches every host.
+    pub host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_requests_per_minute: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_bytes: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(default)]
 pub struct NetworkProxySettings {
@@ -136,9 +236,17 @@ pub struct NetworkProxySettings {
     pub domains: Option<NetworkDomainPermissions>,
     #[serde(default)]
     pub unix_sockets: Option<NetworkUnixSocketPermissions>,
+    #[serde(default)]
+    pub rules: Vec<NetworkRequestRule>,
     pub allow_local_binding: bool,
     #[serde(default)]
     pub mitm: bool,
+    #[serde(default)]
+    pub har: NetworkHarSettings,
+    #[serde(default)]
+    pub secret_guard: NetworkSecretGuardSettings,
+    #[serde(default)]
+    pub budgets: NetworkBudgetSettings,
 }
 
 impl Default for NetworkProxySettings {
@@ -155,8 +263,12 @@ impl Default for NetworkProxySettings {
             mode: NetworkMode::default(),
             domains: None,
             unix_sockets: None,
+            rules: Vec::new(),
             allow_local_binding: false,
             mitm: false,
+            har: NetworkHarSettings::default(),
+            secret_guard: NetworkSecretGuardSettings::default(),
+            budgets: NetworkBudgetSettings::default(),
         }
     }
 }
@@ -586,8 +698,24 @@ mod tests {
                 mode: NetworkMode::Full,
                 domains: None,
                 unix_sockets: None,
+                rules: Vec::new(),
                 allow_local_binding: false,
                 mitm: false,
+                har: NetworkHarSettings {
+                    enabled: false,
+                    max_body_bytes: 64 * 1024,
+                },
+                secret_guard: NetworkSecretGuardSettings {
+                    enabled: false,
+                    allowed_hosts: Vec::new(),
+                    detect_high_entropy: true,
+                    max_body_bytes: 1024 * 1024,
+                },
+                budgets: NetworkBudgetSettings {
+                    max_requests_per_minute: None,
+                    max_total_bytes: None,
+                    hosts: Vec::new(),
+                },
             }
         );
     }
@@ -650,8 +778,24 @@ mod tests {
                         "example.com": "deny",
                     },
                     "unix_sockets": null,
+                    "rules": [],
                     "allow_local_binding": false,
                     "mitm": false,
+                    "har": {
+                        "enabled": false,
+                        "max_body_bytes": 65536,
+                    },
+                    "secret_guard": {
+                        "enabled": false,
+                        "allowed_hosts": [],
+                        "detect_high_entropy": true,
+                        "max_body_bytes": 1048576,
+                    },
+                    "budgets": {
+                        "max_requests_per_minute": null,
+                        "max_total_bytes": null,
+                        "hosts": [],
+                    },
                 }
             })
         );
@@ -0,0 +1,682 @@
+//! Optional per-thread HAR recording of proxied requests.
+//!
+//! Entries are appended as JSON lines to `$CODEX_HOME/proxy/har/<thread-id>.jsonl` as requests
+//! complete, and [`read_har_log`] assembles them into a HAR 1.2 document for viewing or export.
+//! URLs, headers and captured bodies are passed through the `codex-secrets` sanitizer before they
+//! are written.
+
+use crate::state::NetworkProxyState;
+use anyhow::Context as _;
+use anyhow::Result;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_secrets::redact_secrets;
+use codex_utils_home_dir::find_codex_home;
+use rama_core::bytes::Bytes;
+use rama_core::error::BoxError;
+use rama_core::futures::stream::Stream;
+use rama_http::Body;
+use rama_http::BodyDataStream;
+use rama_http::HeaderMap;
+use rama_http::Method;
+use rama_http::Request;
+use rama_http::Response;
+use rama_http::header::CONTENT_TYPE;
+use serde::Deserialize;
+use serde::Serialize;
+use std::io::BufRead as _;
+use std::io::BufReader;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::mpsc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+use tracing::warn;
+use url::Url;
+
+const HAR_LOG_DIR: &str = "proxy/har";
+const HAR_LOG_EXTENSION: &str = "jsonl";
+const DEFAULT_THREAD_ID: &str = "default";
+const REDACTED: &str = "[REDACTED_SECRET]";
+const CREDENTIAL_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "api-key",
+];
+
+/// Path of the HAR log for `thread_id` under `codex_home`.
+pub fn har_log_path(codex_home: &Path, thread_id: &str) -> PathBuf {
+    let file_stem: String = thread_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    codex_home
+        .join(HAR_LOG_DIR)
+        .join(format!("{file_stem}.{HAR_LOG_EXTENSION}"))
+}
+
+/// Thread ids that have a HAR log under `codex_home`, most recently written first.
+pub fn list_har_logs(codex_home: &Path) -> Result<Vec<String>> {
+    let dir = codex_home.join(HAR_LOG_DIR);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("failed to read {}", dir.display())),
+    };
+    let mut logs = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(HAR_LOG_EXTENSION) {
+            continue;
+        }
+        let Some(thread_id) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let modified = std::fs::metadata(&path).and_then(|metadata| metadata.modified())?;
+        logs.push((modified, thread_id.to_string()));
+    }
+    logs.sort_by(|left, right| right.cmp(left));
+    Ok(logs.into_iter().map(|(_, thread_id)| thread_id).collect())
+}
+
+/// Read a HAR log written by the proxy. Lines that fail to parse are skipped.
+pub fn read_har_log(path: &Path) -> Result<HarLog> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut entries = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<HarEntry>(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(err) => warn!(
+                "skipping malformed HAR entry at {}:{}: {err}",
+                path.display(),
+                index + 1
+            ),
+        }
+    }
+    Ok(HarLog::new(entries))
+}
+
+/// A HAR 1.2 document.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HarLog {
+    pub log: HarLogBody,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HarLogBody {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+impl HarLog {
+    fn new(entries: Vec<HarEntry>) -> Self {
+        Self {
+            log: HarLogBody {
+                version: "1.2".to_string(),
+                creator: HarCreator {
+                    name: "codex-network-proxy".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                },
+                entries,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: String,
+    /// Total elapsed time in milliseconds.
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    #[serde(default)]
+    pub cache: HarCache,
+    pub timings: HarTimings,
+    /// Which proxy path handled the request: `http`, `https-connect` or `https` (MITM).
+    #[serde(rename = "_protocol")]
+    pub protocol: String,
+    /// `reasons.rs` code when the proxy blocked the request.
+    #[serde(
+        rename = "_blockReason",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub block_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub headers: Vec<HarNameValue>,
+    pub query_string: Vec<HarNameValue>,
+    pub cookies: Vec<HarNameValue>,
+    pub headers_size: i64,
+    pub body_size: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: String,
+    pub headers: Vec<HarNameValue>,
+    pub cookies: Vec<HarNameValue>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HarNameValue {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    pub mime_type: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    pub mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HarCache {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HarTimings {
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+}
+
+/// Response extension carrying the `reasons.rs` code of a proxy-generated block response.
+#[derive(Debug, Clone)]
+pub(crate) struct HarBlockReason(pub String);
+
+/// Appends entries to one thread's HAR log. Entries are handed to a writer thread, started on
+/// the first entry, so recording never blocks the proxied stream.
+#[derive(Debug)]
+pub(crate) struct HarRecorder {
+    /// `None` when CODEX_HOME could not be resolved; entries are then dropped.
+    path: Option<PathBuf>,
+    writer: OnceLock<Option<mpsc::Sender<HarWrite>>>,
+}
+
+#[derive(Debug)]
+enum HarWrite {
+    Entry(String),
+    #[cfg(test)]
+    Flush(mpsc::Sender<()>),
+}
+
+impl HarRecorder {
+    pub(crate) fn new(thread_id: Option<&str>) -> Self {
+        let thread_id = thread_id.unwrap_or(DEFAULT_THREAD_ID);
+        let path = match find_codex_home() {
+            Ok(codex_home) => Some(har_log_path(&codex_home, thread_id)),
+            Err(err) => {
+                warn!("failed to resolve CODEX_HOME for the HAR log: {err}");
+                None
+            }
+        };
+        Self {
+            path,
+            writer: OnceLock::new(),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_codex_home(thread_id: &str, codex_home: PathBuf) -> Self {
+        Self {
+            path: Some(har_log_path(&codex_home, thread_id)),
+            writer: OnceLock::new(),
+        }
+    }
+
+    fn append(&self, entry: &HarEntry) -> Result<()> {
+        let Some(path) = &self.path else {
+            anyhow::bail!("CODEX_HOME could not be resolved");
+        };
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        let writer = self
+            .writer
+            .get_or_init(|| spawn_writer(path.clone()))
+            .as_ref()
+            .context("HAR writer thread is not running")?;
+        writer
+            .send(HarWrite::Entry(line))
+            .map_err(|_| anyhow::anyhow!("HAR writer thread has stopped"))
+    }
+
+    /// Waits until every entry recorded so far has been written.
+    #[cfg(test)]
+    pub(crate) fn flush(&self) {
+        let Some(Some(writer)) = self.writer.get() else {
+            return;
+        };
+        let (done, wait) = mpsc::channel();
+        if writer.send(HarWrite::Flush(done)).is_ok() {
+            let _ = wait.recv();
+        }
+    }
+}
+
+fn spawn_writer(path: PathBuf) -> Option<mpsc::Sender<HarWrite>> {
+    let (sender, receiver) = mpsc::channel();
+    let spawned = std::thread::Builder::new()
+        .name("codex-har-writer".to_string())
+        .spawn(move || {
+            for write in receiver {
+                match write {
+                    HarWrite::Entry(line) => {
+                        if let Err(err) = append_line(&path, &line) {
+                            warn!("failed to record HAR entry: {err:#}");
+                        }
+                    }
+                    #[cfg(test)]
+                    HarWrite::Flush(done) => {
+                        let _ = done.send(());
+                    }
+                }
+            }
+        });
+    match spawned {
+        Ok(_) => Some(sender),
+        Err(err) => {
+            warn!("failed to start the HAR writer thread: {err}");
+            None
+        }
+    }
+}
+
+fn append_line(path: &Path, line: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.create(true).append(true);
+    // Logs hold request URLs and bodies, so keep them private to the user.
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    file.write_all(line.as_bytes())
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// An in-flight request being recorded. Created by [`HarExchange::begin`] and completed by
+/// [`HarExchange::finish`], which writes the entry once the response body has been sent.
+pub(crate) struct HarExchange {
+    recorder: Arc<HarRecorder>,
+    protocol: &'static str,
+    started_date_time: String,
+    started: Instant,
+    method: String,
+    url: String,
+    http_version: String,
+    request_headers: HeaderMap,
+    request_body: Arc<BodyCapture>,
+}
+
+impl HarExchange {
+    /// Start recording `req` when HAR recording is enabled. Bodies are only captured when
+    /// `capture_bodies` is set (MITM), up to the configured size cap; otherwise only counted.
+    pub(crate) async fn begin(
+        app_state: &NetworkProxyState,
+        req: Request,
+        url: String,
+        protocol: &'static str,
+        capture_bodies: bool,
+    ) -> (Option<Self>, Request) {
+        let (recorder, max_body_bytes) = match app_state.har_recorder().await {
+            Ok(Some(recorder)) => recorder,
+            Ok(None) => return (None, req),
+            Err(err) => {
+                warn!("failed to read HAR settings: {err}");
+                return (None, req);
+            }
+        };
+        let capture_limit = if capture_bodies { max_body_bytes } else { 0 };
+        let request_body = Arc::new(BodyCapture::new(capture_limit));
+        let (parts, body) = req.into_parts();
+        let exchange = Self {
+            recorder,
+            protocol,
+            started_date_time: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            started: Instant::now(),
+            method: parts.method.as_str().to_string(),
+            url,
+            http_version: format!("{:?}", parts.version),
+            request_headers: parts.headers.clone(),
+            request_body: request_body.clone(),
+        };
+        // CONNECT bodies are the tunnel itself, which is handed off through the upgrade.
+        let body = if parts.method == Method::CONNECT {
+            body
+        } else {
+            Body::from_stream(CaptureStream {
+                inner: Box::pin(body.into_data_stream()),
+                capture: request_body,
+                on_end: None,
+            })
+        };
+        (Some(exchange), Request::from_parts(parts, body))
+    }
+
+    pub(crate) fn finish(exchange: Option<Self>, resp: Response) -> Response {
+        let Some(exchange) = exchange else {
+            return resp;
+        };
+        let wait = exchange.started.elapsed();
+        let (parts, body) = resp.into_parts();
+        let block_reason = parts
+            .extensions
+            .get::<HarBlockReason>()
+            .map(|reason| reason.0.clone());
+        let response_body = Arc::new(BodyCapture::new(exchange.request_body.limit));
+        let is_connect = exchange.method == Method::CONNECT.as_str();
+        let pending = PendingEntry {
+            exchange,
+            status: parts.status.as_u16(),
+            status_text: parts
+                .status
+                .canonical_reason()
+                .unwrap_or_default()
+                .to_string(),
+            http_version: format!("{:?}", parts.version),
+            response_headers: parts.headers.clone(),
+            response_body: response_body.clone(),
+            block_reason,
+            wait,
+        };
+        // A CONNECT response has no body of its own, so record it as soon as it is answered.
+        if is_connect {
+            pending.record();
+            return Response::from_parts(parts, body);
+        }
+        let body = Body::from_stream(CaptureStream {
+            inner: Box::pin(body.into_data_stream()),
+            capture: response_body,
+            on_end: Some(Box::new(move || pending.record())),
+        });
+        Response::from_parts(parts, body)
+    }
+}
+
+struct PendingEntry {
+    exchange: HarExchange,
+    status: u16,
+    status_text: String,
+    http_version: String,
+    response_headers: HeaderMap,
+    response_body: Arc<BodyCapture>,
+    block_reason: Option<String>,
+    wait: Duration,
+}
+
+impl PendingEntry {
+    fn record(self) {
+        let (entry, recorder) = self.into_entry();
+        if let Err(err) = recorder.append(&entry) {
+            warn!("failed to record HAR entry: {err:#}");
+        }
+    }
+
+    fn into_entry(self) -> (HarEntry, Arc<HarRecorder>) {
+        let total = self.exchange.started.elapsed();
+        let url = redact_secrets(self.exchange.url);
+        let query_string = Url::parse(&url)
+            .map(|url| {
+                url.query_pairs()
+                    .map(|(name, value)| HarNameValue {
+                        name: name.into_owned(),
+                        value: value.into_owned(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let request_body = self.exchange.request_body.snapshot();
+        let response_body = self.response_body.snapshot();
+        let request_mime_type = content_type(&self.exchange.request_headers);
+        let response_mime_type = content_type(&self.response_headers);
+        let entry = HarEntry {
+            started_date_time: self.exchange.started_date_time,
+            time: millis(total),
+            request: HarRequest {
+                method: self.exchange.method,
+                url,
+                http_version: self.exchange.http_version,
+                headers: redacted_headers(&self.exchange.request_headers),
+                query_string,
+                cookies: Vec::new(),
+                headers_size: -1,
+                body_size: request_body.size,
+                post_data: request_body.text.map(|text| HarPostData {
+                    mime_type: request_mime_type,
+                    text,
+                    comment: request_body.comment,
+                }),
+            },
+            response: HarResponse {
+                status: self.status,
+                status_text: self.status_text,
+                http_version: self.http_version,
+                headers: redacted_headers(&self.response_headers),
+                cookies: Vec::new(),
+                content: HarContent {
+                    size: response_body.size,
+                    mime_type: response_mime_type,
+                    text: response_body.text,
+                    comment: response_body.comment,
+                },
+                redirect_url: self
+                    .response_headers
+                    .get("location")
+                    .and_then(|value| value.to_str().ok())
+                    .map(|value| redact_secrets(value.to_string()))
+                    .unwrap_or_default(),
+                headers_size: -1,
+                body_size: response_body.size,
+            },
+            cache: HarCache::default(),
+            timings: HarTimings {
+                send: 0.0,
+                wait: millis(self.wait),
+                receive: millis(total.saturating_sub(self.wait)),
+            },
+            protocol: self.exchange.protocol.to_string(),
+            block_reason: self.block_reason,
+        };
+        (entry, self.exchange.recorder)
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn content_type(headers: &HeaderMap) -> String {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn redacted_headers(headers: &HeaderMap) -> Vec<HarNameValue> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let name = name.as_str().to_string();
+            let value = if CREDENTIAL_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                redact_secrets(String::from_utf8_lossy(value.as_bytes()).into_owned())
+            };
+            HarNameValue { name, value }
+        })
+        .collect()
+}
+
+/// Byte count and optional capped copy of a body as it streams through the proxy.
+#[derive(Debug)]
+struct BodyCapture {
+    limit: usize,
+    state: Mutex<BodyCaptureState>,
+}
+
+#[derive(Debug, Default)]
+struct BodyCaptureState {
+    size: u64,
+    captured: Vec<u8>,
+}
+
+struct BodySnapshot {
+    size: i64,
+    text: Option<String>,
+    comment: Option<String>,
+}
+
+impl BodyCapture {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            state: Mutex::new(BodyCaptureState::default()),
+        }
+    }
+
+    fn observe(&self, bytes: &[u8]) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.size = state.size.saturating_add(bytes.len() as u64);
+        let remaining = self.limit.saturating_sub(state.captured.len());
+        let take = remaining.min(bytes.len());
+        state.captured.extend_from_slice(&bytes[..take]);
+    }
+
+    fn snapshot(&self) -> BodySnapshot {
+        let Ok(state) = self.state.lock() else {
+            return BodySnapshot {
+                size: -1,
+                text: None,
+                comment: None,
+            };
+        };
+        let size = i64::try_from(state.size).unwrap_or(i64::MAX);
+        if self.limit == 0 || state.size == 0 {
+            return BodySnapshot {
+                size,
+                text: None,
+                comment: None,
+            };
+        }
+        let Ok(text) = std::str::from_utf8(&state.captured) else {
+            return BodySnapshot {
+                size,
+                text: None,
+                comment: Some("binary body not captured".to_string()),
+            };
+        };
+        let truncated = state.size > state.captured.len() as u64;
+        BodySnapshot {
+            size,
+            text: Some(redact_secrets(text.to_string())),
+            comment: truncated.then(|| format!("truncated to {} bytes", state.captured.len())),
+        }
+    }
+}
+
+struct CaptureStream {
+    inner: Pin<Box<BodyDataStream>>,
+    capture: Arc<BodyCapture>,
+    on_end: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl Stream for CaptureStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        match this.inner.as_mut().poll_next(cx) {
+            Poll::Ready(Some(Ok(bytes))) => {
+                this.capture.observe(&bytes);
+                Poll::Ready(Some(Ok(bytes)))
+            }
+            Poll::Ready(None) => {
+                if let Some(on_end) = this.on_end.take() {
+                    on_end();
+                }
+                Poll::Ready(None)
+            }
+            other => other,
+        }
+    }
+}
+
+impl Drop for CaptureStream {
+    fn drop(&mut self) {
+        // Record requests whose response body was abandoned (e.g. client disconnects) too.
+        if let Some(on_end) = self.on_end.take() {
+            on_end();
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "har_tests.rs"]
+mod tests;
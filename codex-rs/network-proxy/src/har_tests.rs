@@ -0,0 +1,179 @@
+use super::*;
+
+use futures::StreamExt;
+use pretty_assertions::assert_eq;
+use rama_http::StatusCode;
+use rama_http::header::AUTHORIZATION;
+use tempfile::TempDir;
+
+fn exchange(recorder: Arc<HarRecorder>, capture_limit: usize) -> HarExchange {
+    let mut request_headers = HeaderMap::new();
+    request_headers.insert(AUTHORIZATION, "Bearer abc123".parse().unwrap());
+    request_headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
+    HarExchange {
+        recorder,
+        protocol: "https",
+        started_date_time: "2026-01-01T00:00:00.000Z".to_string(),
+        started: Instant::now(),
+        method: "POST".to_string(),
+        url: "https://registry.example.com/v1/publish?token=sk-abcdefghijklmnopqrstuvwxyz123456"
+            .to_string(),
+        http_version: "HTTP/1.1".to_string(),
+        request_headers,
+        request_body: Arc::new(BodyCapture::new(capture_limit)),
+    }
+}
+
+#[test]
+fn har_log_path_sanitizes_thread_id() {
+    let path = har_log_path(Path::new("/codex"), "../thread/1");
+
+    assert_eq!(path, PathBuf::from("/codex/proxy/har/___thread_1.jsonl"));
+}
+
+#[test]
+fn redacted_headers_hide_credentials() {
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, "Bearer abc123".parse().unwrap());
+    headers.insert("x-api-key", "plain-value".parse().unwrap());
+    headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+
+    assert_eq!(
+        redacted_headers(&headers),
+        vec![
+            HarNameValue {
+                name: "authorization".to_string(),
+                value: REDACTED.to_string(),
+            },
+            HarNameValue {
+                name: "x-api-key".to_string(),
+                value: REDACTED.to_string(),
+            },
+            HarNameValue {
+                name: "content-type".to_string(),
+                value: "text/plain".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn body_capture_truncates_and_redacts() {
+    let capture = BodyCapture::new(/*limit*/ 40);
+    capture.observe(b"{\"key\":\"sk-abcdefghijklmnopqrstuvwxyz123456\"}");
+    capture.observe(b"trailing bytes");
+
+    let snapshot = capture.snapshot();
+
+    assert_eq!(snapshot.size, 59);
+    assert_eq!(
+        snapshot.text.as_deref(),
+        Some("{\"key\":\"[REDACTED_SECRET]")
+    );
+    assert_eq!(snapshot.comment.as_deref(), Some("truncated to 40 bytes"));
+}
+
+#[test]
+fn body_capture_only_counts_when_disabled() {
+    let capture = BodyCapture::new(/*limit*/ 0);
+    capture.observe(b"hello");
+
+    let snapshot = capture.snapshot();
+
+    assert_eq!(snapshot.size, 5);
+    assert_eq!(snapshot.text, None);
+}
+
+#[tokio::test]
+async fn finished_exchange_is_appended_to_thread_log() {
+    let codex_home = TempDir::new().unwrap();
+    let recorder = Arc::new(HarRecorder::with_codex_home(
+        "thread-1",
+        codex_home.path().to_path_buf(),
+    ));
+    let exchange = exchange(recorder.clone(), /*capture_limit*/ 1024);
+    exchange.request_body.observe(b"{\"name\":\"pkg\"}");
+    let resp = Response::builder()
+        .status(StatusCode::FORBIDDEN)
+        .header(CONTENT_TYPE, "text/plain")
+        .body(Body::from("blocked"))
+        .unwrap();
+    let resp = crate::responses::with_block_reason(resp, "not_allowed");
+
+    let resp = HarExchange::finish(Some(exchange), resp);
+    let mut body = resp.into_body().into_data_stream();
+    while body.next().await.is_some() {}
+    recorder.flush();
+
+    let log = read_har_log(&har_log_path(codex_home.path(), "thread-1")).unwrap();
+    assert_eq!(log.log.version, "1.2");
+    assert_eq!(log.log.entries.len(), 1);
+    let entry = &log.log.entries[0];
+    assert_eq!(
+        entry.request.url,
+        "https://registry.example.com/v1/publish?token=[REDACTED_SECRET]"
+    );
+    assert_eq!(entry.request.method, "POST");
+    assert_eq!(entry.request.headers[0].value, REDACTED);
+    assert_eq!(
+        entry
+            .request
+            .post_data
+            .as_ref()
+            .map(|data| data.text.as_str()),
+        Some("{\"name\":\"pkg\"}")
+    );
+    assert_eq!(entry.response.status, 403);
+    assert_eq!(entry.response.body_size, 7);
+    assert_eq!(entry.response.content.text.as_deref(), Some("blocked"));
+    assert_eq!(entry.block_reason.as_deref(), Some("not_allowed"));
+    assert_eq!(entry.protocol, "https");
+    assert_eq!(
+        list_har_logs(codex_home.path()).unwrap(),
+        vec!["thread-1".to_string()]
+    );
+}
+
+#[test]
+fn abandoned_response_body_is_still_recorded() {
+    let codex_home = TempDir::new().unwrap();
+    let recorder = Arc::new(HarRecorder::with_codex_home(
+        "thread-2",
+        codex_home.path().to_path_buf(),
+    ));
+
+    let resp = HarExchange::finish(
+        Some(exchange(recorder.clone(), /*capture_limit*/ 0)),
+        Response::new(Body::from("partial")),
+    );
+    drop(resp);
+    recorder.flush();
+
+    let log = read_har_log(&har_log_path(codex_home.path(), "thread-2")).unwrap();
+    assert_eq!(log.log.entries.len(), 1);
+    assert_eq!(log.log.entries[0].response.content.text, None);
+}
+
+#[cfg(unix)]
+#[test]
+fn har_log_is_private_to_the_user() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let codex_home = TempDir::new().unwrap();
+    let recorder = Arc::new(HarRecorder::with_codex_home(
+        "thread-3",
+        codex_home.path().to_path_buf(),
+    ));
+
+    drop(HarExchange::finish(
+        Some(exchange(recorder.clone(), /*capture_limit*/ 0)),
+        Response::new(Body::empty()),
+    ));
+    recorder.flush();
+
+    let mode = std::fs::metadata(har_log_path(codex_home.path(), "thread-3"))
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
@@ -1,4 +1,8 @@
+use crate::budgets::BudgetDecision;
+use crate::budgets::BudgetMeter;
+use crate::budgets::MeteredStream;
 use crate::config::NetworkMode;
+use crate::har::HarExchange;
 use crate::mitm;
 use crate::network_policy::BlockDecisionAuditEventArgs;
 use crate::network_policy::NetworkDecision;
@@ -16,13 +20,19 @@ use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_MITM_REQUIRED;
 use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_PROXY_DISABLED;
+use crate::reasons::REASON_SECRET_EXFILTRATION;
 use crate::reasons::REASON_UNIX_SOCKET_UNSUPPORTED;
+use crate::request_rules::RequestRuleDecision;
+use crate::request_rules::RequestRuleTarget;
+use crate::request_rules::TunnelRuleDecision;
 use crate::responses::PolicyDecisionDetails;
 use crate::responses::blocked_header_value;
 use crate::responses::blocked_message_with_policy;
 use crate::responses::blocked_text_response_with_policy;
 use crate::responses::json_response;
+use crate::responses::with_block_reason;
 use crate::runtime::unix_socket_permissions_supported;
+use crate::secret_guard::scan_outbound_request;
 use crate::state::BlockedRequest;
 use crate::state::BlockedRequestArgs;
 use crate::state::NetworkProxyState;
@@ -61,9 +71,7 @@ use rama_net::address::ProxyAddress;
 use rama_net::client::ConnectorService;
 use rama_net::client::EstablishedClientConnection;
 use rama_net::http::RequestContext;
-use rama_net::proxy::ProxyRequest;
 use rama_net::proxy::ProxyTarget;
-use rama_net::proxy::StreamForwardService;
 use rama_net::stream::SocketInfo;
 use rama_tcp::client::Request as TcpRequest;
 use rama_tcp::client::service::TcpConnector;
@@ -129,7 +137,7 @@ async fn run_http_proxy_with_listener(
                 MethodMatcher::CONNECT,
                 service_fn({
                     let policy_decider = policy_decider.clone();
-                    move |req| http_connect_accept(policy_decider.clone(), req)
+                    move |req| http_connect_accept_recorded(policy_decider.clone(), req)
                 }),
                 service_fn(http_connect_proxy),
             ),
@@ -137,7 +145,7 @@ async fn run_http_proxy_with_listener(
         )
             .into_layer(service_fn({
                 let policy_decider = policy_decider.clone();
-                move |req| http_plain_proxy(policy_decider.clone(), req)
+                move |req| http_plain_proxy_recorded(policy_decider.clone(), req)
             })),
     );
 
@@ -149,6 +157,30 @@ async fn run_http_proxy_with_listener(
     Ok(())
 }
 
+/// Record the CONNECT handshake in the thread's HAR log, when enabled. Inner requests of MITM
+/// tunnels are recorded separately by the MITM handler.
+async fn http_connect_accept_recorded(
+    policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
+    req: Request,
+) -> Result<(Response, Request), Response> {
+    let Some(app_state) = req.extensions().get::<Arc<NetworkProxyState>>().cloned() else {
+        return http_connect_accept(policy_decider, req).await;
+    };
+    let url = format!("https://{}", req.uri());
+    let (exchange, req) = HarExchange::begin(
+        &app_state,
+        req,
+        url,
+        "https-connect",
+        /*capture_bodies*/ false,
+    )
+    .await;
+    match http_connect_accept(policy_decider, req).await {
+        Ok((resp, req)) => Ok((HarExchange::finish(exchange, resp), req)),
+        Err(resp) => Err(HarExchange::finish(exchange, resp)),
+    }
+}
+
 async fn http_connect_accept(
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
     mut req: Request,
@@ -256,6 +288,58 @@ async fn http_connect_accept(
         }
     };
 
+    let tunnel_decision = app_state
+        .tunnel_rule_decision(Some("https"), &host)
+        .await
+        .map_err(|err| internal_error("failed to evaluate request rules", err))?;
+    let rule_block_reason = match tunnel_decision {
+        TunnelRuleDecision::Deny(reason) => Some(reason),
+        TunnelRuleDecision::InspectionRequired if mitm_state.is_none() => {
+            Some(REASON_MITM_REQUIRED)
+        }
+        TunnelRuleDecision::InspectionRequired | TunnelRuleDecision::Allow => None,
+    };
+    if let Some(reason) = rule_block_reason {
+        // Path and method rules can only be enforced on inner requests, so a tunnel that needs
+        // them is refused unless MITM can terminate it.
+        emit_http_block_decision_audit_event(
+            &app_state,
+            BlockDecisionAuditEventArgs {
+                source: NetworkDecisionSource::BaselinePolicy,
+                reason,
+                protocol: NetworkProtocol::HttpsConnect,
+                server_address: host.as_str(),
+                server_port: authority.port,
+                method: Some("CONNECT"),
+                client_addr: client.as_deref(),
+            },
+        );
+        let details = PolicyDecisionDetails {
+            decision: NetworkPolicyDecision::Deny,
+            reason,
+            source: NetworkDecisionSource::BaselinePolicy,
+            protocol: NetworkProtocol::HttpsConnect,
+            host: &host,
+            port: authority.port,
+        };
+        let _ = app_state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: host.clone(),
+                reason: reason.to_string(),
+                client: client.clone(),
+                method: Some("CONNECT".to_string()),
+                mode: None,
+                protocol: "http-connect".to_string(),
+                decision: Some(details.decision.as_str().to_string()),
+                source: Some(details.source.as_str().to_string()),
+                port: Some(authority.port),
+            }))
+            .await;
+        let client = client.as_deref().unwrap_or_default();
+        warn!("CONNECT blocked by request rules (client={client}, host={host}, reason={reason})");
+        return Err(blocked_text_with_details(reason, &details));
+    }
+
     if mode == NetworkMode::Limited && mitm_state.is_none() {
         // Limited mode is designed to be read-only. Without MITM, a CONNECT tunnel would hide the
         // inner HTTP method/headers from the proxy, effectively bypassing method policy.
@@ -299,6 +383,62 @@ async fn http_connect_accept(
         return Err(blocked_text_with_details(REASON_MITM_REQUIRED, &details));
     }
 
+    let inspect =
+        mode == NetworkMode::Limited || tunnel_decision == TunnelRuleDecision::InspectionRequired;
+    let mitm_state = mitm_state.filter(|_| inspect);
+    // Inner requests of MITM tunnels are counted against the budgets individually.
+    if mitm_state.is_none() {
+        match app_state
+            .admit_budget_request(&host)
+            .await
+            .map_err(|err| internal_error("failed to evaluate network budgets", err))?
+        {
+            BudgetDecision::Allow(meter) => {
+                req.extensions_mut().insert(meter);
+            }
+            BudgetDecision::Blocked { reason, scope } => {
+                emit_http_block_decision_audit_event(
+                    &app_state,
+                    BlockDecisionAuditEventArgs {
+                        source: NetworkDecisionSource::BaselinePolicy,
+                        reason,
+                        protocol: NetworkProtocol::HttpsConnect,
+                        server_address: host.as_str(),
+                        server_port: authority.port,
+                        method: Some("CONNECT"),
+                        client_addr: client.as_deref(),
+                    },
+                );
+                let details = PolicyDecisionDetails {
+                    decision: NetworkPolicyDecision::Deny,
+                    reason,
+                    source: NetworkDecisionSource::BaselinePolicy,
+                    protocol: NetworkProtocol::HttpsConnect,
+                    host: &host,
+                    port: authority.port,
+                };
+                let _ = app_state
+                    .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                        host: host.clone(),
+                        reason: reason.to_string(),
+                        client: client.clone(),
+                        method: Some("CONNECT".to_string()),
+                        mode: None,
+                        protocol: "http-connect".to_string(),
+                        decision: Some(details.decision.as_str().to_string()),
+                        source: Some(details.source.as_str().to_string()),
+                        port: Some(authority.port),
+                    }))
+                    .await;
+                let client = client.as_deref().unwrap_or_default();
+                warn!(
+                    "CONNECT blocked by network budget (client={client}, host={host}, reason={reason}, budget={scope})"
+                );
+                return Err(blocked_text_with_details(reason, &details));
+            }
+        }
+    }
+
     req.extensions_mut().insert(ProxyTarget(authority));
     req.extensions_mut().insert(mode);
     if let Some(mitm_state) = mitm_state {
@@ -330,11 +470,11 @@ async fn http_connect_proxy(upgraded: Upgraded) -> Result<(), Infallible> {
         return Ok(());
     };
 
-    if mode == NetworkMode::Limited
-        && upgraded
-            .extensions()
-            .get::<Arc<mitm::MitmState>>()
-            .is_some()
+    // The MITM state is only attached when limited mode or request rules need the inner requests.
+    if upgraded
+        .extensions()
+        .get::<Arc<mitm::MitmState>>()
+        .is_some()
     {
         let host = normalize_host(&target.host.to_string());
         let port = target.port;
@@ -406,13 +546,17 @@ async fn forward_connect_tunnel(
                 .into_boxed()
         })?;
 
-    let proxy_req = ProxyRequest {
-        source: upgraded,
-        target,
-    };
-    StreamForwardService::default()
-        .serve(proxy_req)
+    // Count tunneled bytes against the network budgets the CONNECT was admitted under.
+    let meter = upgraded
+        .extensions()
+        .get::<BudgetMeter>()
+        .cloned()
+        .unwrap_or_default();
+    let mut source = upgraded;
+    let mut target = MeteredStream::new(target, meter);
+    tokio::io::copy_bidirectional(&mut source, &mut target)
         .await
+        .map(|_| ())
         .map_err(|err| {
             OpaqueError::from_boxed(err.into())
                 .with_context(|| format!("forward CONNECT tunnel to {authority}"))
@@ -420,6 +564,21 @@ async fn forward_connect_tunnel(
         })
 }
 
+/// Record plain HTTP requests in the thread's HAR log, when enabled.
+async fn http_plain_proxy_recorded(
+    policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
+    req: Request,
+) -> Result<Response, Infallible> {
+    let Some(app_state) = req.extensions().get::<Arc<NetworkProxyState>>().cloned() else {
+        return http_plain_proxy(policy_decider, req).await;
+    };
+    let url = req.uri().to_string();
+    let (exchange, req) =
+        HarExchange::begin(&app_state, req, url, "http", /*capture_bodies*/ false).await;
+    let resp = http_plain_proxy(policy_decider, req).await?;
+    Ok(HarExchange::finish(exchange, resp))
+}
+
 async fn http_plain_proxy(
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
     mut req: Request,
@@ -671,7 +830,63 @@ async fn http_plain_proxy(
         }
     }
 
-    if !method_allowed {
+    let rule_decision = match app_state
+        .request_rule_decision(&RequestRuleTarget {
+            scheme: req.uri().scheme_str().unwrap_or("http"),
+            host: &host,
+            path: req.uri().path(),
+            method: req.method().as_str(),
+        })
+        .await
+        .map_err(|err| internal_error("failed to evaluate request rules", err))
+    {
+        Ok(decision) => decision,
+        Err(resp) => return Ok(resp),
+    };
+    if let RequestRuleDecision::Deny(reason) = rule_decision {
+        emit_http_block_decision_audit_event(
+            &app_state,
+            BlockDecisionAuditEventArgs {
+                source: NetworkDecisionSource::BaselinePolicy,
+                reason,
+                protocol: NetworkProtocol::Http,
+                server_address: host.as_str(),
+                server_port: port,
+                method: Some(req.method().as_str()),
+                client_addr: client.as_deref(),
+            },
+        );
+        let details = PolicyDecisionDetails {
+            decision: NetworkPolicyDecision::Deny,
+            reason,
+            source: NetworkDecisionSource::BaselinePolicy,
+            protocol: NetworkProtocol::Http,
+            host: &host,
+            port,
+        };
+        let _ = app_state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: host.clone(),
+                reason: reason.to_string(),
+                client: client.clone(),
+                method: Some(req.method().as_str().to_string()),
+                mode: None,
+                protocol: "http".to_string(),
+                decision: Some(details.decision.as_str().to_string()),
+                source: Some(details.source.as_str().to_string()),
+                port: Some(port),
+            }))
+            .await;
+        let client = client.as_deref().unwrap_or_default();
+        let method = req.method();
+        let path = req.uri().path();
+        warn!(
+            "request blocked by request rule (client={client}, host={host}, method={method}, path={path}, reason={reason})"
+        );
+        return Ok(json_blocked(&host, reason, Some(&details)));
+    }
+
+    if !method_allowed && !rule_decision.overrides_method_policy() {
         emit_http_block_decision_audit_event(
             &app_state,
             BlockDecisionAuditEventArgs {
@@ -717,6 +932,112 @@ async fn http_plain_proxy(
         ));
     }
 
+    let (secret_match, scanned_req) = match scan_outbound_request(&app_state, &host, req)
+        .await
+        .map_err(|err| internal_error("failed to scan request for secrets", err))
+    {
+        Ok(scanned) => scanned,
+        Err(resp) => return Ok(resp),
+    };
+    req = scanned_req;
+    if let Some(secret_match) = secret_match {
+        emit_http_block_decision_audit_event(
+            &app_state,
+            BlockDecisionAuditEventArgs {
+                source: NetworkDecisionSource::BaselinePolicy,
+                reason: REASON_SECRET_EXFILTRATION,
+                protocol: NetworkProtocol::Http,
+                server_address: host.as_str(),
+                server_port: port,
+                method: Some(req.method().as_str()),
+                client_addr: client.as_deref(),
+            },
+        );
+        let details = PolicyDecisionDetails {
+            decision: NetworkPolicyDecision::Deny,
+            reason: REASON_SECRET_EXFILTRATION,
+            source: NetworkDecisionSource::BaselinePolicy,
+            protocol: NetworkProtocol::Http,
+            host: &host,
+            port,
+        };
+        let _ = app_state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: host.clone(),
+                reason: REASON_SECRET_EXFILTRATION.to_string(),
+                client: client.clone(),
+                method: Some(req.method().as_str().to_string()),
+                mode: None,
+                protocol: "http".to_string(),
+                decision: Some(details.decision.as_str().to_string()),
+                source: Some(details.source.as_str().to_string()),
+                port: Some(port),
+            }))
+            .await;
+        let client = client.as_deref().unwrap_or_default();
+        let method = req.method();
+        let secret_match = secret_match.as_str();
+        warn!(
+            "request blocked by secret guard (client={client}, host={host}, method={method}, match={secret_match})"
+        );
+        return Ok(json_blocked(
+            &host,
+            REASON_SECRET_EXFILTRATION,
+            Some(&details),
+        ));
+    }
+
+    let meter = match app_state
+        .admit_budget_request(&host)
+        .await
+        .map_err(|err| internal_error("failed to evaluate network budgets", err))
+    {
+        Ok(BudgetDecision::Allow(meter)) => meter,
+        Ok(BudgetDecision::Blocked { reason, scope }) => {
+            emit_http_block_decision_audit_event(
+                &app_state,
+                BlockDecisionAuditEventArgs {
+                    source: NetworkDecisionSource::BaselinePolicy,
+                    reason,
+                    protocol: NetworkProtocol::Http,
+                    server_address: host.as_str(),
+                    server_port: port,
+                    method: Some(req.method().as_str()),
+                    client_addr: client.as_deref(),
+                },
+            );
+            let details = PolicyDecisionDetails {
+                decision: NetworkPolicyDecision::Deny,
+                reason,
+                source: NetworkDecisionSource::BaselinePolicy,
+                protocol: NetworkProtocol::Http,
+                host: &host,
+                port,
+            };
+            let _ = app_state
+                .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                    host: host.clone(),
+                    reason: reason.to_string(),
+                    client: client.clone(),
+                    method: Some(req.method().as_str().to_string()),
+                    mode: None,
+                    protocol: "http".to_string(),
+                    decision: Some(details.decision.as_str().to_string()),
+                    source: Some(details.source.as_str().to_string()),
+                    port: Some(port),
+                }))
+                .await;
+            let client = client.as_deref().unwrap_or_default();
+            let method = req.method();
+            warn!(
+                "request blocked by network budget (client={client}, host={host}, method={method}, reason={reason}, budget={scope})"
+            );
+            return Ok(json_blocked(&host, reason, Some(&details)));
+        }
+        Err(resp) => return Ok(resp),
+    };
+    req = meter.meter_request(req);
+
     let client = client.as_deref().unwrap_or_default();
     let method = req.method();
     info!("request allowed (client={client}, host={host}, method={method})");
@@ -738,7 +1059,7 @@ async fn http_plain_proxy(
     // Strip hop-by-hop headers only after extracting metadata used for policy correlation.
     remove_hop_by_hop_request_headers(req.headers_mut());
     match client.serve(req).await {
-        Ok(resp) => Ok(resp),
+        Ok(resp) => Ok(meter.meter_response(resp)),
         Err(err) => {
             warn!("upstream request failed: {err}");
             Ok(text_response(StatusCode::BAD_GATEWAY, "upstream failure"))
@@ -877,7 +1198,7 @@ fn json_blocked(host: &str, reason: &str, details: Option<&PolicyDecisionDetails
         "x-proxy-error",
         HeaderValue::from_static(blocked_header_value(reason)),
     );
-    resp
+    with_block_reason(resp, reason)
 }
 
 fn blocked_text_with_details(reason: &str, details: &PolicyDecisionDetails<'_>) -> Response {
@@ -931,10 +1252,11 @@ async fn proxy_disabled_response(
         host: &host,
         port,
     };
-    text_response(
+    let resp = text_response(
         StatusCode::SERVICE_UNAVAILABLE,
         &blocked_message_with_policy(REASON_PROXY_DISABLED, &details),
-    )
+    );
+    with_block_reason(resp, REASON_PROXY_DISABLED)
 }
 
 fn internal_error(context: &str, err: impl std::fmt::Display) -> Response {
@@ -985,8 +1307,13 @@ struct BlockedResponse<'a> {
 mod tests {
     use super::*;
 
+    use crate::config::NetworkHostBudget;
     use crate::config::NetworkMode;
     use crate::config::NetworkProxySettings;
+    use crate::config::NetworkRequestRule;
+    use crate::config::NetworkRequestRuleAction;
+    use crate::reasons::REASON_RATE_LIMITED;
+    use crate::reasons::REASON_RULE_DENIED;
     use crate::runtime::network_proxy_state_for_policy;
     use pretty_assertions::assert_eq;
     use rama_http::Method;
@@ -1222,6 +1549,153 @@ mod tests {
         );
     }
 
+    fn deny_graphql_posts_policy() -> NetworkProxySettings {
+        let mut policy = NetworkProxySettings::default();
+        policy.set_allowed_domains(vec!["api.github.com".to_string()]);
+        policy.rules = vec![NetworkRequestRule {
+            action: NetworkRequestRuleAction::Deny,
+            scheme: None,
+            host: "*".to_string(),
+            path: Some("**/graphql".to_string()),
+            methods: vec!["POST".to_string()],
+        }];
+        policy
+    }
+
+    #[tokio::test]
+    async fn http_plain_proxy_blocks_request_rule_denials() {
+        let state = Arc::new(network_proxy_state_for_policy(deny_graphql_posts_policy()));
+        let mut req = Request::builder()
+            .method(Method::POST)
+            .uri("http://api.github.com/graphql")
+            .header(header::HOST, "api.github.com")
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut().insert(state.clone());
+
+        let response = http_plain_proxy(/*policy_decider*/ None, req)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            response.headers().get("x-proxy-error").unwrap(),
+            "blocked-by-request-rule"
+        );
+        let blocked = state.drain_blocked().await.unwrap();
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0].reason, REASON_RULE_DENIED);
+        assert_eq!(blocked[0].method.as_deref(), Some("POST"));
+    }
+
+    #[tokio::test]
+    async fn http_plain_proxy_blocks_known_secrets_to_unlisted_hosts() {
+        let mut policy = NetworkProxySettings::default();
+        policy.set_allowed_domains(vec!["api.github.com".to_string()]);
+        policy.secret_guard.enabled = true;
+        let state = Arc::new(network_proxy_state_for_policy(policy));
+        state
+            .set_guarded_secrets(vec!["hunter2-database-password".to_string()])
+            .await;
+        let mut req = Request::builder()
+            .method(Method::POST)
+            .uri("http://api.github.com/gists")
+            .header(header::HOST, "api.github.com")
+            .body(Body::from("content=hunter2-database-password"))
+            .unwrap();
+        req.extensions_mut().insert(state.clone());
+
+        let response = http_plain_proxy(/*policy_decider*/ None, req)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            response.headers().get("x-proxy-error").unwrap(),
+            "blocked-by-secret-guard"
+        );
+        let blocked = state.drain_blocked().await.unwrap();
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0].reason, REASON_SECRET_EXFILTRATION);
+        assert_eq!(blocked[0].decision.as_deref(), Some("deny"));
+    }
+
+    #[tokio::test]
+    async fn http_connect_accept_blocks_tunnels_over_host_rate_budget() {
+        let mut policy = NetworkProxySettings::default();
+        policy.set_allowed_domains(vec!["**.example.com".to_string()]);
+        policy.budgets.hosts = vec![NetworkHostBudget {
+            host: "*.example.com".to_string(),
+            max_requests_per_minute: Some(1),
+            max_total_bytes: None,
+        }];
+        let state = Arc::new(network_proxy_state_for_policy(policy));
+        let connect = |authority: &str| {
+            let mut req = Request::builder()
+                .method(Method::CONNECT)
+                .uri(format!("https://{authority}"))
+                .header("host", authority)
+                .body(Body::empty())
+                .unwrap();
+            req.extensions_mut().insert(state.clone());
+            req
+        };
+
+        let (response, _request) =
+            http_connect_accept(/*policy_decider*/ None, connect("api.example.com:443"))
+                .await
+                .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response =
+            http_connect_accept(/*policy_decider*/ None, connect("db.example.com:443"))
+                .await
+                .unwrap_err();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            response.headers().get("x-proxy-error").unwrap(),
+            "blocked-by-rate-limit"
+        );
+        let (response, _request) =
+            http_connect_accept(/*policy_decider*/ None, connect("example.com:443"))
+                .await
+                .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let blocked = state.drain_blocked().await.unwrap();
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0].host, "db.example.com");
+        assert_eq!(blocked[0].reason, REASON_RATE_LIMITED);
+        let usage = state.budget_usage().await.unwrap();
+        assert_eq!(
+            usage
+                .iter()
+                .map(|usage| (usage.scope.as_str(), usage.requests_last_minute))
+                .collect::<Vec<_>>(),
+            vec![("thread", 2), ("*.example.com", 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn http_connect_accept_requires_mitm_for_path_rules() {
+        let state = Arc::new(network_proxy_state_for_policy(deny_graphql_posts_policy()));
+        let mut req = Request::builder()
+            .method(Method::CONNECT)
+            .uri("https://api.github.com:443")
+            .header("host", "api.github.com:443")
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut().insert(state);
+
+        let response = http_connect_accept(/*policy_decider*/ None, req)
+            .await
+            .unwrap_err();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            response.headers().get("x-proxy-error").unwrap(),
+            "blocked-by-mitm-required"
+        );
+    }
+
     #[tokio::test]
     async fn http_plain_proxy_rejects_absolute_uri_host_header_mismatch() {
         let state = Arc::new(network_proxy_state_for_policy(
@@ -1,27 +1,43 @@
 #![deny(clippy::print_stdout, clippy::print_stderr)]
 
+mod budgets;
 mod certs;
 mod config;
+mod har;
 mod http_proxy;
 mod mitm;
 mod network_policy;
 mod policy;
 mod proxy;
 mod reasons;
+mod request_rules;
 mod responses;
 mod runtime;
+mod secret_guard;
 mod socks5;
 mod state;
 mod upstream;
 
+pub use budgets::NetworkBudgetUsage;
+pub use config::NetworkBudgetSettings;
 pub use config::NetworkDomainPermission;
 pub use config::NetworkDomainPermissionEntry;
 pub use config::NetworkDomainPermissions;
+pub use config::NetworkHarSettings;
+pub use config::NetworkHostBudget;
 pub use config::NetworkMode;
 pub use config::NetworkProxyConfig;
+pub use config::NetworkRequestRule;
+pub use config::NetworkRequestRuleAction;
+pub use config::NetworkSecretGuardSettings;
 pub use config::NetworkUnixSocketPermission;
 pub use config::NetworkUnixSocketPermissions;
 pub use config::host_and_port_from_network_addr;
+pub use har::HarEntry;
+pub use har::HarLog;
+pub use har::har_log_path;
+pub use har::list_har_logs;
+pub use har::read_har_log;
 pub use network_policy::NetworkDecision;
 pub use network_policy::NetworkDecisionSource;
 pub use network_policy::NetworkPolicyDecider;
@@ -1,11 +1,20 @@
+use crate::budgets::BudgetDecision;
 use crate::certs::ManagedMitmCa;
 use crate::config::NetworkMode;
+use crate::har::HarExchange;
+use crate::network_policy::NetworkDecisionSource;
+use crate::network_policy::NetworkPolicyDecision;
 use crate::policy::normalize_host;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
+use crate::reasons::REASON_SECRET_EXFILTRATION;
+use crate::request_rules::RequestRuleDecision;
+use crate::request_rules::RequestRuleTarget;
 use crate::responses::blocked_text_response;
 use crate::responses::text_response;
 use crate::runtime::HostBlockDecision;
 use crate::runtime::HostBlockReason;
+use crate::secret_guard::SecretMatch;
+use crate::secret_guard::scan_outbound_request;
 use crate::state::BlockedRequest;
 use crate::state::BlockedRequestArgs;
 use crate::state::NetworkProxyState;
@@ -184,6 +193,20 @@ async fn handle_mitm_request(
     req: Request,
     request_ctx: Arc<MitmRequestContext>,
 ) -> Result<Response, std::convert::Infallible> {
+    let policy = &request_ctx.policy;
+    let url = format!(
+        "https://{}{}",
+        authority_header_value(&policy.target_host, policy.target_port),
+        path_and_query(req.uri())
+    );
+    let (exchange, req) = HarExchange::begin(
+        &policy.app_state,
+        req,
+        url,
+        "https",
+        /*capture_bodies*/ true,
+    )
+    .await;
     let response = match forward_request(req, &request_ctx).await {
         Ok(resp) => resp,
         Err(err) => {
@@ -191,13 +214,34 @@ async fn handle_mitm_request(
             text_response(StatusCode::BAD_GATEWAY, "mitm upstream error")
         }
     };
-    Ok(response)
+    Ok(HarExchange::finish(exchange, response))
 }
 
 async fn forward_request(req: Request, request_ctx: &MitmRequestContext) -> Result<Response> {
     if let Some(response) = mitm_blocking_response(&req, &request_ctx.policy).await? {
         return Ok(response);
     }
+    let (secret_match, req) = scan_outbound_request(
+        &request_ctx.policy.app_state,
+        &request_ctx.policy.target_host,
+        req,
+    )
+    .await?;
+    if let Some(secret_match) = secret_match {
+        return Ok(secret_guard_response(&req, &request_ctx.policy, secret_match).await);
+    }
+    let meter = match request_ctx
+        .policy
+        .app_state
+        .admit_budget_request(&request_ctx.policy.target_host)
+        .await?
+    {
+        BudgetDecision::Allow(meter) => meter,
+        BudgetDecision::Blocked { reason, scope } => {
+            return Ok(budget_response(&req, &request_ctx.policy, reason, &scope).await);
+        }
+    };
+    let req = meter.meter_request(req);
 
     let target_host = request_ctx.policy.target_host.clone();
     let target_port = request_ctx.policy.target_port;
@@ -233,7 +277,7 @@ async fn forward_request(req: Request, request_ctx: &MitmRequestContext) -> Resu
     let upstream_req = Request::from_parts(parts, body);
     let upstream_resp = mitm.upstream.serve(upstream_req).await?;
     respond_with_inspection(
-        upstream_resp,
+        meter.meter_response(upstream_resp),
         inspect,
         max_body_bytes,
         &method,
@@ -305,7 +349,38 @@ async fn mitm_blocking_response(
         return Ok(Some(blocked_text_response(reason)));
     }
 
-    if !policy.mode.allows_method(&method) {
+    let rule_decision = policy
+        .app_state
+        .request_rule_decision(&RequestRuleTarget {
+            scheme: "https",
+            host: &policy.target_host,
+            path: req.uri().path(),
+            method: &method,
+        })
+        .await?;
+    if let RequestRuleDecision::Deny(reason) = rule_decision {
+        let _ = policy
+            .app_state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: policy.target_host.clone(),
+                reason: reason.to_string(),
+                client: client.clone(),
+                method: Some(method.clone()),
+                mode: Some(policy.mode),
+                protocol: "https".to_string(),
+                decision: None,
+                source: None,
+                port: Some(policy.target_port),
+            }))
+            .await;
+        warn!(
+            "MITM blocked by request rule (host={}, method={method}, path={log_path}, reason={reason})",
+            policy.target_host
+        );
+        return Ok(Some(blocked_text_response(reason)));
+    }
+
+    if !rule_decision.overrides_method_policy() && !policy.mode.allows_method(&method) {
         let _ = policy
             .app_state
             .record_blocked(BlockedRequest::new(BlockedRequestArgs {
@@ -330,6 +405,72 @@ async fn mitm_blocking_response(
     Ok(None)
 }
 
+async fn secret_guard_response(
+    req: &Request,
+    policy: &MitmPolicyContext,
+    secret_match: SecretMatch,
+) -> Response {
+    let method = req.method().as_str().to_string();
+    let log_path = path_for_log(req.uri());
+    let client = req
+        .extensions()
+        .get::<SocketInfo>()
+        .map(|info| info.peer_addr().to_string());
+    let _ = policy
+        .app_state
+        .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+            host: policy.target_host.clone(),
+            reason: REASON_SECRET_EXFILTRATION.to_string(),
+            client,
+            method: Some(method.clone()),
+            mode: Some(policy.mode),
+            protocol: "https".to_string(),
+            decision: Some(NetworkPolicyDecision::Deny.as_str().to_string()),
+            source: Some(NetworkDecisionSource::BaselinePolicy.as_str().to_string()),
+            port: Some(policy.target_port),
+        }))
+        .await;
+    warn!(
+        "MITM blocked by secret guard (host={}, method={method}, path={log_path}, match={})",
+        policy.target_host,
+        secret_match.as_str()
+    );
+    blocked_text_response(REASON_SECRET_EXFILTRATION)
+}
+
+async fn budget_response(
+    req: &Request,
+    policy: &MitmPolicyContext,
+    reason: &'static str,
+    scope: &str,
+) -> Response {
+    let method = req.method().as_str().to_string();
+    let log_path = path_for_log(req.uri());
+    let client = req
+        .extensions()
+        .get::<SocketInfo>()
+        .map(|info| info.peer_addr().to_string());
+    let _ = policy
+        .app_state
+        .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+            host: policy.target_host.clone(),
+            reason: reason.to_string(),
+            client,
+            method: Some(method.clone()),
+            mode: Some(policy.mode),
+            protocol: "https".to_string(),
+            decision: Some(NetworkPolicyDecision::Deny.as_str().to_string()),
+            source: Some(NetworkDecisionSource::BaselinePolicy.as_str().to_string()),
+            port: Some(policy.target_port),
+        }))
+        .await;
+    warn!(
+        "MITM blocked by network budget (host={}, method={method}, path={log_path}, reason={reason}, budget={scope})",
+        policy.target_host
+    );
+    blocked_text_response(reason)
+}
+
 fn respond_with_inspection(
     resp: Response,
     inspect: bool,
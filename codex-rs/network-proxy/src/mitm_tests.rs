@@ -1,8 +1,12 @@
 use super::*;
 
 use crate::config::NetworkProxySettings;
+use crate::config::NetworkRequestRule;
+use crate::config::NetworkRequestRuleAction;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
+use crate::reasons::REASON_RULE_DENIED;
+use crate::reasons::REASON_RULE_NOT_ALLOWED;
 use crate::runtime::network_proxy_state_for_policy;
 use pretty_assertions::assert_eq;
 use rama_http::Body;
@@ -63,6 +67,94 @@ async fn mitm_policy_blocks_disallowed_method_and_records_telemetry() {
     assert_eq!(blocked[0].port, Some(443));
 }
 
+fn github_rules_state() -> Arc<NetworkProxyState> {
+    Arc::new(network_proxy_state_for_policy({
+        let mut network = NetworkProxySettings::default();
+        network.set_allowed_domains(vec!["api.github.com".to_string()]);
+        network.rules = vec![
+            NetworkRequestRule {
+                action: NetworkRequestRuleAction::Allow,
+                scheme: Some("https".to_string()),
+                host: "api.github.com".to_string(),
+                path: Some("/repos/our-org/**".to_string()),
+                methods: vec!["GET".to_string(), "POST".to_string()],
+            },
+            NetworkRequestRule {
+                action: NetworkRequestRuleAction::Deny,
+                scheme: None,
+                host: "*".to_string(),
+                path: Some("**/graphql".to_string()),
+                methods: vec!["POST".to_string()],
+            },
+        ];
+        network
+    }))
+}
+
+#[tokio::test]
+async fn mitm_policy_blocks_request_rule_denials_and_records_telemetry() {
+    let app_state = github_rules_state();
+    let ctx = policy_ctx(
+        app_state.clone(),
+        NetworkMode::Full,
+        "api.github.com",
+        /*target_port*/ 443,
+    );
+
+    for (method, path, reason) in [
+        (Method::POST, "/graphql", REASON_RULE_DENIED),
+        (
+            Method::GET,
+            "/repos/other-org/codex",
+            REASON_RULE_NOT_ALLOWED,
+        ),
+    ] {
+        let req = Request::builder()
+            .method(method)
+            .uri(path)
+            .header(HOST, "api.github.com")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = mitm_blocking_response(&req, &ctx)
+            .await
+            .unwrap()
+            .expect("request rule should block the request");
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            response.headers().get("x-proxy-error").unwrap(),
+            "blocked-by-request-rule"
+        );
+        let blocked = app_state.drain_blocked().await.unwrap();
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0].reason, reason);
+        assert_eq!(blocked[0].host, "api.github.com");
+    }
+}
+
+#[tokio::test]
+async fn mitm_policy_allow_rule_overrides_limited_method_policy() {
+    let app_state = github_rules_state();
+    let ctx = policy_ctx(
+        app_state.clone(),
+        NetworkMode::Limited,
+        "api.github.com",
+        /*target_port*/ 443,
+    );
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri("/repos/our-org/codex/issues")
+        .header(HOST, "api.github.com")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = mitm_blocking_response(&req, &ctx).await.unwrap();
+
+    assert!(response.is_none());
+    assert_eq!(app_state.blocked_snapshot().await.unwrap().len(), 0);
+}
+
 #[tokio::test]
 async fn mitm_policy_rejects_host_mismatch() {
     let app_state = Arc::new(network_proxy_state_for_policy({
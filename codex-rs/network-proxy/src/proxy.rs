@@ -1,3 +1,4 @@
+use crate::budgets::NetworkBudgetUsage;
 use crate::config;
 use crate::http_proxy;
 use crate::network_policy::NetworkPolicyDecider;
@@ -580,6 +581,14 @@ impl NetworkProxy {
         self.state.add_denied_domain(host).await
     }
 
+    pub async fn set_guarded_secrets(&self, secrets: Vec<String>) {
+        self.state.set_guarded_secrets(secrets).await;
+    }
+
+    pub async fn budget_usage(&self) -> Result<Vec<NetworkBudgetUsage>> {
+        self.state.budget_usage().await
+    }
+
     pub fn allow_local_binding(&self) -> bool {
         self.runtime_settings().allow_local_binding
     }
@@ -1,3 +1,4 @@
+pub(crate) const REASON_BYTE_QUOTA_EXCEEDED: &str = "byte_quota_exceeded";
 pub(crate) const REASON_DENIED: &str = "denied";
 pub(crate) const REASON_METHOD_NOT_ALLOWED: &str = "method_not_allowed";
 pub(crate) const REASON_MITM_REQUIRED: &str = "mitm_required";
@@ -5,4 +6,8 @@ pub(crate) const REASON_NOT_ALLOWED: &str = "not_allowed";
 pub(crate) const REASON_NOT_ALLOWED_LOCAL: &str = "not_allowed_local";
 pub(crate) const REASON_POLICY_DENIED: &str = "policy_denied";
 pub(crate) const REASON_PROXY_DISABLED: &str = "proxy_disabled";
+pub(crate) const REASON_RATE_LIMITED: &str = "rate_limited";
+pub(crate) const REASON_RULE_DENIED: &str = "rule_denied";
+pub(crate) const REASON_RULE_NOT_ALLOWED: &str = "rule_not_allowed";
+pub(crate) const REASON_SECRET_EXFILTRATION: &str = "secret_exfiltration";
 pub(crate) const REASON_UNIX_SOCKET_UNSUPPORTED: &str = "unix_socket_unsupported";
@@ -0,0 +1,564 @@
+use crate::config::NetworkRequestRule;
+use crate::config::NetworkRequestRuleAction;
+use crate::policy::compile_allowlist_globset;
+use crate::reasons::REASON_RULE_DENIED;
+use crate::reasons::REASON_RULE_NOT_ALLOWED;
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use globset::GlobSet;
+
+/// `network.rules` compiled for matching against individual requests.
+#[derive(Clone, Debug, Default)]
+pub struct RequestRules {
+    rules: Vec<CompiledRequestRule>,
+}
+
+#[derive(Clone, Debug)]
+struct CompiledRequestRule {
+    action: NetworkRequestRuleAction,
+    scheme: Option<String>,
+    host: GlobSet,
+    path: Option<PathMatcher>,
+    methods: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+enum PathMatcher {
+    Prefix(String),
+    Glob(GlobMatcher),
+}
+
+/// The request being checked. `path` excludes the query string and is normalized before
+/// matching.
+pub(crate) struct RequestRuleTarget<'a> {
+    pub scheme: &'a str,
+    pub host: &'a str,
+    pub path: &'a str,
+    pub method: &'a str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RequestRuleDecision {
+    /// No rule covers the request; the network mode's method policy applies.
+    NoMatch,
+    /// An allow rule matched. `listed_method` is set when the matching rule names the request's
+    /// method explicitly, which overrides the network mode's method policy.
+    Allow {
+        listed_method: bool,
+    },
+    Deny(&'static str),
+}
+
+impl RequestRuleDecision {
+    /// Whether the request may bypass the network mode's method policy.
+    pub(crate) fn overrides_method_policy(self) -> bool {
+        matches!(
+            self,
+            Self::Allow {
+                listed_method: true
+            }
+        )
+    }
+}
+
+/// Decision for a tunnel (HTTPS CONNECT or SOCKS5) whose inner requests are opaque.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TunnelRuleDecision {
+    Allow,
+    /// Some rule depends on the inner path or method, so the tunnel must be terminated to
+    /// enforce it.
+    InspectionRequired,
+    Deny(&'static str),
+}
+
+impl RequestRules {
+    pub(crate) fn compile(rules: &[NetworkRequestRule]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                CompiledRequestRule::compile(rule)
+                    .with_context(|| format!("invalid network.rules[{index}]"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Deny rules win over allow rules. When the host is covered by any allow rule, the request
+    /// must match one of them.
+    pub(crate) fn evaluate(&self, target: &RequestRuleTarget<'_>) -> RequestRuleDecision {
+        let path = normalize_path(target.path);
+        let mut host_has_allow_rules = false;
+        let mut allowed = false;
+        let mut listed_method = false;
+        for rule in self
+            .rules
+            .iter()
+            .filter(|rule| rule.matches_host(Some(target.scheme), target.host))
+        {
+            let matches = rule.matches_request(&path, target.method);
+            match rule.action {
+                NetworkRequestRuleAction::Deny if matches => {
+                    return RequestRuleDecision::Deny(REASON_RULE_DENIED);
+                }
+                NetworkRequestRuleAction::Deny => {}
+                NetworkRequestRuleAction::Allow => {
+                    host_has_allow_rules = true;
+                    allowed |= matches;
+                    listed_method |= matches && !rule.methods.is_empty();
+                }
+            }
+        }
+
+        if allowed {
+            RequestRuleDecision::Allow { listed_method }
+        } else if host_has_allow_rules {
+            RequestRuleDecision::Deny(REASON_RULE_NOT_ALLOWED)
+        } else {
+            RequestRuleDecision::NoMatch
+        }
+    }
+
+    /// `scheme` is `None` when the tunnel protocol is unknown (SOCKS5), in which case rules for
+    /// every scheme apply.
+    pub(crate) fn evaluate_tunnel(&self, scheme: Option<&str>, host: &str) -> TunnelRuleDecision {
+        let mut inspection_required = false;
+        let mut has_allow_rules = false;
+        let mut allows_everything = false;
+        for rule in self
+            .rules
+            .iter()
+            .filter(|rule| rule.matches_host(scheme, host))
+        {
+            match rule.action {
+                NetworkRequestRuleAction::Deny if rule.is_unconditional() => {
+                    return TunnelRuleDecision::Deny(REASON_RULE_DENIED);
+                }
+                NetworkRequestRuleAction::Deny => inspection_required = true,
+                NetworkRequestRuleAction::Allow => {
+                    has_allow_rules = true;
+                    allows_everything |= rule.is_unconditional();
+                }
+            }
+        }
+
+        if inspection_required || (has_allow_rules && !allows_everything) {
+            TunnelRuleDecision::InspectionRequired
+        } else {
+            TunnelRuleDecision::Allow
+        }
+    }
+}
+
+impl CompiledRequestRule {
+    fn compile(rule: &NetworkRequestRule) -> Result<Self> {
+        if rule.host.trim().is_empty() {
+            bail!("host must not be empty");
+        }
+        let scheme = rule
+            .scheme
+            .as_deref()
+            .map(|scheme| scheme.trim().to_ascii_lowercase());
+        if scheme.as_deref().is_some_and(str::is_empty) {
+            bail!("scheme must not be empty");
+        }
+        let path = rule.path.as_deref().map(compile_path).transpose()?;
+        let methods = rule
+            .methods
+            .iter()
+            .map(|method| {
+                let method = method.trim().to_ascii_uppercase();
+                if method.is_empty() {
+                    bail!("methods must not contain empty entries");
+                }
+                Ok(method)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            action: rule.action,
+            scheme,
+            host: compile_allowlist_globset(std::slice::from_ref(&rule.host))?,
+            path,
+            methods,
+        })
+    }
+
+    fn matches_host(&self, scheme: Option<&str>, host: &str) -> bool {
+        let scheme_matches = match (self.scheme.as_deref(), scheme) {
+            (Some(expected), Some(scheme)) => expected.eq_ignore_ascii_case(scheme),
+            _ => true,
+        };
+        scheme_matches && self.host.is_match(host)
+    }
+
+    fn matches_request(&self, path: &str, method: &str) -> bool {
+        let path_matches = match &self.path {
+            Some(PathMatcher::Prefix(prefix)) => {
+                path == prefix
+                    || path
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| prefix.ends_with('/') || rest.starts_with('/'))
+            }
+            Some(PathMatcher::Glob(glob)) => glob.is_match(path),
+            None => true,
+        };
+        let method_matches = self.methods.is_empty()
+            || self
+                .methods
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(method));
+        path_matches && method_matches
+    }
+
+    fn is_unconditional(&self) -> bool {
+        self.path.is_none() && self.methods.is_empty()
+    }
+}
+
+fn compile_path(path: &str) -> Result<PathMatcher> {
+    let path = path.trim();
+    if path.contains(['*', '?', '[', '{']) {
+        let glob = GlobBuilder::new(path)
+            .literal_separator(true)
+            .build()
+            .with_context(|| format!("invalid path glob: {path}"))?;
+        return Ok(PathMatcher::Glob(glob.compile_matcher()));
+    }
+    if !path.starts_with('/') {
+        bail!("path prefix must start with '/': {path}");
+    }
+    Ok(PathMatcher::Prefix(path.to_string()))
+}
+
+/// Normalizes a request path so equivalent spellings match the same rules: percent-encoded
+/// unreserved characters are decoded, `.` and `..` segments are resolved and repeated slashes
+/// are collapsed.
+fn normalize_path(path: &str) -> String {
+    if !path.starts_with('/') {
+        return path.to_string();
+    }
+    let decoded = decode_unreserved(path);
+    let mut segments = Vec::new();
+    let mut trailing_slash = false;
+    for segment in decoded.split('/').skip(1) {
+        trailing_slash = matches!(segment, "" | "." | "..");
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    let mut normalized = format!("/{}", segments.join("/"));
+    if trailing_slash && !segments.is_empty() {
+        normalized.push('/');
+    }
+    normalized
+}
+
+/// Decodes `%XX` escapes of RFC 3986 unreserved characters and leaves every other escape as is.
+fn decode_unreserved(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = String::with_capacity(path.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && let Some(hex) = path.get(index + 1..index + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+            && (byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~'))
+        {
+            decoded.push(char::from(byte));
+            index += 3;
+            continue;
+        }
+        let ch = path[index..].chars().next().unwrap_or_default();
+        decoded.push(ch);
+        index += ch.len_utf8();
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn rule(
+        action: NetworkRequestRuleAction,
+        host: &str,
+        path: Option<&str>,
+        methods: &[&str],
+    ) -> NetworkRequestRule {
+        NetworkRequestRule {
+            action,
+            scheme: None,
+            host: host.to_string(),
+            path: path.map(ToString::to_string),
+            methods: methods.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    fn target<'a>(host: &'a str, path: &'a str, method: &'a str) -> RequestRuleTarget<'a> {
+        RequestRuleTarget {
+            scheme: "https",
+            host,
+            path,
+            method,
+        }
+    }
+
+    fn github_rules() -> RequestRules {
+        RequestRules::compile(&[
+            rule(
+                NetworkRequestRuleAction::Allow,
+                "api.github.com",
+                Some("/repos/our-org/**"),
+                &["get"],
+            ),
+            rule(
+                NetworkRequestRuleAction::Deny,
+                "*",
+                Some("**/graphql"),
+                &["POST"],
+            ),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn allow_rules_scope_requests_to_matching_paths_and_methods() {
+        let rules = github_rules();
+
+        assert_eq!(
+            rules.evaluate(&target(
+                "api.github.com",
+                "/repos/our-org/codex/pulls",
+                "GET"
+            )),
+            RequestRuleDecision::Allow {
+                listed_method: true
+            }
+        );
+        assert_eq!(
+            rules.evaluate(&target("api.github.com", "/repos/our-org/codex", "DELETE")),
+            RequestRuleDecision::Deny(REASON_RULE_NOT_ALLOWED)
+        );
+        assert_eq!(
+            rules.evaluate(&target("api.github.com", "/repos/other-org/codex", "GET")),
+            RequestRuleDecision::Deny(REASON_RULE_NOT_ALLOWED)
+        );
+        assert_eq!(
+            rules.evaluate(&target("example.com", "/index.html", "GET")),
+            RequestRuleDecision::NoMatch
+        );
+    }
+
+    #[test]
+    fn deny_rules_win_over_allow_rules() {
+        let rules = RequestRules::compile(&[
+            rule(NetworkRequestRuleAction::Allow, "**.example.com", None, &[]),
+            rule(
+                NetworkRequestRuleAction::Deny,
+                "*",
+                Some("**/graphql"),
+                &["POST"],
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            rules.evaluate(&target("api.example.com", "/v1/graphql", "POST")),
+            RequestRuleDecision::Deny(REASON_RULE_DENIED)
+        );
+        assert_eq!(
+            rules.evaluate(&target("api.example.com", "/v1/graphql", "GET")),
+            RequestRuleDecision::Allow {
+                listed_method: false
+            }
+        );
+        assert_eq!(
+            rules.evaluate(&target("other.test", "/graphql", "POST")),
+            RequestRuleDecision::Deny(REASON_RULE_DENIED)
+        );
+    }
+
+    #[test]
+    fn paths_are_normalized_before_matching() {
+        let rules = github_rules();
+
+        for path in ["//graphql", "/%67raphql", "/v1/./graphql", "/v1//graphql"] {
+            assert_eq!(
+                rules.evaluate(&target("example.com", path, "POST")),
+                RequestRuleDecision::Deny(REASON_RULE_DENIED),
+                "{path}"
+            );
+        }
+        assert_eq!(
+            rules.evaluate(&target(
+                "api.github.com",
+                "/repos/our-org/../../other-org/x",
+                "GET"
+            )),
+            RequestRuleDecision::Deny(REASON_RULE_NOT_ALLOWED)
+        );
+        assert_eq!(
+            rules.evaluate(&target("api.github.com", "/repos/%6Fur-org/codex", "GET")),
+            RequestRuleDecision::Allow {
+                listed_method: true
+            }
+        );
+        assert_eq!(normalize_path("/a/b/../c/./d/"), "/a/c/d/");
+        assert_eq!(normalize_path("/a/%2F/%2e%2E/b"), "/a/b");
+        assert_eq!(normalize_path("/../.."), "/");
+    }
+
+    #[test]
+    fn path_globs_do_not_match_across_segments() {
+        let rules = RequestRules::compile(&[rule(
+            NetworkRequestRuleAction::Deny,
+            "example.com",
+            Some("/api/*/delete"),
+            &[],
+        )])
+        .unwrap();
+
+        assert_eq!(
+            rules.evaluate(&target("example.com", "/api/users/delete", "GET")),
+            RequestRuleDecision::Deny(REASON_RULE_DENIED)
+        );
+        assert_eq!(
+            rules.evaluate(&target("example.com", "/api/users/1/delete", "GET")),
+            RequestRuleDecision::NoMatch
+        );
+    }
+
+    #[test]
+    fn only_allow_rules_listing_the_method_override_method_policy() {
+        let rules = RequestRules::compile(&[
+            rule(
+                NetworkRequestRuleAction::Allow,
+                "api.github.com",
+                Some("/repos/our-org/**"),
+                &[],
+            ),
+            rule(
+                NetworkRequestRuleAction::Allow,
+                "uploads.github.com",
+                None,
+                &["POST"],
+            ),
+        ])
+        .unwrap();
+
+        assert!(
+            !rules
+                .evaluate(&target("api.github.com", "/repos/our-org/codex", "POST"))
+                .overrides_method_policy()
+        );
+        assert!(
+            rules
+                .evaluate(&target("uploads.github.com", "/assets", "POST"))
+                .overrides_method_policy()
+        );
+    }
+
+    #[test]
+    fn path_prefixes_match_whole_segments() {
+        let rules = RequestRules::compile(&[rule(
+            NetworkRequestRuleAction::Deny,
+            "example.com",
+            Some("/admin"),
+            &[],
+        )])
+        .unwrap();
+
+        assert_eq!(
+            rules.evaluate(&target("example.com", "/admin", "GET")),
+            RequestRuleDecision::Deny(REASON_RULE_DENIED)
+        );
+        assert_eq!(
+            rules.evaluate(&target("example.com", "/admin/users", "GET")),
+            RequestRuleDecision::Deny(REASON_RULE_DENIED)
+        );
+        assert_eq!(
+            rules.evaluate(&target("example.com", "/administrator", "GET")),
+            RequestRuleDecision::NoMatch
+        );
+    }
+
+    #[test]
+    fn scheme_restricts_rule_to_matching_requests() {
+        let mut deny_http = rule(NetworkRequestRuleAction::Deny, "example.com", None, &[]);
+        deny_http.scheme = Some("HTTP".to_string());
+        let rules = RequestRules::compile(&[deny_http]).unwrap();
+
+        assert_eq!(
+            rules.evaluate(&RequestRuleTarget {
+                scheme: "http",
+                host: "example.com",
+                path: "/",
+                method: "GET",
+            }),
+            RequestRuleDecision::Deny(REASON_RULE_DENIED)
+        );
+        assert_eq!(
+            rules.evaluate(&target("example.com", "/", "GET")),
+            RequestRuleDecision::NoMatch
+        );
+    }
+
+    #[test]
+    fn tunnels_require_inspection_when_rules_depend_on_the_request() {
+        let rules = github_rules();
+
+        assert_eq!(
+            rules.evaluate_tunnel(Some("https"), "api.github.com"),
+            TunnelRuleDecision::InspectionRequired
+        );
+        assert_eq!(
+            rules.evaluate_tunnel(Some("https"), "example.com"),
+            TunnelRuleDecision::InspectionRequired
+        );
+
+        let rules = RequestRules::compile(&[
+            rule(NetworkRequestRuleAction::Allow, "example.com", None, &[]),
+            rule(NetworkRequestRuleAction::Deny, "evil.example", None, &[]),
+        ])
+        .unwrap();
+        assert_eq!(
+            rules.evaluate_tunnel(Some("https"), "example.com"),
+            TunnelRuleDecision::Allow
+        );
+        assert_eq!(
+            rules.evaluate_tunnel(/*scheme*/ None, "evil.example"),
+            TunnelRuleDecision::Deny(REASON_RULE_DENIED)
+        );
+        assert_eq!(
+            rules.evaluate_tunnel(Some("https"), "unrelated.test"),
+            TunnelRuleDecision::Allow
+        );
+    }
+
+    #[test]
+    fn compile_rejects_invalid_rules() {
+        let err = RequestRules::compile(&[rule(
+            NetworkRequestRuleAction::Allow,
+            "example.com",
+            Some("repos"),
+            &[],
+        )])
+        .unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "invalid network.rules[0]: path prefix must start with '/': repos"
+        );
+
+        assert!(
+            RequestRules::compile(&[rule(NetworkRequestRuleAction::Deny, " ", None, &[])]).is_err()
+        );
+    }
+}
@@ -1,12 +1,18 @@
+use crate::har::HarBlockReason;
 use crate::network_policy::NetworkDecisionSource;
 use crate::network_policy::NetworkPolicyDecision;
 use crate::network_policy::NetworkProtocol;
+use crate::reasons::REASON_BYTE_QUOTA_EXCEEDED;
 use crate::reasons::REASON_DENIED;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_MITM_REQUIRED;
 use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
 use crate::reasons::REASON_PROXY_DISABLED;
+use crate::reasons::REASON_RATE_LIMITED;
+use crate::reasons::REASON_RULE_DENIED;
+use crate::reasons::REASON_RULE_NOT_ALLOWED;
+use crate::reasons::REASON_SECRET_EXFILTRATION;
 use rama_http::Body;
 use rama_http::Response;
 use rama_http::StatusCode;
@@ -54,6 +60,10 @@ pub fn blocked_header_value(reason: &str) -> &'static str {
         REASON_DENIED => "blocked-by-denylist",
         REASON_METHOD_NOT_ALLOWED => "blocked-by-method-policy",
         REASON_MITM_REQUIRED => "blocked-by-mitm-required",
+        REASON_RULE_DENIED | REASON_RULE_NOT_ALLOWED => "blocked-by-request-rule",
+        REASON_SECRET_EXFILTRATION => "blocked-by-secret-guard",
+        REASON_RATE_LIMITED => "blocked-by-rate-limit",
+        REASON_BYTE_QUOTA_EXCEEDED => "blocked-by-byte-quota",
         _ => "blocked-by-policy",
     }
 }
@@ -65,19 +75,37 @@ pub fn blocked_message(reason: &str) -> &'static str {
         REASON_DENIED => "Domain denied by the sandbox policy.",
         REASON_METHOD_NOT_ALLOWED => "Method not allowed in limited mode.",
         REASON_MITM_REQUIRED => "MITM required for limited HTTPS.",
+        REASON_RULE_DENIED => "Request denied by a network request rule.",
+        REASON_RULE_NOT_ALLOWED => "Request does not match any allowed network request rule.",
+        REASON_SECRET_EXFILTRATION => {
+            "Request appears to contain a secret and the host is not allowed to receive secrets."
+        }
+        REASON_RATE_LIMITED => "Request rate limit exceeded; wait before retrying.",
+        REASON_BYTE_QUOTA_EXCEEDED => {
+            "Network byte quota exhausted; no further requests are allowed."
+        }
         REASON_PROXY_DISABLED => "network proxy is disabled",
         _ => "Request blocked by network policy.",
     }
 }
 
 pub fn blocked_text_response(reason: &str) -> Response {
-    Response::builder()
+    let resp = Response::builder()
         .status(StatusCode::FORBIDDEN)
         .header("content-type", "text/plain")
         .header("x-proxy-error", blocked_header_value(reason))
         .body(Body::from(blocked_message(reason)))
-        .unwrap_or_else(|_| Response::new(Body::from("blocked")))
+        .unwrap_or_else(|_| Response::new(Body::from("blocked")));
+    with_block_reason(resp, reason)
 }
+
+/// Tag a proxy-generated block response with its reason code so HAR logs can record it.
+pub(crate) fn with_block_reason(mut resp: Response, reason: &str) -> Response {
+    resp.extensions_mut()
+        .insert(HarBlockReason(reason.to_string()));
+    resp
+}
+
 pub fn blocked_message_with_policy(reason: &str, details: &PolicyDecisionDetails<'_>) -> String {
     let _ = (details.reason, details.host);
     blocked_message(reason).to_string()
@@ -87,12 +115,13 @@ pub fn blocked_text_response_with_policy(
     reason: &str,
     details: &PolicyDecisionDetails<'_>,
 ) -> Response {
-    Response::builder()
+    let resp = Response::builder()
         .status(StatusCode::FORBIDDEN)
         .header("content-type", "text/plain")
         .header("x-proxy-error", blocked_header_value(reason))
         .body(Body::from(blocked_message_with_policy(reason, details)))
-        .unwrap_or_else(|_| Response::new(Body::from("blocked")))
+        .unwrap_or_else(|_| Response::new(Body::from("blocked")));
+    with_block_reason(resp, reason)
 }
 
 #[cfg(test)]
@@ -1,7 +1,12 @@
+use crate::budgets::BudgetDecision;
+use crate::budgets::BudgetUsageTracker;
+use crate::budgets::NetworkBudgetUsage;
+use crate::budgets::NetworkBudgets;
 use crate::config::NetworkDomainPermission;
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
 use crate::config::ValidatedUnixSocketPath;
+use crate::har::HarRecorder;
 use crate::mitm::MitmState;
 use crate::policy::Host;
 use crate::policy::is_loopback_host;
@@ -10,6 +15,14 @@ use crate::policy::normalize_host;
 use crate::reasons::REASON_DENIED;
 use crate::reasons::REASON_NOT_ALLOWED;
 use crate::reasons::REASON_NOT_ALLOWED_LOCAL;
+use crate::request_rules::RequestRuleDecision;
+use crate::request_rules::RequestRuleTarget;
+use crate::request_rules::RequestRules;
+use crate::request_rules::TunnelRuleDecision;
+use crate::secret_guard::MIN_GUARDED_SECRET_LEN;
+use crate::secret_guard::SecretGuard;
+use crate::secret_guard::SecretScanner;
+use crate::secret_guard::guarded_secret_forms;
 use crate::state::NetworkProxyConstraintError;
 use crate::state::NetworkProxyConstraints;
 use crate::state::build_config_state;
@@ -156,6 +169,9 @@ pub struct ConfigState {
     pub config: NetworkProxyConfig,
     pub allow_set: GlobSet,
     pub deny_set: GlobSet,
+    pub request_rules: RequestRules,
+    pub secret_guard: SecretGuard,
+    pub budgets: NetworkBudgets,
     pub mitm: Option<Arc<MitmState>>,
     pub constraints: NetworkProxyConstraints,
     pub blocked: VecDeque<BlockedRequest>,
@@ -202,6 +218,9 @@ pub struct NetworkProxyState {
     reloader: Arc<dyn ConfigReloader>,
     blocked_request_observer: Arc<RwLock<Option<Arc<dyn BlockedRequestObserver>>>>,
     audit_metadata: NetworkProxyAuditMetadata,
+    har_recorder: Arc<HarRecorder>,
+    guarded_secrets: Arc<RwLock<Arc<[String]>>>,
+    budget_usage: Arc<BudgetUsageTracker>,
 }
 
 impl std::fmt::Debug for NetworkProxyState {
@@ -219,6 +238,9 @@ impl Clone for NetworkProxyState {
             reloader: self.reloader.clone(),
             blocked_request_observer: self.blocked_request_observer.clone(),
             audit_metadata: self.audit_metadata.clone(),
+            har_recorder: self.har_recorder.clone(),
+            guarded_secrets: self.guarded_secrets.clone(),
+            budget_usage: self.budget_usage.clone(),
         }
     }
 }
@@ -264,11 +286,15 @@ impl NetworkProxyState {
         audit_metadata: NetworkProxyAuditMetadata,
         blocked_request_observer: Option<Arc<dyn BlockedRequestObserver>>,
     ) -> Self {
+        let har_recorder = Arc::new(HarRecorder::new(audit_metadata.conversation_id.as_deref()));
         Self {
             state: Arc::new(RwLock::new(state)),
             reloader,
             blocked_request_observer: Arc::new(RwLock::new(blocked_request_observer)),
             audit_metadata,
+            har_recorder,
+            guarded_secrets: Arc::new(RwLock::new(Arc::from(Vec::new()))),
+            budget_usage: Arc::new(BudgetUsageTracker::default()),
         }
     }
 
@@ -280,6 +306,21 @@ impl NetworkProxyState {
         *observer = blocked_request_observer;
     }
 
+    /// Replace the secret values the secret guard looks for in outbound requests, such as
+    /// `codex-secrets` entries and the user's API keys. Values shorter than 8 characters are
+    /// ignored; base64 forms of the rest are matched as well.
+    pub async fn set_guarded_secrets(&self, secrets: Vec<String>) {
+        let mut secrets: Vec<String> = secrets
+            .into_iter()
+            .filter(|secret| secret.len() >= MIN_GUARDED_SECRET_LEN)
+            .flat_map(guarded_secret_forms)
+            .collect();
+        secrets.sort();
+        secrets.dedup();
+        let mut guard = self.guarded_secrets.write().await;
+        *guard = Arc::from(secrets);
+    }
+
     pub fn audit_metadata(&self) -> &NetworkProxyAuditMetadata {
         &self.audit_metadata
     }
@@ -307,6 +348,16 @@ impl NetworkProxyState {
         Ok(guard.config.network.enabled)
     }
 
+    /// The HAR recorder for this thread and the body capture cap, when HAR recording is enabled.
+    pub(crate) async fn har_recorder(&self) -> Result<Option<(Arc<HarRecorder>, usize)>> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        let har = &guard.config.network.har;
+        Ok(har
+            .enabled
+            .then(|| (self.har_recorder.clone(), har.max_body_bytes)))
+    }
+
     pub async fn force_reload(&self) -> Result<()> {
         let previous_cfg = {
             let guard = self.state.read().await;
@@ -526,6 +577,59 @@ impl NetworkProxyState {
         Ok(guard.config.network.mode.allows_method(method))
     }
 
+    pub(crate) async fn request_rule_decision(
+        &self,
+        target: &RequestRuleTarget<'_>,
+    ) -> Result<RequestRuleDecision> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard.request_rules.evaluate(target))
+    }
+
+    pub(crate) async fn tunnel_rule_decision(
+        &self,
+        scheme: Option<&str>,
+        host: &str,
+    ) -> Result<TunnelRuleDecision> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(guard.request_rules.evaluate_tunnel(scheme, host))
+    }
+
+    /// Secret scanner for outbound requests to `host`, when the secret guard applies to it.
+    pub(crate) async fn secret_scanner(&self, host: &str) -> Result<Option<SecretScanner>> {
+        self.reload_if_needed().await?;
+        let known_secrets = self.guarded_secrets.read().await.clone();
+        let guard = self.state.read().await;
+        Ok(guard.secret_guard.scanner_for_host(host, known_secrets))
+    }
+
+    /// Count a request or tunnel to `host` against `network.budgets`, or report the exhausted
+    /// budget.
+    pub(crate) async fn admit_budget_request(&self, host: &str) -> Result<BudgetDecision> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(self
+            .budget_usage
+            .admit(&guard.budgets, host, /*count_request*/ true))
+    }
+
+    /// Check the byte budgets for a datagram to `host`; datagrams do not count as requests.
+    pub(crate) async fn admit_budget_datagram(&self, host: &str) -> Result<BudgetDecision> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(self
+            .budget_usage
+            .admit(&guard.budgets, host, /*count_request*/ false))
+    }
+
+    /// Current usage of the thread-wide budget and each `network.budgets.hosts` entry.
+    pub async fn budget_usage(&self) -> Result<Vec<NetworkBudgetUsage>> {
+        self.reload_if_needed().await?;
+        let guard = self.state.read().await;
+        Ok(self.budget_usage.usage(&guard.budgets))
+    }
+
     pub async fn allow_upstream_proxy(&self) -> Result<bool> {
         self.reload_if_needed().await?;
         let guard = self.state.read().await;
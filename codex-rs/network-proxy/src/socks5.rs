@@ -1,3 +1,5 @@
+use crate::budgets::BudgetDecision;
+use crate::budgets::MeteredStream;
 use crate::config::NetworkMode;
 use crate::network_policy::BlockDecisionAuditEventArgs;
 use crate::network_policy::NetworkDecision;
@@ -12,6 +14,8 @@ use crate::network_policy::evaluate_host_policy;
 use crate::policy::normalize_host;
 use crate::reasons::REASON_METHOD_NOT_ALLOWED;
 use crate::reasons::REASON_PROXY_DISABLED;
+use crate::reasons::REASON_RULE_NOT_ALLOWED;
+use crate::request_rules::TunnelRuleDecision;
 use crate::responses::PolicyDecisionDetails;
 use crate::responses::blocked_message_with_policy;
 use crate::state::BlockedRequest;
@@ -133,7 +137,7 @@ async fn handle_socks5_tcp(
     req: TcpRequest,
     tcp_connector: TcpConnector,
     policy_decider: Option<Arc<dyn NetworkPolicyDecider>>,
-) -> Result<EstablishedClientConnection<TcpStream, TcpRequest>, BoxError> {
+) -> Result<EstablishedClientConnection<MeteredStream<TcpStream>, TcpRequest>, BoxError> {
     let app_state = req
         .extensions()
         .get::<Arc<NetworkProxyState>>()
@@ -280,17 +284,112 @@ async fn handle_socks5_tcp(
             warn!("SOCKS blocked (client={client}, host={host}, reason={reason})");
             return Err(policy_denied_error(&reason, &details).into());
         }
-        Ok(NetworkDecision::Allow) => {
-            let client = client.as_deref().unwrap_or_default();
-            info!("SOCKS allowed (client={client}, host={host}, port={port})");
-        }
+        Ok(NetworkDecision::Allow) => {}
         Err(err) => {
             error!("failed to evaluate host: {err}");
             return Err(io::Error::other("proxy error").into());
         }
     }
 
-    tcp_connector.serve(req).await
+    // SOCKS5 cannot be inspected, so rules that depend on the path or method block the tunnel.
+    let rule_block_reason = match app_state.tunnel_rule_decision(/*scheme*/ None, &host).await {
+        Ok(TunnelRuleDecision::Allow) => None,
+        Ok(TunnelRuleDecision::Deny(reason)) => Some(reason),
+        Ok(TunnelRuleDecision::InspectionRequired) => Some(REASON_RULE_NOT_ALLOWED),
+        Err(err) => {
+            error!("failed to evaluate request rules: {err}");
+            return Err(io::Error::other("proxy error").into());
+        }
+    };
+    if let Some(reason) = rule_block_reason {
+        emit_socks_block_decision_audit_event(
+            &app_state,
+            NetworkDecisionSource::BaselinePolicy,
+            reason,
+            NetworkProtocol::Socks5Tcp,
+            host.as_str(),
+            port,
+            client.as_deref(),
+        );
+        let details = PolicyDecisionDetails {
+            decision: NetworkPolicyDecision::Deny,
+            reason,
+            source: NetworkDecisionSource::BaselinePolicy,
+            protocol: NetworkProtocol::Socks5Tcp,
+            host: &host,
+            port,
+        };
+        let _ = app_state
+            .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                host: host.clone(),
+                reason: reason.to_string(),
+                client: client.clone(),
+                method: None,
+                mode: None,
+                protocol: "socks5".to_string(),
+                decision: Some(details.decision.as_str().to_string()),
+                source: Some(details.source.as_str().to_string()),
+                port: Some(port),
+            }))
+            .await;
+        let client = client.as_deref().unwrap_or_default();
+        warn!("SOCKS blocked by request rules (client={client}, host={host}, reason={reason})");
+        return Err(policy_denied_error(reason, &details).into());
+    }
+
+    // The upstream stream is metered so SOCKS5 traffic counts against the byte budgets too.
+    let meter = match app_state.admit_budget_request(&host).await {
+        Ok(BudgetDecision::Allow(meter)) => meter,
+        Ok(BudgetDecision::Blocked { reason, scope }) => {
+            emit_socks_block_decision_audit_event(
+                &app_state,
+                NetworkDecisionSource::BaselinePolicy,
+                reason,
+                NetworkProtocol::Socks5Tcp,
+                host.as_str(),
+                port,
+                client.as_deref(),
+            );
+            let details = PolicyDecisionDetails {
+                decision: NetworkPolicyDecision::Deny,
+                reason,
+                source: NetworkDecisionSource::BaselinePolicy,
+                protocol: NetworkProtocol::Socks5Tcp,
+                host: &host,
+                port,
+            };
+            let _ = app_state
+                .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                    host: host.clone(),
+                    reason: reason.to_string(),
+                    client: client.clone(),
+                    method: None,
+                    mode: None,
+                    protocol: "socks5".to_string(),
+                    decision: Some(details.decision.as_str().to_string()),
+                    source: Some(details.source.as_str().to_string()),
+                    port: Some(port),
+                }))
+                .await;
+            let client = client.as_deref().unwrap_or_default();
+            warn!(
+                "SOCKS blocked by network budget (client={client}, host={host}, reason={reason}, budget={scope})"
+            );
+            return Err(policy_denied_error(reason, &details).into());
+        }
+        Err(err) => {
+            error!("failed to evaluate network budgets: {err}");
+            return Err(io::Error::other("proxy error").into());
+        }
+    };
+
+    let client = client.as_deref().unwrap_or_default();
+    info!("SOCKS allowed (client={client}, host={host}, port={port})");
+    let EstablishedClientConnection { input, conn } = tcp_connector.serve(req).await?;
+    Ok(EstablishedClientConnection {
+        input,
+        conn: MeteredStream::new(conn, meter),
+    })
 }
 
 async fn inspect_socks5_udp(
@@ -438,14 +537,62 @@ async fn inspect_socks5_udp(
                 .await;
             let client = client.as_deref().unwrap_or_default();
             warn!("SOCKS UDP blocked (client={client}, host={host}, reason={reason})");
-            Err(policy_denied_error(&reason, &details))
+            return Err(policy_denied_error(&reason, &details));
         }
-        Ok(NetworkDecision::Allow) => Ok(RelayResponse {
-            maybe_payload: Some(payload),
-            extensions,
-        }),
+        Ok(NetworkDecision::Allow) => {}
         Err(err) => {
             error!("failed to evaluate UDP host: {err}");
+            return Err(io::Error::other("proxy error"));
+        }
+    }
+
+    match state.admit_budget_datagram(&host).await {
+        Ok(BudgetDecision::Allow(meter)) => {
+            meter.add(payload.len());
+            Ok(RelayResponse {
+                maybe_payload: Some(payload),
+                extensions,
+            })
+        }
+        Ok(BudgetDecision::Blocked { reason, scope }) => {
+            emit_socks_block_decision_audit_event(
+                &state,
+                NetworkDecisionSource::BaselinePolicy,
+                reason,
+                NetworkProtocol::Socks5Udp,
+                host.as_str(),
+                port,
+                client.as_deref(),
+            );
+            let details = PolicyDecisionDetails {
+                decision: NetworkPolicyDecision::Deny,
+                reason,
+                source: NetworkDecisionSource::BaselinePolicy,
+                protocol: NetworkProtocol::Socks5Udp,
+                host: &host,
+                port,
+            };
+            let _ = state
+                .record_blocked(BlockedRequest::new(BlockedRequestArgs {
+                    host: host.clone(),
+                    reason: reason.to_string(),
+                    client: client.clone(),
+                    method: None,
+                    mode: None,
+                    protocol: "socks5-udp".to_string(),
+                    decision: Some(details.decision.as_str().to_string()),
+                    source: Some(details.source.as_str().to_string()),
+                    port: Some(port),
+                }))
+                .await;
+            let client = client.as_deref().unwrap_or_default();
+            warn!(
+                "SOCKS UDP blocked by network budget (client={client}, host={host}, reason={reason}, budget={scope})"
+            );
+            Err(policy_denied_error(reason, &details))
+        }
+        Err(err) => {
+            error!("failed to evaluate network budgets: {err}");
             Err(io::Error::other("proxy error"))
         }
     }
@@ -484,12 +631,14 @@ fn policy_denied_error(reason: &str, details: &PolicyDecisionDetails<'_>) -> io:
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::NetworkBudgetSettings;
     use crate::config::NetworkMode;
     use crate::config::NetworkProxyConfig;
     use crate::config::NetworkProxySettings;
     use crate::network_policy::test_support::POLICY_DECISION_EVENT_NAME;
     use crate::network_policy::test_support::capture_events;
     use crate::network_policy::test_support::find_event_by_name;
+    use crate::reasons::REASON_BYTE_QUOTA_EXCEEDED;
     use crate::runtime::ConfigReloader;
     use crate::runtime::ConfigState;
     use crate::state::NetworkProxyConstraints;
@@ -504,6 +653,8 @@ mod tests {
     use std::net::IpAddr;
     use std::net::Ipv4Addr;
     use std::sync::Arc;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener as TokioTcpListener;
 
     #[derive(Clone)]
     struct StaticReloader {
@@ -613,4 +764,63 @@ mod tests {
         assert_eq!(event.field("http.request.method"), Some("none"));
         assert_eq!(event.field("client.address"), Some("unknown"));
     }
+
+    #[tokio::test]
+    async fn handle_socks5_tcp_meters_bytes_and_stops_at_the_byte_quota() {
+        let target_listener = TokioTcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .expect("target listener should bind");
+        let target_addr = target_listener
+            .local_addr()
+            .expect("target listener should expose local addr");
+        let target_task = tokio::spawn(async move {
+            let (mut stream, _) = target_listener
+                .accept()
+                .await
+                .expect("target listener should accept");
+            let _ = tokio::io::copy(&mut stream, &mut tokio::io::sink()).await;
+        });
+        let state = state_for_settings({
+            let mut network = NetworkProxySettings {
+                enabled: true,
+                mode: NetworkMode::Full,
+                allow_local_binding: true,
+                budgets: NetworkBudgetSettings {
+                    max_total_bytes: Some(16),
+                    ..NetworkBudgetSettings::default()
+                },
+                ..NetworkProxySettings::default()
+            };
+            network.set_allowed_domains(vec!["127.0.0.1".to_string()]);
+            network
+        });
+        let mut request = TcpRequest::new(
+            HostWithPort::try_from(format!("127.0.0.1:{}", target_addr.port()).as_str())
+                .expect("valid authority"),
+        );
+        request.extensions_mut().insert(state.clone());
+
+        let EstablishedClientConnection { mut conn, .. } = handle_socks5_tcp(
+            request,
+            TcpConnector::default(),
+            /*policy_decider*/ None,
+        )
+        .await
+        .expect("SOCKS5 connection should be allowed");
+        conn.write_all(&[0; 32])
+            .await
+            .expect("quota has room when the write starts");
+        let err = conn
+            .write_all(b"more")
+            .await
+            .expect_err("writes should stop once the quota is used up");
+
+        assert_eq!(err.to_string(), REASON_BYTE_QUOTA_EXCEEDED);
+        let usage = state.budget_usage().await.expect("budget usage");
+        assert_eq!(usage[0].total_bytes, 32);
+        assert_eq!(usage[0].requests_last_minute, 1);
+        drop(conn);
+        target_task.abort();
+        let _ = target_task.await;
+    }
 }
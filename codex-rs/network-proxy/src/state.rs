@@ -1,3 +1,4 @@
+use crate::budgets::NetworkBudgets;
 use crate::config::NetworkDomainPermissions;
 use crate::config::NetworkMode;
 use crate::config::NetworkProxyConfig;
@@ -7,7 +8,9 @@ use crate::policy::DomainPattern;
 use crate::policy::compile_allowlist_globset;
 use crate::policy::compile_denylist_globset;
 use crate::policy::is_global_wildcard_domain_pattern;
+use crate::request_rules::RequestRules;
 use crate::runtime::ConfigState;
+use crate::secret_guard::SecretGuard;
 use serde::Deserialize;
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -65,6 +68,9 @@ pub fn build_config_state(
         .map_err(NetworkProxyConstraintError::into_anyhow)?;
     let deny_set = compile_denylist_globset(&denied_domains)?;
     let allow_set = compile_allowlist_globset(&allowed_domains)?;
+    let request_rules = RequestRules::compile(&config.network.rules)?;
+    let secret_guard = SecretGuard::compile(&config.network.secret_guard)?;
+    let budgets = NetworkBudgets::compile(&config.network.budgets)?;
     let mitm = if config.network.mitm {
         Some(Arc::new(MitmState::new(
             config.network.allow_upstream_proxy,
@@ -76,6 +82,9 @@ pub fn build_config_state(
         config,
         allow_set,
         deny_set,
+        request_rules,
+        secret_guard,
+        budgets,
         mitm,
         constraints,
         blocked: std::collections::VecDeque::new(),
@@ -57,6 +57,27 @@ impl From<Vec<String>> for ExecPolicyAmendment {
     }
 }
 
+/// Where and for how long an approved [`ExecPolicyAmendment`] applies once it is saved.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecPolicyAmendmentScope {
+    /// The rule applies to every thread and directory until it is removed.
+    #[default]
+    Permanent,
+    /// The rule only applies to commands run by the thread that approved it.
+    Thread,
+    /// The rule only applies to commands run inside the approving turn's working directory.
+    Project,
+    /// The rule applies everywhere but expires one day after it is approved.
+    Day,
+}
+
+impl ExecPolicyAmendmentScope {
+    pub fn is_permanent(&self) -> bool {
+        matches!(self, Self::Permanent)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum NetworkApprovalProtocol {
@@ -306,6 +327,7 @@ impl ExecApprovalRequestEvent {
         if let Some(prefix) = proposed_execpolicy_amendment {
             decisions.push(ReviewDecision::ApprovedExecpolicyAmendment {
                 proposed_execpolicy_amendment: prefix.clone(),
+                scope: ExecPolicyAmendmentScope::Permanent,
             });
         }
         decisions.push(ReviewDecision::Abort);
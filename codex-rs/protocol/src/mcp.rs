@@ -150,6 +150,151 @@ pub struct CallToolResult {
     pub meta: Option<serde_json::Value>,
 }
 
+/// A prompt or prompt template that the server offers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct Prompt {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub arguments: Option<Vec<PromptArgument>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub icons: Option<Vec<serde_json::Value>>,
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub meta: Option<serde_json::Value>,
+}
+
+/// Describes an argument that a prompt can accept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptArgument {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub required: Option<bool>,
+}
+
+/// A single message in the result of rendering a prompt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptMessage {
+    /// Either `user` or `assistant`.
+    pub role: String,
+    pub content: serde_json::Value,
+}
+
+/// The server's response to a `prompts/get` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPromptResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+    pub messages: Vec<PromptMessage>,
+}
+
+/// A message in an MCP `sampling/createMessage` request or result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct SamplingMessage {
+    /// Either `user` or `assistant`.
+    pub role: String,
+    /// A single content block, or a list of them.
+    pub content: serde_json::Value,
+}
+
+/// A server's request, via `sampling/createMessage`, for the client to call its model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMessageRequest {
+    pub messages: Vec<SamplingMessage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub model_preferences: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub system_prompt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub include_context: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub temperature: Option<f64>,
+    pub max_tokens: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub stop_sequences: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// The client's response to a `sampling/createMessage` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMessageResult {
+    /// Always `assistant`.
+    pub role: String,
+    pub content: serde_json::Value,
+    /// Name of the model that produced the message.
+    pub model: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub stop_reason: Option<String>,
+}
+
+impl SamplingMessage {
+    /// Concatenates the text blocks in this message, skipping non-text content.
+    pub fn text(&self) -> String {
+        let blocks: Vec<&serde_json::Value> = match &self.content {
+            serde_json::Value::Array(blocks) => blocks.iter().collect(),
+            block => vec![block],
+        };
+        blocks
+            .into_iter()
+            .filter_map(|block| block.get("text").and_then(serde_json::Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+impl PromptArgument {
+    pub fn is_required(&self) -> bool {
+        self.required.unwrap_or(false)
+    }
+}
+
+impl GetPromptResult {
+    /// Concatenates the text content of every message, skipping non-text
+    /// content such as images or embedded resources.
+    pub fn text(&self) -> String {
+        self.messages
+            .iter()
+            .filter_map(|message| {
+                message
+                    .content
+                    .get("text")
+                    .and_then(serde_json::Value::as_str)
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
 // === Adapter helpers ===
 //
 // These types and conversions intentionally live in `codex-protocol` so other crates can convert
@@ -316,6 +461,24 @@ impl Resource {
     }
 }
 
+impl Prompt {
+    pub fn from_mcp_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+impl GetPromptResult {
+    pub fn from_mcp_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+impl CreateMessageRequest {
+    pub fn from_mcp_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+}
+
 impl ResourceTemplate {
     pub fn from_mcp_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
         Ok(serde_json::from_value::<ResourceTemplateSerde>(value)?.into())
@@ -357,4 +520,38 @@ mod tests {
         let parsed = Resource::from_mcp_value(resource).expect("should deserialize");
         assert_eq!(parsed.size, None);
     }
+
+    #[test]
+    fn get_prompt_result_text_joins_text_messages() {
+        let result = GetPromptResult::from_mcp_value(serde_json::json!({
+            "messages": [
+                { "role": "user", "content": { "type": "text", "text": "first" } },
+                { "role": "user", "content": { "type": "image", "data": "", "mimeType": "image/png" } },
+                { "role": "assistant", "content": { "type": "text", "text": "second" } },
+            ],
+        }))
+        .expect("should deserialize");
+
+        assert_eq!(result.description, None);
+        assert_eq!(result.text(), "first\n\nsecond");
+    }
+
+    #[test]
+    fn sampling_message_text_accepts_single_or_multiple_blocks() {
+        let single = SamplingMessage {
+            role: "user".to_string(),
+            content: serde_json::json!({ "type": "text", "text": "hello" }),
+        };
+        let multiple = SamplingMessage {
+            role: "user".to_string(),
+            content: serde_json::json!([
+                { "type": "text", "text": "first" },
+                { "type": "image", "data": "", "mimeType": "image/png" },
+                { "type": "text", "text": "second" },
+            ]),
+        };
+
+        assert_eq!(single.text(), "hello");
+        assert_eq!(multiple.text(), "first\n\nsecond");
+    }
 }
@@ -29,6 +29,8 @@ use crate::dynamic_tools::DynamicToolResponse;
 use crate::dynamic_tools::DynamicToolSpec;
 use crate::items::TurnItem;
 use crate::mcp::CallToolResult;
+use crate::mcp::CreateMessageRequest;
+use crate::mcp::CreateMessageResult;
 use crate::mcp::RequestId;
 use crate::mcp::Resource as McpResource;
 use crate::mcp::ResourceTemplate as McpResourceTemplate;
@@ -65,6 +67,7 @@ pub use crate::approvals::ApplyPatchApprovalRequestEvent;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
+pub use crate::approvals::ExecPolicyAmendmentScope;
 pub use crate::approvals::GuardianAssessmentAction;
 pub use crate::approvals::GuardianAssessmentDecisionSource;
 pub use crate::approvals::GuardianAssessmentEvent;
@@ -1480,6 +1483,11 @@ pub enum EventMsg {
 
     McpToolCallEnd(McpToolCallEndEvent),
 
+    /// An MCP server asked Codex to sample the model via `sampling/createMessage`.
+    McpSamplingBegin(McpSamplingBeginEvent),
+
+    McpSamplingEnd(McpSamplingEndEvent),
+
     WebSearchBegin(WebSearchBeginEvent),
 
     WebSearchEnd(WebSearchEndEvent),
@@ -1560,6 +1568,12 @@ pub enum EventMsg {
     /// Notification that skill data may have been updated and clients may want to reload.
     SkillsUpdateAvailable,
 
+    /// Notification that an MCP server's prompt list changed and clients may want to reload.
+    McpPromptsChanged(McpPromptsChangedEvent),
+
+    /// Notification that a subscribed MCP resource changed.
+    McpResourceUpdated(McpResourceUpdatedEvent),
+
     PlanUpdate(UpdatePlanArgs),
 
     TurnAborted(TurnAbortedEvent),
@@ -1616,6 +1630,13 @@ pub enum HookEventName {
     SessionStart,
     UserPromptSubmit,
     Stop,
+    PreCompact,
+    PostCompact,
+    SubagentStart,
+    SubagentStop,
+    PreApplyPatch,
+    PostApplyPatch,
+    TurnError,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -1624,6 +1645,7 @@ pub enum HookHandlerType {
     Command,
     Prompt,
     Agent,
+    Server,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -1672,6 +1694,7 @@ pub enum HookOutputEntryKind {
     Feedback,
     Context,
     Error,
+    Rewrite,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -2460,6 +2483,30 @@ pub struct DynamicToolCallResponseEvent {
     pub duration: Duration,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct McpSamplingBeginEvent {
+    /// Identifier so this can be paired with the McpSamplingEnd event.
+    pub call_id: String,
+    /// Name of the MCP server that sent the request.
+    pub server: String,
+    pub request: CreateMessageRequest,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct McpSamplingEndEvent {
+    /// Identifier for the corresponding McpSamplingBegin that finished.
+    pub call_id: String,
+    /// Name of the MCP server that sent the request.
+    pub server: String,
+    pub request: CreateMessageRequest,
+    #[ts(type = "string")]
+    pub duration: Duration,
+    /// Message returned to the server, or why the request was declined or failed.
+    pub result: Result<CreateMessageResult, String>,
+    /// Tokens spent on the model call, when it was made.
+    pub token_usage: Option<TokenUsage>,
+}
+
 impl McpToolCallEndEvent {
     pub fn is_success(&self) -> bool {
         match &self.result {
@@ -3354,6 +3401,20 @@ pub struct McpListToolsResponseEvent {
     pub auth_statuses: std::collections::HashMap<String, McpAuthStatus>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpPromptsChangedEvent {
+    /// Server whose prompts changed.
+    pub server: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpResourceUpdatedEvent {
+    /// Server that reported the change.
+    pub server: String,
+    /// URI of the subscribed resource that changed.
+    pub uri: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct McpStartupUpdateEvent {
     /// Server name being started.
@@ -3679,6 +3740,12 @@ pub enum ReviewDecision {
     /// amendment so future matching commands are permitted.
     ApprovedExecpolicyAmendment {
         proposed_execpolicy_amendment: ExecPolicyAmendment,
+        /// Limits where and for how long the saved rule applies.
+        #[serde(
+            default,
+            skip_serializing_if = "ExecPolicyAmendmentScope::is_permanent"
+        )]
+        scope: ExecPolicyAmendmentScope,
     },
 
     /// User has approved this request and wants future prompts in the same
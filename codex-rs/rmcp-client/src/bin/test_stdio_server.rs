@@ -4,6 +4,8 @@ use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::sync::Arc;
 use std::sync::OnceLock;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use rmcp::ErrorData as McpError;
@@ -11,11 +13,15 @@ use rmcp::ServiceExt;
 use rmcp::handler::server::ServerHandler;
 use rmcp::model::CallToolRequestParams;
 use rmcp::model::CallToolResult;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::JsonObject;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
 use rmcp::model::PaginatedRequestParams;
+use rmcp::model::Prompt;
 use rmcp::model::RawResource;
 use rmcp::model::RawResourceTemplate;
 use rmcp::model::ReadResourceRequestParams;
@@ -23,13 +29,19 @@ use rmcp::model::ReadResourceResult;
 use rmcp::model::Resource;
 use rmcp::model::ResourceContents;
 use rmcp::model::ResourceTemplate;
+use rmcp::model::ResourceUpdatedNotificationParam;
 use rmcp::model::ServerCapabilities;
 use rmcp::model::ServerInfo;
+use rmcp::model::SubscribeRequestParams;
 use rmcp::model::Tool;
 use rmcp::model::ToolAnnotations;
+use rmcp::model::UnsubscribeRequestParams;
+use rmcp::service::Peer;
+use rmcp::service::RoleServer;
 use serde::Deserialize;
 use serde_json::json;
 use tokio::sync::Barrier;
+use tokio::sync::Mutex;
 use tokio::task;
 use tokio::time::sleep;
 
@@ -38,10 +50,15 @@ struct TestToolServer {
     tools: Arc<Vec<Tool>>,
     resources: Arc<Vec<Resource>>,
     resource_templates: Arc<Vec<ResourceTemplate>>,
+    prompts: Arc<Vec<Prompt>>,
+    memo_revision: Arc<AtomicU64>,
+    /// Peer subscribed to the memo resource via `resources/subscribe`.
+    memo_subscriber: Arc<Mutex<Option<Peer<RoleServer>>>>,
 }
 
 const MEMO_URI: &str = "memo://codex/example-note";
 const MEMO_CONTENT: &str = "This is a sample MCP resource served by the rmcp test server.";
+const TRIAGE_PROMPT_NAME: &str = "triage_incident";
 const SANDBOX_STATE_META_CAPABILITY: &str = "codex/sandbox-state-meta";
 const SMALL_PNG_BASE64: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR4nGP4z8DwHwAFAAH/iZk9HQAAAABJRU5ErkJggg==";
 
@@ -72,14 +89,21 @@ impl TestToolServer {
             Self::sync_tool(),
             Self::image_tool(),
             Self::image_scenario_tool(),
+            Self::sample_tool(),
+            Self::roots_tool(),
+            Self::update_memo_tool(),
             sandbox_meta_tool,
         ];
         let resources = vec![Self::memo_resource()];
         let resource_templates = vec![Self::memo_template()];
+        let prompts = vec![Self::triage_prompt()];
         Self {
             tools: Arc::new(tools),
             resources: Arc::new(resources),
             resource_templates: Arc::new(resource_templates),
+            prompts: Arc::new(prompts),
+            memo_revision: Arc::new(AtomicU64::new(0)),
+            memo_subscriber: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -286,6 +310,60 @@ impl TestToolServer {
         tool
     }
 
+    fn sample_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "prompt": { "type": "string" },
+                "max_tokens": { "type": "integer" }
+            },
+            "required": ["prompt"],
+            "additionalProperties": false
+        }))
+        .expect("sample tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("sample"),
+            Cow::Borrowed(
+                "Ask the client to sample its model with the prompt via sampling/createMessage.",
+            ),
+            Arc::new(schema),
+        )
+    }
+
+    fn roots_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("roots tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("roots"),
+            Cow::Borrowed("Return the client's workspace roots via roots/list."),
+            Arc::new(schema),
+        )
+    }
+
+    fn update_memo_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("update_memo tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("update_memo"),
+            Cow::Borrowed("Append a revision line to the memo resource and notify subscribers."),
+            Arc::new(schema),
+        )
+    }
+
     fn memo_resource() -> Resource {
         let raw = RawResource {
             uri: MEMO_URI.to_string(),
@@ -314,8 +392,35 @@ impl TestToolServer {
         ResourceTemplate::new(raw, None)
     }
 
-    fn memo_text() -> &'static str {
-        MEMO_CONTENT
+    fn memo_text(&self) -> String {
+        let revision = self.memo_revision.load(Ordering::SeqCst);
+        if revision == 0 {
+            MEMO_CONTENT.to_string()
+        } else {
+            format!("{MEMO_CONTENT}\nRevision {revision}.")
+        }
+    }
+
+    fn triage_prompt() -> Prompt {
+        #[expect(clippy::expect_used)]
+        serde_json::from_value(json!({
+            "name": TRIAGE_PROMPT_NAME,
+            "title": "Triage Incident",
+            "description": "Walk through triaging a production incident.",
+            "arguments": [
+                {
+                    "name": "service",
+                    "description": "Service that is paging.",
+                    "required": true
+                },
+                {
+                    "name": "severity",
+                    "description": "Incident severity, e.g. sev2.",
+                    "required": false
+                }
+            ]
+        }))
+        .expect("triage prompt should deserialize")
     }
 }
 
@@ -343,6 +448,17 @@ struct SyncBarrierArgs {
     timeout_ms: u64,
 }
 
+#[derive(Debug, Deserialize)]
+struct SampleArgs {
+    prompt: String,
+    #[serde(default = "default_sample_max_tokens")]
+    max_tokens: u32,
+}
+
+fn default_sample_max_tokens() -> u32 {
+    64
+}
+
 #[derive(Debug, Deserialize)]
 struct SyncArgs {
     #[serde(default)]
@@ -394,6 +510,8 @@ impl ServerHandler for TestToolServer {
             .enable_tools()
             .enable_tool_list_changed()
             .enable_resources()
+            .enable_resources_subscribe()
+            .enable_prompts()
             .build();
         capabilities.experimental = Some(BTreeMap::from([(
             SANDBOX_STATE_META_CAPABILITY.to_string(),
@@ -458,7 +576,7 @@ impl ServerHandler for TestToolServer {
                 contents: vec![ResourceContents::TextResourceContents {
                     uri,
                     mime_type: Some("text/plain".to_string()),
-                    text: Self::memo_text().to_string(),
+                    text: self.memo_text(),
                     meta: None,
                 }],
             })
@@ -470,6 +588,83 @@ impl ServerHandler for TestToolServer {
         }
     }
 
+    async fn subscribe(
+        &self,
+        SubscribeRequestParams { uri, .. }: SubscribeRequestParams,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<(), McpError> {
+        if uri != MEMO_URI {
+            return Err(McpError::resource_not_found(
+                "resource_not_found",
+                Some(json!({ "uri": uri })),
+            ));
+        }
+        *self.memo_subscriber.lock().await = Some(context.peer);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        UnsubscribeRequestParams { uri, .. }: UnsubscribeRequestParams,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<(), McpError> {
+        if uri == MEMO_URI {
+            *self.memo_subscriber.lock().await = None;
+        }
+        Ok(())
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult {
+            prompts: (*self.prompts).clone(),
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParams {
+            name, arguments, ..
+        }: GetPromptRequestParams,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        if name != TRIAGE_PROMPT_NAME {
+            return Err(McpError::invalid_params(
+                format!("unknown prompt: {name}"),
+                None,
+            ));
+        }
+        let arguments = arguments.unwrap_or_default();
+        let Some(service) = arguments.get("service").and_then(serde_json::Value::as_str) else {
+            return Err(McpError::invalid_params(
+                "missing required argument: service",
+                None,
+            ));
+        };
+        let severity = arguments
+            .get("severity")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("unknown");
+        serde_json::from_value(json!({
+            "description": "Incident triage checklist",
+            "messages": [
+                {
+                    "role": "user",
+                    "content": {
+                        "type": "text",
+                        "text": format!("Triage the {severity} incident affecting {service}."),
+                    }
+                }
+            ]
+        }))
+        .map_err(|err| McpError::internal_error(err.to_string(), None))
+    }
+
     async fn call_tool(
         &self,
         request: CallToolRequestParams,
@@ -550,6 +745,12 @@ impl ServerHandler for TestToolServer {
                 let args = Self::parse_call_args::<SyncArgs>(&request, "sync")?;
                 Self::sync_result(args).await
             }
+            "sample" => {
+                let args = Self::parse_call_args::<SampleArgs>(&request, "sample")?;
+                Self::sample_result(args, &context.peer).await
+            }
+            "roots" => Self::roots_result(&context.peer).await,
+            "update_memo" => self.update_memo_result().await,
             other => Err(McpError::invalid_params(
                 format!("unknown tool: {other}"),
                 None,
@@ -648,6 +849,72 @@ impl TestToolServer {
         Ok(CallToolResult::success(content))
     }
 
+    async fn sample_result(
+        args: SampleArgs,
+        peer: &rmcp::service::Peer<rmcp::service::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = serde_json::from_value(json!({
+            "messages": [
+                { "role": "user", "content": { "type": "text", "text": args.prompt } }
+            ],
+            "systemPrompt": "Answer in one short sentence.",
+            "maxTokens": args.max_tokens,
+        }))
+        .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+        let result = peer
+            .create_message(params)
+            .await
+            .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+        let result = serde_json::to_value(result)
+            .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+
+        Ok(CallToolResult {
+            content: Vec::new(),
+            structured_content: Some(json!({
+                "text": result["content"]["text"],
+                "model": result["model"],
+            })),
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+
+    async fn update_memo_result(&self) -> Result<CallToolResult, McpError> {
+        let revision = self.memo_revision.fetch_add(1, Ordering::SeqCst) + 1;
+        if let Some(peer) = self.memo_subscriber.lock().await.as_ref() {
+            peer.notify_resource_updated(ResourceUpdatedNotificationParam {
+                uri: MEMO_URI.to_string(),
+            })
+            .await
+            .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+        }
+
+        Ok(CallToolResult {
+            content: Vec::new(),
+            structured_content: Some(json!({ "revision": revision })),
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+
+    async fn roots_result(
+        peer: &rmcp::service::Peer<rmcp::service::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let result = peer
+            .list_roots()
+            .await
+            .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+        let result = serde_json::to_value(result)
+            .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+
+        Ok(CallToolResult {
+            content: Vec::new(),
+            structured_content: Some(result),
+            is_error: Some(false),
+            meta: None,
+        })
+    }
+
     async fn sync_result(args: SyncArgs) -> Result<CallToolResult, McpError> {
         if let Some(delay) = args.sleep_before_ms
             && delay > 0
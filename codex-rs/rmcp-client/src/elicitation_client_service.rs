@@ -5,6 +5,7 @@ use rmcp::model::ClientInfo;
 use rmcp::model::ClientResult;
 use rmcp::model::CustomResult;
 use rmcp::model::ElicitationAction;
+use rmcp::model::ListRootsResult;
 use rmcp::model::Meta;
 use rmcp::model::RequestParamsMeta;
 use rmcp::model::ServerNotification;
@@ -14,12 +15,17 @@ use rmcp::service::RequestContext;
 use rmcp::service::Service;
 use serde::Serialize;
 use serde_json::Value;
+use tokio::sync::broadcast;
 
 use crate::logging_client_handler::LoggingClientHandler;
 use crate::rmcp_client::Elicitation;
 use crate::rmcp_client::ElicitationPauseState;
 use crate::rmcp_client::ElicitationResponse;
+use crate::rmcp_client::ListRoots;
+use crate::rmcp_client::McpServerChange;
+use crate::rmcp_client::Sampling;
 use crate::rmcp_client::SendElicitation;
+use crate::rmcp_client::SendSampling;
 
 const MCP_PROGRESS_TOKEN_META_KEY: &str = "progressToken";
 
@@ -27,6 +33,8 @@ const MCP_PROGRESS_TOKEN_META_KEY: &str = "progressToken";
 pub(crate) struct ElicitationClientService {
     handler: LoggingClientHandler,
     send_elicitation: Arc<SendElicitation>,
+    send_sampling: Option<Arc<SendSampling>>,
+    list_roots: Option<Arc<ListRoots>>,
     pause_state: ElicitationPauseState,
 }
 
@@ -34,15 +42,21 @@ impl ElicitationClientService {
     pub(crate) fn new(
         client_info: ClientInfo,
         send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
+        list_roots: Option<ListRoots>,
         pause_state: ElicitationPauseState,
+        server_changes: broadcast::Sender<McpServerChange>,
     ) -> Self {
         let send_elicitation = Arc::new(send_elicitation);
         Self {
             handler: LoggingClientHandler::new(
                 client_info,
                 clone_send_elicitation(Arc::clone(&send_elicitation)),
+                server_changes,
             ),
             send_elicitation,
+            send_sampling: send_sampling.map(Arc::new),
+            list_roots: list_roots.map(Arc::new),
             pause_state,
         }
     }
@@ -59,6 +73,20 @@ impl ElicitationClientService {
             .await
             .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
     }
+
+    async fn create_message(
+        &self,
+        send_sampling: &SendSampling,
+        request: Sampling,
+        context: RequestContext<RoleClient>,
+    ) -> Result<ClientResult, rmcp::ErrorData> {
+        // The server is blocked on this request, which may wait on user approval.
+        let _pause = self.pause_state.enter();
+        send_sampling(context.id, request)
+            .await
+            .map(ClientResult::CreateMessageResult)
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
 }
 
 fn clone_send_elicitation(send_elicitation: Arc<SendElicitation>) -> SendElicitation {
@@ -78,6 +106,34 @@ impl Service<RoleClient> for ElicitationClientService {
                 let result = elicitation_response_result(response)?;
                 Ok(ClientResult::CustomResult(result))
             }
+            ServerRequest::CreateMessageRequest(request) => match self.send_sampling.as_deref() {
+                Some(send_sampling) => {
+                    self.create_message(send_sampling, request.params, context)
+                        .await
+                }
+                // Without a sampler, fall back to the handler's "method not found" reply.
+                None => {
+                    <LoggingClientHandler as Service<RoleClient>>::handle_request(
+                        &self.handler,
+                        ServerRequest::CreateMessageRequest(request),
+                        context,
+                    )
+                    .await
+                }
+            },
+            ServerRequest::ListRootsRequest(request) => match self.list_roots.as_deref() {
+                Some(list_roots) => Ok(ClientResult::ListRootsResult(ListRootsResult {
+                    roots: list_roots(),
+                })),
+                None => {
+                    <LoggingClientHandler as Service<RoleClient>>::handle_request(
+                        &self.handler,
+                        ServerRequest::ListRootsRequest(request),
+                        context,
+                    )
+                    .await
+                }
+            },
             request => {
                 <LoggingClientHandler as Service<RoleClient>>::handle_request(
                     &self.handler,
@@ -28,9 +28,13 @@ pub use perform_oauth_login::perform_oauth_login_silent;
 pub use rmcp::model::ElicitationAction;
 pub use rmcp_client::Elicitation;
 pub use rmcp_client::ElicitationResponse;
+pub use rmcp_client::ListRoots;
 pub use rmcp_client::ListToolsWithConnectorIdResult;
+pub use rmcp_client::McpServerChange;
 pub use rmcp_client::RmcpClient;
+pub use rmcp_client::Sampling;
 pub use rmcp_client::SendElicitation;
+pub use rmcp_client::SendSampling;
 pub use rmcp_client::ToolWithConnectorId;
 pub use stdio_server_launcher::ExecutorStdioServerLauncher;
 pub use stdio_server_launcher::LocalStdioServerLauncher;
@@ -12,26 +12,39 @@ use rmcp::model::ProgressNotificationParam;
 use rmcp::model::ResourceUpdatedNotificationParam;
 use rmcp::service::NotificationContext;
 use rmcp::service::RequestContext;
+use tokio::sync::broadcast;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 use tracing::warn;
 
+use crate::rmcp_client::McpServerChange;
 use crate::rmcp_client::SendElicitation;
 
 #[derive(Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
     send_elicitation: Arc<SendElicitation>,
+    server_changes: broadcast::Sender<McpServerChange>,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo, send_elicitation: SendElicitation) -> Self {
+    pub(crate) fn new(
+        client_info: ClientInfo,
+        send_elicitation: SendElicitation,
+        server_changes: broadcast::Sender<McpServerChange>,
+    ) -> Self {
         Self {
             client_info,
             send_elicitation: Arc::new(send_elicitation),
+            server_changes,
         }
     }
+
+    fn notify_server_change(&self, change: McpServerChange) {
+        // Having no subscribers is fine; nobody is caching this listing yet.
+        let _ = self.server_changes.send(change);
+    }
 }
 
 impl ClientHandler for LoggingClientHandler {
@@ -74,18 +87,22 @@ impl ClientHandler for LoggingClientHandler {
         _context: NotificationContext<RoleClient>,
     ) {
         info!("MCP server resource updated (uri: {})", params.uri);
+        self.notify_server_change(McpServerChange::ResourceUpdated { uri: params.uri });
     }
 
     async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server resource list changed");
+        self.notify_server_change(McpServerChange::ResourceListChanged);
     }
 
     async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server tool list changed");
+        self.notify_server_change(McpServerChange::ToolListChanged);
     }
 
     async fn on_prompt_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server prompt list changed");
+        self.notify_server_change(McpServerChange::PromptListChanged);
     }
 
     fn get_info(&self) -> ClientInfo {
@@ -26,12 +26,17 @@ use rmcp::model::ClientNotification;
 use rmcp::model::ClientRequest;
 use rmcp::model::CreateElicitationRequestParams;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::CreateMessageResult;
 use rmcp::model::CustomNotification;
 use rmcp::model::CustomRequest;
 use rmcp::model::ElicitationAction;
 use rmcp::model::Extensions;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::InitializeRequestParams;
 use rmcp::model::InitializeResult;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
@@ -39,8 +44,11 @@ use rmcp::model::PaginatedRequestParams;
 use rmcp::model::ReadResourceRequestParams;
 use rmcp::model::ReadResourceResult;
 use rmcp::model::RequestId;
+use rmcp::model::Root;
 use rmcp::model::ServerResult;
+use rmcp::model::SubscribeRequestParams;
 use rmcp::model::Tool;
+use rmcp::model::UnsubscribeRequestParams;
 use rmcp::service::RoleClient;
 use rmcp::service::RunningService;
 use rmcp::service::{self};
@@ -55,6 +63,7 @@ use serde::Serialize;
 use serde_json::Value;
 use tokio::sync::Mutex;
 use tokio::sync::Semaphore;
+use tokio::sync::broadcast;
 use tokio::sync::watch;
 use tokio::time;
 use tracing::warn;
@@ -249,6 +258,16 @@ pub type SendElicitation = Box<
     dyn Fn(RequestId, Elicitation) -> BoxFuture<'static, Result<ElicitationResponse>> + Send + Sync,
 >;
 
+pub type Sampling = CreateMessageRequestParams;
+
+/// Interface for answering `sampling/createMessage` requests with the client's model.
+pub type SendSampling = Box<
+    dyn Fn(RequestId, Sampling) -> BoxFuture<'static, Result<CreateMessageResult>> + Send + Sync,
+>;
+
+/// Interface for reporting the client's workspace roots in response to `roots/list`.
+pub type ListRoots = Box<dyn Fn() -> Vec<Root> + Send + Sync>;
+
 pub struct ToolWithConnectorId {
     pub tool: Tool,
     pub connector_id: Option<String>,
@@ -261,6 +280,21 @@ pub struct ListToolsWithConnectorIdResult {
     pub tools: Vec<ToolWithConnectorId>,
 }
 
+/// Server-initiated change notifications that callers may want to react to,
+/// e.g. by invalidating cached listings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum McpServerChange {
+    ToolListChanged,
+    ResourceListChanged,
+    PromptListChanged,
+    /// A resource the client subscribed to via `resources/subscribe` changed.
+    ResourceUpdated {
+        uri: String,
+    },
+}
+
+const SERVER_CHANGE_CHANNEL_CAPACITY: usize = 16;
+
 /// MCP client implemented on top of the official `rmcp` SDK.
 /// https://github.com/modelcontextprotocol/rust-sdk
 pub struct RmcpClient {
@@ -269,6 +303,7 @@ pub struct RmcpClient {
     initialize_context: Mutex<Option<InitializeContext>>,
     session_recovery_lock: Semaphore,
     elicitation_pause_state: ElicitationPauseState,
+    server_changes: broadcast::Sender<McpServerChange>,
 }
 
 impl RmcpClient {
@@ -296,6 +331,7 @@ impl RmcpClient {
             initialize_context: Mutex::new(None),
             session_recovery_lock: Semaphore::new(/*permits*/ 1),
             elicitation_pause_state: ElicitationPauseState::new(),
+            server_changes: broadcast::channel(SERVER_CHANGE_CHANNEL_CAPACITY).0,
         })
     }
 
@@ -329,21 +365,31 @@ impl RmcpClient {
             initialize_context: Mutex::new(None),
             session_recovery_lock: Semaphore::new(/*permits*/ 1),
             elicitation_pause_state: ElicitationPauseState::new(),
+            server_changes: broadcast::channel(SERVER_CHANGE_CHANNEL_CAPACITY).0,
         })
     }
 
     /// Perform the initialization handshake with the MCP server.
     /// https://modelcontextprotocol.io/specification/2025-06-18/basic/lifecycle#initialization
+    ///
+    /// `send_sampling` and `list_roots` should be provided only when `params`
+    /// advertises the matching capability; without them,
+    /// `sampling/createMessage` and `roots/list` requests are rejected.
     pub async fn initialize(
         &self,
         params: InitializeRequestParams,
         timeout: Option<Duration>,
         send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
+        list_roots: Option<ListRoots>,
     ) -> Result<InitializeResult> {
         let client_service = ElicitationClientService::new(
             params.clone(),
             send_elicitation,
+            send_sampling,
+            list_roots,
             self.elicitation_pause_state.clone(),
+            self.server_changes.clone(),
         );
         let pending_transport = {
             let mut guard = self.state.lock().await;
@@ -500,6 +546,75 @@ impl RmcpClient {
         Ok(result)
     }
 
+    pub async fn list_prompts(
+        &self,
+        params: Option<PaginatedRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        self.refresh_oauth_if_needed().await;
+        let result = self
+            .run_service_operation("prompts/list", timeout, move |service| {
+                let params = params.clone();
+                async move { service.list_prompts(params).await }.boxed()
+            })
+            .await?;
+        self.persist_oauth_tokens().await;
+        Ok(result)
+    }
+
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        self.refresh_oauth_if_needed().await;
+        let result = self
+            .run_service_operation("prompts/get", timeout, move |service| {
+                let params = params.clone();
+                async move { service.get_prompt(params).await }.boxed()
+            })
+            .await?;
+        self.persist_oauth_tokens().await;
+        Ok(result)
+    }
+
+    pub async fn subscribe_resource(
+        &self,
+        params: SubscribeRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        self.refresh_oauth_if_needed().await;
+        self.run_service_operation("resources/subscribe", timeout, move |service| {
+            let params = params.clone();
+            async move { service.subscribe(params).await }.boxed()
+        })
+        .await?;
+        self.persist_oauth_tokens().await;
+        Ok(())
+    }
+
+    pub async fn unsubscribe_resource(
+        &self,
+        params: UnsubscribeRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        self.refresh_oauth_if_needed().await;
+        self.run_service_operation("resources/unsubscribe", timeout, move |service| {
+            let params = params.clone();
+            async move { service.unsubscribe(params).await }.boxed()
+        })
+        .await?;
+        self.persist_oauth_tokens().await;
+        Ok(())
+    }
+
+    /// Subscribe to list-changed and resource-updated notifications sent by
+    /// the server. Receivers only observe notifications delivered after they
+    /// subscribe.
+    pub fn subscribe_server_changes(&self) -> broadcast::Receiver<McpServerChange> {
+        self.server_changes.subscribe()
+    }
+
     pub async fn call_tool(
         &self,
         name: String,
@@ -595,6 +710,19 @@ impl RmcpClient {
         Ok(())
     }
 
+    /// Tells the server that the result of `roots/list` has changed.
+    pub async fn notify_roots_list_changed(&self) -> Result<()> {
+        self.refresh_oauth_if_needed().await;
+        self.run_service_operation(
+            "notifications/roots/list_changed",
+            /*timeout*/ None,
+            |service| async move { service.notify_roots_list_changed().await }.boxed(),
+        )
+        .await?;
+        self.persist_oauth_tokens().await;
+        Ok(())
+    }
+
     pub async fn send_custom_request(
         &self,
         method: &str,
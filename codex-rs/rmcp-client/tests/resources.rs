@@ -6,6 +6,7 @@ use std::time::Duration;
 use codex_rmcp_client::ElicitationAction;
 use codex_rmcp_client::ElicitationResponse;
 use codex_rmcp_client::LocalStdioServerLauncher;
+use codex_rmcp_client::McpServerChange;
 use codex_rmcp_client::RmcpClient;
 use codex_utils_cargo_bin::CargoBinError;
 use futures::FutureExt as _;
@@ -19,6 +20,8 @@ use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ProtocolVersion;
 use rmcp::model::ReadResourceRequestParams;
 use rmcp::model::ResourceContents;
+use rmcp::model::SubscribeRequestParams;
+use rmcp::model::UnsubscribeRequestParams;
 use serde_json::json;
 
 const RESOURCE_URI: &str = "memo://codex/example-note";
@@ -55,8 +58,7 @@ fn init_params() -> InitializeRequestParams {
     }
 }
 
-#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
-async fn rmcp_client_can_list_and_read_resources() -> anyhow::Result<()> {
+async fn start_client() -> anyhow::Result<RmcpClient> {
     let client = RmcpClient::new_stdio_client(
         stdio_server_bin()?.into(),
         Vec::<OsString>::new(),
@@ -81,9 +83,18 @@ async fn rmcp_client_can_list_and_read_resources() -> anyhow::Result<()> {
                 }
                 .boxed()
             }),
+            /*send_sampling*/ None,
+            /*list_roots*/ None,
         )
         .await?;
 
+    Ok(client)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn rmcp_client_can_list_and_read_resources() -> anyhow::Result<()> {
+    let client = start_client().await?;
+
     let list = client
         .list_resources(/*params*/ None, Some(Duration::from_secs(5)))
         .await?;
@@ -152,3 +163,71 @@ async fn rmcp_client_can_list_and_read_resources() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn rmcp_client_receives_updates_for_subscribed_resources() -> anyhow::Result<()> {
+    let client = start_client().await?;
+    let mut server_changes = client.subscribe_server_changes();
+
+    client
+        .subscribe_resource(
+            SubscribeRequestParams {
+                meta: None,
+                uri: RESOURCE_URI.to_string(),
+            },
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    client
+        .call_tool(
+            "update_memo".to_string(),
+            Some(json!({})),
+            /*meta*/ None,
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+
+    let change = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let change @ McpServerChange::ResourceUpdated { .. } = server_changes.recv().await? {
+                return Ok::<_, anyhow::Error>(change);
+            }
+        }
+    })
+    .await??;
+    assert_eq!(
+        change,
+        McpServerChange::ResourceUpdated {
+            uri: RESOURCE_URI.to_string(),
+        }
+    );
+
+    let read = client
+        .read_resource(
+            ReadResourceRequestParams {
+                meta: None,
+                uri: RESOURCE_URI.to_string(),
+            },
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    let Some(ResourceContents::TextResourceContents { text, .. }) = read.contents.first() else {
+        panic!("expected text resource contents");
+    };
+    assert_eq!(
+        text,
+        "This is a sample MCP resource served by the rmcp test server.\nRevision 1."
+    );
+
+    client
+        .unsubscribe_resource(
+            UnsubscribeRequestParams {
+                meta: None,
+                uri: RESOURCE_URI.to_string(),
+            },
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+
+    Ok(())
+}
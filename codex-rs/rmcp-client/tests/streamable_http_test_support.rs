@@ -116,6 +116,8 @@ pub(crate) async fn create_client(base_url: &str) -> anyhow::Result<RmcpClient>
                 }
                 .boxed()
             }),
+            /*send_sampling*/ None,
+            /*list_roots*/ None,
         )
         .await?;
 
@@ -154,6 +156,8 @@ pub(crate) async fn create_remote_client(
                 }
                 .boxed()
             }),
+            /*send_sampling*/ None,
+            /*list_roots*/ None,
         )
         .await?;
 
@@ -271,6 +271,10 @@ pub(crate) fn tool_runtime_trace_event(event: &EventMsg) -> Option<ToolRuntimeTr
         | EventMsg::ListSkillsResponse(_)
         | EventMsg::RealtimeConversationListVoicesResponse(_)
         | EventMsg::SkillsUpdateAvailable
+        | EventMsg::McpPromptsChanged(_)
+        | EventMsg::McpResourceUpdated(_)
+        | EventMsg::McpSamplingBegin(_)
+        | EventMsg::McpSamplingEnd(_)
         | EventMsg::PlanUpdate(_)
         | EventMsg::TurnAborted(_)
         | EventMsg::ShutdownComplete
@@ -354,6 +358,10 @@ pub(crate) fn wrapped_protocol_event_type(event: &EventMsg) -> Option<&'static s
         | EventMsg::ListSkillsResponse(_)
         | EventMsg::RealtimeConversationListVoicesResponse(_)
         | EventMsg::SkillsUpdateAvailable
+        | EventMsg::McpPromptsChanged(_)
+        | EventMsg::McpResourceUpdated(_)
+        | EventMsg::McpSamplingBegin(_)
+        | EventMsg::McpSamplingEnd(_)
         | EventMsg::PlanUpdate(_)
         | EventMsg::EnteredReviewMode(_)
         | EventMsg::ExitedReviewMode(_)
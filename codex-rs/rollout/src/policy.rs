@@ -1,6 +1,7 @@
 use crate::protocol::EventMsg;
 use crate::protocol::RolloutItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::HookOutputEntryKind;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum EventPersistenceMode {
@@ -115,12 +116,27 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
                 None
             }
         }
+        EventMsg::HookCompleted(event) => {
+            // Hooks that rewrite tool input or output change what the model and
+            // the tool actually saw, so keep those runs in the rollout for audit.
+            if event
+                .run
+                .entries
+                .iter()
+                .any(|entry| entry.kind == HookOutputEntryKind::Rewrite)
+            {
+                Some(EventPersistenceMode::Limited)
+            } else {
+                None
+            }
+        }
         EventMsg::Error(_)
         | EventMsg::GuardianAssessment(_)
         | EventMsg::WebSearchEnd(_)
         | EventMsg::ExecCommandEnd(_)
         | EventMsg::PatchApplyEnd(_)
         | EventMsg::McpToolCallEnd(_)
+        | EventMsg::McpSamplingEnd(_)
         | EventMsg::ViewImageToolCall(_)
         | EventMsg::CollabAgentSpawnEnd(_)
         | EventMsg::CollabAgentInteractionEnd(_)
@@ -145,6 +161,7 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::SessionConfigured(_)
         | EventMsg::ThreadGoalUpdated(_)
         | EventMsg::McpToolCallBegin(_)
+        | EventMsg::McpSamplingBegin(_)
         | EventMsg::WebSearchBegin(_)
         | EventMsg::ExecCommandBegin(_)
         | EventMsg::TerminalInteraction(_)
@@ -171,12 +188,13 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::DeprecationNotice(_)
         | EventMsg::ItemStarted(_)
         | EventMsg::HookStarted(_)
-        | EventMsg::HookCompleted(_)
         | EventMsg::AgentMessageContentDelta(_)
         | EventMsg::PlanDelta(_)
         | EventMsg::ReasoningContentDelta(_)
         | EventMsg::ReasoningRawContentDelta(_)
         | EventMsg::SkillsUpdateAvailable
+        | EventMsg::McpPromptsChanged(_)
+        | EventMsg::McpResourceUpdated(_)
         | EventMsg::CollabAgentSpawnBegin(_)
         | EventMsg::CollabAgentInteractionBegin(_)
         | EventMsg::CollabWaitingBegin(_)
@@ -192,8 +210,20 @@ mod tests {
     use super::should_persist_event_msg;
     use codex_protocol::ThreadId;
     use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::HookCompletedEvent;
+    use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookExecutionMode;
+    use codex_protocol::protocol::HookHandlerType;
+    use codex_protocol::protocol::HookOutputEntry;
+    use codex_protocol::protocol::HookOutputEntryKind;
+    use codex_protocol::protocol::HookRunStatus;
+    use codex_protocol::protocol::HookRunSummary;
+    use codex_protocol::protocol::HookScope;
+    use codex_protocol::protocol::HookSource;
     use codex_protocol::protocol::ImageGenerationEndEvent;
     use codex_protocol::protocol::ThreadNameUpdatedEvent;
+    use codex_utils_absolute_path::test_support::PathExt;
+    use codex_utils_absolute_path::test_support::test_path_buf;
 
     #[test]
     fn persists_image_generation_end_events_in_limited_mode() {
@@ -223,4 +253,47 @@ mod tests {
             EventPersistenceMode::Limited
         ));
     }
+
+    fn hook_completed_event(entries: Vec<HookOutputEntry>) -> EventMsg {
+        EventMsg::HookCompleted(HookCompletedEvent {
+            turn_id: Some("turn-1".to_string()),
+            run: HookRunSummary {
+                id: "pre-tool-use:0:/tmp/hooks.json".to_string(),
+                event_name: HookEventName::PreToolUse,
+                handler_type: HookHandlerType::Command,
+                execution_mode: HookExecutionMode::Sync,
+                scope: HookScope::Turn,
+                source_path: test_path_buf("/tmp/hooks.json").abs(),
+                source: HookSource::User,
+                display_order: 0,
+                status: HookRunStatus::Completed,
+                status_message: None,
+                started_at: 1,
+                completed_at: Some(2),
+                duration_ms: Some(1),
+                entries,
+            },
+        })
+    }
+
+    #[test]
+    fn persists_hook_runs_that_rewrite_tool_calls() {
+        let rewrite = hook_completed_event(vec![HookOutputEntry {
+            kind: HookOutputEntryKind::Rewrite,
+            text: "updated tool input: {\"command\":\"ls --dry-run\"}".to_string(),
+        }]);
+        let context = hook_completed_event(vec![HookOutputEntry {
+            kind: HookOutputEntryKind::Context,
+            text: "remember the tests".to_string(),
+        }]);
+
+        assert!(should_persist_event_msg(
+            &rewrite,
+            EventPersistenceMode::Limited
+        ));
+        assert!(!should_persist_event_msg(
+            &context,
+            EventPersistenceMode::Extended
+        ));
+    }
 }
@@ -187,6 +187,101 @@ impl RolloutRecorderParams {
     }
 }
 
+/// Builds the session metadata line that opens a newly created thread.
+///
+/// Returns `None` for resume params, whose session metadata is already persisted. Stores that do
+/// not write rollout files use this so their history starts the same way a rollout file does.
+pub async fn new_thread_session_meta_line(
+    config: &impl RolloutConfigView,
+    params: RolloutRecorderParams,
+) -> std::io::Result<Option<SessionMetaLine>> {
+    let RolloutRecorderParams::Create {
+        conversation_id,
+        forked_from_id,
+        source,
+        base_instructions,
+        dynamic_tools,
+        event_persistence_mode: _,
+    } = params
+    else {
+        return Ok(None);
+    };
+    let session_meta = new_session_meta(
+        config,
+        conversation_id,
+        forked_from_id,
+        source,
+        base_instructions,
+        dynamic_tools,
+        OffsetDateTime::now_utc(),
+    )?;
+    Ok(Some(SessionMetaLine {
+        meta: session_meta,
+        git: session_git_info(config.cwd()).await,
+    }))
+}
+
+/// Returns the items a rollout recorder with `mode` would persist, in order.
+pub fn persisted_rollout_items(
+    items: &[RolloutItem],
+    mode: EventPersistenceMode,
+) -> Vec<RolloutItem> {
+    // Note that function calls may look a bit strange if they are
+    // "fully qualified MCP tool calls," so we could consider
+    // reformatting them in that case.
+    items
+        .iter()
+        .filter(|item| is_persisted_response_item(item, mode))
+        .map(|item| sanitize_rollout_item_for_persistence(item.clone(), mode))
+        .collect()
+}
+
+fn new_session_meta(
+    config: &impl RolloutConfigView,
+    conversation_id: ThreadId,
+    forked_from_id: Option<ThreadId>,
+    source: SessionSource,
+    base_instructions: BaseInstructions,
+    dynamic_tools: Vec<DynamicToolSpec>,
+    started_at: OffsetDateTime,
+) -> std::io::Result<SessionMeta> {
+    let timestamp_format: &[FormatItem] =
+        format_description!("[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z");
+    let timestamp = started_at
+        .to_offset(time::UtcOffset::UTC)
+        .format(timestamp_format)
+        .map_err(|e| IoError::other(format!("failed to format timestamp: {e}")))?;
+
+    Ok(SessionMeta {
+        id: conversation_id,
+        forked_from_id,
+        timestamp,
+        cwd: config.cwd().to_path_buf(),
+        originator: originator().value,
+        cli_version: env!("CARGO_PKG_VERSION").to_string(),
+        agent_nickname: source.get_nickname(),
+        agent_role: source.get_agent_role(),
+        agent_path: source.get_agent_path().map(Into::into),
+        source,
+        model_provider: Some(config.model_provider_id().to_string()),
+        base_instructions: Some(base_instructions),
+        dynamic_tools: if dynamic_tools.is_empty() {
+            None
+        } else {
+            Some(dynamic_tools)
+        },
+        memory_mode: (!config.generate_memories()).then_some("disabled".to_string()),
+    })
+}
+
+async fn session_git_info(cwd: &Path) -> Option<ProtocolGitInfo> {
+    collect_git_info(cwd).await.map(|info| ProtocolGitInfo {
+        commit_hash: info.commit_hash,
+        branch: info.branch,
+        repository_url: info.repository_url,
+    })
+}
+
 const PERSISTED_EXEC_AGGREGATED_OUTPUT_MAX_BYTES: usize = 10_000;
 
 fn sanitize_rollout_item_for_persistence(
@@ -666,38 +761,15 @@ impl RolloutRecorder {
                 } => {
                     let log_file_info = precompute_log_file_info(config, conversation_id)?;
                     let path = log_file_info.path.clone();
-                    let session_id = log_file_info.conversation_id;
-                    let started_at = log_file_info.timestamp;
-
-                    let timestamp_format: &[FormatItem] = format_description!(
-                        "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z"
-                    );
-                    let timestamp = started_at
-                        .to_offset(time::UtcOffset::UTC)
-                        .format(timestamp_format)
-                        .map_err(|e| IoError::other(format!("failed to format timestamp: {e}")))?;
-
-                    let session_meta = SessionMeta {
-                        id: session_id,
+                    let session_meta = new_session_meta(
+                        config,
+                        log_file_info.conversation_id,
                         forked_from_id,
-                        timestamp,
-                        cwd: config.cwd().to_path_buf(),
-                        originator: originator().value,
-                        cli_version: env!("CARGO_PKG_VERSION").to_string(),
-                        agent_nickname: source.get_nickname(),
-                        agent_role: source.get_agent_role(),
-                        agent_path: source.get_agent_path().map(Into::into),
                         source,
-                        model_provider: Some(config.model_provider_id().to_string()),
-                        base_instructions: Some(base_instructions),
-                        dynamic_tools: if dynamic_tools.is_empty() {
-                            None
-                        } else {
-                            Some(dynamic_tools)
-                        },
-                        memory_mode: (!config.generate_memories())
-                            .then_some("disabled".to_string()),
-                    };
+                        base_instructions,
+                        dynamic_tools,
+                        log_file_info.timestamp,
+                    )?;
 
                     (
                         None,
@@ -784,18 +856,7 @@ impl RolloutRecorder {
     }
 
     pub async fn record_items(&self, items: &[RolloutItem]) -> std::io::Result<()> {
-        let mut filtered = Vec::new();
-        for item in items {
-            // Note that function calls may look a bit strange if they are
-            // "fully qualified MCP tool calls," so we could consider
-            // reformatting them in that case.
-            if is_persisted_response_item(item, self.event_persistence_mode) {
-                filtered.push(sanitize_rollout_item_for_persistence(
-                    item.clone(),
-                    self.event_persistence_mode,
-                ));
-            }
-        }
+        let filtered = persisted_rollout_items(items, self.event_persistence_mode);
         if filtered.is_empty() {
             return Ok(());
         }
@@ -1676,14 +1737,9 @@ async fn write_session_meta(
     default_provider: &str,
     generate_memories: bool,
 ) -> std::io::Result<()> {
-    let git_info = collect_git_info(cwd).await.map(|info| ProtocolGitInfo {
-        commit_hash: info.commit_hash,
-        branch: info.branch,
-        repository_url: info.repository_url,
-    });
     let session_meta_line = SessionMetaLine {
         meta: session_meta,
-        git: git_info,
+        git: session_git_info(cwd).await,
     };
     if state_db_ctx.is_some() {
         *state_builder = metadata::builder_from_session_meta(&session_meta_line, rollout_path);
@@ -245,6 +245,11 @@ pub async fn list_threads_db(
         Ok(mut page) => {
             let mut valid_items = Vec::with_capacity(page.items.len());
             for item in page.items {
+                // Threads written by the SQLite thread store have no rollout file. They are not
+                // stale, so leave them in place instead of deleting them below.
+                if item.rollout_path.as_os_str().is_empty() {
+                    continue;
+                }
                 if tokio::fs::try_exists(&item.rollout_path)
                     .await
                     .unwrap_or(false)
@@ -0,0 +1,7 @@
+CREATE TABLE thread_items (
+    thread_id TEXT NOT NULL,
+    seq INTEGER NOT NULL,
+    item TEXT NOT NULL,
+    PRIMARY KEY(thread_id, seq),
+    FOREIGN KEY(thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
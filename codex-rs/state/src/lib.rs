@@ -49,6 +49,7 @@ pub use model::ThreadMetadataBuilder;
 pub use model::ThreadsPage;
 pub use runtime::DeviceKeyBindingRecord;
 pub use runtime::RemoteControlEnrollmentRecord;
+pub use runtime::ThreadFieldsUpdate;
 pub use runtime::ThreadFilterOptions;
 pub use runtime::ThreadGoalAccountingMode;
 pub use runtime::ThreadGoalAccountingOutcome;
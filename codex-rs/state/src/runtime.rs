@@ -65,6 +65,7 @@ mod memories;
 mod remote_control;
 #[cfg(test)]
 mod test_support;
+mod thread_items;
 mod threads;
 
 pub use device_key::DeviceKeyBindingRecord;
@@ -72,6 +73,7 @@ pub use goals::ThreadGoalAccountingMode;
 pub use goals::ThreadGoalAccountingOutcome;
 pub use goals::ThreadGoalUpdate;
 pub use remote_control::RemoteControlEnrollmentRecord;
+pub use threads::ThreadFieldsUpdate;
 pub use threads::ThreadFilterOptions;
 
 // "Partition" is the retained-log-content bucket we cap at 10 MiB:
@@ -0,0 +1,273 @@
+use super::*;
+
+impl StateRuntime {
+    /// Append rollout items to the stored history of a thread.
+    ///
+    /// The batch is numbered after the thread's last stored item and written in one transaction,
+    /// so readers see either all of it or none of it.
+    pub async fn append_thread_items(
+        &self,
+        thread_id: ThreadId,
+        items: &[RolloutItem],
+    ) -> anyhow::Result<()> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        let mut tx = self.pool.begin().await?;
+        insert_thread_items(&mut tx, thread_id, items).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Store the full history of a thread unless it already has stored items.
+    ///
+    /// Returns `false` without writing anything when items already exist, which keeps repeated
+    /// rollout imports from duplicating history.
+    pub async fn insert_thread_items_if_absent(
+        &self,
+        thread_id: ThreadId,
+        items: &[RolloutItem],
+    ) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await?;
+        let existing: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM thread_items WHERE thread_id = ?")
+                .bind(thread_id.to_string())
+                .fetch_one(&mut *tx)
+                .await?;
+        if existing > 0 {
+            return Ok(false);
+        }
+        insert_thread_items(&mut tx, thread_id, items).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Load the stored history of a thread in append order.
+    pub async fn load_thread_items(&self, thread_id: ThreadId) -> anyhow::Result<Vec<RolloutItem>> {
+        let rows =
+            sqlx::query("SELECT item FROM thread_items WHERE thread_id = ? ORDER BY seq ASC")
+                .bind(thread_id.to_string())
+                .fetch_all(self.pool.as_ref())
+                .await?;
+        rows.into_iter()
+            .map(|row| {
+                let item: String = row.try_get("item")?;
+                Ok(serde_json::from_str(&item)?)
+            })
+            .collect()
+    }
+
+    /// Load the first stored history item of a thread, which holds its session metadata.
+    pub async fn first_thread_item(
+        &self,
+        thread_id: ThreadId,
+    ) -> anyhow::Result<Option<RolloutItem>> {
+        let row = sqlx::query(
+            "SELECT item FROM thread_items WHERE thread_id = ? ORDER BY seq ASC LIMIT 1",
+        )
+        .bind(thread_id.to_string())
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.map(|row| {
+            let item: String = row.try_get("item")?;
+            Ok(serde_json::from_str(&item)?)
+        })
+        .transpose()
+    }
+
+    /// Return whether any history items are stored for a thread.
+    pub async fn has_thread_items(&self, thread_id: ThreadId) -> anyhow::Result<bool> {
+        let row = sqlx::query("SELECT 1 FROM thread_items WHERE thread_id = ? LIMIT 1")
+            .bind(thread_id.to_string())
+            .fetch_optional(self.pool.as_ref())
+            .await?;
+        Ok(row.is_some())
+    }
+}
+
+pub(super) async fn insert_thread_items(
+    tx: &mut SqliteConnection,
+    thread_id: ThreadId,
+    items: &[RolloutItem],
+) -> anyhow::Result<()> {
+    let thread_id = thread_id.to_string();
+    let next_seq: i64 = sqlx::query_scalar(
+        "SELECT COALESCE(MAX(seq) + 1, 0) FROM thread_items WHERE thread_id = ?",
+    )
+    .bind(thread_id.as_str())
+    .fetch_one(&mut *tx)
+    .await?;
+    for (offset, item) in items.iter().enumerate() {
+        let seq = next_seq.saturating_add(i64::try_from(offset).unwrap_or(i64::MAX));
+        sqlx::query("INSERT INTO thread_items (thread_id, seq, item) VALUES (?, ?, ?)")
+            .bind(thread_id.as_str())
+            .bind(seq)
+            .bind(serde_json::to_string(item)?)
+            .execute(&mut *tx)
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::test_thread_metadata;
+    use crate::runtime::test_support::unique_temp_dir;
+    use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    async fn test_runtime() -> Arc<StateRuntime> {
+        StateRuntime::init(unique_temp_dir(), "test-provider".to_string())
+            .await
+            .expect("state db should initialize")
+    }
+
+    fn test_thread_id() -> ThreadId {
+        ThreadId::from_string("00000000-0000-0000-0000-000000000123").expect("valid thread id")
+    }
+
+    async fn upsert_test_thread(runtime: &StateRuntime, thread_id: ThreadId) {
+        let metadata = test_thread_metadata(
+            runtime.codex_home(),
+            thread_id,
+            runtime.codex_home().join("workspace"),
+        );
+        runtime
+            .upsert_thread(&metadata)
+            .await
+            .expect("test thread should be upserted");
+    }
+
+    fn user_message(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        }))
+    }
+
+    fn messages(items: &[RolloutItem]) -> Vec<String> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                RolloutItem::EventMsg(EventMsg::UserMessage(event)) => Some(event.message.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn append_thread_items_preserves_order_across_batches() {
+        let runtime = test_runtime().await;
+        let thread_id = test_thread_id();
+        upsert_test_thread(&runtime, thread_id).await;
+
+        runtime
+            .append_thread_items(thread_id, &[user_message("first"), user_message("second")])
+            .await
+            .expect("first batch should append");
+        runtime
+            .append_thread_items(thread_id, &[user_message("third")])
+            .await
+            .expect("second batch should append");
+
+        let items = runtime
+            .load_thread_items(thread_id)
+            .await
+            .expect("items should load");
+        assert_eq!(messages(&items), vec!["first", "second", "third"]);
+        assert!(
+            runtime
+                .has_thread_items(thread_id)
+                .await
+                .expect("item presence should load")
+        );
+    }
+
+    #[tokio::test]
+    async fn apply_rollout_items_and_append_thread_items_writes_thread_and_items() {
+        let runtime = test_runtime().await;
+        let thread_id = test_thread_id();
+        let builder = crate::ThreadMetadataBuilder::new(
+            thread_id,
+            runtime.codex_home().join("rollout.jsonl"),
+            chrono::Utc::now(),
+            codex_protocol::protocol::SessionSource::Cli,
+        );
+
+        runtime
+            .apply_rollout_items_and_append_thread_items(
+                &builder,
+                &[user_message("first"), user_message("second")],
+                /*new_thread_memory_mode*/ None,
+                /*updated_at_override*/ None,
+            )
+            .await
+            .expect("items should be written");
+
+        assert!(
+            runtime
+                .get_thread(thread_id)
+                .await
+                .expect("thread should load")
+                .is_some()
+        );
+        let items = runtime
+            .load_thread_items(thread_id)
+            .await
+            .expect("items should load");
+        assert_eq!(messages(&items), vec!["first", "second"]);
+    }
+
+    #[tokio::test]
+    async fn insert_thread_items_if_absent_skips_threads_with_history() {
+        let runtime = test_runtime().await;
+        let thread_id = test_thread_id();
+        upsert_test_thread(&runtime, thread_id).await;
+
+        assert!(
+            runtime
+                .insert_thread_items_if_absent(thread_id, &[user_message("imported")])
+                .await
+                .expect("first import should succeed")
+        );
+        assert!(
+            !runtime
+                .insert_thread_items_if_absent(thread_id, &[user_message("imported again")])
+                .await
+                .expect("second import should succeed")
+        );
+
+        let items = runtime
+            .load_thread_items(thread_id)
+            .await
+            .expect("items should load");
+        assert_eq!(messages(&items), vec!["imported"]);
+    }
+
+    #[tokio::test]
+    async fn delete_thread_removes_stored_items() {
+        let runtime = test_runtime().await;
+        let thread_id = test_thread_id();
+        upsert_test_thread(&runtime, thread_id).await;
+        runtime
+            .append_thread_items(thread_id, &[user_message("first")])
+            .await
+            .expect("items should append");
+
+        runtime
+            .delete_thread(thread_id)
+            .await
+            .expect("thread should delete");
+
+        assert!(
+            !runtime
+                .has_thread_items(thread_id)
+                .await
+                .expect("item presence should load")
+        );
+    }
+}
@@ -282,7 +282,7 @@ ORDER BY depth ASC, child_thread_id ASC
     }
 
     async fn insert_thread_spawn_edge_if_absent(
-        &self,
+        conn: &mut SqliteConnection,
         parent_thread_id: ThreadId,
         child_thread_id: ThreadId,
     ) -> anyhow::Result<()> {
@@ -299,21 +299,20 @@ ON CONFLICT(child_thread_id) DO NOTHING
         .bind(parent_thread_id.to_string())
         .bind(child_thread_id.to_string())
         .bind(crate::DirectionalThreadSpawnEdgeStatus::Open.as_ref())
-        .execute(self.pool.as_ref())
+        .execute(&mut *conn)
         .await?;
         Ok(())
     }
 
     async fn insert_thread_spawn_edge_from_source_if_absent(
-        &self,
+        conn: &mut SqliteConnection,
         child_thread_id: ThreadId,
         source: &str,
     ) -> anyhow::Result<()> {
         let Some(parent_thread_id) = thread_spawn_parent_thread_id_from_source_str(source) else {
             return Ok(());
         };
-        self.insert_thread_spawn_edge_if_absent(parent_thread_id, child_thread_id)
-            .await
+        Self::insert_thread_spawn_edge_if_absent(conn, parent_thread_id, child_thread_id).await
     }
 
     /// Find a rollout path by thread id using the underlying database.
@@ -533,8 +532,13 @@ ON CONFLICT(id) DO NOTHING
         .bind("enabled")
         .execute(self.pool.as_ref())
         .await?;
-        self.insert_thread_spawn_edge_from_source_if_absent(metadata.id, metadata.source.as_str())
-            .await?;
+        let mut conn = self.pool.acquire().await?;
+        Self::insert_thread_spawn_edge_from_source_if_absent(
+            &mut conn,
+            metadata.id,
+            metadata.source.as_str(),
+        )
+        .await?;
         Ok(result.rows_affected() > 0)
     }
 
@@ -542,11 +546,20 @@ ON CONFLICT(id) DO NOTHING
         &self,
         thread_id: ThreadId,
         memory_mode: &str,
+    ) -> anyhow::Result<bool> {
+        let mut conn = self.pool.acquire().await?;
+        Self::set_thread_memory_mode_with_executor(&mut conn, thread_id, memory_mode).await
+    }
+
+    async fn set_thread_memory_mode_with_executor(
+        conn: &mut SqliteConnection,
+        thread_id: ThreadId,
+        memory_mode: &str,
     ) -> anyhow::Result<bool> {
         let result = sqlx::query("UPDATE threads SET memory_mode = ? WHERE id = ?")
             .bind(memory_mode)
             .bind(thread_id.to_string())
-            .execute(self.pool.as_ref())
+            .execute(&mut *conn)
             .await?;
         Ok(result.rows_affected() > 0)
     }
@@ -655,10 +668,63 @@ WHERE id = ?
         Ok(result.rows_affected() > 0)
     }
 
+    /// Apply several mutable thread fields in a single statement.
+    ///
+    /// Fields left as `None` keep their stored values. Returns `false` when the thread does not
+    /// exist.
+    pub async fn update_thread_fields(
+        &self,
+        thread_id: ThreadId,
+        update: ThreadFieldsUpdate<'_>,
+    ) -> anyhow::Result<bool> {
+        let ThreadFieldsUpdate {
+            title,
+            memory_mode,
+            git_sha,
+            git_branch,
+            git_origin_url,
+        } = update;
+        let result = sqlx::query(
+            r#"
+UPDATE threads
+SET
+    title = COALESCE(?, title),
+    memory_mode = COALESCE(?, memory_mode),
+    git_sha = CASE WHEN ? THEN ? ELSE git_sha END,
+    git_branch = CASE WHEN ? THEN ? ELSE git_branch END,
+    git_origin_url = CASE WHEN ? THEN ? ELSE git_origin_url END
+WHERE id = ?
+            "#,
+        )
+        .bind(title)
+        .bind(memory_mode)
+        .bind(git_sha.is_some())
+        .bind(git_sha.flatten())
+        .bind(git_branch.is_some())
+        .bind(git_branch.flatten())
+        .bind(git_origin_url.is_some())
+        .bind(git_origin_url.flatten())
+        .bind(thread_id.to_string())
+        .execute(self.pool.as_ref())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn upsert_thread_with_creation_memory_mode(
         &self,
         metadata: &crate::ThreadMetadata,
         creation_memory_mode: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let mut conn = self.pool.acquire().await?;
+        self.upsert_thread_with_executor(&mut conn, metadata, creation_memory_mode)
+            .await
+    }
+
+    async fn upsert_thread_with_executor(
+        &self,
+        conn: &mut SqliteConnection,
+        metadata: &crate::ThreadMetadata,
+        creation_memory_mode: Option<&str>,
     ) -> anyhow::Result<()> {
         let updated_at = self.allocate_thread_updated_at(metadata.updated_at)?;
         sqlx::query(
@@ -749,10 +815,14 @@ ON CONFLICT(id) DO UPDATE SET
         .bind(metadata.git_branch.as_deref())
         .bind(metadata.git_origin_url.as_deref())
         .bind(creation_memory_mode.unwrap_or("enabled"))
-        .execute(self.pool.as_ref())
+        .execute(&mut *conn)
+        .await?;
+        Self::insert_thread_spawn_edge_from_source_if_absent(
+            conn,
+            metadata.id,
+            metadata.source.as_str(),
+        )
         .await?;
-        self.insert_thread_spawn_edge_from_source_if_absent(metadata.id, metadata.source.as_str())
-            .await?;
         Ok(())
     }
 
@@ -771,8 +841,18 @@ ON CONFLICT(id) DO UPDATE SET
         if tools.is_empty() {
             return Ok(());
         }
-        let thread_id = thread_id.to_string();
         let mut tx = self.pool.begin().await?;
+        Self::insert_dynamic_tools_with_executor(&mut tx, thread_id, tools).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn insert_dynamic_tools_with_executor(
+        conn: &mut SqliteConnection,
+        thread_id: ThreadId,
+        tools: &[DynamicToolSpec],
+    ) -> anyhow::Result<()> {
+        let thread_id = thread_id.to_string();
         for (idx, tool) in tools.iter().enumerate() {
             let position = i64::try_from(idx).unwrap_or(i64::MAX);
             let input_schema = serde_json::to_string(&tool.input_schema)?;
@@ -797,10 +877,9 @@ ON CONFLICT(thread_id, position) DO NOTHING
             .bind(tool.description.as_str())
             .bind(input_schema)
             .bind(tool.defer_loading)
-            .execute(&mut *tx)
+            .execute(&mut *conn)
             .await?;
         }
-        tx.commit().await?;
         Ok(())
     }
 
@@ -815,6 +894,54 @@ ON CONFLICT(thread_id, position) DO NOTHING
         if items.is_empty() {
             return Ok(());
         }
+        let mut tx = self.pool.begin().await?;
+        self.apply_rollout_items_with_executor(
+            &mut tx,
+            builder,
+            items,
+            new_thread_memory_mode,
+            updated_at_override,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Apply rollout items like [`Self::apply_rollout_items`] and append them to the thread's
+    /// stored history in the same transaction, so the metadata never reflects items that were
+    /// not stored.
+    pub async fn apply_rollout_items_and_append_thread_items(
+        &self,
+        builder: &ThreadMetadataBuilder,
+        items: &[RolloutItem],
+        new_thread_memory_mode: Option<&str>,
+        updated_at_override: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        let mut tx = self.pool.begin().await?;
+        self.apply_rollout_items_with_executor(
+            &mut tx,
+            builder,
+            items,
+            new_thread_memory_mode,
+            updated_at_override,
+        )
+        .await?;
+        super::thread_items::insert_thread_items(&mut tx, builder.id, items).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn apply_rollout_items_with_executor(
+        &self,
+        conn: &mut SqliteConnection,
+        builder: &ThreadMetadataBuilder,
+        items: &[RolloutItem],
+        new_thread_memory_mode: Option<&str>,
+        updated_at_override: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
         let existing_metadata = self.get_thread(builder.id).await?;
         let mut metadata = existing_metadata
             .clone()
@@ -835,27 +962,24 @@ ON CONFLICT(thread_id, position) DO NOTHING
         }
         // Keep the thread upsert before dynamic tools to satisfy the foreign key constraint:
         // thread_dynamic_tools.thread_id -> threads.id.
-        let upsert_result = if existing_metadata.is_none() {
-            self.upsert_thread_with_creation_memory_mode(&metadata, new_thread_memory_mode)
-                .await
+        let creation_memory_mode = if existing_metadata.is_none() {
+            new_thread_memory_mode
         } else {
-            self.upsert_thread(&metadata).await
+            None
         };
-        upsert_result?;
-        if let Some(memory_mode) = extract_memory_mode(items)
-            && let Err(err) = self
-                .set_thread_memory_mode(builder.id, memory_mode.as_str())
-                .await
-        {
-            return Err(err);
+        self.upsert_thread_with_executor(&mut *conn, &metadata, creation_memory_mode)
+            .await?;
+        if let Some(memory_mode) = extract_memory_mode(items) {
+            Self::set_thread_memory_mode_with_executor(
+                &mut *conn,
+                builder.id,
+                memory_mode.as_str(),
+            )
+            .await?;
         }
-        let dynamic_tools = extract_dynamic_tools(items);
-        if let Some(dynamic_tools) = dynamic_tools
-            && let Err(err) = self
-                .persist_dynamic_tools(builder.id, dynamic_tools.as_deref())
-                .await
-        {
-            return Err(err);
+        if let Some(Some(dynamic_tools)) = extract_dynamic_tools(items) {
+            Self::insert_dynamic_tools_with_executor(&mut *conn, builder.id, &dynamic_tools)
+                .await?;
         }
         Ok(())
     }
@@ -999,6 +1123,18 @@ fn thread_spawn_parent_thread_id_from_source_str(source: &str) -> Option<ThreadI
     }
 }
 
+/// Mutable thread fields for [`StateRuntime::update_thread_fields`].
+///
+/// `None` leaves a field unchanged; for Git fields `Some(None)` clears the stored value.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ThreadFieldsUpdate<'a> {
+    pub title: Option<&'a str>,
+    pub memory_mode: Option<&'a str>,
+    pub git_sha: Option<Option<&'a str>>,
+    pub git_branch: Option<Option<&'a str>>,
+    pub git_origin_url: Option<Option<&'a str>>,
+}
+
 #[derive(Clone, Copy)]
 pub struct ThreadFilterOptions<'a> {
     pub archived_only: bool,
@@ -1871,4 +2007,62 @@ mod tests {
             .expect("all descendants should load");
         assert_eq!(all_descendants, vec![child_thread_id, grandchild_thread_id]);
     }
+
+    #[tokio::test]
+    async fn update_thread_fields_applies_only_requested_fields() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string())
+            .await
+            .expect("state db should initialize");
+        let thread_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000124").expect("valid thread id");
+        let mut metadata = test_thread_metadata(&codex_home, thread_id, codex_home.clone());
+        metadata.title = "original".to_string();
+        metadata.git_sha = Some("abc123".to_string());
+        metadata.git_branch = Some("main".to_string());
+        runtime
+            .upsert_thread(&metadata)
+            .await
+            .expect("thread insert should succeed");
+
+        let updated = runtime
+            .update_thread_fields(
+                thread_id,
+                ThreadFieldsUpdate {
+                    title: Some("renamed"),
+                    memory_mode: Some("disabled"),
+                    git_branch: Some(None),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("thread fields should update");
+        assert!(updated);
+
+        let thread = runtime
+            .get_thread(thread_id)
+            .await
+            .expect("thread should load")
+            .expect("thread should exist");
+        assert_eq!(thread.title, "renamed");
+        assert_eq!(thread.git_sha.as_deref(), Some("abc123"));
+        assert_eq!(thread.git_branch, None);
+        assert_eq!(
+            runtime
+                .get_thread_memory_mode(thread_id)
+                .await
+                .expect("memory mode should load")
+                .as_deref(),
+            Some("disabled")
+        );
+
+        let missing_thread_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000125").expect("valid thread id");
+        assert!(
+            !runtime
+                .update_thread_fields(missing_thread_id, ThreadFieldsUpdate::default())
+                .await
+                .expect("missing thread update should succeed")
+        );
+    }
 }
@@ -10,6 +10,7 @@ mod in_memory;
 mod live_thread;
 mod local;
 mod remote;
+mod sqlite;
 mod store;
 mod types;
 
@@ -23,6 +24,8 @@ pub use live_thread::LiveThread;
 pub use live_thread::LiveThreadInitGuard;
 pub use local::LocalThreadStore;
 pub use remote::RemoteThreadStore;
+pub use sqlite::RolloutImportSummary;
+pub use sqlite::SqliteThreadStore;
 pub use store::ThreadStore;
 pub use types::AppendThreadItemsParams;
 pub use types::ArchiveThreadParams;
@@ -1,6 +1,6 @@
 mod archive_thread;
 mod create_thread;
-mod helpers;
+pub(crate) mod helpers;
 mod list_threads;
 mod live_writer;
 mod read_thread;
@@ -8,7 +8,7 @@ mod unarchive_thread;
 mod update_thread_metadata;
 
 #[cfg(test)]
-mod test_support;
+pub(crate) mod test_support;
 
 use async_trait::async_trait;
 use codex_protocol::ThreadId;
@@ -3,7 +3,6 @@ use chrono::Utc;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::SessionMetaLine;
-use codex_protocol::protocol::SessionSource;
 use codex_rollout::RolloutRecorder;
 use codex_rollout::find_archived_thread_path_by_id_str;
 use codex_rollout::find_thread_name_by_id;
@@ -14,7 +13,10 @@ use codex_state::StateRuntime;
 use codex_state::ThreadMetadata;
 
 use super::LocalThreadStore;
+use super::helpers::distinct_title;
 use super::helpers::git_info_from_parts;
+use super::helpers::parse_or_default;
+use super::helpers::parse_session_source;
 use super::helpers::stored_thread_from_rollout_item;
 use crate::ReadThreadParams;
 use crate::StoredThread;
@@ -318,15 +320,6 @@ fn stored_thread_from_meta_line(
     }
 }
 
-fn distinct_title(metadata: &ThreadMetadata) -> Option<String> {
-    let title = metadata.title.trim();
-    if title.is_empty() || metadata.first_user_message.as_deref().map(str::trim) == Some(title) {
-        None
-    } else {
-        Some(title.to_string())
-    }
-}
-
 fn set_thread_name_from_title(thread: &mut StoredThread, title: String) {
     if title.trim().is_empty() || thread.preview.trim() == title.trim() {
         return;
@@ -334,21 +327,6 @@ fn set_thread_name_from_title(thread: &mut StoredThread, title: String) {
     thread.name = Some(title);
 }
 
-fn parse_session_source(source: &str) -> SessionSource {
-    serde_json::from_str(source)
-        .or_else(|_| serde_json::from_value(serde_json::Value::String(source.to_string())))
-        .unwrap_or(SessionSource::Unknown)
-}
-
-fn parse_or_default<T>(value: &str, default: T) -> T
-where
-    T: serde::de::DeserializeOwned,
-{
-    serde_json::from_str(value)
-        .or_else(|_| serde_json::from_value(serde_json::Value::String(value.to_string())))
-        .unwrap_or(default)
-}
-
 fn parse_rfc3339_non_optional(value: &str) -> Option<DateTime<Utc>> {
     DateTime::parse_from_rfc3339(value)
         .ok()
@@ -17,7 +17,7 @@ pub(super) fn test_config(codex_home: &Path) -> RolloutConfig {
     }
 }
 
-pub(super) fn write_session_file(root: &Path, ts: &str, uuid: Uuid) -> std::io::Result<PathBuf> {
+pub(crate) fn write_session_file(root: &Path, ts: &str, uuid: Uuid) -> std::io::Result<PathBuf> {
     write_session_file_with(
         root,
         root.join("sessions/2025/01/03"),
@@ -28,7 +28,7 @@ pub(super) fn write_session_file(root: &Path, ts: &str, uuid: Uuid) -> std::io::
     )
 }
 
-pub(super) fn write_archived_session_file(
+pub(crate) fn write_archived_session_file(
     root: &Path,
     ts: &str,
     uuid: Uuid,
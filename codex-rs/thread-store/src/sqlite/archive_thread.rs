@@ -0,0 +1,95 @@
+use chrono::Utc;
+
+use super::SqliteThreadStore;
+use super::read_thread::stored_thread_from_metadata;
+use super::read_thread::thread_metadata;
+use crate::ArchiveThreadParams;
+use crate::StoredThread;
+use crate::ThreadStoreError;
+use crate::ThreadStoreResult;
+
+pub(super) async fn archive_thread(
+    store: &SqliteThreadStore,
+    params: ArchiveThreadParams,
+) -> ThreadStoreResult<()> {
+    let thread_id = params.thread_id;
+    let runtime = store.runtime().await?;
+    let metadata = thread_metadata(runtime.as_ref(), thread_id, /*include_archived*/ false).await?;
+    runtime
+        .mark_archived(thread_id, metadata.rollout_path.as_path(), Utc::now())
+        .await
+        .map_err(|err| ThreadStoreError::Internal {
+            message: format!("failed to archive thread {thread_id}: {err}"),
+        })
+}
+
+pub(super) async fn unarchive_thread(
+    store: &SqliteThreadStore,
+    params: ArchiveThreadParams,
+) -> ThreadStoreResult<StoredThread> {
+    let thread_id = params.thread_id;
+    let runtime = store.runtime().await?;
+    let metadata = thread_metadata(runtime.as_ref(), thread_id, /*include_archived*/ true).await?;
+    runtime
+        .mark_unarchived(thread_id, metadata.rollout_path.as_path())
+        .await
+        .map_err(|err| ThreadStoreError::Internal {
+            message: format!("failed to unarchive thread {thread_id}: {err}"),
+        })?;
+    let metadata = thread_metadata(runtime.as_ref(), thread_id, /*include_archived*/ false).await?;
+    stored_thread_from_metadata(store, runtime.as_ref(), metadata).await
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::ReadThreadParams;
+    use crate::ThreadStore;
+    use crate::sqlite::test_support::test_config;
+    use crate::sqlite::test_support::write_thread;
+
+    #[tokio::test]
+    async fn archive_and_unarchive_round_trip() {
+        let home = TempDir::new().expect("temp dir");
+        let store = SqliteThreadStore::new(test_config(home.path()));
+        let thread_id =
+            ThreadId::from_string(&Uuid::from_u128(801).to_string()).expect("valid thread id");
+        write_thread(&store, thread_id, "Hello").await;
+
+        store
+            .archive_thread(ArchiveThreadParams { thread_id })
+            .await
+            .expect("archive thread");
+
+        let err = store
+            .read_thread(ReadThreadParams {
+                thread_id,
+                include_archived: false,
+                include_history: false,
+            })
+            .await
+            .expect_err("archived thread should be hidden");
+        assert!(matches!(err, ThreadStoreError::ThreadNotFound { .. }));
+        let archived = store
+            .read_thread(ReadThreadParams {
+                thread_id,
+                include_archived: true,
+                include_history: false,
+            })
+            .await
+            .expect("read archived thread");
+        assert!(archived.archived_at.is_some());
+
+        let thread = store
+            .unarchive_thread(ArchiveThreadParams { thread_id })
+            .await
+            .expect("unarchive thread");
+        assert_eq!(thread.thread_id, thread_id);
+        assert_eq!(thread.archived_at, None);
+    }
+}
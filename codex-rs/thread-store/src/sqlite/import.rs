@@ -0,0 +1,233 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_rollout::ARCHIVED_SESSIONS_SUBDIR;
+use codex_rollout::RolloutConfig;
+use codex_rollout::RolloutRecorder;
+use codex_rollout::SESSIONS_SUBDIR;
+use codex_rollout::builder_from_items;
+use codex_rollout::state_db::normalize_cwd_for_state_db;
+use codex_state::StateRuntime;
+use tracing::info;
+use tracing::warn;
+
+use crate::local::helpers::thread_id_from_rollout_path;
+
+/// Outcome of importing rollout JSONL files into the SQLite thread store.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RolloutImportSummary {
+    /// Rollouts whose metadata and history were written to the database.
+    pub imported: usize,
+    /// Rollouts that were already imported or carry no session metadata.
+    pub skipped: usize,
+    /// Rollouts that could not be read or written.
+    pub failed: usize,
+}
+
+enum ImportOutcome {
+    Imported,
+    Skipped,
+}
+
+pub(super) async fn import_rollouts(
+    config: &RolloutConfig,
+    runtime: &StateRuntime,
+) -> RolloutImportSummary {
+    let mut summary = RolloutImportSummary::default();
+    for (subdir, archived) in [(SESSIONS_SUBDIR, false), (ARCHIVED_SESSIONS_SUBDIR, true)] {
+        for path in collect_rollout_paths(config.codex_home.join(subdir).as_path()).await {
+            match import_rollout(runtime, path.as_path(), archived).await {
+                Ok(ImportOutcome::Imported) => summary.imported += 1,
+                Ok(ImportOutcome::Skipped) => summary.skipped += 1,
+                Err(err) => {
+                    warn!("failed to import rollout {}: {err}", path.display());
+                    summary.failed += 1;
+                }
+            }
+        }
+    }
+    if summary.imported > 0 || summary.failed > 0 {
+        info!(
+            "imported {} rollouts into the sqlite thread store ({} skipped, {} failed)",
+            summary.imported, summary.skipped, summary.failed
+        );
+    }
+    summary
+}
+
+async fn import_rollout(
+    runtime: &StateRuntime,
+    path: &Path,
+    archived: bool,
+) -> anyhow::Result<ImportOutcome> {
+    // Rollout file names end in the thread id, so threads imported on an earlier start are
+    // skipped without reading their rollouts again.
+    if let Some(thread_id) = thread_id_from_rollout_path(path)
+        && runtime.has_thread_items(thread_id).await?
+    {
+        return Ok(ImportOutcome::Skipped);
+    }
+    let (items, _, _) = RolloutRecorder::load_rollout_items(path).await?;
+    let Some(mut builder) = builder_from_items(items.as_slice(), path) else {
+        return Ok(ImportOutcome::Skipped);
+    };
+    if runtime.has_thread_items(builder.id).await? {
+        return Ok(ImportOutcome::Skipped);
+    }
+    let modified_at = tokio::fs::metadata(path)
+        .await?
+        .modified()
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now());
+    if archived {
+        builder.archived_at = Some(modified_at);
+    }
+    builder.cwd = normalize_cwd_for_state_db(builder.cwd.as_path());
+    runtime
+        .apply_rollout_items(
+            &builder,
+            items.as_slice(),
+            /*new_thread_memory_mode*/ None,
+            Some(modified_at),
+        )
+        .await?;
+    if runtime
+        .insert_thread_items_if_absent(builder.id, items.as_slice())
+        .await?
+    {
+        Ok(ImportOutcome::Imported)
+    } else {
+        Ok(ImportOutcome::Skipped)
+    }
+}
+
+async fn collect_rollout_paths(root: &Path) -> Vec<PathBuf> {
+    let mut stack = vec![root.to_path_buf()];
+    let mut paths = Vec::new();
+    while let Some(dir) = stack.pop() {
+        let mut read_dir = match tokio::fs::read_dir(&dir).await {
+            Ok(read_dir) => read_dir,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                warn!("failed to read directory {}: {err}", dir.display());
+                continue;
+            }
+        };
+        while let Ok(Some(entry)) = read_dir.next_entry().await {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type().await else {
+                continue;
+            };
+            if file_type.is_dir() {
+                stack.push(path);
+            } else if file_type.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("rollout-") && name.ends_with(".jsonl"))
+            {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::ListThreadsParams;
+    use crate::ReadThreadParams;
+    use crate::SortDirection;
+    use crate::ThreadSortKey;
+    use crate::ThreadStore;
+    use crate::local::test_support::write_archived_session_file;
+    use crate::local::test_support::write_session_file;
+    use crate::sqlite::SqliteThreadStore;
+    use crate::sqlite::test_support::test_config;
+
+    #[tokio::test]
+    async fn opening_the_store_imports_existing_rollouts() {
+        let home = TempDir::new().expect("temp dir");
+        let active_uuid = Uuid::from_u128(901);
+        let archived_uuid = Uuid::from_u128(902);
+        let rollout_path = write_session_file(home.path(), "2025-01-03T12-00-00", active_uuid)
+            .expect("session file");
+        write_archived_session_file(home.path(), "2025-01-03T13-00-00", archived_uuid)
+            .expect("archived session file");
+        let store = SqliteThreadStore::new(test_config(home.path()));
+
+        let page = store
+            .list_threads(ListThreadsParams {
+                page_size: 10,
+                cursor: None,
+                sort_key: ThreadSortKey::CreatedAt,
+                sort_direction: SortDirection::Desc,
+                allowed_sources: Vec::new(),
+                model_providers: None,
+                cwd_filters: None,
+                archived: false,
+                search_term: None,
+                use_state_db_only: false,
+            })
+            .await
+            .expect("list threads");
+        let active_id = ThreadId::from_string(&active_uuid.to_string()).expect("valid thread id");
+        assert_eq!(
+            page.items
+                .iter()
+                .map(|thread| thread.thread_id)
+                .collect::<Vec<_>>(),
+            vec![active_id]
+        );
+        assert_eq!(page.items[0].rollout_path, Some(rollout_path));
+        assert_eq!(page.items[0].preview, "Hello from user");
+
+        let archived_id =
+            ThreadId::from_string(&archived_uuid.to_string()).expect("valid thread id");
+        let archived = store
+            .read_thread(ReadThreadParams {
+                thread_id: archived_id,
+                include_archived: true,
+                include_history: true,
+            })
+            .await
+            .expect("read archived thread");
+        assert!(archived.archived_at.is_some());
+        assert_eq!(
+            archived.history.expect("history should load").items.len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn repeated_imports_skip_stored_threads_without_reading_them() {
+        let home = TempDir::new().expect("temp dir");
+        let rollout_path =
+            write_session_file(home.path(), "2025-01-03T12-00-00", Uuid::from_u128(903))
+                .expect("session file");
+        let config = test_config(home.path());
+        let store = SqliteThreadStore::new(config.clone());
+        let runtime = store.runtime().await.expect("runtime");
+        // A rollout that was already imported is skipped by its file name, so it is not parsed.
+        std::fs::write(&rollout_path, "not a rollout\n").expect("overwrite rollout");
+
+        let summary = import_rollouts(&config, runtime.as_ref()).await;
+
+        assert_eq!(
+            summary,
+            RolloutImportSummary {
+                imported: 0,
+                skipped: 1,
+                failed: 0,
+            }
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use chrono::DateTime;
+use codex_rollout::state_db::normalize_cwd_for_state_db;
+use codex_state::Anchor;
+use codex_state::ThreadFilterOptions;
+
+use super::SqliteThreadStore;
+use super::read_thread::stored_thread_from_metadata;
+use crate::ListThreadsParams;
+use crate::SortDirection;
+use crate::ThreadPage;
+use crate::ThreadSortKey;
+use crate::ThreadStoreError;
+use crate::ThreadStoreResult;
+
+pub(super) async fn list_threads(
+    store: &SqliteThreadStore,
+    params: ListThreadsParams,
+) -> ThreadStoreResult<ThreadPage> {
+    let anchor = params
+        .cursor
+        .as_deref()
+        .map(|cursor| {
+            parse_anchor(cursor).ok_or_else(|| ThreadStoreError::InvalidRequest {
+                message: format!("invalid cursor: {cursor}"),
+            })
+        })
+        .transpose()?;
+    let sort_key = match params.sort_key {
+        ThreadSortKey::CreatedAt => codex_state::SortKey::CreatedAt,
+        ThreadSortKey::UpdatedAt => codex_state::SortKey::UpdatedAt,
+    };
+    let sort_direction = match params.sort_direction {
+        SortDirection::Asc => codex_state::SortDirection::Asc,
+        SortDirection::Desc => codex_state::SortDirection::Desc,
+    };
+    let allowed_sources = params
+        .allowed_sources
+        .iter()
+        .map(|source| match serde_json::to_value(source) {
+            Ok(serde_json::Value::String(source)) => source,
+            Ok(other) => other.to_string(),
+            Err(_) => String::new(),
+        })
+        .collect::<Vec<_>>();
+    let cwd_filters = params.cwd_filters.as_ref().map(|cwds| {
+        cwds.iter()
+            .map(|cwd| normalize_cwd_for_state_db(cwd))
+            .collect::<Vec<_>>()
+    });
+
+    let runtime = store.runtime().await?;
+    let page = runtime
+        .list_threads(
+            params.page_size,
+            ThreadFilterOptions {
+                archived_only: params.archived,
+                allowed_sources: allowed_sources.as_slice(),
+                model_providers: params.model_providers.as_deref(),
+                cwd_filters: cwd_filters.as_deref(),
+                anchor: anchor.as_ref(),
+                sort_key,
+                sort_direction,
+                search_term: params.search_term.as_deref(),
+            },
+        )
+        .await
+        .map_err(|err| ThreadStoreError::Internal {
+            message: format!("failed to list threads: {err}"),
+        })?;
+
+    let mut items = Vec::with_capacity(page.items.len());
+    for metadata in page.items {
+        items.push(stored_thread_from_metadata(store, runtime.as_ref(), metadata).await?);
+    }
+    let next_cursor = page
+        .next_anchor
+        .map(|anchor| anchor.ts.timestamp_millis().to_string());
+    Ok(ThreadPage { items, next_cursor })
+}
+
+/// Cursors are the anchor timestamp in milliseconds since the Unix epoch.
+fn parse_anchor(cursor: &str) -> Option<Anchor> {
+    let millis = cursor.parse::<i64>().ok()?;
+    let ts = DateTime::from_timestamp_millis(millis)?;
+    Some(Anchor { ts })
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::ThreadStore;
+    use crate::sqlite::test_support::test_config;
+    use crate::sqlite::test_support::write_thread;
+
+    fn list_params(cursor: Option<String>) -> ListThreadsParams {
+        ListThreadsParams {
+            page_size: 1,
+            cursor,
+            sort_key: ThreadSortKey::CreatedAt,
+            sort_direction: SortDirection::Asc,
+            allowed_sources: Vec::new(),
+            model_providers: None,
+            cwd_filters: None,
+            archived: false,
+            search_term: None,
+            use_state_db_only: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn list_threads_pages_through_stored_threads() {
+        let home = TempDir::new().expect("temp dir");
+        let store = SqliteThreadStore::new(test_config(home.path()));
+        let first_id =
+            ThreadId::from_string(&Uuid::from_u128(601).to_string()).expect("valid thread id");
+        let second_id =
+            ThreadId::from_string(&Uuid::from_u128(602).to_string()).expect("valid thread id");
+        write_thread(&store, first_id, "first").await;
+        // Keep the creation timestamps of the two threads in distinct milliseconds.
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        write_thread(&store, second_id, "second").await;
+
+        let first_page = store
+            .list_threads(list_params(/*cursor*/ None))
+            .await
+            .expect("first page");
+        assert_eq!(
+            first_page
+                .items
+                .iter()
+                .map(|thread| thread.thread_id)
+                .collect::<Vec<_>>(),
+            vec![first_id]
+        );
+        let cursor = first_page.next_cursor.expect("next cursor");
+
+        let second_page = store
+            .list_threads(list_params(Some(cursor)))
+            .await
+            .expect("second page");
+        assert_eq!(
+            second_page
+                .items
+                .iter()
+                .map(|thread| thread.preview.as_str())
+                .collect::<Vec<_>>(),
+            vec!["second"]
+        );
+    }
+
+    #[tokio::test]
+    async fn list_threads_rejects_malformed_cursor() {
+        let home = TempDir::new().expect("temp dir");
+        let store = SqliteThreadStore::new(test_config(home.path()));
+
+        let err = store
+            .list_threads(list_params(Some("not-a-cursor".to_string())))
+            .await
+            .expect_err("cursor should be rejected");
+
+        assert!(matches!(err, ThreadStoreError::InvalidRequest { .. }));
+    }
+}
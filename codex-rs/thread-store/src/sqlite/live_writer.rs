@@ -0,0 +1,218 @@
+use chrono::Utc;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::RolloutItem;
+use codex_rollout::EventPersistenceMode;
+use codex_rollout::RolloutRecorderParams;
+use codex_rollout::builder_from_items;
+use codex_rollout::new_thread_session_meta_line;
+use codex_rollout::persisted_rollout_items;
+use codex_rollout::state_db::normalize_cwd_for_state_db;
+use codex_state::StateRuntime;
+use codex_state::ThreadMetadataBuilder;
+use std::path::Path;
+
+use super::SqliteThreadStore;
+use crate::AppendThreadItemsParams;
+use crate::CreateThreadParams;
+use crate::ResumeThreadParams;
+use crate::ThreadEventPersistenceMode;
+use crate::ThreadStoreError;
+use crate::ThreadStoreResult;
+
+/// Write state for a thread that is open for appends.
+pub(super) struct LiveThreadWriter {
+    builder: ThreadMetadataBuilder,
+    event_persistence_mode: EventPersistenceMode,
+    /// Items held back until a newly created thread is materialized.
+    pending: Vec<RolloutItem>,
+    materialized: bool,
+}
+
+impl LiveThreadWriter {
+    async fn write(
+        &mut self,
+        runtime: &StateRuntime,
+        items: Vec<RolloutItem>,
+    ) -> ThreadStoreResult<()> {
+        if !self.materialized {
+            self.pending.extend(items);
+            return Ok(());
+        }
+        write_items(runtime, &self.builder, &items).await
+    }
+
+    async fn materialize(&mut self, runtime: &StateRuntime) -> ThreadStoreResult<()> {
+        if self.materialized {
+            return Ok(());
+        }
+        write_items(runtime, &self.builder, &self.pending).await?;
+        self.pending.clear();
+        self.materialized = true;
+        Ok(())
+    }
+}
+
+pub(super) async fn create_thread(
+    store: &SqliteThreadStore,
+    params: CreateThreadParams,
+) -> ThreadStoreResult<()> {
+    let thread_id = params.thread_id;
+    let event_persistence_mode = event_persistence_mode(params.event_persistence_mode);
+    let session_meta_line = new_thread_session_meta_line(
+        &store.config,
+        RolloutRecorderParams::new(
+            thread_id,
+            params.forked_from_id,
+            params.source,
+            params.base_instructions,
+            params.dynamic_tools,
+            event_persistence_mode,
+        ),
+    )
+    .await
+    .map_err(|err| ThreadStoreError::Internal {
+        message: format!("failed to initialize sqlite thread {thread_id}: {err}"),
+    })?
+    .ok_or_else(|| ThreadStoreError::Internal {
+        message: format!("failed to build session metadata for thread {thread_id}"),
+    })?;
+    let session_meta = RolloutItem::SessionMeta(session_meta_line);
+    let builder = thread_builder(
+        std::slice::from_ref(&session_meta),
+        Path::new(""),
+        thread_id,
+    )?;
+    store
+        .insert_live_writer(
+            thread_id,
+            LiveThreadWriter {
+                builder,
+                event_persistence_mode,
+                pending: vec![session_meta],
+                materialized: false,
+            },
+        )
+        .await
+}
+
+pub(super) async fn resume_thread(
+    store: &SqliteThreadStore,
+    params: ResumeThreadParams,
+) -> ThreadStoreResult<()> {
+    let thread_id = params.thread_id;
+    let runtime = store.runtime().await?;
+    let metadata = runtime
+        .get_thread(thread_id)
+        .await
+        .map_err(|err| ThreadStoreError::Internal {
+            message: format!("failed to read thread {thread_id}: {err}"),
+        })?
+        .filter(|metadata| params.include_archived || metadata.archived_at.is_none())
+        .ok_or(ThreadStoreError::ThreadNotFound { thread_id })?;
+    let history = match params.history {
+        Some(history) => history,
+        None => runtime.load_thread_items(thread_id).await.map_err(|err| {
+            ThreadStoreError::Internal {
+                message: format!("failed to load history for thread {thread_id}: {err}"),
+            }
+        })?,
+    };
+    let builder = thread_builder(&history, metadata.rollout_path.as_path(), thread_id)?;
+    store
+        .insert_live_writer(
+            thread_id,
+            LiveThreadWriter {
+                builder,
+                event_persistence_mode: event_persistence_mode(params.event_persistence_mode),
+                pending: Vec::new(),
+                materialized: true,
+            },
+        )
+        .await
+}
+
+pub(super) async fn append_items(
+    store: &SqliteThreadStore,
+    params: AppendThreadItemsParams,
+) -> ThreadStoreResult<()> {
+    let writer = store.live_writer(params.thread_id).await?;
+    let mut writer = writer.lock().await;
+    let items = persisted_rollout_items(&params.items, writer.event_persistence_mode);
+    if items.is_empty() {
+        return Ok(());
+    }
+    let runtime = store.runtime().await?;
+    writer.write(runtime.as_ref(), items).await
+}
+
+pub(super) async fn persist_thread(
+    store: &SqliteThreadStore,
+    thread_id: ThreadId,
+) -> ThreadStoreResult<()> {
+    let writer = store.live_writer(thread_id).await?;
+    let runtime = store.runtime().await?;
+    writer.lock().await.materialize(runtime.as_ref()).await
+}
+
+pub(super) async fn flush_thread(
+    store: &SqliteThreadStore,
+    thread_id: ThreadId,
+) -> ThreadStoreResult<()> {
+    // Items of a materialized thread are committed as they are appended, so there is nothing
+    // left to flush.
+    store.live_writer(thread_id).await.map(|_| ())
+}
+
+pub(super) async fn shutdown_thread(
+    store: &SqliteThreadStore,
+    thread_id: ThreadId,
+) -> ThreadStoreResult<()> {
+    store.remove_live_writer(thread_id).await.map(|_| ())
+}
+
+pub(super) async fn discard_thread(
+    store: &SqliteThreadStore,
+    thread_id: ThreadId,
+) -> ThreadStoreResult<()> {
+    store.remove_live_writer(thread_id).await.map(|_| ())
+}
+
+/// Writes thread metadata and the items themselves in one transaction, so a failed write leaves
+/// neither behind.
+async fn write_items(
+    runtime: &StateRuntime,
+    builder: &ThreadMetadataBuilder,
+    items: &[RolloutItem],
+) -> ThreadStoreResult<()> {
+    runtime
+        .apply_rollout_items_and_append_thread_items(
+            builder,
+            items,
+            /*new_thread_memory_mode*/ None,
+            Some(Utc::now()),
+        )
+        .await
+        .map_err(|err| ThreadStoreError::Internal {
+            message: format!("failed to append items to thread {}: {err}", builder.id),
+        })
+}
+
+fn thread_builder(
+    items: &[RolloutItem],
+    rollout_path: &Path,
+    thread_id: ThreadId,
+) -> ThreadStoreResult<ThreadMetadataBuilder> {
+    let mut builder =
+        builder_from_items(items, rollout_path).ok_or_else(|| ThreadStoreError::Internal {
+            message: format!("thread {thread_id} is missing session metadata"),
+        })?;
+    builder.cwd = normalize_cwd_for_state_db(builder.cwd.as_path());
+    Ok(builder)
+}
+
+fn event_persistence_mode(mode: ThreadEventPersistenceMode) -> EventPersistenceMode {
+    match mode {
+        ThreadEventPersistenceMode::Limited => EventPersistenceMode::Limited,
+        ThreadEventPersistenceMode::Extended => EventPersistenceMode::Extended,
+    }
+}
@@ -0,0 +1,215 @@
+mod archive_thread;
+mod import;
+mod list_threads;
+mod live_writer;
+mod read_thread;
+mod update_thread_metadata;
+
+#[cfg(test)]
+mod test_support;
+
+use async_trait::async_trait;
+use codex_protocol::ThreadId;
+use codex_rollout::RolloutConfig;
+use codex_state::StateRuntime;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::sync::OnceCell;
+
+use crate::AppendThreadItemsParams;
+use crate::ArchiveThreadParams;
+use crate::CreateThreadParams;
+use crate::ListThreadsParams;
+use crate::LoadThreadHistoryParams;
+use crate::ReadThreadByRolloutPathParams;
+use crate::ReadThreadParams;
+use crate::ResumeThreadParams;
+use crate::StoredThread;
+use crate::StoredThreadHistory;
+use crate::ThreadPage;
+use crate::ThreadStore;
+use crate::ThreadStoreError;
+use crate::ThreadStoreResult;
+use crate::UpdateThreadMetadataParams;
+
+pub use import::RolloutImportSummary;
+use live_writer::LiveThreadWriter;
+
+/// SQLite-backed implementation of [`ThreadStore`].
+///
+/// Thread metadata and history both live in the `codex-state` database, so reads, listings and
+/// metadata updates never touch rollout files. Rollout files already under `codex_home` are
+/// imported the first time the store opens the database.
+#[derive(Clone)]
+pub struct SqliteThreadStore {
+    pub(super) config: RolloutConfig,
+    runtime: Arc<OnceCell<Arc<StateRuntime>>>,
+    live_writers: Arc<Mutex<HashMap<ThreadId, Arc<Mutex<LiveThreadWriter>>>>>,
+}
+
+impl std::fmt::Debug for SqliteThreadStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqliteThreadStore")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SqliteThreadStore {
+    /// Create a SQLite store that keeps its database under `config.sqlite_home`.
+    pub fn new(config: RolloutConfig) -> Self {
+        Self {
+            config,
+            runtime: Arc::new(OnceCell::new()),
+            live_writers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Import rollout files under `codex_home` that are not in the database yet.
+    ///
+    /// Threads that already have stored history are skipped, so this is safe to call more than
+    /// once. The store runs it automatically when it first opens the database.
+    pub async fn import_rollouts(&self) -> ThreadStoreResult<RolloutImportSummary> {
+        let runtime = self.runtime().await?;
+        Ok(import::import_rollouts(&self.config, runtime.as_ref()).await)
+    }
+
+    pub(super) async fn runtime(&self) -> ThreadStoreResult<Arc<StateRuntime>> {
+        self.runtime
+            .get_or_try_init(|| async {
+                let runtime = StateRuntime::init(
+                    self.config.sqlite_home.clone(),
+                    self.config.model_provider_id.clone(),
+                )
+                .await
+                .map_err(|err| ThreadStoreError::Internal {
+                    message: format!("failed to open thread database: {err}"),
+                })?;
+                import::import_rollouts(&self.config, runtime.as_ref()).await;
+                Ok(runtime)
+            })
+            .await
+            .cloned()
+    }
+
+    async fn live_writer(
+        &self,
+        thread_id: ThreadId,
+    ) -> ThreadStoreResult<Arc<Mutex<LiveThreadWriter>>> {
+        self.live_writers
+            .lock()
+            .await
+            .get(&thread_id)
+            .cloned()
+            .ok_or(ThreadStoreError::ThreadNotFound { thread_id })
+    }
+
+    async fn insert_live_writer(
+        &self,
+        thread_id: ThreadId,
+        writer: LiveThreadWriter,
+    ) -> ThreadStoreResult<()> {
+        match self.live_writers.lock().await.entry(thread_id) {
+            Entry::Occupied(entry) => Err(ThreadStoreError::InvalidRequest {
+                message: format!("thread {} already has a live sqlite writer", entry.key()),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(Arc::new(Mutex::new(writer)));
+                Ok(())
+            }
+        }
+    }
+
+    async fn remove_live_writer(
+        &self,
+        thread_id: ThreadId,
+    ) -> ThreadStoreResult<Arc<Mutex<LiveThreadWriter>>> {
+        self.live_writers
+            .lock()
+            .await
+            .remove(&thread_id)
+            .ok_or(ThreadStoreError::ThreadNotFound { thread_id })
+    }
+}
+
+#[async_trait]
+impl ThreadStore for SqliteThreadStore {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    async fn create_thread(&self, params: CreateThreadParams) -> ThreadStoreResult<()> {
+        live_writer::create_thread(self, params).await
+    }
+
+    async fn resume_thread(&self, params: ResumeThreadParams) -> ThreadStoreResult<()> {
+        live_writer::resume_thread(self, params).await
+    }
+
+    async fn append_items(&self, params: AppendThreadItemsParams) -> ThreadStoreResult<()> {
+        live_writer::append_items(self, params).await
+    }
+
+    async fn persist_thread(&self, thread_id: ThreadId) -> ThreadStoreResult<()> {
+        live_writer::persist_thread(self, thread_id).await
+    }
+
+    async fn flush_thread(&self, thread_id: ThreadId) -> ThreadStoreResult<()> {
+        live_writer::flush_thread(self, thread_id).await
+    }
+
+    async fn shutdown_thread(&self, thread_id: ThreadId) -> ThreadStoreResult<()> {
+        live_writer::shutdown_thread(self, thread_id).await
+    }
+
+    async fn discard_thread(&self, thread_id: ThreadId) -> ThreadStoreResult<()> {
+        live_writer::discard_thread(self, thread_id).await
+    }
+
+    async fn load_history(
+        &self,
+        params: LoadThreadHistoryParams,
+    ) -> ThreadStoreResult<StoredThreadHistory> {
+        read_thread::load_history(self, params).await
+    }
+
+    async fn read_thread(&self, params: ReadThreadParams) -> ThreadStoreResult<StoredThread> {
+        read_thread::read_thread(self, params).await
+    }
+
+    async fn read_thread_by_rollout_path(
+        &self,
+        params: ReadThreadByRolloutPathParams,
+    ) -> ThreadStoreResult<StoredThread> {
+        Err(ThreadStoreError::InvalidRequest {
+            message: format!(
+                "sqlite thread store does not read threads by rollout path {}",
+                params.rollout_path.display()
+            ),
+        })
+    }
+
+    async fn list_threads(&self, params: ListThreadsParams) -> ThreadStoreResult<ThreadPage> {
+        list_threads::list_threads(self, params).await
+    }
+
+    async fn update_thread_metadata(
+        &self,
+        params: UpdateThreadMetadataParams,
+    ) -> ThreadStoreResult<StoredThread> {
+        update_thread_metadata::update_thread_metadata(self, params).await
+    }
+
+    async fn archive_thread(&self, params: ArchiveThreadParams) -> ThreadStoreResult<()> {
+        archive_thread::archive_thread(self, params).await
+    }
+
+    async fn unarchive_thread(
+        &self,
+        params: ArchiveThreadParams,
+    ) -> ThreadStoreResult<StoredThread> {
+        archive_thread::unarchive_thread(self, params).await
+    }
+}
@@ -0,0 +1,256 @@
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_state::StateRuntime;
+use codex_state::ThreadMetadata;
+
+use super::SqliteThreadStore;
+use crate::LoadThreadHistoryParams;
+use crate::ReadThreadParams;
+use crate::StoredThread;
+use crate::StoredThreadHistory;
+use crate::ThreadStoreError;
+use crate::ThreadStoreResult;
+use crate::local::helpers::distinct_title;
+use crate::local::helpers::git_info_from_parts;
+use crate::local::helpers::parse_or_default;
+use crate::local::helpers::parse_session_source;
+
+pub(super) async fn read_thread(
+    store: &SqliteThreadStore,
+    params: ReadThreadParams,
+) -> ThreadStoreResult<StoredThread> {
+    let thread_id = params.thread_id;
+    let runtime = store.runtime().await?;
+    let metadata = thread_metadata(runtime.as_ref(), thread_id, params.include_archived).await?;
+    let mut thread = stored_thread_from_metadata(store, runtime.as_ref(), metadata).await?;
+    if params.include_history {
+        let items = load_items(runtime.as_ref(), thread_id).await?;
+        thread.history = Some(StoredThreadHistory { thread_id, items });
+    }
+    Ok(thread)
+}
+
+pub(super) async fn load_history(
+    store: &SqliteThreadStore,
+    params: LoadThreadHistoryParams,
+) -> ThreadStoreResult<StoredThreadHistory> {
+    let thread_id = params.thread_id;
+    let runtime = store.runtime().await?;
+    thread_metadata(runtime.as_ref(), thread_id, params.include_archived).await?;
+    let items = load_items(runtime.as_ref(), thread_id).await?;
+    Ok(StoredThreadHistory { thread_id, items })
+}
+
+/// Look up a thread row, treating archived threads as missing unless they were requested.
+pub(super) async fn thread_metadata(
+    runtime: &StateRuntime,
+    thread_id: ThreadId,
+    include_archived: bool,
+) -> ThreadStoreResult<ThreadMetadata> {
+    runtime
+        .get_thread(thread_id)
+        .await
+        .map_err(|err| ThreadStoreError::Internal {
+            message: format!("failed to read thread {thread_id}: {err}"),
+        })?
+        .filter(|metadata| include_archived || metadata.archived_at.is_none())
+        .ok_or(ThreadStoreError::ThreadNotFound { thread_id })
+}
+
+pub(super) async fn stored_thread_from_metadata(
+    store: &SqliteThreadStore,
+    runtime: &StateRuntime,
+    metadata: ThreadMetadata,
+) -> ThreadStoreResult<StoredThread> {
+    let forked_from_id = match runtime.first_thread_item(metadata.id).await {
+        Ok(Some(RolloutItem::SessionMeta(meta_line))) => meta_line.meta.forked_from_id,
+        Ok(_) => None,
+        Err(err) => {
+            return Err(ThreadStoreError::Internal {
+                message: format!("failed to read session metadata for {}: {err}", metadata.id),
+            });
+        }
+    };
+    // Threads created by this store have no rollout file, which the state DB records as an
+    // empty path.
+    let rollout_path =
+        (!metadata.rollout_path.as_os_str().is_empty()).then(|| metadata.rollout_path.clone());
+    Ok(StoredThread {
+        thread_id: metadata.id,
+        rollout_path,
+        forked_from_id,
+        preview: metadata.first_user_message.clone().unwrap_or_default(),
+        name: distinct_title(&metadata),
+        model_provider: if metadata.model_provider.is_empty() {
+            store.config.model_provider_id.clone()
+        } else {
+            metadata.model_provider
+        },
+        model: metadata.model,
+        reasoning_effort: metadata.reasoning_effort,
+        created_at: metadata.created_at,
+        updated_at: metadata.updated_at,
+        archived_at: metadata.archived_at,
+        cwd: metadata.cwd,
+        cli_version: metadata.cli_version,
+        source: parse_session_source(&metadata.source),
+        agent_nickname: metadata.agent_nickname,
+        agent_role: metadata.agent_role,
+        agent_path: metadata.agent_path,
+        git_info: git_info_from_parts(
+            metadata.git_sha,
+            metadata.git_branch,
+            metadata.git_origin_url,
+        ),
+        approval_mode: parse_or_default(&metadata.approval_mode, AskForApproval::OnRequest),
+        sandbox_policy: parse_or_default(
+            &metadata.sandbox_policy,
+            SandboxPolicy::new_read_only_policy(),
+        ),
+        token_usage: None,
+        first_user_message: metadata.first_user_message,
+        history: None,
+    })
+}
+
+async fn load_items(
+    runtime: &StateRuntime,
+    thread_id: ThreadId,
+) -> ThreadStoreResult<Vec<RolloutItem>> {
+    runtime
+        .load_thread_items(thread_id)
+        .await
+        .map_err(|err| ThreadStoreError::Internal {
+            message: format!("failed to load history for thread {thread_id}: {err}"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::RolloutItem;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::AppendThreadItemsParams;
+    use crate::ResumeThreadParams;
+    use crate::ThreadEventPersistenceMode;
+    use crate::ThreadStore;
+    use crate::sqlite::test_support::create_thread_params;
+    use crate::sqlite::test_support::test_config;
+    use crate::sqlite::test_support::user_message_item;
+    use crate::sqlite::test_support::write_thread;
+
+    fn user_messages(items: &[RolloutItem]) -> Vec<String> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                RolloutItem::EventMsg(EventMsg::UserMessage(event)) => Some(event.message.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn read_thread_returns_persisted_history() {
+        let home = TempDir::new().expect("temp dir");
+        let store = SqliteThreadStore::new(test_config(home.path()));
+        let thread_id =
+            ThreadId::from_string(&Uuid::from_u128(501).to_string()).expect("valid thread id");
+        write_thread(&store, thread_id, "Hello from sqlite").await;
+
+        let thread = store
+            .read_thread(ReadThreadParams {
+                thread_id,
+                include_archived: false,
+                include_history: true,
+            })
+            .await
+            .expect("read thread");
+
+        assert_eq!(thread.thread_id, thread_id);
+        assert_eq!(thread.rollout_path, None);
+        assert_eq!(thread.preview, "Hello from sqlite");
+        assert_eq!(thread.model_provider, "test-provider");
+        let history = thread.history.expect("history should load");
+        assert!(matches!(
+            history.items.first(),
+            Some(RolloutItem::SessionMeta(_))
+        ));
+        assert_eq!(user_messages(&history.items), vec!["Hello from sqlite"]);
+    }
+
+    #[tokio::test]
+    async fn unpersisted_threads_are_not_readable() {
+        let home = TempDir::new().expect("temp dir");
+        let store = SqliteThreadStore::new(test_config(home.path()));
+        let thread_id =
+            ThreadId::from_string(&Uuid::from_u128(502).to_string()).expect("valid thread id");
+        store
+            .create_thread(create_thread_params(thread_id))
+            .await
+            .expect("create thread");
+        store
+            .append_items(AppendThreadItemsParams {
+                thread_id,
+                items: vec![user_message_item("not yet persisted")],
+            })
+            .await
+            .expect("append items");
+
+        let err = store
+            .load_history(LoadThreadHistoryParams {
+                thread_id,
+                include_archived: false,
+            })
+            .await
+            .expect_err("thread should not be stored yet");
+
+        assert!(matches!(err, ThreadStoreError::ThreadNotFound { .. }));
+    }
+
+    #[tokio::test]
+    async fn resumed_threads_append_after_existing_history() {
+        let home = TempDir::new().expect("temp dir");
+        let store = SqliteThreadStore::new(test_config(home.path()));
+        let thread_id =
+            ThreadId::from_string(&Uuid::from_u128(503).to_string()).expect("valid thread id");
+        write_thread(&store, thread_id, "first").await;
+
+        store
+            .resume_thread(ResumeThreadParams {
+                thread_id,
+                rollout_path: None,
+                history: None,
+                include_archived: false,
+                event_persistence_mode: ThreadEventPersistenceMode::Limited,
+            })
+            .await
+            .expect("resume thread");
+        store
+            .append_items(AppendThreadItemsParams {
+                thread_id,
+                items: vec![user_message_item("second")],
+            })
+            .await
+            .expect("append items");
+        store
+            .shutdown_thread(thread_id)
+            .await
+            .expect("shutdown thread");
+
+        let history = store
+            .load_history(LoadThreadHistoryParams {
+                thread_id,
+                include_archived: false,
+            })
+            .await
+            .expect("load history");
+        assert_eq!(user_messages(&history.items), vec!["first", "second"]);
+    }
+}
@@ -0,0 +1,68 @@
+use std::path::Path;
+
+use codex_protocol::ThreadId;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::UserMessageEvent;
+use codex_rollout::RolloutConfig;
+
+use super::SqliteThreadStore;
+use crate::AppendThreadItemsParams;
+use crate::CreateThreadParams;
+use crate::ThreadEventPersistenceMode;
+use crate::ThreadStore;
+
+pub(super) fn test_config(codex_home: &Path) -> RolloutConfig {
+    RolloutConfig {
+        codex_home: codex_home.to_path_buf(),
+        sqlite_home: codex_home.to_path_buf(),
+        cwd: codex_home.to_path_buf(),
+        model_provider_id: "test-provider".to_string(),
+        generate_memories: true,
+    }
+}
+
+pub(super) fn create_thread_params(thread_id: ThreadId) -> CreateThreadParams {
+    CreateThreadParams {
+        thread_id,
+        forked_from_id: None,
+        source: SessionSource::Cli,
+        base_instructions: BaseInstructions::default(),
+        dynamic_tools: Vec::new(),
+        event_persistence_mode: ThreadEventPersistenceMode::Limited,
+    }
+}
+
+pub(super) fn user_message_item(message: &str) -> RolloutItem {
+    RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+        message: message.to_string(),
+        images: None,
+        local_images: Vec::new(),
+        text_elements: Vec::new(),
+    }))
+}
+
+/// Creates, persists and shuts down a thread whose history holds one user message.
+pub(super) async fn write_thread(store: &SqliteThreadStore, thread_id: ThreadId, message: &str) {
+    store
+        .create_thread(create_thread_params(thread_id))
+        .await
+        .expect("create thread");
+    store
+        .append_items(AppendThreadItemsParams {
+            thread_id,
+            items: vec![user_message_item(message)],
+        })
+        .await
+        .expect("append items");
+    store
+        .persist_thread(thread_id)
+        .await
+        .expect("persist thread");
+    store
+        .shutdown_thread(thread_id)
+        .await
+        .expect("shutdown thread");
+}
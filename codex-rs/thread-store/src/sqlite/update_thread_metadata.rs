@@ -0,0 +1,124 @@
+use codex_protocol::protocol::ThreadMemoryMode;
+use codex_state::ThreadFieldsUpdate;
+
+use super::SqliteThreadStore;
+use super::read_thread::stored_thread_from_metadata;
+use super::read_thread::thread_metadata;
+use crate::StoredThread;
+use crate::ThreadStoreError;
+use crate::ThreadStoreResult;
+use crate::UpdateThreadMetadataParams;
+
+pub(super) async fn update_thread_metadata(
+    store: &SqliteThreadStore,
+    params: UpdateThreadMetadataParams,
+) -> ThreadStoreResult<StoredThread> {
+    let thread_id = params.thread_id;
+    let runtime = store.runtime().await?;
+    thread_metadata(runtime.as_ref(), thread_id, params.include_archived).await?;
+
+    let patch = params.patch;
+    let git_info = patch.git_info.unwrap_or_default();
+    let update = ThreadFieldsUpdate {
+        title: patch.name.as_deref(),
+        memory_mode: patch.memory_mode.map(|memory_mode| match memory_mode {
+            ThreadMemoryMode::Enabled => "enabled",
+            ThreadMemoryMode::Disabled => "disabled",
+        }),
+        git_sha: git_info.sha.as_ref().map(Option::as_deref),
+        git_branch: git_info.branch.as_ref().map(Option::as_deref),
+        git_origin_url: git_info.origin_url.as_ref().map(Option::as_deref),
+    };
+    // All requested fields are written by a single UPDATE, so a patch is applied completely or
+    // not at all.
+    let updated = runtime
+        .update_thread_fields(thread_id, update)
+        .await
+        .map_err(|err| ThreadStoreError::Internal {
+            message: format!("failed to update thread {thread_id}: {err}"),
+        })?;
+    if !updated {
+        return Err(ThreadStoreError::ThreadNotFound { thread_id });
+    }
+
+    let metadata = thread_metadata(runtime.as_ref(), thread_id, params.include_archived).await?;
+    stored_thread_from_metadata(store, runtime.as_ref(), metadata).await
+}
+
+#[cfg(test)]
+mod tests {
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::GitInfoPatch;
+    use crate::ThreadMetadataPatch;
+    use crate::ThreadStore;
+    use crate::sqlite::test_support::test_config;
+    use crate::sqlite::test_support::write_thread;
+
+    #[tokio::test]
+    async fn update_thread_metadata_applies_name_and_git_patch() {
+        let home = TempDir::new().expect("temp dir");
+        let store = SqliteThreadStore::new(test_config(home.path()));
+        let thread_id =
+            ThreadId::from_string(&Uuid::from_u128(701).to_string()).expect("valid thread id");
+        write_thread(&store, thread_id, "Hello").await;
+
+        let thread = store
+            .update_thread_metadata(UpdateThreadMetadataParams {
+                thread_id,
+                patch: ThreadMetadataPatch {
+                    name: Some("Renamed thread".to_string()),
+                    memory_mode: Some(ThreadMemoryMode::Disabled),
+                    git_info: Some(GitInfoPatch {
+                        sha: None,
+                        branch: Some(Some("feature".to_string())),
+                        origin_url: None,
+                    }),
+                },
+                include_archived: false,
+            })
+            .await
+            .expect("update thread metadata");
+
+        assert_eq!(thread.name.as_deref(), Some("Renamed thread"));
+        assert_eq!(
+            thread
+                .git_info
+                .and_then(|git_info| git_info.branch)
+                .as_deref(),
+            Some("feature")
+        );
+        let runtime = store.runtime().await.expect("runtime");
+        assert_eq!(
+            runtime
+                .get_thread_memory_mode(thread_id)
+                .await
+                .expect("memory mode")
+                .as_deref(),
+            Some("disabled")
+        );
+    }
+
+    #[tokio::test]
+    async fn update_thread_metadata_rejects_unknown_threads() {
+        let home = TempDir::new().expect("temp dir");
+        let store = SqliteThreadStore::new(test_config(home.path()));
+        let thread_id =
+            ThreadId::from_string(&Uuid::from_u128(702).to_string()).expect("valid thread id");
+
+        let err = store
+            .update_thread_metadata(UpdateThreadMetadataParams {
+                thread_id,
+                patch: ThreadMetadataPatch::default(),
+                include_archived: true,
+            })
+            .await
+            .expect_err("unknown thread should be rejected");
+
+        assert!(matches!(err, ThreadStoreError::ThreadNotFound { .. }));
+    }
+}
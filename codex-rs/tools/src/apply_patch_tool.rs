@@ -18,13 +18,16 @@ Your patch language is a stripped‑down, file‑oriented diff format designed t
 
 Within that envelope, you get a sequence of file operations.
 You MUST include a header to specify the action you are taking.
-Each operation starts with one of three headers:
+Each operation starts with one of five headers:
 
 *** Add File: <path> - create a new file. Every following line is a + line (the initial contents).
+*** Add Binary File: <path> - create a non-text file. Every following + line holds part of its contents, base64-encoded.
+*** Add Symlink: <path> - create a symbolic link. The next line is *** Link to: <target>.
 *** Delete File: <path> - remove an existing file. Nothing follows.
 *** Update File: <path> - patch an existing file in place (optionally with a rename).
 
 May be immediately followed by *** Move to: <new path> if you want to rename the file.
+Add File, Add Binary File and Update File (after any Move to) may be followed by *** Set Mode: <octal mode>, such as 755, to set the file's permission bits (at most 777; setuid, setgid and sticky bits are not allowed). An Update File may consist of nothing but a Set Mode line. Set Mode is ignored on platforms without Unix permissions, such as Windows.
 Then one or more “hunks”, each introduced by @@ (optionally followed by a hunk header).
 Within a hunk each line starts with:
 
@@ -50,11 +53,14 @@ The full grammar definition is below:
 Patch := Begin { FileOp } End
 Begin := "*** Begin Patch" NEWLINE
 End := "*** End Patch" NEWLINE
-FileOp := AddFile | DeleteFile | UpdateFile
-AddFile := "*** Add File: " path NEWLINE { "+" line NEWLINE }
+FileOp := AddFile | AddBinaryFile | AddSymlink | DeleteFile | UpdateFile
+AddFile := "*** Add File: " path NEWLINE [ SetMode ] { "+" line NEWLINE }
+AddBinaryFile := "*** Add Binary File: " path NEWLINE [ SetMode ] { "+" base64 NEWLINE }
+AddSymlink := "*** Add Symlink: " path NEWLINE "*** Link to: " target NEWLINE
 DeleteFile := "*** Delete File: " path NEWLINE
-UpdateFile := "*** Update File: " path NEWLINE [ MoveTo ] { Hunk }
+UpdateFile := "*** Update File: " path NEWLINE [ MoveTo ] [ SetMode ] { Hunk }
 MoveTo := "*** Move to: " newPath NEWLINE
+SetMode := "*** Set Mode: " octalMode NEWLINE
 Hunk := "@@" [ header ] NEWLINE { HunkLine } [ "*** End of File" NEWLINE ]
 HunkLine := (" " | "-" | "+") text NEWLINE
 
@@ -328,6 +328,105 @@ fn schema_type_name(schema_type: JsonSchemaPrimitiveType) -> &'static str {
     }
 }
 
+/// Check `value` against the subset of JSON Schema modeled by [`JsonSchema`].
+///
+/// Supports `type`, `enum`, `properties`, `required`, `additionalProperties`,
+/// `items`, and `anyOf`. The error names the JSON path of the first mismatch.
+pub fn validate_json_value(schema: &JsonSchema, value: &JsonValue) -> Result<(), String> {
+    validate_json_value_at(schema, value, "$")
+}
+
+fn validate_json_value_at(
+    schema: &JsonSchema,
+    value: &JsonValue,
+    path: &str,
+) -> Result<(), String> {
+    if let Some(variants) = &schema.any_of
+        && !variants
+            .iter()
+            .any(|variant| validate_json_value_at(variant, value, path).is_ok())
+    {
+        return Err(format!("{path} does not match any allowed schema"));
+    }
+
+    if let Some(schema_type) = &schema.schema_type {
+        let allowed = match schema_type {
+            JsonSchemaType::Single(schema_type) => std::slice::from_ref(schema_type),
+            JsonSchemaType::Multiple(schema_types) => schema_types.as_slice(),
+        };
+        if !allowed
+            .iter()
+            .any(|schema_type| value_has_schema_type(value, *schema_type))
+        {
+            let expected = allowed
+                .iter()
+                .map(|schema_type| schema_type_name(*schema_type))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            return Err(format!("{path} must be of type {expected}"));
+        }
+    }
+
+    if let Some(enum_values) = &schema.enum_values
+        && !enum_values.contains(value)
+    {
+        return Err(format!("{path} must be one of the allowed values"));
+    }
+
+    match value {
+        JsonValue::Object(map) => {
+            if let Some(required) = &schema.required {
+                for key in required {
+                    if !map.contains_key(key) {
+                        return Err(format!("{path} is missing required property `{key}`"));
+                    }
+                }
+            }
+            for (key, child) in map {
+                let child_path = format!("{path}.{key}");
+                match schema
+                    .properties
+                    .as_ref()
+                    .and_then(|properties| properties.get(key))
+                {
+                    Some(child_schema) => validate_json_value_at(child_schema, child, &child_path)?,
+                    None => match &schema.additional_properties {
+                        Some(AdditionalProperties::Boolean(false)) => {
+                            return Err(format!("{path} has unexpected property `{key}`"));
+                        }
+                        Some(AdditionalProperties::Schema(child_schema)) => {
+                            validate_json_value_at(child_schema, child, &child_path)?;
+                        }
+                        Some(AdditionalProperties::Boolean(true)) | None => {}
+                    },
+                }
+            }
+        }
+        JsonValue::Array(values) => {
+            if let Some(items) = &schema.items {
+                for (index, item) in values.iter().enumerate() {
+                    validate_json_value_at(items, item, &format!("{path}[{index}]"))?;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn value_has_schema_type(value: &JsonValue, schema_type: JsonSchemaPrimitiveType) -> bool {
+    match schema_type {
+        JsonSchemaPrimitiveType::String => value.is_string(),
+        JsonSchemaPrimitiveType::Number => value.is_number(),
+        JsonSchemaPrimitiveType::Boolean => value.is_boolean(),
+        JsonSchemaPrimitiveType::Integer => value.is_i64() || value.is_u64(),
+        JsonSchemaPrimitiveType::Object => value.is_object(),
+        JsonSchemaPrimitiveType::Array => value.is_array(),
+        JsonSchemaPrimitiveType::Null => value.is_null(),
+    }
+}
+
 fn singleton_null_schema_error() -> serde_json::Error {
     serde_json::Error::io(std::io::Error::new(
         std::io::ErrorKind::InvalidInput,
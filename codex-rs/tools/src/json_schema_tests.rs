@@ -3,6 +3,7 @@ use super::JsonSchema;
 use super::JsonSchemaPrimitiveType;
 use super::JsonSchemaType;
 use super::parse_tool_input_schema;
+use super::validate_json_value;
 use pretty_assertions::assert_eq;
 use std::collections::BTreeMap;
 
@@ -784,3 +785,116 @@ fn parse_tool_input_schema_preserves_string_enum_constraints() {
         )
     );
 }
+
+// Tests in this section cover validation of tool arguments against the
+// modeled schema subset.
+
+#[test]
+fn validate_json_value_accepts_matching_arguments() {
+    let schema = parse_tool_input_schema(&serde_json::json!({
+        "type": "object",
+        "properties": {
+            "command": {"type": "string"},
+            "timeout_ms": {"type": "integer"},
+            "tags": {"type": "array", "items": {"type": "string"}}
+        },
+        "required": ["command"],
+        "additionalProperties": false
+    }))
+    .expect("parse schema");
+
+    assert_eq!(
+        validate_json_value(
+            &schema,
+            &serde_json::json!({"command": "ls --dry-run", "timeout_ms": 1000, "tags": ["a"]})
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn validate_json_value_reports_first_mismatch() {
+    let schema = parse_tool_input_schema(&serde_json::json!({
+        "type": "object",
+        "properties": {
+            "command": {"type": "string"},
+            "mode": {"type": "string", "enum": ["fast", "slow"]},
+            "tags": {"type": "array", "items": {"type": "string"}}
+        },
+        "required": ["command"],
+        "additionalProperties": false
+    }))
+    .expect("parse schema");
+
+    assert_eq!(
+        validate_json_value(&schema, &serde_json::json!({"mode": "fast"})),
+        Err("$ is missing required property `command`".to_string())
+    );
+    assert_eq!(
+        validate_json_value(&schema, &serde_json::json!({"command": ["ls"]})),
+        Err("$.command must be of type string".to_string())
+    );
+    assert_eq!(
+        validate_json_value(
+            &schema,
+            &serde_json::json!({"command": "ls", "mode": "medium"})
+        ),
+        Err("$.mode must be one of the allowed values".to_string())
+    );
+    assert_eq!(
+        validate_json_value(&schema, &serde_json::json!({"command": "ls", "tags": [1]})),
+        Err("$.tags[0] must be of type string".to_string())
+    );
+    assert_eq!(
+        validate_json_value(
+            &schema,
+            &serde_json::json!({"command": "ls", "cwd": "/tmp"})
+        ),
+        Err("$ has unexpected property `cwd`".to_string())
+    );
+}
+
+#[test]
+fn validate_json_value_checks_any_of_and_nullable_types() {
+    let schema = JsonSchema::object(
+        BTreeMap::from([
+            (
+                "workdir".to_string(),
+                JsonSchema {
+                    schema_type: Some(JsonSchemaType::Multiple(vec![
+                        JsonSchemaPrimitiveType::String,
+                        JsonSchemaPrimitiveType::Null,
+                    ])),
+                    ..Default::default()
+                },
+            ),
+            (
+                "limit".to_string(),
+                JsonSchema::any_of(
+                    vec![
+                        JsonSchema::integer(/*description*/ None),
+                        JsonSchema::string_enum(
+                            vec![serde_json::json!("all")],
+                            /*description*/ None,
+                        ),
+                    ],
+                    /*description*/ None,
+                ),
+            ),
+        ]),
+        /*required*/ None,
+        Some(AdditionalProperties::Boolean(true)),
+    );
+
+    assert_eq!(
+        validate_json_value(
+            &schema,
+            &serde_json::json!({"workdir": null, "limit": "all", "extra": 1})
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        validate_json_value(&schema, &serde_json::json!({"limit": 1.5})),
+        Err("$.limit does not match any allowed schema".to_string())
+    );
+}
@@ -66,6 +66,7 @@ pub use json_schema::JsonSchema;
 pub use json_schema::JsonSchemaPrimitiveType;
 pub use json_schema::JsonSchemaType;
 pub use json_schema::parse_tool_input_schema;
+pub use json_schema::validate_json_value;
 pub use local_tool::CommandToolOptions;
 pub use local_tool::ShellToolOptions;
 pub use local_tool::create_exec_command_tool;
@@ -77,6 +78,8 @@ pub use local_tool::request_permissions_tool_description;
 pub use mcp_resource_tool::create_list_mcp_resource_templates_tool;
 pub use mcp_resource_tool::create_list_mcp_resources_tool;
 pub use mcp_resource_tool::create_read_mcp_resource_tool;
+pub use mcp_resource_tool::create_subscribe_mcp_resource_tool;
+pub use mcp_resource_tool::create_unsubscribe_mcp_resource_tool;
 pub use mcp_tool::mcp_call_tool_result_output_schema;
 pub use mcp_tool::parse_mcp_tool;
 pub use plan_tool::create_update_plan_tool;
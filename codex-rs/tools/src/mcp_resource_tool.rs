@@ -93,6 +93,52 @@ pub fn create_read_mcp_resource_tool() -> ToolSpec {
     })
 }
 
+pub fn create_subscribe_mcp_resource_tool() -> ToolSpec {
+    ToolSpec::Function(ResponsesApiTool {
+        name: "subscribe_mcp_resource".to_string(),
+        description: "Subscribe to updates for an MCP resource. When the server reports that the resource changed, the difference is added to the conversation so you can react to it.".to_string(),
+        strict: false,
+        defer_loading: None,
+        parameters: JsonSchema::object(
+            resource_subscription_properties(),
+            Some(vec!["server".to_string(), "uri".to_string()]),
+            Some(false.into()),
+        ),
+        output_schema: None,
+    })
+}
+
+pub fn create_unsubscribe_mcp_resource_tool() -> ToolSpec {
+    ToolSpec::Function(ResponsesApiTool {
+        name: "unsubscribe_mcp_resource".to_string(),
+        description: "Stop receiving updates for an MCP resource previously subscribed to with subscribe_mcp_resource.".to_string(),
+        strict: false,
+        defer_loading: None,
+        parameters: JsonSchema::object(
+            resource_subscription_properties(),
+            Some(vec!["server".to_string(), "uri".to_string()]),
+            Some(false.into()),
+        ),
+        output_schema: None,
+    })
+}
+
+fn resource_subscription_properties() -> BTreeMap<String, JsonSchema> {
+    BTreeMap::from([
+        (
+            "server".to_string(),
+            JsonSchema::string(Some(
+                "MCP server name exactly as configured. Must match the 'server' field returned by list_mcp_resources."
+                    .to_string(),
+            )),
+        ),
+        (
+            "uri".to_string(),
+            JsonSchema::string(Some("Resource URI to watch.".to_string())),
+        ),
+    ])
+}
+
 #[cfg(test)]
 #[path = "mcp_resource_tool_tests.rs"]
 mod tests;
@@ -94,3 +94,44 @@ fn read_mcp_resource_tool_matches_expected_spec() {
         })
     );
 }
+
+#[test]
+fn subscribe_mcp_resource_tools_require_server_and_uri() {
+    let properties = BTreeMap::from([
+        (
+            "server".to_string(),
+            JsonSchema::string(Some(
+                "MCP server name exactly as configured. Must match the 'server' field returned by list_mcp_resources."
+                    .to_string(),
+            )),
+        ),
+        (
+            "uri".to_string(),
+            JsonSchema::string(Some("Resource URI to watch.".to_string())),
+        ),
+    ]);
+    let required = Some(vec!["server".to_string(), "uri".to_string()]);
+
+    assert_eq!(
+        create_subscribe_mcp_resource_tool(),
+        ToolSpec::Function(ResponsesApiTool {
+            name: "subscribe_mcp_resource".to_string(),
+            description: "Subscribe to updates for an MCP resource. When the server reports that the resource changed, the difference is added to the conversation so you can react to it.".to_string(),
+            strict: false,
+            defer_loading: None,
+            parameters: JsonSchema::object(properties.clone(), required.clone(), Some(false.into())),
+            output_schema: None,
+        })
+    );
+    assert_eq!(
+        create_unsubscribe_mcp_resource_tool(),
+        ToolSpec::Function(ResponsesApiTool {
+            name: "unsubscribe_mcp_resource".to_string(),
+            description: "Stop receiving updates for an MCP resource previously subscribed to with subscribe_mcp_resource.".to_string(),
+            strict: false,
+            defer_loading: None,
+            parameters: JsonSchema::object(properties, required, Some(false.into())),
+            output_schema: None,
+        })
+    );
+}
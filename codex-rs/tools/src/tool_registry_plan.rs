@@ -48,9 +48,11 @@ use crate::create_shell_tool;
 use crate::create_spawn_agent_tool_v1;
 use crate::create_spawn_agent_tool_v2;
 use crate::create_spawn_agents_on_csv_tool;
+use crate::create_subscribe_mcp_resource_tool;
 use crate::create_test_sync_tool;
 use crate::create_tool_search_tool;
 use crate::create_tool_suggest_tool;
+use crate::create_unsubscribe_mcp_resource_tool;
 use crate::create_update_goal_tool;
 use crate::create_update_plan_tool;
 use crate::create_view_image_tool;
@@ -207,9 +209,21 @@ pub fn build_tool_registry_plan(
             /*supports_parallel_tool_calls*/ true,
             config.code_mode_enabled,
         );
+        plan.push_spec(
+            create_subscribe_mcp_resource_tool(),
+            /*supports_parallel_tool_calls*/ true,
+            config.code_mode_enabled,
+        );
+        plan.push_spec(
+            create_unsubscribe_mcp_resource_tool(),
+            /*supports_parallel_tool_calls*/ true,
+            config.code_mode_enabled,
+        );
         plan.register_handler("list_mcp_resources", ToolHandlerKind::McpResource);
         plan.register_handler("list_mcp_resource_templates", ToolHandlerKind::McpResource);
         plan.register_handler("read_mcp_resource", ToolHandlerKind::McpResource);
+        plan.register_handler("subscribe_mcp_resource", ToolHandlerKind::McpResource);
+        plan.register_handler("unsubscribe_mcp_resource", ToolHandlerKind::McpResource);
     }
 
     plan.push_spec(
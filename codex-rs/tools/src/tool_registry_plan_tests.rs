@@ -959,7 +959,11 @@ fn mcp_resource_tools_are_hidden_without_mcp_servers() {
     assert!(
         !tools.iter().any(|tool| matches!(
             tool.spec.name(),
-            "list_mcp_resources" | "list_mcp_resource_templates" | "read_mcp_resource"
+            "list_mcp_resources"
+                | "list_mcp_resource_templates"
+                | "read_mcp_resource"
+                | "subscribe_mcp_resource"
+                | "unsubscribe_mcp_resource"
         )),
         "MCP resource tools should be omitted when no MCP servers are configured"
     );
@@ -993,6 +997,8 @@ fn mcp_resource_tools_are_included_when_mcp_servers_are_present() {
             "list_mcp_resources",
             "list_mcp_resource_templates",
             "read_mcp_resource",
+            "subscribe_mcp_resource",
+            "unsubscribe_mcp_resource",
         ],
     );
 }
@@ -21,6 +21,7 @@ use crate::app_server_session::ThreadSessionState;
 use crate::app_server_session::app_server_rate_limit_snapshots_to_core;
 use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::FeedbackAudience;
+use crate::bottom_pane::McpPromptCommand;
 use crate::bottom_pane::McpServerElicitationFormRequest;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
@@ -90,6 +91,15 @@ use codex_app_server_protocol::FeedbackUploadResponse;
 use codex_app_server_protocol::GetAccountRateLimitsResponse;
 use codex_app_server_protocol::ListMcpServerStatusParams;
 use codex_app_server_protocol::ListMcpServerStatusResponse;
+use codex_app_server_protocol::McpServerPromptGetParams;
+use codex_app_server_protocol::McpServerPromptGetResponse;
+use codex_app_server_protocol::McpServerPromptListParams;
+use codex_app_server_protocol::McpServerPromptListResponse;
+use codex_app_server_protocol::McpServerPrompts;
+use codex_app_server_protocol::McpServerResourceSubscribeParams;
+use codex_app_server_protocol::McpServerResourceSubscribeResponse;
+use codex_app_server_protocol::McpServerResourceUnsubscribeParams;
+use codex_app_server_protocol::McpServerResourceUnsubscribeResponse;
 use codex_app_server_protocol::McpServerStatus;
 use codex_app_server_protocol::McpServerStatusDetail;
 use codex_app_server_protocol::MergeStrategy;
@@ -120,6 +130,8 @@ use codex_config::types::ApprovalsReviewer;
 use codex_config::types::ModelAvailabilityNuxConfig;
 use codex_exec_server::EnvironmentManager;
 use codex_features::Feature;
+use codex_file_search::FileIndexStore;
+use codex_file_search::INDEX_DIR_NAME;
 use codex_models_manager::collaboration_mode_presets::CollaborationModesConfig;
 use codex_models_manager::model_presets::HIDE_GPT_5_1_CODEX_MAX_MIGRATION_PROMPT_CONFIG;
 use codex_models_manager::model_presets::HIDE_GPT5_1_MIGRATION_PROMPT_CONFIG;
@@ -245,8 +257,10 @@ fn command_execution_decision_to_review_decision(
         }
         codex_app_server_protocol::CommandExecutionApprovalDecision::AcceptWithExecpolicyAmendment {
             execpolicy_amendment,
+            scope,
         } => codex_protocol::protocol::ReviewDecision::ApprovedExecpolicyAmendment {
             proposed_execpolicy_amendment: execpolicy_amendment.into_core(),
+            scope: scope.to_core(),
         },
         codex_app_server_protocol::CommandExecutionApprovalDecision::ApplyNetworkPolicyAmendment {
             network_policy_amendment,
@@ -882,7 +896,16 @@ impl App {
         chat_widget
             .maybe_prompt_windows_sandbox_enable(should_prompt_windows_sandbox_nux_at_startup);
 
-        let file_search = FileSearchManager::new(config.cwd.to_path_buf(), app_event_tx.clone());
+        let file_search_index = config.features.enabled(Feature::FileSearchIndex).then(|| {
+            Arc::new(FileIndexStore::new(
+                config.codex_home.join(INDEX_DIR_NAME).to_path_buf(),
+            ))
+        });
+        let file_search = FileSearchManager::new(
+            config.cwd.to_path_buf(),
+            file_search_index,
+            app_event_tx.clone(),
+        );
         #[cfg(not(debug_assertions))]
         let upgrade_version = crate::updates::get_upgrade_version(&config);
 
@@ -22,6 +22,7 @@ use crate::exec_command::split_command_string;
 use codex_app_server_client::AppServerEvent;
 use codex_app_server_protocol::AuthMode;
 use codex_app_server_protocol::JSONRPCErrorError;
+use codex_app_server_protocol::McpServerStartupState;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequest;
 #[cfg(test)]
@@ -166,8 +167,20 @@ impl App {
                     self.chat_widget.dismiss_app_server_request(&request);
                 }
             }
-            ServerNotification::McpServerStatusUpdated(_) => {
+            ServerNotification::McpServerStatusUpdated(notification) => {
                 self.refresh_mcp_startup_expected_servers_from_config();
+                if notification.status == McpServerStartupState::Ready
+                    && let Some(thread_id) = self.chat_widget.thread_id()
+                {
+                    self.fetch_mcp_prompts(app_server_client, thread_id);
+                }
+            }
+            ServerNotification::McpServerPromptListUpdated(notification) => {
+                if let Some(thread_id) = self.chat_widget.thread_id()
+                    && thread_id.to_string() == notification.thread_id
+                {
+                    self.fetch_mcp_prompts(app_server_client, thread_id);
+                }
             }
             ServerNotification::AccountRateLimitsUpdated(notification) => {
                 self.chat_widget.on_rate_limit_snapshot(Some(
@@ -435,6 +448,12 @@ fn server_notification_thread_target(
         }
         ServerNotification::Warning(notification) => notification.thread_id.as_deref(),
         ServerNotification::GuardianWarning(notification) => Some(notification.thread_id.as_str()),
+        ServerNotification::McpServerPromptListUpdated(notification) => {
+            Some(notification.thread_id.as_str())
+        }
+        ServerNotification::McpServerResourceUpdated(notification) => {
+            Some(notification.thread_id.as_str())
+        }
         ServerNotification::SkillsChanged(_)
         | ServerNotification::McpServerStatusUpdated(_)
         | ServerNotification::McpServerOauthLoginCompleted(_)
@@ -446,6 +465,7 @@ fn server_notification_thread_target(
         | ServerNotification::ConfigWarning(_)
         | ServerNotification::FuzzyFileSearchSessionUpdated(_)
         | ServerNotification::FuzzyFileSearchSessionCompleted(_)
+        | ServerNotification::FuzzyFileSearchContentSessionUpdated(_)
         | ServerNotification::CommandExecOutputDelta(_)
         | ServerNotification::FsChanged(_)
         | ServerNotification::WindowsWorldWritableWarning(_)
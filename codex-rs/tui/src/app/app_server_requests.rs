@@ -433,6 +433,7 @@ mod tests {
     use codex_app_server_protocol::ToolRequestUserInputResponse;
     use codex_protocol::approvals::ElicitationAction;
     use codex_protocol::approvals::ExecPolicyAmendment;
+    use codex_protocol::approvals::ExecPolicyAmendmentScope;
     use codex_protocol::mcp::RequestId as McpRequestId;
     use codex_protocol::models::FileSystemPermissions;
     use codex_protocol::models::NetworkPermissions;
@@ -727,6 +728,7 @@ mod tests {
                         "echo".to_string(),
                         "hi".to_string(),
                     ]),
+                    scope: ExecPolicyAmendmentScope::Permanent,
                 },
             })
             .expect_err("invalid patch decision should fail");
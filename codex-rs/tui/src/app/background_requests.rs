@@ -22,6 +22,69 @@ impl App {
         });
     }
 
+    pub(super) fn fetch_mcp_prompts(&mut self, app_server: &AppServerSession, thread_id: ThreadId) {
+        let request_handle = app_server.request_handle();
+        let app_event_tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = fetch_mcp_server_prompts(request_handle, thread_id)
+                .await
+                .map_err(|err| err.to_string());
+            app_event_tx.send(AppEvent::McpPromptsLoaded { thread_id, result });
+        });
+    }
+
+    /// Renders an MCP prompt on the app server and routes the result back as
+    /// `McpPromptLoaded` so the chat widget can submit it as a user turn.
+    pub(super) fn fetch_mcp_prompt(
+        &mut self,
+        app_server: &AppServerSession,
+        thread_id: ThreadId,
+        command: McpPromptCommand,
+        arguments: HashMap<String, String>,
+    ) {
+        let request_handle = app_server.request_handle();
+        let app_event_tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = fetch_mcp_server_prompt(request_handle, thread_id, &command, arguments)
+                .await
+                .map_err(|err| err.to_string());
+            app_event_tx.send(AppEvent::McpPromptLoaded {
+                thread_id,
+                command,
+                result,
+            });
+        });
+    }
+
+    pub(super) fn set_mcp_resource_subscription(
+        &mut self,
+        app_server: &AppServerSession,
+        thread_id: ThreadId,
+        server: String,
+        uri: String,
+        subscribe: bool,
+    ) {
+        let request_handle = app_server.request_handle();
+        let app_event_tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = request_mcp_resource_subscription(
+                request_handle,
+                thread_id,
+                server.clone(),
+                uri.clone(),
+                subscribe,
+            )
+            .await
+            .map_err(|err| err.to_string());
+            app_event_tx.send(AppEvent::McpResourceSubscriptionSet {
+                server,
+                uri,
+                subscribe,
+                result,
+            });
+        });
+    }
+
     /// Spawns a background task to fetch account rate limits and deliver the
     /// result as a `RateLimitsLoaded` event.
     ///
@@ -420,6 +483,82 @@ pub(super) async fn fetch_all_mcp_server_statuses(
     Ok(statuses)
 }
 
+pub(super) async fn fetch_mcp_server_prompts(
+    request_handle: AppServerRequestHandle,
+    thread_id: ThreadId,
+) -> Result<Vec<McpServerPrompts>> {
+    let request_id = RequestId::String(format!("mcp-prompts-{}", Uuid::new_v4()));
+    let response: McpServerPromptListResponse = request_handle
+        .request_typed(ClientRequest::McpServerPromptList {
+            request_id,
+            params: McpServerPromptListParams {
+                thread_id: thread_id.to_string(),
+            },
+        })
+        .await
+        .wrap_err("mcpServer/prompt/list failed in TUI")?;
+    Ok(response.data)
+}
+
+pub(super) async fn fetch_mcp_server_prompt(
+    request_handle: AppServerRequestHandle,
+    thread_id: ThreadId,
+    command: &McpPromptCommand,
+    arguments: HashMap<String, String>,
+) -> Result<McpServerPromptGetResponse> {
+    let request_id = RequestId::String(format!("mcp-prompt-get-{}", Uuid::new_v4()));
+    request_handle
+        .request_typed(ClientRequest::McpServerPromptGet {
+            request_id,
+            params: McpServerPromptGetParams {
+                thread_id: thread_id.to_string(),
+                server: command.server.clone(),
+                name: command.prompt.name.clone(),
+                arguments: Some(arguments),
+            },
+        })
+        .await
+        .wrap_err("mcpServer/prompt/get failed in TUI")
+}
+
+pub(super) async fn request_mcp_resource_subscription(
+    request_handle: AppServerRequestHandle,
+    thread_id: ThreadId,
+    server: String,
+    uri: String,
+    subscribe: bool,
+) -> Result<()> {
+    let thread_id = thread_id.to_string();
+    if subscribe {
+        let request_id = RequestId::String(format!("mcp-resource-subscribe-{}", Uuid::new_v4()));
+        let _: McpServerResourceSubscribeResponse = request_handle
+            .request_typed(ClientRequest::McpServerResourceSubscribe {
+                request_id,
+                params: McpServerResourceSubscribeParams {
+                    thread_id,
+                    server,
+                    uri,
+                },
+            })
+            .await
+            .wrap_err("mcpServer/resource/subscribe failed in TUI")?;
+    } else {
+        let request_id = RequestId::String(format!("mcp-resource-unsubscribe-{}", Uuid::new_v4()));
+        let _: McpServerResourceUnsubscribeResponse = request_handle
+            .request_typed(ClientRequest::McpServerResourceUnsubscribe {
+                request_id,
+                params: McpServerResourceUnsubscribeParams {
+                    thread_id,
+                    server,
+                    uri,
+                },
+            })
+            .await
+            .wrap_err("mcpServer/resource/unsubscribe failed in TUI")?;
+    }
+    Ok(())
+}
+
 pub(super) async fn fetch_account_rate_limits(
     request_handle: AppServerRequestHandle,
 ) -> Result<Vec<RateLimitSnapshot>> {
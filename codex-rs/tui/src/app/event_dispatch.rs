@@ -523,6 +523,47 @@ impl App {
             AppEvent::McpInventoryLoaded { result, detail } => {
                 self.handle_mcp_inventory_result(result, detail);
             }
+            AppEvent::FetchMcpPrompts { thread_id } => {
+                self.fetch_mcp_prompts(app_server, thread_id);
+            }
+            AppEvent::SetMcpResourceSubscription {
+                thread_id,
+                server,
+                uri,
+                subscribe,
+            } => {
+                self.set_mcp_resource_subscription(app_server, thread_id, server, uri, subscribe);
+            }
+            AppEvent::McpResourceSubscriptionSet {
+                server,
+                uri,
+                subscribe,
+                result,
+            } => {
+                self.chat_widget
+                    .on_mcp_resource_subscription_set(&server, &uri, subscribe, result);
+            }
+            AppEvent::McpPromptsLoaded { thread_id, result } => {
+                self.chat_widget.on_mcp_prompts_loaded(thread_id, result);
+            }
+            AppEvent::ContinueMcpPrompt { command, arguments } => {
+                self.chat_widget.run_mcp_prompt(command, arguments);
+            }
+            AppEvent::FetchMcpPrompt {
+                thread_id,
+                command,
+                arguments,
+            } => {
+                self.fetch_mcp_prompt(app_server, thread_id, command, arguments);
+            }
+            AppEvent::McpPromptLoaded {
+                thread_id,
+                command,
+                result,
+            } => {
+                self.chat_widget
+                    .on_mcp_prompt_loaded(thread_id, command, result);
+            }
             AppEvent::SkillsListLoaded { result } => {
                 self.handle_skills_list_result(
                     result.map_err(|err| color_eyre::eyre::eyre!(err)),
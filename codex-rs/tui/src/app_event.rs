@@ -8,11 +8,14 @@
 //! Exit is modelled explicitly via `AppEvent::Exit(ExitMode)` so callers can request shutdown-first
 //! quits without reaching into the app loop or coupling to shutdown/exit sequencing.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use codex_app_server_protocol::AddCreditsNudgeCreditType;
 use codex_app_server_protocol::AddCreditsNudgeEmailStatus;
 use codex_app_server_protocol::AppInfo;
+use codex_app_server_protocol::McpServerPromptGetResponse;
+use codex_app_server_protocol::McpServerPrompts;
 use codex_app_server_protocol::McpServerStatus;
 use codex_app_server_protocol::McpServerStatusDetail;
 use codex_app_server_protocol::PluginInstallResponse;
@@ -32,6 +35,7 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_approval_presets::ApprovalPreset;
 
 use crate::bottom_pane::ApprovalRequest;
+use crate::bottom_pane::McpPromptCommand;
 use crate::bottom_pane::StatusLineItem;
 use crate::bottom_pane::TerminalTitleItem;
 use crate::chatwidget::UserMessage;
@@ -381,6 +385,54 @@ pub(crate) enum AppEvent {
         detail: McpServerStatusDetail,
     },
 
+    /// Fetch the prompts MCP servers expose for a thread so the composer can offer them as
+    /// `/server:prompt` slash commands.
+    FetchMcpPrompts {
+        thread_id: ThreadId,
+    },
+
+    /// Subscribe a thread to an MCP resource, or drop an existing subscription.
+    SetMcpResourceSubscription {
+        thread_id: ThreadId,
+        server: String,
+        uri: String,
+        subscribe: bool,
+    },
+
+    /// Result of changing an MCP resource subscription.
+    McpResourceSubscriptionSet {
+        server: String,
+        uri: String,
+        subscribe: bool,
+        result: Result<(), String>,
+    },
+
+    /// Result of fetching MCP server prompts for a thread.
+    McpPromptsLoaded {
+        thread_id: ThreadId,
+        result: Result<Vec<McpServerPrompts>, String>,
+    },
+
+    /// Resume an MCP prompt invocation after the user supplied one of its required arguments.
+    ContinueMcpPrompt {
+        command: McpPromptCommand,
+        arguments: HashMap<String, String>,
+    },
+
+    /// Render an MCP prompt via app-server RPCs so its text can be submitted as a user turn.
+    FetchMcpPrompt {
+        thread_id: ThreadId,
+        command: McpPromptCommand,
+        arguments: HashMap<String, String>,
+    },
+
+    /// Result of rendering an MCP prompt.
+    McpPromptLoaded {
+        thread_id: ThreadId,
+        command: McpPromptCommand,
+        result: Result<McpServerPromptGetResponse, String>,
+    },
+
     /// Result of the startup skills refresh that runs after the first frame is scheduled.
     ///
     /// This event is startup-only. Interactive skills refreshes are handled synchronously through the app
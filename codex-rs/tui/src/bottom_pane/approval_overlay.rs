@@ -737,6 +737,7 @@ fn exec_options(
             }),
             ReviewDecision::ApprovedExecpolicyAmendment {
                 proposed_execpolicy_amendment,
+                scope,
             } => {
                 let rendered_prefix =
                     strip_bash_lc_and_escape(proposed_execpolicy_amendment.command());
@@ -751,6 +752,7 @@ fn exec_options(
                     decision: ApprovalDecision::Review(
                         ReviewDecision::ApprovedExecpolicyAmendment {
                             proposed_execpolicy_amendment: proposed_execpolicy_amendment.clone(),
+                            scope: *scope,
                         },
                     ),
                     display_shortcut: None,
@@ -980,6 +982,7 @@ mod tests {
     use codex_protocol::permissions::FileSystemSandboxEntry;
     use codex_protocol::permissions::FileSystemSpecialPath;
     use codex_protocol::protocol::ExecPolicyAmendment;
+    use codex_protocol::protocol::ExecPolicyAmendmentScope;
     use codex_protocol::protocol::NetworkApprovalProtocol;
     use codex_protocol::protocol::NetworkPolicyAmendment;
     use codex_utils_absolute_path::AbsolutePathBuf;
@@ -1170,6 +1173,7 @@ mod tests {
                         proposed_execpolicy_amendment: ExecPolicyAmendment::new(vec![
                             "echo".to_string(),
                         ]),
+                        scope: ExecPolicyAmendmentScope::Permanent,
                     },
                     ReviewDecision::Abort,
                 ],
@@ -1192,7 +1196,8 @@ mod tests {
                     ReviewDecision::ApprovedExecpolicyAmendment {
                         proposed_execpolicy_amendment: ExecPolicyAmendment::new(vec![
                             "echo".to_string()
-                        ])
+                        ]),
+                        scope: ExecPolicyAmendmentScope::Permanent,
                     }
                 );
                 saw_op = true;
@@ -153,6 +153,7 @@ use super::chat_composer_history::HistoryEntryResponse;
 use super::command_popup::CommandItem;
 use super::command_popup::CommandPopup;
 use super::command_popup::CommandPopupFlags;
+use super::command_popup::McpPromptCommand;
 use super::file_search_popup::FileSearchPopup;
 use super::footer::CollaborationModeIndicator;
 use super::footer::FooterMode;
@@ -184,6 +185,7 @@ use super::skill_popup::SkillPopup;
 use super::slash_commands;
 use super::slash_commands::BuiltinCommandFlags;
 use crate::bottom_pane::paste_burst::FlushResult;
+use crate::bottom_pane::prompt_args::parse_prompt_arguments;
 use crate::bottom_pane::prompt_args::parse_slash_name;
 use crate::render::Insets;
 use crate::render::RectExt;
@@ -264,6 +266,14 @@ pub enum InputResult {
     /// command-history entry still represents the original command invocation that should be
     /// committed only if dispatch accepts it.
     CommandWithArgs(SlashCommand, String, Vec<TextElement>),
+    /// An MCP server prompt selected as `/server:prompt`, with any `KEY=value` arguments typed
+    /// after it.
+    ///
+    /// Like `Command`, the caller resolves the staged local command-history entry.
+    McpPrompt {
+        command: McpPromptCommand,
+        arguments: HashMap<String, String>,
+    },
     None,
 }
 
@@ -367,6 +377,7 @@ pub(crate) struct ChatComposer {
     skills: Option<Vec<SkillMetadata>>,
     plugins: Option<Vec<PluginCapabilitySummary>>,
     connectors_snapshot: Option<ConnectorsSnapshot>,
+    mcp_prompts: Vec<McpPromptCommand>,
     dismissed_mention_popup_token: Option<String>,
     mention_bindings: HashMap<u64, ComposerMentionBinding>,
     recent_submission_mention_bindings: Vec<MentionBinding>,
@@ -524,6 +535,7 @@ impl ChatComposer {
             skills: None,
             plugins: None,
             connectors_snapshot: None,
+            mcp_prompts: Vec::new(),
             dismissed_mention_popup_token: None,
             mention_bindings: HashMap::new(),
             recent_submission_mention_bindings: Vec::new(),
@@ -593,6 +605,14 @@ impl ChatComposer {
         self.sync_popups();
     }
 
+    /// Replace the MCP server prompts offered as `/server:prompt` slash commands.
+    pub(crate) fn set_mcp_prompts(&mut self, mcp_prompts: Vec<McpPromptCommand>) {
+        if let ActivePopup::Command(popup) = &mut self.active_popup {
+            popup.set_mcp_prompts(mcp_prompts.clone());
+        }
+        self.mcp_prompts = mcp_prompts;
+    }
+
     pub(crate) fn take_mention_bindings(&mut self) -> Vec<MentionBinding> {
         let elements = self.current_mention_elements();
         let mut ordered = Vec::new();
@@ -1521,10 +1541,21 @@ impl ChatComposer {
                 // before applying completion.
                 let first_line = self.textarea.text().lines().next().unwrap_or("");
                 popup.on_composer_text_change(first_line.to_string());
-                let selected_cmd = popup.selected_item().map(|sel| {
-                    let CommandItem::Builtin(cmd) = sel;
-                    cmd
-                });
+                let selected_cmd = match popup.selected_item() {
+                    Some(CommandItem::Builtin(cmd)) => Some(cmd),
+                    Some(CommandItem::McpPrompt(idx)) => {
+                        if let Some(mcp_prompt) = popup.mcp_prompt(idx) {
+                            let command_text = format!("/{}", mcp_prompt.command());
+                            if !first_line.trim_start().starts_with(&command_text) {
+                                self.textarea
+                                    .set_text_clearing_elements(&format!("{command_text} "));
+                                self.textarea.set_cursor(self.textarea.text().len());
+                            }
+                        }
+                        return (InputResult::None, true);
+                    }
+                    None => None,
+                };
                 if let Some(cmd) = selected_cmd {
                     if cmd == SlashCommand::Skills {
                         self.stage_selected_slash_command_history(cmd);
@@ -1559,17 +1590,19 @@ impl ChatComposer {
                 // while the slash-command popup is active.
                 let first_line = self.textarea.text().lines().next().unwrap_or("");
                 popup.on_composer_text_change(first_line.to_string());
-                let selected_cmd = popup.selected_item().map(|sel| {
-                    let CommandItem::Builtin(cmd) = sel;
-                    cmd
-                });
-                if let Some(cmd) = selected_cmd {
-                    let starts_with_cmd = first_line
-                        .trim_start()
-                        .starts_with(&format!("/{}", cmd.command()));
+                let selected_command = match popup.selected_item() {
+                    Some(CommandItem::Builtin(cmd)) => Some(cmd.command().to_string()),
+                    Some(CommandItem::McpPrompt(idx)) => {
+                        popup.mcp_prompt(idx).map(McpPromptCommand::command)
+                    }
+                    None => None,
+                };
+                if let Some(command) = selected_command {
+                    let starts_with_cmd =
+                        first_line.trim_start().starts_with(&format!("/{command}"));
                     if !starts_with_cmd {
                         self.textarea
-                            .set_text_clearing_elements(&format!("/{} ", cmd.command()));
+                            .set_text_clearing_elements(&format!("/{command} "));
                         self.is_bash_mode = false;
                     }
                     if !self.textarea.text().is_empty() {
@@ -1583,12 +1616,31 @@ impl ChatComposer {
                 modifiers: KeyModifiers::NONE,
                 ..
             } => {
-                if let Some(sel) = popup.selected_item() {
-                    let CommandItem::Builtin(cmd) = sel;
-                    self.stage_selected_slash_command_history(cmd);
-                    self.textarea.set_text_clearing_elements("");
-                    self.is_bash_mode = false;
-                    return (InputResult::Command(cmd), true);
+                match popup.selected_item() {
+                    Some(CommandItem::Builtin(cmd)) => {
+                        self.stage_selected_slash_command_history(cmd);
+                        self.textarea.set_text_clearing_elements("");
+                        self.is_bash_mode = false;
+                        return (InputResult::Command(cmd), true);
+                    }
+                    Some(CommandItem::McpPrompt(idx)) => {
+                        if let Some(command) = popup.mcp_prompt(idx).cloned() {
+                            let first_line = self.textarea.text().lines().next().unwrap_or("");
+                            // Only keep typed arguments when they follow this exact command.
+                            let arguments = parse_slash_name(first_line)
+                                .filter(|(name, _, _)| *name == command.command())
+                                .map(|(_, rest, _)| parse_prompt_arguments(rest))
+                                .unwrap_or_default();
+                            self.stage_slash_command_history_text(format!(
+                                "/{}",
+                                command.command()
+                            ));
+                            self.textarea.set_text_clearing_elements("");
+                            self.is_bash_mode = false;
+                            return (InputResult::McpPrompt { command, arguments }, true);
+                        }
+                    }
+                    None => {}
                 }
                 // Fallback to default newline handling if no command selected.
                 self.handle_key_event_without_popup(key_event)
@@ -2496,6 +2548,9 @@ impl ChatComposer {
         if let Some(result) = self.try_dispatch_bare_slash_command() {
             return (result, true);
         }
+        if let Some(result) = self.try_dispatch_mcp_prompt() {
+            return (result, true);
+        }
 
         // If we're in a paste-like burst capture, treat Enter/Ctrl+Shift+Q as part of the burst
         // and accumulate it rather than submitting or inserting immediately.
@@ -2604,6 +2659,26 @@ impl ChatComposer {
         }
     }
 
+    /// Check if the first line names an MCP prompt (`/server:prompt KEY=value ...`) and dispatch
+    /// it. Returns Some(InputResult) if a prompt was dispatched, None otherwise.
+    fn try_dispatch_mcp_prompt(&mut self) -> Option<InputResult> {
+        if !self.slash_commands_enabled() || self.is_bash_mode {
+            return None;
+        }
+        let first_line = self.textarea.text().lines().next().unwrap_or("");
+        let (name, rest, _rest_offset) = parse_slash_name(first_line)?;
+        let command = self
+            .mcp_prompts
+            .iter()
+            .find(|mcp_prompt| mcp_prompt.command() == name)?
+            .clone();
+        let arguments = parse_prompt_arguments(rest);
+        self.stage_slash_command_history();
+        self.textarea.set_text_clearing_elements("");
+        self.is_bash_mode = false;
+        Some(InputResult::McpPrompt { command, arguments })
+    }
+
     /// Check if the input is a slash command with args (e.g., /review args) and dispatch it.
     /// Returns Some(InputResult) if a command was dispatched, None otherwise.
     fn try_dispatch_slash_command_with_args(&mut self) -> Option<InputResult> {
@@ -3453,6 +3528,9 @@ impl ChatComposer {
         }
 
         slash_commands::has_builtin_prefix(name, self.builtin_command_flags())
+            || self.mcp_prompts.iter().any(|mcp_prompt| {
+                mcp_prompt.command().starts_with(name) || mcp_prompt.prompt.name.starts_with(name)
+            })
     }
 
     /// Synchronize `self.command_popup` with the current text in the
@@ -3515,6 +3593,7 @@ impl ChatComposer {
                         windows_degraded_sandbox_active: self.windows_degraded_sandbox_active,
                         side_conversation_active: self.side_conversation_active,
                     });
+                    command_popup.set_mcp_prompts(self.mcp_prompts.clone());
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                 }
@@ -6762,6 +6841,9 @@ mod tests {
             InputResult::Queued { .. } => {
                 panic!("expected command dispatch, but composer queued literal text")
             }
+            InputResult::McpPrompt { .. } => {
+                panic!("expected built-in command dispatch for '/init'")
+            }
             InputResult::None => panic!("expected Command result for '/init'"),
         }
         assert!(composer.textarea.is_empty(), "composer should be cleared");
@@ -7118,6 +7200,9 @@ mod tests {
             InputResult::Queued { .. } => {
                 panic!("expected command dispatch after Tab completion, got literal queue")
             }
+            InputResult::McpPrompt { .. } => {
+                panic!("expected built-in command dispatch for '/diff'")
+            }
             InputResult::None => panic!("expected Command result for '/diff'"),
         }
         assert!(composer.textarea.is_empty());
@@ -7312,6 +7397,9 @@ mod tests {
             InputResult::Queued { .. } => {
                 panic!("expected command dispatch, but composer queued literal text")
             }
+            InputResult::McpPrompt { .. } => {
+                panic!("expected built-in command dispatch for '/mention'")
+            }
             InputResult::None => panic!("expected Command result for '/mention'"),
         }
         assert!(composer.textarea.is_empty(), "composer should be cleared");
@@ -13,6 +13,7 @@ use super::slash_commands;
 use crate::render::Insets;
 use crate::render::RectExt;
 use crate::slash_command::SlashCommand;
+use codex_protocol::mcp::Prompt;
 
 // Hide alias commands in the default popup list so each unique action appears once.
 // `quit` is an alias of `exit`, so we skip `quit` here.
@@ -27,11 +28,36 @@ const COMMAND_COLUMN_WIDTH: ColumnWidthConfig = ColumnWidthConfig::new(
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum CommandItem {
     Builtin(SlashCommand),
+    // Index into the popup's MCP prompt list.
+    McpPrompt(usize),
+}
+
+/// A prompt exposed by an MCP server, offered as a `/server:prompt` command.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct McpPromptCommand {
+    pub(crate) server: String,
+    pub(crate) prompt: Prompt,
+}
+
+impl McpPromptCommand {
+    /// The command name without the leading slash, e.g. `docs:summarize`.
+    pub(crate) fn command(&self) -> String {
+        format!("{}:{}", self.server, self.prompt.name)
+    }
+
+    fn description(&self) -> String {
+        self.prompt
+            .description
+            .clone()
+            .or_else(|| self.prompt.title.clone())
+            .unwrap_or_else(|| format!("prompt from the {} MCP server", self.server))
+    }
 }
 
 pub(crate) struct CommandPopup {
     command_filter: String,
     builtins: Vec<(&'static str, SlashCommand)>,
+    mcp_prompts: Vec<McpPromptCommand>,
     state: ScrollState,
 }
 
@@ -78,10 +104,24 @@ impl CommandPopup {
         Self {
             command_filter: String::new(),
             builtins,
+            mcp_prompts: Vec::new(),
             state: ScrollState::new(),
         }
     }
 
+    /// Replace the MCP prompts listed after the built-in commands.
+    pub(crate) fn set_mcp_prompts(&mut self, mcp_prompts: Vec<McpPromptCommand>) {
+        self.mcp_prompts = mcp_prompts;
+        let matches_len = self.filtered_items().len();
+        self.state.clamp_selection(matches_len);
+        self.state
+            .ensure_visible(matches_len, MAX_POPUP_ROWS.min(matches_len));
+    }
+
+    pub(crate) fn mcp_prompt(&self, idx: usize) -> Option<&McpPromptCommand> {
+        self.mcp_prompts.get(idx)
+    }
+
     /// Update the filter string based on the current composer text. The text
     /// passed in is expected to start with a leading '/'. Everything after the
     /// *first* '/' on the *first* line becomes the active filter that is used
@@ -127,7 +167,7 @@ impl CommandPopup {
         )
     }
 
-    /// Compute exact/prefix matches over built-in commands and user prompts,
+    /// Compute exact/prefix matches over built-in commands and MCP prompts,
     /// paired with optional highlight indices. Preserves the original
     /// presentation order for built-ins and prompts.
     fn filtered(&self) -> Vec<(CommandItem, Option<Vec<usize>>)> {
@@ -140,6 +180,9 @@ impl CommandPopup {
                 }
                 out.push((CommandItem::Builtin(*cmd), None));
             }
+            for idx in 0..self.mcp_prompts.len() {
+                out.push((CommandItem::McpPrompt(idx), None));
+            }
             return out;
         }
 
@@ -173,6 +216,15 @@ impl CommandPopup {
         for (_, cmd) in self.builtins.iter() {
             push_match(CommandItem::Builtin(*cmd), cmd.command(), None, 0);
         }
+        for (idx, mcp_prompt) in self.mcp_prompts.iter().enumerate() {
+            // Match either the qualified `server:prompt` form or the bare prompt name.
+            push_match(
+                CommandItem::McpPrompt(idx),
+                &mcp_prompt.command(),
+                Some(&mcp_prompt.prompt.name),
+                mcp_prompt.server.chars().count() + 1,
+            );
+        }
 
         out.extend(exact);
         out.extend(prefix);
@@ -189,11 +241,20 @@ impl CommandPopup {
     ) -> Vec<GenericDisplayRow> {
         matches
             .into_iter()
-            .map(|(item, indices)| {
-                let CommandItem::Builtin(cmd) = item;
-                let name = format!("/{}", cmd.command());
-                let description = cmd.description().to_string();
-                GenericDisplayRow {
+            .filter_map(|(item, indices)| {
+                let (name, description) = match item {
+                    CommandItem::Builtin(cmd) => {
+                        (format!("/{}", cmd.command()), cmd.description().to_string())
+                    }
+                    CommandItem::McpPrompt(idx) => {
+                        let mcp_prompt = self.mcp_prompts.get(idx)?;
+                        (
+                            format!("/{}", mcp_prompt.command()),
+                            mcp_prompt.description(),
+                        )
+                    }
+                };
+                Some(GenericDisplayRow {
                     name,
                     name_prefix_spans: Vec::new(),
                     match_indices: indices.map(|v| v.into_iter().map(|i| i + 1).collect()),
@@ -203,7 +264,7 @@ impl CommandPopup {
                     wrap_indent: None,
                     is_disabled: false,
                     disabled_reason: None,
-                }
+                })
             })
             .collect()
     }
@@ -266,6 +327,7 @@ mod tests {
         let matches = popup.filtered_items();
         let has_init = matches.iter().any(|item| match item {
             CommandItem::Builtin(cmd) => cmd.command() == "init",
+            CommandItem::McpPrompt(_) => false,
         });
         assert!(
             has_init,
@@ -283,7 +345,9 @@ mod tests {
         let selected = popup.selected_item();
         match selected {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "init"),
-            None => panic!("expected a selected command for exact match"),
+            Some(CommandItem::McpPrompt(_)) | None => {
+                panic!("expected a selected command for exact match")
+            }
         }
     }
 
@@ -294,7 +358,9 @@ mod tests {
         let matches = popup.filtered_items();
         match matches.first() {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "model"),
-            None => panic!("expected at least one match for '/mo'"),
+            Some(CommandItem::McpPrompt(_)) | None => {
+                panic!("expected at least one match for '/mo'")
+            }
         }
     }
 
@@ -308,6 +374,7 @@ mod tests {
             .into_iter()
             .map(|item| match item {
                 CommandItem::Builtin(cmd) => cmd.command(),
+                CommandItem::McpPrompt(_) => unreachable!("no MCP prompts configured"),
             })
             .collect();
         assert_eq!(cmds, vec!["model", "memories", "mention", "mcp"]);
@@ -323,6 +390,7 @@ mod tests {
             .into_iter()
             .map(|item| match item {
                 CommandItem::Builtin(cmd) => cmd.command(),
+                CommandItem::McpPrompt(_) => unreachable!("no MCP prompts configured"),
             })
             .collect();
         assert!(
@@ -353,6 +421,7 @@ mod tests {
             .into_iter()
             .map(|item| match item {
                 CommandItem::Builtin(cmd) => cmd.command(),
+                CommandItem::McpPrompt(_) => unreachable!("no MCP prompts configured"),
             })
             .collect();
         assert!(
@@ -430,6 +499,7 @@ mod tests {
             .into_iter()
             .map(|item| match item {
                 CommandItem::Builtin(cmd) => cmd.command(),
+                CommandItem::McpPrompt(_) => unreachable!("no MCP prompts configured"),
             })
             .collect();
         assert!(
@@ -481,6 +551,7 @@ mod tests {
             .into_iter()
             .map(|item| match item {
                 CommandItem::Builtin(cmd) => cmd.command(),
+                CommandItem::McpPrompt(_) => unreachable!("no MCP prompts configured"),
             })
             .collect();
 
@@ -498,6 +569,7 @@ mod tests {
             .into_iter()
             .map(|item| match item {
                 CommandItem::Builtin(cmd) => cmd.command(),
+                CommandItem::McpPrompt(_) => unreachable!("no MCP prompts configured"),
             })
             .collect();
 
@@ -506,4 +578,46 @@ mod tests {
             "expected no /debug* command in popup menu, got {cmds:?}"
         );
     }
+
+    fn triage_prompt_command() -> McpPromptCommand {
+        McpPromptCommand {
+            server: "pager".to_string(),
+            prompt: Prompt {
+                name: "triage".to_string(),
+                title: None,
+                description: Some("Triage a production incident".to_string()),
+                arguments: None,
+                icons: None,
+                meta: None,
+            },
+        }
+    }
+
+    #[test]
+    fn mcp_prompts_match_by_qualified_or_bare_name() {
+        let mut popup = CommandPopup::new(CommandPopupFlags::default());
+        popup.set_mcp_prompts(vec![triage_prompt_command()]);
+
+        popup.on_composer_text_change("/pager:tri".to_string());
+        assert_eq!(popup.selected_item(), Some(CommandItem::McpPrompt(0)));
+
+        popup.on_composer_text_change("/triage".to_string());
+        assert_eq!(popup.selected_item(), Some(CommandItem::McpPrompt(0)));
+        assert_eq!(
+            popup.mcp_prompt(/*idx*/ 0).map(McpPromptCommand::command),
+            Some("pager:triage".to_string())
+        );
+    }
+
+    #[test]
+    fn mcp_prompts_follow_builtins_in_unfiltered_list() {
+        let mut popup = CommandPopup::new(CommandPopupFlags::default());
+        popup.set_mcp_prompts(vec![triage_prompt_command()]);
+        popup.on_composer_text_change("/".to_string());
+
+        assert_eq!(
+            popup.filtered_items().last(),
+            Some(&CommandItem::McpPrompt(0))
+        );
+    }
 }
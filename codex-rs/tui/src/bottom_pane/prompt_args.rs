@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 /// Parse a first-line slash command of the form `/name <rest>`.
 /// Returns `(name, rest_after_name, rest_offset)` if the line begins with `/`
 /// and contains a non-empty name; otherwise returns `None`.
@@ -23,3 +25,39 @@ pub fn parse_slash_name(line: &str) -> Option<(&str, &str, usize)> {
     let rest_offset = rest_start_in_stripped + 1;
     Some((name, rest, rest_offset))
 }
+
+/// Parse `KEY=value` pairs typed after an MCP prompt command, e.g.
+/// `/docs:summarize path=src/lib.rs style="one line"`.
+///
+/// Values follow shell quoting rules so they may contain spaces. Tokens without
+/// an `=` or with an empty key are ignored.
+pub fn parse_prompt_arguments(rest: &str) -> HashMap<String, String> {
+    let tokens =
+        shlex::split(rest).unwrap_or_else(|| rest.split_whitespace().map(str::to_string).collect());
+    tokens
+        .into_iter()
+        .filter_map(|token| {
+            let (key, value) = token.split_once('=')?;
+            (!key.is_empty()).then(|| (key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_prompt_arguments_honors_quotes_and_skips_bare_tokens() {
+        let arguments = parse_prompt_arguments(r#"path=src/lib.rs style="one line" stray =x"#);
+
+        assert_eq!(
+            arguments,
+            HashMap::from([
+                ("path".to_string(), "src/lib.rs".to_string()),
+                ("style".to_string(), "one line".to_string()),
+            ])
+        );
+    }
+}
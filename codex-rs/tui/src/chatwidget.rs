@@ -386,6 +386,8 @@ use self::goal_status::goal_status_indicator_from_app_goal;
 mod goal_menu;
 mod interrupts;
 use self::interrupts::InterruptManager;
+mod mcp_prompts;
+mod mcp_resources;
 mod session_header;
 use self::session_header::SessionHeader;
 mod skills;
@@ -1739,8 +1741,10 @@ fn exec_approval_request_from_params(
                     }
                     codex_app_server_protocol::CommandExecutionApprovalDecision::AcceptWithExecpolicyAmendment {
                         execpolicy_amendment,
+                        scope,
                     } => codex_protocol::protocol::ReviewDecision::ApprovedExecpolicyAmendment {
                         proposed_execpolicy_amendment: execpolicy_amendment.into_core(),
+                        scope: scope.to_core(),
                     },
                     codex_app_server_protocol::CommandExecutionApprovalDecision::ApplyNetworkPolicyAmendment {
                         network_policy_amendment,
@@ -5874,6 +5878,10 @@ impl ChatWidget {
                     InputResult::CommandWithArgs(cmd, args, text_elements) => {
                         self.handle_slash_command_with_args_dispatch(cmd, args, text_elements);
                     }
+                    InputResult::McpPrompt { command, arguments } => {
+                        self.run_mcp_prompt(command, arguments);
+                        self.bottom_pane.record_pending_slash_command_history();
+                    }
                     InputResult::None => {}
                 }
                 if had_modal_or_popup && self.bottom_pane.no_modal_or_popup_active() {
@@ -7193,6 +7201,11 @@ impl ChatWidget {
                     self.on_realtime_conversation_sdp(notification.sdp);
                 }
             }
+            ServerNotification::McpServerResourceUpdated(notification) => {
+                if !from_replay {
+                    self.on_mcp_resource_updated(notification);
+                }
+            }
             ServerNotification::ServerRequestResolved(_)
             | ServerNotification::AccountUpdated(_)
             | ServerNotification::AccountRateLimitsUpdated(_)
@@ -7205,11 +7218,13 @@ impl ChatWidget {
             | ServerNotification::FileChangePatchUpdated(_)
             | ServerNotification::McpToolCallProgress(_)
             | ServerNotification::McpServerOauthLoginCompleted(_)
+            | ServerNotification::McpServerPromptListUpdated(_)
             | ServerNotification::AppListUpdated(_)
             | ServerNotification::ExternalAgentConfigImportCompleted(_)
             | ServerNotification::FsChanged(_)
             | ServerNotification::FuzzyFileSearchSessionUpdated(_)
             | ServerNotification::FuzzyFileSearchSessionCompleted(_)
+            | ServerNotification::FuzzyFileSearchContentSessionUpdated(_)
             | ServerNotification::ThreadRealtimeTranscriptDelta(_)
             | ServerNotification::ThreadRealtimeTranscriptDone(_)
             | ServerNotification::WindowsWorldWritableWarning(_)
@@ -7721,6 +7736,7 @@ impl ChatWidget {
             EventMsg::ImageGenerationEnd(ev) => self.on_image_generation_end(ev),
             EventMsg::McpToolCallBegin(ev) => self.on_mcp_tool_call_begin(ev),
             EventMsg::McpToolCallEnd(ev) => self.on_mcp_tool_call_end(ev),
+            EventMsg::McpSamplingBegin(_) | EventMsg::McpSamplingEnd(_) => {}
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.handle_history_entry_response(ev),
@@ -7729,6 +7745,8 @@ impl ChatWidget {
             EventMsg::SkillsUpdateAvailable => {
                 self.refresh_skills_for_current_cwd(/*force_reload*/ true);
             }
+            EventMsg::McpPromptsChanged(_) => self.refresh_mcp_prompts(),
+            EventMsg::McpResourceUpdated(_) => {}
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
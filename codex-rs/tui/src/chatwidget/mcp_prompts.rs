@@ -0,0 +1,153 @@
+//! MCP server prompts offered as `/server:prompt` slash commands.
+//!
+//! Prompts are fetched per thread through `mcpServer/prompt/list` and handed to the composer.
+//! Selecting one collects any required arguments that were not typed inline, one prompt view at a
+//! time, then renders the prompt through `mcpServer/prompt/get` and submits the resulting text as
+//! a user turn.
+
+use std::collections::HashMap;
+
+use super::ChatWidget;
+use super::UserMessage;
+use crate::app_event::AppEvent;
+use crate::bottom_pane::McpPromptCommand;
+use crate::bottom_pane::custom_prompt_view::CustomPromptView;
+use codex_app_server_protocol::McpServerPromptGetResponse;
+use codex_app_server_protocol::McpServerPrompts;
+use codex_protocol::ThreadId;
+use codex_protocol::mcp::GetPromptResult;
+
+impl ChatWidget {
+    /// Ask the app to refetch MCP prompts for the current thread.
+    pub(super) fn refresh_mcp_prompts(&mut self) {
+        if let Some(thread_id) = self.thread_id {
+            self.app_event_tx
+                .send(AppEvent::FetchMcpPrompts { thread_id });
+        }
+    }
+
+    pub(crate) fn on_mcp_prompts_loaded(
+        &mut self,
+        thread_id: ThreadId,
+        result: Result<Vec<McpServerPrompts>, String>,
+    ) {
+        if self.thread_id != Some(thread_id) {
+            return;
+        }
+        match result {
+            Ok(servers) => {
+                let commands = servers
+                    .into_iter()
+                    .flat_map(|entry| {
+                        let server = entry.server;
+                        entry
+                            .prompts
+                            .into_iter()
+                            .map(move |prompt| McpPromptCommand {
+                                server: server.clone(),
+                                prompt,
+                            })
+                    })
+                    .collect();
+                self.bottom_pane.set_mcp_prompts(commands);
+            }
+            Err(err) => {
+                tracing::warn!("failed to load MCP prompts: {err}");
+            }
+        }
+    }
+
+    /// Prompt for the next required argument that is still missing, or render the prompt once
+    /// every required argument has a value.
+    pub(crate) fn run_mcp_prompt(
+        &mut self,
+        command: McpPromptCommand,
+        arguments: HashMap<String, String>,
+    ) {
+        let Some(thread_id) = self.thread_id else {
+            self.add_error_message(format!(
+                "'/{}' is unavailable until the session has started.",
+                command.command()
+            ));
+            return;
+        };
+        let missing_argument = command
+            .prompt
+            .arguments
+            .iter()
+            .flatten()
+            .find(|argument| argument.is_required() && !arguments.contains_key(&argument.name))
+            .cloned();
+        let Some(argument) = missing_argument else {
+            self.app_event_tx.send(AppEvent::FetchMcpPrompt {
+                thread_id,
+                command,
+                arguments,
+            });
+            return;
+        };
+
+        let tx = self.app_event_tx.clone();
+        let title = format!("/{}", command.command());
+        let placeholder = argument
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("Enter a value for {}", argument.name));
+        let context_label = argument
+            .title
+            .clone()
+            .unwrap_or_else(|| argument.name.clone());
+        let view = CustomPromptView::new(
+            title,
+            placeholder,
+            /*initial_text*/ String::new(),
+            Some(context_label),
+            Box::new(move |value: String| {
+                let mut arguments = arguments.clone();
+                arguments.insert(argument.name.clone(), value);
+                tx.send(AppEvent::ContinueMcpPrompt {
+                    command: command.clone(),
+                    arguments,
+                });
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn on_mcp_prompt_loaded(
+        &mut self,
+        thread_id: ThreadId,
+        command: McpPromptCommand,
+        result: Result<McpServerPromptGetResponse, String>,
+    ) {
+        if self.thread_id != Some(thread_id) {
+            return;
+        }
+        let response = match result {
+            Ok(response) => response,
+            Err(err) => {
+                self.add_error_message(format!("Failed to load '/{}': {err}", command.command()));
+                return;
+            }
+        };
+        let text = GetPromptResult {
+            description: response.description,
+            messages: response.messages,
+        }
+        .text();
+        if text.trim().is_empty() {
+            self.add_error_message(format!(
+                "'/{}' did not return any text to send.",
+                command.command()
+            ));
+            return;
+        }
+
+        let user_message = UserMessage::from(text);
+        if self.is_session_configured() && !self.is_plan_streaming_in_tui() {
+            self.submit_user_message(user_message);
+        } else {
+            self.queue_user_message(user_message);
+        }
+    }
+}
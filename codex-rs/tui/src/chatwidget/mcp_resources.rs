@@ -0,0 +1,69 @@
+//! MCP resource subscriptions managed through `/mcp subscribe` and `/mcp unsubscribe`.
+//!
+//! The app-server re-reads a subscribed resource whenever its server reports a change and hands
+//! the difference to the model; the widget only issues the requests and reports the outcome.
+
+use super::ChatWidget;
+use crate::app_event::AppEvent;
+use codex_app_server_protocol::McpServerResourceUpdatedNotification;
+
+impl ChatWidget {
+    pub(super) fn set_mcp_resource_subscription(
+        &mut self,
+        server: &str,
+        uri: &str,
+        subscribe: bool,
+    ) {
+        let Some(thread_id) = self.thread_id else {
+            self.add_error_message(
+                "'/mcp' subscriptions are unavailable until the session has started.".to_string(),
+            );
+            return;
+        };
+        self.app_event_tx
+            .send(AppEvent::SetMcpResourceSubscription {
+                thread_id,
+                server: server.to_string(),
+                uri: uri.to_string(),
+                subscribe,
+            });
+    }
+
+    pub(crate) fn on_mcp_resource_subscription_set(
+        &mut self,
+        server: &str,
+        uri: &str,
+        subscribe: bool,
+        result: Result<(), String>,
+    ) {
+        match (result, subscribe) {
+            (Ok(()), true) => self.add_info_message(
+                format!("Subscribed to MCP resource `{uri}` on `{server}`."),
+                Some("Changes will be shared with Codex on the next turn.".to_string()),
+            ),
+            (Ok(()), false) => self.add_info_message(
+                format!("Unsubscribed from MCP resource `{uri}` on `{server}`."),
+                /*hint*/ None,
+            ),
+            (Err(err), true) => self.add_error_message(format!(
+                "Failed to subscribe to MCP resource `{uri}` on `{server}`: {err}"
+            )),
+            (Err(err), false) => self.add_error_message(format!(
+                "Failed to unsubscribe from MCP resource `{uri}` on `{server}`: {err}"
+            )),
+        }
+    }
+
+    pub(super) fn on_mcp_resource_updated(
+        &mut self,
+        notification: McpServerResourceUpdatedNotification,
+    ) {
+        self.add_info_message(
+            format!(
+                "MCP resource `{}` on `{}` changed.",
+                notification.uri, notification.server
+            ),
+            /*hint*/ None,
+        );
+    }
+}
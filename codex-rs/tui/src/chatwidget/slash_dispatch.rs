@@ -566,10 +566,25 @@ impl ChatWidget {
                     }
                 }
             }
-            SlashCommand::Mcp => match trimmed.to_ascii_lowercase().as_str() {
-                "verbose" => self.add_mcp_output(McpServerStatusDetail::Full),
-                _ => self.add_error_message("Usage: /mcp [verbose]".to_string()),
-            },
+            SlashCommand::Mcp => {
+                let mut words = trimmed.split_whitespace();
+                let subcommand = words.next().unwrap_or_default().to_ascii_lowercase();
+                match (subcommand.as_str(), words.next(), words.next(), words.next()) {
+                    ("verbose", None, None, None) => {
+                        self.add_mcp_output(McpServerStatusDetail::Full)
+                    }
+                    ("subscribe", Some(server), Some(uri), None) => {
+                        self.set_mcp_resource_subscription(server, uri, /*subscribe*/ true)
+                    }
+                    ("unsubscribe", Some(server), Some(uri), None) => {
+                        self.set_mcp_resource_subscription(server, uri, /*subscribe*/ false)
+                    }
+                    _ => self.add_error_message(
+                        "Usage: /mcp [verbose | subscribe <server> <uri> | unsubscribe <server> <uri>]"
+                            .to_string(),
+                    ),
+                }
+            }
             SlashCommand::Rename if !trimmed.is_empty() => {
                 if !self.ensure_thread_rename_allowed() {
                     return;
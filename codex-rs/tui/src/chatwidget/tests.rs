@@ -66,6 +66,7 @@ pub(super) use codex_app_server_protocol::ItemGuardianApprovalReviewCompletedNot
 pub(super) use codex_app_server_protocol::ItemGuardianApprovalReviewStartedNotification;
 pub(super) use codex_app_server_protocol::ItemStartedNotification;
 pub(super) use codex_app_server_protocol::MarketplaceInterface;
+pub(super) use codex_app_server_protocol::McpServerPrompts;
 pub(super) use codex_app_server_protocol::McpServerStartupState;
 pub(super) use codex_app_server_protocol::McpServerStatusDetail;
 pub(super) use codex_app_server_protocol::McpServerStatusUpdatedNotification;
@@ -1102,5 +1102,12 @@ fn hook_event_label(event_name: codex_protocol::protocol::HookEventName) -> &'st
         codex_protocol::protocol::HookEventName::SessionStart => "SessionStart",
         codex_protocol::protocol::HookEventName::UserPromptSubmit => "UserPromptSubmit",
         codex_protocol::protocol::HookEventName::Stop => "Stop",
+        codex_protocol::protocol::HookEventName::PreCompact => "PreCompact",
+        codex_protocol::protocol::HookEventName::PostCompact => "PostCompact",
+        codex_protocol::protocol::HookEventName::SubagentStart => "SubagentStart",
+        codex_protocol::protocol::HookEventName::SubagentStop => "SubagentStop",
+        codex_protocol::protocol::HookEventName::PreApplyPatch => "PreApplyPatch",
+        codex_protocol::protocol::HookEventName::PostApplyPatch => "PostApplyPatch",
+        codex_protocol::protocol::HookEventName::TurnError => "TurnError",
     }
 }
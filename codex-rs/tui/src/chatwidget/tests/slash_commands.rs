@@ -1755,6 +1755,56 @@ async fn slash_mcp_requests_inventory_via_app_server() {
     assert!(op_rx.try_recv().is_err(), "expected no core op to be sent");
 }
 
+#[tokio::test]
+async fn mcp_prompt_command_asks_for_missing_required_arguments() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    let thread_id = ThreadId::new();
+    chat.thread_id = Some(thread_id);
+    let prompt = serde_json::from_value(json!({
+        "name": "triage",
+        "arguments": [
+            { "name": "service", "required": true },
+            { "name": "severity" },
+        ],
+    }))
+    .expect("prompt should deserialize");
+    chat.on_mcp_prompts_loaded(
+        thread_id,
+        Ok(vec![McpServerPrompts {
+            server: "pager".to_string(),
+            prompts: vec![prompt],
+        }]),
+    );
+
+    submit_composer_text(&mut chat, "/pager:triage severity=sev2");
+    for ch in "billing".chars() {
+        chat.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+    }
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let (command, arguments) = loop {
+        match rx.try_recv() {
+            Ok(AppEvent::ContinueMcpPrompt { command, arguments }) => break (command, arguments),
+            Ok(_) => continue,
+            Err(err) => panic!("expected ContinueMcpPrompt event, got {err:?}"),
+        }
+    };
+    assert_eq!(command.command(), "pager:triage");
+    assert_eq!(
+        arguments,
+        HashMap::from([
+            ("service".to_string(), "billing".to_string()),
+            ("severity".to_string(), "sev2".to_string()),
+        ])
+    );
+
+    chat.run_mcp_prompt(command, arguments);
+    assert_matches!(
+        rx.try_recv(),
+        Ok(AppEvent::FetchMcpPrompt { thread_id: requested, .. }) if requested == thread_id
+    );
+}
+
 #[tokio::test]
 async fn slash_mcp_verbose_requests_full_inventory_via_app_server() {
     let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
@@ -1771,6 +1821,26 @@ async fn slash_mcp_verbose_requests_full_inventory_via_app_server() {
     assert!(op_rx.try_recv().is_err(), "expected no core op to be sent");
 }
 
+#[tokio::test]
+async fn slash_mcp_subscribe_requests_resource_subscription() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    let thread_id = ThreadId::new();
+    chat.thread_id = Some(thread_id);
+
+    submit_composer_text(&mut chat, "/mcp subscribe docs memo://note");
+
+    assert_matches!(
+        rx.try_recv(),
+        Ok(AppEvent::SetMcpResourceSubscription {
+            thread_id: requested,
+            server,
+            uri,
+            subscribe: true,
+        }) if requested == thread_id && server == "docs" && uri == "memo://note"
+    );
+    assert!(op_rx.try_recv().is_err(), "expected no core op to be sent");
+}
+
 #[tokio::test]
 async fn slash_mcp_invalid_args_show_usage() {
     let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
@@ -1784,7 +1854,7 @@ async fn slash_mcp_invalid_args_show_usage() {
         .collect::<Vec<_>>()
         .join("\n");
     assert!(
-        rendered.contains("Usage: /mcp [verbose]"),
+        rendered.contains("Usage: /mcp [verbose"),
         "expected usage message, got: {rendered:?}"
     );
     assert_eq!(recall_latest_after_clearing(&mut chat), "/mcp full");
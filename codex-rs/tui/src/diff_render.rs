@@ -421,6 +421,11 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
     if let [row] = &rows[..] {
         let verb = match &row.change {
             FileChange::Add { .. } => "Added",
+            FileChange::Update { unified_diff, .. }
+                if unified_diff.starts_with("new file mode ") =>
+            {
+                "Added"
+            }
             FileChange::Delete { .. } => "Deleted",
             _ => "Edited",
         };
@@ -546,6 +551,12 @@ fn render_change(
             }
         }
         FileChange::Update { unified_diff, .. } => {
+            let (extended_header, unified_diff) = split_extended_header(unified_diff);
+            out.extend(
+                extended_header
+                    .into_iter()
+                    .map(|line| RtLine::from(line.to_string().dim())),
+            );
             if let Ok(patch) = diffy::Patch::from_str(unified_diff) {
                 let mut max_line_number = 0;
                 let mut total_diff_bytes: usize = 0;
@@ -762,7 +773,33 @@ pub(crate) fn display_path_for(path: &Path, cwd: &Path) -> String {
     chosen.display().to_string()
 }
 
+/// Git extended header lines that may precede the hunks of an update to describe mode changes,
+/// symlinks and binary files.
+const EXTENDED_HEADER_PREFIXES: [&str; 5] = [
+    "new file mode ",
+    "deleted file mode ",
+    "old mode ",
+    "new mode ",
+    "Binary files ",
+];
+
+/// Splits the leading git extended header lines off `unified_diff`.
+fn split_extended_header(unified_diff: &str) -> (Vec<&str>, &str) {
+    let mut header = Vec::new();
+    let mut rest = unified_diff;
+    while let Some((line, remaining)) = rest.split_once('\n')
+        && EXTENDED_HEADER_PREFIXES
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+    {
+        header.push(line);
+        rest = remaining;
+    }
+    (header, rest)
+}
+
 pub(crate) fn calculate_add_remove_from_diff(diff: &str) -> (usize, usize) {
+    let (_, diff) = split_extended_header(diff);
     if let Ok(patch) = diffy::Patch::from_str(diff) {
         patch
             .hunks()
@@ -1467,6 +1504,43 @@ mod tests {
         snapshot_lines(name, lines, width, height);
     }
 
+    #[test]
+    fn extended_header_lines_render_before_the_diff() {
+        let text_of = |changes: HashMap<PathBuf, FileChange>| {
+            diff_summary_for_tests(&changes)
+                .iter()
+                .take(2)
+                .map(|l| {
+                    l.spans
+                        .iter()
+                        .map(|s| s.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            text_of(HashMap::from([(
+                PathBuf::from("run.sh"),
+                FileChange::Update {
+                    unified_diff: "new file mode 100755\n@@ -0,0 +1 @@\n+echo run\n".to_string(),
+                    move_path: None,
+                },
+            )])),
+            vec!["• Added run.sh (+1 -0)", "    new file mode 100755"]
+        );
+        assert_eq!(
+            text_of(HashMap::from([(
+                PathBuf::from("tool.sh"),
+                FileChange::Update {
+                    unified_diff: "new mode 100700\n".to_string(),
+                    move_path: None,
+                },
+            )])),
+            vec!["• Edited tool.sh (+0 -0)", "    new mode 100700"]
+        );
+    }
+
     #[test]
     fn display_path_prefers_cwd_without_git_repo() {
         let cwd = if cfg!(windows) {
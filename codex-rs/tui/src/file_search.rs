@@ -4,6 +4,8 @@
 //! `AppEvent::StartFileSearch(query)`. This manager owns a single
 //! `codex-file-search` session for the current search root, updates the query
 //! on every keystroke, and drops the session when the query becomes empty.
+//! When a persistent index is configured, sessions read the root's entries
+//! from it instead of walking the tree again.
 
 use codex_file_search as file_search;
 use std::path::PathBuf;
@@ -16,6 +18,7 @@ use crate::app_event_sender::AppEventSender;
 pub(crate) struct FileSearchManager {
     state: Arc<Mutex<SearchState>>,
     search_dir: PathBuf,
+    index: Option<Arc<file_search::FileIndexStore>>,
     app_tx: AppEventSender,
 }
 
@@ -26,7 +29,11 @@ struct SearchState {
 }
 
 impl FileSearchManager {
-    pub fn new(search_dir: PathBuf, tx: AppEventSender) -> Self {
+    pub fn new(
+        search_dir: PathBuf,
+        index: Option<Arc<file_search::FileIndexStore>>,
+        tx: AppEventSender,
+    ) -> Self {
         Self {
             state: Arc::new(Mutex::new(SearchState {
                 latest_query: String::new(),
@@ -34,6 +41,7 @@ impl FileSearchManager {
                 session_token: 0,
             })),
             search_dir,
+            index,
             app_tx: tx,
         }
     }
@@ -84,6 +92,7 @@ impl FileSearchManager {
             vec![self.search_dir.clone()],
             file_search::FileSearchOptions {
                 compute_indices: true,
+                index: self.index.clone(),
                 ..Default::default()
             },
             reporter,
@@ -864,6 +864,7 @@ pub fn new_approval_decision_cell(
     decision: codex_protocol::protocol::ReviewDecision,
     actor: ApprovalDecisionActor,
 ) -> Box<dyn HistoryCell> {
+    use codex_protocol::protocol::ExecPolicyAmendmentScope;
     use codex_protocol::protocol::NetworkPolicyRuleAction;
     use codex_protocol::protocol::ReviewDecision::*;
 
@@ -883,17 +884,22 @@ pub fn new_approval_decision_cell(
         }
         ApprovedExecpolicyAmendment {
             proposed_execpolicy_amendment,
+            scope,
         } => {
             let snippet = Span::from(exec_snippet(&proposed_execpolicy_amendment.command)).dim();
-            (
-                "✔ ".green(),
-                vec![
-                    actor.subject().into(),
-                    "approved".bold(),
-                    " codex to always run commands that start with ".into(),
-                    snippet,
-                ],
-            )
+            let mut summary = vec![
+                actor.subject().into(),
+                "approved".bold(),
+                " codex to always run commands that start with ".into(),
+                snippet,
+            ];
+            match scope {
+                ExecPolicyAmendmentScope::Permanent => {}
+                ExecPolicyAmendmentScope::Thread => summary.push(" in this thread".bold()),
+                ExecPolicyAmendmentScope::Project => summary.push(" in this project".bold()),
+                ExecPolicyAmendmentScope::Day => summary.push(" for one day".bold()),
+            }
+            ("✔ ".green(), summary)
         }
         ApprovedForSession => {
             let snippet = Span::from(exec_snippet(&command)).dim();
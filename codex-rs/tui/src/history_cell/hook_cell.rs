@@ -695,6 +695,7 @@ fn hook_output_prefix(kind: HookOutputEntryKind) -> &'static str {
         HookOutputEntryKind::Feedback => "feedback: ",
         HookOutputEntryKind::Context => "hook context: ",
         HookOutputEntryKind::Error => "error: ",
+        HookOutputEntryKind::Rewrite => "rewrite: ",
     }
 }
 
@@ -706,6 +707,13 @@ fn hook_event_label(event_name: HookEventName) -> &'static str {
         HookEventName::SessionStart => "SessionStart",
         HookEventName::UserPromptSubmit => "UserPromptSubmit",
         HookEventName::Stop => "Stop",
+        HookEventName::PreCompact => "PreCompact",
+        HookEventName::PostCompact => "PostCompact",
+        HookEventName::SubagentStart => "SubagentStart",
+        HookEventName::SubagentStop => "SubagentStop",
+        HookEventName::PreApplyPatch => "PreApplyPatch",
+        HookEventName::PostApplyPatch => "PostApplyPatch",
+        HookEventName::TurnError => "TurnError",
     }
 }
 
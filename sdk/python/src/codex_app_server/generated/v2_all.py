@@ -692,11 +692,19 @@ class AgentConfiguredHookHandler(BaseModel):
     type: Annotated[Literal["agent"], Field(title="AgentConfiguredHookHandlerType")]
 
 
+class ServerConfiguredHookHandler(BaseModel):
+    model_config = ConfigDict(
+        populate_by_name=True,
+    )
+    type: Annotated[Literal["server"], Field(title="ServerConfiguredHookHandlerType")]
+
+
 class ConfiguredHookHandler(
     RootModel[
         CommandConfiguredHookHandler
         | PromptConfiguredHookHandler
         | AgentConfiguredHookHandler
+        | ServerConfiguredHookHandler
     ]
 ):
     model_config = ConfigDict(
@@ -1534,6 +1542,13 @@ class HookEventName(Enum):
     session_start = "sessionStart"
     user_prompt_submit = "userPromptSubmit"
     stop = "stop"
+    pre_compact = "preCompact"
+    post_compact = "postCompact"
+    subagent_start = "subagentStart"
+    subagent_stop = "subagentStop"
+    pre_apply_patch = "preApplyPatch"
+    post_apply_patch = "postApplyPatch"
+    turn_error = "turnError"
 
 
 class HookExecutionMode(Enum):
@@ -1545,6 +1560,7 @@ class HookHandlerType(Enum):
     command = "command"
     prompt = "prompt"
     agent = "agent"
+    server = "server"
 
 
 class HookOutputEntryKind(Enum):
@@ -1553,6 +1569,7 @@ class HookOutputEntryKind(Enum):
     feedback = "feedback"
     context = "context"
     error = "error"
+    rewrite = "rewrite"
 
 
 class HookPromptFragment(BaseModel):
@@ -1825,14 +1842,31 @@ class ManagedHooksRequirements(BaseModel):
     permission_request: Annotated[
         list[ConfiguredHookMatcherGroup], Field(alias="PermissionRequest")
     ]
+    post_apply_patch: Annotated[
+        list[ConfiguredHookMatcherGroup], Field(alias="PostApplyPatch")
+    ]
+    post_compact: Annotated[
+        list[ConfiguredHookMatcherGroup], Field(alias="PostCompact")
+    ]
     post_tool_use: Annotated[
         list[ConfiguredHookMatcherGroup], Field(alias="PostToolUse")
     ]
+    pre_apply_patch: Annotated[
+        list[ConfiguredHookMatcherGroup], Field(alias="PreApplyPatch")
+    ]
+    pre_compact: Annotated[list[ConfiguredHookMatcherGroup], Field(alias="PreCompact")]
     pre_tool_use: Annotated[list[ConfiguredHookMatcherGroup], Field(alias="PreToolUse")]
     session_start: Annotated[
         list[ConfiguredHookMatcherGroup], Field(alias="SessionStart")
     ]
     stop: Annotated[list[ConfiguredHookMatcherGroup], Field(alias="Stop")]
+    subagent_start: Annotated[
+        list[ConfiguredHookMatcherGroup], Field(alias="SubagentStart")
+    ]
+    subagent_stop: Annotated[
+        list[ConfiguredHookMatcherGroup], Field(alias="SubagentStop")
+    ]
+    turn_error: Annotated[list[ConfiguredHookMatcherGroup], Field(alias="TurnError")]
     user_prompt_submit: Annotated[
         list[ConfiguredHookMatcherGroup], Field(alias="UserPromptSubmit")
     ]